@@ -128,6 +128,42 @@ fn write_config(v: &toml::Value) -> anyhow::Result<()> {
     Ok(())
 }
 
+fn secrets_path() -> PathBuf {
+    user_data_dir().join("secrets.json")
+}
+
+fn read_secrets() -> anyhow::Result<serde_json::Value> {
+    let p = secrets_path();
+    if !p.exists() {
+        return Ok(json!({}));
+    }
+    let txt =
+        std::fs::read_to_string(&p).with_context(|| format!("read secrets: {}", p.display()))?;
+    Ok(serde_json::from_str(&txt)?)
+}
+
+fn write_secrets(v: &serde_json::Value) -> anyhow::Result<()> {
+    let p = secrets_path();
+    if let Some(parent) = p.parent() {
+        std::fs::create_dir_all(parent)?;
+    }
+    std::fs::write(&p, serde_json::to_string_pretty(v)?)?;
+    Ok(())
+}
+
+fn unix_ms() -> u64 {
+    std::time::SystemTime::now()
+        .duration_since(std::time::UNIX_EPOCH)
+        .map(|d| d.as_millis() as u64)
+        .unwrap_or(0)
+}
+
+const DEFAULT_GATEWAY_BASE_URL: &str = "http://127.0.0.1:4000";
+// Key the app stores the primary gateway token under in secrets.json `providers`.
+const GATEWAY_TOKEN_SECRET_KEY: &str = "__gateway_token__";
+const CONFIG_RESOURCE_URI: &str = "apirouter://config";
+const STATUS_RESOURCE_URI: &str = "apirouter://status";
+
 async fn http_get_json(url: &str) -> anyhow::Result<serde_json::Value> {
     let client = reqwest::Client::builder()
         .user_agent("api-router-mcp/0.1")
@@ -141,16 +177,47 @@ async fn http_get_json(url: &str) -> anyhow::Result<serde_json::Value> {
     Ok(j)
 }
 
+async fn http_post_json(
+    url: reqwest::Url,
+    bearer: Option<&str>,
+) -> anyhow::Result<(u16, serde_json::Value)> {
+    let client = reqwest::Client::builder()
+        .user_agent("api-router-mcp/0.1")
+        .build()?;
+    let mut req = client.post(url);
+    if let Some(token) = bearer {
+        req = req.bearer_auth(token);
+    }
+    let r = req.send().await?;
+    let status = r.status().as_u16();
+    let j = r.json::<serde_json::Value>().await?;
+    Ok((status, j))
+}
+
+fn gateway_token() -> Option<String> {
+    read_secrets()
+        .ok()?
+        .get("providers")?
+        .get(GATEWAY_TOKEN_SECRET_KEY)?
+        .as_str()
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+        .map(str::to_string)
+}
+
 fn tool_list() -> serde_json::Value {
     // Minimal toolset: status probes + safe, fixed test/build commands + config edits.
     json!({
       "tools": [
         {
           "name": "ao.health",
-          "description": "Fetch gateway /health (defaults to http://127.0.0.1:4000).",
+          "description": "Fetch gateway /health (defaults to http://127.0.0.1:4000). Set deep=true to report per-provider upstream health.",
           "inputSchema": {
             "type": "object",
-            "properties": { "baseUrl": { "type": "string" } }
+            "properties": {
+              "baseUrl": { "type": "string" },
+              "deep": { "type": "boolean" }
+            }
           }
         },
         {
@@ -192,13 +259,14 @@ fn tool_list() -> serde_json::Value {
         },
         {
           "name": "ao.config.setSessionPreferredProvider",
-          "description": "Set routing.session_preferred_providers.<codexSessionId> to a provider name in user-data/config.toml.",
+          "description": "Set routing.session_preferred_providers.<codexSessionId> to a provider name, or an ordered list of provider names tried in order, in user-data/config.toml.",
           "inputSchema": {
             "type": "object",
-            "required": ["sessionId", "provider"],
+            "required": ["sessionId"],
             "properties": {
               "sessionId": { "type": "string" },
-              "provider": { "type": "string" }
+              "provider": { "type": "string" },
+              "providers": { "type": "array", "items": { "type": "string" } }
             }
           }
         },
@@ -213,6 +281,69 @@ fn tool_list() -> serde_json::Value {
             }
           }
         },
+        {
+          "name": "ao.provider.upsert",
+          "description": "Create or update providers.<name> (display_name, base_url) in user-data/config.toml, keeping provider_order in sync. Returns the provider list.",
+          "inputSchema": {
+            "type": "object",
+            "required": ["name", "displayName", "baseUrl"],
+            "properties": {
+              "name": { "type": "string" },
+              "displayName": { "type": "string" },
+              "baseUrl": { "type": "string" }
+            }
+          }
+        },
+        {
+          "name": "ao.provider.delete",
+          "description": "Delete providers.<name> from user-data/config.toml (the last provider cannot be deleted). Returns the provider list.",
+          "inputSchema": {
+            "type": "object",
+            "required": ["name"],
+            "properties": {
+              "name": { "type": "string" }
+            }
+          }
+        },
+        {
+          "name": "ao.provider.probe",
+          "description": "Probe a provider through the gateway (defaults to http://127.0.0.1:4000). Returns {ok, status, latencyMs}; upstream failures are returned as errors.",
+          "inputSchema": {
+            "type": "object",
+            "required": ["provider"],
+            "properties": {
+              "provider": { "type": "string" },
+              "baseUrl": { "type": "string" }
+            }
+          }
+        },
+        {
+          "name": "ao.pricing.set",
+          "description": "Set manual pricing for a provider in user-data/secrets.json (mode: none | per_request | package_total). Returns the effective pricing.",
+          "inputSchema": {
+            "type": "object",
+            "required": ["provider", "mode"],
+            "properties": {
+              "provider": { "type": "string" },
+              "mode": { "type": "string", "enum": ["none", "per_request", "package_total"] },
+              "amountUsd": { "type": "number" },
+              "packageExpiresAtUnixMs": { "type": "integer" }
+            }
+          }
+        },
+        {
+          "name": "ao.pricing.gapFill.set",
+          "description": "Set gap-fill pricing for a provider in user-data/secrets.json (mode: none | per_request | total | per_day_average). Returns the effective pricing.",
+          "inputSchema": {
+            "type": "object",
+            "required": ["provider", "mode"],
+            "properties": {
+              "provider": { "type": "string" },
+              "mode": { "type": "string", "enum": ["none", "per_request", "total", "per_day_average"] },
+              "amountUsd": { "type": "number" }
+            }
+          }
+        },
         {
           "name": "ao.dev.run",
           "description": "Run a safe, predefined command (npm_build | cargo_test | cargo_clippy).",
@@ -228,6 +359,55 @@ fn tool_list() -> serde_json::Value {
     })
 }
 
+fn resource_list() -> serde_json::Value {
+    json!({
+      "resources": [
+        {
+          "uri": CONFIG_RESOURCE_URI,
+          "name": "config",
+          "description": "Current user-data/config.toml as JSON (same shape as ao.config.get, without provider keys).",
+          "mimeType": "application/json"
+        },
+        {
+          "uri": STATUS_RESOURCE_URI,
+          "name": "status",
+          "description": "Live gateway /status snapshot (defaults to http://127.0.0.1:4000).",
+          "mimeType": "application/json"
+        }
+      ]
+    })
+}
+
+// Legacy configs may still carry providers.<name>.api_key; never hand it out.
+fn secret_free_config(cfg: &toml::Value) -> anyhow::Result<serde_json::Value> {
+    let mut v = serde_json::to_value(cfg)?;
+    if let Some(providers) = v.get_mut("providers").and_then(|p| p.as_object_mut()) {
+        for provider in providers.values_mut() {
+            if let Some(p) = provider.as_object_mut() {
+                p.remove("api_key");
+            }
+        }
+    }
+    Ok(v)
+}
+
+async fn read_resource(uri: &str) -> anyhow::Result<serde_json::Value> {
+    let doc = match uri {
+        CONFIG_RESOURCE_URI => secret_free_config(&read_config()?)?,
+        STATUS_RESOURCE_URI => http_get_json(&format!("{DEFAULT_GATEWAY_BASE_URL}/status")).await?,
+        _ => return Err(anyhow!("unknown resource: {uri}")),
+    };
+    Ok(json!({
+      "contents": [
+        {
+          "uri": uri,
+          "mimeType": "application/json",
+          "text": serde_json::to_string_pretty(&doc)?
+        }
+      ]
+    }))
+}
+
 async fn run_cmd(kind: &str) -> anyhow::Result<serde_json::Value> {
     let (program, args, cwd) = match kind {
         "npm_build" => ("npm", vec!["run", "build"], std::env::current_dir()?),
@@ -272,6 +452,36 @@ async fn run_cmd(kind: &str) -> anyhow::Result<serde_json::Value> {
     }))
 }
 
+// Same rules as the app's upsert_provider / set_usage_base_url validation.
+fn normalize_base_url(field: &str, raw: &str) -> anyhow::Result<String> {
+    let trimmed = raw.trim().trim_end_matches('/');
+    if trimmed.is_empty() {
+        return Err(anyhow!("{field} is required"));
+    }
+    let invalid = || anyhow!("invalid {field}: expected an http(s):// URL, got {trimmed}");
+    let parsed = reqwest::Url::parse(trimmed).map_err(|_| invalid())?;
+    if !matches!(parsed.scheme(), "http" | "https") || parsed.host_str().is_none_or(str::is_empty) {
+        return Err(invalid());
+    }
+    if parsed.query().is_some() || parsed.fragment().is_some() {
+        return Err(anyhow!(
+            "{field} must not include a query string or fragment"
+        ));
+    }
+    Ok(trimmed.to_string())
+}
+
+// The gateway appends these endpoint paths itself, so a base URL ending in one doubles them.
+fn base_url_warning(field: &str, base_url: &str) -> Option<String> {
+    let lower = base_url.to_ascii_lowercase();
+    ["/responses", "/chat/completions", "/models", "/v1/v1"]
+        .into_iter()
+        .find(|suffix| lower.ends_with(suffix))
+        .map(|suffix| {
+            format!("{field} ends with {suffix}; the gateway appends endpoint paths itself")
+        })
+}
+
 fn toml_set_provider_base_url(
     mut cfg: toml::Value,
     provider: &str,
@@ -327,12 +537,232 @@ fn ensure_provider_exists(cfg: &toml::Value, provider: &str) -> anyhow::Result<(
     Ok(())
 }
 
+fn provider_is_disabled(provider: &toml::Value) -> bool {
+    provider
+        .get("disabled")
+        .and_then(|v| v.as_bool())
+        .unwrap_or(false)
+}
+
+// Same rules as the app's normalize_provider_order: drop unknown/duplicate names, then append
+// any providers missing from the order.
+fn toml_normalize_provider_order(cfg: &mut toml::Value) -> anyhow::Result<()> {
+    let names: Vec<String> = cfg
+        .get("providers")
+        .and_then(|v| v.as_table())
+        .map(|t| t.keys().cloned().collect())
+        .unwrap_or_default();
+    let root = cfg
+        .as_table_mut()
+        .ok_or_else(|| anyhow!("config root must be a table"))?;
+    let current: Vec<String> = root
+        .get("provider_order")
+        .and_then(|v| v.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    let mut seen = std::collections::HashSet::new();
+    let mut next = Vec::new();
+    for name in current.into_iter().chain(names.iter().cloned()) {
+        if names.contains(&name) && seen.insert(name.clone()) {
+            next.push(toml::Value::String(name));
+        }
+    }
+    root.insert("provider_order".to_string(), toml::Value::Array(next));
+    Ok(())
+}
+
+// Mirrors the app's upsert_provider command: other provider fields are preserved and new
+// providers are appended to provider_order.
+fn toml_upsert_provider(
+    mut cfg: toml::Value,
+    name: &str,
+    display_name: &str,
+    base_url: &str,
+) -> anyhow::Result<toml::Value> {
+    if name.trim().is_empty() {
+        return Err(anyhow!("name is required"));
+    }
+    let root = cfg
+        .as_table_mut()
+        .ok_or_else(|| anyhow!("config root must be a table"))?;
+    let providers = root
+        .entry("providers")
+        .or_insert_with(|| toml::Value::Table(toml::map::Map::new()))
+        .as_table_mut()
+        .ok_or_else(|| anyhow!("providers must be a table"))?;
+    let is_new = !providers.contains_key(name);
+    let p = providers
+        .entry(name)
+        .or_insert_with(|| toml::Value::Table(toml::map::Map::new()))
+        .as_table_mut()
+        .ok_or_else(|| anyhow!("providers.{name} must be a table"))?;
+    p.insert(
+        "display_name".to_string(),
+        toml::Value::String(display_name.to_string()),
+    );
+    p.insert(
+        "base_url".to_string(),
+        toml::Value::String(base_url.to_string()),
+    );
+    if is_new {
+        if let Some(order) = root
+            .get_mut("provider_order")
+            .and_then(|v| v.as_array_mut())
+        {
+            order.push(toml::Value::String(name.to_string()));
+        }
+    }
+    toml_normalize_provider_order(&mut cfg)?;
+    Ok(cfg)
+}
+
+// Mirrors the app's delete_provider command, including the last-provider guards and moving
+// routing.preferred_provider to the next active provider.
+fn toml_delete_provider(mut cfg: toml::Value, name: &str) -> anyhow::Result<toml::Value> {
+    ensure_provider_exists(&cfg, name)?;
+    let providers = cfg
+        .get("providers")
+        .and_then(|v| v.as_table())
+        .ok_or_else(|| anyhow!("missing providers table"))?;
+    if providers.len() == 1 {
+        return Err(anyhow!("cannot delete the last provider"));
+    }
+    let remaining_active = providers
+        .iter()
+        .filter(|(n, p)| n.as_str() != name && !provider_is_disabled(p))
+        .count();
+    if remaining_active == 0 {
+        return Err(anyhow!("cannot delete the last active provider"));
+    }
+    let order: Vec<String> = cfg
+        .get("provider_order")
+        .and_then(|v| v.as_array())
+        .map(|a| {
+            a.iter()
+                .filter_map(|v| v.as_str().map(str::to_string))
+                .collect()
+        })
+        .unwrap_or_default();
+    let is_preferred = cfg
+        .get("routing")
+        .and_then(|r| r.get("preferred_provider"))
+        .and_then(|v| v.as_str())
+        == Some(name);
+    let next_preferred = if is_preferred {
+        order
+            .iter()
+            .chain(providers.keys())
+            .find(|n| {
+                n.as_str() != name
+                    && providers
+                        .get(n.as_str())
+                        .is_some_and(|p| !provider_is_disabled(p))
+            })
+            .cloned()
+    } else {
+        None
+    };
+
+    if let Some(providers) = cfg.get_mut("providers").and_then(|v| v.as_table_mut()) {
+        providers.remove(name);
+    }
+    if let Some(routing) = cfg.get_mut("routing").and_then(|v| v.as_table_mut()) {
+        if let Some(spp) = routing
+            .get_mut("session_preferred_providers")
+            .and_then(|v| v.as_table_mut())
+        {
+            for (_, pref) in spp.iter_mut() {
+                if let Some(list) = pref.as_array_mut() {
+                    list.retain(|p| p.as_str() != Some(name));
+                }
+            }
+            let stale: Vec<String> = spp
+                .iter()
+                .filter(|(_, pref)| {
+                    pref.as_str() == Some(name)
+                        || pref.as_array().is_some_and(|list| list.is_empty())
+                })
+                .map(|(session, _)| session.clone())
+                .collect();
+            for session in stale {
+                spp.remove(&session);
+            }
+        }
+        if let Some(next) = next_preferred {
+            routing.insert("preferred_provider".to_string(), toml::Value::String(next));
+        }
+    }
+    toml_normalize_provider_order(&mut cfg)?;
+    Ok(cfg)
+}
+
+// The app drops every per-provider secret when a provider is deleted.
+fn secrets_delete_provider(mut secrets: serde_json::Value, name: &str) -> serde_json::Value {
+    const PER_PROVIDER_KEYS: [&str; 9] = [
+        "providers",
+        "provider_key_storage_modes",
+        "provider_account_emails",
+        "usage_tokens",
+        "usage_logins",
+        "usage_proxy_pools",
+        "provider_pricing",
+        "provider_quota_hard_cap",
+        "provider_shared_ids",
+    ];
+    for key in PER_PROVIDER_KEYS {
+        if let Some(table) = secrets.get_mut(key).and_then(|v| v.as_object_mut()) {
+            table.remove(name);
+        }
+    }
+    secrets
+}
+
+// Provider list in provider_order; never includes keys.
+fn provider_list(cfg: &toml::Value) -> serde_json::Value {
+    let providers = cfg.get("providers").and_then(|v| v.as_table());
+    let order = cfg
+        .get("provider_order")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    let list: Vec<serde_json::Value> = order
+        .iter()
+        .filter_map(|v| v.as_str())
+        .filter_map(|name| {
+            let p = providers?.get(name)?;
+            Some(json!({
+                "name": name,
+                "display_name": p.get("display_name").and_then(|v| v.as_str()),
+                "base_url": p.get("base_url").and_then(|v| v.as_str()),
+                "group": p.get("group").and_then(|v| v.as_str()),
+                "disabled": provider_is_disabled(p),
+            }))
+        })
+        .collect();
+    json!({
+        "preferred_provider": cfg
+            .get("routing")
+            .and_then(|r| r.get("preferred_provider"))
+            .and_then(|v| v.as_str()),
+        "providers": list,
+    })
+}
+
 fn toml_set_session_preferred_provider(
     mut cfg: toml::Value,
     session_id: &str,
-    provider: &str,
+    providers: &[String],
 ) -> anyhow::Result<toml::Value> {
-    ensure_provider_exists(&cfg, provider)?;
+    if providers.is_empty() {
+        return Err(anyhow!("at least one provider is required"));
+    }
+    for provider in providers {
+        ensure_provider_exists(&cfg, provider)?;
+    }
 
     let routing = cfg
         .get_mut("routing")
@@ -344,10 +774,11 @@ fn toml_set_session_preferred_provider(
     let tbl = spp
         .as_table_mut()
         .ok_or_else(|| anyhow!("routing.session_preferred_providers must be a table"))?;
-    tbl.insert(
-        session_id.to_string(),
-        toml::Value::String(provider.to_string()),
-    );
+    let value = match providers {
+        [only] => toml::Value::String(only.clone()),
+        _ => toml::Value::Array(providers.iter().cloned().map(toml::Value::String).collect()),
+    };
+    tbl.insert(session_id.to_string(), value);
     Ok(cfg)
 }
 
@@ -368,6 +799,236 @@ fn toml_clear_session_preferred_provider(
     Ok(cfg)
 }
 
+fn secrets_table_mut<'a>(
+    secrets: &'a mut serde_json::Value,
+    key: &str,
+) -> anyhow::Result<&'a mut serde_json::Map<String, serde_json::Value>> {
+    let root = secrets
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("secrets.json must be an object"))?;
+    root.entry(key.to_string())
+        .or_insert_with(|| json!({}))
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("secrets.json {key} must be an object"))
+}
+
+// Mirrors the app's api_key_ref: a masked key preview, never the key itself.
+fn provider_api_key_ref(secrets: &serde_json::Value, provider: &str) -> String {
+    let Some(key) = secrets
+        .get("providers")
+        .and_then(|v| v.get(provider))
+        .and_then(|v| v.as_str())
+        .map(str::trim)
+        .filter(|v| !v.is_empty())
+    else {
+        return "-".to_string();
+    };
+    let chars: Vec<char> = key.chars().collect();
+    if chars.len() < 10 {
+        return "set".to_string();
+    }
+    let start_len = std::cmp::min(6, chars.len().saturating_sub(4));
+    let start: String = chars.iter().take(start_len).collect();
+    let end: String = chars[chars.len() - 4..].iter().collect();
+    format!("{start}******{end}")
+}
+
+fn validate_pricing_amount(amount_usd: Option<f64>) -> anyhow::Result<f64> {
+    let v = amount_usd.ok_or_else(|| anyhow!("amount_usd is required"))?;
+    if !v.is_finite() || v <= 0.0 {
+        return Err(anyhow!("amount_usd must be > 0"));
+    }
+    Ok(v)
+}
+
+// Same rules as the app's set_provider_manual_pricing command.
+fn secrets_set_provider_pricing(
+    mut secrets: serde_json::Value,
+    provider: &str,
+    mode: &str,
+    amount_usd: Option<f64>,
+    package_expires_at_unix_ms: Option<u64>,
+    now: u64,
+) -> anyhow::Result<serde_json::Value> {
+    let mode = mode.trim().to_lowercase();
+    let (amount_usd, expires) = match mode.as_str() {
+        "none" => (0.0, None),
+        "per_request" | "package_total" => {
+            let v = validate_pricing_amount(amount_usd)?;
+            let expires = match package_expires_at_unix_ms {
+                Some(ts) if mode == "package_total" => {
+                    if ts <= now {
+                        return Err(anyhow!("package_expires_at_unix_ms must be in the future"));
+                    }
+                    Some(ts)
+                }
+                _ => None,
+            };
+            (v, expires)
+        }
+        _ => {
+            return Err(anyhow!(
+                "mode must be one of: none, per_request, package_total"
+            ))
+        }
+    };
+    let api_key_ref = provider_api_key_ref(&secrets, provider);
+    let pricing = secrets_table_mut(&mut secrets, "provider_pricing")?;
+    let entry = pricing
+        .entry(provider.to_string())
+        .or_insert_with(|| json!({}))
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("provider_pricing.{provider} must be an object"))?;
+    let mut periods = entry
+        .get("periods")
+        .and_then(|v| v.as_array())
+        .cloned()
+        .unwrap_or_default();
+    // Like the app, a mode switch ends every open period so the old mode stops billing.
+    let mode_changed = entry
+        .get("mode")
+        .and_then(|v| v.as_str())
+        .is_some_and(|current| current != mode);
+    for period in periods.iter_mut() {
+        let open = period
+            .get("ended_at_unix_ms")
+            .and_then(|v| v.as_u64())
+            .is_none();
+        let same_mode = period.get("mode").and_then(|v| v.as_str()) == Some(mode.as_str());
+        if open && (mode == "none" || mode_changed || same_mode) {
+            period["ended_at_unix_ms"] = json!(now);
+        }
+    }
+    if mode != "none" {
+        periods.push(json!({
+            "id": format!("mcp-{now}-{}", periods.len()),
+            "mode": mode,
+            "amount_usd": amount_usd,
+            "api_key_ref": api_key_ref,
+            "started_at_unix_ms": now,
+            "ended_at_unix_ms": expires,
+        }));
+    }
+    // Only this tool's keys change; gap fill and per-model rates are left as they are. Tiers
+    // belong to the `per_request_tiered` mode, which this tool never sets.
+    entry.insert("mode".to_string(), json!(mode));
+    entry.insert("amount_usd".to_string(), json!(amount_usd));
+    entry.insert("periods".to_string(), json!(periods));
+    entry.remove("tiers");
+    if pricing_entry_is_empty(entry) {
+        pricing.remove(provider);
+    }
+    Ok(secrets)
+}
+
+/// True when a `provider_pricing` entry configures nothing, so it can be dropped.
+fn pricing_entry_is_empty(entry: &serde_json::Map<String, serde_json::Value>) -> bool {
+    let is_empty_array = |key: &str| {
+        entry
+            .get(key)
+            .and_then(|v| v.as_array())
+            .is_none_or(|values| values.is_empty())
+    };
+    entry.get("mode").and_then(|v| v.as_str()).unwrap_or("none") == "none"
+        && entry
+            .get("amount_usd")
+            .and_then(|v| v.as_f64())
+            .unwrap_or(0.0)
+            <= 0.0
+        && is_empty_array("periods")
+        && is_empty_array("tiers")
+        && entry
+            .get("model_rates")
+            .and_then(|v| v.as_object())
+            .is_none_or(|rates| rates.is_empty())
+        && entry.get("gap_fill_mode").is_none_or(|v| v.is_null())
+        && entry.get("gap_fill_amount_usd").is_none_or(|v| v.is_null())
+}
+
+// Same rules as the app's set_provider_gap_fill command.
+fn secrets_set_provider_gap_fill(
+    mut secrets: serde_json::Value,
+    provider: &str,
+    mode: &str,
+    amount_usd: Option<f64>,
+) -> anyhow::Result<serde_json::Value> {
+    let mode = mode.trim().to_lowercase();
+    let (gap_fill_mode, gap_fill_amount_usd) = match mode.as_str() {
+        "none" => (json!(null), json!(null)),
+        "per_request" | "total" | "per_day_average" => {
+            (json!(mode), json!(validate_pricing_amount(amount_usd)?))
+        }
+        _ => {
+            return Err(anyhow!(
+                "mode must be one of: none, per_request, total, per_day_average"
+            ))
+        }
+    };
+    let pricing = secrets_table_mut(&mut secrets, "provider_pricing")?;
+    let entry = pricing
+        .entry(provider.to_string())
+        .or_insert_with(|| {
+            json!({
+                "mode": "none",
+                "amount_usd": 0.0,
+                "periods": [],
+            })
+        })
+        .as_object_mut()
+        .ok_or_else(|| anyhow!("provider_pricing.{provider} must be an object"))?;
+    entry.insert("gap_fill_mode".to_string(), gap_fill_mode);
+    entry.insert("gap_fill_amount_usd".to_string(), gap_fill_amount_usd);
+
+    // Keep storage clean: if no base pricing and no gap config, remove row.
+    if pricing_entry_is_empty(entry) {
+        pricing.remove(provider);
+    }
+    Ok(secrets)
+}
+
+fn effective_pricing(secrets: &serde_json::Value, provider: &str, now: u64) -> serde_json::Value {
+    let Some(entry) = secrets
+        .get("provider_pricing")
+        .and_then(|v| v.get(provider))
+    else {
+        return json!({
+            "provider": provider,
+            "mode": "none",
+            "amount_usd": 0.0,
+            "package_expires_at_unix_ms": null,
+            "gap_fill_mode": null,
+            "gap_fill_amount_usd": null,
+        });
+    };
+    let mode = entry.get("mode").and_then(|v| v.as_str()).unwrap_or("none");
+    let package_expires_at_unix_ms = entry
+        .get("periods")
+        .and_then(|v| v.as_array())
+        .into_iter()
+        .flatten()
+        .filter(|p| p.get("mode").and_then(|v| v.as_str()) == Some("package_total"))
+        .filter(|p| {
+            p.get("started_at_unix_ms")
+                .and_then(|v| v.as_u64())
+                .is_some_and(|start| start <= now)
+        })
+        .filter_map(|p| p.get("ended_at_unix_ms").and_then(|v| v.as_u64()))
+        .filter(|end| *end > now)
+        .max();
+    json!({
+        "provider": provider,
+        "mode": mode,
+        "amount_usd": entry.get("amount_usd").cloned().unwrap_or(json!(0.0)),
+        "package_expires_at_unix_ms": if mode == "package_total" {
+            package_expires_at_unix_ms
+        } else {
+            None
+        },
+        "gap_fill_mode": entry.get("gap_fill_mode").cloned().unwrap_or(json!(null)),
+        "gap_fill_amount_usd": entry.get("gap_fill_amount_usd").cloned().unwrap_or(json!(null)),
+    })
+}
+
 async fn handle_tool_call(
     name: &str,
     args: serde_json::Value,
@@ -377,15 +1038,17 @@ async fn handle_tool_call(
             let base = args
                 .get("baseUrl")
                 .and_then(|v| v.as_str())
-                .unwrap_or("http://127.0.0.1:4000")
+                .unwrap_or(DEFAULT_GATEWAY_BASE_URL)
                 .trim_end_matches('/');
-            http_get_json(&format!("{base}/health")).await
+            let deep = args.get("deep").and_then(|v| v.as_bool()).unwrap_or(false);
+            let query = if deep { "?deep=1" } else { "" };
+            http_get_json(&format!("{base}/health{query}")).await
         }
         "ao.status" => {
             let base = args
                 .get("baseUrl")
                 .and_then(|v| v.as_str())
-                .unwrap_or("http://127.0.0.1:4000")
+                .unwrap_or(DEFAULT_GATEWAY_BASE_URL)
                 .trim_end_matches('/');
             http_get_json(&format!("{base}/status")).await
         }
@@ -402,10 +1065,11 @@ async fn handle_tool_call(
                 .get("baseUrl")
                 .and_then(|v| v.as_str())
                 .ok_or_else(|| anyhow!("missing baseUrl"))?;
+            let base_url = normalize_base_url("baseUrl", base_url)?;
             let cfg = read_config()?;
-            let next = toml_set_provider_base_url(cfg, provider, base_url)?;
+            let next = toml_set_provider_base_url(cfg, provider, &base_url)?;
             write_config(&next)?;
-            Ok(json!({"ok": true}))
+            Ok(json!({"ok": true, "warning": base_url_warning("baseUrl", &base_url)}))
         }
         "ao.config.setUsageBaseUrl" => {
             let provider = args
@@ -416,22 +1080,37 @@ async fn handle_tool_call(
                 .get("usageBaseUrl")
                 .and_then(|v| v.as_str())
                 .ok_or_else(|| anyhow!("missing usageBaseUrl"))?;
+            let usage_base_url = normalize_base_url("usageBaseUrl", usage_base_url)?;
             let cfg = read_config()?;
-            let next = toml_set_provider_usage_base_url(cfg, provider, usage_base_url)?;
+            let next = toml_set_provider_usage_base_url(cfg, provider, &usage_base_url)?;
             write_config(&next)?;
-            Ok(json!({"ok": true}))
+            Ok(json!({
+                "ok": true,
+                "warning": base_url_warning("usageBaseUrl", &usage_base_url),
+            }))
         }
         "ao.config.setSessionPreferredProvider" => {
             let session_id = args
                 .get("sessionId")
                 .and_then(|v| v.as_str())
                 .ok_or_else(|| anyhow!("missing sessionId"))?;
-            let provider = args
-                .get("provider")
-                .and_then(|v| v.as_str())
-                .ok_or_else(|| anyhow!("missing provider"))?;
+            let providers: Vec<String> = match args.get("providers").and_then(|v| v.as_array()) {
+                Some(list) => list
+                    .iter()
+                    .map(|v| {
+                        v.as_str()
+                            .map(str::to_string)
+                            .ok_or_else(|| anyhow!("providers must be strings"))
+                    })
+                    .collect::<anyhow::Result<_>>()?,
+                None => vec![args
+                    .get("provider")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow!("missing provider"))?
+                    .to_string()],
+            };
             let cfg = read_config()?;
-            let next = toml_set_session_preferred_provider(cfg, session_id, provider)?;
+            let next = toml_set_session_preferred_provider(cfg, session_id, &providers)?;
             write_config(&next)?;
             Ok(json!({"ok": true}))
         }
@@ -445,6 +1124,115 @@ async fn handle_tool_call(
             write_config(&next)?;
             Ok(json!({"ok": true}))
         }
+        "ao.provider.upsert" => {
+            let name = args
+                .get("name")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow!("missing name"))?;
+            let display_name = args
+                .get("displayName")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow!("missing displayName"))?;
+            let base_url = args
+                .get("baseUrl")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow!("missing baseUrl"))?;
+            let base_url = normalize_base_url("baseUrl", base_url)?;
+            let cfg = read_config()?;
+            let next = toml_upsert_provider(cfg, name, display_name, &base_url)?;
+            write_config(&next)?;
+            let mut out = provider_list(&next);
+            if let Some(warning) = base_url_warning("baseUrl", &base_url) {
+                out["warning"] = json!(warning);
+            }
+            Ok(out)
+        }
+        "ao.provider.delete" => {
+            let name = args
+                .get("name")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow!("missing name"))?;
+            let cfg = read_config()?;
+            let next = toml_delete_provider(cfg, name)?;
+            write_config(&next)?;
+            if secrets_path().exists() {
+                write_secrets(&secrets_delete_provider(read_secrets()?, name))?;
+            }
+            Ok(provider_list(&next))
+        }
+        "ao.provider.probe" => {
+            let provider = args
+                .get("provider")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow!("missing provider"))?;
+            let base = args
+                .get("baseUrl")
+                .and_then(|v| v.as_str())
+                .unwrap_or(DEFAULT_GATEWAY_BASE_URL);
+            let mut url =
+                reqwest::Url::parse(base).with_context(|| format!("invalid baseUrl: {base}"))?;
+            // Path segments are percent-encoded, so names with spaces or `/` stay one segment.
+            url.path_segments_mut()
+                .map_err(|_| anyhow!("invalid baseUrl: {base}"))?
+                .pop_if_empty()
+                .extend(["providers", provider, "probe"]);
+            let token = gateway_token();
+            let (http_status, body) = http_post_json(url, token.as_deref()).await?;
+            if body.get("ok").and_then(|v| v.as_bool()) == Some(true) {
+                return Ok(json!({
+                    "ok": true,
+                    "status": body.get("status").cloned().unwrap_or(json!(null)),
+                    "latencyMs": body.get("latencyMs").cloned().unwrap_or(json!(null)),
+                }));
+            }
+            let message = body
+                .get("error")
+                .and_then(|e| e.get("message"))
+                .and_then(|v| v.as_str())
+                .map(str::to_string)
+                .unwrap_or_else(|| format!("probe failed (gateway http {http_status})"));
+            Err(anyhow!(message.chars().take(300).collect::<String>()))
+        }
+        "ao.pricing.set" => {
+            let provider = args
+                .get("provider")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow!("missing provider"))?;
+            let mode = args
+                .get("mode")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow!("missing mode"))?;
+            let amount_usd = args.get("amountUsd").and_then(|v| v.as_f64());
+            let package_expires_at_unix_ms =
+                args.get("packageExpiresAtUnixMs").and_then(|v| v.as_u64());
+            ensure_provider_exists(&read_config()?, provider)?;
+            let now = unix_ms();
+            let next = secrets_set_provider_pricing(
+                read_secrets()?,
+                provider,
+                mode,
+                amount_usd,
+                package_expires_at_unix_ms,
+                now,
+            )?;
+            write_secrets(&next)?;
+            Ok(effective_pricing(&next, provider, now))
+        }
+        "ao.pricing.gapFill.set" => {
+            let provider = args
+                .get("provider")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow!("missing provider"))?;
+            let mode = args
+                .get("mode")
+                .and_then(|v| v.as_str())
+                .ok_or_else(|| anyhow!("missing mode"))?;
+            let amount_usd = args.get("amountUsd").and_then(|v| v.as_f64());
+            ensure_provider_exists(&read_config()?, provider)?;
+            let next = secrets_set_provider_gap_fill(read_secrets()?, provider, mode, amount_usd)?;
+            write_secrets(&next)?;
+            Ok(effective_pricing(&next, provider, unix_ms()))
+        }
         "ao.dev.run" => {
             let cmd = args
                 .get("cmd")
@@ -483,7 +1271,7 @@ async fn main() -> anyhow::Result<()> {
             "initialize" => Ok(json!({
               "protocolVersion": "2024-11-05",
               "serverInfo": { "name": "api-router-mcp", "version": "0.1.0" },
-              "capabilities": { "tools": {} }
+              "capabilities": { "tools": {}, "resources": {} }
             })),
             "tools/list" => Ok(tool_list()),
             "tools/call" => {
@@ -495,6 +1283,15 @@ async fn main() -> anyhow::Result<()> {
                 let tool_args = msg.params.get("arguments").cloned().unwrap_or(json!({}));
                 handle_tool_call(tool_name, tool_args).await
             }
+            "resources/list" => Ok(resource_list()),
+            "resources/read" => {
+                let uri = msg
+                    .params
+                    .get("uri")
+                    .and_then(|v| v.as_str())
+                    .ok_or_else(|| anyhow!("missing params.uri"))?;
+                read_resource(uri).await
+            }
             "ping" => Ok(json!({ "ok": true })),
             _ => Err(anyhow!("unknown method: {}", msg.method)),
         };
@@ -521,3 +1318,98 @@ async fn main() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::{secrets_set_provider_gap_fill, secrets_set_provider_pricing};
+    use serde_json::json;
+
+    fn secrets_with_rates() -> serde_json::Value {
+        json!({
+            "providers": {},
+            "provider_pricing": {
+                "p1": {
+                    "mode": "per_request_tiered",
+                    "amount_usd": 0.0,
+                    "periods": [],
+                    "gap_fill_mode": "total",
+                    "gap_fill_amount_usd": 5.0,
+                    "tiers": [{ "up_to_requests": 100, "usd_per_request": 0.01 }],
+                    "model_rates": {
+                        "gpt-5": { "input_usd_per_million": 1.0, "output_usd_per_million": 2.0 }
+                    },
+                }
+            }
+        })
+    }
+
+    #[test]
+    fn set_pricing_keeps_gap_fill_and_model_rates() {
+        let next = secrets_set_provider_pricing(
+            secrets_with_rates(),
+            "p1",
+            "per_request",
+            Some(0.02),
+            None,
+            1_000,
+        )
+        .expect("set pricing");
+        let entry = &next["provider_pricing"]["p1"];
+        assert_eq!(entry["mode"], "per_request");
+        assert_eq!(entry["amount_usd"], 0.02);
+        assert_eq!(entry["gap_fill_mode"], "total");
+        assert_eq!(entry["model_rates"]["gpt-5"]["input_usd_per_million"], 1.0);
+        assert!(entry.get("tiers").is_none());
+        assert_eq!(entry["periods"].as_array().map(Vec::len), Some(1));
+    }
+
+    #[test]
+    fn switching_pricing_mode_ends_open_periods() {
+        let next = secrets_set_provider_pricing(
+            secrets_with_rates(),
+            "p1",
+            "per_request",
+            Some(0.02),
+            None,
+            1_000,
+        )
+        .expect("set per request");
+        let next =
+            secrets_set_provider_pricing(next, "p1", "package_total", Some(5.0), None, 2_000)
+                .expect("set package total");
+        let periods = next["provider_pricing"]["p1"]["periods"]
+            .as_array()
+            .expect("periods")
+            .clone();
+        assert_eq!(periods.len(), 2);
+        assert_eq!(periods[0]["ended_at_unix_ms"], 2_000);
+        assert!(periods[1]["ended_at_unix_ms"].is_null());
+    }
+
+    #[test]
+    fn clearing_pricing_and_gap_fill_keeps_entries_with_model_rates() {
+        let next = secrets_set_provider_pricing(secrets_with_rates(), "p1", "none", None, None, 1)
+            .expect("clear pricing");
+        let next = secrets_set_provider_gap_fill(next, "p1", "none", None).expect("clear gap");
+        let entry = &next["provider_pricing"]["p1"];
+        assert_eq!(entry["mode"], "none");
+        assert!(entry["gap_fill_mode"].is_null());
+        assert_eq!(entry["model_rates"]["gpt-5"]["input_usd_per_million"], 1.0);
+    }
+
+    #[test]
+    fn gap_fill_keeps_tiers_and_removes_empty_entries() {
+        let next =
+            secrets_set_provider_gap_fill(secrets_with_rates(), "p1", "per_request", Some(0.5))
+                .expect("set gap");
+        let entry = &next["provider_pricing"]["p1"];
+        assert_eq!(entry["gap_fill_amount_usd"], 0.5);
+        assert_eq!(entry["tiers"].as_array().map(Vec::len), Some(1));
+        assert_eq!(entry["mode"], "per_request_tiered");
+
+        let next = secrets_set_provider_gap_fill(json!({}), "p2", "per_request", Some(0.5))
+            .expect("set gap");
+        let next = secrets_set_provider_gap_fill(next, "p2", "none", None).expect("clear gap");
+        assert!(next["provider_pricing"].get("p2").is_none());
+    }
+}
@@ -11,7 +11,9 @@ use std::os::windows::process::CommandExt;
 use parking_lot::{Mutex, RwLock};
 
 use crate::orchestrator::config::AppConfig;
-use crate::orchestrator::gateway::{open_store_dir_with_trace, GatewayState};
+use crate::orchestrator::gateway::{
+    load_persisted_last_used_routes, open_store_dir_with_trace, GatewayState,
+};
 use crate::orchestrator::router::RouterState;
 use crate::orchestrator::secrets::SecretStore;
 use crate::orchestrator::store::unix_ms;
@@ -1287,8 +1289,9 @@ pub fn disable_expired_package_providers(state: &AppState) -> Vec<String> {
             return expired;
         }
         cfg.routing
-            .session_preferred_providers
-            .retain(|_, provider_name| !expired.iter().any(|item| item == provider_name));
+            .retain_session_preferred_providers(|provider_name| {
+                !expired.iter().any(|item| item == provider_name)
+            });
         if expired
             .iter()
             .any(|provider_name| provider_name == &cfg.routing.preferred_provider)
@@ -1333,15 +1336,130 @@ pub fn disable_expired_package_providers(state: &AppState) -> Vec<String> {
             serde_json::json!({ "expired_at_unix_ms": now }),
         );
     }
+    if !expired.is_empty() {
+        crate::orchestrator::gateway::persist_last_used_routes(&state.gateway);
+    }
     let cfg = state.gateway.cfg.read().clone();
     state.gateway.router.sync_with_config(&cfg, now);
     expired
 }
 
+/// Applies the `[events]` retention policy.
+///
+/// Events past `retention_days` are dropped unless their level or provider is exempt. Exempt
+/// events are kept until `extended_retention_days`, or forever when that is 0. A
+/// `retention_days` of 0 disables pruning.
+pub fn prune_expired_events(state: &AppState, now_unix_ms: u64) -> usize {
+    const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+    let events = state.gateway.cfg.read().events.clone();
+    if events.retention_days == 0 {
+        return 0;
+    }
+    let cutoff = now_unix_ms.saturating_sub(events.retention_days.saturating_mul(DAY_MS));
+    let extended_cutoff = (events.extended_retention_days > 0).then(|| {
+        now_unix_ms.saturating_sub(
+            events
+                .extended_retention_days
+                .max(events.retention_days)
+                .saturating_mul(DAY_MS),
+        )
+    });
+    let keep_levels: Vec<&str> = events.keep_levels.iter().map(String::as_str).collect();
+    let keep_providers: Vec<&str> = events.keep_providers.iter().map(String::as_str).collect();
+    let store = &state.gateway.store;
+    let mut deleted = 0;
+    match store.prune_events_older_than_except(cutoff, &keep_levels, &keep_providers) {
+        Ok(count) => deleted += count,
+        Err(err) => log::warn!("event retention prune failed: {err}"),
+    }
+    if let Some(extended_cutoff) = extended_cutoff {
+        match store.prune_events_older_than(extended_cutoff, &[]) {
+            Ok(count) => deleted += count,
+            Err(err) => log::warn!("event extended retention prune failed: {err}"),
+        }
+    }
+    if deleted > 0 {
+        store.events().emit(
+            "gateway",
+            crate::orchestrator::store::EventCode::STORE_EVENTS_PRUNED,
+            &format!("pruned {deleted} expired events"),
+            serde_json::json!({
+                "deleted": deleted,
+                "cutoff_unix_ms": cutoff,
+                "extended_cutoff_unix_ms": extended_cutoff,
+            }),
+        );
+    }
+    deleted
+}
+
+/// Applies `routing.max_store_bytes`: evicts the oldest `info` events (then, when
+/// `evict_usage_requests` is set, the oldest raw usage requests) until the events database fits.
+/// Returns the number of rows removed.
+pub fn enforce_store_size_cap(state: &AppState) -> usize {
+    let (max_bytes, evict_usage_requests) = {
+        let cfg = state.gateway.cfg.read();
+        (
+            cfg.routing.max_store_bytes,
+            cfg.routing.evict_usage_requests,
+        )
+    };
+    let store = &state.gateway.store;
+    let eviction = match store.evict_to_size_cap(max_bytes, evict_usage_requests) {
+        Ok(eviction) => eviction,
+        Err(err) => {
+            log::warn!("store size cap eviction failed: {err}");
+            return 0;
+        }
+    };
+    let deleted = eviction.events_deleted + eviction.usage_requests_deleted;
+    if deleted > 0 {
+        store.events().emit(
+            "gateway",
+            crate::orchestrator::store::EventCode::STORE_EVICTED,
+            &format!("evicted {deleted} rows to stay under the store size cap"),
+            serde_json::json!({
+                "max_store_bytes": max_bytes,
+                "events_deleted": eviction.events_deleted,
+                "usage_requests_deleted": eviction.usage_requests_deleted,
+                "bytes_before": eviction.bytes_before,
+                "bytes_after": eviction.bytes_after,
+            }),
+        );
+    }
+    deleted
+}
+
 pub fn load_or_init_config(path: &PathBuf) -> anyhow::Result<AppConfig> {
     if path.exists() {
         let txt = std::fs::read_to_string(path)?;
-        let cfg: AppConfig = toml::from_str(&txt)?;
+        let (cfg, diagnostics) =
+            crate::orchestrator::config_validation::parse_and_validate_config(&txt);
+        for diagnostic in diagnostics.iter().filter(|d| !d.is_error()) {
+            log::warn!("config.toml: {}", diagnostic.describe());
+        }
+        if let Err(err) =
+            crate::orchestrator::config_validation::write_config_errors_report(path, &diagnostics)
+        {
+            log::warn!("failed to update config.errors.json: {err}");
+        }
+        let Some(cfg) = cfg else {
+            let errors = diagnostics
+                .iter()
+                .filter(|d| d.is_error())
+                .map(|d| d.describe())
+                .collect::<Vec<_>>();
+            log::error!(
+                "config.toml is invalid ({} error(s), see config.errors.json): {}",
+                errors.len(),
+                errors.join("; ")
+            );
+            anyhow::bail!(
+                "invalid config.toml ({} error(s); details in config.errors.json): {}",
+                errors.len(),
+                errors.first().cloned().unwrap_or_default()
+            );
+        };
         return Ok(cfg);
     }
     let cfg = AppConfig::default_config();
@@ -1489,6 +1607,7 @@ pub fn build_state(config_path: PathBuf, data_dir: PathBuf) -> anyhow::Result<Ap
     ));
     write_build_state_startup_diag("build_state_router_ok", started, None);
     write_build_state_startup_diag("build_state_gateway_start", started, None);
+    let last_used_by_session = load_persisted_last_used_routes(&store, &cfg, unix_ms());
     let gateway = GatewayState {
         cfg: Arc::new(RwLock::new(cfg)),
         router,
@@ -1496,9 +1615,10 @@ pub fn build_state(config_path: PathBuf, data_dir: PathBuf) -> anyhow::Result<Ap
         upstream: UpstreamClient::new(),
         secrets: secrets.clone(),
         last_activity_unix_ms: Arc::new(AtomicU64::new(0)),
-        last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
+        last_used_by_session: Arc::new(RwLock::new(last_used_by_session)),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
     let lan_node = secrets
@@ -1591,9 +1711,9 @@ fn should_prune_placeholder_provider(cfg: &AppConfig, secrets: &SecretStore, nam
 mod tests {
     use super::{
         build_state, disable_expired_package_providers, load_or_init_config,
-        parse_tasklist_csv_line, parse_tasklist_mem_kb, run_startup_gateway_token_sync,
-        UiWatchdogInvokeResult, UiWatchdogLocalTask, UiWatchdogPageState, UiWatchdogRuntime,
-        UiWatchdogState, UI_WATCHDOG_SLOW_REFRESH_AFTER_MS,
+        parse_tasklist_csv_line, parse_tasklist_mem_kb, prune_expired_events,
+        run_startup_gateway_token_sync, UiWatchdogInvokeResult, UiWatchdogLocalTask,
+        UiWatchdogPageState, UiWatchdogRuntime, UiWatchdogState, UI_WATCHDOG_SLOW_REFRESH_AFTER_MS,
     };
     use crate::orchestrator::config::AppConfig;
     use serde_json::json;
@@ -1876,6 +1996,103 @@ mod tests {
             .is_some_and(|provider| provider.disabled));
     }
 
+    #[test]
+    fn prune_expired_events_keeps_exempt_providers_until_extended_retention() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let config_path = tmp.path().join("user-data").join("config.toml");
+        let data_dir = tmp.path().join("data");
+        std::fs::create_dir_all(config_path.parent().expect("config parent")).expect("mkdir");
+
+        let state = build_state(config_path, data_dir).expect("build state");
+        {
+            let mut cfg = state.gateway.cfg.write();
+            cfg.events.retention_days = 7;
+            cfg.events.extended_retention_days = 30;
+            cfg.events.keep_levels = Vec::new();
+            cfg.events.keep_providers = vec!["kept".to_string()];
+        }
+        let day_ms = 24 * 60 * 60 * 1000;
+        let now = unix_ms();
+        for (provider, age_days) in [("dropped", 10), ("kept", 10), ("kept", 40)] {
+            state.gateway.store.events().emit_at_unix_ms(
+                provider,
+                crate::orchestrator::store::EventCode::ROUTING_ROUTE,
+                &format!("{provider} {age_days}d"),
+                serde_json::Value::Null,
+                now - age_days * day_ms,
+            );
+        }
+
+        assert_eq!(prune_expired_events(&state, now), 2);
+        let remaining: Vec<String> = state
+            .gateway
+            .store
+            .list_events_range(None, Some(now - day_ms), None)
+            .iter()
+            .filter_map(|row| {
+                row.get("message")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            })
+            .collect();
+        assert_eq!(remaining, vec!["kept 10d".to_string()]);
+        assert!(state
+            .gateway
+            .store
+            .list_events_range(None, None, None)
+            .iter()
+            .any(|row| row.get("code").and_then(|v| v.as_str()) == Some("store.events_pruned")));
+    }
+
+    #[test]
+    fn prune_expired_events_keeps_exempt_events_forever_by_default() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let config_path = tmp.path().join("user-data").join("config.toml");
+        let data_dir = tmp.path().join("data");
+        std::fs::create_dir_all(config_path.parent().expect("config parent")).expect("mkdir");
+
+        let state = build_state(config_path, data_dir).expect("build state");
+        {
+            let mut cfg = state.gateway.cfg.write();
+            cfg.events.retention_days = 7;
+            cfg.events.extended_retention_days =
+                crate::orchestrator::config::DEFAULT_EVENT_EXTENDED_RETENTION_DAYS;
+            cfg.events.keep_levels = vec!["error".to_string()];
+            cfg.events.keep_providers = vec!["kept".to_string()];
+        }
+        let day_ms = 24 * 60 * 60 * 1000;
+        let now = unix_ms();
+        use crate::orchestrator::store::EventCode;
+        for (provider, code) in [
+            ("dropped", EventCode::ROUTING_ROUTE),
+            ("kept", EventCode::ROUTING_ROUTE),
+            ("other", EventCode::UPSTREAM_HTTP_ERROR),
+        ] {
+            state.gateway.store.events().emit_at_unix_ms(
+                provider,
+                code,
+                provider,
+                serde_json::Value::Null,
+                now - 400 * day_ms,
+            );
+        }
+
+        assert_eq!(prune_expired_events(&state, now), 1);
+        let mut remaining: Vec<String> = state
+            .gateway
+            .store
+            .list_events_range(None, Some(now - day_ms), None)
+            .iter()
+            .filter_map(|row| {
+                row.get("message")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            })
+            .collect();
+        remaining.sort();
+        assert_eq!(remaining, vec!["kept".to_string(), "other".to_string()]);
+    }
+
     #[test]
     fn build_state_writes_startup_diagnostics_for_remote_update_debugging() {
         let tmp = tempfile::tempdir().expect("tempdir");
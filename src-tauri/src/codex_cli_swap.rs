@@ -193,6 +193,105 @@ fn swap_state(cli_home: &Path) -> Result<&'static str, String> {
     ))
 }
 
+const CLI_HOME_UNDO_BACKUP_SUFFIX: &str = ".apirouter-bak";
+const CLI_HOME_UNDO_BACKUP_FILES: [&str; 2] = ["auth.json", "config.toml"];
+
+fn cli_home_undo_backup_path(cli_home: &Path, file_name: &str) -> PathBuf {
+    cli_home.join(format!("{file_name}{CLI_HOME_UNDO_BACKUP_SUFFIX}"))
+}
+
+fn modified_at(path: &Path) -> Option<std::time::SystemTime> {
+    std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Copies the user's `auth.json`/`config.toml` next to themselves before a swap rewrites them.
+///
+/// Callers only invoke this while the home is still in its original (user-owned) state, so a
+/// backup is refreshed when missing or older than the live file but never replaced by a swap.
+pub(crate) fn ensure_cli_home_undo_backup(cli_home: &Path) -> Result<(), String> {
+    for file_name in CLI_HOME_UNDO_BACKUP_FILES {
+        let live = cli_home.join(file_name);
+        if !live.exists() {
+            continue;
+        }
+        let backup = cli_home_undo_backup_path(cli_home, file_name);
+        let stale = match (modified_at(&backup), modified_at(&live)) {
+            (Some(backup_at), Some(live_at)) => backup_at < live_at,
+            (Some(_), None) => false,
+            (None, _) => true,
+        };
+        if stale {
+            write_bytes(&backup, &read_bytes(&live)?)
+                .map_err(|e| format!("backup {file_name} failed: {e}"))?;
+        }
+    }
+    Ok(())
+}
+
+/// Existing undo backups for a CLI home, keyed by file name (`null` when absent).
+pub(crate) fn cli_home_undo_backup_status(cli_home: &Path) -> serde_json::Value {
+    let mut out = serde_json::Map::new();
+    for file_name in CLI_HOME_UNDO_BACKUP_FILES {
+        let backup = cli_home_undo_backup_path(cli_home, file_name);
+        let value = if backup.exists() {
+            json!(backup.to_string_lossy())
+        } else {
+            serde_json::Value::Null
+        };
+        out.insert(file_name.to_string(), value);
+    }
+    serde_json::Value::Object(out)
+}
+
+/// Puts the `*.apirouter-bak` copies back and clears any swap state, leaving the home exactly as
+/// it was before API Router first edited it.
+pub fn restore_cli_home(cli_home: &str) -> Result<serde_json::Value, String> {
+    let home = resolve_cli_home(Some(cli_home))?;
+    if !home.exists() {
+        return Err(format!("Codex dir does not exist: {}", home.display()));
+    }
+    let pending = CLI_HOME_UNDO_BACKUP_FILES
+        .into_iter()
+        .filter(|file_name| cli_home_undo_backup_path(&home, file_name).exists())
+        .collect::<Vec<_>>();
+    if pending.is_empty() {
+        return Err(format!("No API Router backup found in: {}", home.display()));
+    }
+
+    let mut restored: Vec<(PathBuf, Option<Vec<u8>>)> = Vec::new();
+    for file_name in &pending {
+        let live = home.join(file_name);
+        let res = read_bytes(&cli_home_undo_backup_path(&home, file_name)).and_then(|bytes| {
+            let previous = read_bytes(&live).ok();
+            write_bytes(&live, &bytes)?;
+            restored.push((live.clone(), previous));
+            Ok(())
+        });
+        if let Err(e) = res {
+            // Best-effort local rollback so a half-restored home is not left behind.
+            for (path, previous) in restored.iter().rev() {
+                match previous {
+                    Some(bytes) => {
+                        let _ = write_bytes(path, bytes);
+                    }
+                    None => {
+                        let _ = std::fs::remove_file(path);
+                    }
+                }
+            }
+            return Err(format!("restore {file_name} failed: {e}"));
+        }
+    }
+    let _ = std::fs::remove_dir_all(swap_state_dir(&home));
+
+    Ok(json!({
+      "ok": true,
+      "cli_home": home.to_string_lossy(),
+      "restored": pending,
+      "backups": cli_home_undo_backup_status(&home),
+    }))
+}
+
 fn strip_model_provider_line(cfg: &str) -> String {
     // Keep this minimal and deterministic: remove only the top-level key assignment.
     // We intentionally do NOT attempt to rewrite other parts of the file.
@@ -360,6 +459,7 @@ fn swap_dir(cli_home: &Path, app_auth_json: &serde_json::Value) -> Result<(), St
     let orig_cfg_txt = String::from_utf8(orig_cfg_bytes.clone())
         .map_err(|_| "config.toml is not valid UTF-8".to_string())?;
 
+    ensure_cli_home_undo_backup(cli_home)?;
     std::fs::create_dir_all(&state_dir).map_err(|e| e.to_string())?;
     write_bytes(&backup_auth, &orig_auth)?;
     write_bytes(&backup_cfg, &orig_cfg_bytes)?;
@@ -483,6 +583,7 @@ pub fn cli_auth_config_swap_status(cli_homes: Vec<String>) -> Result<serde_json:
         dirs.push(json!({
           "cli_home": h.to_string_lossy(),
           "state": s,
+          "backups": cli_home_undo_backup_status(h),
         }));
     }
 
@@ -538,6 +639,38 @@ mod tests {
         )));
     }
 
+    #[test]
+    fn swap_writes_undo_backups_that_restore_cli_home_puts_back() {
+        let tmp = tempfile::tempdir().unwrap();
+        let cli_home = tmp.path().join("cli-home");
+        std::fs::create_dir_all(&cli_home).unwrap();
+        let orig_auth = r#"{"OPENAI_API_KEY":"sk-user"}"#;
+        let orig_cfg = "model_provider = \"custom\"\nmodel = \"gpt-5.2\"\n";
+        std::fs::write(cli_home.join("auth.json"), orig_auth).unwrap();
+        std::fs::write(cli_home.join("config.toml"), orig_cfg).unwrap();
+
+        swap_dir(&cli_home, &json!({ "tokens": { "access_token": "x" } })).unwrap();
+        assert_eq!(
+            std::fs::read_to_string(cli_home.join("auth.json.apirouter-bak")).unwrap(),
+            orig_auth
+        );
+        let status =
+            cli_auth_config_swap_status(vec![cli_home.to_string_lossy().to_string()]).unwrap();
+        assert!(status["dirs"][0]["backups"]["config.toml"].is_string());
+
+        let out = restore_cli_home(&cli_home.to_string_lossy()).unwrap();
+        assert_eq!(out["restored"], json!(["auth.json", "config.toml"]));
+        assert_eq!(
+            std::fs::read_to_string(cli_home.join("auth.json")).unwrap(),
+            orig_auth
+        );
+        assert_eq!(
+            std::fs::read_to_string(cli_home.join("config.toml")).unwrap(),
+            orig_cfg
+        );
+        assert_eq!(swap_state(&cli_home).unwrap(), "original");
+    }
+
     #[test]
     fn cli_directories_persist_wsl2_enabled_flag() {
         let tmp = tempfile::tempdir().unwrap();
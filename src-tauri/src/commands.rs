@@ -1,5 +1,6 @@
 use crate::app_state;
 use crate::codex_app_server;
+use crate::orchestrator::config::AmbiguousLocalTime;
 use crate::orchestrator::store::unix_ms;
 use chrono::{Local, LocalResult, NaiveDate, TimeZone, Timelike};
 use serde_json::Value;
@@ -7,8 +8,13 @@ use std::collections::{BTreeMap, BTreeSet};
 use std::sync::atomic::Ordering;
 use std::time::Duration;
 
+mod command_error;
+mod read_only;
 mod status_snapshot_support;
 
+pub(crate) use self::command_error::CommandError;
+pub(crate) use self::read_only::command_allowed_in_read_only;
+
 #[allow(unused_imports)]
 pub(crate) use self::status_snapshot_support::{
     merge_thread_index_session_hints, next_last_discovered_unix_ms,
@@ -298,6 +298,24 @@ pub(crate) fn codex_cli_toggle_auth_config_swap(
     crate::codex_cli_swap::toggle_cli_auth_config_swap(&state, cli_homes.unwrap_or_default())
 }
 
+#[tauri::command]
+pub(crate) fn restore_cli_home(
+    state: tauri::State<'_, app_state::AppState>,
+    home: String,
+) -> Result<serde_json::Value, String> {
+    let out = crate::codex_cli_swap::restore_cli_home(&home)?;
+    state.gateway.store.events().codex().cli_home_restored(
+        "codex",
+        "Codex CLI home restored from API Router backup",
+        serde_json::json!({
+          "cli_home": out.get("cli_home").cloned(),
+          "restored": out.get("restored").cloned(),
+          "restored_at_unix_ms": unix_ms(),
+        }),
+    );
+    Ok(out)
+}
+
 #[tauri::command]
 pub(crate) fn codex_cli_default_home() -> Result<String, String> {
     crate::codex_cli_swap::default_cli_codex_home()
@@ -364,8 +382,10 @@ pub(crate) fn set_codex_cli_config_toml(
 pub(crate) fn provider_switchboard_status(
     state: tauri::State<'_, app_state::AppState>,
     cli_homes: Option<Vec<String>>,
+    target: Option<String>,
+    provider: Option<String>,
 ) -> Result<serde_json::Value, String> {
-    crate::provider_switchboard::get_status(&state, cli_homes.unwrap_or_default())
+    crate::provider_switchboard::get_status(&state, cli_homes.unwrap_or_default(), target, provider)
 }
 
 #[tauri::command]
@@ -374,8 +394,115 @@ pub(crate) fn provider_switchboard_set_target(
     cli_homes: Option<Vec<String>>,
     target: String,
     provider: Option<String>,
+    model: Option<String>,
+    clear_model: Option<bool>,
+    dry_run: Option<bool>,
 ) -> Result<serde_json::Value, String> {
-    crate::provider_switchboard::set_target(&state, cli_homes.unwrap_or_default(), target, provider)
+    use crate::provider_switchboard::ModelOverride;
+
+    let clear_model = clear_model.unwrap_or(false);
+    let model = match model.as_deref() {
+        Some(model) if !clear_model => {
+            Some(crate::provider_switchboard::validate_model_override(model)?)
+        }
+        _ => None,
+    };
+    let model_override = if clear_model {
+        ModelOverride::Clear
+    } else if let Some(model) = model.as_deref() {
+        ModelOverride::Set(model)
+    } else {
+        ModelOverride::Keep
+    };
+    crate::provider_switchboard::set_target(
+        &state,
+        cli_homes.unwrap_or_default(),
+        target,
+        provider,
+        model_override,
+        dry_run.unwrap_or(false),
+    )
+}
+
+fn switchboard_add_profile_impl(
+    state: &app_state::AppState,
+    name: &str,
+    path: &str,
+) -> Result<(), String> {
+    let (name, path) = crate::provider_switchboard::validate_switchboard_profile(name, path)?;
+    let prev = state
+        .gateway
+        .cfg
+        .write()
+        .switchboard_profiles
+        .insert(name.clone(), path.clone());
+    if let Err(err) = persist_config_for_app_state(state) {
+        let mut cfg = state.gateway.cfg.write();
+        match prev {
+            Some(prev) => cfg.switchboard_profiles.insert(name, prev),
+            None => cfg.switchboard_profiles.remove(&name),
+        };
+        return Err(err.to_string());
+    }
+    state
+        .gateway
+        .store
+        .events()
+        .config()
+        .switchboard_profile_added(
+            "codex",
+            "Switchboard profile saved",
+            serde_json::json!({ "name": name, "path": path }),
+        );
+    Ok(())
+}
+
+fn switchboard_remove_profile_impl(state: &app_state::AppState, name: &str) -> Result<(), String> {
+    let name = name.trim();
+    let prev = state
+        .gateway
+        .cfg
+        .write()
+        .switchboard_profiles
+        .remove(name)
+        .ok_or_else(|| format!("unknown switchboard profile: {name}"))?;
+    if let Err(err) = persist_config_for_app_state(state) {
+        state
+            .gateway
+            .cfg
+            .write()
+            .switchboard_profiles
+            .insert(name.to_string(), prev);
+        return Err(err.to_string());
+    }
+    state
+        .gateway
+        .store
+        .events()
+        .config()
+        .switchboard_profile_removed(
+            "codex",
+            "Switchboard profile removed",
+            serde_json::json!({ "name": name, "path": prev }),
+        );
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn switchboard_add_profile(
+    state: tauri::State<'_, app_state::AppState>,
+    name: String,
+    path: String,
+) -> Result<(), String> {
+    switchboard_add_profile_impl(&state, &name, &path)
+}
+
+#[tauri::command]
+pub(crate) fn switchboard_remove_profile(
+    state: tauri::State<'_, app_state::AppState>,
+    name: String,
+) -> Result<(), String> {
+    switchboard_remove_profile_impl(&state, &name)
 }
 
 fn mask_key_preview(key: &str) -> String {
@@ -397,9 +524,60 @@ fn mask_key_preview(key: &str) -> String {
     format!("{start}******{end}")
 }
 
-fn persist_config_for_app_state(state: &app_state::AppState) -> anyhow::Result<()> {
+/// UI-facing key preview revealing `tail` trailing characters (`mask_key_preview` stays the
+/// canonical key ref). Keys that would keep fewer than `MIN_MASKED_KEY_CHARS` hidden show a
+/// short hash fingerprint instead.
+fn display_key_preview(key: &str, tail: usize) -> String {
+    use sha2::{Digest, Sha256};
+    const MIN_MASKED_KEY_CHARS: usize = 4;
+
+    let k = key.trim();
+    let chars: Vec<char> = k.chars().collect();
+    if chars.is_empty() {
+        return "set".to_string();
+    }
+    let end_len = tail.min(chars.len());
+    let start_len = std::cmp::min(6, chars.len() - end_len);
+    if chars.len() < 10 || chars.len() - start_len - end_len < MIN_MASKED_KEY_CHARS {
+        let digest = Sha256::digest(k.as_bytes());
+        let fingerprint: String = digest[..3].iter().map(|b| format!("{b:02x}")).collect();
+        return format!("set#{fingerprint}");
+    }
+    let start: String = chars.iter().take(start_len).collect();
+    let end: String = chars[chars.len() - end_len..].iter().collect();
+    format!("{start}******{end}")
+}
+
+fn key_preview_tail(state: &app_state::AppState) -> usize {
+    state
+        .gateway
+        .cfg
+        .read()
+        .routing
+        .effective_key_preview_tail()
+}
+
+fn serialized_config_toml(state: &app_state::AppState) -> anyhow::Result<String> {
     let cfg = state.gateway.cfg.read().clone();
-    std::fs::write(&state.config_path, toml::to_string_pretty(&cfg)?)?;
+    Ok(toml::to_string_pretty(&cfg)?)
+}
+
+/// `config.toml` as `persist_config` writes it, minus the fields that can carry credentials: a
+/// legacy provider `api_key` (only kept until it migrates into secrets.json) and custom `headers`.
+fn exported_config_toml(state: &app_state::AppState) -> anyhow::Result<String> {
+    let mut cfg = state.gateway.cfg.read().clone();
+    for provider in cfg.providers.values_mut() {
+        provider.api_key.clear();
+        provider.headers.clear();
+    }
+    for template in cfg.provider_templates.values_mut() {
+        template.headers.clear();
+    }
+    Ok(toml::to_string_pretty(&cfg)?)
+}
+
+fn persist_config_for_app_state(state: &app_state::AppState) -> anyhow::Result<()> {
+    std::fs::write(&state.config_path, serialized_config_toml(state)?)?;
     Ok(())
 }
 
@@ -413,7 +591,15 @@ async fn refresh_codex_account_snapshot(
     secrets: Option<&crate::orchestrator::secrets::SecretStore>,
 ) -> Result<bool, String> {
     let app_auth_json = read_codex_auth_from_app(config_path);
-    let usage = read_codex_account_usage(None, app_auth_json.as_ref()).await?;
+    let field_map = codex_account_usage_field_map(gateway);
+    let fractional_percent = gateway.cfg.read().routing.fractional_percent_display;
+    let usage = read_codex_account_usage(
+        None,
+        app_auth_json.as_ref(),
+        field_map.as_ref(),
+        fractional_percent,
+    )
+    .await?;
     gateway
         .store
         .put_codex_account_snapshot(&codex_account_usage_status_snapshot(&usage));
@@ -446,7 +632,15 @@ async fn refresh_codex_account_login_target_snapshot(
     let auth_json = read_codex_auth_from_app(config_path)
         .or_else(|| secrets.official_account_profile_auth_json(profile_id).ok())
         .ok_or_else(|| format!("official account profile not found: {profile_id}"))?;
-    let usage = read_codex_account_usage(None, Some(&auth_json)).await?;
+    let field_map = codex_account_usage_field_map(gateway);
+    let fractional_percent = gateway.cfg.read().routing.fractional_percent_display;
+    let usage = read_codex_account_usage(
+        None,
+        Some(&auth_json),
+        field_map.as_ref(),
+        fractional_percent,
+    )
+    .await?;
     let usage_snapshot = crate::orchestrator::secrets::OfficialAccountUsageSnapshot {
         limit_5h_remaining: usage.limit_5h_remaining.clone(),
         limit_5h_reset_at: usage.limit_5h_reset_at.clone(),
@@ -471,18 +665,44 @@ async fn refresh_codex_account_login_target_snapshot(
     Ok(usage.signed_in)
 }
 
+/// Codex account rate limits belong to OAuth-passthrough providers (no stored key), so their
+/// `usage_field_map` drives parsing of the rate-limit windows.
+fn codex_account_usage_field_map(
+    gateway: &crate::orchestrator::gateway::GatewayState,
+) -> Option<crate::orchestrator::config::UsageFieldMap> {
+    let cfg = gateway.cfg.read();
+    cfg.providers
+        .iter()
+        .filter(|(name, _)| gateway.secrets.get_provider_key(name).is_none())
+        .find_map(|(_, provider)| provider.usage_field_map.clone())
+}
+
 fn codex_account_usage_status_snapshot(usage: &CodexAccountUsageRead) -> serde_json::Value {
+    let checked_at_unix_ms = unix_ms();
+    let limit_weekly_reset_at_unix_ms = usage
+        .limit_weekly_reset_at
+        .as_deref()
+        .and_then(reset_time_unix_ms);
+    let code_review_reset_at_unix_ms = usage
+        .code_review_reset_at
+        .as_deref()
+        .and_then(reset_time_unix_ms);
+    let code_review_resets_in_seconds = code_review_reset_at_unix_ms
+        .map(|reset_at| reset_at.saturating_sub(checked_at_unix_ms) / 1000);
     serde_json::json!({
       "ok": usage.error.is_empty(),
-      "checked_at_unix_ms": unix_ms(),
+      "checked_at_unix_ms": checked_at_unix_ms,
       "signed_in": usage.signed_in,
       "remaining": usage.remaining,
       "limit_5h_remaining": usage.limit_5h_remaining,
       "limit_5h_reset_at": usage.limit_5h_reset_at,
       "limit_weekly_remaining": usage.limit_weekly_remaining,
       "limit_weekly_reset_at": usage.limit_weekly_reset_at,
+      "limit_weekly_reset_at_unix_ms": limit_weekly_reset_at_unix_ms,
       "code_review_remaining": usage.code_review_remaining,
       "code_review_reset_at": usage.code_review_reset_at,
+      "code_review_reset_at_unix_ms": code_review_reset_at_unix_ms,
+      "code_review_resets_in_seconds": code_review_resets_in_seconds,
       "unlimited": usage.unlimited,
       "error": usage.error
     })
@@ -500,7 +720,15 @@ async fn refresh_all_codex_account_usage(
     gateway: &crate::orchestrator::gateway::GatewayState,
     secrets: &crate::orchestrator::secrets::SecretStore,
 ) -> Result<serde_json::Value, String> {
-    let outcome = refresh_official_account_profiles_usage(config_path, secrets).await?;
+    let field_map = codex_account_usage_field_map(gateway);
+    let fractional_percent = gateway.cfg.read().routing.fractional_percent_display;
+    let outcome = refresh_official_account_profiles_usage(
+        config_path,
+        secrets,
+        field_map.as_ref(),
+        fractional_percent,
+    )
+    .await?;
     if let Some(usage) = outcome.active_usage.as_ref() {
         gateway
             .store
@@ -523,6 +751,8 @@ async fn refresh_all_codex_account_usage(
 async fn refresh_official_account_profiles_usage(
     config_path: &std::path::Path,
     secrets: &crate::orchestrator::secrets::SecretStore,
+    field_map: Option<&crate::orchestrator::config::UsageFieldMap>,
+    fractional_percent: bool,
 ) -> Result<OfficialAccountProfilesRefreshOutcome, String> {
     let profile_summaries = secrets.list_official_account_profiles();
     let active_profile_id = profile_summaries
@@ -550,15 +780,25 @@ async fn refresh_official_account_profiles_usage(
             if official_account_auth_needs_runtime_refresh(&entry.auth_json, now_unix_ms) {
                 crate::codex_app_server::refresh_server_in_home(Some(home_text.as_str())).await?;
             }
-            let mut usage =
-                read_codex_account_usage(Some(home_text.as_str()), Some(&entry.auth_json)).await?;
+            let mut usage = read_codex_account_usage(
+                Some(home_text.as_str()),
+                Some(&entry.auth_json),
+                field_map,
+                fractional_percent,
+            )
+            .await?;
             if !has_official_account_usage_limits(&usage)
                 && (official_account_auth_needs_runtime_refresh(&entry.auth_json, now_unix_ms)
                     || !usage.signed_in)
             {
                 crate::codex_app_server::refresh_server_in_home(Some(home_text.as_str())).await?;
-                usage = read_codex_account_usage(Some(home_text.as_str()), Some(&entry.auth_json))
-                    .await?;
+                usage = read_codex_account_usage(
+                    Some(home_text.as_str()),
+                    Some(&entry.auth_json),
+                    field_map,
+                    fractional_percent,
+                )
+                .await?;
             }
             Ok::<CodexAccountUsageRead, String>(usage)
         }
@@ -700,6 +940,7 @@ fn read_official_account_profile_home_auth(home: &std::path::Path) -> Option<Val
     serde_json::from_str::<Value>(&text).ok()
 }
 
+#[derive(Default)]
 struct CodexAccountUsageRead {
     signed_in: bool,
     remaining: Option<String>,
@@ -716,6 +957,8 @@ struct CodexAccountUsageRead {
 async fn read_codex_account_usage(
     codex_home: Option<&str>,
     auth_json: Option<&Value>,
+    field_map: Option<&crate::orchestrator::config::UsageFieldMap>,
+    fractional_percent: bool,
 ) -> Result<CodexAccountUsageRead, String> {
     let mut signed_in = false;
     let mut remaining: Option<String> = None;
@@ -750,7 +993,7 @@ async fn read_codex_account_usage(
             let rate_limits = get_rate_limits_obj(&result);
             let used_percent = rate_limits
                 .and_then(|v| v.get("secondary").or_else(|| v.get("Secondary")))
-                .and_then(get_used_percent);
+                .and_then(|node| window_used_percent(node, field_map));
 
             if let Some(rate_limits) = rate_limits {
                 let mut weekly_best: Option<(String, Option<String>, i32)> = None;
@@ -761,11 +1004,12 @@ async fn read_codex_account_usage(
                     ("Secondary", "secondary"),
                 ] {
                     if let Some(node) = rate_limits.get(key) {
-                        if let Some(used) = get_used_percent(node) {
+                        if let Some(used) = window_used_percent(node, field_map) {
                             let window_mins = get_window_minutes(node);
                             if window_mins == Some(300) {
-                                limit_5h_remaining = Some(format_percent(100.0 - used));
-                                limit_5h_reset_at = get_reset_time_str(node);
+                                limit_5h_remaining =
+                                    Some(format_percent(100.0 - used, fractional_percent));
+                                limit_5h_reset_at = window_reset_time_str(node, field_map);
                             } else if window_mins == Some(10080) || target == "secondary" {
                                 let priority = if window_mins == Some(10080) { 2 } else { 1 };
                                 let should_update = weekly_best
@@ -774,8 +1018,8 @@ async fn read_codex_account_usage(
                                     .unwrap_or(true);
                                 if should_update {
                                     weekly_best = Some((
-                                        format_percent(100.0 - used),
-                                        get_reset_time_str(node),
+                                        format_percent(100.0 - used, fractional_percent),
+                                        window_reset_time_str(node, field_map),
                                         priority,
                                     ));
                                 }
@@ -798,9 +1042,9 @@ async fn read_codex_account_usage(
                         "CodeReview",
                     ] {
                         if let Some(node) = rate_limits.get(key) {
-                            if let Some(rem) = get_remaining_percent(node) {
+                            if let Some(rem) = get_remaining_percent(node, fractional_percent) {
                                 code_review_remaining = Some(rem);
-                                code_review_reset_at = get_reset_time_str(node);
+                                code_review_reset_at = window_reset_time_str(node, field_map);
                                 break;
                             }
                         }
@@ -826,7 +1070,7 @@ async fn read_codex_account_usage(
             }
             if unlimited != Some(true) {
                 if let Some(used) = used_percent {
-                    remaining = Some(format_percent(100.0 - used));
+                    remaining = Some(format_percent(100.0 - used, fractional_percent));
                 }
             }
         }
@@ -838,7 +1082,9 @@ async fn read_codex_account_usage(
     }
 
     if let Some(access_token) = read_codex_access_token(auth_json) {
-        if let Ok(Some((remaining, reset_at))) = fetch_code_review_from_wham(&access_token).await {
+        if let Ok(Some((remaining, reset_at))) =
+            fetch_code_review_from_wham(&access_token, fractional_percent).await
+        {
             code_review_remaining = Some(remaining);
             code_review_reset_at = reset_at;
         }
@@ -953,6 +1199,117 @@ mod account_switchboard_tests {
         );
     }
 
+    #[test]
+    fn display_key_preview_matches_default_mask_and_fingerprints_short_keys() {
+        let key = "sk-abcdefghijklmnop1234";
+        assert_eq!(display_key_preview(key, 4), mask_key_preview(key));
+        assert_eq!(display_key_preview(key, 8), "sk-abc******mnop1234");
+        assert_eq!(display_key_preview(key, 0), "sk-abc******");
+
+        let short_a = display_key_preview("short-a", 4);
+        let short_b = display_key_preview("short-b", 4);
+        assert!(short_a.starts_with("set#"));
+        assert_eq!(short_a.len(), "set#".len() + 6);
+        assert_ne!(short_a, short_b);
+        assert!(!short_a.contains("short"));
+        assert_eq!(display_key_preview("", 4), "set");
+    }
+
+    #[test]
+    fn display_key_preview_never_reveals_keys_with_a_short_masked_middle() {
+        // len == 6 + tail: nothing would be left to mask.
+        let key = "sk-abc12345678";
+        let preview = display_key_preview(key, 8);
+        assert!(preview.starts_with("set#"), "{preview}");
+        assert!(!preview.contains("12345678"));
+        assert!(display_key_preview(key, 64).starts_with("set#"));
+
+        // Exactly four hidden characters is still masked normally.
+        assert_eq!(
+            display_key_preview("sk-abcWXYZ12345678", 8),
+            "sk-abc******12345678"
+        );
+    }
+
+    #[test]
+    fn reset_time_unix_ms_accepts_seconds_millis_and_iso_strings() {
+        assert_eq!(reset_time_unix_ms("1700000000"), Some(1_700_000_000_000));
+        assert_eq!(reset_time_unix_ms("1700000000000"), Some(1_700_000_000_000));
+        assert_eq!(reset_time_unix_ms("1700000000.5"), Some(1_700_000_000_000));
+        assert_eq!(
+            reset_time_unix_ms("2023-11-14T22:13:20Z"),
+            Some(1_700_000_000_000)
+        );
+        assert_eq!(reset_time_unix_ms("soon"), None);
+    }
+
+    #[test]
+    fn format_percent_keeps_one_decimal_only_when_fractional() {
+        assert_eq!(format_percent(0.4, false), "0%");
+        assert_eq!(format_percent(41.96, false), "41%");
+        assert_eq!(format_percent(0.4, true), "0.4%");
+        assert_eq!(format_percent(41.96, true), "41.9%");
+        assert_eq!(format_percent(0.04, true), "<0.1%");
+        assert_eq!(format_percent(0.0, true), "0.0%");
+        assert_eq!(format_percent(-3.0, true), "0.0%");
+        assert_eq!(format_percent(120.0, true), "100.0%");
+        assert_eq!(format_percent(f64::NAN, true), "0.0%");
+    }
+
+    #[test]
+    fn codex_account_snapshot_reports_code_review_countdown() {
+        let reset_at = unix_ms() + 90_000;
+        let usage = CodexAccountUsageRead {
+            code_review_reset_at: Some((reset_at / 1000).to_string()),
+            limit_weekly_reset_at: Some(reset_at.to_string()),
+            ..Default::default()
+        };
+        let snapshot = codex_account_usage_status_snapshot(&usage);
+        assert_eq!(
+            snapshot["code_review_reset_at_unix_ms"].as_u64(),
+            Some(reset_at / 1000 * 1000)
+        );
+        assert_eq!(
+            snapshot["limit_weekly_reset_at_unix_ms"].as_u64(),
+            Some(reset_at)
+        );
+        let resets_in = snapshot["code_review_resets_in_seconds"]
+            .as_u64()
+            .expect("countdown");
+        assert!((88..=90).contains(&resets_in));
+    }
+
+    #[test]
+    fn usage_field_map_pointers_replace_window_heuristics() {
+        let node = serde_json::json!({
+            "used_percent": 10,
+            "expires_at": 1_700_000_000,
+            "quota": { "used": 40, "next_reset": 1_800_000_000 }
+        });
+        assert_eq!(window_used_percent(&node, None), Some(10.0));
+        assert_eq!(
+            window_reset_time_str(&node, None).as_deref(),
+            Some("1700000000000")
+        );
+
+        let field_map = crate::orchestrator::config::UsageFieldMap {
+            reset_at_pointer: Some("/quota/next_reset".to_string()),
+            used_percent_pointer: Some("/quota/used".to_string()),
+        };
+        assert_eq!(window_used_percent(&node, Some(&field_map)), Some(40.0));
+        assert_eq!(
+            window_reset_time_str(&node, Some(&field_map)).as_deref(),
+            Some("1800000000000")
+        );
+
+        let missing = crate::orchestrator::config::UsageFieldMap {
+            reset_at_pointer: Some("/quota/missing".to_string()),
+            used_percent_pointer: None,
+        };
+        assert_eq!(window_reset_time_str(&node, Some(&missing)), None);
+        assert_eq!(window_used_percent(&node, Some(&missing)), Some(10.0));
+    }
+
     #[test]
     fn write_codex_auth_to_app_persists_selected_profile() {
         let tmp = tempfile::tempdir().expect("tempdir");
@@ -1350,7 +1707,7 @@ mod account_switchboard_tests {
         *first_profile_id.lock().expect("first profile id lock") = first.id.clone();
         *second_profile_id.lock().expect("second profile id lock") = second.id.clone();
 
-        refresh_official_account_profiles_usage(&config_path, &store)
+        refresh_official_account_profiles_usage(&config_path, &store, None)
             .await
             .expect("refresh usage");
 
@@ -1420,7 +1777,7 @@ mod account_switchboard_tests {
             )
             .expect("capture profile");
 
-        refresh_official_account_profiles_usage(&config_path, &store)
+        refresh_official_account_profiles_usage(&config_path, &store, None)
             .await
             .expect("refresh usage");
 
@@ -1503,7 +1860,7 @@ mod account_switchboard_tests {
         *first_profile_id.lock().expect("first profile id lock") = first.id.clone();
         *second_profile_id.lock().expect("second profile id lock") = second.id.clone();
 
-        let outcome = refresh_official_account_profiles_usage(&config_path, &store)
+        let outcome = refresh_official_account_profiles_usage(&config_path, &store, None)
             .await
             .expect("partial refresh succeeds");
 
@@ -1517,10 +1874,43 @@ mod account_switchboard_tests {
 
         crate::codex_app_server::_set_test_request_handler(None).await;
     }
+
+    #[test]
+    fn switchboard_profiles_persist_and_validate_codex_homes() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let config_path = tmp.path().join("user-data").join("config.toml");
+        let data_dir = tmp.path().join("data");
+        std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        let state = crate::app_state::build_state(config_path.clone(), data_dir).expect("state");
+
+        let not_codex = tmp.path().join("empty");
+        std::fs::create_dir_all(&not_codex).unwrap();
+        let err = switchboard_add_profile_impl(&state, "work", &not_codex.to_string_lossy())
+            .expect_err("empty dir is not a codex home");
+        assert!(err.contains("Not a Codex home"));
+        assert!(switchboard_add_profile_impl(&state, "a/b", &not_codex.to_string_lossy()).is_err());
+
+        let cli_home = tmp.path().join("work-codex");
+        std::fs::create_dir_all(&cli_home).unwrap();
+        std::fs::write(cli_home.join("config.toml"), "model = \"gpt-5.2\"\n").unwrap();
+        switchboard_add_profile_impl(&state, " work ", &cli_home.to_string_lossy())
+            .expect("add profile");
+        let saved: crate::orchestrator::config::AppConfig =
+            toml::from_str(&std::fs::read_to_string(&config_path).unwrap()).expect("saved config");
+        assert_eq!(
+            saved.switchboard_profiles.get("work").map(String::as_str),
+            Some(cli_home.to_string_lossy().as_ref())
+        );
+
+        switchboard_remove_profile_impl(&state, "work").expect("remove profile");
+        assert!(state.gateway.cfg.read().switchboard_profiles.is_empty());
+        assert!(switchboard_remove_profile_impl(&state, "work").is_err());
+    }
 }
 
 async fn fetch_code_review_from_wham(
     token: &str,
+    fractional_percent: bool,
 ) -> Result<Option<(String, Option<String>)>, String> {
     let client = reqwest::Client::builder()
         .timeout(Duration::from_secs(8))
@@ -1545,7 +1935,7 @@ async fn fetch_code_review_from_wham(
     let Some(used_percent) = used else {
         return Ok(None);
     };
-    let remaining = format_percent(100.0 - used_percent);
+    let remaining = format_percent(100.0 - used_percent, fractional_percent);
     let reset_at = node
         .and_then(|n| n.get("primary_window"))
         .and_then(|n| n.get("reset_at"))
@@ -1558,8 +1948,19 @@ async fn fetch_code_review_from_wham(
     Ok(Some((remaining, reset_at)))
 }
 
-fn format_percent(value: f64) -> String {
+/// Whole percents by default, with anything under 1% shown as `0%`. `fractional` keeps one
+/// decimal, truncated so the remainder is never overstated, and a nonzero remainder below
+/// 0.1% reads `<0.1%` rather than looking exhausted.
+fn format_percent(value: f64, fractional: bool) -> String {
     let mut pct = if value.is_finite() { value } else { 0.0 };
+    if fractional {
+        let pct = pct.clamp(0.0, 100.0);
+        let tenths = (pct * 10.0).floor() / 10.0;
+        if tenths == 0.0 && pct > 0.0 {
+            return "<0.1%".to_string();
+        }
+        return format!("{tenths:.1}%");
+    }
     if pct < 1.0 {
         pct = 0.0;
     }
@@ -1589,45 +1990,87 @@ fn get_window_minutes(obj: &Value) -> Option<i64> {
         .map(|v| v.round() as i64)
 }
 
-fn get_remaining_percent(obj: &Value) -> Option<String> {
+fn get_remaining_percent(obj: &Value, fractional: bool) -> Option<String> {
     if let Some(used) = get_used_percent(obj) {
-        return Some(format_percent(100.0 - used));
+        return Some(format_percent(100.0 - used, fractional));
     }
     if let Some(rem) = obj
         .get("remainingPercent")
         .or_else(|| obj.get("remaining_percent"))
         .and_then(parse_number)
     {
-        return Some(format_percent(rem));
+        return Some(format_percent(rem, fractional));
     }
     obj.get("remaining")
         .and_then(parse_number)
-        .map(format_percent)
+        .map(|rem| format_percent(rem, fractional))
 }
 
-fn get_reset_time_str(obj: &Value) -> Option<String> {
-    use std::collections::VecDeque;
+/// Used percent of a rate-limit window, read from the configured pointer when one is mapped.
+fn window_used_percent(
+    node: &Value,
+    field_map: Option<&crate::orchestrator::config::UsageFieldMap>,
+) -> Option<f64> {
+    match field_map.and_then(crate::orchestrator::config::UsageFieldMap::used_percent_pointer) {
+        Some(pointer) => node.pointer(pointer).and_then(parse_number),
+        None => get_used_percent(node),
+    }
+}
 
-    fn read_time_value(v: &Value) -> Option<String> {
-        if let Some(s) = v.as_str().map(|s| s.trim().to_string()) {
-            if !s.is_empty() {
-                return Some(s);
-            }
-        }
-        if let Some(n) = v
-            .as_u64()
-            .or_else(|| v.as_i64().and_then(|x| u64::try_from(x).ok()))
-        {
-            // Heuristic: seconds vs milliseconds.
-            let ms = if n < 1_000_000_000_000 {
-                n.saturating_mul(1000)
-            } else {
-                n
-            };
-            return Some(ms.to_string());
+/// Reset time of a rate-limit window, read from the configured pointer when one is mapped.
+fn window_reset_time_str(
+    node: &Value,
+    field_map: Option<&crate::orchestrator::config::UsageFieldMap>,
+) -> Option<String> {
+    match field_map.and_then(crate::orchestrator::config::UsageFieldMap::reset_at_pointer) {
+        Some(pointer) => node.pointer(pointer).and_then(read_time_value),
+        None => get_reset_time_str(node),
+    }
+}
+
+fn read_time_value(v: &Value) -> Option<String> {
+    if let Some(s) = v.as_str().map(|s| s.trim().to_string()) {
+        if !s.is_empty() {
+            return Some(s);
         }
-        None
     }
+    if let Some(n) = v
+        .as_u64()
+        .or_else(|| v.as_i64().and_then(|x| u64::try_from(x).ok()))
+    {
+        return Some(epoch_to_unix_ms(n).to_string());
+    }
+    None
+}
+
+fn epoch_to_unix_ms(n: u64) -> u64 {
+    // Heuristic: seconds vs milliseconds.
+    if n < 1_000_000_000_000 {
+        n.saturating_mul(1000)
+    } else {
+        n
+    }
+}
+
+/// Normalizes a reset time captured by `read_time_value` (epoch seconds/ms or an ISO string).
+fn reset_time_unix_ms(raw: &str) -> Option<u64> {
+    let raw = raw.trim();
+    if let Ok(n) = raw.parse::<u64>() {
+        return Some(epoch_to_unix_ms(n));
+    }
+    if let Some(n) = raw
+        .parse::<f64>()
+        .ok()
+        .filter(|n| n.is_finite() && *n >= 0.0)
+    {
+        return Some(epoch_to_unix_ms(n as u64));
+    }
+    let parsed = chrono::DateTime::parse_from_rfc3339(raw).ok()?;
+    u64::try_from(parsed.timestamp_millis()).ok()
+}
+
+fn get_reset_time_str(obj: &Value) -> Option<String> {
+    use std::collections::VecDeque;
 
     // Try direct keys first, then a small BFS for nested shapes.
     let keys = [
@@ -0,0 +1,151 @@
+//! Structured error returned by provider, pricing and quota commands.
+//!
+//! Serializes to `{ "code": ..., "message": ... }` so the UI can switch on `code` while
+//! still showing `message` as-is. `upstream` errors additionally carry `status`.
+
+use serde::ser::SerializeMap;
+
+const UNKNOWN_PROVIDER_PREFIX: &str = "unknown provider: ";
+
+#[derive(Debug, Clone, PartialEq, Eq, thiserror::Error)]
+pub(crate) enum CommandError {
+    #[error("unknown provider: {0}")]
+    UnknownProvider(String),
+    #[error("{0}")]
+    Validation(String),
+    #[error("{message}")]
+    Upstream {
+        status: Option<u16>,
+        message: String,
+    },
+    #[error("{0}")]
+    Io(String),
+    #[error("{0}")]
+    Internal(String),
+    #[error("read-only mode: {0} is disabled")]
+    ReadOnly(String),
+}
+
+impl CommandError {
+    pub(crate) fn unknown_provider(name: &str) -> Self {
+        Self::UnknownProvider(name.to_string())
+    }
+
+    pub(crate) fn upstream(status: Option<u16>, message: impl Into<String>) -> Self {
+        Self::Upstream {
+            status,
+            message: message.into(),
+        }
+    }
+
+    pub(crate) fn code(&self) -> &'static str {
+        match self {
+            Self::UnknownProvider(_) => "unknown_provider",
+            Self::Validation(_) => "validation",
+            Self::Upstream { .. } => "upstream",
+            Self::Io(_) => "io",
+            Self::Internal(_) => "internal",
+            Self::ReadOnly(_) => "read_only",
+        }
+    }
+}
+
+/// Lifts the plain string errors still produced by `_impl` helpers and secrets/LAN calls.
+///
+/// The historical `unknown provider: <name>` wording is recognised; everything else is
+/// treated as a validation failure since that is what those helpers report.
+impl From<String> for CommandError {
+    fn from(message: String) -> Self {
+        match message.strip_prefix(UNKNOWN_PROVIDER_PREFIX) {
+            Some(name) => Self::UnknownProvider(name.to_string()),
+            None => Self::Validation(message),
+        }
+    }
+}
+
+impl From<&str> for CommandError {
+    fn from(message: &str) -> Self {
+        Self::from(message.to_string())
+    }
+}
+
+impl From<std::io::Error> for CommandError {
+    fn from(err: std::io::Error) -> Self {
+        Self::Io(err.to_string())
+    }
+}
+
+impl From<anyhow::Error> for CommandError {
+    fn from(err: anyhow::Error) -> Self {
+        if err.downcast_ref::<std::io::Error>().is_some() {
+            Self::Io(err.to_string())
+        } else {
+            Self::Internal(err.to_string())
+        }
+    }
+}
+
+impl serde::Serialize for CommandError {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        let status = match self {
+            Self::Upstream { status, .. } => Some(status),
+            _ => None,
+        };
+        let mut map = serializer.serialize_map(Some(if status.is_some() { 3 } else { 2 }))?;
+        map.serialize_entry("code", self.code())?;
+        map.serialize_entry("message", &self.to_string())?;
+        if let Some(status) = status {
+            map.serialize_entry("status", status)?;
+        }
+        map.end()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::CommandError;
+
+    #[test]
+    fn serializes_code_and_message() {
+        assert_eq!(
+            serde_json::to_value(CommandError::unknown_provider("p1")).unwrap(),
+            serde_json::json!({ "code": "unknown_provider", "message": "unknown provider: p1" })
+        );
+        assert_eq!(
+            serde_json::to_value(CommandError::upstream(Some(502), "http 502")).unwrap(),
+            serde_json::json!({ "code": "upstream", "message": "http 502", "status": 502 })
+        );
+        assert_eq!(
+            serde_json::to_value(CommandError::Validation("name is required".to_string())).unwrap(),
+            serde_json::json!({ "code": "validation", "message": "name is required" })
+        );
+        assert_eq!(
+            serde_json::to_value(CommandError::ReadOnly("set_provider_key".to_string())).unwrap(),
+            serde_json::json!({
+                "code": "read_only",
+                "message": "read-only mode: set_provider_key is disabled"
+            })
+        );
+    }
+
+    #[test]
+    fn classifies_legacy_string_and_anyhow_errors() {
+        assert_eq!(
+            CommandError::from("unknown provider: p2".to_string()),
+            CommandError::unknown_provider("p2")
+        );
+        assert_eq!(
+            CommandError::from("invalid url"),
+            CommandError::Validation("invalid url".to_string())
+        );
+        let io = anyhow::Error::from(std::io::Error::other("disk full"));
+        assert_eq!(CommandError::from(io).code(), "io");
+        assert_eq!(
+            CommandError::from(anyhow::anyhow!("boom")).code(),
+            "internal"
+        );
+    }
+}
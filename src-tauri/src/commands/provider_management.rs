@@ -1,15 +1,23 @@
 fn clear_observed_session_routes(state: &app_state::AppState) -> usize {
-    let mut routes = state.gateway.last_used_by_session.write();
-    let cleared = routes.len();
-    routes.clear();
+    let cleared = {
+        let mut routes = state.gateway.last_used_by_session.write();
+        let cleared = routes.len();
+        routes.clear();
+        cleared
+    };
+    crate::orchestrator::gateway::persist_last_used_routes(&state.gateway);
     cleared
 }
 
 fn clear_observed_session_routes_for_provider(state: &app_state::AppState, provider: &str) -> usize {
-    let mut routes = state.gateway.last_used_by_session.write();
-    let before = routes.len();
-    routes.retain(|_, route| route.provider != provider);
-    before.saturating_sub(routes.len())
+    let cleared = {
+        let mut routes = state.gateway.last_used_by_session.write();
+        let before = routes.len();
+        routes.retain(|_, route| route.provider != provider);
+        before.saturating_sub(routes.len())
+    };
+    crate::orchestrator::gateway::persist_last_used_routes(&state.gateway);
+    cleared
 }
 
 fn rename_observed_session_routes_provider_refs(
@@ -17,22 +25,27 @@ fn rename_observed_session_routes_provider_refs(
     old_provider: &str,
     new_provider: &str,
 ) -> usize {
-    let mut routes = state.gateway.last_used_by_session.write();
     let mut updated = 0_usize;
-    for route in routes.values_mut() {
-        let mut changed = false;
-        if route.provider == old_provider {
-            route.provider = new_provider.to_string();
-            changed = true;
-        }
-        if route.preferred == old_provider {
-            route.preferred = new_provider.to_string();
-            changed = true;
-        }
-        if changed {
-            updated = updated.saturating_add(1);
+    {
+        let mut routes = state.gateway.last_used_by_session.write();
+        for route in routes.values_mut() {
+            let mut changed = false;
+            if route.provider == old_provider {
+                route.provider = new_provider.to_string();
+                changed = true;
+            }
+            if route.preferred == old_provider {
+                route.preferred = new_provider.to_string();
+                changed = true;
+            }
+            if changed {
+                updated = updated.saturating_add(1);
+            }
         }
     }
+    if updated > 0 {
+        crate::orchestrator::gateway::persist_last_used_routes(&state.gateway);
+    }
     updated
 }
 
@@ -142,6 +155,31 @@ pub(crate) fn ensure_local_provider_definitions_editable(
     }
 }
 
+/// Audit snapshots never carry secrets: inline keys collapse to a boolean.
+fn provider_audit_snapshot(
+    provider: Option<&crate::orchestrator::config::ProviderConfig>,
+) -> serde_json::Value {
+    let Some(provider) = provider else {
+        return serde_json::Value::Null;
+    };
+    let mut value = serde_json::to_value(provider).unwrap_or(serde_json::Value::Null);
+    if let Some(obj) = value.as_object_mut() {
+        obj.remove("api_key");
+        obj.insert(
+            "api_key_set".to_string(),
+            serde_json::Value::Bool(!provider.api_key.trim().is_empty()),
+        );
+    }
+    value
+}
+
+fn provider_key_audit_value(key: Option<&str>) -> serde_json::Value {
+    match normalized_provider_key(key) {
+        Some(key) => serde_json::json!({ "set": true, "preview": mask_key_preview(&key) }),
+        None => serde_json::json!({ "set": false }),
+    }
+}
+
 fn normalized_provider_key(key: Option<&str>) -> Option<String> {
     key.map(str::trim)
         .filter(|value| !value.is_empty())
@@ -252,20 +290,28 @@ fn set_manual_override_impl(
         if cfg.providers.get(p).is_some_and(|provider_cfg| provider_cfg.disabled) {
             return Err(format!("provider is deactivated: {p}"));
         }
+        if cfg
+            .providers
+            .get(p)
+            .is_some_and(|provider_cfg| !provider_cfg.routing_eligible)
+        {
+            return Err(format!("provider is usage-only: {p}"));
+        }
     }
     let prev_override = state.gateway.router.manual_override.read().clone();
     if prev_override == provider {
         return Ok(());
     }
+    let active_provider = active_serving_provider(state);
     state.gateway.router.set_manual_override(provider.clone());
     let cleared_assignments = state.gateway.store.delete_all_session_route_assignments();
     let cleared_observed_routes = clear_observed_session_routes(state);
-    state.gateway.store.events().routing().manual_override_changed(
-        provider.as_deref().unwrap_or("-"),
-        "manual override changed",
+    emit_manual_override_changed(
+        state,
+        prev_override.as_deref(),
+        provider.as_deref(),
+        active_provider.as_deref(),
         serde_json::json!({
-            "previous_manual_override": prev_override,
-            "manual_override": provider,
             "cleared_session_route_assignments": cleared_assignments,
             "cleared_observed_session_routes": cleared_observed_routes,
         }),
@@ -273,18 +319,153 @@ fn set_manual_override_impl(
     Ok(())
 }
 
+/// Provider that served the most recent request within the dashboard active window.
+fn active_serving_provider(state: &app_state::AppState) -> Option<String> {
+    let active_window_ms = state.gateway.cfg.read().routing.active_window_ms();
+    let now = unix_ms();
+    state
+        .gateway
+        .last_used_by_session
+        .read()
+        .values()
+        .filter(|route| now.saturating_sub(route.unix_ms) < active_window_ms)
+        .max_by_key(|route| route.unix_ms)
+        .map(|route| route.provider.clone())
+}
+
+fn emit_manual_override_changed(
+    state: &app_state::AppState,
+    previous: Option<&str>,
+    next: Option<&str>,
+    active_provider: Option<&str>,
+    extra_fields: serde_json::Value,
+) {
+    let (action, message) = match (previous, next) {
+        (_, Some(next)) => ("set", format!("manual override set to {next}")),
+        (Some(previous), None) => (
+            "cleared",
+            format!("manual override cleared (was {previous})"),
+        ),
+        (None, None) => ("cleared", "manual override cleared".to_string()),
+    };
+    let mut fields = serde_json::json!({
+        "action": action,
+        "previous_manual_override": previous,
+        "manual_override": next,
+        "active_provider": active_provider,
+    });
+    if let (Some(fields), serde_json::Value::Object(extra)) = (fields.as_object_mut(), extra_fields)
+    {
+        fields.extend(extra);
+    }
+    state
+        .gateway
+        .store
+        .events()
+        .routing()
+        .manual_override_changed(next.or(previous).unwrap_or("-"), &message, fields);
+}
+
 #[tauri::command]
 pub(crate) fn set_manual_override(
     state: tauri::State<'_, app_state::AppState>,
     provider: Option<String>,
-) -> Result<(), String> {
-    set_manual_override_impl(&state, provider)
+) -> Result<(), CommandError> {
+    set_manual_override_impl(&state, provider).map_err(CommandError::from)
+}
+
+const SIMULATED_FAILURE_MAX_SECONDS: u64 = 60 * 60;
+
+/// Debug builds only: drills failover by cooling `provider` down in router state for
+/// `duration_seconds`, then lifts it again unless a real failure took over meanwhile.
+fn simulate_provider_failure_impl(
+    state: &app_state::AppState,
+    provider: &str,
+    duration_seconds: u64,
+) -> Result<u64, CommandError> {
+    if !cfg!(debug_assertions) {
+        return Err(CommandError::Validation(
+            "simulate_provider_failure is only available in debug builds".to_string(),
+        ));
+    }
+    let provider = provider.trim();
+    if !state.gateway.cfg.read().providers.contains_key(provider) {
+        return Err(CommandError::unknown_provider(provider));
+    }
+    if duration_seconds == 0 || duration_seconds > SIMULATED_FAILURE_MAX_SECONDS {
+        return Err(CommandError::Validation(format!(
+            "duration_seconds must be between 1 and {SIMULATED_FAILURE_MAX_SECONDS}"
+        )));
+    }
+    let now = unix_ms();
+    let until = now + duration_seconds * 1000;
+    state
+        .gateway
+        .router
+        .simulate_failure(provider, until, now)
+        .ok_or_else(|| CommandError::unknown_provider(provider))?;
+    state.gateway.store.events().routing().simulated_failure(
+        provider,
+        &format!("simulated failure started for {duration_seconds}s (testing only)"),
+        serde_json::json!({
+            "action": "started",
+            "simulated": true,
+            "duration_seconds": duration_seconds,
+            "until_unix_ms": until,
+        }),
+    );
+    Ok(until)
+}
+
+#[tauri::command]
+pub(crate) fn simulate_provider_failure(
+    state: tauri::State<'_, app_state::AppState>,
+    provider: String,
+    duration_seconds: u64,
+) -> Result<serde_json::Value, CommandError> {
+    let until = simulate_provider_failure_impl(&state, &provider, duration_seconds)?;
+    let provider = provider.trim().to_string();
+    let router = state.gateway.router.clone();
+    let store = state.gateway.store.clone();
+    let recovered = provider.clone();
+    tauri::async_runtime::spawn(async move {
+        tokio::time::sleep(std::time::Duration::from_millis(
+            until.saturating_sub(unix_ms()),
+        ))
+        .await;
+        if router.end_simulated_failure(&recovered, until, unix_ms()) {
+            store.events().routing().simulated_failure(
+                &recovered,
+                "simulated failure ended (testing only)",
+                serde_json::json!({ "action": "ended", "simulated": true }),
+            );
+        }
+    });
+    Ok(serde_json::json!({
+        "ok": true,
+        "provider": provider,
+        "cooldown_until_unix_ms": until,
+    }))
+}
+
+/// The `config.toml` text for display and backups, with provider `api_key` and `headers` removed.
+#[tauri::command]
+pub(crate) fn get_config_toml(
+    state: tauri::State<'_, app_state::AppState>,
+) -> Result<String, CommandError> {
+    exported_config_toml(&state).map_err(|e| CommandError::Internal(e.to_string()))
+}
+
+#[tauri::command]
+pub(crate) fn get_config_path(state: tauri::State<'_, app_state::AppState>) -> String {
+    state.config_path.display().to_string()
 }
 
 #[tauri::command]
 pub(crate) fn get_config(state: tauri::State<'_, app_state::AppState>) -> serde_json::Value {
     crate::lan_sync::reconcile_remote_update_terminal_event(&state.gateway);
     let cfg = state.gateway.cfg.read().clone();
+    let preview_tail = cfg.routing.effective_key_preview_tail();
     let pricing = state.secrets.list_provider_pricing();
     let quota_hard_caps = state.secrets.list_provider_quota_hard_cap();
     let now = unix_ms();
@@ -330,7 +511,9 @@ pub(crate) fn get_config(state: tauri::State<'_, app_state::AppState>) -> serde_
                 }
             });
             let has_key = key.is_some();
-            let key_preview = key.as_deref().map(mask_key_preview);
+            let key_preview = key
+                .as_deref()
+                .map(|key| display_key_preview(key, preview_tail));
             let shared_provider_id = state.secrets.get_provider_shared_id(name);
             let local_copy_state = borrowed.then(|| {
                 let normalized_key = normalized_provider_key(key.as_deref());
@@ -354,7 +537,12 @@ pub(crate) fn get_config(state: tauri::State<'_, app_state::AppState>) -> serde_
                   "base_url": p.base_url,
                   "group": p.group.clone(),
                   "disabled": p.disabled,
+                  "routing_eligible": p.routing_eligible,
+                  "tier": p.tier,
                   "supports_websockets": p.supports_websockets,
+                  "system_prefix": p.system_prefix.clone(),
+                  "default_model": p.default_model.clone(),
+                  "notes": p.notes.clone(),
                   "usage_adapter": p.usage_adapter.clone(),
                   "usage_presentation": match crate::orchestrator::providers::provider_usage_presentation(p) {
                     crate::orchestrator::providers::UsagePresentation::Standard => "standard",
@@ -368,6 +556,14 @@ pub(crate) fn get_config(state: tauri::State<'_, app_state::AppState>) -> serde_
                   "manual_pricing_mode": manual_mode.filter(|m| m != "none"),
                   "manual_pricing_amount_usd": manual_amount,
                   "manual_pricing_expires_at_unix_ms": active_package_expires,
+                  "manual_pricing_tiers": manual_pricing
+                    .as_ref()
+                    .map(|v| v.tiers.clone())
+                    .filter(|tiers| !tiers.is_empty()),
+                  "model_pricing": manual_pricing
+                    .as_ref()
+                    .map(|v| v.model_rates.clone())
+                    .unwrap_or_default(),
                   "manual_gap_fill_mode": manual_pricing.as_ref().and_then(|v| v.gap_fill_mode.clone()),
                   "manual_gap_fill_amount_usd": manual_pricing.as_ref().and_then(|v| v.gap_fill_amount_usd),
                   "account_email": account_email,
@@ -835,6 +1031,14 @@ fn copy_provider_from_config_source_impl(
         usage_adapter: payload.usage_adapter.clone(),
         usage_base_url: payload.usage_base_url.clone(),
         api_key: String::new(),
+        model_aliases: std::collections::BTreeMap::new(),
+        system_prefix: None,
+        usage_field_map: None,
+        headers: std::collections::BTreeMap::new(),
+        routing_eligible: true,
+        default_model: None,
+        tier: 0,
+        notes: String::new(),
     };
     let remote_usage_login = match (
         payload
@@ -1023,10 +1227,10 @@ pub(crate) fn set_provider_account_email(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
     email: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     ensure_local_provider_definitions_editable(&state)?;
     if !state.gateway.cfg.read().providers.contains_key(&provider) {
-        return Err(format!("unknown provider: {provider}"));
+        return Err(CommandError::unknown_provider(&provider));
     }
     state
         .secrets
@@ -1054,10 +1258,10 @@ pub(crate) fn set_provider_account_email(
 pub(crate) fn clear_provider_account_email(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     ensure_local_provider_definitions_editable(&state)?;
     if !state.gateway.cfg.read().providers.contains_key(&provider) {
-        return Err(format!("unknown provider: {provider}"));
+        return Err(CommandError::unknown_provider(&provider));
     }
     state.secrets.clear_provider_account_email(&provider)?;
     if let Err(err) = crate::lan_sync::record_provider_definition_patch(
@@ -1082,7 +1286,7 @@ pub(crate) fn clear_provider_account_email(
 #[tauri::command]
 pub(crate) fn get_gateway_token_preview(state: tauri::State<'_, app_state::AppState>) -> String {
     let tok = state.secrets.get_gateway_token().unwrap_or_default();
-    mask_key_preview(&tok)
+    display_key_preview(&tok, key_preview_tail(&state))
 }
 
 #[tauri::command]
@@ -1090,6 +1294,112 @@ pub(crate) fn get_gateway_token(state: tauri::State<'_, app_state::AppState>) ->
     state.secrets.get_gateway_token().unwrap_or_default()
 }
 
+fn named_gateway_token_summary(
+    token: &crate::orchestrator::secrets::NamedGatewayToken,
+    preview_tail: usize,
+) -> serde_json::Value {
+    serde_json::json!({
+      "label": token.label,
+      "preview": display_key_preview(&token.token, preview_tail),
+      "created_at_unix_ms": token.created_at_unix_ms,
+    })
+}
+
+fn create_gateway_token_impl(
+    state: &app_state::AppState,
+    label: &str,
+) -> Result<serde_json::Value, String> {
+    let created = state.secrets.create_named_gateway_token(label)?;
+    state.gateway.store.events().emit(
+        "gateway",
+        crate::orchestrator::store::EventCode::GATEWAY_TOKEN_CREATED,
+        "named gateway token created (user-data/secrets.json)",
+        serde_json::json!({ "label": created.label }),
+    );
+    // The raw token is only returned once, at creation time.
+    let mut out = named_gateway_token_summary(&created, key_preview_tail(state));
+    out["token"] = serde_json::json!(created.token);
+    Ok(out)
+}
+
+fn list_gateway_tokens_impl(state: &app_state::AppState) -> serde_json::Value {
+    let primary = state.secrets.get_gateway_token().unwrap_or_default();
+    let preview_tail = key_preview_tail(state);
+    let named = state
+        .secrets
+        .named_gateway_tokens()
+        .iter()
+        .map(|token| named_gateway_token_summary(token, preview_tail))
+        .collect::<Vec<_>>();
+    serde_json::json!({
+      "primary": {
+        "label": crate::orchestrator::secrets::PRIMARY_GATEWAY_TOKEN_LABEL,
+        "preview": display_key_preview(&primary, preview_tail),
+        "configured": !primary.trim().is_empty(),
+      },
+      "tokens": named,
+    })
+}
+
+/// Masked preview of every live gateway token, keyed by label. Sessions keep the label they
+/// authenticated with, so a revoked token simply has no entry here.
+fn gateway_token_previews(
+    state: &app_state::AppState,
+) -> std::collections::HashMap<String, String> {
+    let preview_tail = key_preview_tail(state);
+    let mut previews = state
+        .secrets
+        .named_gateway_tokens()
+        .into_iter()
+        .map(|token| (token.label, display_key_preview(&token.token, preview_tail)))
+        .collect::<std::collections::HashMap<_, _>>();
+    if let Some(primary) = state
+        .secrets
+        .get_gateway_token()
+        .filter(|token| !token.trim().is_empty())
+    {
+        previews.insert(
+            crate::orchestrator::secrets::PRIMARY_GATEWAY_TOKEN_LABEL.to_string(),
+            display_key_preview(&primary, preview_tail),
+        );
+    }
+    previews
+}
+
+fn revoke_gateway_token_impl(state: &app_state::AppState, label: &str) -> Result<(), String> {
+    state.secrets.revoke_named_gateway_token(label)?;
+    state.gateway.store.events().emit(
+        "gateway",
+        crate::orchestrator::store::EventCode::GATEWAY_TOKEN_REVOKED,
+        "named gateway token revoked (user-data/secrets.json)",
+        serde_json::json!({ "label": label.trim() }),
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn create_gateway_token(
+    state: tauri::State<'_, app_state::AppState>,
+    label: String,
+) -> Result<serde_json::Value, String> {
+    create_gateway_token_impl(&state, &label)
+}
+
+#[tauri::command]
+pub(crate) fn list_gateway_tokens(
+    state: tauri::State<'_, app_state::AppState>,
+) -> serde_json::Value {
+    list_gateway_tokens_impl(&state)
+}
+
+#[tauri::command]
+pub(crate) fn revoke_gateway_token(
+    state: tauri::State<'_, app_state::AppState>,
+    label: String,
+) -> Result<(), String> {
+    revoke_gateway_token_impl(&state, &label)
+}
+
 #[tauri::command]
 pub(crate) fn rotate_gateway_token(
     state: tauri::State<'_, app_state::AppState>,
@@ -1136,18 +1446,35 @@ pub(crate) fn rotate_gateway_token(
 pub(crate) fn set_preferred_provider(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
-) -> Result<(), String> {
-    {
+) -> Result<(), CommandError> {
+    let prev_provider = {
         let mut cfg = state.gateway.cfg.write();
         if !cfg.providers.contains_key(&provider) {
-            return Err(format!("unknown provider: {provider}"));
+            return Err(CommandError::unknown_provider(&provider));
         }
         if cfg.providers.get(&provider).is_some_and(|p| p.disabled) {
-            return Err(format!("provider is deactivated: {provider}"));
+            return Err(CommandError::Validation(format!(
+                "provider is deactivated: {provider}"
+            )));
         }
-        cfg.routing.preferred_provider = provider.clone();
-    }
-    persist_config(&state).map_err(|e| e.to_string())?;
+        if cfg
+            .providers
+            .get(&provider)
+            .is_some_and(|p| !p.routing_eligible)
+        {
+            return Err(CommandError::Validation(format!(
+                "provider is usage-only: {provider}"
+            )));
+        }
+        std::mem::replace(&mut cfg.routing.preferred_provider, provider.clone())
+    };
+    persist_config(&state)?;
+    state.gateway.store.record_config_audit(
+        "set_preferred_provider",
+        "routing.preferred_provider",
+        serde_json::json!(prev_provider),
+        serde_json::json!(provider),
+    );
     state.gateway.store.events().config().preferred_provider_updated(
         &provider,
         "preferred_provider updated",
@@ -1176,16 +1503,23 @@ fn set_route_mode_impl(state: &app_state::AppState, mode: &str) -> Result<(), St
 
     let cleared_assignments = state.gateway.store.delete_all_session_route_assignments();
     let cleared_observed_routes = clear_observed_session_routes(state);
+    let previous_route_mode = match prev_mode {
+        crate::orchestrator::config::RouteMode::FollowPreferredAuto => "follow_preferred_auto",
+        crate::orchestrator::config::RouteMode::BalancedAuto => "balanced_auto",
+    };
 
+    state.gateway.store.record_config_audit(
+        "set_route_mode",
+        "routing.route_mode",
+        serde_json::json!(previous_route_mode),
+        serde_json::json!(mode),
+    );
     state.gateway.store.events().config().route_mode_updated(
         "gateway",
         "route_mode updated",
         serde_json::json!({
             "route_mode": mode,
-            "previous_route_mode": match prev_mode {
-                crate::orchestrator::config::RouteMode::FollowPreferredAuto => "follow_preferred_auto",
-                crate::orchestrator::config::RouteMode::BalancedAuto => "balanced_auto",
-            },
+            "previous_route_mode": previous_route_mode,
             "cleared_session_route_assignments": cleared_assignments,
             "cleared_observed_session_routes": cleared_observed_routes,
         }),
@@ -1197,8 +1531,116 @@ fn set_route_mode_impl(state: &app_state::AppState, mode: &str) -> Result<(), St
 pub(crate) fn set_route_mode(
     state: tauri::State<'_, app_state::AppState>,
     mode: String,
-) -> Result<(), String> {
-    set_route_mode_impl(&state, &mode)
+) -> Result<(), CommandError> {
+    set_route_mode_impl(&state, &mode).map_err(CommandError::from)
+}
+
+fn explain_routing_impl(
+    state: &app_state::AppState,
+    session_id: Option<&str>,
+    model: Option<&str>,
+) -> Value {
+    // Requests without a Codex session id are keyed by peer address; mirror that so the preview
+    // skips per-session balancing the same way an anonymous request would.
+    let session_key = session_id
+        .map(str::trim)
+        .filter(|sid| !sid.is_empty())
+        .map(ToString::to_string)
+        .unwrap_or_else(|| "peer:explain_routing".to_string());
+    let model = model.map(str::trim).filter(|m| !m.is_empty());
+    crate::orchestrator::gateway::explain_routing_decision(&state.gateway, &session_key, model)
+}
+
+#[tauri::command]
+pub(crate) fn explain_routing(
+    state: tauri::State<'_, app_state::AppState>,
+    session_id: Option<String>,
+    model: Option<String>,
+) -> Value {
+    explain_routing_impl(&state, session_id.as_deref(), model.as_deref())
+}
+
+const USAGE_LABEL_MAX_CHARS: usize = 64;
+
+/// Labels end up in the usage database and LAN sync payloads, so keep them short, plain, and
+/// reject anything that embeds a configured provider key or the gateway token.
+fn validate_usage_label(state: &app_state::AppState, label: &str) -> Result<String, String> {
+    let label = label.trim();
+    if label.is_empty() {
+        return Err("label is required".to_string());
+    }
+    if label.chars().count() > USAGE_LABEL_MAX_CHARS {
+        return Err(format!(
+            "label must be at most {USAGE_LABEL_MAX_CHARS} characters"
+        ));
+    }
+    if !label
+        .chars()
+        .all(|c| c.is_ascii_alphanumeric() || matches!(c, ' ' | '-' | '_' | '.' | ':' | '/'))
+    {
+        return Err("label may only contain letters, digits, spaces and - _ . : /".to_string());
+    }
+    let provider_names: Vec<String> = state.gateway.cfg.read().providers.keys().cloned().collect();
+    let looks_like_secret = provider_names
+        .iter()
+        .filter_map(|name| state.secrets.get_provider_key(name))
+        .chain(state.secrets.get_gateway_token())
+        .map(|secret| secret.trim().to_string())
+        .any(|secret| !secret.is_empty() && label.contains(&secret));
+    if looks_like_secret {
+        return Err("label must not contain secrets".to_string());
+    }
+    Ok(label.to_string())
+}
+
+fn set_usage_label_impl(state: &app_state::AppState, label: &str) -> Result<(), String> {
+    let label = validate_usage_label(state, label)?;
+    let prev = {
+        let mut cfg = state.gateway.cfg.write();
+        std::mem::replace(&mut cfg.routing.usage_label, Some(label.clone()))
+    };
+    if let Err(err) = persist_config_for_app_state(state) {
+        state.gateway.cfg.write().routing.usage_label = prev;
+        return Err(err.to_string());
+    }
+    state.gateway.store.events().config().usage_label_updated(
+        "gateway",
+        "usage_label updated",
+        serde_json::json!({ "label": label }),
+    );
+    Ok(())
+}
+
+fn clear_usage_label_impl(state: &app_state::AppState) -> Result<(), String> {
+    let prev = state.gateway.cfg.write().routing.usage_label.take();
+    if prev.is_none() {
+        return Ok(());
+    }
+    if let Err(err) = persist_config_for_app_state(state) {
+        state.gateway.cfg.write().routing.usage_label = prev;
+        return Err(err.to_string());
+    }
+    state.gateway.store.events().config().usage_label_cleared(
+        "gateway",
+        "usage_label cleared",
+        serde_json::Value::Null,
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn set_usage_label(
+    state: tauri::State<'_, app_state::AppState>,
+    label: String,
+) -> Result<(), CommandError> {
+    set_usage_label_impl(&state, &label).map_err(CommandError::from)
+}
+
+#[tauri::command]
+pub(crate) fn clear_usage_label(
+    state: tauri::State<'_, app_state::AppState>,
+) -> Result<(), CommandError> {
+    clear_usage_label_impl(&state).map_err(CommandError::from)
 }
 
 fn session_is_agent(state: &app_state::AppState, codex_session_id: &str) -> bool {
@@ -1223,7 +1665,7 @@ fn session_is_agent(state: &app_state::AppState, codex_session_id: &str) -> bool
 fn set_session_preferred_provider_impl(
     state: &app_state::AppState,
     session_id: String,
-    provider: String,
+    providers: Vec<String>,
 ) -> Result<(), String> {
     // Canonical session identity: Codex session id (from request headers), not WT_SESSION.
     let codex_session_id = session_id.trim().to_string();
@@ -1233,34 +1675,53 @@ fn set_session_preferred_provider_impl(
     if session_is_agent(state, &codex_session_id) {
         return Err("agent sessions cannot set preferred provider".to_string());
     }
-    let prev_provider: Option<String> = {
-        let mut cfg = state.gateway.cfg.write();
-        if !cfg.providers.contains_key(&provider) {
-            return Err(format!("unknown provider: {provider}"));
+    let mut next_providers: Vec<String> = Vec::with_capacity(providers.len());
+    for provider in providers {
+        let provider = provider.trim().to_string();
+        if !provider.is_empty() && !next_providers.contains(&provider) {
+            next_providers.push(provider);
         }
-        if cfg.providers.get(&provider).is_some_and(|p| p.disabled) {
-            return Err(format!("provider is deactivated: {provider}"));
+    }
+    if next_providers.is_empty() {
+        return Err("at least one provider is required".to_string());
+    }
+    let prev_providers: Option<Vec<String>> = {
+        let mut cfg = state.gateway.cfg.write();
+        for provider in &next_providers {
+            if !cfg.providers.contains_key(provider) {
+                return Err(format!("unknown provider: {provider}"));
+            }
+            if cfg.providers.get(provider).is_some_and(|p| p.disabled) {
+                return Err(format!("provider is deactivated: {provider}"));
+            }
+            if cfg
+                .providers
+                .get(provider)
+                .is_some_and(|p| !p.routing_eligible)
+            {
+                return Err(format!("provider is usage-only: {provider}"));
+            }
         }
         let prev = cfg
             .routing
             .session_preferred_providers
             .get(&codex_session_id)
             .cloned();
-        // No-op: avoid emitting confusing events when the user selects the same provider again.
-        if prev.as_deref() == Some(provider.as_str()) {
+        // No-op: avoid emitting confusing events when the user selects the same providers again.
+        if prev.as_ref() == Some(&next_providers) {
             return Ok(());
         }
         cfg.routing
             .session_preferred_providers
-            .insert(codex_session_id.clone(), provider.clone());
+            .insert(codex_session_id.clone(), next_providers.clone());
         prev
     };
     if let Err(e) = persist_config_for_app_state(state) {
         let mut cfg = state.gateway.cfg.write();
-        if let Some(prev) = prev_provider.as_deref() {
+        if let Some(prev) = prev_providers.clone() {
             cfg.routing
                 .session_preferred_providers
-                .insert(codex_session_id.clone(), prev.to_string());
+                .insert(codex_session_id.clone(), prev);
         } else {
             cfg.routing
                 .session_preferred_providers
@@ -1277,17 +1738,29 @@ fn set_session_preferred_provider_impl(
         .last_used_by_session
         .write()
         .remove(&codex_session_id);
-    let msg = match prev_provider.as_deref() {
-        Some(prev) => format!("session preferred_provider updated: {prev} -> {provider}"),
-        None => format!("session preferred_provider set: {provider}"),
+    crate::orchestrator::gateway::persist_last_used_routes(&state.gateway);
+    state.gateway.store.record_config_audit(
+        "set_session_preferred_provider",
+        &format!("routing.session_preferred_providers.{codex_session_id}"),
+        serde_json::json!(prev_providers),
+        serde_json::json!(next_providers),
+    );
+    let next_label = next_providers.join(" > ");
+    let msg = match prev_providers.as_ref() {
+        Some(prev) => format!(
+            "session preferred_provider updated: {} -> {next_label}",
+            prev.join(" > ")
+        ),
+        None => format!("session preferred_provider set: {next_label}"),
     };
     state.gateway.store.events().config().session_preferred_provider_updated(
-        &provider,
+        &next_providers[0],
         &msg,
         serde_json::json!({
             "codex_session_id": codex_session_id,
-            "provider": provider,
-            "prev_provider": prev_provider,
+            "provider": next_providers[0],
+            "providers": next_providers,
+            "prev_providers": prev_providers,
         }),
     );
     Ok(())
@@ -1297,9 +1770,9 @@ fn set_session_preferred_provider_impl(
 pub(crate) fn set_session_preferred_provider(
     state: tauri::State<'_, app_state::AppState>,
     session_id: String,
-    provider: String,
-) -> Result<(), String> {
-    set_session_preferred_provider_impl(&state, session_id, provider)
+    providers: Vec<String>,
+) -> Result<(), CommandError> {
+    set_session_preferred_provider_impl(&state, session_id, providers).map_err(CommandError::from)
 }
 
 fn clear_session_preferred_provider_impl(
@@ -1310,26 +1783,24 @@ fn clear_session_preferred_provider_impl(
     if codex_session_id.is_empty() {
         return Err("codex_session_id is required".to_string());
     }
-    let prev_provider: Option<String> = {
+    let prev_providers: Option<Vec<String>> = {
         let mut cfg = state.gateway.cfg.write();
         cfg.routing
             .session_preferred_providers
             .remove(&codex_session_id)
     };
     // No-op: don't write config or emit events if nothing was set.
-    if prev_provider.is_none() {
+    let Some(prev_providers) = prev_providers else {
         return Ok(());
-    }
+    };
     if let Err(e) = persist_config_for_app_state(state) {
-        if let Some(prev) = prev_provider.as_deref() {
-            state
-                .gateway
-                .cfg
-                .write()
-                .routing
-                .session_preferred_providers
-                .insert(codex_session_id.clone(), prev.to_string());
-        }
+        state
+            .gateway
+            .cfg
+            .write()
+            .routing
+            .session_preferred_providers
+            .insert(codex_session_id.clone(), prev_providers);
         return Err(e.to_string());
     }
     state
@@ -1341,15 +1812,22 @@ fn clear_session_preferred_provider_impl(
         .last_used_by_session
         .write()
         .remove(&codex_session_id);
+    crate::orchestrator::gateway::persist_last_used_routes(&state.gateway);
+    state.gateway.store.record_config_audit(
+        "clear_session_preferred_provider",
+        &format!("routing.session_preferred_providers.{codex_session_id}"),
+        serde_json::json!(prev_providers),
+        serde_json::Value::Null,
+    );
     state.gateway.store.events().config().session_preferred_provider_cleared(
         "gateway",
         &format!(
             "session preferred_provider cleared (was {})",
-            prev_provider.as_deref().unwrap_or("unknown")
+            prev_providers.join(" > ")
         ),
         serde_json::json!({
             "codex_session_id": codex_session_id,
-            "prev_provider": prev_provider,
+            "prev_providers": prev_providers,
         }),
     );
     Ok(())
@@ -1359,8 +1837,69 @@ fn clear_session_preferred_provider_impl(
 pub(crate) fn clear_session_preferred_provider(
     state: tauri::State<'_, app_state::AppState>,
     session_id: String,
+) -> Result<(), CommandError> {
+    clear_session_preferred_provider_impl(&state, session_id).map_err(CommandError::from)
+}
+
+fn forget_client_session_impl(
+    state: &app_state::AppState,
+    codex_session_id: String,
+    clear_preference: bool,
 ) -> Result<(), String> {
-    clear_session_preferred_provider_impl(&state, session_id)
+    let codex_session_id = codex_session_id.trim().to_string();
+    if codex_session_id.is_empty() {
+        return Err("codex_session_id is required".to_string());
+    }
+    let Some(removed) = state
+        .gateway
+        .client_sessions
+        .write()
+        .remove(&codex_session_id)
+    else {
+        return Err(format!("unknown client session: {codex_session_id}"));
+    };
+    let preference_cleared = clear_preference
+        && state
+            .gateway
+            .cfg
+            .read()
+            .routing
+            .session_preferred_providers
+            .contains_key(&codex_session_id);
+    if preference_cleared {
+        clear_session_preferred_provider_impl(state, codex_session_id.clone())?;
+    }
+    if state
+        .gateway
+        .last_used_by_session
+        .write()
+        .remove(&codex_session_id)
+        .is_some()
+    {
+        crate::orchestrator::gateway::persist_last_used_routes(&state.gateway);
+    }
+    state.gateway.store.events().emit(
+        "gateway",
+        crate::orchestrator::store::EventCode::SESSION_FORGOTTEN,
+        &format!("client session forgotten: {codex_session_id}"),
+        serde_json::json!({
+            "codex_session_id": codex_session_id,
+            "pid": removed.pid,
+            "wt_session": removed.wt_session,
+            "preference_cleared": preference_cleared,
+        }),
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn forget_client_session(
+    state: tauri::State<'_, app_state::AppState>,
+    codex_session_id: String,
+    clear_preference: Option<bool>,
+) -> Result<(), CommandError> {
+    forget_client_session_impl(&state, codex_session_id, clear_preference.unwrap_or(false))
+        .map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -1370,17 +1909,74 @@ pub(crate) fn upsert_provider(
     display_name: String,
     base_url: String,
     group: Option<Option<String>>,
-) -> Result<(), String> {
-    upsert_provider_impl(&state, name, display_name, base_url, group)
+    template: Option<String>,
+) -> Result<(), CommandError> {
+    upsert_provider_impl(&state, name, display_name, base_url, group, template)
+        .map_err(CommandError::from)
 }
 
-fn upsert_provider_impl(
-    state: &app_state::AppState,
-    name: String,
-    display_name: String,
-    base_url: String,
-    group: Option<Option<String>>,
-) -> Result<(), String> {
+fn non_empty_trimmed(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    (!trimmed.is_empty()).then(|| trimmed.to_string())
+}
+
+/// Endpoint paths the gateway appends to a base URL itself; a base URL that already ends in one
+/// of them yields doubled paths such as `/v1/responses/responses`.
+const BASE_URL_ENDPOINT_SUFFIXES: &[&str] =
+    &["/responses", "/chat/completions", "/models", "/v1/v1"];
+
+/// Validates an `http(s)://` base URL and returns it trimmed, without trailing slashes.
+fn normalize_base_url(field: &str, raw: &str) -> Result<String, String> {
+    let trimmed = raw.trim().trim_end_matches('/');
+    if trimmed.is_empty() {
+        return Err(format!("{field} is required"));
+    }
+    let invalid = || format!("invalid {field}: expected an http(s):// URL, got {trimmed}");
+    let parsed = reqwest::Url::parse(trimmed).map_err(|_| invalid())?;
+    if !matches!(parsed.scheme(), "http" | "https")
+        || !parsed.host_str().is_some_and(|host| !host.is_empty())
+    {
+        return Err(invalid());
+    }
+    if parsed.query().is_some() || parsed.fragment().is_some() {
+        return Err(format!(
+            "{field} must not include a query string or fragment"
+        ));
+    }
+    Ok(trimmed.to_string())
+}
+
+/// Emits a warning event when a stored base URL ends in a path the gateway appends itself.
+fn warn_on_base_url_endpoint_suffix(
+    state: &app_state::AppState,
+    provider: &str,
+    field: &str,
+    base_url: &str,
+) {
+    let lower = base_url.to_ascii_lowercase();
+    let Some(suffix) = BASE_URL_ENDPOINT_SUFFIXES
+        .iter()
+        .copied()
+        .find(|suffix| lower.ends_with(suffix))
+    else {
+        return;
+    };
+    state.gateway.store.events().emit(
+        provider,
+        crate::orchestrator::store::EventCode::CONFIG_PROVIDER_BASE_URL_SUSPICIOUS,
+        &format!("{field} ends with {suffix}; the gateway appends endpoint paths itself"),
+        serde_json::json!({ "field": field, "url": base_url, "suffix": suffix }),
+    );
+}
+
+fn upsert_provider_impl(
+    state: &app_state::AppState,
+    name: String,
+    display_name: String,
+    base_url: String,
+    group: Option<Option<String>>,
+    template: Option<String>,
+) -> Result<(), String> {
     ensure_local_provider_definitions_editable(state)?;
     if name.trim().is_empty() {
         return Err("name is required".to_string());
@@ -1396,10 +1992,71 @@ fn upsert_provider_impl(
         })
     });
     let base_url_changed;
+    let stored_base_url;
+    let audit_old;
     {
         let mut cfg = state.gateway.cfg.write();
         let existing = cfg.providers.get(&name).cloned();
+        // Routing defaults only fill in new providers; editing one never swaps in a default.
         let is_new = existing.is_none();
+        let template = match template.as_deref().and_then(non_empty_trimmed) {
+            Some(template_name) => Some(
+                cfg.provider_templates
+                    .get(&template_name)
+                    .cloned()
+                    .ok_or_else(|| format!("unknown provider template: {template_name}"))?,
+            ),
+            None => None,
+        };
+        let base_url = non_empty_trimmed(&base_url)
+            .or_else(|| {
+                template
+                    .as_ref()
+                    .and_then(|template| non_empty_trimmed(&template.base_url))
+            })
+            .or_else(|| {
+                cfg.routing
+                    .default_base_url
+                    .as_deref()
+                    .filter(|_| is_new)
+                    .and_then(non_empty_trimmed)
+            })
+            .unwrap_or_default();
+        let base_url = normalize_base_url("base_url", &base_url)?;
+        stored_base_url = base_url.clone();
+        let usage_adapter = template
+            .as_ref()
+            .and_then(|template| non_empty_trimmed(&template.usage_adapter))
+            .or_else(|| {
+                existing
+                    .as_ref()
+                    .and_then(|provider| non_empty_trimmed(&provider.usage_adapter))
+            })
+            .or_else(|| {
+                cfg.routing
+                    .default_usage_adapter
+                    .as_deref()
+                    .filter(|_| is_new)
+                    .and_then(non_empty_trimmed)
+            })
+            .unwrap_or_default();
+        let usage_base_url = template
+            .as_ref()
+            .and_then(|template| template.usage_base_url.as_deref())
+            .and_then(non_empty_trimmed)
+            .or_else(|| {
+                existing
+                    .as_ref()
+                    .and_then(|provider| provider.usage_base_url.clone())
+            });
+        // Headers already on the provider win over the template's.
+        let mut headers = template
+            .map(|template| template.headers)
+            .unwrap_or_default();
+        if let Some(provider) = existing.as_ref() {
+            headers.extend(provider.headers.clone());
+        }
+        audit_old = provider_audit_snapshot(existing.as_ref());
         base_url_changed = existing
             .as_ref()
             .is_some_and(|provider| provider.base_url != base_url);
@@ -1414,16 +2071,33 @@ fn upsert_provider_impl(
                 supports_websockets: existing
                     .as_ref()
                     .is_some_and(|provider| provider.supports_websockets),
-                usage_adapter: existing
+                usage_adapter,
+                usage_base_url,
+                api_key: existing
                     .as_ref()
-                    .map(|provider| provider.usage_adapter.clone())
+                    .map(|provider| provider.api_key.clone())
                     .unwrap_or_default(),
-                usage_base_url: existing
+                model_aliases: existing
                     .as_ref()
-                    .and_then(|provider| provider.usage_base_url.clone()),
-                api_key: existing
+                    .map(|provider| provider.model_aliases.clone())
+                    .unwrap_or_default(),
+                system_prefix: existing
                     .as_ref()
-                    .map(|provider| provider.api_key.clone())
+                    .and_then(|provider| provider.system_prefix.clone()),
+                usage_field_map: existing
+                    .as_ref()
+                    .and_then(|provider| provider.usage_field_map.clone()),
+                headers,
+                routing_eligible: existing
+                    .as_ref()
+                    .map_or(true, |provider| provider.routing_eligible),
+                default_model: existing
+                    .as_ref()
+                    .and_then(|provider| provider.default_model.clone()),
+                tier: existing.as_ref().map_or(0, |provider| provider.tier),
+                notes: existing
+                    .as_ref()
+                    .map(|provider| provider.notes.clone())
                     .unwrap_or_default(),
             },
         );
@@ -1440,6 +2114,7 @@ fn upsert_provider_impl(
     if base_url_changed {
         state.gateway.router.reset_provider_health(&name, unix_ms());
     }
+    warn_on_base_url_endpoint_suffix(state, &name, "base_url", &stored_base_url);
     let patch_payload = provider_definition_patch_payload(state, &name, &name)?;
     if let Err(err) = crate::lan_sync::record_provider_definition_patch(state, &name, patch_payload)
     {
@@ -1449,6 +2124,11 @@ fn upsert_provider_impl(
             serde_json::Value::Null,
         );
     }
+    let audit_new = provider_audit_snapshot(state.gateway.cfg.read().providers.get(&name));
+    state
+        .gateway
+        .store
+        .record_config_audit("upsert_provider", &name, audit_old, audit_new);
     state.gateway.store.events().config().provider_upserted(
         &name,
         "provider upserted",
@@ -1462,7 +2142,7 @@ pub(crate) fn set_provider_supports_websockets(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
     enabled: bool,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     ensure_local_provider_definitions_editable(&state)?;
     let changed = set_provider_supports_websockets_impl(&state, provider.clone(), enabled)?;
     if !changed {
@@ -1527,26 +2207,234 @@ fn set_provider_supports_websockets_impl(
     Ok(true)
 }
 
+#[tauri::command]
+pub(crate) fn set_provider_system_prefix(
+    state: tauri::State<'_, app_state::AppState>,
+    provider: String,
+    text: Option<String>,
+) -> Result<(), CommandError> {
+    ensure_local_provider_definitions_editable(&state)?;
+    let enabled = text
+        .as_deref()
+        .is_some_and(|value| !value.trim().is_empty());
+    let changed = set_provider_system_prefix_impl(&state, provider.clone(), text)?;
+    if !changed {
+        return Ok(());
+    }
+    state
+        .gateway
+        .store
+        .events()
+        .config()
+        .provider_system_prefix_updated(
+            &provider,
+            if enabled {
+                "provider system prefix updated"
+            } else {
+                "provider system prefix cleared"
+            },
+            serde_json::json!({ "enabled": enabled }),
+        );
+    Ok(())
+}
+
+fn set_provider_system_prefix_impl(
+    state: &app_state::AppState,
+    provider: String,
+    text: Option<String>,
+) -> Result<bool, String> {
+    let next = text
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string);
+    let previous = {
+        let mut cfg = state.gateway.cfg.write();
+        let entry = cfg
+            .providers
+            .get_mut(&provider)
+            .ok_or_else(|| format!("unknown provider: {provider}"))?;
+        if entry.system_prefix == next {
+            return Ok(false);
+        }
+        std::mem::replace(&mut entry.system_prefix, next)
+    };
+
+    if let Err(error) = persist_config_for_app_state(state) {
+        let mut cfg = state.gateway.cfg.write();
+        if let Some(entry) = cfg.providers.get_mut(&provider) {
+            entry.system_prefix = previous;
+        }
+        return Err(error.to_string());
+    }
+
+    Ok(true)
+}
+
+#[tauri::command]
+pub(crate) fn set_provider_default_model(
+    state: tauri::State<'_, app_state::AppState>,
+    provider: String,
+    model: Option<String>,
+) -> Result<(), CommandError> {
+    ensure_local_provider_definitions_editable(&state)?;
+    let next = model
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string);
+    let changed = set_provider_default_model_impl(&state, provider.clone(), model)?;
+    if !changed {
+        return Ok(());
+    }
+    state
+        .gateway
+        .store
+        .events()
+        .config()
+        .provider_default_model_updated(
+            &provider,
+            &match next.as_deref() {
+                Some(model) => format!("provider default model set to {model}"),
+                None => "provider default model cleared".to_string(),
+            },
+            serde_json::json!({ "model": next }),
+        );
+    Ok(())
+}
+
+fn set_provider_default_model_impl(
+    state: &app_state::AppState,
+    provider: String,
+    model: Option<String>,
+) -> Result<bool, String> {
+    let next = model
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+        .map(str::to_string);
+    let previous = {
+        let mut cfg = state.gateway.cfg.write();
+        let entry = cfg
+            .providers
+            .get_mut(&provider)
+            .ok_or_else(|| format!("unknown provider: {provider}"))?;
+        if entry.default_model == next {
+            return Ok(false);
+        }
+        std::mem::replace(&mut entry.default_model, next)
+    };
+
+    if let Err(error) = persist_config_for_app_state(state) {
+        let mut cfg = state.gateway.cfg.write();
+        if let Some(entry) = cfg.providers.get_mut(&provider) {
+            entry.default_model = previous;
+        }
+        return Err(error.to_string());
+    }
+
+    Ok(true)
+}
+
+#[tauri::command]
+pub(crate) fn set_provider_notes(
+    state: tauri::State<'_, app_state::AppState>,
+    provider: String,
+    text: String,
+) -> Result<(), CommandError> {
+    ensure_local_provider_definitions_editable(&state)?;
+    let changed = set_provider_notes_impl(&state, provider.clone(), text)?;
+    if !changed {
+        return Ok(());
+    }
+    let has_notes = state
+        .gateway
+        .cfg
+        .read()
+        .providers
+        .get(&provider)
+        .is_some_and(|entry| !entry.notes.is_empty());
+    state
+        .gateway
+        .store
+        .events()
+        .config()
+        .provider_notes_updated(
+            &provider,
+            if has_notes {
+                "provider notes updated"
+            } else {
+                "provider notes cleared"
+            },
+            serde_json::json!({ "has_notes": has_notes }),
+        );
+    Ok(())
+}
+
+/// Notes land in config.toml, so text containing the provider's stored key or usage token is
+/// rejected rather than persisted.
+fn set_provider_notes_impl(
+    state: &app_state::AppState,
+    provider: String,
+    text: String,
+) -> Result<bool, String> {
+    let next = text.trim().to_string();
+    let leaks_secret = [
+        state.secrets.get_provider_key(&provider),
+        state.secrets.get_usage_token(&provider),
+    ]
+    .into_iter()
+    .flatten()
+    .map(|secret| secret.trim().to_string())
+    .any(|secret| !secret.is_empty() && next.contains(&secret));
+    let previous = {
+        let mut cfg = state.gateway.cfg.write();
+        let entry = cfg
+            .providers
+            .get_mut(&provider)
+            .ok_or_else(|| format!("unknown provider: {provider}"))?;
+        if leaks_secret {
+            return Err("notes must not contain the provider key or usage token".to_string());
+        }
+        if entry.notes == next {
+            return Ok(false);
+        }
+        std::mem::replace(&mut entry.notes, next)
+    };
+
+    if let Err(error) = persist_config_for_app_state(state) {
+        let mut cfg = state.gateway.cfg.write();
+        if let Some(entry) = cfg.providers.get_mut(&provider) {
+            entry.notes = previous;
+        }
+        return Err(error.to_string());
+    }
+
+    Ok(true)
+}
+
 #[tauri::command]
 pub(crate) fn set_provider_disabled(
     state: tauri::State<'_, app_state::AppState>,
     name: String,
     disabled: bool,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     ensure_local_provider_definitions_editable(&state)?;
     let mut switched_preferred = false;
     {
         let mut cfg = state.gateway.cfg.write();
         let current_disabled = match cfg.providers.get(&name) {
             Some(provider) => provider.disabled,
-            None => return Err(format!("unknown provider: {name}")),
+            None => return Err(CommandError::unknown_provider(&name)),
         };
         if current_disabled == disabled {
             return Ok(());
         }
 
         if disabled && cfg.providers.values().filter(|p| !p.disabled).count() <= 1 {
-            return Err("cannot deactivate the last active provider".to_string());
+            return Err(CommandError::Validation(
+                "cannot deactivate the last active provider".to_string(),
+            ));
         }
 
         if let Some(provider) = cfg.providers.get_mut(&name) {
@@ -1555,8 +2443,7 @@ pub(crate) fn set_provider_disabled(
 
         if disabled {
             cfg.routing
-                .session_preferred_providers
-                .retain(|_, pref| pref != &name);
+                .retain_session_preferred_providers(|pref| pref != name);
 
             if cfg.routing.preferred_provider == name {
                 let fallback = cfg
@@ -1589,14 +2476,26 @@ pub(crate) fn set_provider_disabled(
         app_state::normalize_provider_order(&mut cfg);
     }
 
-    if disabled {
+    let override_cleared_by_disable = disabled && {
         let mut manual = state.gateway.router.manual_override.write();
-        if manual.as_deref() == Some(name.as_str()) {
+        let matches = manual.as_deref() == Some(name.as_str());
+        if matches {
             *manual = None;
         }
+        matches
+    };
+    if override_cleared_by_disable {
+        let active_provider = active_serving_provider(&state);
+        emit_manual_override_changed(
+            &state,
+            Some(name.as_str()),
+            None,
+            active_provider.as_deref(),
+            serde_json::json!({ "reason": "provider_disabled" }),
+        );
     }
 
-    persist_config(&state).map_err(|e| e.to_string())?;
+    persist_config(&state)?;
     if disabled {
         let _ = clear_observed_session_routes_for_provider(&state, &name);
     }
@@ -1639,7 +2538,7 @@ pub(crate) fn set_provider_group(
     state: tauri::State<'_, app_state::AppState>,
     name: String,
     group: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     ensure_local_provider_definitions_editable(&state)?;
     let (changed, normalized_group) = set_provider_group_impl(&state, name.clone(), group)?;
     if !changed {
@@ -1773,7 +2672,7 @@ pub(crate) fn set_providers_group(
     state: tauri::State<'_, app_state::AppState>,
     providers: Vec<String>,
     group: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     ensure_local_provider_definitions_editable(&state)?;
     let (updated, normalized_group) = set_providers_group_impl(&state, providers, group)?;
     if updated.is_empty() {
@@ -1804,7 +2703,7 @@ pub(crate) fn set_providers_group(
 pub(crate) fn delete_provider(
     state: tauri::State<'_, app_state::AppState>,
     name: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let next_preferred = delete_provider_impl(&state, &name)?;
     state.gateway.store.events().config().provider_deleted(
         &name,
@@ -1826,7 +2725,7 @@ fn delete_provider_impl(
     name: &str,
 ) -> Result<Option<String>, String> {
     ensure_local_provider_definitions_editable(state)?;
-    let (next_preferred, next_cfg) = {
+    let (next_preferred, next_cfg, mut audit_old) = {
         let mut cfg = state.gateway.cfg.write();
         if !cfg.providers.contains_key(name) {
             return Err(format!("unknown provider: {name}"));
@@ -1836,29 +2735,47 @@ fn delete_provider_impl(
         }
 
         let preferred_after_delete = next_preferred_after_delete(&cfg, name)?;
+        let audit_old = provider_audit_snapshot(cfg.providers.get(name));
 
         cfg.providers.remove(name);
         cfg.provider_order.retain(|p| p != name);
         cfg.routing
-            .session_preferred_providers
-            .retain(|_, pref| pref != name);
+            .retain_session_preferred_providers(|pref| pref != name);
         app_state::normalize_provider_order(&mut cfg);
 
         let next_preferred = preferred_after_delete.clone();
         if let Some(p) = preferred_after_delete {
             cfg.routing.preferred_provider = p;
         }
-        (next_preferred, cfg.clone())
+        (next_preferred, cfg.clone(), audit_old)
     };
+    if let Some(obj) = audit_old.as_object_mut() {
+        obj.insert(
+            "key".to_string(),
+            provider_key_audit_value(state.secrets.get_provider_key(name).as_deref()),
+        );
+    }
 
     // Keep the tombstone write before secrets.delete_provider(name), otherwise the original
     // shared provider id is gone and LAN peers may receive a tombstone under the wrong entity id.
     // If the deleted provider was manually locked, return to auto.
-    {
+    let override_cleared_by_delete = {
         let mut mo = state.gateway.router.manual_override.write();
-        if mo.as_deref() == Some(name) {
+        let matches = mo.as_deref() == Some(name);
+        if matches {
             *mo = None;
         }
+        matches
+    };
+    if override_cleared_by_delete {
+        let active_provider = active_serving_provider(state);
+        emit_manual_override_changed(
+            state,
+            Some(name),
+            None,
+            active_provider.as_deref(),
+            serde_json::json!({ "reason": "provider_deleted" }),
+        );
     }
     if let Err(err) = crate::lan_sync::record_provider_definition_tombstone(state, name) {
         state.gateway.store.events().lan().edit_sync_record_failed(
@@ -1871,6 +2788,12 @@ fn delete_provider_impl(
     persist_config_for_app_state(state).map_err(|e| e.to_string())?;
     state.gateway.router.sync_with_config(&next_cfg, unix_ms());
     let _ = clear_observed_session_routes_for_provider(state, name);
+    state.gateway.store.record_config_audit(
+        "delete_provider",
+        name,
+        audit_old,
+        serde_json::Value::Null,
+    );
     Ok(next_preferred)
 }
 
@@ -1961,12 +2884,12 @@ pub(crate) fn rename_provider(
     state: tauri::State<'_, app_state::AppState>,
     old_name: String,
     new_name: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     ensure_local_provider_definitions_editable(&state)?;
     let old = old_name.trim();
     let new = new_name.trim();
     if old.is_empty() || new.is_empty() {
-        return Err("name is required".to_string());
+        return Err(CommandError::Validation("name is required".to_string()));
     }
     if old == new {
         return Ok(());
@@ -1975,13 +2898,15 @@ pub(crate) fn rename_provider(
     {
         let mut cfg = state.gateway.cfg.write();
         if !cfg.providers.contains_key(old) {
-            return Err(format!("unknown provider: {old}"));
+            return Err(CommandError::unknown_provider(old));
         }
         if cfg.providers.contains_key(new) {
-            return Err(format!("provider already exists: {new}"));
+            return Err(CommandError::Validation(format!(
+                "provider already exists: {new}"
+            )));
         }
         if !app_state::migrate_provider_name(&mut cfg, old, new) {
-            return Err("rename failed".to_string());
+            return Err(CommandError::Internal("rename failed".to_string()));
         }
         if let Some(p) = cfg.providers.get_mut(new) {
             p.display_name = new.to_string();
@@ -1989,7 +2914,12 @@ pub(crate) fn rename_provider(
         if cfg.routing.preferred_provider == old {
             cfg.routing.preferred_provider = new.to_string();
         }
-        for (_session, pref) in cfg.routing.session_preferred_providers.iter_mut() {
+        for pref in cfg
+            .routing
+            .session_preferred_providers
+            .values_mut()
+            .flatten()
+        {
             if pref == old {
                 *pref = new.to_string();
             }
@@ -2011,7 +2941,7 @@ pub(crate) fn rename_provider(
 
     state.gateway.store.rename_provider(old, new);
     state.secrets.rename_provider(old, new)?;
-    persist_config(&state).map_err(|e| e.to_string())?;
+    persist_config(&state)?;
     let renamed_observed_session_routes =
         rename_observed_session_routes_provider_refs(&state, old, new);
     state
@@ -2038,6 +2968,12 @@ pub(crate) fn rename_provider(
             serde_json::Value::Null,
         );
     }
+    state.gateway.store.record_config_audit(
+        "rename_provider",
+        new,
+        serde_json::json!({ "name": old }),
+        serde_json::json!({ "name": new }),
+    );
     state.gateway.store.events().config().provider_renamed(
         new,
         "provider renamed",
@@ -2048,52 +2984,245 @@ pub(crate) fn rename_provider(
     Ok(())
 }
 
+#[tauri::command]
+pub(crate) fn merge_providers(
+    state: tauri::State<'_, app_state::AppState>,
+    source: String,
+    target: String,
+) -> Result<(), CommandError> {
+    let (source, target) = (source.trim(), target.trim());
+    let moved_session_routes = merge_providers_impl(&state, source, target)?;
+    state.gateway.store.events().config().providers_merged(
+        target,
+        "providers merged",
+        serde_json::json!({
+            "source": source,
+            "target": target,
+            "moved_observed_session_routes": moved_session_routes,
+        }),
+    );
+    Ok(())
+}
+
+/// Folds `source` history into `target`, points every config reference at `target`, then
+/// deletes `source`. Returns the number of observed session routes moved over.
+fn merge_providers_impl(
+    state: &app_state::AppState,
+    source: &str,
+    target: &str,
+) -> Result<usize, String> {
+    ensure_local_provider_definitions_editable(state)?;
+    if source.is_empty() || target.is_empty() {
+        return Err("name is required".to_string());
+    }
+    if source == target {
+        return Err("cannot merge a provider into itself".to_string());
+    }
+
+    {
+        let mut cfg = state.gateway.cfg.write();
+        for name in [source, target] {
+            if !cfg.providers.contains_key(name) {
+                return Err(format!("unknown provider: {name}"));
+            }
+        }
+        next_preferred_after_delete(&cfg, source)?;
+        if cfg.routing.preferred_provider == source {
+            cfg.routing.preferred_provider = target.to_string();
+        }
+        for prefs in cfg.routing.session_preferred_providers.values_mut() {
+            let mut seen = std::collections::HashSet::new();
+            for pref in prefs.iter_mut() {
+                if pref == source {
+                    *pref = target.to_string();
+                }
+            }
+            prefs.retain(|pref| seen.insert(pref.clone()));
+        }
+    }
+
+    {
+        let mut mo = state.gateway.router.manual_override.write();
+        if mo.as_deref() == Some(source) {
+            *mo = Some(target.to_string());
+        }
+    }
+
+    state.gateway.store.merge_provider(source, target);
+    let moved_session_routes = rename_observed_session_routes_provider_refs(state, source, target);
+    delete_provider_impl(state, source)?;
+    state.gateway.store.record_config_audit(
+        "merge_providers",
+        target,
+        serde_json::json!({ "name": source }),
+        serde_json::json!({ "name": target }),
+    );
+    Ok(moved_session_routes)
+}
+
 #[tauri::command]
 pub(crate) fn set_provider_key(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
     key: String,
     storage_mode: Option<String>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     ensure_local_provider_definitions_editable(&state)?;
     if !state.gateway.cfg.read().providers.contains_key(&provider) {
-        return Err(format!("unknown provider: {provider}"));
+        return Err(CommandError::unknown_provider(&provider));
     }
     let normalized_storage_mode = storage_mode
         .as_deref()
         .map(str::trim)
         .filter(|value| !value.is_empty());
-    let storage_mode_for_event = normalized_storage_mode.unwrap_or("auth_json").to_string();
-    state
-        .secrets
-        .set_provider_key_with_storage_mode(&provider, &key, normalized_storage_mode)?;
-    if let Err(e) =
-        crate::provider_switchboard::sync_active_provider_target_for_key(&state, &provider)
-    {
-        state.gateway.store.events().codex().provider_switchboard_sync_failed(
-            &provider,
+    update_provider_key(
+        &state,
+        &provider,
+        &key,
+        normalized_storage_mode,
+        "set_provider_key",
+    )
+}
+
+#[tauri::command]
+pub(crate) fn rotate_provider_key(
+    state: tauri::State<'_, app_state::AppState>,
+    provider: String,
+    new_key: String,
+) -> Result<serde_json::Value, CommandError> {
+    ensure_local_provider_definitions_editable(&state)?;
+    if !state.gateway.cfg.read().providers.contains_key(&provider) {
+        return Err(CommandError::unknown_provider(&provider));
+    }
+    let new_key = new_key.trim();
+    if new_key.is_empty() {
+        return Err(CommandError::Validation("new_key is required".to_string()));
+    }
+    let previous_key =
+        normalized_provider_key(state.secrets.get_provider_key(&provider).as_deref());
+    if previous_key.as_deref() == Some(new_key) {
+        return Err(CommandError::Validation(
+            "new_key matches the current provider key".to_string(),
+        ));
+    }
+    let storage_mode = state.secrets.get_provider_key_storage_mode(&provider);
+    update_provider_key(
+        &state,
+        &provider,
+        new_key,
+        Some(storage_mode.as_str()),
+        "rotate_provider_key",
+    )?;
+    if let Some(previous_key) = previous_key.as_deref() {
+        state.secrets.record_provider_key_rotation(
+            &provider,
+            mask_key_preview(previous_key),
+            unix_ms(),
+        )?;
+    }
+    Ok(provider_key_history_value(&state, &provider))
+}
+
+#[tauri::command]
+pub(crate) fn get_provider_key_history(
+    state: tauri::State<'_, app_state::AppState>,
+    provider: String,
+) -> Result<serde_json::Value, CommandError> {
+    if !state.gateway.cfg.read().providers.contains_key(&provider) {
+        return Err(CommandError::unknown_provider(&provider));
+    }
+    Ok(provider_key_history_value(&state, &provider))
+}
+
+/// Masked previews of retired keys, newest first. Raw previous keys are never retained.
+fn provider_key_history_value(state: &app_state::AppState, provider: &str) -> serde_json::Value {
+    let history = state
+        .secrets
+        .provider_key_history(provider)
+        .into_iter()
+        .map(|entry| {
+            serde_json::json!({
+                "preview": entry.preview,
+                "rotated_at_unix_ms": entry.rotated_at_unix_ms,
+            })
+        })
+        .collect::<Vec<_>>();
+    serde_json::json!({
+        "provider": provider,
+        "history": history,
+    })
+}
+
+fn update_provider_key(
+    state: &tauri::State<'_, app_state::AppState>,
+    provider: &str,
+    key: &str,
+    storage_mode: Option<&str>,
+    audit_action: &str,
+) -> Result<(), CommandError> {
+    let storage_mode_for_event = storage_mode.unwrap_or("auth_json").to_string();
+    let audit_old = provider_key_audit_value(state.secrets.get_provider_key(provider).as_deref());
+    let old_key_ref = provider_api_key_ref(&state.secrets, provider);
+    state
+        .secrets
+        .set_provider_key_with_storage_mode(provider, key, storage_mode)?;
+    let new_key_ref = provider_api_key_ref(&state.secrets, provider);
+    if let Some((closed, next)) = state.secrets.split_active_package_period_for_key(
+        provider,
+        &old_key_ref,
+        &new_key_ref,
+        unix_ms(),
+    )? {
+        state.gateway.store.events().config().pricing_period_split(
+            provider,
+            "package period split on provider key change",
+            serde_json::json!({
+                "closed_period_id": closed.id,
+                "closed_api_key_ref": closed.api_key_ref,
+                "new_period_id": next.id,
+                "new_api_key_ref": next.api_key_ref,
+                "split_at_unix_ms": next.started_at_unix_ms,
+            }),
+        );
+    }
+    if let Err(e) =
+        crate::provider_switchboard::sync_active_provider_target_for_key(state, provider)
+    {
+        state.gateway.store.events().codex().provider_switchboard_sync_failed(
+            provider,
             &format!("provider key sync to active switchboard target failed: {e}"),
             serde_json::json!({
-                "provider": provider.clone(),
+                "provider": provider,
             }),
         );
     }
     if let Err(err) = crate::lan_sync::record_provider_definition_patch(
-        &state,
-        &provider,
+        state,
+        provider,
         serde_json::json!({
             "key": key,
             "key_storage": storage_mode_for_event.clone(),
         }),
     ) {
         state.gateway.store.events().lan().edit_sync_record_failed(
-            &provider,
+            provider,
             &format!("failed to record provider key update for LAN sync: {err}"),
             serde_json::Value::Null,
         );
     }
+    let mut audit_new = provider_key_audit_value(Some(key));
+    if let Some(obj) = audit_new.as_object_mut() {
+        obj.insert(
+            "storage_mode".to_string(),
+            serde_json::json!(storage_mode_for_event),
+        );
+    }
+    state
+        .gateway
+        .store
+        .record_config_audit(audit_action, provider, audit_old, audit_new);
     state.gateway.store.events().config().provider_key_updated(
-        &provider,
+        provider,
         "provider key updated",
         serde_json::json!({
             "storage_mode": storage_mode_for_event
@@ -2106,15 +3235,15 @@ pub(crate) fn set_provider_key(
 pub(crate) fn set_provider_order(
     state: tauri::State<'_, app_state::AppState>,
     order: Vec<String>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     ensure_local_provider_definitions_editable(&state)?;
-    let ordered_providers = {
+    let (previous_order, ordered_providers) = {
         let mut cfg = state.gateway.cfg.write();
-        cfg.provider_order = order;
+        let previous_order = std::mem::replace(&mut cfg.provider_order, order);
         app_state::normalize_provider_order(&mut cfg);
-        cfg.provider_order.clone()
+        (previous_order, cfg.provider_order.clone())
     };
-    persist_config(&state).map_err(|e| e.to_string())?;
+    persist_config(&state)?;
     for (index, provider_name) in ordered_providers.iter().enumerate() {
         if let Err(err) = crate::lan_sync::record_provider_definition_patch(
             &state,
@@ -2130,6 +3259,12 @@ pub(crate) fn set_provider_order(
             );
         }
     }
+    state.gateway.store.record_config_audit(
+        "set_provider_order",
+        "provider_order",
+        serde_json::json!(previous_order),
+        serde_json::json!(ordered_providers),
+    );
     state.gateway.store.events().config().provider_order_updated(
         "-",
         "provider order updated",
@@ -2142,9 +3277,9 @@ pub(crate) fn set_provider_order(
 pub(crate) fn get_provider_key(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
-) -> Result<Option<String>, String> {
+) -> Result<Option<String>, CommandError> {
     if !state.gateway.cfg.read().providers.contains_key(&provider) {
-        return Err(format!("unknown provider: {provider}"));
+        return Err(CommandError::unknown_provider(&provider));
     }
     Ok(state.secrets.get_provider_key(&provider))
 }
@@ -2153,11 +3288,12 @@ pub(crate) fn get_provider_key(
 pub(crate) fn clear_provider_key(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     ensure_local_provider_definitions_editable(&state)?;
     if !state.gateway.cfg.read().providers.contains_key(&provider) {
-        return Err(format!("unknown provider: {provider}"));
+        return Err(CommandError::unknown_provider(&provider));
     }
+    let audit_old = provider_key_audit_value(state.secrets.get_provider_key(&provider).as_deref());
     state.secrets.clear_provider_key(&provider)?;
     if let Err(err) = crate::lan_sync::record_provider_definition_patch(
         &state,
@@ -2170,6 +3306,12 @@ pub(crate) fn clear_provider_key(
             serde_json::Value::Null,
         );
     }
+    state.gateway.store.record_config_audit(
+        "clear_provider_key",
+        &provider,
+        audit_old,
+        provider_key_audit_value(None),
+    );
     state.gateway.store.events().config().provider_key_cleared(
         &provider,
         "provider key cleared (user-data/secrets.json)",
@@ -2182,19 +3324,23 @@ pub(crate) fn clear_provider_key(
 mod provider_management_tests {
     use super::{
         clear_followed_config_source_impl, clear_session_preferred_provider_impl,
-        copy_provider_from_config_source_impl, current_local_provider_state_snapshot,
-        delete_provider_impl, ensure_local_provider_definitions_editable,
+        clear_usage_label_impl, copy_provider_from_config_source_impl,
+        current_local_provider_state_snapshot, delete_provider_impl,
+        ensure_local_provider_definitions_editable, explain_routing_impl, exported_config_toml,
+        forget_client_session_impl, gateway_token_previews, merge_providers_impl,
         next_preferred_after_delete, offline_followed_config_source_snapshot,
-        persist_followed_config_source_change,
-        provider_definition_patch_payload, LocalCopyState, rename_observed_session_routes_provider_refs,
-        set_followed_config_source_impl, set_manual_override_impl, set_provider_group_impl,
-        set_provider_supports_websockets_impl, set_route_mode_impl, set_providers_group_impl,
-        set_session_preferred_provider_impl, upsert_provider_impl,
+        persist_followed_config_source_change, provider_definition_patch_payload,
+        rename_observed_session_routes_provider_refs, serialized_config_toml,
+        set_followed_config_source_impl, set_manual_override_impl, set_provider_default_model_impl,
+        set_provider_group_impl, set_provider_notes_impl, set_provider_supports_websockets_impl,
+        set_provider_system_prefix_impl, set_providers_group_impl, set_route_mode_impl,
+        set_session_preferred_provider_impl, set_usage_label_impl, upsert_provider_impl,
+        LocalCopyState,
     };
     use crate::app_state::AppState;
     use crate::constants::GATEWAY_MODEL_PROVIDER_ID;
     use crate::orchestrator::config::AppConfig;
-    use crate::orchestrator::gateway::{ClientSessionRuntime, LastUsedRoute};
+    use crate::orchestrator::gateway::{ClientSessionRuntime, LastUsedRoute, RouteReason};
     use crate::orchestrator::store::unix_ms;
 
     fn build_test_state() -> (tempfile::TempDir, AppState) {
@@ -2223,6 +3369,8 @@ mod provider_management_tests {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
     }
@@ -2232,7 +3380,7 @@ mod provider_management_tests {
             session_id.to_string(),
             LastUsedRoute {
                 provider: provider.to_string(),
-                reason: "seed".to_string(),
+                reason: RouteReason::PreferredHealthy,
                 preferred: preferred.to_string(),
                 unix_ms: unix_ms(),
             },
@@ -2274,6 +3422,26 @@ mod provider_management_tests {
             .expect("upsert remote snapshot");
     }
 
+    #[test]
+    fn gateway_token_previews_are_masked_and_drop_revoked_tokens() {
+        let (_tmp, state) = build_test_state();
+        let laptop = state
+            .secrets
+            .create_named_gateway_token("laptop")
+            .expect("create token");
+
+        let previews = gateway_token_previews(&state);
+        let preview = previews.get("laptop").expect("laptop preview");
+        assert_ne!(preview, &laptop.token);
+        assert!(!preview.contains(&laptop.token));
+
+        state
+            .secrets
+            .revoke_named_gateway_token("laptop")
+            .expect("revoke token");
+        assert!(!gateway_token_previews(&state).contains_key("laptop"));
+    }
+
     #[test]
     fn delete_provider_rejects_removing_last_active_provider() {
         let mut cfg = AppConfig::default_config();
@@ -2334,7 +3502,7 @@ mod provider_management_tests {
             cfg.routing.preferred_provider = "provider_2".to_string();
             cfg.routing
                 .session_preferred_providers
-                .insert("session-a".to_string(), "provider_1".to_string());
+                .insert("session-a".to_string(), vec!["provider_1".to_string()]);
             cfg.provider_order = vec!["provider_2".to_string(), "provider_1".to_string()];
         }
         state
@@ -2353,8 +3521,8 @@ mod provider_management_tests {
             snapshot
                 .session_preferred_providers
                 .get("session-a")
-                .map(String::as_str),
-            Some("provider_1")
+                .map(Vec::as_slice),
+            Some(["provider_1".to_string()].as_slice())
         );
         assert_eq!(
             snapshot
@@ -2448,6 +3616,109 @@ mod provider_management_tests {
         assert_eq!(tombstone.entity_id, original_shared_id);
     }
 
+    #[test]
+    fn exported_config_toml_strips_provider_credentials() {
+        let (_tmp, state) = build_test_state();
+        {
+            let mut cfg = state.gateway.cfg.write();
+            let provider = cfg.providers.get_mut("provider_1").expect("provider_1");
+            provider.api_key = "sk-legacy-key".to_string();
+            provider
+                .headers
+                .insert("X-Api-Key".to_string(), "header-secret".to_string());
+            cfg.provider_templates.insert(
+                "relay".to_string(),
+                crate::orchestrator::config::ProviderTemplate {
+                    base_url: "https://relay.example/v1".to_string(),
+                    headers: std::collections::BTreeMap::from([(
+                        "Authorization".to_string(),
+                        "Bearer template-secret".to_string(),
+                    )]),
+                    ..Default::default()
+                },
+            );
+        }
+
+        let text = exported_config_toml(&state).expect("export config");
+        for secret in ["sk-legacy-key", "header-secret", "template-secret"] {
+            assert!(!text.contains(secret), "{secret} leaked into the export");
+        }
+        let parsed: AppConfig = toml::from_str(&text).expect("parse config");
+        assert!(parsed.providers["provider_1"].api_key.is_empty());
+        assert!(parsed.providers["provider_1"].headers.is_empty());
+        assert_eq!(
+            parsed.provider_templates["relay"].base_url,
+            "https://relay.example/v1"
+        );
+
+        // The persisted file still keeps them.
+        let persisted = serialized_config_toml(&state).expect("serialize config");
+        assert!(persisted.contains("header-secret"));
+    }
+
+    #[test]
+    fn serialized_config_toml_round_trips_current_config() {
+        let (_tmp, state) = build_test_state();
+        state
+            .gateway
+            .cfg
+            .write()
+            .providers
+            .get_mut("provider_1")
+            .expect("provider_1")
+            .group = Some("shared".to_string());
+
+        let text = serialized_config_toml(&state).expect("serialize config");
+        let parsed: AppConfig = toml::from_str(&text).expect("parse config");
+
+        assert_eq!(
+            parsed.providers["provider_1"].group.as_deref(),
+            Some("shared")
+        );
+        assert_eq!(
+            parsed.providers.keys().collect::<Vec<_>>(),
+            state
+                .gateway
+                .cfg
+                .read()
+                .providers
+                .keys()
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn merge_providers_impl_moves_references_and_deletes_source() {
+        let (_tmp, state) = build_test_state();
+        {
+            let mut cfg = state.gateway.cfg.write();
+            cfg.routing.preferred_provider = "provider_1".to_string();
+            cfg.routing.session_preferred_providers.insert(
+                "session-a".to_string(),
+                vec!["provider_1".to_string(), "provider_2".to_string()],
+            );
+        }
+
+        assert_eq!(
+            merge_providers_impl(&state, "provider_1", "provider_1"),
+            Err("cannot merge a provider into itself".to_string())
+        );
+        assert_eq!(
+            merge_providers_impl(&state, "provider_1", "missing"),
+            Err("unknown provider: missing".to_string())
+        );
+        merge_providers_impl(&state, "provider_1", "provider_2").expect("merge providers");
+
+        let cfg = state.gateway.cfg.read();
+        assert!(!cfg.providers.contains_key("provider_1"));
+        assert!(!cfg.provider_order.iter().any(|name| name == "provider_1"));
+        assert_eq!(cfg.routing.preferred_provider, "provider_2");
+        assert_eq!(
+            cfg.routing.session_preferred_providers.get("session-a"),
+            Some(&vec!["provider_2".to_string()])
+        );
+    }
+
     #[test]
     fn delete_provider_impl_removes_deleted_provider_from_router_snapshot_immediately() {
         let (_tmp, state) = build_test_state();
@@ -2876,6 +4147,118 @@ mod provider_management_tests {
         );
     }
 
+    #[test]
+    fn usage_only_provider_cannot_be_manual_override_but_stays_in_config() {
+        let (_tmp, state) = build_test_state();
+        state
+            .gateway
+            .cfg
+            .write()
+            .providers
+            .get_mut("provider_1")
+            .expect("provider_1")
+            .routing_eligible = false;
+
+        let err = set_manual_override_impl(&state, Some("provider_1".to_string()))
+            .expect_err("usage-only provider");
+        assert!(err.contains("usage-only"));
+        assert!(state.gateway.router.manual_override.read().is_none());
+        let cfg = state.gateway.cfg.read().clone();
+        let toml_text = toml::to_string(&cfg).expect("serialize config");
+        assert!(toml_text.contains("routing_eligible = false"));
+    }
+
+    #[test]
+    fn explain_routing_reports_choice_and_skip_reasons_without_side_effects() {
+        let (_tmp, state) = build_test_state();
+        state
+            .gateway
+            .cfg
+            .write()
+            .providers
+            .get_mut("provider_1")
+            .expect("provider_1")
+            .disabled = true;
+        let cfg = state.gateway.cfg.read().clone();
+        for _ in 0..cfg.routing.failure_threshold.max(1) {
+            state
+                .gateway
+                .router
+                .mark_failure("provider_2", &cfg, "boom", unix_ms());
+        }
+
+        let trace = explain_routing_impl(&state, None, Some("gpt-5"));
+        assert_eq!(trace["provider"].as_str(), Some("official"));
+        assert_eq!(trace["reason"].as_str(), Some("preferred_healthy"));
+        let candidates = trace["candidates"].as_array().expect("candidates");
+        let find = |name: &str| {
+            candidates
+                .iter()
+                .find(|c| c["provider"].as_str() == Some(name))
+                .cloned()
+                .expect("candidate")
+        };
+        assert_eq!(find("official")["chosen"].as_bool(), Some(true));
+        assert_eq!(
+            find("provider_1")["skip_reasons"],
+            serde_json::json!(["disabled"])
+        );
+        assert_eq!(
+            find("provider_2")["skip_reasons"],
+            serde_json::json!(["cooldown"])
+        );
+        assert_eq!(find("provider_2")["eligible"].as_bool(), Some(false));
+        assert!(state.gateway.last_used_by_session.read().is_empty());
+    }
+
+    #[test]
+    fn manual_override_events_record_previous_next_and_active_provider() {
+        let (_tmp, state) = build_test_state();
+        seed_last_used_route(&state, "s1", "provider_1", "provider_1");
+
+        set_manual_override_impl(&state, Some("provider_2".to_string())).expect("set manual");
+        let event = latest_event_by_code(&state, "routing.manual_override_changed");
+        assert_eq!(event["fields"]["action"].as_str(), Some("set"));
+        assert_eq!(
+            event["fields"]["previous_manual_override"],
+            serde_json::Value::Null
+        );
+        assert_eq!(
+            event["fields"]["manual_override"].as_str(),
+            Some("provider_2")
+        );
+        assert_eq!(
+            event["fields"]["active_provider"].as_str(),
+            Some("provider_1")
+        );
+
+        seed_last_used_route(&state, "s1", "provider_2", "provider_1");
+        set_manual_override_impl(&state, None).expect("clear manual");
+        let event = state
+            .gateway
+            .store
+            .list_events_range(None, None, Some(20))
+            .into_iter()
+            .find(|event| {
+                event["code"].as_str() == Some("routing.manual_override_changed")
+                    && event["fields"]["action"].as_str() == Some("cleared")
+            })
+            .expect("cleared event");
+        assert_eq!(
+            event["fields"]["previous_manual_override"].as_str(),
+            Some("provider_2")
+        );
+        assert_eq!(event["fields"]["manual_override"], serde_json::Value::Null);
+        assert_eq!(
+            event["fields"]["active_provider"].as_str(),
+            Some("provider_2")
+        );
+        assert_eq!(
+            event["message"].as_str(),
+            Some("manual override cleared (was provider_2)")
+        );
+    }
+
     #[test]
     fn set_route_mode_clears_observed_routes_and_emits_count() {
         let (_tmp, state) = build_test_state();
@@ -2941,6 +4324,39 @@ mod provider_management_tests {
         );
     }
 
+    #[test]
+    fn set_usage_label_validates_and_persists_until_cleared() {
+        let (_tmp, state) = build_test_state();
+        state
+            .secrets
+            .set_provider_key("provider_1", "sk-test-secret-value")
+            .expect("set key");
+
+        assert!(set_usage_label_impl(&state, "   ").is_err());
+        assert!(set_usage_label_impl(&state, "bench\nrun").is_err());
+        assert!(set_usage_label_impl(&state, &"x".repeat(65)).is_err());
+        assert_eq!(
+            set_usage_label_impl(&state, "run sk-test-secret-value").unwrap_err(),
+            "label must not contain secrets"
+        );
+        assert_eq!(state.gateway.cfg.read().routing.usage_label, None);
+
+        set_usage_label_impl(&state, " bench-1 ").expect("set label");
+        assert_eq!(
+            state.gateway.cfg.read().routing.usage_label.as_deref(),
+            Some("bench-1")
+        );
+        let persisted = std::fs::read_to_string(&state.config_path).expect("read config");
+        assert!(persisted.contains("usage_label = \"bench-1\""));
+        let event = latest_event_by_code(&state, "config.usage_label_updated");
+        assert_eq!(event["fields"]["label"].as_str(), Some("bench-1"));
+
+        clear_usage_label_impl(&state).expect("clear label");
+        assert_eq!(state.gateway.cfg.read().routing.usage_label, None);
+        let persisted = std::fs::read_to_string(&state.config_path).expect("read config");
+        assert!(!persisted.contains("usage_label"));
+    }
+
     #[test]
     fn set_provider_group_rolls_back_when_persist_fails() {
         let (_tmp, mut state) = build_test_state();
@@ -3003,6 +4419,7 @@ mod provider_management_tests {
             "Provider 1".to_string(),
             "https://example.com/v2".to_string(),
             None,
+            None,
         )
         .expect("upsert provider");
 
@@ -3012,6 +4429,175 @@ mod provider_management_tests {
         assert_eq!(provider.base_url, "https://example.com/v2");
     }
 
+    #[test]
+    fn upsert_provider_prefills_from_template_and_routing_defaults() {
+        let (_tmp, state) = build_test_state();
+        {
+            let mut cfg = state.gateway.cfg.write();
+            cfg.routing.default_base_url = Some("https://default.example.com/v1".to_string());
+            cfg.routing.default_usage_adapter = Some("token_stats".to_string());
+            cfg.provider_templates.insert(
+                "relay".to_string(),
+                crate::orchestrator::config::ProviderTemplate {
+                    base_url: "https://relay.example.com/v1".to_string(),
+                    usage_adapter: "budget_info".to_string(),
+                    usage_base_url: Some("https://relay.example.com".to_string()),
+                    headers: std::collections::BTreeMap::from([(
+                        "X-Team".to_string(),
+                        "router".to_string(),
+                    )]),
+                },
+            );
+        }
+
+        upsert_provider_impl(
+            &state,
+            "plain".to_string(),
+            "Plain".to_string(),
+            "  ".to_string(),
+            None,
+            None,
+        )
+        .expect("upsert with defaults");
+        upsert_provider_impl(
+            &state,
+            "templated".to_string(),
+            "Templated".to_string(),
+            String::new(),
+            None,
+            Some("relay".to_string()),
+        )
+        .expect("upsert from template");
+        let missing = upsert_provider_impl(
+            &state,
+            "other".to_string(),
+            "Other".to_string(),
+            String::new(),
+            None,
+            Some("missing".to_string()),
+        );
+        assert!(missing.is_err());
+
+        let cfg = state.gateway.cfg.read();
+        let plain = cfg.providers.get("plain").expect("plain");
+        assert_eq!(plain.base_url, "https://default.example.com/v1");
+        assert_eq!(plain.usage_adapter, "token_stats");
+        let templated = cfg.providers.get("templated").expect("templated");
+        assert_eq!(templated.base_url, "https://relay.example.com/v1");
+        assert_eq!(templated.usage_adapter, "budget_info");
+        assert_eq!(
+            templated.usage_base_url.as_deref(),
+            Some("https://relay.example.com")
+        );
+        assert_eq!(templated.headers["X-Team"], "router");
+        assert!(!cfg.providers.contains_key("other"));
+        drop(cfg);
+
+        // Editing an existing provider keeps its own adapter and never falls back to the
+        // default base URL.
+        let adapter_before = state.gateway.cfg.read().providers["provider_1"]
+            .usage_adapter
+            .clone();
+        upsert_provider_impl(
+            &state,
+            "provider_1".to_string(),
+            "Provider 1".to_string(),
+            "https://edited.example.com/v1".to_string(),
+            None,
+            None,
+        )
+        .expect("edit existing provider");
+        assert_eq!(
+            state.gateway.cfg.read().providers["provider_1"].usage_adapter,
+            adapter_before
+        );
+        assert!(upsert_provider_impl(
+            &state,
+            "provider_1".to_string(),
+            "Provider 1".to_string(),
+            String::new(),
+            None,
+            None,
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn upsert_provider_normalizes_and_validates_base_url() {
+        let (_tmp, state) = build_test_state();
+        let upsert = |base_url: &str| {
+            upsert_provider_impl(
+                &state,
+                "provider_1".to_string(),
+                "Provider 1".to_string(),
+                base_url.to_string(),
+                None,
+                None,
+            )
+        };
+
+        upsert(" https://relay.example.com/v1/ ").expect("valid base url");
+        assert_eq!(
+            state.gateway.cfg.read().providers["provider_1"].base_url,
+            "https://relay.example.com/v1"
+        );
+        for invalid in [
+            "relay.example.com/v1",
+            "ftp://relay.example.com",
+            "https://",
+        ] {
+            let err = upsert(invalid).expect_err("invalid base url");
+            assert!(err.contains("invalid base_url"), "{invalid}: {err}");
+        }
+        assert!(upsert("https://relay.example.com/v1?key=1")
+            .expect_err("query string")
+            .contains("query string"));
+        assert_eq!(
+            state.gateway.cfg.read().providers["provider_1"].base_url,
+            "https://relay.example.com/v1"
+        );
+
+        upsert("https://relay.example.com/v1/responses").expect("suffix only warns");
+        let event = latest_event_by_code(&state, "config.provider_base_url_suspicious");
+        assert_eq!(event["fields"]["suffix"].as_str(), Some("/responses"));
+    }
+
+    #[test]
+    fn upsert_provider_records_audit_entry_without_inline_key() {
+        let (_tmp, state) = build_test_state();
+        {
+            let mut cfg = state.gateway.cfg.write();
+            cfg.providers
+                .get_mut("provider_1")
+                .expect("provider_1")
+                .api_key = "sk-inline-secret-value".to_string();
+        }
+
+        upsert_provider_impl(
+            &state,
+            "provider_1".to_string(),
+            "Provider 1".to_string(),
+            "https://audit.example.com/v1".to_string(),
+            None,
+            None,
+        )
+        .expect("upsert provider");
+
+        let entries = state.gateway.store.list_config_audit(None, None, 10);
+        let entry = entries
+            .iter()
+            .find(|entry| entry["action"].as_str() == Some("upsert_provider"))
+            .expect("upsert audit entry");
+        assert_eq!(entry["target"].as_str(), Some("provider_1"));
+        assert_eq!(
+            entry["new"]["base_url"].as_str(),
+            Some("https://audit.example.com/v1")
+        );
+        assert_eq!(entry["old"]["api_key_set"].as_bool(), Some(true));
+        assert!(entry["old"].get("api_key").is_none());
+        assert!(!entry.to_string().contains("sk-inline-secret-value"));
+    }
+
     #[test]
     fn upsert_provider_base_url_change_resets_stale_health() {
         let (_tmp, state) = build_test_state();
@@ -3036,6 +4622,7 @@ mod provider_management_tests {
             "Provider 1".to_string(),
             "https://fresh.example.com/v1".to_string(),
             None,
+            None,
         )
         .expect("upsert provider");
 
@@ -3093,6 +4680,133 @@ mod provider_management_tests {
         );
     }
 
+    #[test]
+    fn set_provider_system_prefix_trims_persists_and_clears() {
+        let (_tmp, state) = build_test_state();
+
+        let changed = set_provider_system_prefix_impl(
+            &state,
+            "provider_1".to_string(),
+            Some("  Answer in English.  ".to_string()),
+        )
+        .expect("set system prefix");
+        assert!(changed);
+        assert_eq!(
+            state
+                .gateway
+                .cfg
+                .read()
+                .providers
+                .get("provider_1")
+                .and_then(|provider| provider.system_prefix.clone())
+                .as_deref(),
+            Some("Answer in English.")
+        );
+        let persisted = std::fs::read_to_string(&state.config_path).expect("read config");
+        assert!(persisted.contains("system_prefix = \"Answer in English.\""));
+
+        let changed = set_provider_system_prefix_impl(
+            &state,
+            "provider_1".to_string(),
+            Some(" ".to_string()),
+        )
+        .expect("clear system prefix");
+        assert!(changed);
+        assert!(state
+            .gateway
+            .cfg
+            .read()
+            .providers
+            .get("provider_1")
+            .is_some_and(|provider| provider.system_prefix.is_none()));
+        assert!(set_provider_system_prefix_impl(&state, "missing".to_string(), None).is_err());
+    }
+
+    #[test]
+    fn set_provider_default_model_trims_persists_and_clears() {
+        let (_tmp, state) = build_test_state();
+
+        let changed = set_provider_default_model_impl(
+            &state,
+            "provider_1".to_string(),
+            Some("  gpt-5  ".to_string()),
+        )
+        .expect("set default model");
+        assert!(changed);
+        let persisted = std::fs::read_to_string(&state.config_path).expect("read config");
+        assert!(persisted.contains("default_model = \"gpt-5\""));
+
+        let unchanged = set_provider_default_model_impl(
+            &state,
+            "provider_1".to_string(),
+            Some("gpt-5".to_string()),
+        )
+        .expect("repeat default model");
+        assert!(!unchanged);
+
+        let cleared = set_provider_default_model_impl(
+            &state,
+            "provider_1".to_string(),
+            Some(" ".to_string()),
+        )
+        .expect("clear default model");
+        assert!(cleared);
+        assert!(state
+            .gateway
+            .cfg
+            .read()
+            .providers
+            .get("provider_1")
+            .is_some_and(|provider| provider.default_model.is_none()));
+        assert!(set_provider_default_model_impl(&state, "missing".to_string(), None).is_err());
+    }
+
+    #[test]
+    fn set_provider_notes_persists_clears_and_rejects_secrets() {
+        let (_tmp, state) = build_test_state();
+        state
+            .secrets
+            .set_provider_key("provider_1", "sk-notes-secret")
+            .expect("set key");
+
+        let changed = set_provider_notes_impl(
+            &state,
+            "provider_1".to_string(),
+            "  Team plan, billing via ops@example.com  ".to_string(),
+        )
+        .expect("set notes");
+        assert!(changed);
+        assert_eq!(
+            state.gateway.cfg.read().providers["provider_1"].notes,
+            "Team plan, billing via ops@example.com"
+        );
+        let persisted = std::fs::read_to_string(&state.config_path).expect("read config");
+        assert!(persisted.contains("notes = \"Team plan, billing via ops@example.com\""));
+
+        let err = set_provider_notes_impl(
+            &state,
+            "provider_1".to_string(),
+            "key is sk-notes-secret".to_string(),
+        )
+        .expect_err("secret rejected");
+        assert!(err.contains("must not contain"));
+        assert_eq!(
+            state.gateway.cfg.read().providers["provider_1"].notes,
+            "Team plan, billing via ops@example.com"
+        );
+
+        assert!(
+            set_provider_notes_impl(&state, "provider_1".to_string(), " ".to_string())
+                .expect("clear notes")
+        );
+        assert!(state.gateway.cfg.read().providers["provider_1"]
+            .notes
+            .is_empty());
+        let persisted = std::fs::read_to_string(&state.config_path).expect("read config");
+        assert!(!persisted.contains("notes ="));
+        assert!(set_provider_notes_impl(&state, "missing".to_string(), String::new()).is_err());
+    }
+
     #[test]
     fn set_providers_group_validates_all_names_before_mutation() {
         let (_tmp, state) = build_test_state();
@@ -3183,7 +4897,7 @@ mod provider_management_tests {
         set_session_preferred_provider_impl(
             &state,
             "s1".to_string(),
-            "provider_2".to_string(),
+            vec!["provider_2".to_string()],
         )
         .expect("set session preferred");
 
@@ -3215,12 +4929,57 @@ mod provider_management_tests {
         );
     }
 
+    #[test]
+    fn set_session_preferred_provider_stores_ordered_deduped_list() {
+        let (_tmp, state) = build_test_state();
+        seed_non_agent_session(&state, "s1");
+
+        set_session_preferred_provider_impl(
+            &state,
+            "s1".to_string(),
+            vec![
+                "provider_2".to_string(),
+                " provider_1 ".to_string(),
+                "provider_2".to_string(),
+            ],
+        )
+        .expect("set session preferred list");
+        assert_eq!(
+            state
+                .gateway
+                .cfg
+                .read()
+                .routing
+                .session_preferred_list("s1"),
+            ["provider_2".to_string(), "provider_1".to_string()]
+        );
+
+        let err = set_session_preferred_provider_impl(
+            &state,
+            "s1".to_string(),
+            vec!["provider_1".to_string(), "missing".to_string()],
+        )
+        .expect_err("unknown provider in list");
+        assert!(err.contains("unknown provider: missing"));
+        assert!(set_session_preferred_provider_impl(&state, "s1".to_string(), Vec::new()).is_err());
+        assert_eq!(
+            state
+                .gateway
+                .cfg
+                .read()
+                .routing
+                .session_preferred_list("s1")
+                .len(),
+            2
+        );
+    }
+
     #[test]
     fn clear_session_preferred_provider_clears_only_target_observed_route() {
         let (_tmp, state) = build_test_state();
         state.gateway.cfg.write().routing.session_preferred_providers.insert(
             "s1".to_string(),
-            "provider_2".to_string(),
+            vec!["provider_2".to_string()],
         );
         seed_last_used_route(&state, "s1", "provider_2", "provider_1");
         seed_last_used_route(&state, "s2", "provider_1", "provider_1");
@@ -3270,7 +5029,7 @@ mod provider_management_tests {
         seed_non_agent_session(&state, "s1");
         state.gateway.cfg.write().routing.session_preferred_providers.insert(
             "s1".to_string(),
-            "provider_1".to_string(),
+            vec!["provider_1".to_string()],
         );
         seed_last_used_route(&state, "s1", "provider_1", "provider_1");
         state
@@ -3286,7 +5045,7 @@ mod provider_management_tests {
         state.config_path = bad_path;
 
         let result =
-            set_session_preferred_provider_impl(&state, "s1".to_string(), "provider_2".to_string());
+            set_session_preferred_provider_impl(&state, "s1".to_string(), vec!["provider_2".to_string()]);
         assert!(result.is_err());
         assert_eq!(
             state
@@ -3297,7 +5056,7 @@ mod provider_management_tests {
                 .session_preferred_providers
                 .get("s1")
                 .cloned(),
-            Some("provider_1".to_string())
+            Some(vec!["provider_1".to_string()])
         );
         assert!(
             state
@@ -3314,7 +5073,7 @@ mod provider_management_tests {
         let (_tmp, mut state) = build_test_state();
         state.gateway.cfg.write().routing.session_preferred_providers.insert(
             "s1".to_string(),
-            "provider_2".to_string(),
+            vec!["provider_2".to_string()],
         );
         seed_last_used_route(&state, "s1", "provider_2", "provider_1");
         state
@@ -3340,7 +5099,7 @@ mod provider_management_tests {
                 .session_preferred_providers
                 .get("s1")
                 .cloned(),
-            Some("provider_2".to_string())
+            Some(vec!["provider_2".to_string()])
         );
         assert!(
             state
@@ -3385,4 +5144,45 @@ mod provider_management_tests {
             Some("provider_2")
         );
     }
+    #[test]
+    fn forget_client_session_removes_entry_and_optionally_preference() {
+        let (_tmp, state) = build_test_state();
+        seed_non_agent_session(&state, "s1");
+        seed_non_agent_session(&state, "s2");
+        seed_last_used_route(&state, "s1", "provider_1", "provider_1");
+        state
+            .gateway
+            .cfg
+            .write()
+            .routing
+            .session_preferred_providers
+            .insert("s1".to_string(), vec!["provider_2".to_string()]);
+
+        forget_client_session_impl(&state, "s1".to_string(), false).expect("forget s1");
+        assert!(!state.gateway.client_sessions.read().contains_key("s1"));
+        assert!(state.gateway.client_sessions.read().contains_key("s2"));
+        assert!(!state.gateway.last_used_by_session.read().contains_key("s1"));
+        assert!(state
+            .gateway
+            .cfg
+            .read()
+            .routing
+            .session_preferred_providers
+            .contains_key("s1"));
+        let event = latest_event_by_code(&state, "session.forgotten");
+        assert_eq!(event["fields"]["preference_cleared"].as_bool(), Some(false));
+
+        seed_non_agent_session(&state, "s1");
+        forget_client_session_impl(&state, "s1".to_string(), true).expect("forget s1 again");
+        assert!(!state
+            .gateway
+            .cfg
+            .read()
+            .routing
+            .session_preferred_providers
+            .contains_key("s1"));
+
+        assert!(forget_client_session_impl(&state, "s1".to_string(), false).is_err());
+        assert!(forget_client_session_impl(&state, "  ".to_string(), false).is_err());
+    }
 }
@@ -2,9 +2,9 @@
 pub(crate) fn get_provider_schedule(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, CommandError> {
     if !state.gateway.cfg.read().providers.contains_key(&provider) {
-        return Err(format!("unknown provider: {provider}"));
+        return Err(CommandError::unknown_provider(&provider));
     }
     let periods = state.secrets.list_provider_schedule(&provider);
     let rows = periods
@@ -54,9 +54,11 @@ pub(crate) struct ProviderTimelinePeriodInput {
     ended_at_unix_ms: Option<u64>,
 }
 
-fn provider_api_key_ref(state: &tauri::State<'_, app_state::AppState>, provider: &str) -> String {
-    state
-        .secrets
+fn provider_api_key_ref(
+    secrets: &crate::orchestrator::secrets::SecretStore,
+    provider: &str,
+) -> String {
+    secrets
         .get_provider_key(provider)
         .as_deref()
         .map(mask_key_preview)
@@ -66,9 +68,9 @@ fn provider_api_key_ref(state: &tauri::State<'_, app_state::AppState>, provider:
 pub(crate) fn get_provider_timeline(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
-) -> Result<serde_json::Value, String> {
+) -> Result<serde_json::Value, CommandError> {
     if !state.gateway.cfg.read().providers.contains_key(&provider) {
-        return Err(format!("unknown provider: {provider}"));
+        return Err(CommandError::unknown_provider(&provider));
     }
     let rows = state
         .secrets
@@ -92,16 +94,91 @@ pub(crate) fn get_provider_timeline(
     }))
 }
 
+#[tauri::command]
+pub(crate) fn validate_provider_timeline(
+    state: tauri::State<'_, app_state::AppState>,
+    provider: String,
+) -> Result<serde_json::Value, CommandError> {
+    if !state.gateway.cfg.read().providers.contains_key(&provider) {
+        return Err(CommandError::unknown_provider(&provider));
+    }
+    let issues = provider_timeline_issues(&state.secrets.list_provider_timeline(&provider));
+    Ok(serde_json::json!({
+        "ok": true,
+        "provider": provider,
+        "valid": issues.is_empty(),
+        "issues": issues
+    }))
+}
+
+/// Problems in a stored pricing timeline, in chronological order. `set_provider_timeline` rejects
+/// overlaps and misplaced open-ended rows on save, but timelines written by migrations or edited
+/// on disk are not re-checked, and gaps between periods are allowed and leave those days unpriced.
+fn provider_timeline_issues(
+    periods: &[crate::orchestrator::secrets::ProviderPricingPeriod],
+) -> Vec<serde_json::Value> {
+    let mut sorted = periods.iter().collect::<Vec<_>>();
+    sorted.sort_by_key(|period| period.started_at_unix_ms);
+    let mut issues = Vec::new();
+    // Compare each period with whichever earlier period reaches furthest, not just its
+    // predecessor: a long period can cover several shorter ones that start inside it.
+    let Some((first, rest)) = sorted.split_first() else {
+        return issues;
+    };
+    let mut furthest = *first;
+    for &right in rest {
+        match furthest.ended_at_unix_ms {
+            None => issues.push(serde_json::json!({
+                "kind": "open_ended_not_last",
+                "period_id": furthest.id,
+                "started_at_unix_ms": furthest.started_at_unix_ms,
+                "next_period_id": right.id,
+                "next_started_at_unix_ms": right.started_at_unix_ms,
+            })),
+            Some(furthest_end) if furthest_end > right.started_at_unix_ms => {
+                let overlap_end = right
+                    .ended_at_unix_ms
+                    .map_or(furthest_end, |right_end| right_end.min(furthest_end));
+                issues.push(serde_json::json!({
+                    "kind": "overlap",
+                    "period_ids": [furthest.id, right.id],
+                    "from_unix_ms": right.started_at_unix_ms,
+                    "to_unix_ms": overlap_end,
+                }));
+            }
+            Some(furthest_end) if furthest_end < right.started_at_unix_ms => {
+                issues.push(serde_json::json!({
+                    "kind": "gap",
+                    "after_period_id": furthest.id,
+                    "before_period_id": right.id,
+                    "from_unix_ms": furthest_end,
+                    "to_unix_ms": right.started_at_unix_ms,
+                }));
+            }
+            Some(_) => {}
+        }
+        let extends = match (furthest.ended_at_unix_ms, right.ended_at_unix_ms) {
+            (None, _) => false,
+            (Some(_), None) => true,
+            (Some(furthest_end), Some(right_end)) => right_end > furthest_end,
+        };
+        if extends {
+            furthest = right;
+        }
+    }
+    issues
+}
+
 #[tauri::command]
 pub(crate) fn set_provider_timeline(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
     periods: Vec<ProviderTimelinePeriodInput>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     if !state.gateway.cfg.read().providers.contains_key(&provider) {
-        return Err(format!("unknown provider: {provider}"));
+        return Err(CommandError::unknown_provider(&provider));
     }
-    let default_key_ref = provider_api_key_ref(&state, &provider);
+    let default_key_ref = provider_api_key_ref(&state.secrets, &provider);
 
     let mut normalized = periods
         .into_iter()
@@ -147,10 +224,14 @@ pub(crate) fn set_provider_timeline(
         let left = &pair[0];
         let right = &pair[1];
         let Some(left_end) = left.ended_at_unix_ms else {
-            return Err("open-ended timeline period must be the latest row".to_string());
+            return Err(CommandError::Validation(
+                "open-ended timeline period must be the latest row".to_string(),
+            ));
         };
         if left_end > right.started_at_unix_ms {
-            return Err("timeline periods must not overlap".to_string());
+            return Err(CommandError::Validation(
+                "timeline periods must not overlap".to_string(),
+            ));
         }
     }
 
@@ -182,12 +263,12 @@ pub(crate) fn set_provider_schedule(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
     periods: Vec<ProviderSchedulePeriodInput>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     if !state.gateway.cfg.read().providers.contains_key(&provider) {
-        return Err(format!("unknown provider: {provider}"));
+        return Err(CommandError::unknown_provider(&provider));
     }
 
-    let default_key_ref = provider_api_key_ref(&state, &provider);
+    let default_key_ref = provider_api_key_ref(&state.secrets, &provider);
     let mut normalized = periods
         .into_iter()
         .map(|period| {
@@ -223,7 +304,9 @@ pub(crate) fn set_provider_schedule(
         let right = &pair[1];
         let left_end = left.ended_at_unix_ms.unwrap_or(0);
         if left_end > right.started_at_unix_ms {
-            return Err("schedule periods must not overlap".to_string());
+            return Err(CommandError::Validation(
+                "schedule periods must not overlap".to_string(),
+            ));
         }
     }
 
@@ -249,3 +332,100 @@ pub(crate) fn set_provider_schedule(
     );
     Ok(())
 }
+
+#[cfg(test)]
+mod provider_timeline_tests {
+    use super::provider_timeline_issues;
+    use crate::orchestrator::secrets::ProviderPricingPeriod;
+
+    fn period(
+        id: &str,
+        started_at_unix_ms: u64,
+        ended_at_unix_ms: Option<u64>,
+    ) -> ProviderPricingPeriod {
+        ProviderPricingPeriod {
+            id: id.to_string(),
+            mode: "per_request".to_string(),
+            amount_usd: 0.01,
+            api_key_ref: "-".to_string(),
+            started_at_unix_ms,
+            ended_at_unix_ms,
+        }
+    }
+
+    #[test]
+    fn contiguous_timeline_has_no_issues() {
+        let periods = [period("b", 200, None), period("a", 100, Some(200))];
+        assert!(provider_timeline_issues(&periods).is_empty());
+    }
+
+    #[test]
+    fn timeline_issues_report_gaps_overlaps_and_misplaced_open_rows() {
+        let periods = [
+            period("a", 100, Some(200)),
+            period("b", 300, Some(500)),
+            period("c", 400, Some(600)),
+            period("d", 700, None),
+            period("e", 800, Some(900)),
+        ];
+        assert_eq!(
+            provider_timeline_issues(&periods),
+            vec![
+                serde_json::json!({
+                    "kind": "gap",
+                    "after_period_id": "a",
+                    "before_period_id": "b",
+                    "from_unix_ms": 200,
+                    "to_unix_ms": 300,
+                }),
+                serde_json::json!({
+                    "kind": "overlap",
+                    "period_ids": ["b", "c"],
+                    "from_unix_ms": 400,
+                    "to_unix_ms": 500,
+                }),
+                serde_json::json!({
+                    "kind": "gap",
+                    "after_period_id": "c",
+                    "before_period_id": "d",
+                    "from_unix_ms": 600,
+                    "to_unix_ms": 700,
+                }),
+                serde_json::json!({
+                    "kind": "open_ended_not_last",
+                    "period_id": "d",
+                    "started_at_unix_ms": 700,
+                    "next_period_id": "e",
+                    "next_started_at_unix_ms": 800,
+                }),
+            ]
+        );
+    }
+
+    #[test]
+    fn timeline_issues_compare_against_the_period_reaching_furthest() {
+        // B and C both sit inside A, so nothing between them is a gap.
+        let periods = [
+            period("a", 0, Some(1000)),
+            period("b", 100, Some(200)),
+            period("c", 300, Some(400)),
+        ];
+        assert_eq!(
+            provider_timeline_issues(&periods),
+            vec![
+                serde_json::json!({
+                    "kind": "overlap",
+                    "period_ids": ["a", "b"],
+                    "from_unix_ms": 100,
+                    "to_unix_ms": 200,
+                }),
+                serde_json::json!({
+                    "kind": "overlap",
+                    "period_ids": ["a", "c"],
+                    "from_unix_ms": 300,
+                    "to_unix_ms": 400,
+                }),
+            ]
+        );
+    }
+}
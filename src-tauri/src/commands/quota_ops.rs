@@ -80,9 +80,9 @@ fn usage_refresh_on_failure() {
 pub(crate) async fn refresh_quota(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     if !state.gateway.cfg.read().providers.contains_key(&provider) {
-        return Err(format!("unknown provider: {provider}"));
+        return Err(CommandError::unknown_provider(&provider));
     }
     if let Some(owner) = crate::orchestrator::quota::shared_quota_owner_for_provider(
         &state.gateway,
@@ -130,7 +130,7 @@ pub(crate) async fn refresh_quota(
         } else {
             snap.last_error.chars().take(300).collect::<String>()
         };
-        return Err(err);
+        return Err(CommandError::upstream(None, err));
     }
     Ok(())
 }
@@ -139,7 +139,7 @@ pub(crate) async fn refresh_quota(
 pub(crate) async fn refresh_quota_shared(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let group =
         crate::orchestrator::quota::refresh_quota_shared(&state.gateway, &state.lan_sync, &provider)
             .await?;
@@ -157,7 +157,7 @@ pub(crate) async fn refresh_quota_shared(
 #[tauri::command]
 pub(crate) async fn refresh_quota_all(
     state: tauri::State<'_, app_state::AppState>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     let (ok, err, failed) =
         crate::orchestrator::quota::refresh_quota_all_with_summary(&state.gateway, &state.lan_sync)
             .await;
@@ -364,15 +364,9 @@ fn set_usage_base_url_impl(
         .get(provider)
         .map(|provider| provider.base_url.clone())
         .ok_or_else(|| format!("unknown provider: {provider}"))?;
-    let parsed = url.trim().trim_end_matches('/').to_string();
+    let parsed = normalize_base_url("url", url)?;
     let usage_base_url = crate::orchestrator::quota::normalize_usage_base_url(&provider_base_url, &parsed)
         .unwrap_or(parsed);
-    if usage_base_url.is_empty() {
-        return Err("url is required".to_string());
-    }
-    if reqwest::Url::parse(&usage_base_url).is_err() {
-        return Err("invalid url".to_string());
-    }
     {
         let mut cfg = state.gateway.cfg.write();
         if let Some(p) = cfg.providers.get_mut(provider) {
@@ -398,6 +392,7 @@ fn set_usage_base_url_impl(
         "usage base url updated",
         serde_json::Value::Null,
     );
+    warn_on_base_url_endpoint_suffix(state, provider, "usage_base_url", &usage_base_url);
     crate::orchestrator::quota::clear_quota_snapshot(&state.gateway, provider);
     Ok(())
 }
@@ -438,25 +433,25 @@ pub(crate) fn set_usage_token(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
     token: String,
-) -> Result<(), String> {
-    set_usage_token_impl(&state, &provider, &token)
+) -> Result<(), CommandError> {
+    set_usage_token_impl(&state, &provider, &token).map_err(CommandError::from)
 }
 
 #[tauri::command]
 pub(crate) fn clear_usage_token(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
-) -> Result<(), String> {
-    clear_usage_token_impl(&state, &provider)
+) -> Result<(), CommandError> {
+    clear_usage_token_impl(&state, &provider).map_err(CommandError::from)
 }
 
 #[tauri::command]
 pub(crate) fn get_usage_auth(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
-) -> Result<UsageAuthPayload, String> {
+) -> Result<UsageAuthPayload, CommandError> {
     if !state.gateway.cfg.read().providers.contains_key(&provider) {
-        return Err(format!("unknown provider: {provider}"));
+        return Err(CommandError::unknown_provider(&provider));
     }
     let token = state.secrets.get_usage_token(&provider).unwrap_or_default();
     let login = state.secrets.get_usage_login(&provider);
@@ -477,16 +472,16 @@ pub(crate) fn set_usage_auth(
     token: String,
     username: String,
     password: String,
-) -> Result<(), String> {
-    set_usage_auth_impl(&state, &provider, &token, &username, &password)
+) -> Result<(), CommandError> {
+    set_usage_auth_impl(&state, &provider, &token, &username, &password).map_err(CommandError::from)
 }
 
 #[tauri::command]
 pub(crate) fn clear_usage_auth(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
-) -> Result<(), String> {
-    clear_usage_auth_impl(&state, &provider)
+) -> Result<(), CommandError> {
+    clear_usage_auth_impl(&state, &provider).map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -494,16 +489,16 @@ pub(crate) fn set_usage_base_url(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
     url: String,
-) -> Result<(), String> {
-    set_usage_base_url_impl(&state, &provider, &url)
+) -> Result<(), CommandError> {
+    set_usage_base_url_impl(&state, &provider, &url).map_err(CommandError::from)
 }
 
 #[tauri::command]
 pub(crate) fn clear_usage_base_url(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
-) -> Result<(), String> {
-    clear_usage_base_url_impl(&state, &provider)
+) -> Result<(), CommandError> {
+    clear_usage_base_url_impl(&state, &provider).map_err(CommandError::from)
 }
 
 #[tauri::command]
@@ -511,9 +506,9 @@ pub(crate) fn set_usage_proxy_pool(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
     proxies: Vec<String>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     if !state.gateway.cfg.read().providers.contains_key(&provider) {
-        return Err(format!("unknown provider: {provider}"));
+        return Err(CommandError::unknown_provider(&provider));
     }
     state.secrets.set_usage_proxy_pool(&provider, proxies.clone())?;
     state.gateway.store.events().emit(
@@ -532,9 +527,9 @@ pub(crate) fn set_provider_quota_hard_cap(
     daily: bool,
     weekly: bool,
     monthly: bool,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     if !state.gateway.cfg.read().providers.contains_key(&provider) {
-        return Err(format!("unknown provider: {provider}"));
+        return Err(CommandError::unknown_provider(&provider));
     }
     let hard_cap = crate::orchestrator::secrets::ProviderQuotaHardCapConfig {
         daily,
@@ -578,9 +573,9 @@ pub(crate) fn set_provider_quota_hard_cap_field(
     provider: String,
     field: String,
     enabled: bool,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     if !state.gateway.cfg.read().providers.contains_key(&provider) {
-        return Err(format!("unknown provider: {provider}"));
+        return Err(CommandError::unknown_provider(&provider));
     }
     let normalized_field = field.trim().to_ascii_lowercase();
     let hard_cap = state
@@ -616,6 +611,56 @@ pub(crate) fn set_provider_quota_hard_cap_field(
     Ok(())
 }
 
+fn provider_pricing_audit_value(state: &app_state::AppState, provider: &str) -> serde_json::Value {
+    match state.secrets.list_provider_pricing().get(provider) {
+        Some(pricing) => serde_json::json!({
+            "mode": pricing.mode,
+            "amount_usd": pricing.amount_usd,
+            "gap_fill_mode": pricing.gap_fill_mode,
+            "gap_fill_amount_usd": pricing.gap_fill_amount_usd,
+            "tiers": pricing.tiers,
+            "model_rates": pricing.model_rates,
+        }),
+        None => serde_json::Value::Null,
+    }
+}
+
+/// Follows up a saved pricing edit: refreshes the store's pricing copy, records the LAN sync
+/// snapshot, writes the config audit entry and emits `code`.
+fn publish_provider_pricing_change(
+    state: &app_state::AppState,
+    provider: &str,
+    audit_action: &str,
+    audit_old: serde_json::Value,
+    code: crate::orchestrator::store::EventCode,
+    message: &str,
+    fields: serde_json::Value,
+) {
+    state
+        .gateway
+        .store
+        .sync_provider_pricing_configs(&state.secrets.list_provider_pricing());
+    if let Err(err) = crate::lan_sync::record_provider_pricing_snapshot(state, provider) {
+        state.gateway.store.events().emit(
+            provider,
+            crate::orchestrator::store::EventCode::LAN_EDIT_SYNC_RECORD_FAILED,
+            &format!("failed to record {audit_action} for LAN sync: {err}"),
+            serde_json::Value::Null,
+        );
+    }
+    state.gateway.store.record_config_audit(
+        audit_action,
+        provider,
+        audit_old,
+        provider_pricing_audit_value(state, provider),
+    );
+    state
+        .gateway
+        .store
+        .events()
+        .emit(provider, code, message, fields);
+}
+
 #[tauri::command]
 pub(crate) fn set_provider_manual_pricing(
     state: tauri::State<'_, app_state::AppState>,
@@ -623,12 +668,14 @@ pub(crate) fn set_provider_manual_pricing(
     mode: String,
     amount_usd: Option<f64>,
     package_expires_at_unix_ms: Option<u64>,
-) -> Result<(), String> {
+    tiers: Option<Vec<crate::orchestrator::secrets::ProviderPricingTier>>,
+) -> Result<(), CommandError> {
     if !state.gateway.cfg.read().providers.contains_key(&provider) {
-        return Err(format!("unknown provider: {provider}"));
+        return Err(CommandError::unknown_provider(&provider));
     }
     let mode = mode.trim().to_lowercase();
-    let api_key_ref = provider_api_key_ref(&state, &provider);
+    let api_key_ref = provider_api_key_ref(&state.secrets, &provider);
+    let audit_old = provider_pricing_audit_value(&state, &provider);
     match mode.as_str() {
         "none" => {
             state.secrets.set_provider_pricing(
@@ -638,20 +685,11 @@ pub(crate) fn set_provider_manual_pricing(
                 None,
                 Some(api_key_ref.clone()),
             )?;
-            state
-                .gateway
-                .store
-                .sync_provider_pricing_configs(&state.secrets.list_provider_pricing());
-            if let Err(err) = crate::lan_sync::record_provider_pricing_snapshot(&state, &provider) {
-                state.gateway.store.events().emit(
-                    &provider,
-                    crate::orchestrator::store::EventCode::LAN_EDIT_SYNC_RECORD_FAILED,
-                    &format!("failed to record pricing clear for LAN sync: {err}"),
-                    serde_json::Value::Null,
-                );
-            }
-            state.gateway.store.events().emit(
+            publish_provider_pricing_change(
+                &state,
                 &provider,
+                "set_provider_manual_pricing",
+                audit_old,
                 crate::orchestrator::store::EventCode::CONFIG_PROVIDER_PRICING_CLEARED,
                 "provider manual pricing cleared",
                 serde_json::Value::Null,
@@ -660,15 +698,21 @@ pub(crate) fn set_provider_manual_pricing(
         }
         "per_request" | "package_total" => {
             let Some(v) = amount_usd else {
-                return Err("amount_usd is required".to_string());
+                return Err(CommandError::Validation(
+                    "amount_usd is required".to_string(),
+                ));
             };
             if !v.is_finite() || v <= 0.0 {
-                return Err("amount_usd must be > 0".to_string());
+                return Err(CommandError::Validation(
+                    "amount_usd must be > 0".to_string(),
+                ));
             }
             let expires = if mode == "package_total" {
                 if let Some(ts) = package_expires_at_unix_ms {
                     if ts <= unix_ms() {
-                        return Err("package_expires_at_unix_ms must be in the future".to_string());
+                        return Err(CommandError::Validation(
+                            "package_expires_at_unix_ms must be in the future".to_string(),
+                        ));
                     }
                     Some(ts)
                 } else {
@@ -684,20 +728,11 @@ pub(crate) fn set_provider_manual_pricing(
                 expires,
                 Some(api_key_ref.clone()),
             )?;
-            state
-                .gateway
-                .store
-                .sync_provider_pricing_configs(&state.secrets.list_provider_pricing());
-            if let Err(err) = crate::lan_sync::record_provider_pricing_snapshot(&state, &provider) {
-                state.gateway.store.events().emit(
-                    &provider,
-                    crate::orchestrator::store::EventCode::LAN_EDIT_SYNC_RECORD_FAILED,
-                    &format!("failed to record pricing update for LAN sync: {err}"),
-                    serde_json::Value::Null,
-                );
-            }
-            state.gateway.store.events().emit(
+            publish_provider_pricing_change(
+                &state,
                 &provider,
+                "set_provider_manual_pricing",
+                audit_old,
                 crate::orchestrator::store::EventCode::CONFIG_PROVIDER_PRICING_UPDATED,
                 "provider manual pricing updated",
                 serde_json::json!({
@@ -708,8 +743,185 @@ pub(crate) fn set_provider_manual_pricing(
             );
             Ok(())
         }
-        _ => Err("mode must be one of: none, per_request, package_total".to_string()),
+        "per_request_tiered" => {
+            let tiers = tiers.unwrap_or_default();
+            state.secrets.set_provider_pricing_tiered(
+                &provider,
+                tiers.clone(),
+                Some(api_key_ref.clone()),
+            )?;
+            publish_provider_pricing_change(
+                &state,
+                &provider,
+                "set_provider_manual_pricing",
+                audit_old,
+                crate::orchestrator::store::EventCode::CONFIG_PROVIDER_PRICING_UPDATED,
+                "provider manual pricing updated",
+                serde_json::json!({
+                    "mode": mode,
+                    "tiers": tiers,
+                }),
+            );
+            Ok(())
+        }
+        _ => Err(CommandError::Validation(
+            "mode must be one of: none, per_request, per_request_tiered, package_total".to_string(),
+        )),
+    }
+}
+
+/// A provider's published price sheet: `{models: [{model, input_usd_per_million, ...}]}`.
+#[derive(Debug, serde::Deserialize)]
+struct ProviderPriceSheet {
+    models: Vec<ProviderPriceSheetModel>,
+}
+
+#[derive(Debug, serde::Deserialize)]
+struct ProviderPriceSheetModel {
+    model: String,
+    #[serde(flatten)]
+    rate: crate::orchestrator::secrets::ProviderModelRate,
+}
+
+fn import_provider_pricing_from_json_impl(
+    state: &app_state::AppState,
+    provider: &str,
+    json: &str,
+) -> Result<usize, CommandError> {
+    if !state.gateway.cfg.read().providers.contains_key(provider) {
+        return Err(CommandError::unknown_provider(provider));
+    }
+    let sheet: ProviderPriceSheet = serde_json::from_str(json)
+        .map_err(|e| CommandError::Validation(format!("invalid price sheet: {e}")))?;
+    let rates = sheet
+        .models
+        .into_iter()
+        .map(|entry| (entry.model.trim().to_string(), entry.rate))
+        .collect::<std::collections::BTreeMap<_, _>>();
+    if rates.is_empty() {
+        return Err(CommandError::Validation(
+            "price sheet has no models".to_string(),
+        ));
+    }
+    let count = rates.len();
+    let audit_old = provider_pricing_audit_value(state, provider);
+    state
+        .secrets
+        .upsert_provider_model_rates(provider, rates)
+        .map_err(CommandError::Validation)?;
+    publish_provider_pricing_change(
+        state,
+        provider,
+        "import_provider_pricing_from_json",
+        audit_old,
+        crate::orchestrator::store::EventCode::CONFIG_PRICING_IMPORTED,
+        "provider model pricing imported",
+        serde_json::json!({ "count": count }),
+    );
+    Ok(count)
+}
+
+#[tauri::command]
+pub(crate) fn import_provider_pricing_from_json(
+    state: tauri::State<'_, app_state::AppState>,
+    provider: String,
+    json: String,
+) -> Result<serde_json::Value, CommandError> {
+    let count = import_provider_pricing_from_json_impl(&state, &provider, &json)?;
+    Ok(serde_json::json!({ "ok": true, "provider": provider, "count": count }))
+}
+
+fn set_provider_model_pricing_impl(
+    state: &app_state::AppState,
+    provider: &str,
+    model: &str,
+    rate: crate::orchestrator::secrets::ProviderModelRate,
+) -> Result<(), CommandError> {
+    if !state.gateway.cfg.read().providers.contains_key(provider) {
+        return Err(CommandError::unknown_provider(provider));
     }
+    let model = model.trim().to_string();
+    let audit_old = provider_pricing_audit_value(state, provider);
+    state
+        .secrets
+        .upsert_provider_model_rates(
+            provider,
+            std::collections::BTreeMap::from([(model.clone(), rate.clone())]),
+        )
+        .map_err(CommandError::Validation)?;
+    publish_provider_pricing_change(
+        state,
+        provider,
+        "set_provider_model_pricing",
+        audit_old,
+        crate::orchestrator::store::EventCode::CONFIG_PROVIDER_MODEL_PRICING_UPDATED,
+        "provider model pricing updated",
+        serde_json::json!({ "model": model, "rate": rate }),
+    );
+    Ok(())
+}
+
+#[tauri::command]
+pub(crate) fn set_provider_model_pricing(
+    state: tauri::State<'_, app_state::AppState>,
+    provider: String,
+    model: String,
+    input_rate: f64,
+    output_rate: f64,
+    cache_rate: Option<f64>,
+    cache_creation_rate: Option<f64>,
+) -> Result<(), CommandError> {
+    set_provider_model_pricing_impl(
+        &state,
+        &provider,
+        &model,
+        crate::orchestrator::secrets::ProviderModelRate {
+            input_usd_per_million: input_rate,
+            output_usd_per_million: output_rate,
+            cache_creation_usd_per_million: cache_creation_rate,
+            cache_read_usd_per_million: cache_rate,
+        },
+    )
+}
+
+fn clear_provider_model_pricing_impl(
+    state: &app_state::AppState,
+    provider: &str,
+    model: &str,
+) -> Result<bool, CommandError> {
+    if !state.gateway.cfg.read().providers.contains_key(provider) {
+        return Err(CommandError::unknown_provider(provider));
+    }
+    let model = model.trim();
+    let audit_old = provider_pricing_audit_value(state, provider);
+    if !state.secrets.remove_provider_model_rate(provider, model)? {
+        return Ok(false);
+    }
+    publish_provider_pricing_change(
+        state,
+        provider,
+        "clear_provider_model_pricing",
+        audit_old,
+        crate::orchestrator::store::EventCode::CONFIG_PROVIDER_MODEL_PRICING_CLEARED,
+        "provider model pricing cleared",
+        serde_json::json!({ "model": model }),
+    );
+    Ok(true)
+}
+
+#[tauri::command]
+pub(crate) fn clear_provider_model_pricing(
+    state: tauri::State<'_, app_state::AppState>,
+    provider: String,
+    model: String,
+) -> Result<serde_json::Value, CommandError> {
+    let removed = clear_provider_model_pricing_impl(&state, &provider, &model)?;
+    Ok(serde_json::json!({
+        "ok": true,
+        "provider": provider,
+        "model": model.trim(),
+        "removed": removed,
+    }))
 }
 
 #[tauri::command]
@@ -718,28 +930,20 @@ pub(crate) fn set_provider_gap_fill(
     provider: String,
     mode: String,
     amount_usd: Option<f64>,
-) -> Result<(), String> {
+) -> Result<(), CommandError> {
     if !state.gateway.cfg.read().providers.contains_key(&provider) {
-        return Err(format!("unknown provider: {provider}"));
+        return Err(CommandError::unknown_provider(&provider));
     }
     let mode = mode.trim().to_lowercase();
+    let audit_old = provider_pricing_audit_value(&state, &provider);
     match mode.as_str() {
         "none" => {
             state.secrets.set_provider_gap_fill(&provider, None, None)?;
-            state
-                .gateway
-                .store
-                .sync_provider_pricing_configs(&state.secrets.list_provider_pricing());
-            if let Err(err) = crate::lan_sync::record_provider_pricing_snapshot(&state, &provider) {
-                state.gateway.store.events().emit(
-                    &provider,
-                    crate::orchestrator::store::EventCode::LAN_EDIT_SYNC_RECORD_FAILED,
-                    &format!("failed to record gap-fill clear for LAN sync: {err}"),
-                    serde_json::Value::Null,
-                );
-            }
-            state.gateway.store.events().emit(
+            publish_provider_pricing_change(
+                &state,
                 &provider,
+                "set_provider_gap_fill",
+                audit_old,
                 crate::orchestrator::store::EventCode::CONFIG_PROVIDER_GAP_FILL_CLEARED,
                 "provider gap-fill pricing cleared",
                 serde_json::Value::Null,
@@ -748,28 +952,23 @@ pub(crate) fn set_provider_gap_fill(
         }
         "per_request" | "total" | "per_day_average" => {
             let Some(v) = amount_usd else {
-                return Err("amount_usd is required".to_string());
+                return Err(CommandError::Validation(
+                    "amount_usd is required".to_string(),
+                ));
             };
             if !v.is_finite() || v <= 0.0 {
-                return Err("amount_usd must be > 0".to_string());
+                return Err(CommandError::Validation(
+                    "amount_usd must be > 0".to_string(),
+                ));
             }
             state
                 .secrets
                 .set_provider_gap_fill(&provider, Some(&mode), Some(v))?;
-            state
-                .gateway
-                .store
-                .sync_provider_pricing_configs(&state.secrets.list_provider_pricing());
-            if let Err(err) = crate::lan_sync::record_provider_pricing_snapshot(&state, &provider) {
-                state.gateway.store.events().emit(
-                    &provider,
-                    crate::orchestrator::store::EventCode::LAN_EDIT_SYNC_RECORD_FAILED,
-                    &format!("failed to record gap-fill update for LAN sync: {err}"),
-                    serde_json::Value::Null,
-                );
-            }
-            state.gateway.store.events().emit(
+            publish_provider_pricing_change(
+                &state,
                 &provider,
+                "set_provider_gap_fill",
+                audit_old,
                 crate::orchestrator::store::EventCode::CONFIG_PROVIDER_GAP_FILL_UPDATED,
                 "provider gap-fill pricing updated",
                 serde_json::json!({
@@ -779,7 +978,9 @@ pub(crate) fn set_provider_gap_fill(
             );
             Ok(())
         }
-        _ => Err("mode must be one of: none, per_request, total, per_day_average".to_string()),
+        _ => Err(CommandError::Validation(
+            "mode must be one of: none, per_request, total, per_day_average".to_string(),
+        )),
     }
 }
 
@@ -787,79 +988,86 @@ pub(crate) fn set_provider_gap_fill(
 pub(crate) async fn get_effective_usage_base(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
-) -> Result<Option<String>, String> {
+) -> Result<Option<String>, CommandError> {
     if !state.gateway.cfg.read().providers.contains_key(&provider) {
-        return Err(format!("unknown provider: {provider}"));
+        return Err(CommandError::unknown_provider(&provider));
     }
     Ok(crate::orchestrator::quota::effective_usage_base(&state.gateway, &provider).await)
 }
 
+async fn test_usage_endpoint_impl(
+    state: &app_state::AppState,
+    provider: &str,
+) -> Result<serde_json::Value, CommandError> {
+    if !state.gateway.cfg.read().providers.contains_key(provider) {
+        return Err(CommandError::unknown_provider(provider));
+    }
+    let effective_usage_base =
+        crate::orchestrator::quota::effective_usage_base(&state.gateway, provider).await;
+    let snap = crate::orchestrator::quota::test_quota_for_provider(&state.gateway, provider).await;
+    let ok = snap.last_error.is_empty() && snap.updated_at_unix_ms > 0;
+    Ok(serde_json::json!({
+        "provider": provider,
+        "ok": ok,
+        "effective_usage_base": effective_usage_base,
+        "error": if ok { None } else { Some(snap.last_error.clone()) },
+        "snapshot": snap.to_json(),
+    }))
+}
+
 #[tauri::command]
-pub(crate) async fn probe_provider(
+pub(crate) async fn test_usage_endpoint(
     state: tauri::State<'_, app_state::AppState>,
     provider: String,
-) -> Result<(), String> {
-    let cfg = state.gateway.cfg.read().clone();
-    let Some(p) = cfg.providers.get(&provider) else {
-        return Err(format!("unknown provider: {provider}"));
-    };
-    let now = unix_ms();
-    state.gateway.router.sync_with_config(&cfg, now);
-    let key = state.secrets.get_provider_key(&provider);
-
-    let (status, _payload) = state
-        .gateway
-        .upstream
-        .get_json(
-            p,
-            "/v1/models",
-            key.as_deref(),
-            None,
-            cfg.routing.request_timeout_seconds,
-        )
-        .await
-        .map_err(|e| {
-            state
-                .gateway
-                .router
-                .mark_failure(&provider, &cfg, &format!("request error: {e}"), now);
-            state.gateway.store.events().emit(
-                &provider,
-                crate::orchestrator::store::EventCode::HEALTH_PROBE_FAILED,
-                "health probe failed (request error)",
-                serde_json::Value::Null,
-            );
-            format!("request error: {e}")
-        })?;
+) -> Result<serde_json::Value, CommandError> {
+    test_usage_endpoint_impl(&state, &provider).await
+}
 
-    if (200..300).contains(&status) {
-        state.gateway.router.mark_success(&provider, now);
-        state.gateway.store.events().emit(
-            &provider,
-            crate::orchestrator::store::EventCode::HEALTH_PROBE_OK,
-            "Provider is reachable and responding",
-            serde_json::Value::Null,
-        );
-        return Ok(());
+#[tauri::command]
+pub(crate) async fn probe_provider(
+    state: tauri::State<'_, app_state::AppState>,
+    provider: String,
+) -> Result<(), CommandError> {
+    match crate::orchestrator::gateway::probe_provider_health(&state.gateway, &provider).await {
+        Ok(_) => Ok(()),
+        Err(crate::orchestrator::gateway::ProviderProbeError::UnknownProvider) => {
+            Err(CommandError::unknown_provider(&provider))
+        }
+        Err(crate::orchestrator::gateway::ProviderProbeError::Upstream {
+            status, message, ..
+        }) => Err(CommandError::upstream(status, message)),
     }
+}
 
-    let err = format!("http {status}");
-    state
-        .gateway
-        .router
-        .mark_failure(&provider, &cfg, &err, now);
-    state.gateway.store.events().emit(
-        &provider,
-        crate::orchestrator::store::EventCode::HEALTH_PROBE_FAILED,
-        "health probe failed",
-        serde_json::Value::Null,
-    );
-    Err(err)
+#[tauri::command]
+pub(crate) async fn warm_providers(
+    state: tauri::State<'_, app_state::AppState>,
+) -> Result<serde_json::Value, CommandError> {
+    let rows = crate::orchestrator::gateway::warm_providers(&state.gateway)
+        .await
+        .into_iter()
+        .map(|(provider, result)| match result {
+            Ok(latency_ms) => serde_json::json!({
+                "provider": provider,
+                "ok": true,
+                "latency_ms": latency_ms,
+            }),
+            Err(error) => serde_json::json!({
+                "provider": provider,
+                "ok": false,
+                "error": error,
+            }),
+        })
+        .collect::<Vec<_>>();
+    Ok(serde_json::json!({ "ok": true, "providers": rows }))
 }
 
 #[cfg(test)]
 mod quota_ops_tests {
-    use super::{set_usage_auth_impl, set_usage_base_url_impl, set_usage_token_impl};
+    use super::{
+        import_provider_pricing_from_json_impl, set_usage_auth_impl, set_usage_base_url_impl,
+        set_usage_token_impl, test_usage_endpoint_impl,
+    };
     use crate::app_state::AppState;
     use crate::orchestrator::quota::{QuotaSnapshot, UsageKind};
 
@@ -896,6 +1104,105 @@ mod quota_ops_tests {
             })
     }
 
+    #[test]
+    fn price_sheet_import_stores_model_rates_and_rejects_negative_rates() {
+        let (_tmp, state) = build_test_state();
+        let rejected = import_provider_pricing_from_json_impl(
+            &state,
+            "provider_1",
+            r#"{"models":[{"model":"gpt-5.2","input_usd_per_million":-1,"output_usd_per_million":10}]}"#,
+        );
+        assert!(rejected.is_err());
+        assert!(state
+            .secrets
+            .list_provider_pricing()
+            .get("provider_1")
+            .is_none());
+
+        let count = import_provider_pricing_from_json_impl(
+            &state,
+            "provider_1",
+            r#"{"models":[
+                {"model":"gpt-5.2","input_usd_per_million":1.25,"output_usd_per_million":10,"cache_read_usd_per_million":0.125},
+                {"model":"gpt-5.2-mini","input_usd_per_million":0.25,"output_usd_per_million":2}
+            ]}"#,
+        )
+        .expect("import price sheet");
+        assert_eq!(count, 2);
+        let rates = state.secrets.list_provider_pricing()["provider_1"]
+            .model_rates
+            .clone();
+        assert_eq!(rates["gpt-5.2"].cache_read_usd_per_million, Some(0.125));
+        assert_eq!(rates["gpt-5.2-mini"].output_usd_per_million, 2.0);
+
+        let imported = state
+            .gateway
+            .store
+            .list_events_range(None, None, Some(50))
+            .into_iter()
+            .find(|event| event["code"] == "config.pricing_imported")
+            .expect("pricing imported event");
+        assert_eq!(imported["fields"]["count"], 2);
+    }
+
+    #[test]
+    fn model_pricing_set_and_clear_round_trip_through_secrets() {
+        let (_tmp, state) = build_test_state();
+        let rate = crate::orchestrator::secrets::ProviderModelRate {
+            input_usd_per_million: 3.0,
+            output_usd_per_million: 15.0,
+            cache_creation_usd_per_million: Some(3.75),
+            cache_read_usd_per_million: None,
+        };
+        assert!(set_provider_model_pricing_impl(&state, "missing", "m", rate.clone()).is_err());
+        assert!(set_provider_model_pricing_impl(
+            &state,
+            "provider_1",
+            "claude-sonnet",
+            crate::orchestrator::secrets::ProviderModelRate {
+                output_usd_per_million: -1.0,
+                ..rate.clone()
+            },
+        )
+        .is_err());
+
+        set_provider_model_pricing_impl(&state, "provider_1", " claude-sonnet ", rate.clone())
+            .expect("set model pricing");
+        assert_eq!(
+            state.secrets.list_provider_pricing()["provider_1"].model_rates["claude-sonnet"],
+            rate
+        );
+
+        assert!(
+            clear_provider_model_pricing_impl(&state, "provider_1", "claude-sonnet")
+                .expect("clear model pricing")
+        );
+        assert!(
+            !clear_provider_model_pricing_impl(&state, "provider_1", "claude-sonnet")
+                .expect("clear missing model pricing")
+        );
+        assert!(state
+            .secrets
+            .list_provider_pricing()
+            .get("provider_1")
+            .is_none());
+        let codes = state
+            .gateway
+            .store
+            .list_events_range(None, None, Some(50))
+            .into_iter()
+            .filter_map(|event| event["code"].as_str().map(ToString::to_string))
+            .collect::<Vec<_>>();
+        assert!(codes.contains(&"config.provider_model_pricing_updated".to_string()));
+        assert_eq!(
+            codes
+                .iter()
+                .filter(|code| *code == "config.provider_model_pricing_cleared")
+                .count(),
+            1
+        );
+    }
+
     #[test]
     fn usage_secret_mutations_are_blocked_while_following_remote() {
         let (_tmp, state) = build_test_state();
@@ -986,4 +1293,27 @@ mod quota_ops_tests {
 
         assert_eq!(stored_usage_kind(&state, "provider_1").as_deref(), Some("none"));
     }
+
+    #[tokio::test]
+    async fn test_usage_endpoint_reports_error_without_persisting_snapshot() {
+        let (_tmp, state) = build_test_state();
+        set_usage_base_url_impl(&state, "provider_1", "http://127.0.0.1:9")
+            .expect("set usage base");
+        seed_usage_snapshot(&state, "provider_1");
+
+        let result = test_usage_endpoint_impl(&state, "provider_1")
+            .await
+            .expect("test usage endpoint");
+
+        assert_eq!(result["ok"], false);
+        assert!(result["error"].as_str().is_some_and(|err| !err.is_empty()));
+        assert!(result["effective_usage_base"]
+            .as_str()
+            .is_some_and(|base| base.starts_with("http://127.0.0.1:9")));
+        assert_eq!(
+            stored_usage_kind(&state, "provider_1").as_deref(),
+            Some("budget_info")
+        );
+        assert!(test_usage_endpoint_impl(&state, "missing").await.is_err());
+    }
 }
@@ -0,0 +1,94 @@
+//! Observer mode (`routing.read_only` / `APIROUTER_READONLY=1`).
+//!
+//! Only the commands listed here keep working; everything else is rejected with
+//! `CommandError::ReadOnly` before it runs. New commands are therefore blocked until they are
+//! added, which is the safe default for a kiosk display.
+
+const READ_ONLY_ALLOWED_COMMANDS: &[&str] = &[
+    "get_status",
+    "get_store_stats",
+    "flush_store",
+    "create_manual_backup",
+    "record_app_startup_stage",
+    "record_web_transport_event",
+    "record_ui_watchdog_heartbeat",
+    "record_ui_trace",
+    "record_ui_diagnostics_batch",
+    "record_ui_slow_refresh",
+    "record_ui_long_task",
+    "record_ui_frame_stall",
+    "record_ui_frontend_error",
+    "record_ui_invoke_result",
+    "open_external_url",
+    "get_local_diagnostics",
+    "get_event_log_entries",
+    "get_audit_log",
+    "get_health_history",
+    "get_event_log_entry_by_id",
+    "get_event_log_years",
+    "get_event_log_daily_stats",
+    "get_config",
+    "get_config_path",
+    "get_config_toml",
+    "fetch_lan_peer_remote_update_debug",
+    "get_remote_peer_diagnostics",
+    "get_gateway_token_preview",
+    "list_gateway_tokens",
+    "get_provider_key_history",
+    "get_provider_timeline",
+    "validate_provider_timeline",
+    "get_provider_schedule",
+    "get_effective_usage_base",
+    "explain_routing",
+    "probe_provider",
+    "refresh_quota",
+    "refresh_quota_shared",
+    "refresh_quota_all",
+    "refresh_wsl_gateway_host",
+    "codex_cli_default_home",
+    "codex_cli_default_wsl_home",
+    "codex_cli_directories_get",
+    "codex_cli_swap_status",
+    "get_codex_cli_config_toml",
+    "provider_switchboard_status",
+    "tailscale_status",
+    "codex_account_refresh",
+    "codex_account_refresh_async",
+    "codex_account_profiles_list",
+    "codex_account_remote_profiles_list",
+    "get_usage_statistics",
+    "get_top_cost_models",
+    "export_usage_jsonl",
+    "get_usage_request_entries",
+    "get_usage_request_summary",
+    "get_usage_request_daily_totals",
+    "get_spend_history",
+    "get_spend_history_weekly",
+    "get_spend_total",
+];
+
+pub(crate) fn command_allowed_in_read_only(command: &str) -> bool {
+    READ_ONLY_ALLOWED_COMMANDS.contains(&command)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::command_allowed_in_read_only;
+
+    #[test]
+    fn read_only_allows_status_and_history_but_not_edits() {
+        for command in ["get_status", "get_usage_statistics", "get_spend_history"] {
+            assert!(command_allowed_in_read_only(command), "{command}");
+        }
+        for command in [
+            "upsert_provider",
+            "set_provider_key",
+            "get_provider_key",
+            "set_provider_manual_pricing",
+            "set_manual_override",
+            "delete_provider",
+        ] {
+            assert!(!command_allowed_in_read_only(command), "{command}");
+        }
+    }
+}
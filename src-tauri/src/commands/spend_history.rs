@@ -87,12 +87,115 @@ fn merge_manual_per_req_for_spend_history_day(
     }
 }
 
+/// Longest window `get_spend_history` will look back over.
+const MAX_SPEND_HISTORY_DAYS: u64 = 365;
+const SPEND_SPIKE_TRAILING_DAYS: i64 = 14;
+const SPEND_SPIKE_MIN_HISTORY_DAYS: usize = 5;
+/// Scales MAD to a standard-deviation estimate for normally distributed data.
+const MAD_TO_SIGMA: f64 = 1.4826;
+
+fn median_of(values: &mut [f64]) -> f64 {
+    values.sort_by(f64::total_cmp);
+    let mid = values.len() / 2;
+    if values.len() % 2 == 0 {
+        (values[mid - 1] + values[mid]) / 2.0
+    } else {
+        values[mid]
+    }
+}
+
+/// Sets `spike` on every row and returns the flagged days. Each provider is compared against its
+/// own rows from the prior `SPEND_SPIKE_TRAILING_DAYS` days using effective cost, or total tokens
+/// when the provider has no priced days. The MAD spread is floored at 5% of the median so a
+/// perfectly flat history does not flag every small bump.
+fn flag_spend_history_spikes(rows: &mut [Value], sigma: f64) -> Vec<Value> {
+    let mut by_provider: BTreeMap<String, Vec<(NaiveDate, usize)>> = BTreeMap::new();
+    for (idx, row) in rows.iter_mut().enumerate() {
+        row["spike"] = Value::Bool(false);
+        let provider = row.get("provider").and_then(Value::as_str).unwrap_or("");
+        let day = row
+            .get("day_key")
+            .and_then(Value::as_str)
+            .and_then(|key| NaiveDate::parse_from_str(key, "%Y-%m-%d").ok());
+        if let Some(day) = day {
+            by_provider
+                .entry(provider.to_string())
+                .or_default()
+                .push((day, idx));
+        }
+    }
+
+    let mut anomalies = Vec::new();
+    for (provider, mut days) in by_provider {
+        days.sort_by_key(|(day, _)| *day);
+        let metric = if days.iter().any(|(_, idx)| {
+            rows[*idx]
+                .get("effective_total_usd")
+                .and_then(Value::as_f64)
+                .is_some()
+        }) {
+            "effective_total_usd"
+        } else {
+            "total_tokens"
+        };
+        let series: Vec<(NaiveDate, usize, Option<f64>)> = days
+            .into_iter()
+            .map(|(day, idx)| (day, idx, rows[idx].get(metric).and_then(Value::as_f64)))
+            .collect();
+        for (pos, (day, idx, value)) in series.iter().enumerate() {
+            let Some(value) = *value else {
+                continue;
+            };
+            let window_start = *day - chrono::Duration::days(SPEND_SPIKE_TRAILING_DAYS);
+            let mut history: Vec<f64> = series[..pos]
+                .iter()
+                .filter(|(prior, _, _)| *prior >= window_start)
+                .filter_map(|(_, _, prior_value)| *prior_value)
+                .collect();
+            if history.len() < SPEND_SPIKE_MIN_HISTORY_DAYS {
+                continue;
+            }
+            let median = median_of(&mut history);
+            let mut deviations: Vec<f64> = history.iter().map(|v| (v - median).abs()).collect();
+            let mad = median_of(&mut deviations);
+            let spread = (mad * MAD_TO_SIGMA).max(median.abs() * 0.05);
+            let threshold = median + sigma * spread;
+            if value > threshold {
+                rows[*idx]["spike"] = Value::Bool(true);
+                anomalies.push(serde_json::json!({
+                    "provider": provider,
+                    "day_key": day.format("%Y-%m-%d").to_string(),
+                    "metric": metric,
+                    "value": value,
+                    "median": median,
+                    "threshold": threshold,
+                }));
+            }
+        }
+    }
+    anomalies.sort_by(|a, b| {
+        let ad = a.get("day_key").and_then(Value::as_str).unwrap_or("");
+        let bd = b.get("day_key").and_then(Value::as_str).unwrap_or("");
+        bd.cmp(ad)
+    });
+    anomalies
+}
+
 #[tauri::command]
 pub(crate) fn get_spend_history(
     state: tauri::State<'_, app_state::AppState>,
     provider: Option<String>,
     days: Option<u64>,
     compact_only: Option<bool>,
+) -> serde_json::Value {
+    get_spend_history_impl(&state, provider, days, compact_only)
+}
+
+fn get_spend_history_impl(
+    state: &app_state::AppState,
+    provider: Option<String>,
+    days: Option<u64>,
+    compact_only: Option<bool>,
 ) -> serde_json::Value {
     fn as_f64(v: Option<&Value>) -> Option<f64> {
         v.and_then(|x| {
@@ -109,7 +212,7 @@ pub(crate) fn get_spend_history(
     }
 
     let now = unix_ms();
-    let keep_days = days.unwrap_or(60).clamp(1, 365);
+    let keep_days = days.unwrap_or(60).clamp(1, MAX_SPEND_HISTORY_DAYS);
     let compact_only = compact_only.unwrap_or(true);
     let since = now.saturating_sub(keep_days.saturating_mul(24 * 60 * 60 * 1000));
     let requested_provider = provider
@@ -122,6 +225,7 @@ pub(crate) fn get_spend_history(
         .map(|s| s.to_ascii_lowercase());
 
     let cfg = state.gateway.cfg.read().clone();
+    let ambiguous = cfg.routing.ambiguous_local_time;
     let mut pricing = state.gateway.store.list_provider_pricing_configs();
     for (provider_name, config) in state.secrets.list_provider_pricing() {
         pricing.insert(provider_name, config);
@@ -267,7 +371,7 @@ pub(crate) fn get_spend_history(
         day_keys.extend(manual_by_day.keys().cloned());
 
         for day_key in day_keys {
-            let Some((day_start, day_end)) = local_day_range_from_key(&day_key) else {
+            let Some((day_start, day_end)) = local_day_range_from_key(&day_key, ambiguous) else {
                 continue;
             };
             if day_start < since || day_start > now {
@@ -306,13 +410,24 @@ pub(crate) fn get_spend_history(
                     )
                 })
             });
-            let scheduled_total = package_total_schedule_by_day(pricing_cfg, day_start, day_end)
-                .remove(&day_key)
-                .filter(|value| value.is_finite() && *value > 0.0);
+            let scheduled_total =
+                package_total_schedule_by_day(pricing_cfg, day_start, day_end, ambiguous)
+                    .remove(&day_key)
+                    .filter(|value| value.is_finite() && *value > 0.0);
             let per_request_total = if req_count > 0 {
                 per_request_amount_at(pricing_cfg, day_start)
                     .filter(|value| value.is_finite() && *value > 0.0)
                     .map(|value| value * req_count as f64)
+                    .or_else(|| {
+                        pricing_cfg
+                            .filter(|cfg| cfg.mode == "per_request_tiered")
+                            .map(|cfg| {
+                                crate::orchestrator::secrets::tiered_request_cost_usd(
+                                    &cfg.tiers, 0, req_count,
+                                )
+                            })
+                            .filter(|value| value.is_finite() && *value > 0.0)
+                    })
             } else {
                 None
             };
@@ -444,14 +559,233 @@ pub(crate) fn get_spend_history(
         }
     });
 
+    let anomalies = flag_spend_history_spikes(&mut rows, cfg.routing.effective_spike_sigma());
+
     serde_json::json!({
         "ok": true,
         "generated_at_unix_ms": now,
         "days": keep_days,
+        "rows": rows,
+        "anomalies": anomalies
+    })
+}
+
+#[tauri::command]
+pub(crate) fn get_spend_history_weekly(
+    state: tauri::State<'_, app_state::AppState>,
+    provider: Option<String>,
+    weeks: Option<u64>,
+) -> serde_json::Value {
+    get_spend_history_weekly_impl(&state, provider, weeks)
+}
+
+/// Rolls the daily spend history into ISO weeks (Monday start, local time like the day keys),
+/// so each weekly total is exactly the sum of the daily rows it covers.
+fn get_spend_history_weekly_impl(
+    state: &app_state::AppState,
+    provider: Option<String>,
+    weeks: Option<u64>,
+) -> serde_json::Value {
+    use chrono::Datelike;
+
+    let keep_weeks = weeks.unwrap_or(12).clamp(1, 52);
+    let ambiguous = state.gateway.cfg.read().routing.ambiguous_local_time;
+    let today = Local::now().date_naive();
+    let first_week_start = today
+        - chrono::Duration::days(
+            i64::from(today.weekday().num_days_from_monday()) + 7 * (keep_weeks as i64 - 1),
+        );
+    let days = (today - first_week_start).num_days().max(0) as u64 + 1;
+    let daily = get_spend_history_impl(state, provider, Some(days), None);
+
+    // (provider, week start) -> (req_count, total_tokens, effective_total_usd)
+    let mut by_week: BTreeMap<(String, NaiveDate), (u64, u64, Option<f64>)> = BTreeMap::new();
+    for row in daily
+        .get("rows")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let (Some(provider_name), Some(date)) = (
+            row.get("provider").and_then(Value::as_str),
+            row.get("day_key")
+                .and_then(Value::as_str)
+                .and_then(|key| NaiveDate::parse_from_str(key, "%Y-%m-%d").ok()),
+        ) else {
+            continue;
+        };
+        let week_start =
+            date - chrono::Duration::days(i64::from(date.weekday().num_days_from_monday()));
+        if week_start < first_week_start {
+            continue;
+        }
+        let entry = by_week
+            .entry((provider_name.to_string(), week_start))
+            .or_insert((0, 0, None));
+        entry.0 = entry
+            .0
+            .saturating_add(row.get("req_count").and_then(Value::as_u64).unwrap_or(0));
+        entry.1 = entry
+            .1
+            .saturating_add(row.get("total_tokens").and_then(Value::as_u64).unwrap_or(0));
+        if let Some(day_total) = row.get("effective_total_usd").and_then(Value::as_f64) {
+            entry.2 = Some(entry.2.unwrap_or(0.0) + day_total);
+        }
+    }
+
+    let mut rows: Vec<Value> = by_week
+        .into_iter()
+        .filter_map(
+            |((provider_name, week_start), (req_count, total_tokens, total))| {
+                let (week_start_unix_ms, _) = local_day_range_from_key(
+                    &week_start.format("%Y-%m-%d").to_string(),
+                    ambiguous,
+                )?;
+                Some(serde_json::json!({
+                    "provider": provider_name,
+                    "week_key": week_start.format("%G-W%V").to_string(),
+                    "week_start_unix_ms": week_start_unix_ms,
+                    "req_count": req_count,
+                    "total_tokens": total_tokens,
+                    "effective_total_usd": total.map(|v| (v * 1000.0).round() / 1000.0),
+                }))
+            },
+        )
+        .collect();
+    rows.sort_by(|a, b| {
+        let aw = a
+            .get("week_start_unix_ms")
+            .and_then(Value::as_u64)
+            .unwrap_or(0);
+        let bw = b
+            .get("week_start_unix_ms")
+            .and_then(Value::as_u64)
+            .unwrap_or(0);
+        bw.cmp(&aw).then_with(|| {
+            let ap = a.get("provider").and_then(Value::as_str).unwrap_or("");
+            let bp = b.get("provider").and_then(Value::as_str).unwrap_or("");
+            ap.cmp(bp)
+        })
+    });
+
+    serde_json::json!({
+        "ok": true,
+        "generated_at_unix_ms": daily.get("generated_at_unix_ms").cloned().unwrap_or(Value::Null),
+        "weeks": keep_weeks,
         "rows": rows
     })
 }
 
+#[tauri::command]
+pub(crate) fn get_spend_total(
+    state: tauri::State<'_, app_state::AppState>,
+    provider: Option<String>,
+    from_unix_ms: u64,
+    to_unix_ms: u64,
+) -> Result<serde_json::Value, String> {
+    get_spend_total_impl(&state, provider, from_unix_ms, to_unix_ms)
+}
+
+/// Sums the daily spend history over `[from_unix_ms, to_unix_ms)`. Request and token counts come
+/// straight from the usage requests inside the range. Costs of edge days only count the share of
+/// the day that falls inside the range (today's share is measured against the elapsed part of
+/// the day). Ranges starting before the daily history window are rejected rather than truncated.
+fn get_spend_total_impl(
+    state: &app_state::AppState,
+    provider: Option<String>,
+    from_unix_ms: u64,
+    to_unix_ms: u64,
+) -> Result<serde_json::Value, String> {
+    const DAY_MS: u64 = 24 * 60 * 60 * 1000;
+
+    if from_unix_ms >= to_unix_ms {
+        return Err("from_unix_ms must be earlier than to_unix_ms".to_string());
+    }
+    let ambiguous = state.gateway.cfg.read().routing.ambiguous_local_time;
+    let now = unix_ms();
+    let days = now.saturating_sub(from_unix_ms).div_ceil(DAY_MS) + 1;
+    if days > MAX_SPEND_HISTORY_DAYS {
+        // The daily history stops there; summing it would silently undercount the range.
+        return Err(format!(
+            "from_unix_ms is more than {MAX_SPEND_HISTORY_DAYS} days ago; spend totals only cover the last {MAX_SPEND_HISTORY_DAYS} days"
+        ));
+    }
+    let daily = get_spend_history_impl(state, provider, Some(days), None);
+
+    // provider -> effective_total_usd
+    let mut by_provider: BTreeMap<String, Option<f64>> = BTreeMap::new();
+    for row in daily
+        .get("rows")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+    {
+        let (Some(provider_name), Some((day_start, day_end))) = (
+            row.get("provider").and_then(Value::as_str),
+            row.get("day_key")
+                .and_then(Value::as_str)
+                .and_then(|key| local_day_range_from_key(key, ambiguous)),
+        ) else {
+            continue;
+        };
+        let span_end = day_end.min(now.max(day_start + 1));
+        let overlap = to_unix_ms
+            .min(span_end)
+            .saturating_sub(from_unix_ms.max(day_start));
+        if overlap == 0 {
+            continue;
+        }
+        let share = overlap as f64 / span_end.saturating_sub(day_start) as f64;
+        let entry = by_provider.entry(provider_name.to_string()).or_insert(None);
+        if let Some(day_total) = row.get("effective_total_usd").and_then(Value::as_f64) {
+            *entry = Some(entry.unwrap_or(0.0) + day_total * share);
+        }
+    }
+
+    let round3 = |v: f64| (v * 1000.0).round() / 1000.0;
+    let mut req_count = 0u64;
+    let mut total_tokens = 0u64;
+    let mut total_usd: Option<f64> = None;
+    let rows: Vec<Value> = by_provider
+        .into_iter()
+        .map(|(provider_name, total)| {
+            let (reqs, _, _, tokens, _, _) = state.gateway.store.summarize_usage_requests(
+                from_unix_ms,
+                Some(from_unix_ms),
+                Some(to_unix_ms),
+                &[],
+                std::slice::from_ref(&provider_name),
+                &[],
+                &[],
+                &[],
+                &[],
+            );
+            req_count = req_count.saturating_add(reqs);
+            total_tokens = total_tokens.saturating_add(tokens);
+            if let Some(total) = total {
+                total_usd = Some(total_usd.unwrap_or(0.0) + total);
+            }
+            serde_json::json!({
+                "provider": provider_name,
+                "req_count": reqs,
+                "total_tokens": tokens,
+                "effective_total_usd": total.map(round3),
+            })
+        })
+        .collect();
+
+    Ok(serde_json::json!({
+        "ok": true,
+        "generated_at_unix_ms": now,
+        "from_unix_ms": from_unix_ms,
+        "to_unix_ms": to_unix_ms,
+        "req_count": req_count,
+        "total_tokens": total_tokens,
+        "total_usd": total_usd.map(round3),
+        "by_provider": rows
+    }))
+}
+
 fn merge_usage_history_day_counts(
     usage_by_day: &mut BTreeMap<String, (u64, u64, u64)>,
     usage_by_day_from_req: BTreeMap<String, (u64, u64, u64)>,
@@ -494,6 +828,55 @@ fn tracked_spend_day_matches_history_target(
         && tracked_spend_day_source_node_id(day, local_node_id) == target_source_node_id
 }
 
+/// Longest range `set_spend_manual_range` writes in one call.
+const MAX_SPEND_MANUAL_RANGE_DAYS: i64 = 366;
+
+fn validate_spend_day_key(day_key: &str) -> Result<String, String> {
+    let day_key = day_key.trim().to_string();
+    if local_day_range_from_key(&day_key, AmbiguousLocalTime::default()).is_none() {
+        return Err("day_key must be YYYY-MM-DD".to_string());
+    }
+    Ok(day_key)
+}
+
+fn manual_total_usd_value(total_used_usd: Option<f64>) -> Option<f64> {
+    total_used_usd.filter(|v| v.is_finite() && *v != 0.0)
+}
+
+fn put_spend_manual_day_entry(
+    state: &app_state::AppState,
+    provider: &str,
+    day_key: &str,
+    total_used_usd: Option<f64>,
+    usd_per_req: Option<f64>,
+) {
+    let row = serde_json::json!({
+        "provider": provider,
+        "day_key": day_key,
+        "manual_total_usd": total_used_usd,
+        "manual_usd_per_req": usd_per_req,
+        "updated_at_unix_ms": unix_ms()
+    });
+    state
+        .gateway
+        .store
+        .put_spend_manual_day(provider, day_key, &row);
+    if let Err(err) = crate::lan_sync::record_spend_manual_day(
+        state,
+        provider,
+        day_key,
+        total_used_usd,
+        usd_per_req,
+    ) {
+        state.gateway.store.events().emit(
+            provider,
+            crate::orchestrator::store::EventCode::LAN_EDIT_SYNC_RECORD_FAILED,
+            &format!("failed to record spend manual update for LAN sync: {err}"),
+            serde_json::json!({ "day_key": day_key }),
+        );
+    }
+}
+
 #[tauri::command]
 pub(crate) fn set_spend_history_entry(
     state: tauri::State<'_, app_state::AppState>,
@@ -505,13 +888,8 @@ pub(crate) fn set_spend_history_entry(
     if !state.gateway.cfg.read().providers.contains_key(&provider) {
         return Err(format!("unknown provider: {provider}"));
     }
-    let day_key = day_key.trim().to_string();
-    if local_day_range_from_key(&day_key).is_none() {
-        return Err("day_key must be YYYY-MM-DD".to_string());
-    }
-    let total_used_usd = total_used_usd
-        .filter(|v| v.is_finite() && *v != 0.0)
-        .or(None);
+    let day_key = validate_spend_day_key(&day_key)?;
+    let total_used_usd = manual_total_usd_value(total_used_usd);
     let usd_per_req = usd_per_req.filter(|v| v.is_finite() && *v > 0.0).or(None);
 
     if total_used_usd.is_none() && usd_per_req.is_none() {
@@ -538,31 +916,7 @@ pub(crate) fn set_spend_history_entry(
         return Ok(());
     }
 
-    let row = serde_json::json!({
-        "provider": provider,
-        "day_key": day_key,
-        "manual_total_usd": total_used_usd,
-        "manual_usd_per_req": usd_per_req,
-        "updated_at_unix_ms": unix_ms()
-    });
-    state
-        .gateway
-        .store
-        .put_spend_manual_day(&provider, &day_key, &row);
-    if let Err(err) = crate::lan_sync::record_spend_manual_day(
-        &state,
-        &provider,
-        &day_key,
-        total_used_usd,
-        usd_per_req,
-    ) {
-        state.gateway.store.events().emit(
-            &provider,
-            crate::orchestrator::store::EventCode::LAN_EDIT_SYNC_RECORD_FAILED,
-            &format!("failed to record spend manual update for LAN sync: {err}"),
-            serde_json::json!({ "day_key": day_key }),
-        );
-    }
+    put_spend_manual_day_entry(&state, &provider, &day_key, total_used_usd, usd_per_req);
     state.gateway.store.events().emit(
         &provider,
         crate::orchestrator::store::EventCode::USAGE_SPEND_HISTORY_ENTRY_UPDATED,
@@ -585,7 +939,7 @@ fn remove_tracked_spend_history_entries_impl(
         return Err(format!("unknown provider: {provider}"));
     }
     let day_key = day_key.trim().to_string();
-    if local_day_range_from_key(&day_key).is_none() {
+    if local_day_range_from_key(&day_key, AmbiguousLocalTime::default()).is_none() {
         return Err("day_key must be YYYY-MM-DD".to_string());
     }
     let removed =
@@ -625,6 +979,153 @@ pub(crate) fn remove_tracked_spend_history_entries(
     remove_tracked_spend_history_entries_impl(&state, &provider, &day_key)
 }
 
+fn clear_spend_manual_days_impl(
+    state: &app_state::AppState,
+    provider: &str,
+    from_day_key: &str,
+    to_day_key: &str,
+) -> Result<usize, String> {
+    if !state.gateway.cfg.read().providers.contains_key(provider) {
+        return Err(format!("unknown provider: {provider}"));
+    }
+    let from_day_key = from_day_key.trim().to_string();
+    let to_day_key = to_day_key.trim().to_string();
+    let (Some((from_start, _)), Some((to_start, _))) = (
+        local_day_range_from_key(&from_day_key, AmbiguousLocalTime::default()),
+        local_day_range_from_key(&to_day_key, AmbiguousLocalTime::default()),
+    ) else {
+        return Err("from_day_key and to_day_key must be YYYY-MM-DD".to_string());
+    };
+    if from_start > to_start {
+        return Err("from_day_key must not be after to_day_key".to_string());
+    }
+    let removed_day_keys =
+        state
+            .gateway
+            .store
+            .remove_spend_manual_days_in_range(provider, &from_day_key, &to_day_key);
+    for day_key in &removed_day_keys {
+        if let Err(err) =
+            crate::lan_sync::record_spend_manual_day(state, provider, day_key, None, None)
+        {
+            state.gateway.store.events().emit(
+                provider,
+                crate::orchestrator::store::EventCode::LAN_EDIT_SYNC_RECORD_FAILED,
+                &format!("failed to record spend manual clear for LAN sync: {err}"),
+                serde_json::json!({ "day_key": day_key }),
+            );
+        }
+    }
+
+    state.gateway.store.events().emit(
+        provider,
+        crate::orchestrator::store::EventCode::USAGE_SPEND_HISTORY_RANGE_CLEARED,
+        "spend history manual entries cleared",
+        serde_json::json!({
+            "from_day_key": from_day_key,
+            "to_day_key": to_day_key,
+            "removed": removed_day_keys.len()
+        }),
+    );
+    Ok(removed_day_keys.len())
+}
+
+#[tauri::command]
+pub(crate) fn clear_spend_manual_days(
+    state: tauri::State<'_, app_state::AppState>,
+    provider: String,
+    from_day_key: String,
+    to_day_key: String,
+) -> Result<usize, String> {
+    clear_spend_manual_days_impl(&state, &provider, &from_day_key, &to_day_key)
+}
+
+fn set_spend_manual_range_impl(
+    state: &app_state::AppState,
+    provider: &str,
+    from_day_key: &str,
+    to_day_key: &str,
+    usd_per_day: f64,
+) -> Result<usize, String> {
+    if !state.gateway.cfg.read().providers.contains_key(provider) {
+        return Err(format!("unknown provider: {provider}"));
+    }
+    let from_day_key = validate_spend_day_key(from_day_key)?;
+    let to_day_key = validate_spend_day_key(to_day_key)?;
+    let Some(usd_per_day) = manual_total_usd_value(Some(usd_per_day)) else {
+        return Err("usd_per_day must be a non-zero number".to_string());
+    };
+    let (Ok(from), Ok(to)) = (
+        NaiveDate::parse_from_str(&from_day_key, "%Y-%m-%d"),
+        NaiveDate::parse_from_str(&to_day_key, "%Y-%m-%d"),
+    ) else {
+        return Err("day_key must be YYYY-MM-DD".to_string());
+    };
+    if from > to {
+        return Err("from_day_key must not be after to_day_key".to_string());
+    }
+    if (to - from).num_days() >= MAX_SPEND_MANUAL_RANGE_DAYS {
+        return Err(format!(
+            "range must not exceed {MAX_SPEND_MANUAL_RANGE_DAYS} days"
+        ));
+    }
+
+    let day_keys: Vec<String> = from
+        .iter_days()
+        .take_while(|day| *day <= to)
+        .map(|day| day.format("%Y-%m-%d").to_string())
+        .collect();
+    for day_key in &day_keys {
+        put_spend_manual_day_entry(state, provider, day_key, Some(usd_per_day), None);
+    }
+
+    state.gateway.store.events().emit(
+        provider,
+        crate::orchestrator::store::EventCode::USAGE_SPEND_HISTORY_RANGE_UPDATED,
+        "spend history manual entries updated",
+        serde_json::json!({
+            "from_day_key": from_day_key,
+            "to_day_key": to_day_key,
+            "manual_total_usd": usd_per_day,
+            "days": day_keys.len()
+        }),
+    );
+    Ok(day_keys.len())
+}
+
+#[tauri::command]
+pub(crate) fn set_spend_manual_range(
+    state: tauri::State<'_, app_state::AppState>,
+    provider: String,
+    from_day_key: String,
+    to_day_key: String,
+    usd_per_day: f64,
+) -> Result<usize, String> {
+    set_spend_manual_range_impl(&state, &provider, &from_day_key, &to_day_key, usd_per_day)
+}
+
+fn rebuild_usage_day_aggregates_impl(state: &app_state::AppState, provider: Option<&str>) -> usize {
+    let provider = provider.map(str::trim).filter(|value| !value.is_empty());
+    let rebuilt = state.gateway.store.rebuild_usage_day_aggregates(provider);
+    state.gateway.store.events().emit(
+        provider.unwrap_or("gateway"),
+        crate::orchestrator::store::EventCode::USAGE_DAY_AGGREGATES_REBUILT,
+        "usage day aggregates rebuilt from raw requests",
+        serde_json::json!({ "days": rebuilt }),
+    );
+    rebuilt
+}
+
+/// Repairs drifted `usage_day` aggregates. Providers that were since removed are accepted, since
+/// their history still shows in spend history.
+#[tauri::command]
+pub(crate) fn rebuild_usage_day_aggregates(
+    state: tauri::State<'_, app_state::AppState>,
+    provider: Option<String>,
+) -> usize {
+    rebuild_usage_day_aggregates_impl(&state, provider.as_deref())
+}
+
 #[cfg(test)]
 mod spend_history_tests {
     use std::collections::BTreeMap;
@@ -632,15 +1133,18 @@ mod spend_history_tests {
     use chrono::{Local, LocalResult, TimeZone};
 
     use crate::lan_sync::{self, LanNodeIdentity, LanSyncRuntime};
-    use crate::orchestrator::config::{AppConfig, ProviderConfig};
+    use crate::orchestrator::config::{AmbiguousLocalTime, AppConfig, ProviderConfig};
     use crate::orchestrator::secrets::{
         resolve_provider_pricing_config, ProviderPricingConfig, ProviderPricingPeriod,
     };
 
     use super::{
-        include_compact_spend_history_row, merge_manual_per_req_for_spend_history_day,
-        merge_usage_history_day_counts,
-        remove_tracked_spend_history_entries_impl, spend_history_provider_names,
+        clear_spend_manual_days_impl, flag_spend_history_spikes, get_spend_history_impl,
+        get_spend_history_weekly_impl, get_spend_total_impl,
+        include_compact_spend_history_row, local_day_range_from_key,
+        merge_manual_per_req_for_spend_history_day, merge_usage_history_day_counts,
+        remove_tracked_spend_history_entries_impl, set_spend_manual_range_impl,
+        spend_history_provider_names,
         tracked_spend_day_matches_history_target, tracked_spend_days_with_remote_fallback,
         tracked_spend_history_day_key, tracked_spend_history_snapshot,
     };
@@ -685,6 +1189,8 @@ mod spend_history_tests {
                 }],
                 gap_fill_mode: None,
                 gap_fill_amount_usd: None,
+                tiers: Vec::new(),
+                model_rates: std::collections::BTreeMap::new(),
             },
         )]);
 
@@ -699,6 +1205,208 @@ mod spend_history_tests {
         assert_eq!(resolved.expect("pricing").amount_usd, 56.10);
     }
 
+    #[test]
+    fn weekly_spend_history_sums_daily_effective_totals_per_iso_week() {
+        use chrono::Datelike;
+
+        let (_tmp, state) = build_test_state();
+        let today = Local::now().date_naive();
+        let this_monday =
+            today - chrono::Duration::days(i64::from(today.weekday().num_days_from_monday()));
+        let last_monday = this_monday - chrono::Duration::days(7);
+        let last_tuesday = last_monday + chrono::Duration::days(1);
+        for (date, total) in [(last_monday, 1.25), (last_tuesday, 2.5), (this_monday, 4.0)] {
+            let day_key = date.format("%Y-%m-%d").to_string();
+            state.gateway.store.put_spend_manual_day(
+                "provider_1",
+                &day_key,
+                &serde_json::json!({
+                    "provider": "provider_1",
+                    "day_key": day_key,
+                    "manual_total_usd": total,
+                    "manual_usd_per_req": null,
+                    "updated_at_unix_ms": 1,
+                }),
+            );
+        }
+
+        let weekly = get_spend_history_weekly_impl(&state, Some("provider_1".to_string()), Some(2));
+        let rows = weekly
+            .get("rows")
+            .and_then(serde_json::Value::as_array)
+            .expect("rows");
+        assert_eq!(rows.len(), 2);
+        assert_eq!(
+            rows[0].get("week_key").and_then(serde_json::Value::as_str),
+            Some(this_monday.format("%G-W%V").to_string().as_str())
+        );
+        assert_eq!(rows[0]["effective_total_usd"], serde_json::json!(4.0));
+        assert_eq!(
+            rows[1].get("week_key").and_then(serde_json::Value::as_str),
+            Some(last_monday.format("%G-W%V").to_string().as_str())
+        );
+        assert_eq!(rows[1]["effective_total_usd"], serde_json::json!(3.75));
+
+        let daily = get_spend_history_impl(&state, Some("provider_1".to_string()), Some(14), None);
+        let daily_sum: f64 = daily["rows"]
+            .as_array()
+            .expect("daily rows")
+            .iter()
+            .filter_map(|row| row["effective_total_usd"].as_f64())
+            .sum();
+        assert_eq!(daily_sum, 4.0 + 3.75);
+    }
+
+    #[test]
+    fn spend_total_prorates_edge_days_and_breaks_down_by_provider() {
+        let (_tmp, state) = build_test_state();
+        let today = Local::now().date_naive();
+        let two_days_ago = today - chrono::Duration::days(2);
+        let yesterday = today - chrono::Duration::days(1);
+        for (provider, date, total) in [
+            ("provider_1", two_days_ago, 4.0),
+            ("provider_1", yesterday, 2.0),
+            ("provider_2", yesterday, 1.5),
+        ] {
+            let day_key = date.format("%Y-%m-%d").to_string();
+            state.gateway.store.put_spend_manual_day(
+                provider,
+                &day_key,
+                &serde_json::json!({
+                    "provider": provider,
+                    "day_key": day_key,
+                    "manual_total_usd": total,
+                    "manual_usd_per_req": null,
+                    "updated_at_unix_ms": 1,
+                }),
+            );
+        }
+        let ambiguous = AmbiguousLocalTime::default();
+        let (start, end) =
+            local_day_range_from_key(&two_days_ago.format("%Y-%m-%d").to_string(), ambiguous)
+                .expect("two days ago range");
+        let (_, yesterday_end) =
+            local_day_range_from_key(&yesterday.format("%Y-%m-%d").to_string(), ambiguous)
+                .expect("yesterday range");
+        let from = start + (end - start) / 2;
+
+        let total = get_spend_total_impl(&state, None, from, yesterday_end).expect("total");
+        assert_eq!(total["total_usd"], serde_json::json!(5.5));
+        let by_provider = total["by_provider"].as_array().expect("by_provider");
+        assert_eq!(by_provider.len(), 2);
+        assert_eq!(by_provider[0]["provider"], serde_json::json!("provider_1"));
+        assert_eq!(
+            by_provider[0]["effective_total_usd"],
+            serde_json::json!(4.0)
+        );
+        assert_eq!(by_provider[1]["provider"], serde_json::json!("provider_2"));
+        assert_eq!(
+            by_provider[1]["effective_total_usd"],
+            serde_json::json!(1.5)
+        );
+
+        let filtered =
+            get_spend_total_impl(&state, Some("provider_2".to_string()), from, yesterday_end)
+                .expect("filtered total");
+        assert_eq!(filtered["total_usd"], serde_json::json!(1.5));
+
+        assert!(get_spend_total_impl(&state, None, yesterday_end, from).is_err());
+        let too_old = crate::orchestrator::store::unix_ms() - 400 * 24 * 60 * 60 * 1000;
+        assert!(get_spend_total_impl(&state, None, too_old, yesterday_end).is_err());
+    }
+
+    #[test]
+    fn spend_total_counts_requests_inside_the_range_exactly() {
+        use crate::orchestrator::store::UsageRequestSyncRow;
+
+        const HOUR_MS: u64 = 60 * 60 * 1000;
+        let (_tmp, state) = build_test_state();
+        let yesterday = (Local::now().date_naive() - chrono::Duration::days(1))
+            .format("%Y-%m-%d")
+            .to_string();
+        let (day_start, _) = local_day_range_from_key(&yesterday, AmbiguousLocalTime::default())
+            .expect("yesterday range");
+        let rows: Vec<UsageRequestSyncRow> = [1, 2, 3, 20]
+            .into_iter()
+            .map(|hour| UsageRequestSyncRow {
+                model: "gpt-5".to_string(),
+                ..UsageRequestSyncRow::test_row(
+                    format!("row-{hour}"),
+                    "provider_1",
+                    day_start + hour * HOUR_MS,
+                )
+            })
+            .collect();
+        state.gateway.store.upsert_usage_request_sync_rows(&rows);
+
+        // Prorating the day would report 2 of the 4 requests; three fall inside the range.
+        let total = get_spend_total_impl(
+            &state,
+            Some("provider_1".to_string()),
+            day_start,
+            day_start + 12 * HOUR_MS,
+        )
+        .expect("total");
+        assert_eq!(total["req_count"], serde_json::json!(3));
+        assert_eq!(total["total_tokens"], serde_json::json!(45));
+    }
+
+    #[test]
+    fn spend_history_and_total_price_tiered_requests_per_day() {
+        use crate::orchestrator::secrets::ProviderPricingTier;
+        use crate::orchestrator::store::UsageRequestSyncRow;
+
+        let (_tmp, state) = build_test_state();
+        state
+            .secrets
+            .set_provider_pricing_tiered(
+                "provider_1",
+                vec![
+                    ProviderPricingTier {
+                        up_to_requests: Some(10),
+                        usd_per_request: 0.05,
+                    },
+                    ProviderPricingTier {
+                        up_to_requests: None,
+                        usd_per_request: 0.01,
+                    },
+                ],
+                None,
+            )
+            .expect("tiered pricing");
+        let yesterday = (Local::now().date_naive() - chrono::Duration::days(1))
+            .format("%Y-%m-%d")
+            .to_string();
+        let (day_start, day_end) =
+            local_day_range_from_key(&yesterday, AmbiguousLocalTime::default())
+                .expect("yesterday range");
+        let rows: Vec<UsageRequestSyncRow> = (0..12u64)
+            .map(|idx| UsageRequestSyncRow {
+                model: "gpt-5".to_string(),
+                ..UsageRequestSyncRow::test_row(
+                    format!("row-{idx}"),
+                    "provider_1",
+                    day_start + 60_000 * (idx + 1),
+                )
+            })
+            .collect();
+        state.gateway.store.upsert_usage_request_sync_rows(&rows);
+
+        // Ten requests at the first tier's rate, two more at the open-ended tier's rate.
+        let daily = get_spend_history_impl(&state, Some("provider_1".to_string()), Some(3), None);
+        let row = daily["rows"]
+            .as_array()
+            .expect("rows")
+            .iter()
+            .find(|row| row["day_key"] == serde_json::json!(yesterday))
+            .expect("yesterday row");
+        assert_eq!(row["req_count"], serde_json::json!(12));
+        assert_eq!(row["effective_total_usd"], serde_json::json!(0.52));
+
+        let total = get_spend_total_impl(&state, None, day_start, day_end).expect("total");
+        assert_eq!(total["total_usd"], serde_json::json!(0.52));
+    }
+
     #[test]
     fn resolves_history_per_request_pricing_by_api_key_ref_when_provider_was_renamed() {
         let pricing = std::collections::BTreeMap::from([(
@@ -716,6 +1424,8 @@ mod spend_history_tests {
                 }],
                 gap_fill_mode: None,
                 gap_fill_amount_usd: None,
+                tiers: Vec::new(),
+                model_rates: std::collections::BTreeMap::new(),
             },
         )]);
 
@@ -799,6 +1509,8 @@ mod spend_history_tests {
                 }],
                 gap_fill_mode: None,
                 gap_fill_amount_usd: None,
+                tiers: Vec::new(),
+                model_rates: std::collections::BTreeMap::new(),
             },
         )]);
 
@@ -808,8 +1520,11 @@ mod spend_history_tests {
             Some("sk-tPN******hxNs"),
             1_700_100_000_000,
         );
-        let per_request =
-            crate::orchestrator::secrets::pricing_per_request_amount_at(resolved, 1_700_100_000_000);
+        let per_request = crate::orchestrator::secrets::pricing_per_request_amount_at(
+            resolved,
+            1_700_100_000_000,
+            0,
+        );
 
         assert_eq!(per_request, Some(0.035));
         let total = per_request.map(|v| (v * 100.0 * 1000.0).round() / 1000.0);
@@ -834,6 +1549,14 @@ mod spend_history_tests {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -847,6 +1570,14 @@ mod spend_history_tests {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -860,6 +1591,14 @@ mod spend_history_tests {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]);
@@ -897,6 +1636,14 @@ mod spend_history_tests {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]);
         cfg.provider_order = vec!["official".to_string()];
@@ -1322,4 +2069,144 @@ mod spend_history_tests {
                 && event.entity_id.ends_with("|2026-04-01")
         }));
     }
+
+    #[test]
+    fn clear_spend_manual_days_validates_range_and_emits_one_event() {
+        let (_tmp, state) = build_test_state();
+        let provider = "provider_1";
+        for day_key in ["2026-04-01", "2026-04-02", "2026-04-05"] {
+            state.gateway.store.put_spend_manual_day(
+                provider,
+                day_key,
+                &serde_json::json!({
+                    "provider": provider,
+                    "day_key": day_key,
+                    "manual_total_usd": 1.0
+                }),
+            );
+        }
+
+        assert!(
+            clear_spend_manual_days_impl(&state, provider, "2026-04-03", "2026-04-01").is_err()
+        );
+        assert!(
+            clear_spend_manual_days_impl(&state, provider, "04/01/2026", "2026-04-02").is_err()
+        );
+
+        let removed = clear_spend_manual_days_impl(&state, provider, "2026-04-01", "2026-04-02")
+            .expect("clear range");
+
+        assert_eq!(removed, 2);
+        assert_eq!(
+            state
+                .gateway
+                .store
+                .list_local_spend_manual_days(provider)
+                .len(),
+            1
+        );
+        let cleared_events = state
+            .gateway
+            .store
+            .list_events_range(None, None, Some(50))
+            .into_iter()
+            .filter(|event| event["code"] == "usage.spend_history_range_cleared")
+            .collect::<Vec<_>>();
+        assert_eq!(cleared_events.len(), 1);
+        assert_eq!(cleared_events[0]["fields"]["removed"], 2);
+    }
+
+    #[test]
+    fn set_spend_manual_range_overwrites_each_day_in_range() {
+        let (_tmp, state) = build_test_state();
+        let provider = "provider_1";
+        state.gateway.store.put_spend_manual_day(
+            provider,
+            "2026-04-02",
+            &serde_json::json!({
+                "provider": provider,
+                "day_key": "2026-04-02",
+                "manual_usd_per_req": 0.5
+            }),
+        );
+
+        assert!(
+            set_spend_manual_range_impl(&state, provider, "2026-04-03", "2026-04-01", 2.0).is_err()
+        );
+        assert!(set_spend_manual_range_impl(
+            &state,
+            provider,
+            "2026-04-01",
+            "2026-04-03",
+            f64::NAN
+        )
+        .is_err());
+
+        let written =
+            set_spend_manual_range_impl(&state, provider, "2026-03-31", "2026-04-02", 2.0)
+                .expect("set range");
+
+        assert_eq!(written, 3);
+        let days = state.gateway.store.list_local_spend_manual_days(provider);
+        assert_eq!(
+            days.iter()
+                .filter_map(|row| row.get("day_key").and_then(|value| value.as_str()))
+                .collect::<Vec<_>>(),
+            vec!["2026-03-31", "2026-04-01", "2026-04-02"]
+        );
+        assert!(days.iter().all(|row| {
+            row.get("manual_total_usd").and_then(|value| value.as_f64()) == Some(2.0)
+                && row
+                    .get("manual_usd_per_req")
+                    .is_some_and(|value| value.is_null())
+        }));
+    }
+
+    #[test]
+    fn spend_history_flags_days_far_above_trailing_median() {
+        let mut rows: Vec<Value> = (1..=10)
+            .map(|day| {
+                let cost = match day {
+                    9 => 12.0,
+                    _ => 1.0 + (day % 3) as f64 * 0.1,
+                };
+                serde_json::json!({
+                    "provider": "p1",
+                    "day_key": format!("2026-05-{day:02}"),
+                    "total_tokens": 1000,
+                    "effective_total_usd": cost,
+                })
+            })
+            .chain((1..=10).map(|day| {
+                serde_json::json!({
+                    "provider": "p2",
+                    "day_key": format!("2026-05-{day:02}"),
+                    "total_tokens": if day == 10 { 90_000 } else { 10_000 + day * 100 },
+                    "effective_total_usd": null,
+                })
+            }))
+            .collect();
+
+        let anomalies = flag_spend_history_spikes(&mut rows, 3.0);
+
+        let flagged: Vec<(&str, &str)> = rows
+            .iter()
+            .filter(|row| row["spike"] == true)
+            .map(|row| {
+                (
+                    row["provider"].as_str().unwrap_or(""),
+                    row["day_key"].as_str().unwrap_or(""),
+                )
+            })
+            .collect();
+        assert_eq!(flagged, vec![("p1", "2026-05-09"), ("p2", "2026-05-10")]);
+        assert_eq!(anomalies.len(), 2);
+        assert_eq!(anomalies[0]["provider"], "p2");
+        assert_eq!(anomalies[0]["metric"], "total_tokens");
+        assert_eq!(anomalies[1]["metric"], "effective_total_usd");
+        assert!(rows.iter().all(|row| row.get("spike").is_some()));
+
+        let anomalies = flag_spend_history_spikes(&mut rows, 1000.0);
+        assert!(anomalies.is_empty());
+    }
 }
@@ -30,7 +30,7 @@ fn visible_last_error_events_cache() -> &'static Mutex<Option<VisibleLastErrorEv
 struct DisplayedSessionRouteCacheEntry {
     captured_at_unix_ms: u64,
     provider: Option<String>,
-    reason: Option<String>,
+    reason: Option<crate::orchestrator::gateway::RouteReason>,
 }
 
 fn displayed_session_route_cache(
@@ -73,7 +73,10 @@ fn load_cached_displayed_session_route(
     codex_session_id: &str,
     preferred_provider: &str,
     now: u64,
-) -> Option<(Option<String>, Option<String>)> {
+) -> Option<(
+    Option<String>,
+    Option<crate::orchestrator::gateway::RouteReason>,
+)> {
     let cache = displayed_session_route_cache();
     let mut guard = match cache.lock() {
         Ok(guard) => guard,
@@ -88,7 +91,7 @@ fn load_cached_displayed_session_route(
             codex_session_id,
             preferred_provider,
         ))
-        .map(|entry| (entry.provider.clone(), entry.reason.clone()))
+        .map(|entry| (entry.provider.clone(), entry.reason))
 }
 
 fn store_displayed_session_route_cache(
@@ -96,7 +99,7 @@ fn store_displayed_session_route_cache(
     codex_session_id: &str,
     preferred_provider: &str,
     provider: Option<String>,
-    reason: Option<String>,
+    reason: Option<crate::orchestrator::gateway::RouteReason>,
     now: u64,
 ) {
     let cache = displayed_session_route_cache();
@@ -651,8 +654,11 @@ pub(crate) fn get_status(
     let phase_started_at = std::time::Instant::now();
     let cfg = state.gateway.cfg.read().clone();
     let config_revision = config_revision(&state, &cfg);
-    let wsl_gateway_host =
-        crate::platform::wsl_gateway_host::cached_or_default_wsl_gateway_host(Some(&state.config_path));
+    let wsl_gateway_host = crate::platform::wsl_gateway_host::status_wsl_gateway_host(
+        &state.config_path,
+        cfg.listen.host.as_str(),
+        cfg.listen.port,
+    );
     #[cfg(windows)]
     maybe_refresh_runtime_wsl_listener(
         &state,
@@ -719,6 +725,30 @@ pub(crate) fn get_status(
             snapshot.cooldown_until_unix_ms = 0;
         }
     }
+    let provider_models = state.gateway.provider_models_cache.read().clone();
+    attach_served_models(&mut providers, &provider_models, now);
+    for (provider_name, snapshot) in providers.iter_mut() {
+        snapshot.tier = cfg
+            .providers
+            .get(provider_name)
+            .map_or(0, |provider| provider.tier);
+    }
+    for (provider_name, provider) in &cfg.providers {
+        if provider.disabled
+            || !crate::orchestrator::gateway::claim_provider_models_refresh(
+                &state.gateway,
+                provider_name,
+                now,
+            )
+        {
+            continue;
+        }
+        let gateway = state.gateway.clone();
+        let provider_name = provider_name.clone();
+        tokio::spawn(async move {
+            crate::orchestrator::gateway::refresh_provider_models(&gateway, &provider_name).await;
+        });
+    }
     let ledgers = state.gateway.store.list_ledgers();
     phase_timings_ms.insert(
         "metrics_quota_ledgers".to_string(),
@@ -726,41 +756,19 @@ pub(crate) fn get_status(
     );
     status_watchdog.phase("metrics_quota_ledgers");
     let last_activity = state.gateway.last_activity_unix_ms.load(Ordering::Relaxed);
-    let active_recent = last_activity > 0 && now.saturating_sub(last_activity) < 2 * 60 * 1000;
+    let active_window_ms = cfg.routing.active_window_ms();
+    let active_recent = last_activity > 0 && now.saturating_sub(last_activity) < active_window_ms;
     let phase_started_at = std::time::Instant::now();
-    let (active_provider, active_reason, active_provider_counts) = if active_recent {
+    let (active_provider, active_reason, active_provider_details) = if active_recent {
         let map = state.gateway.last_used_by_session.read().clone();
-
-        // Multiple Codex sessions can be active simultaneously, potentially routing through different
-        // providers. Expose the full active provider set so the UI can mark multiple providers as
-        // "effective" at once.
-        //
-        // Keep this a single pass so `active_provider` (most recent) and `active_provider_counts`
-        // share the same time window semantics.
-        let mut counts: std::collections::BTreeMap<String, u64> = std::collections::BTreeMap::new();
-        let mut last: Option<crate::orchestrator::gateway::LastUsedRoute> = None;
-
-        for v in map.values() {
-            if now.saturating_sub(v.unix_ms) >= 2 * 60 * 1000 {
-                continue;
-            }
-            *counts.entry(v.provider.clone()).or_default() += 1;
-            if last
-                .as_ref()
-                .map(|cur| v.unix_ms > cur.unix_ms)
-                .unwrap_or(true)
-            {
-                last = Some(v.clone());
-            }
-        }
-
+        let (last, details) = active_provider_details(map.values(), now, active_window_ms);
         (
             last.as_ref().map(|v| v.provider.clone()),
             last.map(|v| v.reason),
-            counts,
+            details,
         )
     } else {
-        (None, None, std::collections::BTreeMap::<String, u64>::new())
+        (None, None, serde_json::json!({}))
     };
     phase_timings_ms.insert(
         "active_provider".to_string(),
@@ -822,7 +830,7 @@ pub(crate) fn get_status(
     }
 
     let phase_started_at = std::time::Instant::now();
-    let client_sessions = {
+    let (client_sessions, discovery_fresh, discovery_age_ms) = {
         let subphase_started_at = std::time::Instant::now();
         let thread_index_snapshot =
             crate::orchestrator::gateway::web_codex_threads::cached_threads_snapshot_stale_while_revalidate();
@@ -877,6 +885,7 @@ pub(crate) fn get_status(
             serde_json::json!(elapsed_ms_since(subphase_started_at)),
         );
         status_watchdog.phase("client_sessions_runtime_snapshot");
+        let auth_token_previews = gateway_token_previews(&state);
         let last_used_by_session = state.gateway.last_used_by_session.read();
         let subphase_started_at = std::time::Instant::now();
         let sessions = refreshed
@@ -885,15 +894,17 @@ pub(crate) fn get_status(
             .map(|(_codex_session_id, v)| {
                 // Consider a session "active" only if it has recently made requests through the router.
                 // Discovery scans run frequently and should not keep sessions pinned as active forever.
-                let active = session_is_active(&v, now);
+                let active = session_is_active(&v, now, cfg.routing.session_active_window_ms());
 
                 let codex_id = v.codex_session_id.clone();
-                let pref = cfg
+                let prefs = cfg
                     .routing
-                    .session_preferred_providers
-                    .get(&codex_id)
+                    .session_preferred_list(&codex_id)
+                    .iter()
+                    .filter(|p| cfg.providers.contains_key(p.as_str()))
                     .cloned()
-                    .filter(|p| cfg.providers.contains_key(p));
+                    .collect::<Vec<_>>();
+                let pref = prefs.first().cloned();
                 let current_route = last_used_by_session
                     .get(&codex_id)
                     .filter(|route| cfg.providers.contains_key(route.provider.as_str()));
@@ -917,15 +928,27 @@ pub(crate) fn get_status(
                     "agent_parent_session_id": v.agent_parent_session_id,
                     "reported_model_provider": v.last_reported_model_provider,
                     "reported_model": v.last_reported_model,
+                    "reported_model_served": reported_model_served(
+                        &cfg,
+                        &provider_models,
+                        v.last_reported_model.as_deref(),
+                    ),
                     "reported_base_url": v.last_reported_base_url,
                     "last_seen_unix_ms": last_seen_unix_ms,
                     "active": active,
                     "preferred_provider": pref,
+                    "preferred_providers": prefs,
                     "current_provider": display_provider,
                     "current_reason": display_reason,
                     "verified": v.confirmed_router,
                     "is_agent": v.is_agent,
-                    "is_review": v.is_review
+                    "is_review": v.is_review,
+                    "auth_token_label": v.auth_token_label,
+                    "auth_token_preview": v
+                        .auth_token_label
+                        .as_deref()
+                        .and_then(|label| auth_token_previews.get(label)),
+                    "peer_process": v.peer_process
                 })
             })
             .collect::<Vec<_>>();
@@ -941,7 +964,11 @@ pub(crate) fn get_status(
             serde_json::json!(elapsed_ms_since(subphase_started_at)),
         );
         status_watchdog.phase("client_sessions_trace_snapshot");
-        sessions
+        (
+            sessions,
+            terminal_discovery.fresh && thread_index_snapshot.fresh,
+            terminal_discovery.age_ms,
+        )
     };
     phase_timings_ms.insert(
         "client_sessions".to_string(),
@@ -950,25 +977,38 @@ pub(crate) fn get_status(
     status_watchdog.phase("client_sessions");
 
     let response = serde_json::json!({
-      "listen": { "host": cfg.listen.host, "port": cfg.listen.port },
+      "listen": {
+        "host": cfg.listen.host,
+        "port": cfg.listen.port,
+        "unix_socket_path": crate::orchestrator::gateway::active_unix_socket_path(),
+        "bound_addrs": crate::orchestrator::gateway::bound_listener_addrs(),
+      },
       "config_revision": config_revision,
       "wsl_gateway_host": wsl_gateway_host,
+      "wsl_openai_base_url_export": crate::platform::wsl_gateway_host::wsl_openai_base_url_export(
+        &wsl_gateway_host,
+        cfg.listen.port,
+      ),
       "local_network_online": local_network.online,
       "local_network_source": local_network.source,
       "local_network_last_error": local_network.last_error,
       "preferred_provider": cfg.routing.preferred_provider,
+      "read_only": cfg.routing.read_only_enabled(),
       "manual_override": manual_override,
       "providers": providers,
       "metrics": metrics,
       "recent_events": recent_events,
       "active_provider": active_provider,
       "active_reason": active_reason,
-      "active_provider_counts": active_provider_counts,
+      "active_provider_details": active_provider_details,
+      "quota_groups": quota_groups_for_status(&cfg, &quota),
       "quota": quota,
       "ledgers": ledgers,
       "last_activity_unix_ms": last_activity,
       "codex_account": codex_account,
       "client_sessions": client_sessions,
+      "discovery_fresh": discovery_fresh,
+      "discovery_age_ms": discovery_age_ms,
       "lan_sync": lan_sync,
       "tailscale": tailscale,
       "shared_quota_owners": shared_quota_owners
@@ -989,6 +1029,87 @@ pub(crate) fn get_status(
     Ok(response)
 }
 
+/// Sessions routed within the active window, grouped per provider with a breakdown of the
+/// routing reasons that put them there (and how many of those were failovers), plus the most
+/// recent route overall.
+///
+/// Multiple Codex sessions can be active simultaneously, potentially routing through different
+/// providers, so the UI can mark several providers as "effective" at once. Keep this a single
+/// pass so `active_provider` (most recent) and the per-provider details share the same window.
+fn active_provider_details<'a>(
+    routes: impl IntoIterator<Item = &'a crate::orchestrator::gateway::LastUsedRoute>,
+    now: u64,
+    active_window_ms: u64,
+) -> (Option<crate::orchestrator::gateway::LastUsedRoute>, Value) {
+    let mut details: std::collections::BTreeMap<
+        &str,
+        (u64, u64, std::collections::BTreeMap<&str, u64>),
+    > = std::collections::BTreeMap::new();
+    let mut last: Option<&crate::orchestrator::gateway::LastUsedRoute> = None;
+    for route in routes {
+        if now.saturating_sub(route.unix_ms) >= active_window_ms {
+            continue;
+        }
+        let (count, failover_count, reasons) = details.entry(route.provider.as_str()).or_default();
+        *count += 1;
+        *failover_count += u64::from(route.reason.is_failover());
+        *reasons.entry(route.reason.as_str()).or_default() += 1;
+        if last.map(|cur| route.unix_ms > cur.unix_ms).unwrap_or(true) {
+            last = Some(route);
+        }
+    }
+    let details = details
+        .into_iter()
+        .map(|(provider, (count, failover_count, reasons))| {
+            (
+                provider.to_string(),
+                serde_json::json!({
+                    "count": count,
+                    "failover_count": failover_count,
+                    "reasons": reasons,
+                }),
+            )
+        })
+        .collect::<serde_json::Map<_, _>>();
+    (last.cloned(), Value::Object(details))
+}
+
+/// One quota figure per declared provider group (two or more members), taken from the most
+/// recently refreshed member so a shared account is not shown once per provider.
+fn quota_groups_for_status(cfg: &crate::orchestrator::config::AppConfig, quota: &Value) -> Value {
+    let mut groups: std::collections::BTreeMap<&str, Vec<&String>> =
+        std::collections::BTreeMap::new();
+    for (name, provider) in &cfg.providers {
+        if let Some(group) = crate::orchestrator::quota::provider_quota_group(provider) {
+            groups.entry(group).or_default().push(name);
+        }
+    }
+    let updated_at = |name: &str| {
+        quota
+            .get(name)
+            .and_then(|snapshot| snapshot.get("updated_at_unix_ms"))
+            .and_then(Value::as_u64)
+            .unwrap_or(0)
+    };
+    let rows = groups
+        .into_iter()
+        .filter(|(_, members)| members.len() > 1)
+        .map(|(group, members)| {
+            let source = members
+                .iter()
+                .filter(|name| quota.get(name.as_str()).is_some())
+                .max_by_key(|name| updated_at(name.as_str()));
+            serde_json::json!({
+                "group": group,
+                "providers": members,
+                "source_provider": source,
+                "quota": source.and_then(|name| quota.get(name.as_str())),
+            })
+        })
+        .collect();
+    Value::Array(rows)
+}
+
 fn config_revision(state: &app_state::AppState, cfg: &crate::orchestrator::config::AppConfig) -> String {
     let followed_source_node_id = state.secrets.get_followed_config_source_node_id();
     let local_copied_shared_ids = crate::lan_sync::load_local_provider_copy_state(state)
@@ -1062,6 +1183,84 @@ fn config_revision(state: &app_state::AppState, cfg: &crate::orchestrator::confi
     digest.iter().map(|byte| format!("{byte:02x}")).collect()
 }
 
+#[tauri::command]
+pub(crate) async fn refresh_wsl_gateway_host(
+    state: tauri::State<'_, app_state::AppState>,
+) -> Result<Value, String> {
+    let (listen_host, listen_port) = {
+        let cfg = state.gateway.cfg.read();
+        (cfg.listen.host.clone(), cfg.listen.port)
+    };
+    let config_path = state.config_path.clone();
+    // Detection shells into WSL; keep it off the async runtime.
+    let wsl_gateway_host = tauri::async_runtime::spawn_blocking(move || {
+        crate::platform::wsl_gateway_host::refresh_wsl_gateway_host(Some(&config_path))
+    })
+    .await
+    .map_err(|err| format!("wsl_gateway_host_refresh_failed: {err}"))?;
+    #[cfg(windows)]
+    maybe_refresh_runtime_wsl_listener(&state, &listen_host, listen_port, &wsl_gateway_host);
+    #[cfg(not(windows))]
+    let _ = listen_host;
+    Ok(serde_json::json!({
+        "wsl_gateway_host": wsl_gateway_host,
+        "wsl_openai_base_url_export": crate::platform::wsl_gateway_host::wsl_openai_base_url_export(
+            &wsl_gateway_host,
+            listen_port,
+        ),
+    }))
+}
+
+#[tauri::command]
+pub(crate) fn get_store_stats(state: tauri::State<'_, app_state::AppState>) -> serde_json::Value {
+    let mut stats = state.gateway.store.storage_stats();
+    let (max_store_bytes, evict_usage_requests) = {
+        let cfg = state.gateway.cfg.read();
+        (
+            cfg.routing.max_store_bytes,
+            cfg.routing.evict_usage_requests,
+        )
+    };
+    stats["max_store_bytes"] = serde_json::json!(max_store_bytes);
+    stats["eviction_order"] = if evict_usage_requests {
+        serde_json::json!(["info_events", "usage_requests"])
+    } else {
+        serde_json::json!(["info_events"])
+    };
+    stats
+}
+
+/// Forces buffered usage rows, metrics and ledger updates to disk and checkpoints the WAL.
+#[tauri::command]
+pub(crate) fn flush_store(
+    state: tauri::State<'_, app_state::AppState>,
+) -> Result<serde_json::Value, CommandError> {
+    let usage_requests_flushed = state.gateway.store.flush_to_disk()?;
+    Ok(serde_json::json!({ "usage_requests_flushed": usage_requests_flushed }))
+}
+
+/// Snapshots the store into `sled.manual-backup.<ts>[-label]` under the data dir, e.g. before
+/// a risky config change or migration. Returns the created directory.
+#[tauri::command]
+pub(crate) fn create_manual_backup(
+    state: tauri::State<'_, app_state::AppState>,
+    label: Option<String>,
+) -> Result<String, CommandError> {
+    let path = state
+        .gateway
+        .store
+        .create_manual_backup(label.as_deref())?
+        .display()
+        .to_string();
+    state.gateway.store.events().emit(
+        "gateway",
+        crate::orchestrator::store::EventCode::STORE_MANUAL_BACKUP_CREATED,
+        "manual backup created",
+        serde_json::json!({ "path": path, "label": label }),
+    );
+    Ok(path)
+}
+
 #[tauri::command]
 pub(crate) fn record_app_startup_stage(
     stage: String,
@@ -1346,6 +1545,8 @@ fn merge_discovered_terminal_sessions(
                 is_agent: discovered.is_agent,
                 is_review: discovered.is_review,
                 confirmed_router: discovered.router_confirmed,
+                auth_token_label: None,
+                peer_process: None,
             }
         });
 
@@ -1435,6 +1636,8 @@ fn confirm_router_from_live_thread_base_url(
                 is_agent: false,
                 is_review: false,
                 confirmed_router: false,
+                auth_token_label: None,
+                peer_process: None,
             }
         });
         entry.last_reported_base_url = Some(base_url);
@@ -1452,11 +1655,14 @@ fn displayed_session_route(
     preferred_provider: &str,
     verified: bool,
     current_route: Option<&crate::orchestrator::gateway::LastUsedRoute>,
-) -> (Option<String>, Option<String>) {
+) -> (
+    Option<String>,
+    Option<crate::orchestrator::gateway::RouteReason>,
+) {
     if let Some(route) = current_route {
         // Keep status polling cheap: once we have an observed route, reuse it instead of
         // recomputing a full routing decision for every poll.
-        return (Some(route.provider.clone()), Some(route.reason.clone()));
+        return (Some(route.provider.clone()), Some(route.reason));
     }
     if verified {
         let now = unix_ms();
@@ -1475,13 +1681,13 @@ fn displayed_session_route(
             codex_session_id,
         );
         let provider = Some(provider);
-        let reason = Some(reason.to_string());
+        let reason = Some(reason);
         store_displayed_session_route_cache(
             cache_scope,
             codex_session_id,
             preferred_provider,
             provider.clone(),
-            reason.clone(),
+            reason,
             now,
         );
         return (provider, reason);
@@ -1502,6 +1708,7 @@ fn clear_removed_main_session_routes_and_assignments(
             routes.remove(session_id);
         }
     }
+    crate::orchestrator::gateway::persist_last_used_routes(gateway);
     for session_id in removed_session_ids {
         gateway.store.delete_session_route_assignment(session_id);
     }
@@ -1541,6 +1748,7 @@ fn rebalance_balanced_assignments_on_main_session_change(
         let mut routes = gateway.last_used_by_session.write();
         routes.retain(|session_id, _| kept_agent_or_review_ids.contains(session_id));
     }
+    crate::orchestrator::gateway::persist_last_used_routes(gateway);
     let cleared_assignments = gateway.store.delete_all_session_route_assignments();
     gateway.store.events().emit(
         "gateway",
@@ -1660,6 +1868,8 @@ fn backfill_main_confirmation_from_verified_agent(
                     is_agent: false,
                     is_review: false,
                     confirmed_router: false,
+                    auth_token_label: None,
+                    peer_process: None,
                 }
             });
         }
@@ -1732,14 +1942,16 @@ fn event_shape_is_valid(e: &Value) -> bool {
         && e.get("message").and_then(|v| v.as_str()).is_some()
 }
 
-const EVENT_LOG_QUERY_DEFAULT_LIMIT: usize = 2000;
-const EVENT_LOG_QUERY_MAX_LIMIT: usize = 5000;
 const EVENT_LOG_DASHBOARD_VISIBLE_LIMIT: usize = 200;
 
-fn normalize_event_query_limit(limit: Option<usize>) -> usize {
+/// Applies `routing.event_query_default_limit` / `routing.event_query_max_limit`.
+fn normalize_event_query_limit(
+    routing: &crate::orchestrator::config::RoutingConfig,
+    limit: Option<usize>,
+) -> usize {
     limit
-        .unwrap_or(EVENT_LOG_QUERY_DEFAULT_LIMIT)
-        .clamp(1, EVENT_LOG_QUERY_MAX_LIMIT)
+        .unwrap_or_else(|| routing.effective_event_query_default_limit())
+        .clamp(1, routing.effective_event_query_max_limit())
 }
 
 fn event_in_time_window(e: &Value, from: Option<u64>, to: Option<u64>) -> bool {
@@ -1759,24 +1971,40 @@ fn event_in_time_window(e: &Value, from: Option<u64>, to: Option<u64>) -> bool {
     true
 }
 
+/// Exclusive position in the newest-first event log; the next page starts just after it.
+#[derive(Debug, Clone, PartialEq, Eq, serde::Serialize, serde::Deserialize)]
+pub(crate) struct EventLogCursor {
+    unix_ms: u64,
+    id: String,
+}
+
+fn event_log_position(e: &Value) -> (u64, &str) {
+    (
+        e.get("unix_ms").and_then(Value::as_u64).unwrap_or(0),
+        e.get("id").and_then(Value::as_str).unwrap_or(""),
+    )
+}
+
+fn event_before_cursor(e: &Value, before: Option<&EventLogCursor>) -> bool {
+    before.map_or(true, |cursor| {
+        event_log_position(e) < (cursor.unix_ms, cursor.id.as_str())
+    })
+}
+
+/// Appends up to `limit` events from each backup directory, newest first.
 fn append_backup_events(
     out: &mut Vec<Value>,
     dedup: &mut std::collections::HashSet<String>,
     data_root: &std::path::Path,
     from: Option<u64>,
     to: Option<u64>,
-    cap: usize,
+    before: Option<&EventLogCursor>,
+    limit: usize,
 ) {
-    if out.len() >= cap {
-        return;
-    }
     let Ok(entries) = std::fs::read_dir(data_root) else {
         return;
     };
     for entry in entries.flatten() {
-        if out.len() >= cap {
-            break;
-        }
         let path = entry.path();
         if !path.is_dir() {
             continue;
@@ -1793,20 +2021,28 @@ fn append_backup_events(
         let Ok(db) = sled::open(&path) else {
             continue;
         };
+        let mut taken = 0_usize;
         for item in db.scan_prefix(b"event:").rev() {
-            if out.len() >= cap {
+            if taken >= limit {
                 break;
             }
-            let Ok((_, v)) = item else {
+            let Ok((k, v)) = item else {
                 continue;
             };
-            let Ok(e) = serde_json::from_slice::<Value>(&v) else {
+            let Ok(mut e) = serde_json::from_slice::<Value>(&v) else {
                 continue;
             };
+            // Backup rows keep their id in the key (`event:{unix_ms}:{id}`), not the body.
+            if let (Some(obj), Some(id)) = (
+                e.as_object_mut(),
+                crate::orchestrator::store::Store::parse_event_key_id(k.as_ref()),
+            ) {
+                obj.entry("id").or_insert(Value::String(id));
+            }
             if !event_shape_is_valid(&e) {
                 continue;
             }
-            if !event_in_time_window(&e, from, to) {
+            if !event_in_time_window(&e, from, to) || !event_before_cursor(&e, before) {
                 continue;
             }
             let Some(key) = event_query_key(&e) else {
@@ -1814,19 +2050,27 @@ fn append_backup_events(
             };
             if dedup.insert(key) {
                 out.push(e);
+                taken += 1;
             }
         }
     }
 }
 
-fn load_event_log_entries_for_display(
+/// Uncompressed events from the store and its backups, newest first by `(unix_ms, id)`.
+fn load_event_log_rows(
     store: &crate::orchestrator::store::Store,
     config_path: &std::path::Path,
     from: Option<u64>,
     to: Option<u64>,
-    cap: usize,
+    before: Option<&EventLogCursor>,
+    limit: usize,
 ) -> Vec<Value> {
-    let mut events = store.list_events_range(from, to, Some(cap));
+    let mut events = store.list_events_before(
+        from,
+        to,
+        before.map(|cursor| (cursor.unix_ms, cursor.id.as_str())),
+        Some(limit),
+    );
     events.retain(event_shape_is_valid);
     let mut dedup = std::collections::HashSet::<String>::new();
     for event in &events {
@@ -1835,12 +2079,86 @@ fn load_event_log_entries_for_display(
         }
     }
     let backup_root = backup_data_root_from_config_path(config_path);
-    append_backup_events(&mut events, &mut dedup, &backup_root, from, to, cap);
+    append_backup_events(
+        &mut events,
+        &mut dedup,
+        &backup_root,
+        from,
+        to,
+        before,
+        limit,
+    );
+    events.sort_by(|a, b| event_log_position(b).cmp(&event_log_position(a)));
+    events.truncate(limit);
+    events
+}
+
+fn load_event_log_entries_for_display(
+    store: &crate::orchestrator::store::Store,
+    config_path: &std::path::Path,
+    from: Option<u64>,
+    to: Option<u64>,
+    cap: usize,
+) -> Vec<Value> {
+    let events = load_event_log_rows(store, config_path, from, to, None, cap);
     let mut events = crate::orchestrator::store::Store::compress_events_for_display(events);
     events.truncate(cap);
     events
 }
 
+fn attach_served_models(
+    providers: &mut std::collections::HashMap<
+        String,
+        crate::orchestrator::router::ProviderHealthSnapshot,
+    >,
+    provider_models: &std::collections::HashMap<
+        String,
+        crate::orchestrator::gateway::ProviderModelCatalog,
+    >,
+    now: u64,
+) {
+    for (provider_name, snapshot) in providers.iter_mut() {
+        let Some(catalog) = provider_models
+            .get(provider_name)
+            .filter(|catalog| catalog.fetched_at_unix_ms > 0)
+        else {
+            continue;
+        };
+        snapshot.served_models = Some(catalog.models.clone());
+        snapshot.served_models_age_ms = Some(now.saturating_sub(catalog.fetched_at_unix_ms));
+    }
+}
+
+/// Whether any enabled provider serves `model` (directly or via an alias). `None` when the
+/// session has no reported model or some enabled provider has no catalog yet.
+fn reported_model_served(
+    cfg: &crate::orchestrator::config::AppConfig,
+    provider_models: &std::collections::HashMap<
+        String,
+        crate::orchestrator::gateway::ProviderModelCatalog,
+    >,
+    model: Option<&str>,
+) -> Option<bool> {
+    let model = model.map(str::trim).filter(|model| !model.is_empty())?;
+    let mut all_known = true;
+    for (provider_name, provider) in cfg.providers.iter().filter(|(_, p)| p.routable()) {
+        if provider.model_aliases.contains_key(model) {
+            return Some(true);
+        }
+        match provider_models
+            .get(provider_name)
+            .filter(|catalog| catalog.fetched_at_unix_ms > 0)
+        {
+            Some(catalog) if catalog.models.iter().any(|served| served == model) => {
+                return Some(true)
+            }
+            Some(_) => {}
+            None => all_known = false,
+        }
+    }
+    (all_known && cfg.providers.values().any(|p| p.routable())).then_some(false)
+}
+
 fn attach_visible_last_error_event_ids(
     providers: &mut std::collections::HashMap<
         String,
@@ -2035,26 +2353,50 @@ fn append_backup_event_daily_stats(
     }
 }
 
+/// Turns up to `cap + 1` rows from `load_event_log_rows` into a newest-first display page and
+/// the cursor for the next one. The cursor is the last raw row kept, so rows sharing its
+/// millisecond, in the store or in a backup, are still returned by the next page.
+fn event_log_page(mut rows: Vec<Value>, cap: usize) -> (Vec<Value>, Option<EventLogCursor>) {
+    let mut next_cursor = None;
+    if rows.len() > cap {
+        rows.truncate(cap);
+        next_cursor = rows.last().map(|event| {
+            let (unix_ms, id) = event_log_position(event);
+            EventLogCursor {
+                unix_ms,
+                id: id.to_string(),
+            }
+        });
+    }
+    let mut events = crate::orchestrator::store::Store::compress_events_for_display(rows);
+    events.sort_by(|a, b| event_log_position(b).cmp(&event_log_position(a)));
+    (events, next_cursor)
+}
+
 #[tauri::command]
 pub(crate) fn get_event_log_entries(
     state: tauri::State<'_, app_state::AppState>,
     from_unix_ms: Option<u64>,
     to_unix_ms: Option<u64>,
     limit: Option<usize>,
+    before: Option<EventLogCursor>,
 ) -> serde_json::Value {
     let (from, to) = match (from_unix_ms, to_unix_ms) {
         (Some(from), Some(to)) if from > to => (Some(to), Some(from)),
         _ => (from_unix_ms, to_unix_ms),
     };
-    let cap = normalize_event_query_limit(limit);
-    let events = load_event_log_entries_for_display(
+    let cap = normalize_event_query_limit(&state.gateway.cfg.read().routing, limit);
+    // One extra row tells whether anything older than the page exists.
+    let rows = load_event_log_rows(
         &state.gateway.store,
         state.config_path.as_path(),
         from,
         to,
-        cap,
+        before.as_ref(),
+        cap + 1,
     );
-    serde_json::Value::Array(events)
+    let (events, next_cursor) = event_log_page(rows, cap);
+    serde_json::json!({ "events": events, "next_cursor": next_cursor })
 }
 
 #[tauri::command]
@@ -2097,12 +2439,47 @@ pub(crate) fn get_event_log_daily_stats(
     serde_json::Value::Array(rows)
 }
 
+#[tauri::command]
+pub(crate) fn get_audit_log(
+    state: tauri::State<'_, app_state::AppState>,
+    from_unix_ms: Option<u64>,
+    to_unix_ms: Option<u64>,
+    limit: Option<usize>,
+) -> serde_json::Value {
+    let (from, to) = match (from_unix_ms, to_unix_ms) {
+        (Some(from), Some(to)) if from > to => (Some(to), Some(from)),
+        _ => (from_unix_ms, to_unix_ms),
+    };
+    let cap = normalize_event_query_limit(&state.gateway.cfg.read().routing, limit);
+    serde_json::Value::Array(state.gateway.store.list_config_audit(from, to, cap))
+}
+
+/// Chronological up/down transitions for one provider within the inclusive window.
+#[tauri::command]
+pub(crate) fn get_health_history(
+    state: tauri::State<'_, app_state::AppState>,
+    provider: String,
+    from_unix_ms: Option<u64>,
+    to_unix_ms: Option<u64>,
+) -> serde_json::Value {
+    let (from, to) = match (from_unix_ms, to_unix_ms) {
+        (Some(from), Some(to)) if from > to => (Some(to), Some(from)),
+        _ => (from_unix_ms, to_unix_ms),
+    };
+    serde_json::Value::Array(
+        state
+            .gateway
+            .store
+            .list_health_transitions(provider.trim(), from, to),
+    )
+}
+
 #[cfg(test)]
 mod tests {
     use super::{fallback_tailscale_snapshot, run_blocking_snapshot};
     use crate::constants::GATEWAY_MODEL_PROVIDER_ID;
     use crate::commands::{
-        attach_visible_last_error_event_ids,
+        attach_served_models, attach_visible_last_error_event_ids,
         backfill_main_confirmation_from_verified_agent,
         clear_displayed_session_route_cache,
         clear_visible_last_error_events_cache,
@@ -2111,11 +2488,13 @@ mod tests {
         config_revision,
         displayed_session_route_cache_scope,
         load_visible_last_error_events_with_cache,
-        load_event_log_entries_for_display,
+        active_provider_details, event_log_page, load_event_log_entries_for_display,
+        load_event_log_rows, EventLogCursor,
+        quota_groups_for_status,
         main_session_ids_excluding_agents_and_reviews,
         merge_thread_index_session_hints,
         rebalance_balanced_assignments_on_main_session_change,
-        refresh_client_sessions_runtime,
+        refresh_client_sessions_runtime, reported_model_served,
         retain_live_app_server_sessions,
         displayed_session_route, merge_discovered_model_provider, next_last_discovered_unix_ms,
         normalize_event_query_limit, EVENT_LOG_DASHBOARD_VISIBLE_LIMIT,
@@ -2123,14 +2502,17 @@ mod tests {
     };
     #[cfg(windows)]
     use super::should_refresh_runtime_wsl_listener;
+    use super::{day_range_from_key_in_zone, AmbiguousLocalTime};
     use crate::orchestrator::config::{AppConfig, ListenConfig, ProviderConfig, RoutingConfig};
-    use crate::orchestrator::gateway::{decide_provider, open_store_dir, GatewayState, LastUsedRoute};
+    use crate::orchestrator::gateway::{
+        decide_provider, open_store_dir, GatewayState, LastUsedRoute, RouteReason,
+    };
     use crate::orchestrator::router::{ProviderHealthSnapshot, RouterState};
-    use crate::orchestrator::secrets::SecretStore;
+    use crate::orchestrator::secrets::{tiered_request_cost_usd, SecretStore};
     use crate::orchestrator::store::{unix_ms, Store, StoredEventRow, UsageRequestSyncRow};
     use crate::orchestrator::upstream::UpstreamClient;
     use crate::orchestrator::gateway::ClientSessionRuntime;
-    use chrono::TimeZone;
+    use chrono::{Datelike, TimeZone};
     use parking_lot::RwLock;
     use serde_json::Value;
     use std::collections::HashMap;
@@ -2181,6 +2563,8 @@ mod tests {
             is_agent: false,
             is_review: false,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
         merge_discovered_model_provider(&mut entry, Some("openai"));
         assert_eq!(
@@ -2209,6 +2593,8 @@ mod tests {
                     is_agent: false,
                     is_review: false,
                     confirmed_router: true,
+                    auth_token_label: None,
+                    peer_process: None,
                 },
             ),
             (
@@ -2227,12 +2613,15 @@ mod tests {
                     is_agent: true,
                     is_review: false,
                     confirmed_router: true,
+                    auth_token_label: None,
+                    peer_process: None,
                 },
             ),
         ]);
         let terminal_discovery = crate::platform::windows_terminal::SessionDiscoverySnapshot {
             items: Vec::new(),
             fresh: false,
+            age_ms: None,
         };
         let thread_index_snapshot =
             crate::orchestrator::gateway::web_codex_threads::CachedThreadIndexSnapshot {
@@ -2286,11 +2675,14 @@ mod tests {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: false,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]);
         let terminal_discovery = crate::platform::windows_terminal::SessionDiscoverySnapshot {
             items: Vec::new(),
             fresh: true,
+            age_ms: None,
         };
         let thread_index_snapshot =
             crate::orchestrator::gateway::web_codex_threads::CachedThreadIndexSnapshot {
@@ -2373,6 +2765,14 @@ mod tests {
                 last_ok_at_unix_ms: 0,
                 last_fail_at_unix_ms: old_error_ts,
                 last_error_event_id: None,
+                ewma_latency_ms: None,
+                recent_latencies_ms: Vec::new(),
+                recent_error_rate: 0.0,
+                uptime_24h_percent: None,
+                uptime_7d_percent: None,
+                served_models: None,
+                served_models_age_ms: None,
+                tier: 0,
             },
         )]);
 
@@ -2386,6 +2786,77 @@ mod tests {
         );
     }
 
+    #[test]
+    fn served_models_attach_with_age_and_flag_unserved_session_models() {
+        let mut cfg = AppConfig::default_config();
+        cfg.providers
+            .get_mut("provider_1")
+            .expect("provider_1")
+            .model_aliases
+            .insert("fast".to_string(), "gpt-5-mini".to_string());
+        let mut provider_models = HashMap::from([(
+            "provider_1".to_string(),
+            crate::orchestrator::gateway::ProviderModelCatalog {
+                models: vec!["gpt-5".to_string()],
+                fetched_at_unix_ms: 1_000,
+                attempted_at_unix_ms: 1_000,
+            },
+        )]);
+
+        let mut providers = HashMap::from([(
+            "provider_1".to_string(),
+            ProviderHealthSnapshot {
+                status: "healthy".to_string(),
+                consecutive_failures: 0,
+                cooldown_until_unix_ms: 0,
+                last_error: String::new(),
+                last_ok_at_unix_ms: 0,
+                last_fail_at_unix_ms: 0,
+                last_error_event_id: None,
+                ewma_latency_ms: None,
+                recent_latencies_ms: Vec::new(),
+                recent_error_rate: 0.0,
+                uptime_24h_percent: None,
+                uptime_7d_percent: None,
+                served_models: None,
+                served_models_age_ms: None,
+                tier: 0,
+            },
+        )]);
+        attach_served_models(&mut providers, &provider_models, 4_000);
+        let snapshot = providers.get("provider_1").expect("snapshot");
+        assert_eq!(snapshot.served_models, Some(vec!["gpt-5".to_string()]));
+        assert_eq!(snapshot.served_models_age_ms, Some(3_000));
+
+        // Other enabled providers have no catalog yet, so an unknown model stays undecided.
+        assert_eq!(
+            reported_model_served(&cfg, &provider_models, Some("o9")),
+            None
+        );
+        assert_eq!(
+            reported_model_served(&cfg, &provider_models, Some("gpt-5")),
+            Some(true)
+        );
+        assert_eq!(
+            reported_model_served(&cfg, &provider_models, Some("fast")),
+            Some(true)
+        );
+        assert_eq!(reported_model_served(&cfg, &provider_models, None), None);
+
+        for (name, provider) in cfg.providers.iter_mut() {
+            provider.disabled = name != "provider_1";
+        }
+        assert_eq!(
+            reported_model_served(&cfg, &provider_models, Some("o9")),
+            Some(false)
+        );
+        provider_models.clear();
+        assert_eq!(
+            reported_model_served(&cfg, &provider_models, Some("o9")),
+            None
+        );
+    }
+
     #[test]
     fn visible_last_error_ids_attach_visible_exact_error() {
         let provider = "codex-for.me";
@@ -2399,6 +2870,14 @@ mod tests {
                 last_ok_at_unix_ms: 0,
                 last_fail_at_unix_ms: 2_000,
                 last_error_event_id: None,
+                ewma_latency_ms: None,
+                recent_latencies_ms: Vec::new(),
+                recent_error_rate: 0.0,
+                uptime_24h_percent: None,
+                uptime_7d_percent: None,
+                served_models: None,
+                served_models_age_ms: None,
+                tier: 0,
             },
         )]);
         let visible = vec![
@@ -2446,6 +2925,14 @@ mod tests {
                 last_ok_at_unix_ms: snapshot_ts + 60_000,
                 last_fail_at_unix_ms: snapshot_ts,
                 last_error_event_id: None,
+                ewma_latency_ms: None,
+                recent_latencies_ms: Vec::new(),
+                recent_error_rate: 0.0,
+                uptime_24h_percent: None,
+                uptime_7d_percent: None,
+                served_models: None,
+                served_models_age_ms: None,
+                tier: 0,
             },
         )]);
         let visible = vec![serde_json::json!({
@@ -2519,6 +3006,14 @@ mod tests {
                 last_ok_at_unix_ms: 0,
                 last_fail_at_unix_ms: target_ts,
                 last_error_event_id: None,
+                ewma_latency_ms: None,
+                recent_latencies_ms: Vec::new(),
+                recent_error_rate: 0.0,
+                uptime_24h_percent: None,
+                uptime_7d_percent: None,
+                served_models: None,
+                served_models_age_ms: None,
+                tier: 0,
             },
         )]);
 
@@ -2693,11 +3188,90 @@ mod tests {
         );
     }
 
+    #[test]
+    fn event_log_page_cursor_walks_store_and_backups_without_skipping_a_millisecond() {
+        let tmp = tempdir().expect("tempdir");
+        let config_path = tmp.path().join("config.toml");
+        let store = open_store_dir(tmp.path().join("data")).expect("store");
+        for (idx, unix_ms) in [100_u64, 200, 300, 300, 300, 400].into_iter().enumerate() {
+            assert!(store.insert_event_row(StoredEventRow {
+                id: format!("evt-{idx}"),
+                provider: "gateway".to_string(),
+                level: "info".to_string(),
+                code: format!("test.page_{idx}"),
+                message: format!("event {idx}"),
+                fields: Value::Null,
+                unix_ms,
+            }));
+        }
+        let db = sled::open(tmp.path().join("data").join("sled.backup.page")).expect("backup");
+        for (id, unix_ms) in [("evt-b", 300_u64), ("evt-old", 50)] {
+            let v = serde_json::json!({
+                "provider": "gateway",
+                "level": "info",
+                "unix_ms": unix_ms,
+                "code": format!("test.backup_{id}"),
+                "message": format!("backup {id}"),
+                "fields": {}
+            });
+            db.insert(
+                format!("event:{unix_ms}:{id}").as_bytes(),
+                serde_json::to_vec(&v).unwrap(),
+            )
+            .unwrap();
+        }
+        db.flush().unwrap();
+        drop(db);
+
+        let mut pages = Vec::new();
+        let mut before: Option<EventLogCursor> = None;
+        loop {
+            let rows = load_event_log_rows(&store, &config_path, None, None, before.as_ref(), 3);
+            let (page, next_cursor) = event_log_page(rows, 2);
+            pages.push(
+                page.iter()
+                    .filter_map(|event| event.get("id").and_then(Value::as_str))
+                    .map(str::to_string)
+                    .collect::<Vec<_>>(),
+            );
+            match next_cursor {
+                Some(cursor) => before = Some(cursor),
+                None => break,
+            }
+        }
+        assert_eq!(
+            pages,
+            vec![
+                vec!["evt-5", "evt-b"],
+                vec!["evt-4", "evt-3"],
+                vec!["evt-2", "evt-1"],
+                vec!["evt-0", "evt-old"],
+            ]
+        );
+    }
+
     #[test]
     fn normalize_event_query_limit_applies_default_and_cap() {
-        assert_eq!(normalize_event_query_limit(None), 2000);
-        assert_eq!(normalize_event_query_limit(Some(0)), 1);
-        assert_eq!(normalize_event_query_limit(Some(999_999)), 5000);
+        let mut routing = crate::orchestrator::config::AppConfig::default_config().routing;
+        assert_eq!(normalize_event_query_limit(&routing, None), 2000);
+        assert_eq!(normalize_event_query_limit(&routing, Some(0)), 1);
+        assert_eq!(normalize_event_query_limit(&routing, Some(999_999)), 5000);
+
+        routing.event_query_default_limit = 10_000;
+        routing.event_query_max_limit = 50_000;
+        assert_eq!(normalize_event_query_limit(&routing, None), 10_000);
+        assert_eq!(normalize_event_query_limit(&routing, Some(999_999)), 50_000);
+
+        // Configured limits are bounded by the hard ceiling, and zero means "use the default".
+        routing.event_query_max_limit = u32::MAX;
+        assert_eq!(
+            normalize_event_query_limit(&routing, Some(usize::MAX)),
+            crate::orchestrator::config::MAX_EVENT_QUERY_LIMIT as usize
+        );
+        routing.event_query_default_limit = 0;
+        routing.event_query_max_limit = 0;
+        assert_eq!(normalize_event_query_limit(&routing, None), 2000);
+        assert_eq!(normalize_event_query_limit(&routing, Some(999_999)), 5000);
     }
 
     #[test]
@@ -2807,6 +3381,8 @@ mod tests {
             is_agent: false,
             is_review: false,
             confirmed_router: false,
+            auth_token_label: None,
+            peer_process: None,
         };
         merge_discovered_model_provider(&mut entry, Some("openai"));
         assert_eq!(entry.last_reported_model_provider.as_deref(), Some("openai"));
@@ -2830,6 +3406,14 @@ mod tests {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
         providers.insert(
@@ -2843,12 +3427,21 @@ mod tests {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
         let cfg = AppConfig {
             listen: ListenConfig {
                 host: "127.0.0.1".to_string(),
                 port: 4000,
+                unix_socket_path: None,
             },
             routing: RoutingConfig {
                 preferred_provider: "p1".to_string(),
@@ -2859,9 +3452,38 @@ mod tests {
                 failure_threshold: 2,
                 cooldown_seconds: 30,
                 request_timeout_seconds: 300,
+                session_sticky_ttl_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+                usage_label: None,
+                max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+                allowed_peer_ips: Vec::new(),
+                connect_timeout_seconds: None,
+                read_timeout_seconds: None,
+                otel_endpoint: None,
+                key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+                default_base_url: None,
+                default_usage_adapter: None,
+                active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+                session_active_window_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+                read_only: false,
+                spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+                ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+                fractional_percent_display: false,
+                anthropic_compat: false,
+                cors_allowed_origins: Vec::new(),
+                max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+                evict_usage_requests: false,
+                event_query_default_limit:
+                    crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+                event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+                keepalive_warm_seconds: 0,
             },
             providers,
             provider_order: vec!["p1".to_string(), "p2".to_string()],
+            events: Default::default(),
+            switchboard_profiles: Default::default(),
+            provider_templates: std::collections::BTreeMap::new(),
         };
         let now = unix_ms();
         let state = GatewayState {
@@ -2874,6 +3496,7 @@ mod tests {
             last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
             usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
             prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+            provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
             client_sessions: Arc::new(RwLock::new(HashMap::from([(
                 "main-session".to_string(),
                 ClientSessionRuntime {
@@ -2890,6 +3513,8 @@ mod tests {
                     is_agent: false,
                     is_review: false,
                     confirmed_router: true,
+                    auth_token_label: None,
+                    peer_process: None,
                 },
             )]))),
         };
@@ -2909,7 +3534,7 @@ mod tests {
             None::<&LastUsedRoute>,
         );
         assert_eq!(provider.as_deref(), Some("p1"));
-        assert_eq!(reason.as_deref(), Some("balanced_auto"));
+        assert_eq!(reason, Some(RouteReason::BalancedAuto));
         assert!(
             state
                 .store
@@ -2941,6 +3566,14 @@ mod tests {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
         providers.insert(
@@ -2954,12 +3587,21 @@ mod tests {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
         let cfg = AppConfig {
             listen: ListenConfig {
                 host: "127.0.0.1".to_string(),
                 port: 4000,
+                unix_socket_path: None,
             },
             routing: RoutingConfig {
                 preferred_provider: "p1".to_string(),
@@ -2970,9 +3612,38 @@ mod tests {
                 failure_threshold: 2,
                 cooldown_seconds: 30,
                 request_timeout_seconds: 300,
+                session_sticky_ttl_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+                usage_label: None,
+                max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+                allowed_peer_ips: Vec::new(),
+                connect_timeout_seconds: None,
+                read_timeout_seconds: None,
+                otel_endpoint: None,
+                key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+                default_base_url: None,
+                default_usage_adapter: None,
+                active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+                session_active_window_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+                read_only: false,
+                spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+                ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+                fractional_percent_display: false,
+                anthropic_compat: false,
+                cors_allowed_origins: Vec::new(),
+                max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+                evict_usage_requests: false,
+                event_query_default_limit:
+                    crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+                event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+                keepalive_warm_seconds: 0,
             },
             providers,
             provider_order: vec!["p1".to_string(), "p2".to_string()],
+            events: Default::default(),
+            switchboard_profiles: Default::default(),
+            provider_templates: std::collections::BTreeMap::new(),
         };
         let now = unix_ms();
         let state = GatewayState {
@@ -2985,6 +3656,7 @@ mod tests {
             last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
             usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
             prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+            provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
             client_sessions: Arc::new(RwLock::new(HashMap::from([
                 (
                     "session-a".to_string(),
@@ -3002,6 +3674,8 @@ mod tests {
                         is_agent: false,
                         is_review: false,
                         confirmed_router: true,
+                        auth_token_label: None,
+                        peer_process: None,
                     },
                 ),
                 (
@@ -3020,6 +3694,8 @@ mod tests {
                         is_agent: false,
                         is_review: false,
                         confirmed_router: true,
+                        auth_token_label: None,
+                        peer_process: None,
                     },
                 ),
             ]))),
@@ -3069,12 +3745,21 @@ mod tests {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
         let cfg = AppConfig {
             listen: ListenConfig {
                 host: "127.0.0.1".to_string(),
                 port: 4000,
+                unix_socket_path: None,
             },
             routing: RoutingConfig {
                 preferred_provider: "p1".to_string(),
@@ -3085,9 +3770,38 @@ mod tests {
                 failure_threshold: 2,
                 cooldown_seconds: 30,
                 request_timeout_seconds: 300,
+                session_sticky_ttl_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+                usage_label: None,
+                max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+                allowed_peer_ips: Vec::new(),
+                connect_timeout_seconds: None,
+                read_timeout_seconds: None,
+                otel_endpoint: None,
+                key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+                default_base_url: None,
+                default_usage_adapter: None,
+                active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+                session_active_window_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+                read_only: false,
+                spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+                ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+                fractional_percent_display: false,
+                anthropic_compat: false,
+                cors_allowed_origins: Vec::new(),
+                max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+                evict_usage_requests: false,
+                event_query_default_limit:
+                    crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+                event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+                keepalive_warm_seconds: 0,
             },
             providers,
             provider_order: vec!["p1".to_string()],
+            events: Default::default(),
+            switchboard_profiles: Default::default(),
+            provider_templates: std::collections::BTreeMap::new(),
         };
         let now = unix_ms();
         let state = GatewayState {
@@ -3102,7 +3816,7 @@ mod tests {
                     "main-session".to_string(),
                     LastUsedRoute {
                         provider: "p1".to_string(),
-                        reason: "balanced_auto".to_string(),
+                        reason: RouteReason::BalancedAuto,
                         preferred: "p1".to_string(),
                         unix_ms: now,
                     },
@@ -3111,7 +3825,7 @@ mod tests {
                     "agent-session".to_string(),
                     LastUsedRoute {
                         provider: "p1".to_string(),
-                        reason: "balanced_auto".to_string(),
+                        reason: RouteReason::BalancedAuto,
                         preferred: "p1".to_string(),
                         unix_ms: now,
                     },
@@ -3119,6 +3833,7 @@ mod tests {
             ]))),
             usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
             prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+            provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
             client_sessions: Arc::new(RwLock::new(HashMap::new())),
         };
         state
@@ -3169,6 +3884,8 @@ mod tests {
                     is_agent: false,
                     is_review: false,
                     confirmed_router: true,
+                    auth_token_label: None,
+                    peer_process: None,
                 },
             ),
             (
@@ -3187,6 +3904,8 @@ mod tests {
                     is_agent: true,
                     is_review: false,
                     confirmed_router: true,
+                    auth_token_label: None,
+                    peer_process: None,
                 },
             ),
             (
@@ -3205,6 +3924,8 @@ mod tests {
                     is_agent: true,
                     is_review: true,
                     confirmed_router: true,
+                    auth_token_label: None,
+                    peer_process: None,
                 },
             ),
         ]);
@@ -3225,6 +3946,7 @@ mod tests {
             listen: ListenConfig {
                 host: "127.0.0.1".to_string(),
                 port: 4000,
+                unix_socket_path: None,
             },
             routing: RoutingConfig {
                 preferred_provider: "p1".to_string(),
@@ -3235,6 +3957,32 @@ mod tests {
                 failure_threshold: 2,
                 cooldown_seconds: 30,
                 request_timeout_seconds: 300,
+                session_sticky_ttl_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+                usage_label: None,
+                max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+                allowed_peer_ips: Vec::new(),
+                connect_timeout_seconds: None,
+                read_timeout_seconds: None,
+                otel_endpoint: None,
+                key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+                default_base_url: None,
+                default_usage_adapter: None,
+                active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+                session_active_window_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+                read_only: false,
+                spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+                ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+                fractional_percent_display: false,
+                anthropic_compat: false,
+                cors_allowed_origins: Vec::new(),
+                max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+                evict_usage_requests: false,
+                event_query_default_limit:
+                    crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+                event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+                keepalive_warm_seconds: 0,
             },
             providers: std::collections::BTreeMap::from([(
                 "p1".to_string(),
@@ -3247,9 +3995,20 @@ mod tests {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             )]),
             provider_order: vec!["p1".to_string()],
+            events: Default::default(),
+            switchboard_profiles: Default::default(),
+            provider_templates: std::collections::BTreeMap::new(),
         };
         let now = unix_ms();
         let mk = |sid: &str, is_agent: bool, is_review: bool| ClientSessionRuntime {
@@ -3266,6 +4025,8 @@ mod tests {
             is_agent,
             is_review,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
         let state = GatewayState {
             cfg: Arc::new(RwLock::new(cfg.clone())),
@@ -3279,7 +4040,7 @@ mod tests {
                     "main-a".to_string(),
                     LastUsedRoute {
                         provider: "p1".to_string(),
-                        reason: "balanced_auto".to_string(),
+                        reason: RouteReason::BalancedAuto,
                         preferred: "p1".to_string(),
                         unix_ms: now,
                     },
@@ -3288,7 +4049,7 @@ mod tests {
                     "agent-a".to_string(),
                     LastUsedRoute {
                         provider: "p1".to_string(),
-                        reason: "balanced_auto".to_string(),
+                        reason: RouteReason::BalancedAuto,
                         preferred: "p1".to_string(),
                         unix_ms: now,
                     },
@@ -3296,6 +4057,7 @@ mod tests {
             ]))),
             usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
             prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+            provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
             client_sessions: Arc::new(RwLock::new(HashMap::from([
                 ("main-a".to_string(), mk("main-a", false, false)),
                 ("agent-a".to_string(), mk("agent-a", true, false)),
@@ -3365,6 +4127,14 @@ mod tests {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
         providers.insert(
@@ -3378,12 +4148,21 @@ mod tests {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
         let cfg = AppConfig {
             listen: ListenConfig {
                 host: "127.0.0.1".to_string(),
                 port: 4000,
+                unix_socket_path: None,
             },
             routing: RoutingConfig {
                 preferred_provider: "p1".to_string(),
@@ -3394,9 +4173,38 @@ mod tests {
                 failure_threshold: 2,
                 cooldown_seconds: 30,
                 request_timeout_seconds: 300,
+                session_sticky_ttl_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+                usage_label: None,
+                max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+                allowed_peer_ips: Vec::new(),
+                connect_timeout_seconds: None,
+                read_timeout_seconds: None,
+                otel_endpoint: None,
+                key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+                default_base_url: None,
+                default_usage_adapter: None,
+                active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+                session_active_window_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+                read_only: false,
+                spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+                ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+                fractional_percent_display: false,
+                anthropic_compat: false,
+                cors_allowed_origins: Vec::new(),
+                max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+                evict_usage_requests: false,
+                event_query_default_limit:
+                    crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+                event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+                keepalive_warm_seconds: 0,
             },
             providers,
             provider_order: vec!["p1".to_string(), "p2".to_string()],
+            events: Default::default(),
+            switchboard_profiles: Default::default(),
+            provider_templates: std::collections::BTreeMap::new(),
         };
         let now = unix_ms();
         let state = GatewayState {
@@ -3409,6 +4217,7 @@ mod tests {
             last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
             usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
             prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+            provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
             client_sessions: Arc::new(RwLock::new(HashMap::new())),
         };
 
@@ -3426,7 +4235,7 @@ mod tests {
 
         let observed = LastUsedRoute {
             provider: "p2".to_string(),
-            reason: "preferred_unhealthy".to_string(),
+            reason: RouteReason::PreferredUnhealthy,
             preferred: "p1".to_string(),
             unix_ms: now,
         };
@@ -3440,7 +4249,7 @@ mod tests {
             Some(&observed),
         );
         assert_eq!(provider.as_deref(), Some("p2"));
-        assert_eq!(reason.as_deref(), Some("preferred_unhealthy"));
+        assert_eq!(reason, Some(RouteReason::PreferredUnhealthy));
         assert!(
             state.router.is_waiting_usage_confirmation("p1"),
             "using observed route should not trigger display routing side effects"
@@ -3465,6 +4274,14 @@ mod tests {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
         providers.insert(
@@ -3478,12 +4295,21 @@ mod tests {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
         let cfg = AppConfig {
             listen: ListenConfig {
                 host: "127.0.0.1".to_string(),
                 port: 4000,
+                unix_socket_path: None,
             },
             routing: RoutingConfig {
                 preferred_provider: "p1".to_string(),
@@ -3494,9 +4320,38 @@ mod tests {
                 failure_threshold: 2,
                 cooldown_seconds: 30,
                 request_timeout_seconds: 300,
+                session_sticky_ttl_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+                usage_label: None,
+                max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+                allowed_peer_ips: Vec::new(),
+                connect_timeout_seconds: None,
+                read_timeout_seconds: None,
+                otel_endpoint: None,
+                key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+                default_base_url: None,
+                default_usage_adapter: None,
+                active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+                session_active_window_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+                read_only: false,
+                spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+                ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+                fractional_percent_display: false,
+                anthropic_compat: false,
+                cors_allowed_origins: Vec::new(),
+                max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+                evict_usage_requests: false,
+                event_query_default_limit:
+                    crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+                event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+                keepalive_warm_seconds: 0,
             },
             providers,
             provider_order: vec!["p1".to_string(), "p2".to_string()],
+            events: Default::default(),
+            switchboard_profiles: Default::default(),
+            provider_templates: std::collections::BTreeMap::new(),
         };
         let now = unix_ms();
         let state = GatewayState {
@@ -3509,6 +4364,7 @@ mod tests {
             last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
             usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
             prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+            provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
             client_sessions: Arc::new(RwLock::new(HashMap::from([(
                 "main-session".to_string(),
                 ClientSessionRuntime {
@@ -3525,6 +4381,8 @@ mod tests {
                     is_agent: false,
                     is_review: false,
                     confirmed_router: true,
+                    auth_token_label: None,
+                    peer_process: None,
                 },
             )]))),
         };
@@ -3547,7 +4405,7 @@ mod tests {
             None::<&LastUsedRoute>,
         );
         assert_eq!(before_provider.as_deref(), Some("p1"));
-        assert_eq!(before_reason.as_deref(), Some("balanced_auto"));
+        assert_eq!(before_reason, Some(RouteReason::BalancedAuto));
 
         *state.router.manual_override.write() = Some("p2".to_string());
         let after_scope = displayed_session_route_cache_scope(
@@ -3574,7 +4432,7 @@ mod tests {
         );
 
         assert_eq!(after_provider.as_deref(), Some("p2"));
-        assert_eq!(after_reason.as_deref(), Some("manual_override"));
+        assert_eq!(after_reason, Some(RouteReason::ManualOverride));
     }
 
     #[test]
@@ -3596,6 +4454,14 @@ mod tests {
                     supports_websockets: false,
                     group: None,
                     disabled: false,
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -3609,6 +4475,14 @@ mod tests {
                     supports_websockets: false,
                     group: None,
                     disabled: false,
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]);
@@ -3616,6 +4490,7 @@ mod tests {
             listen: ListenConfig {
                 host: "127.0.0.1".to_string(),
                 port: 4000,
+                unix_socket_path: None,
             },
             routing: RoutingConfig {
                 preferred_provider: "p1".to_string(),
@@ -3626,9 +4501,38 @@ mod tests {
                 failure_threshold: 2,
                 cooldown_seconds: 30,
                 request_timeout_seconds: 300,
+                session_sticky_ttl_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+                usage_label: None,
+                max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+                allowed_peer_ips: Vec::new(),
+                connect_timeout_seconds: None,
+                read_timeout_seconds: None,
+                otel_endpoint: None,
+                key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+                default_base_url: None,
+                default_usage_adapter: None,
+                active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+                session_active_window_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+                read_only: false,
+                spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+                ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+                fractional_percent_display: false,
+                anthropic_compat: false,
+                cors_allowed_origins: Vec::new(),
+                max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+                evict_usage_requests: false,
+                event_query_default_limit:
+                    crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+                event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+                keepalive_warm_seconds: 0,
             },
             providers,
             provider_order: vec!["p1".to_string(), "p2".to_string()],
+            events: Default::default(),
+            switchboard_profiles: Default::default(),
+            provider_templates: std::collections::BTreeMap::new(),
         };
         let now = unix_ms();
         let state = GatewayState {
@@ -3641,6 +4545,7 @@ mod tests {
             last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
             usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
             prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+            provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
             client_sessions: Arc::new(RwLock::new(HashMap::new())),
         };
         state
@@ -3686,7 +4591,7 @@ mod tests {
             None::<&LastUsedRoute>,
         );
         assert_eq!(before_provider.as_deref(), Some("p2"));
-        assert_eq!(before_reason.as_deref(), Some("preferred_unhealthy"));
+        assert_eq!(before_reason, Some(RouteReason::PreferredUnhealthy));
 
         state
             .secrets
@@ -3723,7 +4628,7 @@ mod tests {
             None::<&LastUsedRoute>,
         );
         assert_eq!(after_provider.as_deref(), Some("p1"));
-        assert_eq!(after_reason.as_deref(), Some("preferred_healthy"));
+        assert_eq!(after_reason, Some(RouteReason::PreferredHealthy));
     }
 
     #[test]
@@ -3745,6 +4650,8 @@ mod tests {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: false,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
         map.insert(
@@ -3763,6 +4670,8 @@ mod tests {
                 is_agent: true,
                 is_review: true,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
 
@@ -3792,6 +4701,8 @@ mod tests {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: false,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
         map.insert(
@@ -3810,6 +4721,8 @@ mod tests {
                 is_agent: true,
                 is_review: true,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
 
@@ -3839,6 +4752,8 @@ mod tests {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: false,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
         map.insert(
@@ -3857,6 +4772,8 @@ mod tests {
                 is_agent: true,
                 is_review: true,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
 
@@ -3886,6 +4803,8 @@ mod tests {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: false,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
         map.insert(
@@ -3904,6 +4823,8 @@ mod tests {
                 is_agent: true,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
 
@@ -3933,6 +4854,8 @@ mod tests {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: false,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
         map.insert(
@@ -3951,6 +4874,8 @@ mod tests {
                 is_agent: true,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
 
@@ -3980,6 +4905,8 @@ mod tests {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
         map.insert(
@@ -3998,6 +4925,8 @@ mod tests {
                 is_agent: true,
                 is_review: false,
                 confirmed_router: false,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
 
@@ -4030,6 +4959,8 @@ mod tests {
                 is_agent: true,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
 
@@ -4063,6 +4994,8 @@ mod tests {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
         map.insert(
@@ -4081,6 +5014,8 @@ mod tests {
                 is_agent: true,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
 
@@ -4109,6 +5044,8 @@ mod tests {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
         map.insert(
@@ -4127,6 +5064,8 @@ mod tests {
                 is_agent: true,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
         for index in 0..20 {
@@ -4147,6 +5086,8 @@ mod tests {
                     is_agent: false,
                     is_review: false,
                     confirmed_router: true,
+                    auth_token_label: None,
+                    peer_process: None,
                 },
             );
         }
@@ -4179,6 +5120,8 @@ mod tests {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
         map.insert(
@@ -4197,6 +5140,8 @@ mod tests {
                 is_agent: true,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
         map.insert(
@@ -4215,6 +5160,8 @@ mod tests {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
         map.insert(
@@ -4235,6 +5182,8 @@ mod tests {
                 is_agent: true,
                 is_review: true,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
 
@@ -4267,6 +5216,8 @@ mod tests {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
         map.insert(
@@ -4285,6 +5236,8 @@ mod tests {
                 is_agent: true,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
         for index in 0..20 {
@@ -4307,6 +5260,8 @@ mod tests {
                     is_agent: false,
                     is_review: false,
                     confirmed_router: true,
+                    auth_token_label: None,
+                    peer_process: None,
                 },
             );
         }
@@ -4339,6 +5294,8 @@ mod tests {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
         map.insert(
@@ -4357,6 +5314,8 @@ mod tests {
                 is_agent: true,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
 
@@ -4388,6 +5347,8 @@ mod tests {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: false,
+                auth_token_label: None,
+                peer_process: None,
             }
         }
 
@@ -4443,6 +5404,8 @@ mod tests {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: false,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
         map.insert(
@@ -4461,6 +5424,8 @@ mod tests {
                 is_agent: true,
                 is_review: true,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
 
@@ -4490,6 +5455,8 @@ mod tests {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: false,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
         map.insert(
@@ -4508,6 +5475,8 @@ mod tests {
                 is_agent: true,
                 is_review: true,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
 
@@ -4548,22 +5517,13 @@ mod tests {
             .gateway
             .store
             .upsert_usage_request_sync_rows(&[UsageRequestSyncRow {
-                id: "row-1".to_string(),
-                unix_ms: now.saturating_add(1),
-                ingested_at_unix_ms: now.saturating_add(1),
-                provider: "packycode".to_string(),
-                api_key_ref: "-".to_string(),
                 model: "gpt-4.1".to_string(),
-                origin: "windows".to_string(),
-                transport: "http".to_string(),
                 session_id: "session-1".to_string(),
-                node_id: "node-a".to_string(),
                 node_name: "DESKTOP-A".to_string(),
                 input_tokens: 0,
                 output_tokens: 0,
                 total_tokens: 128,
-                cache_creation_input_tokens: 0,
-                cache_read_input_tokens: 0,
+                ..UsageRequestSyncRow::test_row("row-1", "packycode", now.saturating_add(1))
             }]);
         assert_eq!(inserted, 1);
 
@@ -4693,11 +5653,14 @@ usage_presentation = "total_only"
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]);
 
         let discovery = crate::platform::windows_terminal::SessionDiscoverySnapshot {
             fresh: true,
+            age_ms: None,
             items: vec![crate::platform::windows_terminal::InferredWtSession {
                 wt_session: "wsl:bb64caa2-94fc-4951-ac56-771dd8e2ce6d".to_string(),
                 pid: 0,
@@ -4755,11 +5718,14 @@ usage_presentation = "total_only"
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]);
 
         let discovery = crate::platform::windows_terminal::SessionDiscoverySnapshot {
             fresh: false,
+            age_ms: None,
             items: vec![crate::platform::windows_terminal::InferredWtSession {
                 wt_session: "existing-tab".to_string(),
                 pid: 0,
@@ -4801,6 +5767,8 @@ usage_presentation = "total_only"
             is_agent: false,
             is_review: false,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep =
@@ -4825,6 +5793,8 @@ usage_presentation = "total_only"
             is_agent: false,
             is_review: false,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep =
@@ -4849,6 +5819,8 @@ usage_presentation = "total_only"
             is_agent: true,
             is_review: false,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep = should_keep_runtime_session(&entry, now, |_pid| true, |_wt| true, false, 1, true);
@@ -4875,6 +5847,8 @@ usage_presentation = "total_only"
             is_agent: false,
             is_review: false,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep = should_keep_runtime_session(&entry, now, |_pid| true, |_wt| true, false, 3, false);
@@ -4898,6 +5872,8 @@ usage_presentation = "total_only"
             is_agent: false,
             is_review: false,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep =
@@ -4922,6 +5898,8 @@ usage_presentation = "total_only"
             is_agent: true,
             is_review: true,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep = should_keep_runtime_session(&entry, now, |_pid| true, |_wt| true, false, 3, true);
@@ -4945,6 +5923,8 @@ usage_presentation = "total_only"
             is_agent: true,
             is_review: true,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep = should_keep_runtime_session(&entry, now, |_pid| true, |_wt| true, false, 0, true);
@@ -4968,6 +5948,8 @@ usage_presentation = "total_only"
             is_agent: true,
             is_review: false,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep = should_keep_runtime_session(&entry, now, |_pid| true, |_wt| true, false, 0, true);
@@ -4991,6 +5973,8 @@ usage_presentation = "total_only"
             is_agent: true,
             is_review: false,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep = should_keep_runtime_session(&entry, now, |_pid| true, |_wt| true, false, 0, true);
@@ -5016,6 +6000,8 @@ usage_presentation = "total_only"
             is_agent: true,
             is_review: false,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep = should_keep_runtime_session(&entry, now, |_pid| true, |_wt| true, true, 0, true);
@@ -5042,6 +6028,8 @@ usage_presentation = "total_only"
             is_agent: false,
             is_review: false,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep =
@@ -5066,6 +6054,8 @@ usage_presentation = "total_only"
             is_agent: false,
             is_review: false,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep = should_keep_runtime_session(&entry, now, |_pid| true, |_wt| false, false, 0, false);
@@ -5089,6 +6079,8 @@ usage_presentation = "total_only"
             is_agent: false,
             is_review: false,
             confirmed_router: false,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep = should_keep_runtime_session(&entry, now, |_pid| true, |_wt| true, false, 0, true);
@@ -5223,6 +6215,8 @@ usage_presentation = "total_only"
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         );
 
@@ -5255,6 +6249,8 @@ usage_presentation = "total_only"
                 is_agent: false,
                 is_review: false,
                 confirmed_router: false,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]);
 
@@ -5326,6 +6322,8 @@ usage_presentation = "total_only"
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]);
 
@@ -5362,6 +6360,8 @@ usage_presentation = "total_only"
                 is_agent: false,
                 is_review: false,
                 confirmed_router: false,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]);
 
@@ -5408,6 +6408,8 @@ usage_presentation = "total_only"
             is_agent: false,
             is_review: false,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep = should_keep_runtime_session(&entry, now, |_pid| true, |_wt| true, false, 0, true);
@@ -5437,6 +6439,8 @@ usage_presentation = "total_only"
             is_agent: false,
             is_review: false,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep = should_keep_runtime_session(&entry, now, |_pid| true, |_wt| true, false, 0, true);
@@ -5468,6 +6472,8 @@ usage_presentation = "total_only"
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]);
 
@@ -5502,6 +6508,8 @@ usage_presentation = "total_only"
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]);
 
@@ -5553,6 +6561,8 @@ usage_presentation = "total_only"
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]);
 
@@ -5604,6 +6614,8 @@ usage_presentation = "total_only"
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]);
 
@@ -5653,6 +6665,8 @@ usage_presentation = "total_only"
             is_agent: false,
             is_review: false,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep = should_keep_runtime_session(&entry, now, |_pid| true, |_wt| true, false, 0, true);
@@ -5679,6 +6693,8 @@ usage_presentation = "total_only"
             is_agent: false,
             is_review: false,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep = should_keep_runtime_session(&entry, now, |_pid| true, |_wt| true, true, 0, true);
@@ -5702,6 +6718,8 @@ usage_presentation = "total_only"
             is_agent: true,
             is_review: false,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep = should_keep_runtime_session(&entry, now, |_pid| true, |_wt| true, false, 0, true);
@@ -5728,6 +6746,8 @@ usage_presentation = "total_only"
             is_agent: false,
             is_review: false,
             confirmed_router: true,
+            auth_token_label: None,
+            peer_process: None,
         };
 
         let keep = should_keep_runtime_session(&entry, now, |_pid| true, |_wt| true, false, 0, true);
@@ -5739,22 +6759,208 @@ usage_presentation = "total_only"
         assert_eq!(next_last_discovered_unix_ms(1234, 9999, false), 1234);
         assert_eq!(next_last_discovered_unix_ms(1234, 9999, true), 9999);
     }
+
+    /// UTC+0 with a +1h DST from 2026-03-08 00:00 to 2026-11-01 01:00 local, so the spring
+    /// midnight does not exist and the autumn midnight happens twice.
+    #[derive(Clone, Copy, Debug)]
+    struct MidnightDstZone;
+
+    fn midnight_dst_zone_utc(month: u32, day: u32, hour: u32) -> chrono::NaiveDateTime {
+        chrono::NaiveDate::from_ymd_opt(2026, month, day)
+            .and_then(|date| date.and_hms_opt(hour, 0, 0))
+            .unwrap()
+    }
+
+    impl chrono::TimeZone for MidnightDstZone {
+        type Offset = chrono::FixedOffset;
+
+        fn from_offset(_offset: &chrono::FixedOffset) -> Self {
+            MidnightDstZone
+        }
+
+        fn offset_from_local_date(
+            &self,
+            local: &chrono::NaiveDate,
+        ) -> chrono::LocalResult<chrono::FixedOffset> {
+            self.offset_from_local_datetime(&local.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_local_datetime(
+            &self,
+            local: &chrono::NaiveDateTime,
+        ) -> chrono::LocalResult<chrono::FixedOffset> {
+            let valid: Vec<chrono::FixedOffset> = [3600, 0]
+                .into_iter()
+                .map(|secs| chrono::FixedOffset::east_opt(secs).unwrap())
+                .filter(|offset| {
+                    let utc = *local - chrono::Duration::seconds(offset.local_minus_utc().into());
+                    self.offset_from_utc_datetime(&utc) == *offset
+                })
+                .collect();
+            match valid.as_slice() {
+                [] => chrono::LocalResult::None,
+                [only] => chrono::LocalResult::Single(*only),
+                [first, second, ..] => chrono::LocalResult::Ambiguous(*first, *second),
+            }
+        }
+
+        fn offset_from_utc_date(&self, utc: &chrono::NaiveDate) -> chrono::FixedOffset {
+            self.offset_from_utc_datetime(&utc.and_hms_opt(0, 0, 0).unwrap())
+        }
+
+        fn offset_from_utc_datetime(&self, utc: &chrono::NaiveDateTime) -> chrono::FixedOffset {
+            let dst =
+                *utc >= midnight_dst_zone_utc(3, 8, 0) && *utc < midnight_dst_zone_utc(11, 1, 0);
+            chrono::FixedOffset::east_opt(if dst { 3600 } else { 0 }).unwrap()
+        }
+    }
+
+    fn utc_ms(month: u32, day: u32, hour: u32) -> u64 {
+        let utc = midnight_dst_zone_utc(month, day, hour);
+        u64::try_from(utc.and_utc().timestamp_millis()).unwrap()
+    }
+
+    #[test]
+    fn spring_forward_midnight_shifts_to_the_transition() {
+        for ambiguous in [AmbiguousLocalTime::Earliest, AmbiguousLocalTime::Latest] {
+            let (start, end) =
+                day_range_from_key_in_zone(&MidnightDstZone, "2026-03-08", ambiguous).unwrap();
+            assert_eq!(start, utc_ms(3, 8, 0));
+            assert_eq!(end, utc_ms(3, 8, 23));
+            let (_, previous_end) =
+                day_range_from_key_in_zone(&MidnightDstZone, "2026-03-07", ambiguous).unwrap();
+            assert_eq!(previous_end, start);
+        }
+    }
+
+    #[test]
+    fn fall_back_midnight_follows_ambiguous_policy() {
+        let (earliest_start, earliest_end) = day_range_from_key_in_zone(
+            &MidnightDstZone,
+            "2026-11-01",
+            AmbiguousLocalTime::Earliest,
+        )
+        .unwrap();
+        assert_eq!(earliest_start, utc_ms(10, 31, 23));
+        assert_eq!(earliest_end, utc_ms(11, 2, 0));
+
+        let (latest_start, latest_end) =
+            day_range_from_key_in_zone(&MidnightDstZone, "2026-11-01", AmbiguousLocalTime::Latest)
+                .unwrap();
+        assert_eq!(latest_start, utc_ms(11, 1, 0));
+        assert_eq!(latest_end, utc_ms(11, 2, 0));
+
+        let (_, previous_end) =
+            day_range_from_key_in_zone(&MidnightDstZone, "2026-10-31", AmbiguousLocalTime::Latest)
+                .unwrap();
+        assert_eq!(previous_end, latest_start);
+    }
+
+    #[test]
+    fn every_day_key_resolves_to_a_contiguous_range_across_dst() {
+        for ambiguous in [AmbiguousLocalTime::Earliest, AmbiguousLocalTime::Latest] {
+            let mut date = chrono::NaiveDate::from_ymd_opt(2026, 1, 1).unwrap();
+            let mut previous_end = None;
+            while date.year() == 2026 {
+                let day_key = date.format("%Y-%m-%d").to_string();
+                let (start, end) =
+                    day_range_from_key_in_zone(&MidnightDstZone, &day_key, ambiguous)
+                        .unwrap_or_else(|| panic!("{day_key} should resolve"));
+                assert!(end > start, "{day_key} range should not be empty");
+                if let Some(previous_end) = previous_end {
+                    assert_eq!(
+                        previous_end, start,
+                        "{day_key} should follow the previous day"
+                    );
+                }
+                previous_end = Some(end);
+                date = date.succ_opt().unwrap();
+            }
+        }
+    }
+
+    #[test]
+    fn tiered_request_cost_applies_each_request_to_its_daily_tier() {
+        use crate::orchestrator::secrets::ProviderPricingTier;
+
+        let tiers = [
+            ProviderPricingTier {
+                up_to_requests: Some(10),
+                usd_per_request: 0.05,
+            },
+            ProviderPricingTier {
+                up_to_requests: Some(50),
+                usd_per_request: 0.03,
+            },
+            ProviderPricingTier {
+                up_to_requests: None,
+                usd_per_request: 0.01,
+            },
+        ];
+        let close = |left: f64, right: f64| (left - right).abs() < 1e-9;
+        assert!(close(
+            tiered_request_cost_usd(&tiers, 0, 60),
+            0.5 + 1.2 + 0.1
+        ));
+        // Requests 9..=13 straddle the first boundary.
+        assert!(close(
+            tiered_request_cost_usd(&tiers, 8, 5),
+            2.0 * 0.05 + 3.0 * 0.03
+        ));
+        assert!(close(tiered_request_cost_usd(&tiers, 100, 4), 4.0 * 0.01));
+        assert_eq!(tiered_request_cost_usd(&tiers, 7, 0), 0.0);
+
+        // A bounded last tier keeps charging its rate past the boundary.
+        assert!(close(
+            tiered_request_cost_usd(&tiers[..2], 40, 20),
+            20.0 * 0.03
+        ));
+    }
+}
+
+fn local_day_range_from_key(day_key: &str, ambiguous: AmbiguousLocalTime) -> Option<(u64, u64)> {
+    day_range_from_key_in_zone(&Local, day_key, ambiguous)
 }
 
-fn local_day_range_from_key(day_key: &str) -> Option<(u64, u64)> {
+/// Midnight of `day_key` up to midnight of the next day in `tz`. Both ends go through
+/// `resolve_zone_midnight`, so consecutive days stay contiguous and DST days span 23 or 25 hours.
+fn day_range_from_key_in_zone<Tz: TimeZone>(
+    tz: &Tz,
+    day_key: &str,
+    ambiguous: AmbiguousLocalTime,
+) -> Option<(u64, u64)> {
     let date = NaiveDate::parse_from_str(day_key, "%Y-%m-%d").ok()?;
-    let start_naive = date.and_hms_opt(0, 0, 0)?;
-    let start = match Local.from_local_datetime(&start_naive) {
-        LocalResult::Single(dt) => dt,
-        LocalResult::Ambiguous(a, b) => a.min(b),
-        LocalResult::None => return None,
-    };
-    let end = start + chrono::Duration::days(1);
-    let start_ms = u64::try_from(start.timestamp_millis()).ok()?;
-    let end_ms = u64::try_from(end.timestamp_millis()).ok()?;
+    let start_ms = resolve_zone_midnight(tz, date, ambiguous)?;
+    let end_ms = resolve_zone_midnight(tz, date.succ_opt()?, ambiguous)?;
+    if end_ms <= start_ms {
+        return None;
+    }
     Some((start_ms, end_ms))
 }
 
+/// Ambiguous midnights pick the earlier or later instant per `ambiguous`; a midnight skipped by
+/// a spring-forward moves to the first wall-clock minute that exists, i.e. the transition itself.
+fn resolve_zone_midnight<Tz: TimeZone>(
+    tz: &Tz,
+    date: NaiveDate,
+    ambiguous: AmbiguousLocalTime,
+) -> Option<u64> {
+    let midnight = date.and_hms_opt(0, 0, 0)?;
+    for minute in 0..24 * 60 {
+        let local = midnight + chrono::Duration::minutes(minute);
+        let resolved = match tz.from_local_datetime(&local) {
+            LocalResult::Single(dt) => dt,
+            LocalResult::Ambiguous(a, b) => match ambiguous {
+                AmbiguousLocalTime::Earliest => a.min(b),
+                AmbiguousLocalTime::Latest => a.max(b),
+            },
+            LocalResult::None => continue,
+        };
+        return u64::try_from(resolved.timestamp_millis()).ok();
+    }
+    None
+}
+
 fn add_package_total_segment_by_day(
     by_day: &mut BTreeMap<String, f64>,
     package_total_usd: f64,
@@ -5762,6 +6968,7 @@ fn add_package_total_segment_by_day(
     segment_end_unix_ms: u64,
     window_start_unix_ms: u64,
     window_end_unix_ms: u64,
+    ambiguous: AmbiguousLocalTime,
 ) {
     if !package_total_usd.is_finite() || package_total_usd <= 0.0 {
         return;
@@ -5781,7 +6988,7 @@ fn add_package_total_segment_by_day(
         let Some(day_key) = local_day_key_from_unix_ms(cursor) else {
             break;
         };
-        let Some((day_start, day_end)) = local_day_range_from_key(&day_key) else {
+        let Some((day_start, day_end)) = local_day_range_from_key(&day_key, ambiguous) else {
             break;
         };
         let part_start = cursor.max(day_start);
@@ -5805,6 +7012,7 @@ fn package_total_schedule_by_day(
     pricing_cfg: Option<&crate::orchestrator::secrets::ProviderPricingConfig>,
     window_start_unix_ms: u64,
     window_end_unix_ms: u64,
+    ambiguous: AmbiguousLocalTime,
 ) -> BTreeMap<String, f64> {
     let mut by_day: BTreeMap<String, f64> = BTreeMap::new();
     let Some(cfg) = pricing_cfg else {
@@ -5826,6 +7034,7 @@ fn package_total_schedule_by_day(
             segment_end,
             window_start_unix_ms,
             window_end_unix_ms,
+            ambiguous,
         );
         has_timeline = true;
     }
@@ -5841,6 +7050,7 @@ fn package_total_schedule_by_day(
             window_end_unix_ms,
             window_start_unix_ms,
             window_end_unix_ms,
+            ambiguous,
         );
     }
     by_day
@@ -5927,8 +7137,18 @@ pub(crate) fn active_package_period(
     pricing_cfg: Option<&crate::orchestrator::secrets::ProviderPricingConfig>,
     now_unix_ms: u64,
 ) -> Option<(f64, Option<u64>)> {
+    active_package_period_with_start(pricing_cfg, now_unix_ms)
+        .map(|(amount, _, ended)| (amount, ended))
+}
+
+/// `(amount_usd, started_at_unix_ms, ended_at_unix_ms)` of the active package. The start is
+/// unknown for the legacy single-amount form without a timeline.
+pub(crate) fn active_package_period_with_start(
+    pricing_cfg: Option<&crate::orchestrator::secrets::ProviderPricingConfig>,
+    now_unix_ms: u64,
+) -> Option<(f64, Option<u64>, Option<u64>)> {
     let cfg = pricing_cfg?;
-    let mut active: Option<(f64, Option<u64>)> = None;
+    let mut active: Option<(f64, Option<u64>, Option<u64>)> = None;
     let mut active_start = 0u64;
     for period in cfg.periods.iter() {
         if period.mode != "package_total" {
@@ -5942,7 +7162,11 @@ pub(crate) fn active_package_period(
             && now_unix_ms < ended
             && period.started_at_unix_ms >= active_start
         {
-            active = Some((period.amount_usd, period.ended_at_unix_ms));
+            active = Some((
+                period.amount_usd,
+                Some(period.started_at_unix_ms),
+                period.ended_at_unix_ms,
+            ));
             active_start = period.started_at_unix_ms;
         }
     }
@@ -5950,7 +7174,7 @@ pub(crate) fn active_package_period(
         return active;
     }
     if cfg.mode == "package_total" && cfg.amount_usd.is_finite() && cfg.amount_usd > 0.0 {
-        return Some((cfg.amount_usd, None));
+        return Some((cfg.amount_usd, None, None));
     }
     None
 }
@@ -6053,10 +7277,14 @@ fn has_per_request_timeline(
     })
 }
 
-fn aligned_bucket_start_unix_ms(ts_unix_ms: u64, bucket_ms: u64) -> Option<u64> {
+fn aligned_bucket_start_unix_ms(
+    ts_unix_ms: u64,
+    bucket_ms: u64,
+    ambiguous: AmbiguousLocalTime,
+) -> Option<u64> {
     if bucket_ms == 24 * 60 * 60 * 1000 {
         let day_key = local_day_key_from_unix_ms(ts_unix_ms)?;
-        let (start, _) = local_day_range_from_key(&day_key)?;
+        let (start, _) = local_day_range_from_key(&day_key, ambiguous)?;
         return Some(start);
     }
     if bucket_ms == 60 * 60 * 1000 {
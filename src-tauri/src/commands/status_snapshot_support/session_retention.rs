@@ -89,8 +89,13 @@ pub(crate) fn retain_live_app_server_sessions(
     removed_main_sessions
 }
 
-pub(crate) fn session_is_active(entry: &ClientSessionRuntime, now: u64) -> bool {
-    entry.last_request_unix_ms > 0 && now.saturating_sub(entry.last_request_unix_ms) < 60_000
+pub(crate) fn session_is_active(
+    entry: &ClientSessionRuntime,
+    now: u64,
+    active_window_ms: u64,
+) -> bool {
+    entry.last_request_unix_ms > 0
+        && now.saturating_sub(entry.last_request_unix_ms) < active_window_ms
 }
 
 pub(crate) fn session_last_seen_unix_ms(entry: &ClientSessionRuntime) -> u64 {
@@ -137,7 +142,12 @@ fn evaluate_runtime_session_retention(
     const PIDLESS_DESKTOP_LIVE_MAX_STALE_MS: u64 = 60 * 1000;
     const PIDLESS_WSL_GATEWAY_HEARTBEAT_MAX_STALE_MS: u64 = 60 * 1000;
 
-    let active = session_is_active(entry, now);
+    // Retention keeps its own fixed idle window; the configurable one only affects display.
+    let active = session_is_active(
+        entry,
+        now,
+        crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS * 1000,
+    );
     if entry.pid != 0 && !is_pid_alive(entry.pid) {
         return SessionRetentionDecision {
             keep: false,
@@ -166,6 +166,8 @@ pub(crate) fn merge_thread_index_session_hints(
                 is_agent: false,
                 is_review: false,
                 confirmed_router: false,
+                auth_token_label: None,
+                peer_process: None,
             });
         let updated_unix_ms = thread_item_updated_unix_ms(item);
         let should_refresh_discovery = snapshot_is_fresh;
@@ -351,6 +353,8 @@ mod tests {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]);
 
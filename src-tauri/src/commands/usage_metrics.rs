@@ -113,6 +113,15 @@ fn normalize_usage_node_filter(nodes: Option<Vec<String>>) -> BTreeSet<String> {
         .collect()
 }
 
+fn normalize_usage_label_filter(labels: Option<Vec<String>>) -> BTreeSet<String> {
+    labels
+        .unwrap_or_default()
+        .into_iter()
+        .map(|raw| raw.trim().to_ascii_lowercase())
+        .filter(|value| !value.is_empty())
+        .collect()
+}
+
 fn list_usage_requests_for_statistics_window(
     store: &crate::orchestrator::store::Store,
     since_unix_ms: u64,
@@ -206,22 +215,44 @@ struct UsageLocalTimeContext {
     hour_start_unix_ms: u64,
 }
 
-fn usage_local_time_context(ts_unix_ms: u64) -> Option<UsageLocalTimeContext> {
-    use chrono::{Datelike, Local, TimeZone, Timelike};
+fn usage_local_time_context(
+    ts_unix_ms: u64,
+    ambiguous: AmbiguousLocalTime,
+) -> Option<UsageLocalTimeContext> {
+    use chrono::{Local, TimeZone, Timelike};
 
     let ts = i64::try_from(ts_unix_ms).ok()?;
     let dt = Local.timestamp_millis_opt(ts).single()?;
-    let day_start = Local
-        .with_ymd_and_hms(dt.year(), dt.month(), dt.day(), 0, 0, 0)
-        .single()?;
+    let day_key = dt.format("%Y-%m-%d").to_string();
+    let (day_start_unix_ms, _) = local_day_range_from_key(&day_key, ambiguous)?;
     let hour_start = dt.with_minute(0)?.with_second(0)?.with_nanosecond(0)?;
     Some(UsageLocalTimeContext {
-        day_key: dt.format("%Y-%m-%d").to_string(),
-        day_start_unix_ms: u64::try_from(day_start.timestamp_millis()).ok()?,
+        day_key,
+        day_start_unix_ms,
         hour_start_unix_ms: u64::try_from(hour_start.timestamp_millis()).ok()?,
     })
 }
 
+fn usage_timeline_bucket_unix_ms(
+    ts_unix_ms: u64,
+    bucket_ms: u64,
+    local_time: Option<&UsageLocalTimeContext>,
+    ambiguous: AmbiguousLocalTime,
+) -> u64 {
+    if bucket_ms == 24 * 60 * 60 * 1000 {
+        local_time
+            .map(|ctx| ctx.day_start_unix_ms)
+            .unwrap_or((ts_unix_ms / bucket_ms) * bucket_ms)
+    } else if bucket_ms == 60 * 60 * 1000 {
+        local_time
+            .map(|ctx| ctx.hour_start_unix_ms)
+            .unwrap_or((ts_unix_ms / bucket_ms) * bucket_ms)
+    } else {
+        aligned_bucket_start_unix_ms(ts_unix_ms, bucket_ms, ambiguous)
+            .unwrap_or((ts_unix_ms / bucket_ms) * bucket_ms)
+    }
+}
+
 fn resolve_budget_or_token_rate_cost(
     allow_latest_day_budget_fallback: bool,
     provider_daily_spent_usd: Option<f64>,
@@ -246,6 +277,34 @@ fn resolve_budget_or_token_rate_cost(
     (None, None, "none".to_string())
 }
 
+/// Projects when the active package runs out at the recent request rate. The cost of one
+/// request is what the package has lost so far (`amount - remaining`) spread over the requests
+/// made since it started. Returns `(burn_rate_usd_per_day, projected_exhaustion_unix_ms)`.
+fn package_exhaustion_projection(
+    package_amount_usd: f64,
+    remaining_usd: Option<f64>,
+    requests_since_package_start: u64,
+    recent_requests: u64,
+    window_hours: u64,
+    now_unix_ms: u64,
+) -> Option<(f64, u64)> {
+    let remaining = remaining_usd.filter(|v| v.is_finite())?.max(0.0);
+    let consumed = package_amount_usd - remaining;
+    if consumed <= 0.0
+        || requests_since_package_start == 0
+        || recent_requests == 0
+        || window_hours == 0
+    {
+        return None;
+    }
+    let cost_per_request = consumed / requests_since_package_start as f64;
+    let burn_rate_usd_per_day =
+        recent_requests as f64 * 24.0 / window_hours as f64 * cost_per_request;
+    let days_left = remaining / burn_rate_usd_per_day;
+    let exhaustion_unix_ms = now_unix_ms.saturating_add((days_left * 86_400_000.0) as u64);
+    Some((burn_rate_usd_per_day, exhaustion_unix_ms))
+}
+
 #[tauri::command]
 #[allow(clippy::too_many_arguments)]
 pub(crate) fn get_usage_request_entries(
@@ -428,6 +487,7 @@ pub(crate) fn get_usage_request_daily_totals(
     days: Option<u64>,
 ) -> serde_json::Value {
     let day_limit = days.unwrap_or(45).clamp(1, 180) as usize;
+    let ambiguous = state.gateway.cfg.read().routing.ambiguous_local_time;
     let visible_providers: BTreeSet<String> = usage_metrics_configured_provider_names(&state.gateway.cfg.read())
         .into_iter()
         .collect();
@@ -449,7 +509,7 @@ pub(crate) fn get_usage_request_daily_totals(
         if !visible_providers.contains(&provider) {
             continue;
         }
-        let Some((day_start_unix_ms, _)) = local_day_range_from_key(&day_key) else {
+        let Some((day_start_unix_ms, _)) = local_day_range_from_key(&day_key, ambiguous) else {
             continue;
         };
         let day_entry = by_day
@@ -514,6 +574,7 @@ pub(crate) fn get_usage_request_daily_totals(
 }
 
 #[tauri::command]
+#[allow(clippy::too_many_arguments)]
 pub(crate) fn get_usage_statistics(
     state: tauri::State<'_, app_state::AppState>,
     detail_level: Option<String>,
@@ -522,6 +583,285 @@ pub(crate) fn get_usage_statistics(
     providers: Option<Vec<String>>,
     models: Option<Vec<String>>,
     origins: Option<Vec<String>>,
+    labels: Option<Vec<String>>,
+) -> serde_json::Value {
+    get_usage_statistics_impl(
+        &state,
+        detail_level,
+        hours,
+        nodes,
+        providers,
+        models,
+        origins,
+        labels,
+    )
+}
+
+const TOP_COST_MODELS_DEFAULT_LIMIT: usize = 10;
+const TOP_COST_MODELS_MAX_LIMIT: usize = 100;
+
+#[tauri::command]
+pub(crate) fn get_top_cost_models(
+    state: tauri::State<'_, app_state::AppState>,
+    hours: Option<u64>,
+    limit: Option<usize>,
+) -> serde_json::Value {
+    get_top_cost_models_impl(&state, hours, limit)
+}
+
+/// Ranks models by the per-model cost estimate from `get_usage_statistics`, which spreads each
+/// provider's average request cost across the models it served.
+fn get_top_cost_models_impl(
+    state: &app_state::AppState,
+    hours: Option<u64>,
+    limit: Option<usize>,
+) -> serde_json::Value {
+    let limit = limit
+        .unwrap_or(TOP_COST_MODELS_DEFAULT_LIMIT)
+        .clamp(1, TOP_COST_MODELS_MAX_LIMIT);
+    let stats = get_usage_statistics_impl(state, None, hours, None, None, None, None, None);
+    let cost_of = |row: &Value| {
+        row.get("estimated_total_cost_usd")
+            .and_then(Value::as_f64)
+            .unwrap_or(0.0)
+    };
+    let mut models: Vec<Value> = stats
+        .pointer("/summary/by_model")
+        .and_then(Value::as_array)
+        .cloned()
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|row| cost_of(row) > 0.0)
+        .map(|row| {
+            serde_json::json!({
+                "model": row.get("model").cloned().unwrap_or(Value::Null),
+                "requests": row.get("requests").and_then(Value::as_u64).unwrap_or(0),
+                "total_tokens": row.get("total_tokens").and_then(Value::as_u64).unwrap_or(0),
+                "estimated_total_cost_usd": cost_of(&row),
+            })
+        })
+        .collect();
+    models.sort_by(|a, b| {
+        cost_of(b).total_cmp(&cost_of(a)).then_with(|| {
+            let am = a.get("model").and_then(Value::as_str).unwrap_or("");
+            let bm = b.get("model").and_then(Value::as_str).unwrap_or("");
+            am.cmp(bm)
+        })
+    });
+    models.truncate(limit);
+    serde_json::json!({
+        "ok": true,
+        "generated_at_unix_ms": stats.get("generated_at_unix_ms").cloned().unwrap_or(Value::Null),
+        "window_hours": stats.get("window_hours").cloned().unwrap_or(Value::Null),
+        "limit": limit,
+        "models": models,
+    })
+}
+
+const USAGE_TIMESERIES_MAX_BUCKETS: u64 = 10_000;
+
+/// Per-provider series in Grafana SimpleJSON shape: `[{target, datapoints: [[value, unix_ms]]}]`.
+///
+/// `cost` spreads each provider's average request cost from `get_usage_statistics` across its
+/// requests, the same estimate the statistics view uses per model.
+pub(crate) fn usage_timeseries(
+    gateway: &crate::orchestrator::gateway::GatewayState,
+    metric: &str,
+    providers: Option<Vec<String>>,
+    from_unix_ms: u64,
+    to_unix_ms: u64,
+    bucket: &str,
+) -> Result<Value, String> {
+    let metric = metric.trim().to_ascii_lowercase();
+    if !matches!(metric.as_str(), "tokens" | "cost" | "requests") {
+        return Err(format!(
+            "unsupported metric {metric:?} (expected tokens, cost, or requests)"
+        ));
+    }
+    let bucket_ms: u64 = match bucket.trim().to_ascii_lowercase().as_str() {
+        "hour" => 60 * 60 * 1000,
+        "day" => 24 * 60 * 60 * 1000,
+        other => {
+            return Err(format!(
+                "unsupported bucket {other:?} (expected hour or day)"
+            ))
+        }
+    };
+    if from_unix_ms >= to_unix_ms {
+        return Err("from must be earlier than to".to_string());
+    }
+    if (to_unix_ms - from_unix_ms) / bucket_ms > USAGE_TIMESERIES_MAX_BUCKETS {
+        return Err(format!(
+            "range spans more than {USAGE_TIMESERIES_MAX_BUCKETS} buckets; use a larger bucket"
+        ));
+    }
+    let cfg = gateway.cfg.read().clone();
+    let ambiguous = cfg.routing.ambiguous_local_time;
+    let align = |ts: u64| {
+        aligned_bucket_start_unix_ms(ts, bucket_ms, ambiguous)
+            .unwrap_or((ts / bucket_ms) * bucket_ms)
+    };
+    let provider_filter = effective_provider_filter(&cfg, providers);
+    let mut series: BTreeMap<String, BTreeMap<u64, (u64, u64)>> = BTreeMap::new();
+    for rec in list_usage_requests_for_statistics_window(&gateway.store, from_unix_ms) {
+        if rec.unix_ms < from_unix_ms
+            || rec.unix_ms >= to_unix_ms
+            || !provider_filter.contains(&rec.provider.to_ascii_lowercase())
+        {
+            continue;
+        }
+        let total_tokens = rec
+            .total_tokens
+            .max(rec.input_tokens.saturating_add(rec.output_tokens));
+        let entry = series
+            .entry(rec.provider)
+            .or_default()
+            .entry(align(rec.unix_ms))
+            .or_insert((0, 0));
+        entry.0 = entry.0.saturating_add(1);
+        entry.1 = entry.1.saturating_add(total_tokens);
+    }
+
+    let mut avg_request_cost: BTreeMap<String, f64> = BTreeMap::new();
+    if metric == "cost" && !series.is_empty() {
+        let hours = unix_ms()
+            .saturating_sub(from_unix_ms)
+            .div_ceil(60 * 60 * 1000)
+            .max(1);
+        let stats = usage_statistics_for_gateway(
+            gateway,
+            None,
+            None,
+            Some(hours),
+            None,
+            Some(series.keys().cloned().collect()),
+            None,
+            None,
+            None,
+        );
+        let mut totals: BTreeMap<String, (f64, u64)> = BTreeMap::new();
+        for row in stats
+            .pointer("/summary/by_provider")
+            .and_then(Value::as_array)
+            .into_iter()
+            .flatten()
+        {
+            let Some(provider) = row.get("provider").and_then(Value::as_str) else {
+                continue;
+            };
+            let entry = totals.entry(provider.to_string()).or_insert((0.0, 0));
+            entry.0 += row
+                .get("estimated_total_cost_usd")
+                .and_then(Value::as_f64)
+                .unwrap_or(0.0);
+            entry.1 += row
+                .get("estimated_cost_request_count")
+                .and_then(Value::as_u64)
+                .unwrap_or(0);
+        }
+        for (provider, (cost, requests)) in totals {
+            if requests > 0 {
+                avg_request_cost.insert(provider, cost / requests as f64);
+            }
+        }
+    }
+
+    let first_bucket = align(from_unix_ms);
+    let last_bucket = align(to_unix_ms - 1);
+    let targets: Vec<Value> = series
+        .into_iter()
+        .map(|(provider, buckets)| {
+            let avg_cost = avg_request_cost.get(&provider).copied().unwrap_or(0.0);
+            let mut datapoints: Vec<Value> = Vec::new();
+            let mut bucket = first_bucket;
+            while bucket <= last_bucket {
+                let (requests, tokens) = buckets.get(&bucket).copied().unwrap_or((0, 0));
+                let value = match metric.as_str() {
+                    "tokens" => serde_json::json!(tokens),
+                    "requests" => serde_json::json!(requests),
+                    _ => serde_json::json!((requests as f64 * avg_cost * 1000.0).round() / 1000.0),
+                };
+                datapoints.push(serde_json::json!([value, bucket]));
+                // DST days are not 24h long, so step from the middle of the next bucket.
+                bucket = align(bucket.saturating_add(bucket_ms + bucket_ms / 2));
+            }
+            serde_json::json!({ "target": provider, "datapoints": datapoints })
+        })
+        .collect();
+    Ok(Value::Array(targets))
+}
+
+#[tauri::command]
+pub(crate) fn export_usage_jsonl(
+    state: tauri::State<'_, app_state::AppState>,
+    from_unix_ms: u64,
+    to_unix_ms: u64,
+    path: String,
+) -> Result<u64, CommandError> {
+    export_usage_jsonl_impl(&state.gateway.store, from_unix_ms, to_unix_ms, &path)
+}
+
+/// Writes raw `usage_requests` rows in `[from_unix_ms, to_unix_ms)` to `path` as JSON Lines,
+/// streaming from SQLite so the export is not bounded by memory.
+fn export_usage_jsonl_impl(
+    store: &crate::orchestrator::store::Store,
+    from_unix_ms: u64,
+    to_unix_ms: u64,
+    path: &str,
+) -> Result<u64, CommandError> {
+    let path = path.trim();
+    if path.is_empty() {
+        return Err(CommandError::Validation(
+            "export path is required".to_string(),
+        ));
+    }
+    if from_unix_ms >= to_unix_ms {
+        return Err(CommandError::Validation(
+            "from_unix_ms must be earlier than to_unix_ms".to_string(),
+        ));
+    }
+    let file = std::fs::File::create(path)?;
+    let mut out = std::io::BufWriter::new(file);
+    Ok(store.export_usage_requests_jsonl(from_unix_ms, to_unix_ms, &mut out)?)
+}
+
+#[allow(clippy::too_many_arguments)]
+fn get_usage_statistics_impl(
+    state: &app_state::AppState,
+    detail_level: Option<String>,
+    hours: Option<u64>,
+    nodes: Option<Vec<String>>,
+    providers: Option<Vec<String>>,
+    models: Option<Vec<String>>,
+    origins: Option<Vec<String>>,
+    labels: Option<Vec<String>>,
+) -> serde_json::Value {
+    usage_statistics_for_gateway(
+        &state.gateway,
+        Some(&state.diagnostics_dir),
+        detail_level,
+        hours,
+        nodes,
+        providers,
+        models,
+        origins,
+        labels,
+    )
+}
+
+/// Body of `get_usage_statistics`, usable from the gateway where no `AppState` exists. Slow
+/// runs are only dumped when a diagnostics directory is given.
+#[allow(clippy::too_many_arguments)]
+fn usage_statistics_for_gateway(
+    gateway: &crate::orchestrator::gateway::GatewayState,
+    diagnostics_dir: Option<&std::path::Path>,
+    detail_level: Option<String>,
+    hours: Option<u64>,
+    nodes: Option<Vec<String>>,
+    providers: Option<Vec<String>>,
+    models: Option<Vec<String>>,
+    origins: Option<Vec<String>>,
+    labels: Option<Vec<String>>,
 ) -> serde_json::Value {
     let command_started_at = std::time::Instant::now();
     let mut phase_timings_ms: Vec<(&'static str, u64)> = Vec::new();
@@ -545,6 +885,10 @@ pub(crate) fn get_usage_statistics(
     struct UsageRow {
         provider: String,
         model: String,
+        input_tokens: u64,
+        output_tokens: u64,
+        cache_creation_input_tokens: u64,
+        cache_read_input_tokens: u64,
     }
 
     #[derive(Default)]
@@ -555,6 +899,8 @@ pub(crate) fn get_usage_statistics(
         total_tokens: u64,
         estimated_total_cost_usd: f64,
         estimated_cost_request_count: u64,
+        cache_creation_cost_usd: f64,
+        cache_read_cost_usd: f64,
     }
 
     #[derive(Default)]
@@ -573,7 +919,8 @@ pub(crate) fn get_usage_statistics(
 
     let now = unix_ms();
     let detail_level = parse_usage_statistics_detail_level(detail_level.as_deref());
-    let cfg = state.gateway.cfg.read().clone();
+    let cfg = gateway.cfg.read().clone();
+    let ambiguous = cfg.routing.ambiguous_local_time;
     let window_hours = hours.unwrap_or(24).clamp(1, 24 * 30);
     let window_ms = window_hours.saturating_mul(60 * 60 * 1000);
     let since_unix_ms = now.saturating_sub(window_ms);
@@ -588,10 +935,12 @@ pub(crate) fn get_usage_statistics(
         .collect();
     let node_filter = normalize_usage_node_filter(nodes);
     let origin_filter = normalize_usage_origin_filter(origins);
+    let label_filter = normalize_usage_label_filter(labels);
     let has_node_filter = !node_filter.is_empty();
     let has_provider_filter = !provider_filter.is_empty();
     let has_model_filter = !model_filter.is_empty();
     let has_origin_filter = !origin_filter.is_empty();
+    let has_label_filter = !label_filter.is_empty();
     let bucket_ms = if window_hours <= 48 {
         60 * 60 * 1000
     } else {
@@ -600,15 +949,15 @@ pub(crate) fn get_usage_statistics(
     let active_bucket_ms = 60 * 60 * 1000;
     let projection_hours = projection_hours_for_day_estimate();
 
-    let records = list_usage_requests_for_statistics_window(&state.gateway.store, since_unix_ms);
+    let records = list_usage_requests_for_statistics_window(&gateway.store, since_unix_ms);
     phase_timings_ms.push((
         "load_usage_requests",
         phase_started_at.elapsed().as_millis().min(u128::from(u64::MAX)) as u64,
     ));
     phase_started_at = std::time::Instant::now();
-    let quota = state.gateway.store.list_quota_snapshots();
-    let mut provider_pricing = state.gateway.store.list_provider_pricing_configs();
-    for (provider_name, config) in state.secrets.list_provider_pricing() {
+    let quota = gateway.store.list_quota_snapshots();
+    let mut provider_pricing = gateway.store.list_provider_pricing_configs();
+    for (provider_name, config) in gateway.secrets.list_provider_pricing() {
         provider_pricing.insert(provider_name, config);
     }
     phase_timings_ms.push((
@@ -633,7 +982,11 @@ pub(crate) fn get_usage_statistics(
     let mut total_tokens = 0u64;
     let mut total_cache_creation_tokens = 0u64;
     let mut total_cache_read_tokens = 0u64;
+    let mut total_request_bytes = 0u64;
+    let mut total_response_bytes = 0u64;
+    let mut sized_requests = 0u64;
     let mut by_model_map: BTreeMap<String, ModelAgg> = BTreeMap::new();
+    let mut by_provider_model_map: BTreeMap<(String, String), ModelAgg> = BTreeMap::new();
     let mut by_provider_map: BTreeMap<String, ProviderAgg> = BTreeMap::new();
     let mut provider_req_by_key_in_window: BTreeMap<String, BTreeMap<String, (u64, u64)>> =
         BTreeMap::new();
@@ -664,7 +1017,7 @@ pub(crate) fn get_usage_statistics(
         let cache_creation_input_tokens = rec.cache_creation_input_tokens;
         let cache_read_input_tokens = rec.cache_read_input_tokens;
 
-        let local_time = usage_local_time_context(ts);
+        let local_time = usage_local_time_context(ts, ambiguous);
         if ts >= last_24h_unix_ms {
             *provider_tokens_24h.entry(provider.clone()).or_default() += total_tokens_row;
         }
@@ -672,6 +1025,11 @@ pub(crate) fn get_usage_statistics(
         let model_matches = !has_model_filter || model_filter.contains(&model_lc);
         let origin_matches = !has_origin_filter || origin_filter.contains(&origin_lc);
         let node_matches = !has_node_filter || node_filter.contains(&node_lc);
+        let label_matches =
+            !has_label_filter || label_filter.contains(&rec.label.trim().to_ascii_lowercase());
+        if !label_matches {
+            continue;
+        }
         if provider_matches {
             if let Some(day_key) = local_time.as_ref().map(|ctx| ctx.day_key.clone()) {
                 provider_req_by_day_all_from_req
@@ -712,6 +1070,12 @@ pub(crate) fn get_usage_statistics(
         total_cache_creation_tokens =
             total_cache_creation_tokens.saturating_add(cache_creation_input_tokens);
         total_cache_read_tokens = total_cache_read_tokens.saturating_add(cache_read_input_tokens);
+        // Rows recorded before body sizes were tracked carry zeros; keep them out of the averages.
+        if rec.request_bytes > 0 || rec.response_bytes > 0 {
+            sized_requests = sized_requests.saturating_add(1);
+            total_request_bytes = total_request_bytes.saturating_add(rec.request_bytes);
+            total_response_bytes = total_response_bytes.saturating_add(rec.response_bytes);
+        }
 
         {
             let entry = by_model_map.entry(model.clone()).or_default();
@@ -720,6 +1084,15 @@ pub(crate) fn get_usage_statistics(
             entry.output_tokens = entry.output_tokens.saturating_add(output_tokens);
             entry.total_tokens = entry.total_tokens.saturating_add(total_tokens_row);
         }
+        {
+            let entry = by_provider_model_map
+                .entry((provider.clone(), model.clone()))
+                .or_default();
+            entry.requests = entry.requests.saturating_add(1);
+            entry.input_tokens = entry.input_tokens.saturating_add(input_tokens);
+            entry.output_tokens = entry.output_tokens.saturating_add(output_tokens);
+            entry.total_tokens = entry.total_tokens.saturating_add(total_tokens_row);
+        }
         {
             let entry = by_provider_map.entry(provider.clone()).or_default();
             entry.requests = entry.requests.saturating_add(1);
@@ -757,23 +1130,44 @@ pub(crate) fn get_usage_statistics(
             .insert(active_hour_bucket);
         active_window_hour_buckets.insert(active_hour_bucket);
 
-        let bucket = if bucket_ms == 24 * 60 * 60 * 1000 {
-            local_time
-                .as_ref()
-                .map(|ctx| ctx.day_start_unix_ms)
-                .unwrap_or((ts / bucket_ms) * bucket_ms)
-        } else if bucket_ms == 60 * 60 * 1000 {
-            active_hour_bucket
-        } else {
-            aligned_bucket_start_unix_ms(ts, bucket_ms).unwrap_or((ts / bucket_ms) * bucket_ms)
-        };
+        let bucket = usage_timeline_bucket_unix_ms(ts, bucket_ms, local_time.as_ref(), ambiguous);
         let entry = timeline.entry(bucket).or_insert((0, 0, 0, 0));
         entry.0 += 1;
         entry.1 += total_tokens_row;
         entry.2 += cache_creation_input_tokens;
         entry.3 += cache_read_input_tokens;
 
-        filtered.push(UsageRow { provider, model });
+        filtered.push(UsageRow {
+            provider,
+            model,
+            input_tokens,
+            output_tokens,
+            cache_creation_input_tokens,
+            cache_read_input_tokens,
+        });
+    }
+
+    // Failures carry only a provider, so they are left out once a model/origin/node/label filter
+    // narrows the view.
+    let mut timeline_errors: BTreeMap<u64, u64> = BTreeMap::new();
+    let mut total_errors = 0u64;
+    if !has_model_filter && !has_origin_filter && !has_node_filter && !has_label_filter {
+        for failure in gateway.store.list_usage_failures_window(since_unix_ms) {
+            if has_provider_filter
+                && !provider_filter.contains(&failure.provider.to_ascii_lowercase())
+            {
+                continue;
+            }
+            let local_time = usage_local_time_context(failure.unix_ms, ambiguous);
+            let bucket = usage_timeline_bucket_unix_ms(
+                failure.unix_ms,
+                bucket_ms,
+                local_time.as_ref(),
+                ambiguous,
+            );
+            *timeline_errors.entry(bucket).or_default() += 1;
+            total_errors = total_errors.saturating_add(1);
+        }
     }
     phase_timings_ms.push((
         "aggregate_request_rows",
@@ -803,6 +1197,37 @@ pub(crate) fn get_usage_statistics(
         }
     }
 
+    // Rows whose model has an explicit token rate are priced from their own tokens everywhere:
+    // per provider, per model and in the summary. Other rows take the provider's request average.
+    let row_model_rate_costs: Vec<Option<crate::orchestrator::secrets::ModelRateCost>> = filtered
+        .iter()
+        .map(|row| {
+            provider_pricing
+                .get(&row.provider)
+                .and_then(|cfg| {
+                    crate::orchestrator::secrets::model_rate_for(&cfg.model_rates, &row.model)
+                })
+                .map(|rate| {
+                    rate.cost(
+                        row.input_tokens,
+                        row.output_tokens,
+                        row.cache_creation_input_tokens,
+                        row.cache_read_input_tokens,
+                    )
+                })
+        })
+        .collect();
+    let mut provider_model_rated: BTreeMap<String, (u64, f64)> = BTreeMap::new();
+    for (row, cost) in filtered.iter().zip(&row_model_rate_costs) {
+        if let Some(cost) = cost {
+            let entry = provider_model_rated
+                .entry(row.provider.clone())
+                .or_default();
+            entry.0 = entry.0.saturating_add(1);
+            entry.1 += cost.total_usd();
+        }
+    }
+
     let mut provider_avg_req_cost: BTreeMap<String, f64> = BTreeMap::new();
     let mut by_provider: Vec<Value> = Vec::new();
     for (provider, agg) in by_provider_map.iter() {
@@ -818,7 +1243,7 @@ pub(crate) fn get_usage_statistics(
         let pricing_cfg = crate::orchestrator::secrets::resolve_provider_pricing_config(
             &provider_pricing,
             provider,
-            Some(&provider_api_key_ref(&state, provider)),
+            Some(&provider_api_key_ref(&gateway.secrets, provider)),
             now,
         );
         let mode = pricing_cfg
@@ -827,9 +1252,27 @@ pub(crate) fn get_usage_statistics(
         let amount_usd = pricing_cfg
             .map(|cfg| cfg.amount_usd)
             .filter(|v| v.is_finite() && *v > 0.0);
+        let package_projection =
+            active_package_period_with_start(pricing_cfg, now).and_then(|(amount, started, _)| {
+                let started = started?;
+                let remaining = quota
+                    .get(provider)
+                    .filter(|q| q.get("kind").and_then(Value::as_str) == Some("balance_info"))
+                    .and_then(|q| as_f64(q.get("remaining")));
+                let (requests_since_start, _) = gateway
+                    .store
+                    .summarize_usage_requests_since_by_provider(provider, started);
+                package_exhaustion_projection(
+                    amount,
+                    remaining,
+                    requests_since_start,
+                    agg.requests,
+                    window_hours,
+                    now,
+                )
+            });
         let req_by_day_in_window = provider_req_by_day_in_window.get(provider);
-        let mut req_by_day = state
-            .gateway
+        let mut req_by_day = gateway
             .store
             .list_usage_request_day_counts_for_provider(provider);
         merge_usage_metrics_day_counts(
@@ -838,7 +1281,7 @@ pub(crate) fn get_usage_statistics(
         );
         let mut manual_by_day: BTreeMap<String, (Option<f64>, Option<f64>, u64)> =
             BTreeMap::new();
-        for day in state.gateway.store.list_spend_manual_days(provider) {
+        for day in gateway.store.list_spend_manual_days(provider) {
             let Some(day_key) = day.get("day_key").and_then(|v| v.as_str()) else {
                 continue;
             };
@@ -909,6 +1352,42 @@ pub(crate) fn get_usage_statistics(
                     pricing_source = "manual_per_request".to_string();
                 }
             }
+            "per_request_tiered" => {
+                let tiers = pricing_cfg
+                    .map(|cfg| cfg.tiers.as_slice())
+                    .unwrap_or_default();
+                let mut total_used = 0.0_f64;
+                let mut priced_reqs = 0u64;
+                if !tiers.is_empty() {
+                    for (day_key, day_req_in_window) in req_by_day_in_window.into_iter().flatten() {
+                        // Requests made earlier that day, before the window opened, already
+                        // filled the day's first tier slots.
+                        let day_req_before_window = req_by_day
+                            .get(day_key)
+                            .copied()
+                            .unwrap_or(0)
+                            .saturating_sub(*day_req_in_window);
+                        total_used += crate::orchestrator::secrets::tiered_request_cost_usd(
+                            tiers,
+                            day_req_before_window,
+                            *day_req_in_window,
+                        );
+                        priced_reqs = priced_reqs.saturating_add(*day_req_in_window);
+                    }
+                }
+                if priced_reqs > 0 {
+                    total_used_cost_usd = Some(total_used);
+                    estimated_avg_request_cost_usd = Some(total_used / priced_reqs as f64);
+                    let projected_reqs = (req_per_hour * projection_hours).round() as u64;
+                    estimated_daily_cost_usd =
+                        Some(crate::orchestrator::secrets::tiered_request_cost_usd(
+                            tiers,
+                            0,
+                            projected_reqs,
+                        ));
+                    pricing_source = "manual_per_request_tiered".to_string();
+                }
+            }
             "package_total" => {
                 let has_package_timeline = pricing_cfg
                     .map(|cfg| {
@@ -918,7 +1397,7 @@ pub(crate) fn get_usage_statistics(
                     })
                     .unwrap_or(false);
                 let scheduled_by_day =
-                    package_total_schedule_by_day(pricing_cfg, since_unix_ms, now);
+                    package_total_schedule_by_day(pricing_cfg, since_unix_ms, now, ambiguous);
                 let forward_window_end = now.saturating_add(window_ms);
                 let scheduled_total_by_slots = package_total_window_total_by_day_slots(
                     pricing_cfg,
@@ -946,7 +1425,8 @@ pub(crate) fn get_usage_statistics(
 
                         let manual_window = manual_by_day.get(&day_key).and_then(
                             |(manual_total, manual_per_req, _)| {
-                                let (day_start, day_end) = local_day_range_from_key(&day_key)?;
+                                let (day_start, day_end) =
+                                    local_day_range_from_key(&day_key, ambiguous)?;
                                 let overlap_start = day_start.max(since_unix_ms);
                                 let overlap_end = day_end.min(now);
                                 if overlap_end <= overlap_start {
@@ -1030,7 +1510,7 @@ pub(crate) fn get_usage_statistics(
             }
             _ => {
                 let spend_days =
-                    tracked_spend_days_with_remote_fallback(&state.gateway.store, provider);
+                    tracked_spend_days_with_remote_fallback(&gateway.store, provider);
                 let mut tracked_in_window = 0.0_f64;
                 for day in spend_days {
                     let tracked = as_f64(day.get("tracked_spend_usd")).unwrap_or(0.0);
@@ -1040,7 +1520,8 @@ pub(crate) fn get_usage_statistics(
                     let Some(day_key) = tracked_spend_day_key(&day) else {
                         continue;
                     };
-                    let Some((day_start, day_end)) = local_day_range_from_key(&day_key) else {
+                    let Some((day_start, day_end)) = local_day_range_from_key(&day_key, ambiguous)
+                    else {
                         continue;
                     };
                     let overlap_start = day_start.max(since_unix_ms);
@@ -1059,7 +1540,8 @@ pub(crate) fn get_usage_statistics(
 
                 let mut manual_additional_in_window = 0.0_f64;
                 for (day_key, (manual_total, manual_per_req, _)) in manual_by_day.iter() {
-                    let Some((day_start, day_end)) = local_day_range_from_key(day_key) else {
+                    let Some((day_start, day_end)) = local_day_range_from_key(day_key, ambiguous)
+                    else {
                         continue;
                     };
                     let overlap_start = day_start.max(since_unix_ms);
@@ -1188,6 +1670,21 @@ pub(crate) fn get_usage_statistics(
                 provider_avg_req_cost.insert(provider.clone(), avg);
             }
         }
+        if let Some(&(rated_requests, rated_cost)) = provider_model_rated.get(provider) {
+            let unrated_cost = provider_avg_req_cost.get(provider).map_or(0.0, |avg| {
+                avg * agg.requests.saturating_sub(rated_requests) as f64
+            });
+            let total_used = rated_cost + unrated_cost;
+            total_used_cost_usd = Some(total_used);
+            if agg.requests > 0 {
+                let avg = total_used / agg.requests as f64;
+                estimated_avg_request_cost_usd = Some(avg);
+                estimated_daily_cost_usd = Some(req_per_hour * projection_hours * avg);
+            }
+            if pricing_source == "none" {
+                pricing_source = "model_rates".to_string();
+            }
+        }
 
         let key_rows = provider_req_by_key_in_window
             .get(provider)
@@ -1195,7 +1692,7 @@ pub(crate) fn get_usage_statistics(
             .unwrap_or_else(|| {
                 let mut fallback = BTreeMap::new();
                 fallback.insert(
-                    provider_api_key_ref(&state, provider),
+                    provider_api_key_ref(&gateway.secrets, provider),
                     (agg.requests, agg.total_tokens),
                 );
                 fallback
@@ -1240,7 +1737,9 @@ pub(crate) fn get_usage_statistics(
                 "pricing_source": pricing_source,
                 "estimated_cost_request_count": estimated_cost_request_count,
                 "actual_tracked_spend_usd": json_num_or_null(actual_tracked_spend_usd.map(|v| v * request_ratio)),
-                "gap_filled_spend_usd": json_num_or_null(gap_filled_spend_usd.map(|v| v * request_ratio))
+                "gap_filled_spend_usd": json_num_or_null(gap_filled_spend_usd.map(|v| v * request_ratio)),
+                "burn_rate_usd_per_day": json_num_or_null(package_projection.map(|(rate, _)| rate * request_ratio)),
+                "projected_exhaustion_unix_ms": package_projection.map(|(_, at)| at)
             }));
         }
     }
@@ -1255,13 +1754,31 @@ pub(crate) fn get_usage_statistics(
         br.cmp(&ar)
     });
 
-    for row in &filtered {
-        if let Some(avg_req) = provider_avg_req_cost.get(&row.provider).copied() {
-            if let Some(entry) = by_model_map.get_mut(&row.model) {
-                entry.estimated_total_cost_usd += avg_req;
-                entry.estimated_cost_request_count =
-                    entry.estimated_cost_request_count.saturating_add(1);
-            }
+    let mut total_cache_creation_cost_usd = 0.0_f64;
+    let mut total_cache_read_cost_usd = 0.0_f64;
+    for (row, model_rate_cost) in filtered.iter().zip(row_model_rate_costs) {
+        let request_cost = model_rate_cost
+            .map(|cost| cost.total_usd())
+            .or_else(|| provider_avg_req_cost.get(&row.provider).copied());
+        let Some(request_cost) = request_cost else {
+            continue;
+        };
+        let cache_cost = model_rate_cost.unwrap_or_default();
+        total_cache_creation_cost_usd += cache_cost.cache_creation_usd;
+        total_cache_read_cost_usd += cache_cost.cache_read_usd;
+        let pair_key = (row.provider.clone(), row.model.clone());
+        for entry in [
+            by_model_map.get_mut(&row.model),
+            by_provider_model_map.get_mut(&pair_key),
+        ]
+        .into_iter()
+        .flatten()
+        {
+            entry.estimated_total_cost_usd += request_cost;
+            entry.estimated_cost_request_count =
+                entry.estimated_cost_request_count.saturating_add(1);
+            entry.cache_creation_cost_usd += cache_cost.cache_creation_usd;
+            entry.cache_read_cost_usd += cache_cost.cache_read_usd;
         }
     }
 
@@ -1287,7 +1804,9 @@ pub(crate) fn get_usage_statistics(
                 "share_pct": round3(share_pct),
                 "estimated_total_cost_usd": round3(agg.estimated_total_cost_usd),
                 "estimated_avg_request_cost_usd": round3(avg_req_cost),
-                "estimated_cost_request_count": agg.estimated_cost_request_count
+                "estimated_cost_request_count": agg.estimated_cost_request_count,
+                "cache_creation_cost_usd": round3(agg.cache_creation_cost_usd),
+                "cache_read_cost_usd": round3(agg.cache_read_cost_usd)
             })
         })
         .collect();
@@ -1297,18 +1816,53 @@ pub(crate) fn get_usage_statistics(
         br.cmp(&ar)
     });
 
-    let first_bucket = aligned_bucket_start_unix_ms(since_unix_ms, bucket_ms)
+    // Provider x model cross-tab: each pair is priced with its own provider's per-request cost,
+    // so the same model served by two providers shows two separate costs.
+    let mut by_provider_model: Vec<Value> = by_provider_model_map
+        .into_iter()
+        .map(|((provider, model), agg)| {
+            let avg_req_cost = if agg.estimated_cost_request_count > 0 {
+                agg.estimated_total_cost_usd / agg.estimated_cost_request_count as f64
+            } else {
+                0.0
+            };
+            serde_json::json!({
+                "provider": provider,
+                "model": model,
+                "requests": agg.requests,
+                "input_tokens": agg.input_tokens,
+                "output_tokens": agg.output_tokens,
+                "total_tokens": agg.total_tokens,
+                "estimated_total_cost_usd": round3(agg.estimated_total_cost_usd),
+                "estimated_avg_request_cost_usd": round3(avg_req_cost),
+                "estimated_cost_request_count": agg.estimated_cost_request_count,
+                "cache_creation_cost_usd": round3(agg.cache_creation_cost_usd),
+                "cache_read_cost_usd": round3(agg.cache_read_cost_usd)
+            })
+        })
+        .collect();
+    by_provider_model.sort_by(|a, b| {
+        let ar = a.get("requests").and_then(|v| v.as_u64()).unwrap_or(0);
+        let br = b.get("requests").and_then(|v| v.as_u64()).unwrap_or(0);
+        br.cmp(&ar)
+    });
+
+    let first_bucket = aligned_bucket_start_unix_ms(since_unix_ms, bucket_ms, ambiguous)
         .unwrap_or((since_unix_ms / bucket_ms) * bucket_ms);
-    let last_bucket =
-        aligned_bucket_start_unix_ms(now, bucket_ms).unwrap_or((now / bucket_ms) * bucket_ms);
+    let last_bucket = aligned_bucket_start_unix_ms(now, bucket_ms, ambiguous)
+        .unwrap_or((now / bucket_ms) * bucket_ms);
     let mut timeline_points: Vec<Value> = Vec::new();
     let mut bucket = first_bucket;
     while bucket <= last_bucket {
         let (requests, tokens, cache_creation_tokens, cache_read_tokens) =
             timeline.get(&bucket).copied().unwrap_or((0, 0, 0, 0));
+        let errors = timeline_errors.get(&bucket).copied().unwrap_or(0);
+        let attempts = requests.saturating_add(errors);
         timeline_points.push(serde_json::json!({
             "bucket_unix_ms": bucket,
             "requests": requests,
+            "errors": errors,
+            "error_rate": (attempts > 0).then(|| round3(errors as f64 / attempts as f64)),
             "total_tokens": tokens,
             "cache_creation_tokens": cache_creation_tokens,
             "cache_read_tokens": cache_read_tokens
@@ -1349,6 +1903,11 @@ pub(crate) fn get_usage_statistics(
     } else {
         Value::Null
     };
+    let filter_labels_json = if has_label_filter {
+        serde_json::json!(label_filter.into_iter().collect::<Vec<_>>())
+    } else {
+        Value::Null
+    };
     let catalog_node_values: Vec<String> = catalog_nodes.into_iter().collect();
     let catalog_provider_values: Vec<String> = catalog_providers.into_iter().collect();
     let catalog_model_values: Vec<String> = catalog_models.into_iter().collect();
@@ -1358,23 +1917,35 @@ pub(crate) fn get_usage_statistics(
     let response_timeline_count = timeline_points.len();
     let filtered_row_count = filtered.len();
     let top_model = by_model.first().cloned().unwrap_or(Value::Null);
+    let avg_request_bytes =
+        (sized_requests > 0).then(|| total_request_bytes as f64 / sized_requests as f64);
+    let avg_response_bytes =
+        (sized_requests > 0).then(|| total_response_bytes as f64 / sized_requests as f64);
     let mut summary = serde_json::json!({
       "total_requests": total_requests,
+      "total_errors": total_errors,
       "total_tokens": total_tokens,
       "input_tokens": total_input_tokens,
       "output_tokens": total_output_tokens,
       "active_window_hours": round3(active_window_hours),
       "cache_creation_tokens": total_cache_creation_tokens,
       "cache_read_tokens": total_cache_read_tokens,
+      "request_bytes": total_request_bytes,
+      "response_bytes": total_response_bytes,
+      "avg_request_bytes": json_num_or_null(avg_request_bytes),
+      "avg_response_bytes": json_num_or_null(avg_response_bytes),
       "unique_models": by_model.len(),
       "top_model": top_model,
       "estimated_total_cost_usd": round3(total_used_cost_usd),
       "estimated_daily_cost_usd": round3(estimated_daily_cost_usd),
+      "cache_creation_cost_usd": round3(total_cache_creation_cost_usd),
+      "cache_read_cost_usd": round3(total_cache_read_cost_usd),
       "by_provider": by_provider,
       "timeline": timeline_points
     });
     if detail_level == UsageStatisticsDetailLevel::Full {
         summary["by_model"] = serde_json::json!(by_model);
+        summary["by_provider_model"] = serde_json::json!(by_provider_model);
     }
 
     let mut response = serde_json::json!({
@@ -1389,7 +1960,8 @@ pub(crate) fn get_usage_statistics(
           "nodes": filter_nodes_json,
           "providers": filter_providers_json,
           "models": filter_models_json,
-          "origins": filter_origins_json
+          "origins": filter_origins_json,
+          "labels": filter_labels_json
         });
         response["catalog"] = serde_json::json!({
           "nodes": catalog_node_values,
@@ -1412,10 +1984,10 @@ pub(crate) fn get_usage_statistics(
         .elapsed()
         .as_millis()
         .min(u128::from(u64::MAX)) as u64;
-    if total_elapsed_ms >= 300
+    let slow = total_elapsed_ms >= 300
         || response_encode_elapsed_ms >= 50
-        || response_bytes.len() >= 256 * 1024
-    {
+        || response_bytes.len() >= 256 * 1024;
+    if let Some(diagnostics_dir) = diagnostics_dir.filter(|_| slow) {
         let diag = serde_json::json!({
           "captured_at_unix_ms": unix_ms(),
           "detail_level": match detail_level {
@@ -1432,9 +2004,7 @@ pub(crate) fn get_usage_statistics(
           "timeline_points": response_timeline_count,
           "phase_timings_ms": phase_timings_ms,
         });
-        let path = state
-            .diagnostics_dir
-            .join(format!("usage-stats-slow-{}.json", unix_ms()));
+        let path = diagnostics_dir.join(format!("usage-stats-slow-{}.json", unix_ms()));
         let _ = std::fs::write(path, serde_json::to_vec_pretty(&diag).unwrap_or_default());
     }
     response
@@ -1443,15 +2013,16 @@ pub(crate) fn get_usage_statistics(
 #[cfg(test)]
 mod usage_metrics_tests {
     use super::{
-        effective_provider_filter, latest_day_budget_fallback_allowed,
+        effective_provider_filter, export_usage_jsonl_impl, get_top_cost_models_impl, get_usage_statistics_impl,
+        latest_day_budget_fallback_allowed,
         list_usage_requests_for_statistics_window,
         merge_manual_per_req_for_usage_metrics_day, merge_usage_metrics_day_counts,
-        normalize_usage_origin,
+        normalize_usage_origin, package_exhaustion_projection,
         parse_usage_statistics_detail_level,
         projection_hours_for_day_estimate, request_window_ratio,
         resolve_budget_or_token_rate_cost, tracked_spend_day_key,
         tracked_spend_days_with_remote_fallback, UsageStatisticsDetailLevel,
-        usage_metrics_configured_provider_names,
+        usage_metrics_configured_provider_names, usage_timeseries,
     };
     use crate::orchestrator::config::{AppConfig, ProviderConfig};
     use crate::orchestrator::store::{Store, UsageRequestSyncRow};
@@ -1561,6 +2132,34 @@ mod usage_metrics_tests {
         assert_eq!(current_updated_at, 110);
     }
 
+    #[test]
+    fn package_exhaustion_projects_from_recent_rate_and_consumed_share() {
+        let day_ms: u64 = 24 * 60 * 60 * 1000;
+        // $100 package, $40 used over 200 requests => $0.20 per request; 48 requests in the last
+        // 24h burn $9.60/day, so the remaining $60 lasts 6.25 days.
+        let (rate, at) =
+            package_exhaustion_projection(100.0, Some(60.0), 200, 48, 24, 1_000).unwrap();
+        assert!((rate - 9.6).abs() < 1e-9);
+        assert!(at.abs_diff(1_000 + day_ms * 25 / 4) <= 1);
+
+        assert_eq!(
+            package_exhaustion_projection(100.0, None, 200, 48, 24, 0),
+            None
+        );
+        assert_eq!(
+            package_exhaustion_projection(100.0, Some(100.0), 200, 48, 24, 0),
+            None
+        );
+        assert_eq!(
+            package_exhaustion_projection(100.0, Some(60.0), 0, 48, 24, 0),
+            None
+        );
+        assert_eq!(
+            package_exhaustion_projection(100.0, Some(60.0), 200, 0, 24, 0),
+            None
+        );
+    }
+
     #[test]
     fn budget_or_token_rate_cost_falls_back_to_per_token_when_budget_missing() {
         let (total_used, tracked, source) =
@@ -1584,6 +2183,14 @@ mod usage_metrics_tests {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -1597,6 +2204,14 @@ mod usage_metrics_tests {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -1610,6 +2225,14 @@ mod usage_metrics_tests {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]);
@@ -1659,6 +2282,452 @@ mod usage_metrics_tests {
         );
     }
 
+    #[test]
+    fn top_cost_models_ranks_models_by_estimated_cost_and_applies_limit() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = crate::app_state::build_state(
+            tmp.path().join("user-data").join("config.toml"),
+            tmp.path().join("data"),
+        )
+        .unwrap();
+        state
+            .secrets
+            .set_provider_pricing("provider_1", "per_request", 0.5, None, None)
+            .unwrap();
+        let now = crate::orchestrator::store::unix_ms();
+        let rows: Vec<UsageRequestSyncRow> = ["model-a", "model-a", "model-a", "model-b"]
+            .iter()
+            .enumerate()
+            .map(|(idx, model)| UsageRequestSyncRow {
+                ingested_at_unix_ms: now - 60_000,
+                model: model.to_string(),
+                ..UsageRequestSyncRow::test_row(
+                    format!("row-{idx}"),
+                    "provider_1",
+                    now - 60_000 - idx as u64,
+                )
+            })
+            .collect();
+        state.gateway.store.upsert_usage_request_sync_rows(&rows);
+
+        let top = get_top_cost_models_impl(&state, Some(24), Some(1));
+        let models = top["models"].as_array().unwrap();
+        assert_eq!(top["limit"], 1);
+        assert_eq!(models.len(), 1);
+        assert_eq!(models[0]["model"], "model-a");
+        assert_eq!(models[0]["requests"], 3);
+        assert_eq!(models[0]["total_tokens"], 45);
+        assert_eq!(models[0]["estimated_total_cost_usd"], 1.5);
+
+        let all = get_top_cost_models_impl(&state, Some(24), Some(10_000));
+        assert_eq!(all["limit"], 100);
+        assert_eq!(all["models"].as_array().unwrap().len(), 2);
+    }
+
+    #[test]
+    fn usage_statistics_prices_models_with_their_own_rates_before_provider_fallback() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = crate::app_state::build_state(
+            tmp.path().join("user-data").join("config.toml"),
+            tmp.path().join("data"),
+        )
+        .unwrap();
+        state
+            .secrets
+            .set_provider_pricing("provider_1", "per_request", 0.5, None, None)
+            .unwrap();
+        state
+            .secrets
+            .upsert_provider_model_rates(
+                "provider_1",
+                BTreeMap::from([(
+                    "model-b".to_string(),
+                    crate::orchestrator::secrets::ProviderModelRate {
+                        input_usd_per_million: 1000.0,
+                        output_usd_per_million: 2000.0,
+                        cache_creation_usd_per_million: Some(2000.0),
+                        cache_read_usd_per_million: Some(100.0),
+                    },
+                )]),
+            )
+            .unwrap();
+        let now = crate::orchestrator::store::unix_ms();
+        let rows: Vec<UsageRequestSyncRow> = ["model-a", "model-b"]
+            .iter()
+            .enumerate()
+            .map(|(idx, model)| UsageRequestSyncRow {
+                ingested_at_unix_ms: now - 60_000,
+                model: model.to_string(),
+                input_tokens: 1000,
+                output_tokens: 500,
+                total_tokens: 1500,
+                cache_creation_input_tokens: 100,
+                cache_read_input_tokens: 400,
+                ..UsageRequestSyncRow::test_row(
+                    format!("row-{idx}"),
+                    "provider_1",
+                    now - 60_000 - idx as u64,
+                )
+            })
+            .collect();
+        state.gateway.store.upsert_usage_request_sync_rows(&rows);
+
+        let stats = get_usage_statistics_impl(&state, None, Some(24), None, None, None, None, None);
+        let models = stats["summary"]["by_model"].as_array().unwrap();
+        let model = |model: &str| {
+            models
+                .iter()
+                .find(|row| row["model"] == model)
+                .cloned()
+                .unwrap()
+        };
+        // 500 uncached input, 100 cache-write, 400 cache-read and 500 output tokens at the
+        // model's own rates.
+        assert_eq!(model("model-b")["estimated_total_cost_usd"], 1.74);
+        assert_eq!(model("model-b")["cache_creation_cost_usd"], 0.2);
+        assert_eq!(model("model-b")["cache_read_cost_usd"], 0.04);
+        assert_eq!(model("model-a")["estimated_total_cost_usd"], 0.5);
+        assert_eq!(model("model-a")["cache_read_cost_usd"], 0.0);
+        assert_eq!(stats["summary"]["cache_creation_cost_usd"], 0.2);
+        assert_eq!(stats["summary"]["cache_read_cost_usd"], 0.04);
+        // The provider row and the summary add up the same per-row costs as by_model.
+        let providers = stats["summary"]["by_provider"].as_array().unwrap();
+        assert_eq!(providers.len(), 1);
+        assert_eq!(providers[0]["estimated_total_cost_usd"], 2.24);
+        assert_eq!(stats["summary"]["estimated_total_cost_usd"], 2.24);
+    }
+
+    #[test]
+    fn usage_statistics_prices_providers_that_only_have_model_rates() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = crate::app_state::build_state(
+            tmp.path().join("user-data").join("config.toml"),
+            tmp.path().join("data"),
+        )
+        .unwrap();
+        state
+            .secrets
+            .upsert_provider_model_rates(
+                "provider_1",
+                BTreeMap::from([(
+                    "model-a".to_string(),
+                    crate::orchestrator::secrets::ProviderModelRate {
+                        input_usd_per_million: 1000.0,
+                        output_usd_per_million: 2000.0,
+                        cache_creation_usd_per_million: None,
+                        cache_read_usd_per_million: None,
+                    },
+                )]),
+            )
+            .unwrap();
+        let now = crate::orchestrator::store::unix_ms();
+        let rows: Vec<UsageRequestSyncRow> = ["model-a", "model-a", "model-c"]
+            .iter()
+            .enumerate()
+            .map(|(idx, model)| UsageRequestSyncRow {
+                ingested_at_unix_ms: now - 60_000,
+                model: model.to_string(),
+                input_tokens: 1000,
+                output_tokens: 500,
+                total_tokens: 1500,
+                ..UsageRequestSyncRow::test_row(
+                    format!("row-{idx}"),
+                    "provider_1",
+                    now - 60_000 - idx as u64,
+                )
+            })
+            .collect();
+        state.gateway.store.upsert_usage_request_sync_rows(&rows);
+
+        let stats = get_usage_statistics_impl(&state, None, Some(24), None, None, None, None, None);
+        // Two model-a requests at $2 each; model-c has no rate and no provider price.
+        let providers = stats["summary"]["by_provider"].as_array().unwrap();
+        assert_eq!(providers.len(), 1);
+        assert_eq!(providers[0]["estimated_total_cost_usd"], 4.0);
+        assert_eq!(providers[0]["pricing_source"], "model_rates");
+        assert_eq!(stats["summary"]["estimated_total_cost_usd"], 4.0);
+    }
+
+    #[test]
+    fn usage_statistics_cross_tabs_cost_per_provider_and_model() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = crate::app_state::build_state(
+            tmp.path().join("user-data").join("config.toml"),
+            tmp.path().join("data"),
+        )
+        .unwrap();
+        state
+            .secrets
+            .set_provider_pricing("provider_1", "per_request", 0.5, None, None)
+            .unwrap();
+        state
+            .secrets
+            .set_provider_pricing("provider_2", "per_request", 2.0, None, None)
+            .unwrap();
+        let now = crate::orchestrator::store::unix_ms();
+        let rows: Vec<UsageRequestSyncRow> = [
+            ("provider_1", "model-a"),
+            ("provider_1", "model-a"),
+            ("provider_2", "model-a"),
+            ("provider_2", "model-b"),
+        ]
+        .iter()
+        .enumerate()
+        .map(|(idx, (provider, model))| UsageRequestSyncRow {
+            ingested_at_unix_ms: now - 60_000,
+            model: model.to_string(),
+            ..UsageRequestSyncRow::test_row(
+                format!("row-{idx}"),
+                provider,
+                now - 60_000 - idx as u64,
+            )
+        })
+        .collect();
+        state.gateway.store.upsert_usage_request_sync_rows(&rows);
+
+        let stats = get_usage_statistics_impl(&state, None, Some(24), None, None, None, None, None);
+        let pairs = stats["summary"]["by_provider_model"].as_array().unwrap();
+        assert_eq!(pairs.len(), 3);
+        let pair = |provider: &str, model: &str| {
+            pairs
+                .iter()
+                .find(|row| row["provider"] == provider && row["model"] == model)
+                .cloned()
+                .unwrap()
+        };
+        assert_eq!(pair("provider_1", "model-a")["requests"], 2);
+        assert_eq!(
+            pair("provider_1", "model-a")["estimated_total_cost_usd"],
+            1.0
+        );
+        assert_eq!(pair("provider_2", "model-a")["requests"], 1);
+        assert_eq!(
+            pair("provider_2", "model-a")["estimated_total_cost_usd"],
+            2.0
+        );
+        assert_eq!(pair("provider_2", "model-b")["total_tokens"], 15);
+
+        let overview = get_usage_statistics_impl(
+            &state,
+            Some("overview".to_string()),
+            Some(24),
+            None,
+            None,
+            None,
+            None,
+            None,
+        );
+        assert!(overview["summary"].get("by_provider_model").is_none());
+    }
+
+    #[test]
+    fn usage_statistics_labels_filter_only_counts_tagged_requests() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = crate::app_state::build_state(
+            tmp.path().join("user-data").join("config.toml"),
+            tmp.path().join("data"),
+        )
+        .unwrap();
+        let now = crate::orchestrator::store::unix_ms();
+        let rows: Vec<UsageRequestSyncRow> = ["bench-1", "bench-1", ""]
+            .iter()
+            .enumerate()
+            .map(|(idx, label)| UsageRequestSyncRow {
+                ingested_at_unix_ms: now - 60_000,
+                model: "model-a".to_string(),
+                label: label.to_string(),
+                ..UsageRequestSyncRow::test_row(
+                    format!("row-{idx}"),
+                    "provider_1",
+                    now - 60_000 - idx as u64,
+                )
+            })
+            .collect();
+        state.gateway.store.upsert_usage_request_sync_rows(&rows);
+
+        let all = get_usage_statistics_impl(&state, None, Some(24), None, None, None, None, None);
+        assert_eq!(all["summary"]["total_requests"], 3);
+        let tagged = get_usage_statistics_impl(
+            &state,
+            None,
+            Some(24),
+            None,
+            None,
+            None,
+            None,
+            Some(vec![" Bench-1 ".to_string()]),
+        );
+        assert_eq!(tagged["summary"]["total_requests"], 2);
+        assert_eq!(tagged["filter"]["labels"], serde_json::json!(["bench-1"]));
+    }
+
+    #[test]
+    fn usage_statistics_timeline_reports_errors_alongside_requests() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = crate::app_state::build_state(
+            tmp.path().join("user-data").join("config.toml"),
+            tmp.path().join("data"),
+        )
+        .unwrap();
+        let now = crate::orchestrator::store::unix_ms();
+        let row = UsageRequestSyncRow {
+            model: "model-a".to_string(),
+            ..UsageRequestSyncRow::test_row("row-ok", "provider_1", now - 1_000)
+        };
+        state.gateway.store.upsert_usage_request_sync_rows(&[row]);
+        // Failures share the request's timestamp so they always land in its bucket.
+        for provider in ["provider_1", "provider_1", "provider_2"] {
+            state
+                .gateway
+                .store
+                .insert_usage_failure(provider, now - 1_000);
+        }
+
+        let all = get_usage_statistics_impl(&state, None, Some(24), None, None, None, None, None);
+        assert_eq!(all["summary"]["total_errors"], 3);
+        let timeline = all["summary"]["timeline"].as_array().unwrap();
+        assert_eq!(
+            timeline
+                .iter()
+                .map(|point| point["errors"].as_u64().unwrap())
+                .sum::<u64>(),
+            3
+        );
+        assert!(timeline
+            .iter()
+            .filter(|point| point["requests"] == 0 && point["errors"] == 0)
+            .all(|point| point["error_rate"].is_null()));
+
+        let provider_1 = get_usage_statistics_impl(
+            &state,
+            None,
+            Some(24),
+            None,
+            Some(vec!["provider_1".to_string()]),
+            None,
+            None,
+            None,
+        );
+        assert_eq!(provider_1["summary"]["total_errors"], 2);
+        let busiest = provider_1["summary"]["timeline"]
+            .as_array()
+            .unwrap()
+            .iter()
+            .find(|point| point["errors"].as_u64().unwrap_or(0) > 0)
+            .cloned()
+            .unwrap();
+        assert_eq!(busiest["requests"], 1);
+        assert_eq!(busiest["errors"], 2);
+        assert_eq!(busiest["error_rate"].as_f64(), Some(0.667));
+
+        let by_model = get_usage_statistics_impl(
+            &state,
+            None,
+            Some(24),
+            None,
+            None,
+            Some(vec!["model-a".to_string()]),
+            None,
+            None,
+        );
+        assert_eq!(by_model["summary"]["total_errors"], 0);
+    }
+
+    #[test]
+    fn usage_timeseries_buckets_tokens_requests_and_cost_per_provider() {
+        let tmp = tempfile::tempdir().unwrap();
+        let state = crate::app_state::build_state(
+            tmp.path().join("user-data").join("config.toml"),
+            tmp.path().join("data"),
+        )
+        .unwrap();
+        state
+            .secrets
+            .set_provider_pricing("provider_1", "per_request", 0.5, None, None)
+            .unwrap();
+        let now = crate::orchestrator::store::unix_ms();
+        let rows: Vec<UsageRequestSyncRow> = (0..4u64)
+            .map(|idx| UsageRequestSyncRow {
+                ingested_at_unix_ms: now - 60_000,
+                model: "model-a".to_string(),
+                ..UsageRequestSyncRow::test_row(
+                    format!("row-{idx}"),
+                    "provider_1",
+                    now - 60_000 - idx,
+                )
+            })
+            .collect();
+        state.gateway.store.upsert_usage_request_sync_rows(&rows);
+
+        let from = now - 3 * 60 * 60 * 1000;
+        let sum = |metric: &str| {
+            let series = usage_timeseries(&state.gateway, metric, None, from, now, "hour").unwrap();
+            let series = series.as_array().unwrap();
+            assert_eq!(series.len(), 1);
+            assert_eq!(series[0]["target"], "provider_1");
+            let points = series[0]["datapoints"].as_array().unwrap();
+            assert!(points.len() >= 3);
+            assert!(points
+                .windows(2)
+                .all(|pair| pair[0][1].as_u64() < pair[1][1].as_u64()));
+            points
+                .iter()
+                .map(|point| point[0].as_f64().unwrap())
+                .sum::<f64>()
+        };
+        assert_eq!(sum("tokens"), 60.0);
+        assert_eq!(sum("requests"), 4.0);
+        assert_eq!(sum("cost"), 2.0);
+
+        assert!(usage_timeseries(&state.gateway, "latency", None, from, now, "hour").is_err());
+        assert!(usage_timeseries(&state.gateway, "tokens", None, from, now, "week").is_err());
+        assert!(usage_timeseries(&state.gateway, "tokens", None, now, from, "hour").is_err());
+        let other = usage_timeseries(
+            &state.gateway,
+            "tokens",
+            Some(vec!["provider_2".to_string()]),
+            from,
+            now,
+            "day",
+        )
+        .unwrap();
+        assert_eq!(other, serde_json::json!([]));
+    }
+
+    #[test]
+    fn export_usage_jsonl_writes_raw_rows_in_window_oldest_first() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = Store::open(tmp.path()).unwrap();
+        let rows: Vec<UsageRequestSyncRow> = [3_000u64, 1_000, 2_000, 9_000]
+            .iter()
+            .map(|unix_ms| UsageRequestSyncRow {
+                api_key_ref: "sk-abc******wxyz".to_string(),
+                cache_creation_input_tokens: 1,
+                cache_read_input_tokens: 2,
+                ..UsageRequestSyncRow::test_row(format!("row-{unix_ms}"), "official", *unix_ms)
+            })
+            .collect();
+        store.upsert_usage_request_sync_rows(&rows);
+
+        let path = tmp.path().join("usage.jsonl");
+        let written =
+            export_usage_jsonl_impl(&store, 1_000, 9_000, path.to_str().unwrap()).unwrap();
+        assert_eq!(written, 3);
+        let text = std::fs::read_to_string(&path).unwrap();
+        let exported: Vec<UsageRequestSyncRow> = text
+            .lines()
+            .map(|line| serde_json::from_str(line).unwrap())
+            .collect();
+        assert_eq!(
+            exported.iter().map(|row| row.unix_ms).collect::<Vec<_>>(),
+            vec![1_000, 2_000, 3_000]
+        );
+        assert_eq!(exported[0], rows[1]);
+
+        assert!(export_usage_jsonl_impl(&store, 9_000, 1_000, path.to_str().unwrap()).is_err());
+        assert!(export_usage_jsonl_impl(&store, 1_000, 9_000, "  ").is_err());
+    }
+
     #[test]
     fn usage_statistics_request_scan_is_limited_to_the_requested_window() {
         let tmp = tempfile::tempdir().unwrap();
@@ -1676,40 +2745,17 @@ mod usage_metrics_tests {
 
         store.upsert_usage_request_sync_rows(&[
             UsageRequestSyncRow {
-                id: "older-row".to_string(),
-                unix_ms: older as u64,
-                ingested_at_unix_ms: older as u64,
-                provider: "official".to_string(),
-                api_key_ref: "-".to_string(),
-                model: "gpt-5.2-codex".to_string(),
-                origin: "windows".to_string(),
-                transport: "http".to_string(),
                 session_id: "older".to_string(),
-                node_id: "node-a".to_string(),
-                node_name: "Desk A".to_string(),
-                input_tokens: 10,
                 output_tokens: 1,
                 total_tokens: 11,
-                cache_creation_input_tokens: 0,
-                cache_read_input_tokens: 0,
+                ..UsageRequestSyncRow::test_row("older-row", "official", older as u64)
             },
             UsageRequestSyncRow {
-                id: "newer-row".to_string(),
-                unix_ms: newer as u64,
-                ingested_at_unix_ms: newer as u64,
-                provider: "official".to_string(),
-                api_key_ref: "-".to_string(),
-                model: "gpt-5.2-codex".to_string(),
-                origin: "windows".to_string(),
-                transport: "http".to_string(),
                 session_id: "newer".to_string(),
-                node_id: "node-a".to_string(),
-                node_name: "Desk A".to_string(),
                 input_tokens: 20,
                 output_tokens: 2,
                 total_tokens: 22,
-                cache_creation_input_tokens: 0,
-                cache_read_input_tokens: 0,
+                ..UsageRequestSyncRow::test_row("newer-row", "official", newer as u64)
             },
         ]);
 
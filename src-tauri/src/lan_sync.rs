@@ -5,7 +5,7 @@ use std::net::{IpAddr, Ipv4Addr, SocketAddr, UdpSocket};
 use std::path::Path;
 use std::sync::atomic::{AtomicBool, AtomicU64, Ordering};
 use std::sync::{Arc, OnceLock};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 
 use axum::extract::{Json, State};
 use axum::http::{HeaderMap, StatusCode};
@@ -192,11 +192,9 @@ fn append_lan_peer_diagnostics_log(message: &str) {
     );
 }
 
-fn heartbeat_sender_delay_ms(previous_sent_unix_ms: Option<u64>, now_unix_ms: u64) -> Option<u64> {
-    previous_sent_unix_ms.and_then(|previous_sent_unix_ms| {
-        let delay_ms = now_unix_ms.saturating_sub(previous_sent_unix_ms);
-        (delay_ms > LAN_HEARTBEAT_SENDER_DELAY_LOG_THRESHOLD_MS).then_some(delay_ms)
-    })
+fn heartbeat_sender_delay_ms(since_previous_sent_ms: Option<u64>) -> Option<u64> {
+    since_previous_sent_ms
+        .filter(|delay_ms| *delay_ms > LAN_HEARTBEAT_SENDER_DELAY_LOG_THRESHOLD_MS)
 }
 
 fn optional_u64_log_value(value: Option<u64>) -> String {
@@ -205,8 +203,9 @@ fn optional_u64_log_value(value: Option<u64>) -> String {
         .unwrap_or_else(|| "none".to_string())
 }
 
-fn heartbeat_step_elapsed_ms(started_at_unix_ms: u64) -> u64 {
-    unix_ms().saturating_sub(started_at_unix_ms)
+// Heartbeat timings use the monotonic clock so a wall-clock step cannot report 0ms or hours.
+fn heartbeat_step_elapsed_ms(started: Instant) -> u64 {
+    started.elapsed().as_millis() as u64
 }
 
 pub(crate) fn current_build_identity() -> LanBuildIdentitySnapshot {
@@ -3463,18 +3462,17 @@ fn run_listener(
 }
 
 fn run_sender(runtime: LanSyncRuntime, gateway: crate::orchestrator::gateway::GatewayState) {
-    let mut last_heartbeat_sent_unix_ms: Option<u64> = None;
+    let mut last_heartbeat_sent_at: Option<Instant> = None;
     let mut last_heartbeat_elapsed_ms: Option<u64> = None;
     let mut heartbeat_sequence: u64 = 0;
     let mut sender_delay_active = false;
     let mut sender_delay_gap_ms: Option<u64> = None;
     loop {
+        let heartbeat_started = Instant::now();
         let heartbeat_started_unix_ms = unix_ms();
-        let sender_previous_gap_ms = last_heartbeat_sent_unix_ms
-            .map(|last_sent| heartbeat_started_unix_ms.saturating_sub(last_sent));
-        if let Some(delay_ms) =
-            heartbeat_sender_delay_ms(last_heartbeat_sent_unix_ms, heartbeat_started_unix_ms)
-        {
+        let sender_previous_gap_ms = last_heartbeat_sent_at
+            .map(|last_sent| heartbeat_started.duration_since(last_sent).as_millis() as u64);
+        if let Some(delay_ms) = heartbeat_sender_delay_ms(sender_previous_gap_ms) {
             if !sender_delay_active {
                 append_lan_peer_diagnostics_log(&format!(
                     "Heartbeat sender delayed {}ms before broadcast; node={} name={}",
@@ -3484,30 +3482,30 @@ fn run_sender(runtime: LanSyncRuntime, gateway: crate::orchestrator::gateway::Ga
             sender_delay_active = true;
             sender_delay_gap_ms = Some(delay_ms);
         }
-        let cfg_started_unix_ms = unix_ms();
+        let cfg_started = Instant::now();
         let cfg_snapshot = gateway.cfg.read().clone();
-        let cfg_elapsed_ms = heartbeat_step_elapsed_ms(cfg_started_unix_ms);
-        let provider_fingerprints_started_unix_ms = unix_ms();
+        let cfg_elapsed_ms = heartbeat_step_elapsed_ms(cfg_started);
+        let provider_fingerprints_started = Instant::now();
         let provider_fingerprints = build_provider_fingerprints(&cfg_snapshot, &gateway.secrets);
         let provider_fingerprints_elapsed_ms =
-            heartbeat_step_elapsed_ms(provider_fingerprints_started_unix_ms);
-        let provider_definitions_started_unix_ms = unix_ms();
+            heartbeat_step_elapsed_ms(provider_fingerprints_started);
+        let provider_definitions_started = Instant::now();
         let provider_definition_items = local_provider_definition_sync_items(&gateway);
         let provider_definitions_elapsed_ms =
-            heartbeat_step_elapsed_ms(provider_definitions_started_unix_ms);
+            heartbeat_step_elapsed_ms(provider_definitions_started);
         *runtime.local_provider_fingerprints.write() = provider_fingerprints.clone();
         heartbeat_sequence = heartbeat_sequence.saturating_add(1);
-        let remote_update_started_unix_ms = unix_ms();
+        let remote_update_started = Instant::now();
         let remote_update_readiness = Some(current_local_remote_update_readiness());
         let remote_update_status =
             compact_remote_update_status_for_heartbeat(load_lan_remote_update_status());
-        let remote_update_elapsed_ms = heartbeat_step_elapsed_ms(remote_update_started_unix_ms);
-        let sync_contracts_started_unix_ms = unix_ms();
+        let remote_update_elapsed_ms = heartbeat_step_elapsed_ms(remote_update_started);
+        let sync_contracts_started = Instant::now();
         let sync_contracts = local_sync_contracts();
-        let sync_contracts_elapsed_ms = heartbeat_step_elapsed_ms(sync_contracts_started_unix_ms);
-        let followed_source_started_unix_ms = unix_ms();
+        let sync_contracts_elapsed_ms = heartbeat_step_elapsed_ms(sync_contracts_started);
+        let followed_source_started = Instant::now();
         let followed_source_node_id = gateway.secrets.get_followed_config_source_node_id();
-        let followed_source_elapsed_ms = heartbeat_step_elapsed_ms(followed_source_started_unix_ms);
+        let followed_source_elapsed_ms = heartbeat_step_elapsed_ms(followed_source_started);
         let packet = LanSyncPacket::Heartbeat(Box::new(LanHeartbeatPacket {
             version: 1,
             node_id: runtime.local_node.node_id.clone(),
@@ -3529,7 +3527,7 @@ fn run_sender(runtime: LanSyncRuntime, gateway: crate::orchestrator::gateway::Ga
             ),
             followed_source_node_id,
         }));
-        let prep_elapsed_ms = heartbeat_step_elapsed_ms(heartbeat_started_unix_ms);
+        let prep_elapsed_ms = heartbeat_step_elapsed_ms(heartbeat_started);
         if prep_elapsed_ms >= LAN_HEARTBEAT_PREP_SLOW_LOG_THRESHOLD_MS {
             append_lan_peer_diagnostics_log(&format!(
                 "Heartbeat sender prep slow {}ms; node={} name={} cfg={}ms provider_fingerprints={}ms provider_definitions={}ms remote_update={}ms sync_contracts={}ms followed_source={}ms",
@@ -3548,11 +3546,8 @@ fn run_sender(runtime: LanSyncRuntime, gateway: crate::orchestrator::gateway::Ga
             let targets = runtime.heartbeat_delivery_targets(heartbeat_started_unix_ms);
             if let Err(err) = send_heartbeat_packet(heartbeat, &targets) {
                 log::warn!("lan sync sender heartbeat failed: {err}");
-                let failure_context = match last_heartbeat_sent_unix_ms {
-                    Some(last_sent_unix_ms) => format!(
-                        " after {}ms since last successful heartbeat",
-                        heartbeat_started_unix_ms.saturating_sub(last_sent_unix_ms)
-                    ),
+                let failure_context = match sender_previous_gap_ms {
+                    Some(gap_ms) => format!(" after {gap_ms}ms since last successful heartbeat"),
                     None => " before first successful heartbeat".to_string(),
                 };
                 append_lan_peer_diagnostics_log(&format!(
@@ -3563,10 +3558,8 @@ fn run_sender(runtime: LanSyncRuntime, gateway: crate::orchestrator::gateway::Ga
                 continue;
             }
         }
-        let heartbeat_finished_unix_ms = unix_ms();
-        last_heartbeat_elapsed_ms =
-            Some(heartbeat_finished_unix_ms.saturating_sub(heartbeat_started_unix_ms));
-        last_heartbeat_sent_unix_ms = Some(heartbeat_finished_unix_ms);
+        last_heartbeat_elapsed_ms = Some(heartbeat_step_elapsed_ms(heartbeat_started));
+        last_heartbeat_sent_at = Some(Instant::now());
         if sender_delay_active {
             append_lan_peer_diagnostics_log(&format!(
                 "Heartbeat sender recovered after {}ms delay; node={} name={}",
@@ -5920,22 +5913,16 @@ mod tests {
                 .gateway
                 .store
                 .upsert_usage_request_sync_rows(&[UsageRequestSyncRow {
-                    id: "usage-row-1".to_string(),
-                    unix_ms: crate::orchestrator::store::unix_ms(),
-                    ingested_at_unix_ms: crate::orchestrator::store::unix_ms(),
-                    provider: "official".to_string(),
                     api_key_ref: "sk-test".to_string(),
                     model: "gpt-5".to_string(),
-                    origin: crate::constants::USAGE_ORIGIN_WINDOWS.to_string(),
-                    transport: "http".to_string(),
                     session_id: "session-1".to_string(),
                     node_id: "node-local".to_string(),
                     node_name: "Desk Local".to_string(),
-                    input_tokens: 10,
-                    output_tokens: 5,
-                    total_tokens: 15,
-                    cache_creation_input_tokens: 0,
-                    cache_read_input_tokens: 0,
+                    ..UsageRequestSyncRow::test_row(
+                        "usage-row-1",
+                        "official",
+                        crate::orchestrator::store::unix_ms()
+                    )
                 }]),
             1
         );
@@ -6202,6 +6189,7 @@ mod tests {
             listen: crate::orchestrator::config::ListenConfig {
                 host: "127.0.0.1".to_string(),
                 port: 4000,
+                unix_socket_path: None,
             },
             routing: crate::orchestrator::config::RoutingConfig {
                 preferred_provider: "p1".to_string(),
@@ -6212,6 +6200,32 @@ mod tests {
                 failure_threshold: 1,
                 cooldown_seconds: 600,
                 request_timeout_seconds: 5,
+                session_sticky_ttl_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+                usage_label: None,
+                max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+                allowed_peer_ips: Vec::new(),
+                connect_timeout_seconds: None,
+                read_timeout_seconds: None,
+                otel_endpoint: None,
+                key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+                default_base_url: None,
+                default_usage_adapter: None,
+                active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+                session_active_window_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+                read_only: false,
+                spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+                ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+                fractional_percent_display: false,
+                anthropic_compat: false,
+                cors_allowed_origins: Vec::new(),
+                max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+                evict_usage_requests: false,
+                event_query_default_limit:
+                    crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+                event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+                keepalive_warm_seconds: 0,
             },
             providers: std::collections::BTreeMap::from([(
                 "p1".to_string(),
@@ -6224,9 +6238,20 @@ mod tests {
                     disabled: false,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             )]),
             provider_order: vec!["p1".to_string()],
+            events: Default::default(),
+            switchboard_profiles: Default::default(),
+            provider_templates: std::collections::BTreeMap::new(),
         };
 
         let a = crate::orchestrator::quota::shared_provider_fingerprint(&cfg, &secrets, "p1")
@@ -6295,6 +6320,7 @@ mod tests {
             listen: crate::orchestrator::config::ListenConfig {
                 host: "127.0.0.1".to_string(),
                 port: 4000,
+                unix_socket_path: None,
             },
             routing: crate::orchestrator::config::RoutingConfig {
                 preferred_provider: provider_name.clone(),
@@ -6305,6 +6331,32 @@ mod tests {
                 failure_threshold: 1,
                 cooldown_seconds: 60,
                 request_timeout_seconds: 5,
+                session_sticky_ttl_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+                usage_label: None,
+                max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+                allowed_peer_ips: Vec::new(),
+                connect_timeout_seconds: None,
+                read_timeout_seconds: None,
+                otel_endpoint: None,
+                key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+                default_base_url: None,
+                default_usage_adapter: None,
+                active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+                session_active_window_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+                read_only: false,
+                spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+                ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+                fractional_percent_display: false,
+                anthropic_compat: false,
+                cors_allowed_origins: Vec::new(),
+                max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+                evict_usage_requests: false,
+                event_query_default_limit:
+                    crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+                event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+                keepalive_warm_seconds: 0,
             },
             providers: std::collections::BTreeMap::from([(
                 provider_name.clone(),
@@ -6317,9 +6369,20 @@ mod tests {
                     disabled: false,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             )]),
             provider_order: vec![provider_name.clone()],
+            events: Default::default(),
+            switchboard_profiles: Default::default(),
+            provider_templates: std::collections::BTreeMap::new(),
         };
         *state.gateway.cfg.write() = cfg.clone();
         state
@@ -7908,19 +7971,16 @@ mod tests {
 
     #[test]
     fn heartbeat_sender_delay_stays_quiet_without_previous_success() {
-        assert_eq!(super::heartbeat_sender_delay_ms(None, 10_000), None);
+        assert_eq!(super::heartbeat_sender_delay_ms(None), None);
     }
 
     #[test]
     fn heartbeat_sender_delay_uses_strict_threshold_boundary() {
         let threshold = super::LAN_HEARTBEAT_SENDER_DELAY_LOG_THRESHOLD_MS;
-        assert_eq!(super::heartbeat_sender_delay_ms(Some(10_000), 10_000), None);
-        assert_eq!(
-            super::heartbeat_sender_delay_ms(Some(10_000), 10_000 + threshold),
-            None
-        );
+        assert_eq!(super::heartbeat_sender_delay_ms(Some(0)), None);
+        assert_eq!(super::heartbeat_sender_delay_ms(Some(threshold)), None);
         assert_eq!(
-            super::heartbeat_sender_delay_ms(Some(10_000), 10_000 + threshold + 1),
+            super::heartbeat_sender_delay_ms(Some(threshold + 1)),
             Some(threshold + 1)
         );
     }
@@ -8068,6 +8128,8 @@ mod tests {
             monthly_spent_usd: None,
             monthly_budget_usd: None,
             package_expires_at_unix_ms: None,
+            reset_at_unix_ms: None,
+            used_percent: None,
             last_error: String::new(),
             effective_usage_base: Some("https://example.com/v1".to_string()),
             effective_usage_source: Some("remote".to_string()),
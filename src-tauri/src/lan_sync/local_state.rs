@@ -17,7 +17,8 @@ pub struct LocalProviderStateSnapshot {
     pub providers: BTreeMap<String, ProviderConfig>,
     pub provider_order: Vec<String>,
     pub preferred_provider: String,
-    pub session_preferred_providers: BTreeMap<String, String>,
+    #[serde(with = "crate::orchestrator::config::session_preference_lists")]
+    pub session_preferred_providers: BTreeMap<String, Vec<String>>,
     pub provider_state: ProviderStateBundle,
 }
 
@@ -167,8 +168,7 @@ fn sanitize_active_routing_refs(cfg: &mut AppConfig) {
             .unwrap_or_default();
     }
     cfg.routing
-        .session_preferred_providers
-        .retain(|_, provider| cfg.providers.contains_key(provider));
+        .retain_session_preferred_providers(|provider| cfg.providers.contains_key(provider));
 }
 
 fn build_followed_provider_state(
@@ -234,6 +234,14 @@ fn build_followed_provider_state(
                 usage_adapter: payload.usage_adapter.clone(),
                 usage_base_url: payload.usage_base_url.clone(),
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
         if let Some(key) = non_empty_trimmed(payload.key) {
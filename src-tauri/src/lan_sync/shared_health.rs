@@ -188,7 +188,7 @@ fn run_single_owner_recovery_probe(
         recovery_probe_endpoint(),
         api_key.as_deref(),
         None,
-        cfg.routing.request_timeout_seconds,
+        crate::orchestrator::upstream::UpstreamTimeouts::from_routing(&cfg.routing),
     ));
     match result {
         Ok((status, _payload)) if (200..300).contains(&status) => {
@@ -115,6 +115,31 @@ fn reveal_main_window(app: &tauri::AppHandle) {
     }
 }
 
+// Bounded so a stuck stream cannot keep the process alive after the user asked to quit.
+const GRACEFUL_SHUTDOWN_TIMEOUT: std::time::Duration = std::time::Duration::from_secs(10);
+
+/// Real exit path: stop the gateway, let in-flight requests finish, flush the store, then exit.
+fn quit_gracefully(app: &tauri::AppHandle) {
+    static QUITTING: std::sync::atomic::AtomicBool = std::sync::atomic::AtomicBool::new(false);
+    if QUITTING.swap(true, std::sync::atomic::Ordering::SeqCst) {
+        return;
+    }
+    if let Some(w) = app.get_webview_window("main") {
+        let _ = w.hide();
+    }
+    let app = app.clone();
+    tauri::async_runtime::spawn(async move {
+        let gateway = app
+            .try_state::<app_state::AppState>()
+            .map(|st| st.gateway.clone());
+        if let Some(gateway) = gateway {
+            crate::orchestrator::gateway::shutdown_gracefully(&gateway, GRACEFUL_SHUTDOWN_TIMEOUT)
+                .await;
+        }
+        app.exit(0);
+    });
+}
+
 fn hide_main_window_for_background_launch(app: &tauri::AppHandle) {
     if let Some(w) = app.get_webview_window("main") {
         let _ = w.set_skip_taskbar(true);
@@ -171,6 +196,27 @@ fn maybe_notify_hidden_remote_update_success(app: &tauri::AppHandle) {
 #[cfg(not(target_os = "windows"))]
 fn maybe_notify_hidden_remote_update_success(_app: &tauri::AppHandle) {}
 
+/// Rejects commands outside the read-only allowlist while observer mode is on.
+fn with_read_only_guard<R: tauri::Runtime>(
+    handler: impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static,
+) -> impl Fn(tauri::ipc::Invoke<R>) -> bool + Send + Sync + 'static {
+    move |invoke| {
+        let command = invoke.message.command();
+        if !commands::command_allowed_in_read_only(command)
+            && invoke
+                .message
+                .webview()
+                .try_state::<app_state::AppState>()
+                .is_some_and(|state| state.gateway.cfg.read().routing.read_only_enabled())
+        {
+            let err = commands::CommandError::ReadOnly(command.to_string());
+            invoke.resolver.reject(err);
+            return true;
+        }
+        handler(invoke)
+    }
+}
+
 fn profile_data_dir_name(profile: &str) -> String {
     if profile == "default" {
         "user-data".to_string()
@@ -250,6 +296,24 @@ fn resolve_codex_home(user_data_dir: &Path, _is_ui_tauri: bool, _app_profile: &s
     isolated
 }
 
+/// `APIROUTER_DATA_DIR` relocates `config.toml`, `secrets.json`, and `data/` (e.g. into a synced
+/// folder). Unlike `API_ROUTER_USER_DATA_DIR` it is never created; it must already be a
+/// writable directory.
+fn usable_data_dir_override(raw: Option<&str>) -> Option<PathBuf> {
+    let trimmed = raw?.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    let path = PathBuf::from(trimmed);
+    if !path.is_dir() {
+        return None;
+    }
+    let probe = path.join(".api-router-write-probe");
+    std::fs::write(&probe, b"").ok()?;
+    let _ = std::fs::remove_file(&probe);
+    Some(path)
+}
+
 fn resolve_user_data_dir(exe_dir: &Path, is_ui_tauri: bool, app_profile: &str) -> PathBuf {
     if let Ok(explicit) = std::env::var("API_ROUTER_USER_DATA_DIR") {
         let trimmed = explicit.trim();
@@ -260,6 +324,14 @@ fn resolve_user_data_dir(exe_dir: &Path, is_ui_tauri: bool, app_profile: &str) -
         }
     }
 
+    let data_dir_override = std::env::var("APIROUTER_DATA_DIR").ok();
+    if let Some(path) = usable_data_dir_override(data_dir_override.as_deref()) {
+        return path;
+    }
+    if let Some(raw) = data_dir_override.filter(|raw| !raw.trim().is_empty()) {
+        log::warn!("ignoring APIROUTER_DATA_DIR={raw}: not an existing writable directory");
+    }
+
     if is_ui_tauri {
         if let Ok(p) = std::env::var("UI_TAURI_PROFILE_DIR") {
             let p = PathBuf::from(p);
@@ -435,6 +507,9 @@ fn seed_test_profile_data(state: &app_state::AppState) -> anyhow::Result<()> {
                         session_id: Some(session_id.as_str()),
                         node_id: Some(local_node_id.as_str()),
                         node_name: Some(local_node_name.as_str()),
+                        label: None,
+                        request_bytes: 0,
+                        response_bytes: 0,
                     },
                     None,
                 );
@@ -596,6 +671,9 @@ pub fn run() {
             }
             std::env::set_var("API_ROUTER_USER_DATA_DIR", &user_data_dir);
             reset_app_startup_diag();
+            let user_data_dir_detail = format!("user_data_dir={}", user_data_dir.display());
+            log::info!("using {user_data_dir_detail}");
+            write_app_startup_diag("user_data_dir_resolved", 0, Some(&user_data_dir_detail));
             let shared_tui_runtime_started = Instant::now();
             match crate::shared_tui_runtime::reconcile_shared_tui_runtime(&user_data_dir) {
                 Ok(result) => {
@@ -791,6 +869,12 @@ pub fn run() {
                     crate::orchestrator::quota::run_quota_scheduler(gateway, lan_sync).await;
                 });
 
+                // Connection warmer: idle unless `routing.keepalive_warm_seconds` is set.
+                let gateway = st.gateway.clone();
+                tauri::async_runtime::spawn(async move {
+                    crate::orchestrator::gateway::run_connection_warmer(gateway).await;
+                });
+
                 let app_handle = app.handle().clone();
                 tauri::async_runtime::spawn(async move {
                     loop {
@@ -800,6 +884,45 @@ pub fn run() {
                     }
                 });
 
+                // Usage rows are batched in memory; make sure a quiet gateway still lands them.
+                let store = app.state::<app_state::AppState>().gateway.store.clone();
+                tauri::async_runtime::spawn(async move {
+                    loop {
+                        tokio::time::sleep(std::time::Duration::from_millis(
+                            crate::orchestrator::store::USAGE_REQUEST_BATCH_MAX_DELAY_MS,
+                        ))
+                        .await;
+                        let store = store.clone();
+                        let _ =
+                            tokio::task::spawn_blocking(move || store.flush_usage_requests()).await;
+                    }
+                });
+
+                // Session routes are only written when they change; refresh their timestamps so
+                // the sticky TTL still holds after a restart.
+                let gateway = app.state::<app_state::AppState>().gateway.clone();
+                tauri::async_runtime::spawn(async move {
+                    loop {
+                        tokio::time::sleep(std::time::Duration::from_secs(60)).await;
+                        let gateway = gateway.clone();
+                        let _ = tokio::task::spawn_blocking(move || {
+                            crate::orchestrator::gateway::persist_last_used_routes(&gateway)
+                        })
+                        .await;
+                    }
+                });
+
+                let app_handle = app.handle().clone();
+                tauri::async_runtime::spawn(async move {
+                    loop {
+                        tokio::time::sleep(std::time::Duration::from_secs(60 * 60)).await;
+                        let st = app_handle.state::<app_state::AppState>();
+                        let _ = app_state::prune_expired_events(&st, unix_ms());
+                        let _ = st.gateway.store.prune_usage_failures(unix_ms());
+                        let _ = app_state::enforce_store_size_cap(&st);
+                    }
+                });
+
                 let app_handle = app.handle().clone();
                 tauri::async_runtime::spawn(async move {
                     loop {
@@ -845,7 +968,7 @@ pub fn run() {
                                 reveal_main_window(app);
                             }
                             "quit" => {
-                                app.exit(0);
+                                quit_gracefully(app);
                             }
                             _ => {}
                         }
@@ -866,21 +989,27 @@ pub fn run() {
                         app_profile.to_ascii_uppercase()
                     ));
                 }
-                if app_profile == "default" {
-                    let w2 = w.clone();
-                    w.on_window_event(move |event| {
-                        if let tauri::WindowEvent::CloseRequested { api, .. } = event {
-                            api.prevent_close();
+                let w2 = w.clone();
+                let minimize_to_tray = app_profile == "default";
+                w.on_window_event(move |event| {
+                    if let tauri::WindowEvent::CloseRequested { api, .. } = event {
+                        api.prevent_close();
+                        if minimize_to_tray {
                             let _ = w2.hide();
+                        } else {
+                            quit_gracefully(w2.app_handle());
                         }
-                    });
-                }
+                    }
+                });
             }
 
             Ok(())
         })
-        .invoke_handler(tauri::generate_handler![
+        .invoke_handler(with_read_only_guard(tauri::generate_handler![
             commands::get_status,
+            commands::get_store_stats,
+            commands::flush_store,
+            commands::create_manual_backup,
             commands::record_app_startup_stage,
             commands::record_web_transport_event,
             commands::record_ui_watchdog_heartbeat,
@@ -894,11 +1023,16 @@ pub fn run() {
             commands::open_external_url,
             commands::get_local_diagnostics,
             commands::get_event_log_entries,
+            commands::get_audit_log,
+            commands::get_health_history,
             commands::get_event_log_entry_by_id,
             commands::get_event_log_years,
             commands::get_event_log_daily_stats,
             commands::set_manual_override,
+            commands::simulate_provider_failure,
             commands::get_config,
+            commands::get_config_toml,
+            commands::get_config_path,
             commands::request_lan_pair,
             commands::approve_lan_pair,
             commands::submit_lan_pair_pin,
@@ -913,19 +1047,32 @@ pub fn run() {
             commands::get_gateway_token_preview,
             commands::get_gateway_token,
             commands::rotate_gateway_token,
+            commands::create_gateway_token,
+            commands::list_gateway_tokens,
+            commands::revoke_gateway_token,
             commands::set_preferred_provider,
             commands::set_route_mode,
+            commands::explain_routing,
+            commands::set_usage_label,
+            commands::clear_usage_label,
             commands::set_session_preferred_provider,
             commands::clear_session_preferred_provider,
+            commands::forget_client_session,
             commands::upsert_provider,
             commands::set_provider_supports_websockets,
+            commands::set_provider_system_prefix,
+            commands::set_provider_default_model,
+            commands::set_provider_notes,
             commands::set_provider_disabled,
             commands::set_provider_group,
             commands::set_providers_group,
             commands::delete_provider,
             commands::rename_provider,
+            commands::merge_providers,
             commands::get_provider_key,
             commands::set_provider_key,
+            commands::rotate_provider_key,
+            commands::get_provider_key_history,
             commands::clear_provider_key,
             commands::set_provider_account_email,
             commands::clear_provider_account_email,
@@ -943,17 +1090,25 @@ pub fn run() {
             commands::set_provider_quota_hard_cap,
             commands::set_provider_quota_hard_cap_field,
             commands::set_provider_manual_pricing,
+            commands::import_provider_pricing_from_json,
+            commands::set_provider_model_pricing,
+            commands::clear_provider_model_pricing,
             commands::get_provider_timeline,
             commands::set_provider_timeline,
+            commands::validate_provider_timeline,
             commands::get_provider_schedule,
             commands::set_provider_schedule,
             commands::set_provider_gap_fill,
             commands::get_effective_usage_base,
             commands::set_provider_order,
             commands::probe_provider,
+            commands::warm_providers,
+            commands::test_usage_endpoint,
             commands::codex_cli_toggle_auth_config_swap,
+            commands::restore_cli_home,
             commands::codex_cli_default_home,
             commands::codex_cli_default_wsl_home,
+            commands::refresh_wsl_gateway_host,
             commands::codex_cli_directories_get,
             commands::codex_cli_directories_set,
             commands::codex_cli_swap_status,
@@ -961,6 +1116,8 @@ pub fn run() {
             commands::set_codex_cli_config_toml,
             commands::provider_switchboard_status,
             commands::provider_switchboard_set_target,
+            commands::switchboard_add_profile,
+            commands::switchboard_remove_profile,
             commands::tailscale_status,
             commands::codex_account_login,
             commands::codex_account_logout,
@@ -972,13 +1129,20 @@ pub fn run() {
             commands::codex_account_profile_select,
             commands::codex_account_profile_remove,
             commands::get_usage_statistics,
+            commands::get_top_cost_models,
+            commands::export_usage_jsonl,
             commands::get_usage_request_entries,
             commands::get_usage_request_summary,
             commands::get_usage_request_daily_totals,
             commands::get_spend_history,
+            commands::get_spend_history_weekly,
+            commands::get_spend_total,
             commands::set_spend_history_entry,
+            commands::clear_spend_manual_days,
+            commands::set_spend_manual_range,
+            commands::rebuild_usage_day_aggregates,
             commands::remove_tracked_spend_history_entries
-        ])
+        ]))
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
@@ -989,7 +1153,7 @@ mod tests {
         app_launch_requests_hidden, app_profile_name_from_inputs, canonical_user_data_dir,
         is_noncanonical_user_data_override, profile_data_dir_name, resolve_codex_home,
         resolve_user_data_dir, should_enable_single_instance, should_reset_profile_data,
-        should_reveal_main_window_on_setup, should_seed_mock_data,
+        should_reveal_main_window_on_setup, should_seed_mock_data, usable_data_dir_override,
     };
 
     #[test]
@@ -1042,6 +1206,25 @@ mod tests {
         assert_eq!(got, override_dir);
     }
 
+    #[test]
+    fn data_dir_override_requires_existing_writable_directory() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let synced = tmp.path().join("synced");
+        std::fs::create_dir_all(&synced).unwrap();
+        let synced_raw = synced.to_string_lossy().to_string();
+
+        assert_eq!(
+            usable_data_dir_override(Some(&synced_raw)),
+            Some(synced.clone())
+        );
+        assert!(!synced.join(".api-router-write-probe").exists());
+        let missing = tmp.path().join("missing").to_string_lossy().to_string();
+        assert_eq!(usable_data_dir_override(Some(&missing)), None);
+        assert!(!tmp.path().join("missing").exists());
+        assert_eq!(usable_data_dir_override(Some("  ")), None);
+        assert_eq!(usable_data_dir_override(None), None);
+    }
+
     #[test]
     fn canonical_user_data_override_keeps_single_instance_enabled() {
         let tmp = tempfile::tempdir().expect("tempdir");
@@ -0,0 +1,463 @@
+//! Anthropic Messages API <-> OpenAI Responses API translation behind `/v1/messages`
+//! (`routing.anthropic_compat`). Only the plain text subset is translated; anything else is
+//! rejected up front so a client never gets a silently degraded request.
+
+use serde_json::{json, Map, Value};
+
+use super::openai::extract_text_from_responses;
+
+const SUPPORTED_REQUEST_KEYS: &[&str] = &[
+    "model",
+    "messages",
+    "system",
+    "max_tokens",
+    "stream",
+    "temperature",
+    "top_p",
+    "metadata",
+];
+
+/// Converts a Messages request body into a Responses request body.
+pub fn messages_request_to_responses(body: &Value) -> Result<Value, String> {
+    let obj = body
+        .as_object()
+        .ok_or_else(|| "request body must be a JSON object".to_string())?;
+    if let Some(key) = obj
+        .keys()
+        .find(|key| !SUPPORTED_REQUEST_KEYS.contains(&key.as_str()))
+    {
+        return Err(format!(
+            "{key} is not supported by the gateway's Anthropic compatibility"
+        ));
+    }
+    let model = obj
+        .get("model")
+        .and_then(Value::as_str)
+        .map(str::trim)
+        .filter(|model| !model.is_empty())
+        .ok_or_else(|| "model is required".to_string())?;
+    let messages = obj
+        .get("messages")
+        .and_then(Value::as_array)
+        .filter(|messages| !messages.is_empty())
+        .ok_or_else(|| "messages must be a non-empty array".to_string())?;
+
+    let mut input = Vec::with_capacity(messages.len());
+    for (idx, message) in messages.iter().enumerate() {
+        let role = message.get("role").and_then(Value::as_str).unwrap_or("");
+        let part_type = match role {
+            "user" => "input_text",
+            "assistant" => "output_text",
+            other => return Err(format!("messages.{idx}.role {other:?} is not supported")),
+        };
+        let parts = text_parts(message.get("content"), &format!("messages.{idx}.content"))?;
+        input.push(json!({
+            "type": "message",
+            "role": role,
+            "content": parts
+                .into_iter()
+                .map(|text| json!({ "type": part_type, "text": text }))
+                .collect::<Vec<_>>(),
+        }));
+    }
+
+    let mut out = Map::new();
+    out.insert("model".to_string(), json!(model));
+    out.insert("input".to_string(), Value::Array(input));
+    if let Some(system) = obj.get("system") {
+        let instructions = text_parts(Some(system), "system")?.join("\n\n");
+        if !instructions.is_empty() {
+            out.insert("instructions".to_string(), json!(instructions));
+        }
+    }
+    if let Some(max_tokens) = obj.get("max_tokens") {
+        let max_tokens = max_tokens
+            .as_u64()
+            .filter(|value| *value > 0)
+            .ok_or_else(|| "max_tokens must be a positive integer".to_string())?;
+        out.insert("max_output_tokens".to_string(), json!(max_tokens));
+    }
+    for key in ["temperature", "top_p"] {
+        if let Some(value) = obj.get(key) {
+            if !value.is_number() {
+                return Err(format!("{key} must be a number"));
+            }
+            out.insert(key.to_string(), value.clone());
+        }
+    }
+    if let Some(metadata) = obj.get("metadata") {
+        let metadata = metadata
+            .as_object()
+            .ok_or_else(|| "metadata must be an object".to_string())?;
+        if let Some(key) = metadata.keys().find(|key| key.as_str() != "user_id") {
+            return Err(format!("metadata.{key} is not supported"));
+        }
+        if let Some(user_id) = metadata.get("user_id").and_then(Value::as_str) {
+            out.insert("user".to_string(), json!(user_id));
+        }
+    }
+    let stream = match obj.get("stream") {
+        None | Some(Value::Null) => false,
+        Some(Value::Bool(stream)) => *stream,
+        Some(_) => return Err("stream must be a boolean".to_string()),
+    };
+    out.insert("stream".to_string(), json!(stream));
+    Ok(Value::Object(out))
+}
+
+/// Text of a string or `[{ "type": "text", ... }]` content value, one entry per block.
+fn text_parts(content: Option<&Value>, path: &str) -> Result<Vec<String>, String> {
+    match content {
+        Some(Value::String(text)) => Ok(vec![text.clone()]),
+        Some(Value::Array(blocks)) => blocks
+            .iter()
+            .enumerate()
+            .map(|(idx, block)| {
+                let block_type = block.get("type").and_then(Value::as_str).unwrap_or("");
+                if block_type != "text" {
+                    return Err(format!(
+                        "{path}.{idx}: content block type {block_type:?} is not supported"
+                    ));
+                }
+                block
+                    .get("text")
+                    .and_then(Value::as_str)
+                    .map(ToString::to_string)
+                    .ok_or_else(|| format!("{path}.{idx}.text must be a string"))
+            })
+            .collect(),
+        _ => Err(format!(
+            "{path} must be a string or an array of text blocks"
+        )),
+    }
+}
+
+fn stop_reason(response: &Value) -> &'static str {
+    let truncated = response.get("status").and_then(Value::as_str) == Some("incomplete")
+        && response
+            .pointer("/incomplete_details/reason")
+            .and_then(Value::as_str)
+            == Some("max_output_tokens");
+    if truncated {
+        "max_tokens"
+    } else {
+        "end_turn"
+    }
+}
+
+fn usage_tokens(response: &Value, key: &str) -> u64 {
+    response
+        .get("usage")
+        .and_then(|usage| usage.get(key))
+        .and_then(Value::as_u64)
+        .unwrap_or(0)
+}
+
+fn message_id(response: &Value) -> String {
+    response
+        .get("id")
+        .or_else(|| response.get("response_id"))
+        .and_then(Value::as_str)
+        .filter(|id| !id.is_empty())
+        .map(|id| format!("msg_{id}"))
+        .unwrap_or_else(|| "msg_gateway".to_string())
+}
+
+/// Converts a final Responses object into a Messages response.
+pub fn responses_to_message(response: &Value, requested_model: &str) -> Value {
+    json!({
+        "id": message_id(response),
+        "type": "message",
+        "role": "assistant",
+        "model": response.get("model").and_then(Value::as_str).unwrap_or(requested_model),
+        "content": [{ "type": "text", "text": extract_text_from_responses(response) }],
+        "stop_reason": stop_reason(response),
+        "stop_sequence": Value::Null,
+        "usage": {
+            "input_tokens": usage_tokens(response, "input_tokens"),
+            "output_tokens": usage_tokens(response, "output_tokens"),
+        },
+    })
+}
+
+pub fn error_type_for_status(status: u16) -> &'static str {
+    match status {
+        400 | 422 => "invalid_request_error",
+        401 => "authentication_error",
+        403 => "permission_error",
+        404 => "not_found_error",
+        413 => "request_too_large",
+        429 => "rate_limit_error",
+        529 | 503 => "overloaded_error",
+        _ => "api_error",
+    }
+}
+
+pub fn error_body(error_type: &str, message: &str) -> Value {
+    json!({
+        "type": "error",
+        "error": { "type": error_type, "message": message },
+    })
+}
+
+fn sse_event(name: &str, data: &Value) -> String {
+    format!("event: {name}\ndata: {data}\n\n")
+}
+
+/// Rewrites a Responses SSE stream into Messages SSE events, one chunk at a time.
+pub struct MessagesSseTranslator {
+    pending: Vec<u8>,
+    requested_model: String,
+    started: bool,
+    block_open: bool,
+    finished: bool,
+}
+
+impl MessagesSseTranslator {
+    pub fn new(requested_model: &str) -> Self {
+        Self {
+            pending: Vec::new(),
+            requested_model: requested_model.to_string(),
+            started: false,
+            block_open: false,
+            finished: false,
+        }
+    }
+
+    pub fn feed(&mut self, chunk: &[u8]) -> Vec<String> {
+        self.pending
+            .extend(chunk.iter().copied().filter(|byte| *byte != b'\r'));
+        let mut out = Vec::new();
+        while let Some(end) = self.pending.windows(2).position(|pair| pair == b"\n\n") {
+            let frame: Vec<u8> = self.pending.drain(..end + 2).collect();
+            let frame = String::from_utf8_lossy(&frame);
+            let data = frame
+                .lines()
+                .filter_map(|line| line.strip_prefix("data:"))
+                .map(str::trim_start)
+                .collect::<Vec<_>>()
+                .join("\n");
+            if let Ok(event) = serde_json::from_str::<Value>(&data) {
+                self.on_event(&event, &mut out);
+            }
+        }
+        out
+    }
+
+    /// Closes the message if upstream ended without a terminal event.
+    pub fn finish(&mut self) -> Vec<String> {
+        let mut out = Vec::new();
+        if !self.finished {
+            self.complete(&Value::Null, &mut out);
+        }
+        out
+    }
+
+    fn on_event(&mut self, event: &Value, out: &mut Vec<String>) {
+        if self.finished {
+            return;
+        }
+        let response = event.get("response").unwrap_or(event);
+        match event.get("type").and_then(Value::as_str).unwrap_or("") {
+            "response.created" => self.start(response, out),
+            "response.output_text.delta" => {
+                let delta = event.get("delta").and_then(Value::as_str).unwrap_or("");
+                self.start(response, out);
+                if !self.block_open {
+                    self.block_open = true;
+                    out.push(sse_event(
+                        "content_block_start",
+                        &json!({
+                            "type": "content_block_start",
+                            "index": 0,
+                            "content_block": { "type": "text", "text": "" },
+                        }),
+                    ));
+                }
+                out.push(sse_event(
+                    "content_block_delta",
+                    &json!({
+                        "type": "content_block_delta",
+                        "index": 0,
+                        "delta": { "type": "text_delta", "text": delta },
+                    }),
+                ));
+            }
+            "response.completed" | "response.incomplete" => {
+                self.start(response, out);
+                self.complete(response, out);
+            }
+            "response.failed" | "error" => {
+                let message = event
+                    .pointer("/response/error/message")
+                    .or_else(|| event.pointer("/error/message"))
+                    .or_else(|| event.get("message"))
+                    .and_then(Value::as_str)
+                    .unwrap_or("upstream stream failed");
+                self.finished = true;
+                out.push(sse_event("error", &error_body("api_error", message)));
+            }
+            _ => {}
+        }
+    }
+
+    fn start(&mut self, response: &Value, out: &mut Vec<String>) {
+        if self.started {
+            return;
+        }
+        self.started = true;
+        out.push(sse_event(
+            "message_start",
+            &json!({
+                "type": "message_start",
+                "message": {
+                    "id": message_id(response),
+                    "type": "message",
+                    "role": "assistant",
+                    "model": response
+                        .get("model")
+                        .and_then(Value::as_str)
+                        .unwrap_or(&self.requested_model),
+                    "content": [],
+                    "stop_reason": Value::Null,
+                    "stop_sequence": Value::Null,
+                    "usage": {
+                        "input_tokens": usage_tokens(response, "input_tokens"),
+                        "output_tokens": 0,
+                    },
+                },
+            }),
+        ));
+    }
+
+    fn complete(&mut self, response: &Value, out: &mut Vec<String>) {
+        self.start(response, out);
+        if self.block_open {
+            self.block_open = false;
+            out.push(sse_event(
+                "content_block_stop",
+                &json!({ "type": "content_block_stop", "index": 0 }),
+            ));
+        }
+        out.push(sse_event(
+            "message_delta",
+            &json!({
+                "type": "message_delta",
+                "delta": { "stop_reason": stop_reason(response), "stop_sequence": Value::Null },
+                "usage": { "output_tokens": usage_tokens(response, "output_tokens") },
+            }),
+        ));
+        out.push(sse_event(
+            "message_stop",
+            &json!({ "type": "message_stop" }),
+        ));
+        self.finished = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn translates_text_messages_and_system_prompt() {
+        let body = json!({
+            "model": "claude-test",
+            "max_tokens": 256,
+            "system": [{ "type": "text", "text": "Be brief.", "cache_control": { "type": "ephemeral" } }],
+            "messages": [
+                { "role": "user", "content": "hi" },
+                { "role": "assistant", "content": [{ "type": "text", "text": "hello" }] },
+                { "role": "user", "content": "again" }
+            ],
+            "stream": true,
+            "metadata": { "user_id": "u-1" }
+        });
+
+        let translated = messages_request_to_responses(&body).expect("translate");
+
+        assert_eq!(translated["model"], "claude-test");
+        assert_eq!(translated["instructions"], "Be brief.");
+        assert_eq!(translated["max_output_tokens"], 256);
+        assert_eq!(translated["stream"], true);
+        assert_eq!(translated["user"], "u-1");
+        assert_eq!(translated["input"][1]["content"][0]["type"], "output_text");
+        assert_eq!(translated["input"][2]["content"][0]["text"], "again");
+    }
+
+    #[test]
+    fn rejects_unsupported_features_instead_of_dropping_them() {
+        let base = json!({ "model": "m", "messages": [{ "role": "user", "content": "hi" }] });
+        let mut with_tools = base.clone();
+        with_tools["tools"] = json!([{ "name": "lookup" }]);
+        let err = messages_request_to_responses(&with_tools).unwrap_err();
+        assert!(err.contains("tools"));
+
+        let mut with_image = base.clone();
+        with_image["messages"][0]["content"] = json!([{ "type": "image", "source": {} }]);
+        let err = messages_request_to_responses(&with_image).unwrap_err();
+        assert!(err.contains("messages.0.content.0"));
+
+        let mut with_stop = base;
+        with_stop["stop_sequences"] = json!(["END"]);
+        assert!(messages_request_to_responses(&with_stop).is_err());
+    }
+
+    #[test]
+    fn converts_final_response_into_message() {
+        let response = json!({
+            "id": "resp_1",
+            "model": "gpt-test",
+            "status": "incomplete",
+            "incomplete_details": { "reason": "max_output_tokens" },
+            "output": [{ "content": [{ "type": "output_text", "text": "partial" }] }],
+            "usage": { "input_tokens": 7, "output_tokens": 3 }
+        });
+
+        let message = responses_to_message(&response, "claude-test");
+
+        assert_eq!(message["id"], "msg_resp_1");
+        assert_eq!(message["model"], "gpt-test");
+        assert_eq!(message["content"][0]["text"], "partial");
+        assert_eq!(message["stop_reason"], "max_tokens");
+        assert_eq!(message["usage"]["output_tokens"], 3);
+    }
+
+    #[test]
+    fn sse_translator_emits_message_events_across_split_chunks() {
+        let upstream = concat!(
+            "event: response.created\r\n",
+            "data: {\"type\":\"response.created\",\"response\":{\"id\":\"resp_1\",\"model\":\"gpt-test\"}}\r\n\r\n",
+            "data: {\"type\":\"response.output_text.delta\",\"delta\":\"Hel\"}\n\n",
+            "data: {\"type\":\"response.output_text.delta\",\"delta\":\"lo\"}\n\n",
+            "data: {\"type\":\"response.completed\",\"response\":{\"id\":\"resp_1\",\"usage\":{\"output_tokens\":2}}}\n\n",
+            "data: [DONE]\n\n"
+        );
+        let mut translator = MessagesSseTranslator::new("claude-test");
+        let mut events = Vec::new();
+        for chunk in upstream.as_bytes().chunks(7) {
+            events.extend(translator.feed(chunk));
+        }
+        events.extend(translator.finish());
+
+        let names: Vec<&str> = events
+            .iter()
+            .filter_map(|event| event.lines().next()?.strip_prefix("event: "))
+            .collect();
+        assert_eq!(
+            names,
+            vec![
+                "message_start",
+                "content_block_start",
+                "content_block_delta",
+                "content_block_delta",
+                "content_block_stop",
+                "message_delta",
+                "message_stop"
+            ]
+        );
+        assert!(events[0].contains("\"model\":\"gpt-test\""));
+        assert!(events[3].contains("\"text\":\"lo\""));
+        assert!(events[5].contains("\"output_tokens\":2"));
+    }
+}
@@ -18,15 +18,28 @@ impl RouteMode {
     }
 }
 
+/// Which instant a local wall-clock time that occurs twice (DST fall-back) resolves to.
+#[derive(Debug, Clone, Copy, Serialize, Deserialize, PartialEq, Eq, Default)]
+#[serde(rename_all = "snake_case")]
+pub enum AmbiguousLocalTime {
+    #[default]
+    Earliest,
+    Latest,
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct RoutingConfig {
     pub preferred_provider: String,
     /// Per-client-session preferred provider overrides (keyed by Codex session id).
     ///
-    /// When a request is tagged with a client session id, the router can use this mapping
-    /// instead of the global `preferred_provider`.
-    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
-    pub session_preferred_providers: std::collections::BTreeMap<String, String>,
+    /// When a request is tagged with a client session id, the router tries the session's
+    /// providers in order before the global `preferred_provider`.
+    #[serde(
+        default,
+        skip_serializing_if = "std::collections::BTreeMap::is_empty",
+        with = "session_preference_lists"
+    )]
+    pub session_preferred_providers: std::collections::BTreeMap<String, Vec<String>>,
     #[serde(default)]
     pub route_mode: RouteMode,
     pub auto_return_to_preferred: bool,
@@ -34,12 +47,302 @@ pub struct RoutingConfig {
     pub failure_threshold: u32,
     pub cooldown_seconds: u64,
     pub request_timeout_seconds: u64,
+    /// Idle gap after which a session's last route is ignored and provider selection re-runs.
+    #[serde(default = "default_session_sticky_ttl_seconds")]
+    pub session_sticky_ttl_seconds: u64,
+    /// Free-form tag stamped on every recorded usage request while set (e.g. a benchmark run).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_label: Option<String>,
+    /// Proxy requests with a larger body are rejected with 413 before any upstream call.
+    /// Zero disables the limit.
+    #[serde(default = "default_max_request_body_bytes")]
+    pub max_request_body_bytes: u64,
+    /// Peer addresses (exact IP or CIDR) allowed to reach the gateway. Empty allows all.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub allowed_peer_ips: Vec<String>,
+    /// Browser origins (or `*`) that get CORS headers and preflight answers. Empty sends none.
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub cors_allowed_origins: Vec<String>,
+    /// Upstream TCP/TLS connect timeout. Defaults to `request_timeout_seconds`, capped at
+    /// `DEFAULT_CONNECT_TIMEOUT_SECONDS`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub connect_timeout_seconds: Option<u64>,
+    /// Upstream idle read timeout: fails only when no bytes arrive for this long, so a steadily
+    /// streaming response is never cut off. Defaults to `request_timeout_seconds`.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub read_timeout_seconds: Option<u64>,
+    /// OTLP/HTTP collector (e.g. `http://127.0.0.1:4318`) that receives a span per proxied
+    /// request. Unset disables tracing.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub otel_endpoint: Option<String>,
+    /// Trailing key characters revealed in UI key previews (clamped to `MAX_KEY_PREVIEW_TAIL`).
+    #[serde(default = "default_key_preview_tail")]
+    pub key_preview_tail: u32,
+    /// Base URL `upsert_provider` fills in when the caller leaves it empty.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_base_url: Option<String>,
+    /// Usage adapter `upsert_provider` assigns to new providers that would otherwise have none.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_usage_adapter: Option<String>,
+    /// How long after the last routed request the dashboard still reports active providers.
+    #[serde(default = "default_active_window_seconds")]
+    pub active_window_seconds: u64,
+    /// How long after its last request a client session counts as active.
+    #[serde(default = "default_session_active_window_seconds")]
+    pub session_active_window_seconds: u64,
+    /// Observer mode: mutating desktop commands are rejected while the gateway keeps routing.
+    /// `APIROUTER_READONLY=1` enables it without editing the config.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub read_only: bool,
+    /// Robust z-score (median + MAD over the trailing days) above which a spend history day is
+    /// flagged as a spike.
+    #[serde(default = "default_spike_sigma")]
+    pub spike_sigma: f64,
+    /// How a DST-ambiguous local midnight resolves when usage and spend are bucketed by day.
+    /// Midnights skipped by a spring-forward always shift to the next valid instant.
+    #[serde(default)]
+    pub ambiguous_local_time: AmbiguousLocalTime,
+    /// Quota percentages keep one decimal (`0.4%`) instead of flooring to a whole percent, so
+    /// a nearly drained window no longer reads the same as an exhausted one.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub fractional_percent_display: bool,
+    /// Serves Anthropic `/v1/messages` by translating to and from the Responses API.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub anthropic_compat: bool,
+    /// Soft cap on the events database. Above it the oldest `info` events are evicted until it
+    /// fits. Zero (the default) disables eviction.
+    #[serde(default = "default_max_store_bytes")]
+    pub max_store_bytes: u64,
+    /// Also evict the oldest raw usage requests once no `info` events are left to drop.
+    #[serde(default, skip_serializing_if = "std::ops::Not::not")]
+    pub evict_usage_requests: bool,
+    /// Event/audit log rows returned when the caller passes no limit.
+    #[serde(default = "default_event_query_default_limit")]
+    pub event_query_default_limit: u32,
+    /// Largest event/audit log slice a caller may request (clamped to `MAX_EVENT_QUERY_LIMIT`).
+    #[serde(default = "default_event_query_max_limit")]
+    pub event_query_max_limit: u32,
+    /// Interval between background `/models` requests that keep pooled upstream connections to
+    /// every enabled provider warm (raised to `MIN_KEEPALIVE_WARM_SECONDS`). Zero disables it.
+    #[serde(default)]
+    pub keepalive_warm_seconds: u64,
 }
 
+/// Large enough that sessions keep their last provider unless explicitly configured otherwise.
+pub const DEFAULT_SESSION_STICKY_TTL_SECONDS: u64 = 30 * 24 * 60 * 60;
+
+/// Serde for `routing.session_preferred_providers`. Older configs and LAN peers store a single
+/// provider name per session, so a plain string reads as a one-element list and one-element
+/// lists are written back as a string.
+pub mod session_preference_lists {
+    use std::collections::BTreeMap;
+
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    #[derive(Deserialize)]
+    #[serde(untagged)]
+    enum OneOrMany {
+        One(String),
+        Many(Vec<String>),
+    }
+
+    #[derive(Serialize)]
+    #[serde(untagged)]
+    enum OneOrManyRef<'a> {
+        One(&'a String),
+        Many(&'a Vec<String>),
+    }
+
+    pub fn serialize<S: Serializer>(
+        map: &BTreeMap<String, Vec<String>>,
+        serializer: S,
+    ) -> Result<S::Ok, S::Error> {
+        serializer.collect_map(map.iter().map(|(session, providers)| {
+            let value = match providers.as_slice() {
+                [only] => OneOrManyRef::One(only),
+                _ => OneOrManyRef::Many(providers),
+            };
+            (session, value)
+        }))
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<BTreeMap<String, Vec<String>>, D::Error> {
+        let raw = BTreeMap::<String, OneOrMany>::deserialize(deserializer)?;
+        Ok(raw
+            .into_iter()
+            .map(|(session, value)| match value {
+                OneOrMany::One(provider) => (session, vec![provider]),
+                OneOrMany::Many(providers) => (session, providers),
+            })
+            .filter(|(_, providers)| !providers.is_empty())
+            .collect())
+    }
+}
+
+fn default_session_sticky_ttl_seconds() -> u64 {
+    DEFAULT_SESSION_STICKY_TTL_SECONDS
+}
+
+pub const DEFAULT_CONNECT_TIMEOUT_SECONDS: u64 = 10;
+
+pub const DEFAULT_MAX_REQUEST_BODY_BYTES: u64 = 8 * 1024 * 1024;
+
+fn default_max_request_body_bytes() -> u64 {
+    DEFAULT_MAX_REQUEST_BODY_BYTES
+}
+
+pub const DEFAULT_KEY_PREVIEW_TAIL: u32 = 4;
+pub const MAX_KEY_PREVIEW_TAIL: u32 = 8;
+
+fn default_key_preview_tail() -> u32 {
+    DEFAULT_KEY_PREVIEW_TAIL
+}
+
+pub const DEFAULT_ACTIVE_WINDOW_SECONDS: u64 = 2 * 60;
+pub const DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS: u64 = 60;
+
+fn default_active_window_seconds() -> u64 {
+    DEFAULT_ACTIVE_WINDOW_SECONDS
+}
+
+fn default_session_active_window_seconds() -> u64 {
+    DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS
+}
+
+pub const DEFAULT_SPIKE_SIGMA: f64 = 3.0;
+
+fn default_spike_sigma() -> f64 {
+    DEFAULT_SPIKE_SIGMA
+}
+
+pub const DEFAULT_MAX_STORE_BYTES: u64 = 0;
+
+fn default_max_store_bytes() -> u64 {
+    DEFAULT_MAX_STORE_BYTES
+}
+
+pub const DEFAULT_EVENT_QUERY_DEFAULT_LIMIT: u32 = 2000;
+pub const DEFAULT_EVENT_QUERY_MAX_LIMIT: u32 = 5000;
+/// Hard ceiling so a misconfigured limit cannot load an unbounded slice into memory.
+pub const MAX_EVENT_QUERY_LIMIT: u32 = 100_000;
+
+fn default_event_query_default_limit() -> u32 {
+    DEFAULT_EVENT_QUERY_DEFAULT_LIMIT
+}
+
+fn default_event_query_max_limit() -> u32 {
+    DEFAULT_EVENT_QUERY_MAX_LIMIT
+}
+
+/// Warming more often than this only adds upstream traffic; pooled connections outlive it.
+pub const MIN_KEEPALIVE_WARM_SECONDS: u64 = 15;
+
 impl RoutingConfig {
+    /// Providers the session prefers, in order; empty when it has no override.
+    pub fn session_preferred_list(&self, session_id: &str) -> &[String] {
+        self.session_preferred_providers
+            .get(session_id)
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+
+    /// Drops providers rejected by `keep` from every session list, removing emptied sessions.
+    pub fn retain_session_preferred_providers(&mut self, mut keep: impl FnMut(&str) -> bool) {
+        for providers in self.session_preferred_providers.values_mut() {
+            providers.retain(|provider| keep(provider));
+        }
+        self.session_preferred_providers
+            .retain(|_, providers| !providers.is_empty());
+    }
+
     pub fn effective_cooldown_seconds(&self) -> u64 {
         self.cooldown_seconds.max(10 * 60)
     }
+
+    pub fn effective_connect_timeout_seconds(&self) -> u64 {
+        self.connect_timeout_seconds
+            .unwrap_or_else(|| {
+                self.request_timeout_seconds
+                    .min(DEFAULT_CONNECT_TIMEOUT_SECONDS)
+            })
+            .max(1)
+    }
+
+    pub fn effective_read_timeout_seconds(&self) -> u64 {
+        self.read_timeout_seconds
+            .unwrap_or(self.request_timeout_seconds)
+            .max(1)
+    }
+
+    pub fn effective_key_preview_tail(&self) -> usize {
+        self.key_preview_tail.min(MAX_KEY_PREVIEW_TAIL) as usize
+    }
+
+    /// Zero falls back to the default so a blank setting never hides all activity.
+    pub fn active_window_ms(&self) -> u64 {
+        window_seconds_or(self.active_window_seconds, DEFAULT_ACTIVE_WINDOW_SECONDS) * 1000
+    }
+
+    pub fn read_only_enabled(&self) -> bool {
+        self.read_only
+            || std::env::var("APIROUTER_READONLY")
+                .is_ok_and(|value| matches!(value.trim(), "1" | "true"))
+    }
+
+    pub fn session_active_window_ms(&self) -> u64 {
+        window_seconds_or(
+            self.session_active_window_seconds,
+            DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+        ) * 1000
+    }
+
+    /// Zero falls back to the default; never above `MAX_EVENT_QUERY_LIMIT`.
+    pub fn effective_event_query_max_limit(&self) -> usize {
+        let limit = if self.event_query_max_limit == 0 {
+            DEFAULT_EVENT_QUERY_MAX_LIMIT
+        } else {
+            self.event_query_max_limit
+        };
+        limit.min(MAX_EVENT_QUERY_LIMIT) as usize
+    }
+
+    /// Zero falls back to the default; never above the effective max limit.
+    pub fn effective_event_query_default_limit(&self) -> usize {
+        let limit = if self.event_query_default_limit == 0 {
+            DEFAULT_EVENT_QUERY_DEFAULT_LIMIT
+        } else {
+            self.event_query_default_limit
+        };
+        (limit as usize).min(self.effective_event_query_max_limit())
+    }
+
+    /// `None` while the connection warmer is disabled.
+    pub fn keepalive_warm_interval(&self) -> Option<std::time::Duration> {
+        (self.keepalive_warm_seconds > 0).then(|| {
+            std::time::Duration::from_secs(
+                self.keepalive_warm_seconds.max(MIN_KEEPALIVE_WARM_SECONDS),
+            )
+        })
+    }
+
+    /// Non-positive or non-finite values fall back to the default.
+    pub fn effective_spike_sigma(&self) -> f64 {
+        if self.spike_sigma.is_finite() && self.spike_sigma > 0.0 {
+            self.spike_sigma
+        } else {
+            DEFAULT_SPIKE_SIGMA
+        }
+    }
+}
+
+fn window_seconds_or(seconds: u64, default: u64) -> u64 {
+    if seconds == 0 {
+        default
+    } else {
+        seconds.min(24 * 60 * 60)
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -50,6 +353,15 @@ pub struct ProviderConfig {
     pub group: Option<String>,
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub disabled: bool,
+    /// Usage-only providers (`false`) are never routed to but still count for spend tracking.
+    #[serde(
+        default = "default_routing_eligible",
+        skip_serializing_if = "is_routing_eligible_default"
+    )]
+    pub routing_eligible: bool,
+    /// Auto-selection tier; lower tiers are tried first and `provider_order` breaks ties.
+    #[serde(default, skip_serializing_if = "is_default_tier")]
+    pub tier: u8,
     #[serde(default, skip_serializing_if = "std::ops::Not::not")]
     pub supports_websockets: bool,
     /// Optional usage/quota source type for this provider.
@@ -74,12 +386,136 @@ pub struct ProviderConfig {
     /// The UI/API never exposes it.
     #[serde(default, skip_serializing_if = "String::is_empty")]
     pub api_key: String,
+    /// Optional model name remapping applied to outgoing requests (requested -> upstream).
+    ///
+    /// Usage stats keep the originally requested model name.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub model_aliases: std::collections::BTreeMap<String, String>,
+    /// Optional system instruction prepended to chat-style requests routed to this provider.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub system_prefix: Option<String>,
+    /// Model injected into requests that arrive without one (before `model_aliases` applies).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub default_model: Option<String>,
+    /// Optional explicit locations of usage fields, used instead of the heuristic key search.
+    /// Applies to Codex account windows and to this provider's quota responses.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_field_map: Option<UsageFieldMap>,
+    /// Extra headers sent on every upstream request; never overrides auth or request headers.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub headers: std::collections::BTreeMap<String, String>,
+    /// Operator notes (plan, account, ...). Purely informational; must never hold secrets.
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub notes: String,
+}
+
+fn default_routing_eligible() -> bool {
+    true
+}
+
+fn is_routing_eligible_default(routing_eligible: &bool) -> bool {
+    *routing_eligible
+}
+
+fn is_default_tier(tier: &u8) -> bool {
+    *tier == 0
+}
+
+impl ProviderConfig {
+    /// Whether the router may select this provider at all.
+    pub fn routable(&self) -> bool {
+        !self.disabled && self.routing_eligible
+    }
+}
+
+/// Reusable provider defaults `upsert_provider` can clone from by name.
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ProviderTemplate {
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub base_url: String,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub usage_adapter: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub usage_base_url: Option<String>,
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub headers: std::collections::BTreeMap<String, String>,
+}
+
+/// JSON Pointers (RFC 6901) into a usage/rate-limit window object.
+///
+/// When a pointer is set, its value is read directly and the heuristic key search for that
+/// field is skipped; unset pointers keep the heuristic.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq, Eq)]
+pub struct UsageFieldMap {
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub reset_at_pointer: Option<String>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub used_percent_pointer: Option<String>,
+}
+
+impl UsageFieldMap {
+    pub fn reset_at_pointer(&self) -> Option<&str> {
+        non_empty_pointer(self.reset_at_pointer.as_deref())
+    }
+
+    pub fn used_percent_pointer(&self) -> Option<&str> {
+        non_empty_pointer(self.used_percent_pointer.as_deref())
+    }
+}
+
+fn non_empty_pointer(pointer: Option<&str>) -> Option<&str> {
+    pointer.map(str::trim).filter(|pointer| !pointer.is_empty())
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct ListenConfig {
     pub host: String,
     pub port: u16,
+    /// Also serve the gateway on this Unix domain socket (Unix only; TCP stays bound).
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub unix_socket_path: Option<String>,
+}
+
+/// Event log retention applied by the periodic pruner.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct EventsConfig {
+    /// Events older than this are deleted unless they match `keep_levels` or `keep_providers`.
+    /// 0 (the default) keeps events forever.
+    #[serde(default = "default_event_retention_days")]
+    pub retention_days: u64,
+    /// Upper bound for events kept by `keep_levels` / `keep_providers`; 0 means no extra bound.
+    #[serde(default = "default_event_extended_retention_days")]
+    pub extended_retention_days: u64,
+    #[serde(default = "default_event_keep_levels")]
+    pub keep_levels: Vec<String>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub keep_providers: Vec<String>,
+}
+
+pub const DEFAULT_EVENT_RETENTION_DAYS: u64 = 0;
+pub const DEFAULT_EVENT_EXTENDED_RETENTION_DAYS: u64 = 0;
+
+fn default_event_retention_days() -> u64 {
+    DEFAULT_EVENT_RETENTION_DAYS
+}
+
+fn default_event_extended_retention_days() -> u64 {
+    DEFAULT_EVENT_EXTENDED_RETENTION_DAYS
+}
+
+fn default_event_keep_levels() -> Vec<String> {
+    vec!["error".to_string(), "warning".to_string()]
+}
+
+impl Default for EventsConfig {
+    fn default() -> Self {
+        Self {
+            retention_days: DEFAULT_EVENT_RETENTION_DAYS,
+            extended_retention_days: DEFAULT_EVENT_EXTENDED_RETENTION_DAYS,
+            keep_levels: default_event_keep_levels(),
+            keep_providers: Vec::new(),
+        }
+    }
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -89,6 +525,14 @@ pub struct AppConfig {
     pub providers: std::collections::BTreeMap<String, ProviderConfig>,
     #[serde(default, skip_serializing_if = "Vec::is_empty")]
     pub provider_order: Vec<String>,
+    #[serde(default)]
+    pub events: EventsConfig,
+    /// Named Codex CLI homes (name -> path) the provider switchboard accepts in place of paths.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub switchboard_profiles: std::collections::BTreeMap<String, String>,
+    /// Named provider presets (name -> template) accepted by `upsert_provider`.
+    #[serde(default, skip_serializing_if = "std::collections::BTreeMap::is_empty")]
+    pub provider_templates: std::collections::BTreeMap<String, ProviderTemplate>,
 }
 
 impl AppConfig {
@@ -105,6 +549,14 @@ impl AppConfig {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: "".to_string(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
         for i in 1..=2 {
@@ -120,6 +572,14 @@ impl AppConfig {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             );
         }
@@ -128,6 +588,7 @@ impl AppConfig {
             listen: ListenConfig {
                 host: "127.0.0.1".to_string(),
                 port: 4000,
+                unix_socket_path: None,
             },
             routing: RoutingConfig {
                 preferred_provider: "official".to_string(),
@@ -140,6 +601,29 @@ impl AppConfig {
                 // Streamed responses can be long-lived; keep a larger default to avoid
                 // premature timeouts on slower providers/networks.
                 request_timeout_seconds: 300,
+                session_sticky_ttl_seconds: DEFAULT_SESSION_STICKY_TTL_SECONDS,
+                usage_label: None,
+                max_request_body_bytes: DEFAULT_MAX_REQUEST_BODY_BYTES,
+                allowed_peer_ips: Vec::new(),
+                connect_timeout_seconds: None,
+                read_timeout_seconds: None,
+                otel_endpoint: None,
+                key_preview_tail: DEFAULT_KEY_PREVIEW_TAIL,
+                default_base_url: None,
+                default_usage_adapter: None,
+                active_window_seconds: DEFAULT_ACTIVE_WINDOW_SECONDS,
+                session_active_window_seconds: DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+                read_only: false,
+                spike_sigma: DEFAULT_SPIKE_SIGMA,
+                ambiguous_local_time: AmbiguousLocalTime::Earliest,
+                fractional_percent_display: false,
+                anthropic_compat: false,
+                cors_allowed_origins: Vec::new(),
+                max_store_bytes: DEFAULT_MAX_STORE_BYTES,
+                evict_usage_requests: false,
+                event_query_default_limit: DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+                event_query_max_limit: DEFAULT_EVENT_QUERY_MAX_LIMIT,
+                keepalive_warm_seconds: 0,
             },
             providers,
             provider_order: vec![
@@ -147,6 +631,9 @@ impl AppConfig {
                 "provider_1".to_string(),
                 "provider_2".to_string(),
             ],
+            events: EventsConfig::default(),
+            switchboard_profiles: std::collections::BTreeMap::new(),
+            provider_templates: std::collections::BTreeMap::new(),
         }
     }
 }
@@ -165,4 +652,94 @@ mod tests {
             Some("https://api.openai.com/v1")
         );
     }
+
+    #[test]
+    fn upstream_timeouts_default_from_request_timeout() {
+        let mut cfg = AppConfig::default_config();
+        cfg.routing.request_timeout_seconds = 300;
+        assert_eq!(cfg.routing.effective_connect_timeout_seconds(), 10);
+        assert_eq!(cfg.routing.effective_read_timeout_seconds(), 300);
+
+        cfg.routing.request_timeout_seconds = 5;
+        assert_eq!(cfg.routing.effective_connect_timeout_seconds(), 5);
+
+        cfg.routing.connect_timeout_seconds = Some(3);
+        cfg.routing.read_timeout_seconds = Some(900);
+        assert_eq!(cfg.routing.effective_connect_timeout_seconds(), 3);
+        assert_eq!(cfg.routing.effective_read_timeout_seconds(), 900);
+    }
+
+    #[test]
+    fn active_windows_default_and_ignore_zero() {
+        let mut cfg = AppConfig::default_config();
+        assert_eq!(cfg.routing.active_window_ms(), 120_000);
+        assert_eq!(cfg.routing.session_active_window_ms(), 60_000);
+
+        cfg.routing.active_window_seconds = 600;
+        cfg.routing.session_active_window_seconds = 0;
+        assert_eq!(cfg.routing.active_window_ms(), 600_000);
+        assert_eq!(cfg.routing.session_active_window_ms(), 60_000);
+    }
+
+    #[test]
+    fn keepalive_warmer_is_off_by_default_and_floored() {
+        let mut cfg = AppConfig::default_config();
+        assert_eq!(cfg.routing.keepalive_warm_interval(), None);
+
+        cfg.routing.keepalive_warm_seconds = 1;
+        assert_eq!(
+            cfg.routing.keepalive_warm_interval(),
+            Some(std::time::Duration::from_secs(15))
+        );
+        cfg.routing.keepalive_warm_seconds = 240;
+        assert_eq!(
+            cfg.routing.keepalive_warm_interval(),
+            Some(std::time::Duration::from_secs(240))
+        );
+    }
+
+    #[test]
+    fn read_only_is_omitted_until_enabled() {
+        let mut cfg = AppConfig::default_config();
+        let toml_text = toml::to_string_pretty(&cfg).expect("serialize");
+        assert!(!toml_text.contains("read_only"));
+
+        cfg.routing.read_only = true;
+        let toml_text = toml::to_string_pretty(&cfg).expect("serialize");
+        let reparsed: AppConfig = toml::from_str(&toml_text).expect("parse");
+        assert!(reparsed.routing.read_only);
+        assert!(reparsed.routing.read_only_enabled());
+    }
+
+    #[test]
+    fn session_preferred_providers_accept_string_or_list() {
+        let mut cfg = AppConfig::default_config();
+        let mut toml_text = toml::to_string_pretty(&cfg).expect("serialize");
+        toml_text.push_str(
+            "\n[routing.session_preferred_providers]\nsingle = \"a\"\nordered = [\"b\", \"c\"]\nempty = []\n",
+        );
+        cfg = toml::from_str(&toml_text).expect("parse");
+        assert_eq!(
+            cfg.routing.session_preferred_list("single"),
+            ["a".to_string()]
+        );
+        assert_eq!(
+            cfg.routing.session_preferred_list("ordered"),
+            ["b".to_string(), "c".to_string()]
+        );
+        assert!(cfg.routing.session_preferred_list("empty").is_empty());
+
+        cfg.routing
+            .retain_session_preferred_providers(|name| name != "b");
+        let reparsed: AppConfig =
+            toml::from_str(&toml::to_string_pretty(&cfg).expect("serialize")).expect("reparse");
+        assert_eq!(
+            reparsed.routing.session_preferred_providers,
+            cfg.routing.session_preferred_providers
+        );
+        assert_eq!(
+            reparsed.routing.session_preferred_list("ordered"),
+            ["c".to_string()]
+        );
+    }
 }
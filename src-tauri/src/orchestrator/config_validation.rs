@@ -0,0 +1,629 @@
+use serde::Serialize;
+
+use super::config::AppConfig;
+
+const TOP_LEVEL_KEYS: &[&str] = &[
+    "listen",
+    "routing",
+    "providers",
+    "provider_order",
+    "events",
+    "switchboard_profiles",
+    "provider_templates",
+];
+const LISTEN_KEYS: &[&str] = &["host", "port", "unix_socket_path"];
+const ROUTING_KEYS: &[&str] = &[
+    "preferred_provider",
+    "session_preferred_providers",
+    "route_mode",
+    "auto_return_to_preferred",
+    "preferred_stable_seconds",
+    "failure_threshold",
+    "cooldown_seconds",
+    "request_timeout_seconds",
+    "session_sticky_ttl_seconds",
+    "usage_label",
+    "max_request_body_bytes",
+    "allowed_peer_ips",
+    "connect_timeout_seconds",
+    "read_timeout_seconds",
+    "otel_endpoint",
+    "key_preview_tail",
+    "default_base_url",
+    "default_usage_adapter",
+    "active_window_seconds",
+    "session_active_window_seconds",
+    "read_only",
+    "spike_sigma",
+    "ambiguous_local_time",
+    "fractional_percent_display",
+    "anthropic_compat",
+    "cors_allowed_origins",
+    "max_store_bytes",
+    "evict_usage_requests",
+    "event_query_default_limit",
+    "event_query_max_limit",
+    "keepalive_warm_seconds",
+];
+const EVENTS_KEYS: &[&str] = &[
+    "retention_days",
+    "extended_retention_days",
+    "keep_levels",
+    "keep_providers",
+];
+const PROVIDER_KEYS: &[&str] = &[
+    "display_name",
+    "base_url",
+    "group",
+    "disabled",
+    "routing_eligible",
+    "tier",
+    "supports_websockets",
+    "usage_adapter",
+    "quota_kind",
+    "usage_base_url",
+    "quota_base_url",
+    "api_key",
+    "model_aliases",
+    "system_prefix",
+    "default_model",
+    "usage_field_map",
+    "headers",
+    "notes",
+];
+const KNOWN_USAGE_ADAPTERS: &[&str] = &["", "none", "token_stats", "budget_info", "balance_info"];
+
+pub const CONFIG_ERRORS_FILE_NAME: &str = "config.errors.json";
+
+#[derive(Debug, Clone, Copy, Serialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ConfigDiagnosticSeverity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug, Clone, Serialize, PartialEq, Eq)]
+pub struct ConfigDiagnostic {
+    pub severity: ConfigDiagnosticSeverity,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub provider: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub field: Option<String>,
+    pub message: String,
+    /// 1-based line in `config.toml`, when it could be located.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub line: Option<usize>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub column: Option<usize>,
+}
+
+impl ConfigDiagnostic {
+    pub fn is_error(&self) -> bool {
+        self.severity == ConfigDiagnosticSeverity::Error
+    }
+
+    pub fn describe(&self) -> String {
+        let mut location = Vec::new();
+        if let Some(provider) = self.provider.as_deref() {
+            location.push(format!("provider {provider}"));
+        }
+        if let Some(field) = self.field.as_deref() {
+            location.push(format!("field {field}"));
+        }
+        if let Some(line) = self.line {
+            location.push(format!("line {line}"));
+        }
+        if location.is_empty() {
+            self.message.clone()
+        } else {
+            format!("{} ({})", self.message, location.join(", "))
+        }
+    }
+}
+
+/// Parses `config.toml` text and reports structured diagnostics.
+///
+/// Returns the parsed config only when there are no error-level diagnostics.
+pub fn parse_and_validate_config(txt: &str) -> (Option<AppConfig>, Vec<ConfigDiagnostic>) {
+    let mut diagnostics = Vec::new();
+    let cfg = match toml::from_str::<AppConfig>(txt) {
+        Ok(cfg) => cfg,
+        Err(err) => {
+            let (line, column) = err
+                .span()
+                .map(|span| line_and_column(txt, span.start))
+                .unzip();
+            diagnostics.push(ConfigDiagnostic {
+                severity: ConfigDiagnosticSeverity::Error,
+                provider: None,
+                field: None,
+                message: err.message().trim().to_string(),
+                line,
+                column,
+            });
+            return (None, diagnostics);
+        }
+    };
+
+    if let Ok(raw) = toml::from_str::<toml::Table>(txt) {
+        collect_unknown_keys(txt, &raw, &mut diagnostics);
+    }
+
+    let mut seen_names: std::collections::HashMap<String, &str> = std::collections::HashMap::new();
+    for (name, provider) in &cfg.providers {
+        let normalized = name.trim().to_ascii_lowercase();
+        if let Some(existing) = seen_names.insert(normalized, name.as_str()) {
+            diagnostics.push(ConfigDiagnostic {
+                severity: ConfigDiagnosticSeverity::Warning,
+                provider: Some(name.clone()),
+                field: None,
+                message: format!("duplicate provider name (conflicts with {existing})"),
+                line: locate_line(txt, Some(name), None),
+                column: None,
+            });
+        }
+        if let Some(message) = invalid_url_message(&provider.base_url) {
+            diagnostics.push(ConfigDiagnostic {
+                severity: ConfigDiagnosticSeverity::Error,
+                provider: Some(name.clone()),
+                field: Some("base_url".to_string()),
+                message: format!("invalid base_url {:?}: {message}", provider.base_url),
+                line: locate_line(txt, Some(name), Some("base_url")),
+                column: None,
+            });
+        }
+        if let Some(usage_base_url) = provider.usage_base_url.as_deref() {
+            if let Some(message) = invalid_url_message(usage_base_url) {
+                diagnostics.push(ConfigDiagnostic {
+                    severity: ConfigDiagnosticSeverity::Error,
+                    provider: Some(name.clone()),
+                    field: Some("usage_base_url".to_string()),
+                    message: format!("invalid usage_base_url {usage_base_url:?}: {message}"),
+                    line: locate_line(txt, Some(name), Some("usage_base_url"))
+                        .or_else(|| locate_line(txt, Some(name), Some("quota_base_url"))),
+                    column: None,
+                });
+            }
+        }
+        // Unknown adapters used to be ignored silently, so existing configs keep loading.
+        if !is_known_usage_adapter(&provider.usage_adapter) {
+            diagnostics.push(ConfigDiagnostic {
+                severity: ConfigDiagnosticSeverity::Warning,
+                provider: Some(name.clone()),
+                field: Some("usage_adapter".to_string()),
+                message: format!(
+                    "{} (usage polling disabled)",
+                    unknown_usage_adapter_message("usage_adapter", &provider.usage_adapter)
+                ),
+                line: locate_line(txt, Some(name), Some("usage_adapter"))
+                    .or_else(|| locate_line(txt, Some(name), Some("quota_kind"))),
+                column: None,
+            });
+        }
+        if let Some(field_map) = provider.usage_field_map.as_ref() {
+            for (key, pointer) in [
+                ("reset_at_pointer", field_map.reset_at_pointer()),
+                ("used_percent_pointer", field_map.used_percent_pointer()),
+            ] {
+                if let Some(pointer) = pointer.filter(|pointer| !pointer.starts_with('/')) {
+                    diagnostics.push(ConfigDiagnostic {
+                        severity: ConfigDiagnosticSeverity::Error,
+                        provider: Some(name.clone()),
+                        field: Some(format!("usage_field_map.{key}")),
+                        message: format!(
+                            "invalid usage_field_map.{key} {pointer:?} (expected a JSON Pointer starting with \"/\")"
+                        ),
+                        line: locate_line(txt, Some(name), Some(key))
+                            .or_else(|| locate_line(txt, Some(name), Some("usage_field_map"))),
+                        column: None,
+                    });
+                }
+            }
+        }
+    }
+
+    for rule in &cfg.routing.allowed_peer_ips {
+        if let Err(message) = super::peer_allowlist::parse_peer_ip_rule(rule) {
+            diagnostics.push(ConfigDiagnostic {
+                severity: ConfigDiagnosticSeverity::Error,
+                provider: None,
+                field: Some("allowed_peer_ips".to_string()),
+                message: format!("invalid allowed_peer_ips entry {rule:?}: {message}"),
+                line: locate_section_key_line(txt, "routing", "allowed_peer_ips"),
+                column: None,
+            });
+        }
+    }
+
+    if let Some(endpoint) = cfg.routing.otel_endpoint.as_deref() {
+        if let Some(message) = invalid_url_message(endpoint) {
+            diagnostics.push(ConfigDiagnostic {
+                severity: ConfigDiagnosticSeverity::Error,
+                provider: None,
+                field: Some("otel_endpoint".to_string()),
+                message: format!("invalid otel_endpoint {endpoint:?}: {message}"),
+                line: locate_section_key_line(txt, "routing", "otel_endpoint"),
+                column: None,
+            });
+        }
+    }
+
+    if let Some(base_url) = cfg.routing.default_base_url.as_deref() {
+        if let Some(message) = invalid_url_message(base_url) {
+            diagnostics.push(ConfigDiagnostic {
+                severity: ConfigDiagnosticSeverity::Error,
+                provider: None,
+                field: Some("default_base_url".to_string()),
+                message: format!("invalid default_base_url {base_url:?}: {message}"),
+                line: locate_section_key_line(txt, "routing", "default_base_url"),
+                column: None,
+            });
+        }
+    }
+    if let Some(adapter) = cfg.routing.default_usage_adapter.as_deref() {
+        if !is_known_usage_adapter(adapter) {
+            diagnostics.push(ConfigDiagnostic {
+                severity: ConfigDiagnosticSeverity::Error,
+                provider: None,
+                field: Some("default_usage_adapter".to_string()),
+                message: unknown_usage_adapter_message("default_usage_adapter", adapter),
+                line: locate_section_key_line(txt, "routing", "default_usage_adapter"),
+                column: None,
+            });
+        }
+    }
+
+    for (name, template) in &cfg.provider_templates {
+        let section = format!("provider_templates.{name}");
+        for (key, url) in [
+            ("base_url", Some(template.base_url.as_str())),
+            ("usage_base_url", template.usage_base_url.as_deref()),
+        ] {
+            if let Some(message) = url.and_then(invalid_url_message) {
+                diagnostics.push(ConfigDiagnostic {
+                    severity: ConfigDiagnosticSeverity::Error,
+                    provider: None,
+                    field: Some(format!("{section}.{key}")),
+                    message: format!(
+                        "invalid {key} {:?} in template {name}: {message}",
+                        url.unwrap_or_default()
+                    ),
+                    line: locate_section_key_line(txt, &section, key),
+                    column: None,
+                });
+            }
+        }
+        if !is_known_usage_adapter(&template.usage_adapter) {
+            diagnostics.push(ConfigDiagnostic {
+                severity: ConfigDiagnosticSeverity::Error,
+                provider: None,
+                field: Some(format!("{section}.usage_adapter")),
+                message: unknown_usage_adapter_message("usage_adapter", &template.usage_adapter),
+                line: locate_section_key_line(txt, &section, "usage_adapter"),
+                column: None,
+            });
+        }
+    }
+
+    if diagnostics.iter().any(ConfigDiagnostic::is_error) {
+        return (None, diagnostics);
+    }
+    (Some(cfg), diagnostics)
+}
+
+/// Writes error diagnostics to `config.errors.json` next to the config, or removes a stale
+/// report when the config is valid.
+pub fn write_config_errors_report(
+    config_path: &std::path::Path,
+    diagnostics: &[ConfigDiagnostic],
+) -> std::io::Result<()> {
+    let report_path = config_path
+        .parent()
+        .unwrap_or(std::path::Path::new("."))
+        .join(CONFIG_ERRORS_FILE_NAME);
+    let (errors, warnings): (Vec<_>, Vec<_>) = diagnostics
+        .iter()
+        .partition(|diagnostic| diagnostic.is_error());
+    if errors.is_empty() {
+        return match std::fs::remove_file(&report_path) {
+            Err(err) if err.kind() != std::io::ErrorKind::NotFound => Err(err),
+            _ => Ok(()),
+        };
+    }
+    let report = serde_json::json!({
+        "config_path": config_path.display().to_string(),
+        "generated_at_unix_ms": super::store::unix_ms(),
+        "errors": errors,
+        "warnings": warnings,
+    });
+    std::fs::write(
+        report_path,
+        serde_json::to_vec_pretty(&report).unwrap_or_default(),
+    )
+}
+
+fn is_known_usage_adapter(adapter: &str) -> bool {
+    KNOWN_USAGE_ADAPTERS.contains(&adapter.trim().to_ascii_lowercase().as_str())
+}
+
+fn unknown_usage_adapter_message(key: &str, adapter: &str) -> String {
+    format!("unknown {key} {adapter:?} (expected one of: token_stats, budget_info, balance_info)")
+}
+
+fn invalid_url_message(value: &str) -> Option<String> {
+    let trimmed = value.trim();
+    if trimmed.is_empty() {
+        return None;
+    }
+    match reqwest::Url::parse(trimmed) {
+        Ok(url) if matches!(url.scheme(), "http" | "https") && url.host_str().is_some() => None,
+        Ok(url) if !matches!(url.scheme(), "http" | "https") => {
+            Some(format!("unsupported scheme {:?}", url.scheme()))
+        }
+        Ok(_) => Some("missing host".to_string()),
+        Err(err) => Some(err.to_string()),
+    }
+}
+
+fn collect_unknown_keys(txt: &str, raw: &toml::Table, diagnostics: &mut Vec<ConfigDiagnostic>) {
+    let mut warn = |provider: Option<&str>, section: &str, key: &str| {
+        diagnostics.push(ConfigDiagnostic {
+            severity: ConfigDiagnosticSeverity::Warning,
+            provider: provider.map(str::to_string),
+            field: Some(key.to_string()),
+            message: if section.is_empty() {
+                format!("unknown top-level key {key:?} (ignored)")
+            } else {
+                format!("unknown key {key:?} in [{section}] (ignored)")
+            },
+            line: match provider {
+                Some(name) => locate_line(txt, Some(name), Some(key)),
+                None => locate_section_key_line(txt, section, key),
+            },
+            column: None,
+        });
+    };
+    for key in raw.keys() {
+        if !TOP_LEVEL_KEYS.contains(&key.as_str()) {
+            warn(None, "", key);
+        }
+    }
+    for (section, known) in [
+        ("listen", LISTEN_KEYS),
+        ("routing", ROUTING_KEYS),
+        ("events", EVENTS_KEYS),
+    ] {
+        if let Some(table) = raw.get(section).and_then(toml::Value::as_table) {
+            for key in table.keys() {
+                if !known.contains(&key.as_str()) {
+                    warn(None, section, key);
+                }
+            }
+        }
+    }
+    if let Some(providers) = raw.get("providers").and_then(toml::Value::as_table) {
+        for (name, provider) in providers {
+            let Some(table) = provider.as_table() else {
+                continue;
+            };
+            for key in table.keys() {
+                if !PROVIDER_KEYS.contains(&key.as_str()) {
+                    warn(Some(name.as_str()), &format!("providers.{name}"), key);
+                }
+            }
+        }
+    }
+}
+
+fn line_and_column(txt: &str, offset: usize) -> (usize, usize) {
+    let prefix = &txt[..offset.min(txt.len())];
+    let line = prefix.matches('\n').count() + 1;
+    let column = prefix
+        .rsplit('\n')
+        .next()
+        .map(|tail| tail.chars().count() + 1)
+        .unwrap_or(1);
+    (line, column)
+}
+
+fn table_header(line: &str) -> Option<String> {
+    let trimmed = line.trim();
+    let inner = trimmed.strip_prefix('[')?.strip_suffix(']')?;
+    Some(
+        inner
+            .trim_matches(|c| c == '[' || c == ']')
+            .split('.')
+            .map(|part| part.trim().trim_matches('"'))
+            .collect::<Vec<_>>()
+            .join("."),
+    )
+}
+
+fn line_assigns_key(line: &str, key: &str) -> bool {
+    let trimmed = line.trim_start();
+    let Some(rest) = trimmed
+        .strip_prefix(key)
+        .or_else(|| trimmed.strip_prefix(&format!("\"{key}\"")))
+    else {
+        return false;
+    };
+    rest.trim_start().starts_with('=')
+}
+
+fn locate_section_key_line(txt: &str, section: &str, key: &str) -> Option<usize> {
+    let mut current = String::new();
+    for (idx, line) in txt.lines().enumerate() {
+        if let Some(header) = table_header(line) {
+            current = header;
+            continue;
+        }
+        if current == section && line_assigns_key(line, key) {
+            return Some(idx + 1);
+        }
+    }
+    None
+}
+
+/// Finds the provider table header line, or the line assigning `field` inside it.
+fn locate_line(txt: &str, provider: Option<&str>, field: Option<&str>) -> Option<usize> {
+    let section = format!("providers.{}", provider?);
+    let mut current = String::new();
+    for (idx, line) in txt.lines().enumerate() {
+        if let Some(header) = table_header(line) {
+            current = header;
+            if field.is_none() && current == section {
+                return Some(idx + 1);
+            }
+            continue;
+        }
+        if let Some(field) = field {
+            if current == section && line_assigns_key(line, field) {
+                return Some(idx + 1);
+            }
+        }
+    }
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_and_validate_config, write_config_errors_report, ConfigDiagnosticSeverity};
+
+    const VALID: &str = r#"
+[listen]
+host = "127.0.0.1"
+port = 4000
+
+[routing]
+preferred_provider = "p1"
+auto_return_to_preferred = true
+preferred_stable_seconds = 30
+failure_threshold = 2
+cooldown_seconds = 600
+request_timeout_seconds = 300
+
+[providers.p1]
+display_name = "P1"
+base_url = "https://example.com/v1"
+"#;
+
+    #[test]
+    fn reports_invalid_base_url_with_provider_field_and_line() {
+        let txt = VALID.replace("https://example.com/v1", "htps//example.com");
+        let (cfg, diagnostics) = parse_and_validate_config(&txt);
+        assert!(cfg.is_none());
+        let error = diagnostics
+            .iter()
+            .find(|d| d.severity == ConfigDiagnosticSeverity::Error)
+            .expect("error diagnostic");
+        assert_eq!(error.provider.as_deref(), Some("p1"));
+        assert_eq!(error.field.as_deref(), Some("base_url"));
+        assert_eq!(error.line, Some(16));
+    }
+
+    #[test]
+    fn reports_unknown_usage_adapter_and_parse_errors() {
+        let txt = format!("{VALID}usage_adapter = \"mystery\"\n");
+        let (cfg, diagnostics) = parse_and_validate_config(&txt);
+        assert!(cfg.is_some(), "unknown adapters must not block startup");
+        assert!(diagnostics.iter().any(|d| {
+            d.severity == ConfigDiagnosticSeverity::Warning
+                && d.field.as_deref() == Some("usage_adapter")
+                && d.line == Some(17)
+        }));
+
+        let (cfg, diagnostics) = parse_and_validate_config(&format!("{VALID}[providers.p1]\n"));
+        assert!(cfg.is_none());
+        assert!(diagnostics[0].line.is_some());
+    }
+
+    #[test]
+    fn case_insensitive_duplicate_provider_names_are_warnings() {
+        let txt = format!("{VALID}\n[providers.P1]\nbase_url = \"https://example.org/v1\"\n");
+        let (cfg, diagnostics) = parse_and_validate_config(&txt);
+        assert_eq!(cfg.expect("config still loads").providers.len(), 2);
+        assert!(diagnostics.iter().any(|d| {
+            d.severity == ConfigDiagnosticSeverity::Warning
+                && d.message.starts_with("duplicate provider name")
+        }));
+    }
+
+    #[test]
+    fn usage_field_map_requires_json_pointers() {
+        let ok = format!(
+            "{VALID}usage_field_map = {{ reset_at_pointer = \"/limits/reset\", used_percent_pointer = \"/used\" }}\n"
+        );
+        let (cfg, diagnostics) = parse_and_validate_config(&ok);
+        assert!(diagnostics.is_empty());
+        let map = cfg.expect("config").providers["p1"]
+            .usage_field_map
+            .clone()
+            .expect("usage_field_map");
+        assert_eq!(map.reset_at_pointer(), Some("/limits/reset"));
+        assert_eq!(map.used_percent_pointer(), Some("/used"));
+
+        let bad = format!("{VALID}usage_field_map = {{ reset_at_pointer = \"limits.reset\" }}\n");
+        let (cfg, diagnostics) = parse_and_validate_config(&bad);
+        assert!(cfg.is_none());
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.field.as_deref() == Some("usage_field_map.reset_at_pointer")));
+    }
+
+    #[test]
+    fn provider_templates_and_routing_defaults_are_validated() {
+        let ok = format!(
+            "{VALID}[provider_templates.relay]\nbase_url = \"https://relay.example.com/v1\"\nusage_adapter = \"budget_info\"\nheaders = {{ X-Team = \"router\" }}\n"
+        );
+        let (cfg, diagnostics) = parse_and_validate_config(&ok);
+        assert!(diagnostics.is_empty());
+        let template = cfg.expect("config").provider_templates["relay"].clone();
+        assert_eq!(template.usage_adapter, "budget_info");
+        assert_eq!(template.headers["X-Team"], "router");
+
+        let bad = ok.replace("https://relay.example.com/v1", "relay.example.com");
+        let (cfg, diagnostics) = parse_and_validate_config(&bad);
+        assert!(cfg.is_none());
+        assert!(diagnostics.iter().any(|d| {
+            d.field.as_deref() == Some("provider_templates.relay.base_url") && d.line == Some(18)
+        }));
+
+        let bad_default = VALID.replace(
+            "request_timeout_seconds = 300",
+            "request_timeout_seconds = 300\ndefault_usage_adapter = \"mystery\"",
+        );
+        let (cfg, diagnostics) = parse_and_validate_config(&bad_default);
+        assert!(cfg.is_none());
+        assert!(diagnostics
+            .iter()
+            .any(|d| d.field.as_deref() == Some("default_usage_adapter")));
+    }
+
+    #[test]
+    fn unknown_keys_are_warnings_and_config_still_loads() {
+        let txt = format!("{VALID}base_ulr = \"typo\"\n");
+        let (cfg, diagnostics) = parse_and_validate_config(&txt);
+        assert!(cfg.is_some());
+        assert_eq!(diagnostics.len(), 1);
+        assert_eq!(diagnostics[0].severity, ConfigDiagnosticSeverity::Warning);
+        assert_eq!(diagnostics[0].field.as_deref(), Some("base_ulr"));
+        assert_eq!(diagnostics[0].line, Some(17));
+    }
+
+    #[test]
+    fn writes_and_clears_errors_report_next_to_config() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let config_path = tmp.path().join("config.toml");
+        let txt = VALID.replace("https://example.com/v1", "ftp://example.com");
+        let (_, diagnostics) = parse_and_validate_config(&txt);
+        write_config_errors_report(&config_path, &diagnostics).expect("write report");
+        let report_path = tmp.path().join("config.errors.json");
+        let report: serde_json::Value =
+            serde_json::from_slice(&std::fs::read(&report_path).expect("read report"))
+                .expect("parse report");
+        assert_eq!(report["errors"][0]["field"], "base_url");
+
+        write_config_errors_report(&config_path, &[]).expect("clear report");
+        assert!(!report_path.exists());
+    }
+}
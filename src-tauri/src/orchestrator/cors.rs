@@ -0,0 +1,71 @@
+//! `routing.cors_allowed_origins`: browser origins allowed to call the gateway. Empty keeps the
+//! gateway free of CORS headers.
+
+pub const CORS_ALLOWED_METHODS: &str = "GET, POST, PATCH, DELETE, OPTIONS";
+/// Always allowed so browser tools can send the gateway token.
+const CORS_BASE_ALLOWED_HEADERS: &[&str] = &["authorization", "content-type"];
+pub const CORS_MAX_AGE_SECONDS: u64 = 600;
+
+fn normalize_origin(origin: &str) -> String {
+    origin.trim().trim_end_matches('/').to_ascii_lowercase()
+}
+
+/// `Access-Control-Allow-Origin` value for `origin`, or `None` when it is not listed.
+/// A `*` entry allows every origin and is answered with `*`.
+pub fn cors_allow_origin(allowed: &[String], origin: &str) -> Option<String> {
+    if allowed.iter().any(|entry| entry.trim() == "*") {
+        return Some("*".to_string());
+    }
+    let origin_key = normalize_origin(origin);
+    if origin_key.is_empty() {
+        return None;
+    }
+    allowed
+        .iter()
+        .any(|entry| normalize_origin(entry) == origin_key)
+        .then(|| origin.trim().to_string())
+}
+
+/// Headers the browser asked for in the preflight plus `Authorization`/`Content-Type`.
+pub fn cors_allow_headers(requested: Option<&str>) -> String {
+    let mut headers: Vec<String> = CORS_BASE_ALLOWED_HEADERS
+        .iter()
+        .map(|name| name.to_string())
+        .collect();
+    for name in requested.unwrap_or("").split(',') {
+        let name = name.trim().to_ascii_lowercase();
+        if !name.is_empty() && !headers.contains(&name) {
+            headers.push(name);
+        }
+    }
+    headers.join(", ")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_listed_origins_and_wildcard() {
+        let allowed = vec!["http://localhost:5173/".to_string()];
+        assert_eq!(
+            cors_allow_origin(&allowed, "http://LOCALHOST:5173"),
+            Some("http://LOCALHOST:5173".to_string())
+        );
+        assert_eq!(cors_allow_origin(&allowed, "http://evil.example"), None);
+        assert_eq!(cors_allow_origin(&[], "http://localhost:5173"), None);
+        assert_eq!(
+            cors_allow_origin(&["*".to_string()], "http://evil.example"),
+            Some("*".to_string())
+        );
+    }
+
+    #[test]
+    fn allow_headers_always_include_authorization() {
+        assert_eq!(cors_allow_headers(None), "authorization, content-type");
+        assert_eq!(
+            cors_allow_headers(Some("Authorization, X-Api-Key")),
+            "authorization, content-type, x-api-key"
+        );
+    }
+}
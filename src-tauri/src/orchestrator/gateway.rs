@@ -9,7 +9,7 @@ use std::sync::Arc;
 
 use axum::body::Body;
 use axum::extract::DefaultBodyLimit;
-use axum::extract::{FromRequest, FromRequestParts, Json, State};
+use axum::extract::{FromRequest, FromRequestParts, Json, Query, State};
 use axum::http::request::Parts;
 use axum::http::Request;
 use axum::http::{header, HeaderMap, StatusCode};
@@ -30,7 +30,7 @@ use super::quota::is_quota_refresh_config_gap;
 use super::router::{provider_iteration_order, select_fallback_provider, RouterState};
 use super::secrets::SecretStore;
 use super::store::{extract_response_model_option, unix_ms, Store};
-use super::upstream::{UpstreamClient, RESPONSES_ENDPOINT};
+use super::upstream::{UpstreamClient, UpstreamTimeouts, RESPONSES_ENDPOINT};
 use crate::constants::GATEWAY_MODEL_PROVIDER_ID;
 use crate::platform::windows_terminal;
 use parking_lot::Mutex;
@@ -70,6 +70,7 @@ pub struct GatewayState {
     pub last_used_by_session: Arc<RwLock<HashMap<String, LastUsedRoute>>>,
     pub usage_base_speed_cache: Arc<RwLock<HashMap<String, UsageBaseSpeedCacheEntry>>>,
     pub prev_id_support_cache: Arc<RwLock<HashMap<String, bool>>>,
+    pub provider_models_cache: Arc<RwLock<HashMap<String, ProviderModelCatalog>>>,
     pub client_sessions: Arc<RwLock<HashMap<String, ClientSessionRuntime>>>,
 }
 
@@ -79,6 +80,28 @@ fn runtime_bound_listener_addrs() -> &'static Mutex<HashSet<SocketAddr>> {
     RUNTIME_BOUND_LISTENER_ADDRS.get_or_init(|| Mutex::new(HashSet::new()))
 }
 
+static ACTIVE_UNIX_SOCKET_PATH: OnceLock<Mutex<Option<String>>> = OnceLock::new();
+
+fn active_unix_socket_slot() -> &'static Mutex<Option<String>> {
+    ACTIVE_UNIX_SOCKET_PATH.get_or_init(|| Mutex::new(None))
+}
+
+/// Unix socket the gateway is currently accepting on, if any.
+pub(crate) fn active_unix_socket_path() -> Option<String> {
+    active_unix_socket_slot().lock().clone()
+}
+
+/// TCP addresses the gateway is currently listening on, e.g. both loopback families.
+pub(crate) fn bound_listener_addrs() -> Vec<String> {
+    let mut addrs = runtime_bound_listener_addrs()
+        .lock()
+        .iter()
+        .copied()
+        .collect::<Vec<_>>();
+    addrs.sort();
+    addrs.into_iter().map(|addr| addr.to_string()).collect()
+}
+
 fn header_text(headers: &HeaderMap, name: &str) -> Option<String> {
     headers
         .get(name)
@@ -336,14 +359,103 @@ pub(crate) fn unregister_runtime_gateway_listener_bindings(addrs: &[SocketAddr])
     }
 }
 
+/// Why a request (or a displayed session) was routed to its provider. The snake_case wire names
+/// are stable: they are persisted with session routes and aggregated by `get_status`.
+///
+/// Failover is not a variant of its own: the reasons that moved traffic off its usual provider
+/// report [`RouteReason::is_failover`]. There is no model pinning, weighting or latency-based
+/// mode in the router, so no reasons exist for those.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, serde::Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum RouteReason {
+    PreferredHealthy,
+    SessionPreferred,
+    PreferredUnhealthy,
+    PreferredStabilizing,
+    ManualOverride,
+    ManualOverrideUnhealthy,
+    BalancedAuto,
+    SessionInvalidRequestFallback,
+    ModelNotAllowed,
+    NoRoutableProvider,
+}
+
+impl RouteReason {
+    const ALL: [Self; 10] = [
+        Self::PreferredHealthy,
+        Self::SessionPreferred,
+        Self::PreferredUnhealthy,
+        Self::PreferredStabilizing,
+        Self::ManualOverride,
+        Self::ManualOverrideUnhealthy,
+        Self::BalancedAuto,
+        Self::SessionInvalidRequestFallback,
+        Self::ModelNotAllowed,
+        Self::NoRoutableProvider,
+    ];
+
+    pub fn as_str(self) -> &'static str {
+        match self {
+            Self::PreferredHealthy => "preferred_healthy",
+            Self::SessionPreferred => "session_preferred",
+            Self::PreferredUnhealthy => "preferred_unhealthy",
+            Self::PreferredStabilizing => "preferred_stabilizing",
+            Self::ManualOverride => "manual_override",
+            Self::ManualOverrideUnhealthy => "manual_override_unhealthy",
+            Self::BalancedAuto => "balanced_auto",
+            Self::SessionInvalidRequestFallback => "session_invalid_request_fallback",
+            Self::ModelNotAllowed => "model_not_allowed",
+            Self::NoRoutableProvider => "no_routable_provider",
+        }
+    }
+
+    pub fn from_wire(value: &str) -> Option<Self> {
+        Self::ALL
+            .into_iter()
+            .find(|reason| reason.as_str() == value.trim())
+    }
+
+    /// True when traffic left the provider it would normally use, i.e. failover kicked in.
+    pub fn is_failover(self) -> bool {
+        matches!(
+            self,
+            Self::PreferredUnhealthy
+                | Self::PreferredStabilizing
+                | Self::ManualOverrideUnhealthy
+                | Self::SessionInvalidRequestFallback
+        )
+    }
+
+    /// True when a provider was actually selected (as opposed to a dead-end fallback).
+    pub fn is_routable(self) -> bool {
+        !matches!(self, Self::ModelNotAllowed | Self::NoRoutableProvider)
+    }
+}
+
+impl std::fmt::Display for RouteReason {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
 #[derive(Clone, Debug)]
 pub struct LastUsedRoute {
     pub provider: String,
-    pub reason: String,
+    pub reason: RouteReason,
     pub preferred: String,
     pub unix_ms: u64,
 }
 
+#[derive(Clone, Debug, Default)]
+pub struct ProviderModelCatalog {
+    // Model ids from the provider's last successful `/models` response.
+    pub models: Vec<String>,
+    // When `models` was fetched; 0 until a listing succeeds.
+    pub fetched_at_unix_ms: u64,
+    // When the last lazy refresh was started, successful or not.
+    pub attempted_at_unix_ms: u64,
+}
+
 #[derive(Clone, Debug)]
 pub struct ClientSessionRuntime {
     // The stable Codex session id. This is the canonical session identity.
@@ -372,6 +484,10 @@ pub struct ClientSessionRuntime {
     // the user edits Codex config files while Codex is running (the process keeps the old config
     // in memory, but we may no longer be able to prove it from disk).
     pub confirmed_router: bool,
+    // Label of the gateway token the session last authenticated with (primary or named).
+    pub auth_token_label: Option<String>,
+    // Executable name of the loopback peer process (e.g. `node.exe`), when resolvable.
+    pub peer_process: Option<String>,
 }
 
 const SESSION_UNSUPPORTED_MODEL_CACHE_TTL_MS: u64 = 5 * 60 * 1000;
@@ -726,15 +842,193 @@ where
     }
 }
 
+/// Rejects peers outside `routing.allowed_peer_ips` before any auth or routing runs.
+async fn enforce_peer_allowlist(
+    State(st): State<GatewayState>,
+    PeerAddr(peer): PeerAddr,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let allowed =
+        super::peer_allowlist::peer_ip_allowed(&st.cfg.read().routing.allowed_peer_ips, peer.ip());
+    if allowed {
+        return next.run(req).await;
+    }
+    if super::peer_allowlist::should_report_peer_rejection(peer.ip(), unix_ms()) {
+        let method = req.method().to_string();
+        let path = req.uri().path().to_string();
+        st.store.events().routing().peer_rejected(
+            "gateway",
+            &format!(
+                "403 {method} {path}: peer {} is not in routing.allowed_peer_ips",
+                peer.ip()
+            ),
+            json!({ "peer_ip": peer.ip().to_string(), "method": method, "path": path }),
+        );
+    }
+    (
+        StatusCode::FORBIDDEN,
+        Json(json!({"error": {"message": "peer address is not allowed", "type": "forbidden"}})),
+    )
+        .into_response()
+}
+
+/// Answers preflights and adds `Access-Control-Allow-*` headers for origins listed in
+/// `routing.cors_allowed_origins`; other requests pass through untouched.
+async fn apply_cors(State(st): State<GatewayState>, req: Request<Body>, next: Next) -> Response {
+    let allow_origin = req
+        .headers()
+        .get(header::ORIGIN)
+        .and_then(|v| v.to_str().ok())
+        .and_then(|origin| {
+            super::cors::cors_allow_origin(&st.cfg.read().routing.cors_allowed_origins, origin)
+        });
+    let Some(allow_origin) = allow_origin else {
+        return next.run(req).await;
+    };
+    let is_preflight = req.method() == axum::http::Method::OPTIONS
+        && req
+            .headers()
+            .contains_key(header::ACCESS_CONTROL_REQUEST_METHOD);
+    let allow_headers = super::cors::cors_allow_headers(
+        req.headers()
+            .get(header::ACCESS_CONTROL_REQUEST_HEADERS)
+            .and_then(|v| v.to_str().ok()),
+    );
+    let mut resp = if is_preflight {
+        StatusCode::NO_CONTENT.into_response()
+    } else {
+        next.run(req).await
+    };
+    let headers = resp.headers_mut();
+    if let Ok(value) = header::HeaderValue::from_str(&allow_origin) {
+        headers.insert(header::ACCESS_CONTROL_ALLOW_ORIGIN, value);
+    }
+    if allow_origin != "*" {
+        headers.append(header::VARY, header::HeaderValue::from_static("Origin"));
+    }
+    if is_preflight {
+        headers.insert(
+            header::ACCESS_CONTROL_ALLOW_METHODS,
+            header::HeaderValue::from_static(super::cors::CORS_ALLOWED_METHODS),
+        );
+        if let Ok(value) = header::HeaderValue::from_str(&allow_headers) {
+            headers.insert(header::ACCESS_CONTROL_ALLOW_HEADERS, value);
+        }
+        headers.insert(
+            header::ACCESS_CONTROL_MAX_AGE,
+            header::HeaderValue::from(super::cors::CORS_MAX_AGE_SECONDS),
+        );
+    }
+    resp
+}
+
+/// Exports a span per proxied request to `routing.otel_endpoint`. The trace stays open until the
+/// response body finishes so streamed usage and end-of-stream timing are captured.
+async fn trace_proxy_request(
+    State(st): State<GatewayState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let endpoint = st
+        .cfg
+        .read()
+        .routing
+        .otel_endpoint
+        .clone()
+        .filter(|endpoint| !endpoint.trim().is_empty());
+    let Some(endpoint) = endpoint else {
+        return next.run(req).await;
+    };
+    let trace = super::otel::ProxyTrace::start(&endpoint, req.method().as_str(), req.uri().path());
+    let resp = trace.clone().scope(next.run(req)).await;
+    let status = resp.status().as_u16();
+    let (parts, body) = resp.into_parts();
+    let body = super::otel::TracedBody::new(body.into_data_stream(), trace, status);
+    Response::from_parts(parts, Body::from_stream(body))
+}
+
+/// Enforces `routing.max_request_body_bytes` on the proxy routes. The body is counted as it
+/// streams in, so an oversized request is rejected without buffering it whole.
+async fn limit_proxy_request_body(
+    State(st): State<GatewayState>,
+    req: Request<Body>,
+    next: Next,
+) -> Response {
+    let limit = st.cfg.read().routing.max_request_body_bytes;
+    if limit == 0 {
+        return next.run(req).await;
+    }
+    let method = req.method().to_string();
+    let path = req.uri().path().to_string();
+    if let Some(declared) = content_length_header(req.headers()).filter(|len| *len > limit) {
+        return request_too_large_response(&st, &method, &path, limit, Some(declared));
+    }
+    let (parts, body) = req.into_parts();
+    let mut stream = body.into_data_stream();
+    let mut buf = bytes::BytesMut::new();
+    while let Some(chunk) = futures_util::StreamExt::next(&mut stream).await {
+        let chunk = match chunk {
+            Ok(chunk) => chunk,
+            Err(err) => {
+                let message = format!("failed to read request body: {err}");
+                return (
+                    StatusCode::BAD_REQUEST,
+                    Json(json!({"error": {"message": message, "type": "invalid_request_error"}})),
+                )
+                    .into_response();
+            }
+        };
+        if (buf.len() + chunk.len()) as u64 > limit {
+            return request_too_large_response(&st, &method, &path, limit, None);
+        }
+        buf.extend_from_slice(&chunk);
+    }
+    next.run(Request::from_parts(parts, Body::from(buf.freeze())))
+        .await
+}
+
+fn request_too_large_response(
+    st: &GatewayState,
+    method: &str,
+    path: &str,
+    limit: u64,
+    declared_bytes: Option<u64>,
+) -> Response {
+    st.store.events().routing().request_too_large(
+        "gateway",
+        &format!("413 {method} {path}: request body exceeds {limit} bytes"),
+        json!({
+            "method": method,
+            "path": path,
+            "max_request_body_bytes": limit,
+            "content_length": declared_bytes,
+        }),
+    );
+    let message = format!(
+        "request body exceeds the gateway limit of {limit} bytes (routing.max_request_body_bytes)"
+    );
+    (
+        StatusCode::PAYLOAD_TOO_LARGE,
+        Json(json!({"error": {"message": message, "type": "request_too_large"}})),
+    )
+        .into_response()
+}
+
 pub(crate) fn should_log_routing_path_event(
     prev: Option<&LastUsedRoute>,
     provider: &str,
-    reason: &str,
+    reason: RouteReason,
     preferred: &str,
     is_first_attempt: bool,
 ) -> bool {
     // Skip routine preferred-path success logs on first attempt.
-    if is_first_attempt && reason == "preferred_healthy" {
+    if is_first_attempt
+        && matches!(
+            reason,
+            RouteReason::PreferredHealthy | RouteReason::SessionPreferred
+        )
+    {
         return false;
     }
     match prev {
@@ -963,7 +1257,7 @@ struct NonStreamRetryRequest<'a> {
     payload: &'a Value,
     api_key: Option<&'a str>,
     client_auth: Option<&'a str>,
-    timeout: u64,
+    timeouts: UpstreamTimeouts,
 }
 
 struct RequestRetryBudget {
@@ -1305,7 +1599,7 @@ async fn post_non_stream_with_http_retry(
                 request.payload,
                 request.api_key,
                 request.client_auth,
-                request.timeout,
+                request.timeouts,
             )
             .await;
         let should_retry = match &result {
@@ -1400,6 +1694,8 @@ pub(crate) fn build_router_with_body_limit(state: GatewayState, max_body_bytes:
     let router = Router::new()
         .route("/health", get(health))
         .route("/status", get(status))
+        .route("/providers/:provider/probe", post(probe_provider_http))
+        .route("/timeseries", get(timeseries))
         .route(
             "/lan-sync/usage",
             post(crate::lan_sync::lan_sync_usage_http),
@@ -1430,8 +1726,42 @@ pub(crate) fn build_router_with_body_limit(state: GatewayState, max_body_bytes:
             post(crate::lan_sync::lan_sync_diagnostics_http),
         )
         .route("/v1/models", get(models))
-        .route("/v1/responses", post(responses))
-        .route("/responses", post(responses))
+        .route(
+            "/v1/responses",
+            post(responses)
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    limit_proxy_request_body,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    trace_proxy_request,
+                )),
+        )
+        .route(
+            "/v1/messages",
+            post(anthropic_messages)
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    limit_proxy_request_body,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    trace_proxy_request,
+                )),
+        )
+        .route(
+            "/responses",
+            post(responses)
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    limit_proxy_request_body,
+                ))
+                .layer(middleware::from_fn_with_state(
+                    state.clone(),
+                    trace_proxy_request,
+                )),
+        )
         .route("/", get(codex_app_server_ws))
         .route("/codex-web", get(codex_web_index))
         .route("/codex-web/app.js", get(codex_web_app_js))
@@ -1521,6 +1851,11 @@ pub(crate) fn build_router_with_body_limit(state: GatewayState, max_body_bytes:
         .route("/codex/rpc", post(codex_rpc_proxy))
         .layer(middleware::from_fn(trace_codex_web_http_request))
         .layer(DefaultBodyLimit::max(max_body_bytes))
+        .layer(middleware::from_fn_with_state(state.clone(), apply_cors))
+        .layer(middleware::from_fn_with_state(
+            state.clone(),
+            enforce_peer_allowlist,
+        ))
         .with_state(state);
     #[cfg(test)]
     let router = router.route("/__test/block-history", get(codex_test_block_history));
@@ -1638,6 +1973,111 @@ pub(crate) fn _set_test_web_codex_history_loader(loader: Option<TestWebCodexHist
     }));
 }
 
+struct GatewayShutdown {
+    requested: tokio::sync::watch::Sender<bool>,
+    active_listeners: AtomicU64,
+}
+
+fn gateway_shutdown() -> &'static GatewayShutdown {
+    static SHUTDOWN: OnceLock<GatewayShutdown> = OnceLock::new();
+    SHUTDOWN.get_or_init(|| GatewayShutdown {
+        requested: tokio::sync::watch::channel(false).0,
+        active_listeners: AtomicU64::new(0),
+    })
+}
+
+async fn gateway_shutdown_requested() {
+    let mut rx = gateway_shutdown().requested.subscribe();
+    let _ = rx.wait_for(|requested| *requested).await;
+}
+
+/// Serves one listener until the gateway shutdown signal fires; axum then stops accepting and
+/// drains the connections it already has.
+async fn serve_listener_until_shutdown(
+    listener: tokio::net::TcpListener,
+    app: Router,
+) -> std::io::Result<()> {
+    let shutdown = gateway_shutdown();
+    shutdown.active_listeners.fetch_add(1, Ordering::SeqCst);
+    let result = axum::serve(
+        listener,
+        app.into_make_service_with_connect_info::<SocketAddr>(),
+    )
+    .with_graceful_shutdown(gateway_shutdown_requested())
+    .await;
+    shutdown.active_listeners.fetch_sub(1, Ordering::SeqCst);
+    result
+}
+
+/// Unix-socket counterpart of `serve_listener_until_shutdown`. axum 0.7 only serves TCP, so
+/// connections are driven through hyper directly; peers resolve to loopback in `PeerAddr`.
+#[cfg(unix)]
+async fn serve_unix_listener_until_shutdown(
+    listener: tokio::net::UnixListener,
+    app: Router,
+    path: PathBuf,
+) -> std::io::Result<()> {
+    let shutdown = gateway_shutdown();
+    shutdown.active_listeners.fetch_add(1, Ordering::SeqCst);
+    *active_unix_socket_slot().lock() = Some(path.display().to_string());
+    let mut connections = tokio::task::JoinSet::new();
+    let result = loop {
+        tokio::select! {
+            accepted = listener.accept() => {
+                let stream = match accepted {
+                    Ok((stream, _)) => stream,
+                    Err(err) => break Err(err),
+                };
+                let service = hyper_util::service::TowerToHyperService::new(app.clone());
+                connections.spawn(async move {
+                    let _ = hyper::server::conn::http1::Builder::new()
+                        .serve_connection(hyper_util::rt::TokioIo::new(stream), service)
+                        .with_upgrades()
+                        .await;
+                });
+            }
+            _ = gateway_shutdown_requested() => break Ok(()),
+        }
+    };
+    drop(listener);
+    while connections.join_next().await.is_some() {}
+    *active_unix_socket_slot().lock() = None;
+    let _ = std::fs::remove_file(&path);
+    shutdown.active_listeners.fetch_sub(1, Ordering::SeqCst);
+    result
+}
+
+/// Stops accepting new connections, waits up to `timeout` for in-flight requests to finish,
+/// then flushes the store. Returns whether every listener drained before the deadline.
+pub async fn shutdown_gracefully(state: &GatewayState, timeout: std::time::Duration) -> bool {
+    let started = std::time::Instant::now();
+    let shutdown = gateway_shutdown();
+    shutdown.requested.send_replace(true);
+    let drained = tokio::time::timeout(timeout, async {
+        while shutdown.active_listeners.load(Ordering::SeqCst) > 0 {
+            tokio::time::sleep(std::time::Duration::from_millis(50)).await;
+        }
+    })
+    .await
+    .is_ok();
+    state.store.events().emit(
+        "gateway",
+        crate::orchestrator::store::EventCode::GATEWAY_SHUTDOWN,
+        if drained {
+            "gateway stopped after draining in-flight requests"
+        } else {
+            "gateway stopped before in-flight requests drained"
+        },
+        json!({
+            "drained": drained,
+            "elapsed_ms": started.elapsed().as_millis() as u64,
+            "remaining_listeners": shutdown.active_listeners.load(Ordering::SeqCst),
+        }),
+    );
+    let _ = state.store.flush_to_disk();
+    drained
+}
+
 pub async fn serve_in_background(
     state: GatewayState,
     prepared: crate::orchestrator::gateway_bootstrap::PreparedGatewayListeners,
@@ -1655,20 +2095,48 @@ pub async fn serve_in_background(
     }
     write_gateway_startup_diag("binding", diag_addr, Some(&diag_binding));
 
-    let app = build_router(state);
+    let app = build_router(state.clone());
     write_gateway_startup_diag("listening", diag_addr, Some(&diag_binding));
+    let cors_allowed_origins = state.cfg.read().routing.cors_allowed_origins.clone();
+    if !cors_allowed_origins.is_empty() {
+        state.store.events().emit(
+            "gateway",
+            crate::orchestrator::store::EventCode::GATEWAY_CORS_ENABLED,
+            &format!("CORS enabled for {}", cors_allowed_origins.join(", ")),
+            json!({ "origins": cors_allowed_origins }),
+        );
+    }
+    #[cfg(unix)]
+    if let Some((path, listener)) = prepared.unix_listener {
+        let listener = tokio::net::UnixListener::from_std(listener)?;
+        let app_for_socket = app.clone();
+        tauri::async_runtime::spawn(async move {
+            if let Err(err) =
+                serve_unix_listener_until_shutdown(listener, app_for_socket, path.clone()).await
+            {
+                write_gateway_startup_diag(
+                    "unix_socket_serve_failed",
+                    None,
+                    Some(&format!("path={}; {err}", path.display())),
+                );
+                state.store.events().emit(
+                    "gateway",
+                    crate::orchestrator::store::EventCode::GATEWAY_RUNTIME_LISTENER_FAILED,
+                    &format!("unix socket listener exited on {}: {err}", path.display()),
+                    json!({ "unix_socket_path": path.display().to_string() }),
+                );
+            }
+        });
+    }
     web_codex_threads::spawn_thread_index_prewarm();
     let mut servers = tokio::task::JoinSet::new();
     for (addr, listener) in prepared.listeners {
         let listener = tokio::net::TcpListener::from_std(listener)?;
         let app_for_addr = app.clone();
         servers.spawn(async move {
-            axum::serve(
-                listener,
-                app_for_addr.into_make_service_with_connect_info::<SocketAddr>(),
-            )
-            .await
-            .map_err(|err| (addr, err))
+            serve_listener_until_shutdown(listener, app_for_addr)
+                .await
+                .map_err(|err| (addr, err))
         });
     }
     while let Some(result) = servers.join_next().await {
@@ -1736,11 +2204,7 @@ pub(crate) fn ensure_runtime_gateway_listener_bindings(
         let app_for_addr = app.clone();
         let state_for_addr = state.clone();
         tauri::async_runtime::spawn(async move {
-            let result = axum::serve(
-                listener,
-                app_for_addr.into_make_service_with_connect_info::<SocketAddr>(),
-            )
-            .await;
+            let result = serve_listener_until_shutdown(listener, app_for_addr).await;
             runtime_bound_listener_addrs().lock().remove(&addr);
             if let Err(err) = result {
                 write_gateway_startup_diag(
@@ -1787,9 +2251,10 @@ async fn responses(
     headers: HeaderMap,
     LoggedJson(body): LoggedJson<Value>,
 ) -> Response {
-    if let Some(resp) = require_gateway_auth(&st, &headers) {
-        return resp;
-    }
+    let auth_token_label = match require_gateway_auth(&st, &headers) {
+        Ok(label) => label,
+        Err(resp) => return resp,
+    };
     st.last_activity_unix_ms.store(unix_ms(), Ordering::Relaxed);
     let cfg = st.cfg.read().clone();
     let client_auth = headers
@@ -1863,6 +2328,18 @@ async fn responses(
     if !session_key.starts_with("peer:") {
         let now_unix_ms = unix_ms();
         let is_review_session = review_request;
+        // Resolve outside the session lock; the lookup is cached per PID.
+        let peer_process = (!is_review_session)
+            .then(|| {
+                client_session.as_ref().map(|s| s.pid).or_else(|| {
+                    crate::platform::windows_loopback_peer::infer_loopback_peer_pid(
+                        peer,
+                        cfg.listen.port,
+                    )
+                })
+            })
+            .flatten()
+            .and_then(crate::platform::windows_loopback_peer::cached_process_image_name);
         let mut map = st.client_sessions.write();
         let previous_entry = map.get(&session_key).cloned();
         let entry = map
@@ -1896,7 +2373,12 @@ async fn responses(
                 is_agent: agent_request || client_session.as_ref().is_some_and(|s| s.is_agent),
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             });
+        if peer_process.is_some() {
+            entry.peer_process = peer_process;
+        }
         if is_review_session {
             entry.pid = 0;
             entry.wt_session = None;
@@ -1946,6 +2428,9 @@ async fn responses(
         if let Some(base_url) = request_base_url.as_deref() {
             entry.last_reported_base_url = Some(base_url.to_string());
         }
+        if let Some(label) = auth_token_label.as_deref() {
+            entry.auth_token_label = Some(label.to_string());
+        }
         // Keep codex provider deterministic once the session is proven to route through gateway.
         entry.last_reported_model_provider = Some(GATEWAY_MODEL_PROVIDER_ID.to_string());
         entry.last_request_unix_ms = now_unix_ms;
@@ -1982,6 +2467,8 @@ async fn responses(
                             is_agent: false,
                             is_review: false,
                             confirmed_router: false,
+                            auth_token_label: None,
+                            peer_process: None,
                         });
                 if let Some(inferred) = client_session.as_ref() {
                     if inferred.pid != 0 {
@@ -2025,6 +2512,16 @@ async fn responses(
         );
     }
 
+    // Without a client model only providers with a default_model can serve the request.
+    if requested_model.is_none()
+        && !cfg
+            .providers
+            .values()
+            .any(|p| !p.disabled && p.routing_eligible && provider_default_model(p).is_some())
+    {
+        return missing_model_response("request has no model and no provider has a default_model");
+    }
+
     // Try providers in order: chosen, then fallbacks.
     let mut tried = Vec::new();
     let mut last_err = String::new();
@@ -2033,6 +2530,11 @@ async fn responses(
     let mut retry_budget = GatewayRetryBudget::new(notification, TRANSIENT_UPSTREAM_RETRY_ATTEMPTS);
     let gateway_owns_thread_status = retry_budget.emits_thread_status_notifications();
     let mut terminal_status_sent = false;
+    let mut default_model_event_sent = false;
+    // Whether a provider able to default the missing model was reached. If none was, the
+    // request itself is at fault and gets a 400 rather than an outage error.
+    let mut default_model_provider_reached = false;
+    let mut skipped_without_default_model = Vec::new();
 
     let mut session_messages: Option<Vec<Value>> = None;
     let provider_iteration_limit = cfg.providers.len().max(1) + TRANSIENT_UPSTREAM_RETRY_ATTEMPTS;
@@ -2045,13 +2547,10 @@ async fn responses(
             unix_ms(),
         );
         let is_first_attempt = tried.is_empty();
-        let preferred = cfg
-            .routing
-            .session_preferred_providers
-            .get(&session_key)
-            .filter(|p| cfg.providers.contains_key(*p))
-            .map(|s| s.as_str())
-            .unwrap_or(cfg.routing.preferred_provider.as_str());
+        let preferred = session_preferred_provider(&st, &cfg, &session_key, |name| {
+            tried.iter().any(|tried_name| tried_name == name)
+                || request_unsupported_providers.contains(name)
+        });
         let (mut provider_name, mut reason) = decide_provider(&st, &cfg, preferred, &session_key);
         if tried.contains(&provider_name) || request_unsupported_providers.contains(&provider_name)
         {
@@ -2079,7 +2578,7 @@ async fn responses(
                 )
             {
                 provider_name = picked;
-                reason = "session_invalid_request_fallback";
+                reason = RouteReason::SessionInvalidRequestFallback;
             } else if request_unsupported_providers.contains(&provider_name) {
                 let unsupported_message = unsupported_model_message(
                     requested_model.as_deref(),
@@ -2102,7 +2601,11 @@ async fn responses(
                 break;
             }
         }
-        if reason == "no_routable_provider" {
+        if reason == RouteReason::NoRoutableProvider {
+            if requested_model.is_none() && !default_model_provider_reached {
+                // Retrying cannot help: no routable provider can supply the model.
+                break;
+            }
             last_err = format!(
                 "no routable providers available; preferred={preferred}; tried={}",
                 tried.join(",")
@@ -2134,6 +2637,30 @@ async fn responses(
             }
             break;
         }
+        if is_first_attempt {
+            let now_ms = unix_ms();
+            let expired_route = st
+                .last_used_by_session
+                .read()
+                .get(&session_key)
+                .filter(|route| session_route_expired(&cfg, route, now_ms))
+                .cloned();
+            if let Some(route) = expired_route.filter(|route| route.provider != provider_name) {
+                st.store.events().routing().session_reevaluated(
+                    &provider_name,
+                    &format!(
+                        "session idle past sticky TTL; re-routed from {} to {provider_name}",
+                        route.provider
+                    ),
+                    json!({
+                        "session_id": session_key,
+                        "previous_provider": route.provider,
+                        "idle_ms": now_ms.saturating_sub(route.unix_ms),
+                        "reason": reason,
+                    }),
+                );
+            }
+        }
         if tried.contains(&provider_name) {
             break;
         }
@@ -2142,13 +2669,26 @@ async fn responses(
             Some(p) => p.clone(),
             None => break,
         };
+        if requested_model.is_none() {
+            if provider_default_model(&p).is_none() {
+                // Another candidate has a default_model; fail over to it. The next provider
+                // is not the first attempt, so a `previous_response_id` chain is not sent to
+                // it: the conversation is replayed from the Codex session history instead,
+                // as on any provider switch, and `default_model_applied` records the skip.
+                last_err = format!("provider '{provider_name}' has no default_model");
+                skipped_without_default_model.push(provider_name.clone());
+                continue;
+            }
+            default_model_provider_reached = true;
+        }
+        super::otel::note_provider_attempt(&provider_name);
         let mut provider_supports_prev = st
             .prev_id_support_cache
             .read()
             .get(&provider_name)
             .cloned()
             .unwrap_or(true);
-        let timeout = cfg.routing.request_timeout_seconds;
+        let timeouts = UpstreamTimeouts::from_routing(&cfg.routing);
 
         for _ in 0..2 {
             let switching_provider = has_prev && !is_first_attempt;
@@ -2156,6 +2696,33 @@ async fn responses(
 
             let mut body_for_provider = base_body.clone();
             scrub_session_id_aliases_from_body(&mut body_for_provider);
+            let defaulted_model = apply_provider_default_model(&mut body_for_provider, &p);
+            if let Some(model) = defaulted_model.as_deref() {
+                if !default_model_event_sent {
+                    default_model_event_sent = true;
+                    st.store.events().routing().default_model_applied(
+                        &provider_name,
+                        &format!("request had no model; injected default {model}"),
+                        json!({
+                            "session_id": session_key,
+                            "model": model,
+                            "origin": request_origin,
+                            "skipped_providers": skipped_without_default_model,
+                            "previous_response_id_replayed": switching_provider,
+                        }),
+                    );
+                }
+            }
+            let effective_model = requested_model.clone().or(defaulted_model.clone());
+            // Keep usage stats on the requested (or defaulted) model; compare upstream replies
+            // against the alias.
+            let aliased_model = apply_provider_model_alias(&mut body_for_provider, &p);
+            let usage_model = if aliased_model.is_some() || defaulted_model.is_some() {
+                effective_model.clone()
+            } else {
+                None
+            };
+            let upstream_model = aliased_model.or(effective_model);
             if !use_prev_id {
                 body_for_provider
                     .as_object_mut()
@@ -2231,6 +2798,8 @@ async fn responses(
             body_for_provider
                 .as_object_mut()
                 .map(|m| m.insert("input".to_string(), input_value));
+            apply_provider_system_prefix(&mut body_for_provider, &p);
+            let attempt_started_at = std::time::Instant::now();
 
             // Stream mode (best-effort): if upstream supports Responses streaming, we pass it through
             // and tap the stream to persist the final response for continuity.
@@ -2238,6 +2807,7 @@ async fn responses(
                 body_for_provider
                     .as_object_mut()
                     .map(|m| m.insert("stream".to_string(), Value::Bool(true)));
+                let request_bytes = json_body_bytes(&body_for_provider);
                 let api_key = st.secrets.get_provider_key(&provider_name);
                 let allow_websocket_transport = p.supports_websockets && !use_prev_id;
                 let mut websocket_stream_attempted = false;
@@ -2252,24 +2822,27 @@ async fn responses(
                                 &body_for_provider,
                                 api_key.as_deref(),
                                 client_auth,
-                                timeout,
+                                timeouts,
                             )
                             .await
                         {
                             Ok(ws_stream) => {
-                                let prev =
-                                    st.last_used_by_session.read().get(&session_key).cloned();
-                                st.last_used_by_session.write().insert(
-                                    session_key.clone(),
+                                let prev = record_last_used_route(
+                                    &st,
+                                    &session_key,
                                     LastUsedRoute {
                                         provider: provider_name.clone(),
-                                        reason: reason.to_string(),
+                                        reason,
                                         preferred: preferred.to_string(),
                                         unix_ms: unix_ms(),
                                     },
                                 );
                                 let now_ms = unix_ms();
-                                st.router.mark_success(&provider_name, now_ms);
+                                st.router.mark_success_with_latency(
+                                    &provider_name,
+                                    now_ms,
+                                    Some(attempt_started_at.elapsed()),
+                                );
                                 clear_session_unsupported_model_provider(
                                     &session_key,
                                     requested_model.as_deref(),
@@ -2313,7 +2886,7 @@ async fn responses(
                                         json!({
                                             "provider": provider_name,
                                             "from_provider": prev.as_ref().map(|p| p.provider.clone()),
-                                            "from_reason": prev.as_ref().map(|p| p.reason.clone()),
+                                            "from_reason": prev.as_ref().map(|p| p.reason),
                                             "from_preferred": prev.as_ref().map(|p| p.preferred.clone()),
                                             "preferred": preferred,
                                             "transport": "ws",
@@ -2327,13 +2900,15 @@ async fn responses(
                                     ws_stream,
                                     st.clone(),
                                     provider_name,
-                                    timeout,
+                                    timeouts.read_seconds,
                                     SsePersistContext {
                                         api_key_ref: api_key_ref_from_raw(api_key.as_deref()),
                                         session_key: session_key.clone(),
-                                        requested_model: requested_model.clone(),
+                                        requested_model: upstream_model.clone(),
+                                        usage_model: usage_model.clone(),
                                         request_origin: request_origin.to_string(),
                                         transport: "ws",
+                                        request_bytes,
                                     },
                                 );
                             }
@@ -2356,23 +2931,27 @@ async fn responses(
                             &body_for_provider,
                             api_key.as_deref(),
                             client_auth,
-                            timeout,
+                            timeouts,
                         )
                         .await
                     {
                         Ok(resp) if resp.status().is_success() => {
-                            let prev = st.last_used_by_session.read().get(&session_key).cloned();
-                            st.last_used_by_session.write().insert(
-                                session_key.clone(),
+                            let prev = record_last_used_route(
+                                &st,
+                                &session_key,
                                 LastUsedRoute {
                                     provider: provider_name.clone(),
-                                    reason: reason.to_string(),
+                                    reason,
                                     preferred: preferred.to_string(),
                                     unix_ms: unix_ms(),
                                 },
                             );
                             let now_ms = unix_ms();
-                            st.router.mark_success(&provider_name, now_ms);
+                            st.router.mark_success_with_latency(
+                                &provider_name,
+                                now_ms,
+                                Some(attempt_started_at.elapsed()),
+                            );
                             clear_session_unsupported_model_provider(
                                 &session_key,
                                 requested_model.as_deref(),
@@ -2416,7 +2995,7 @@ async fn responses(
                                     json!({
                                         "provider": provider_name,
                                         "from_provider": prev.as_ref().map(|p| p.provider.clone()),
-                                        "from_reason": prev.as_ref().map(|p| p.reason.clone()),
+                                        "from_reason": prev.as_ref().map(|p| p.reason),
                                         "from_preferred": prev.as_ref().map(|p| p.preferred.clone()),
                                         "preferred": preferred,
                                         "transport": "sse",
@@ -2430,13 +3009,15 @@ async fn responses(
                                 resp,
                                 st.clone(),
                                 provider_name,
-                                timeout,
+                                timeouts.read_seconds,
                                 SsePersistContext {
                                     api_key_ref: api_key_ref_from_raw(api_key.as_deref()),
                                     session_key: session_key.clone(),
-                                    requested_model: requested_model.clone(),
+                                    requested_model: upstream_model.clone(),
+                                    usage_model: usage_model.clone(),
                                     request_origin: request_origin.to_string(),
                                     transport: "sse",
+                                    request_bytes,
                                 },
                             );
                         }
@@ -2686,6 +3267,7 @@ async fn responses(
             body_for_provider
                 .as_object_mut()
                 .map(|m| m.insert("stream".to_string(), Value::Bool(false)));
+            let request_bytes = json_body_bytes(&body_for_provider);
 
             let api_key = st.secrets.get_provider_key(&provider_name);
             let mut actual_transport = "http";
@@ -2698,7 +3280,7 @@ async fn responses(
                         &body_for_provider,
                         api_key.as_deref(),
                         client_auth,
-                        timeout,
+                        timeouts,
                     )
                     .await
                 {
@@ -2724,7 +3306,7 @@ async fn responses(
                                 payload: &body_for_provider,
                                 api_key: api_key.as_deref(),
                                 client_auth,
-                                timeout,
+                                timeouts,
                             },
                         )
                         .await
@@ -2741,7 +3323,7 @@ async fn responses(
                         payload: &body_for_provider,
                         api_key: api_key.as_deref(),
                         client_auth,
-                        timeout,
+                        timeouts,
                     },
                 )
                 .await
@@ -2749,18 +3331,22 @@ async fn responses(
 
             match upstream_result {
                 Ok((code, upstream_json)) if (200..300).contains(&code) => {
-                    let prev = st.last_used_by_session.read().get(&session_key).cloned();
-                    st.last_used_by_session.write().insert(
-                        session_key.clone(),
+                    let prev = record_last_used_route(
+                        &st,
+                        &session_key,
                         LastUsedRoute {
                             provider: provider_name.clone(),
-                            reason: reason.to_string(),
+                            reason,
                             preferred: preferred.to_string(),
                             unix_ms: unix_ms(),
                         },
                     );
                     let now_ms = unix_ms();
-                    st.router.mark_success(&provider_name, now_ms);
+                    st.router.mark_success_with_latency(
+                        &provider_name,
+                        now_ms,
+                        Some(attempt_started_at.elapsed()),
+                    );
                     clear_session_unsupported_model_provider(
                         &session_key,
                         requested_model.as_deref(),
@@ -2775,6 +3361,7 @@ async fn responses(
                         .unwrap_or("resp_unknown")
                         .to_string();
                     let text = extract_text_from_responses(&upstream_json);
+                    let response_bytes = json_body_bytes(&upstream_json);
                     let response_obj = upstream_json;
                     if let Some(response_model) = extract_response_model_option(&response_obj) {
                         update_session_response_model(&st, &session_key, &response_model);
@@ -2782,16 +3369,17 @@ async fn responses(
                             &st,
                             &provider_name,
                             &session_key,
-                            requested_model.as_deref(),
+                            upstream_model.as_deref(),
                             &response_model,
                             false,
                         );
                     }
                     let api_key_ref = api_key_ref_from_raw(api_key.as_deref());
                     let local_node = st.secrets.get_lan_node_identity();
+                    let usage_label = usage_label_for_recording(&st);
 
                     // Persist the exchange so we can keep continuity if provider changes later.
-                    st.store.record_success(
+                    st.store.record_success_with_model(
                         &provider_name,
                         &response_obj,
                         crate::orchestrator::store::UsageRequestContext {
@@ -2801,7 +3389,11 @@ async fn responses(
                             session_id: Some(session_key.as_str()),
                             node_id: local_node.as_ref().map(|value| value.node_id.as_str()),
                             node_name: local_node.as_ref().map(|value| value.node_name.as_str()),
+                            label: usage_label.as_deref(),
+                            request_bytes,
+                            response_bytes,
                         },
+                        usage_model.as_deref(),
                     );
 
                     // Avoid spamming the event log for routine successful requests; only surface
@@ -2843,7 +3435,7 @@ async fn responses(
                             json!({
                                 "provider": provider_name,
                                 "from_provider": prev.as_ref().map(|p| p.provider.clone()),
-                                "from_reason": prev.as_ref().map(|p| p.reason.clone()),
+                                "from_reason": prev.as_ref().map(|p| p.reason),
                                 "from_preferred": prev.as_ref().map(|p| p.preferred.clone()),
                                 "preferred": preferred,
                                 "transport": actual_transport,
@@ -2999,6 +3591,20 @@ async fn responses(
         return response;
     }
 
+    if requested_model.is_none() && !default_model_provider_reached {
+        let message = "request has no model and no routable provider has a default_model";
+        if gateway_owns_thread_status && !terminal_status_sent {
+            push_gateway_thread_status_notification(
+                notification,
+                "failed",
+                "gateway.invalid_request",
+                Some(message),
+            )
+            .await;
+        }
+        return missing_model_response(message);
+    }
+
     if gateway_owns_thread_status && !terminal_status_sent && !last_err.is_empty() {
         push_gateway_thread_status_notification(
             notification,
@@ -3031,3 +3637,4 @@ async fn responses(
 }
 
 include!("gateway/response_io.rs");
+include!("gateway/anthropic_compat.rs");
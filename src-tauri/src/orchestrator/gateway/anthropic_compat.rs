@@ -0,0 +1,124 @@
+fn anthropic_error_response(status: StatusCode, message: &str) -> Response {
+    (
+        status,
+        Json(super::anthropic::error_body(
+            super::anthropic::error_type_for_status(status.as_u16()),
+            message,
+        )),
+    )
+        .into_response()
+}
+
+/// `/v1/messages`: translates the Anthropic request, runs it through the regular `/v1/responses`
+/// path (routing, retries, usage recording) and translates the result back.
+async fn anthropic_messages(
+    peer: PeerAddr,
+    State(st): State<GatewayState>,
+    mut headers: HeaderMap,
+    LoggedJson(body): LoggedJson<Value>,
+) -> Response {
+    if !st.cfg.read().routing.anthropic_compat {
+        return anthropic_error_response(
+            StatusCode::NOT_FOUND,
+            "Anthropic compatibility is disabled (set routing.anthropic_compat = true)",
+        );
+    }
+    let translated = match super::anthropic::messages_request_to_responses(&body) {
+        Ok(translated) => translated,
+        Err(message) => return anthropic_error_response(StatusCode::BAD_REQUEST, &message),
+    };
+    let want_stream = translated
+        .get("stream")
+        .and_then(Value::as_bool)
+        .unwrap_or(false);
+    let requested_model = translated
+        .get("model")
+        .and_then(Value::as_str)
+        .unwrap_or_default()
+        .to_string();
+    // Anthropic SDKs send the key as `x-api-key` rather than a bearer token.
+    if !headers.contains_key(header::AUTHORIZATION) {
+        let bearer = headers
+            .get("x-api-key")
+            .and_then(|v| v.to_str().ok())
+            .and_then(|key| header::HeaderValue::from_str(&format!("Bearer {}", key.trim())).ok());
+        if let Some(bearer) = bearer {
+            headers.insert(header::AUTHORIZATION, bearer);
+        }
+    }
+
+    let resp = responses(peer, State(st), headers, LoggedJson(translated)).await;
+    let status = resp.status();
+    let is_sse = resp
+        .headers()
+        .get(header::CONTENT_TYPE)
+        .and_then(|v| v.to_str().ok())
+        .is_some_and(|v| v.starts_with("text/event-stream"));
+
+    if status.is_success() && is_sse {
+        use futures_util::StreamExt;
+
+        let mut upstream = resp.into_body().into_data_stream();
+        let stream = async_stream::stream! {
+            let mut translator = super::anthropic::MessagesSseTranslator::new(&requested_model);
+            while let Some(chunk) = upstream.next().await {
+                let Ok(chunk) = chunk else {
+                    break;
+                };
+                for event in translator.feed(&chunk) {
+                    yield Ok::<_, std::convert::Infallible>(event);
+                }
+            }
+            for event in translator.finish() {
+                yield Ok(event);
+            }
+        };
+        let mut out = Response::new(Body::from_stream(stream));
+        insert_sse_passthrough_headers(out.headers_mut());
+        return out;
+    }
+
+    let bytes = match axum::body::to_bytes(resp.into_body(), usize::MAX).await {
+        Ok(bytes) => bytes,
+        Err(e) => {
+            return anthropic_error_response(
+                StatusCode::BAD_GATEWAY,
+                &format!("failed to read upstream response: {e}"),
+            )
+        }
+    };
+    let parsed = serde_json::from_slice::<Value>(&bytes).unwrap_or(Value::Null);
+    if !status.is_success() {
+        let message = parsed
+            .pointer("/error/message")
+            .and_then(Value::as_str)
+            .map(ToString::to_string)
+            .unwrap_or_else(|| String::from_utf8_lossy(&bytes).chars().take(500).collect());
+        return anthropic_error_response(status, &message);
+    }
+    if !want_stream {
+        return (
+            StatusCode::OK,
+            Json(super::anthropic::responses_to_message(
+                &parsed,
+                &requested_model,
+            )),
+        )
+            .into_response();
+    }
+    // Upstream answered a streaming request with a plain body; replay it as Messages SSE.
+    let mut translator = super::anthropic::MessagesSseTranslator::new(&requested_model);
+    let replay = format!(
+        "data: {}\n\ndata: {}\n\ndata: {}\n\n",
+        json!({ "type": "response.created", "response": parsed }),
+        json!({ "type": "response.output_text.delta", "delta": extract_text_from_responses(&parsed) }),
+        json!({ "type": "response.completed", "response": parsed }),
+    );
+    let mut events = translator.feed(replay.as_bytes());
+    events.extend(translator.finish());
+    let stream =
+        futures_util::stream::iter(events.into_iter().map(Ok::<_, std::convert::Infallible>));
+    let mut out = Response::new(Body::from_stream(stream));
+    insert_sse_passthrough_headers(out.headers_mut());
+    out
+}
@@ -163,6 +163,143 @@ fn scrub_session_id_aliases_from_body(body: &mut Value) {
     }
 }
 
+/// The provider's trimmed `default_model`, if it has a non-empty one.
+fn provider_default_model(provider: &super::config::ProviderConfig) -> Option<&str> {
+    provider
+        .default_model
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+}
+
+/// The 400 for a request that has no `model` and no provider able to default one.
+fn missing_model_response(message: &str) -> Response {
+    (
+        StatusCode::BAD_REQUEST,
+        Json(json!({
+            "error": {
+                "message": message,
+                "type": "invalid_request_error"
+            }
+        })),
+    )
+        .into_response()
+}
+
+/// Fills in the provider's `default_model` when the request body has no usable `model`.
+///
+/// Returns the injected model so callers can attribute usage to it.
+fn apply_provider_default_model(
+    body: &mut Value,
+    provider: &super::config::ProviderConfig,
+) -> Option<String> {
+    let default_model = provider_default_model(provider)?;
+    let map = body.as_object_mut()?;
+    let has_model = map
+        .get("model")
+        .and_then(Value::as_str)
+        .is_some_and(|value| !value.trim().is_empty());
+    if has_model {
+        return None;
+    }
+    map.insert(
+        "model".to_string(),
+        Value::String(default_model.to_string()),
+    );
+    Some(default_model.to_string())
+}
+
+/// Rewrites the outgoing `model` field using the provider's `model_aliases`.
+///
+/// Returns the aliased model name when a mapping was applied.
+fn apply_provider_model_alias(
+    body: &mut Value,
+    provider: &super::config::ProviderConfig,
+) -> Option<String> {
+    if provider.model_aliases.is_empty() {
+        return None;
+    }
+    let map = body.as_object_mut()?;
+    let requested = map.get("model").and_then(Value::as_str)?.trim();
+    let aliased = provider
+        .model_aliases
+        .get(requested)
+        .map(|value| value.trim())
+        .filter(|value| !value.is_empty())?
+        .to_string();
+    map.insert("model".to_string(), Value::String(aliased.clone()));
+    Some(aliased)
+}
+
+/// Prepends the provider's `system_prefix` to a chat-style `input` message list.
+///
+/// Merges into a leading system message when present; non-list inputs are left untouched.
+fn apply_provider_system_prefix(body: &mut Value, provider: &super::config::ProviderConfig) {
+    let Some(prefix) = provider
+        .system_prefix
+        .as_deref()
+        .map(str::trim)
+        .filter(|value| !value.is_empty())
+    else {
+        return;
+    };
+    let Some(items) = body.get_mut("input").and_then(Value::as_array_mut) else {
+        return;
+    };
+    if !items
+        .iter()
+        .any(|item| item.get("role").and_then(Value::as_str).is_some())
+    {
+        return;
+    }
+    if let Some(first) = items.first_mut() {
+        if first.get("role").and_then(Value::as_str) == Some("system") {
+            match first.get_mut("content") {
+                Some(Value::String(text)) => {
+                    *text = format!("{prefix}\n\n{text}");
+                    return;
+                }
+                Some(Value::Array(parts)) => {
+                    parts.insert(0, json!({"type": "input_text", "text": prefix}));
+                    return;
+                }
+                _ => {}
+            }
+        }
+    }
+    let simple_content = items
+        .iter()
+        .find_map(|item| item.get("content"))
+        .is_some_and(Value::is_string);
+    let system_message = if simple_content {
+        json!({"role": "system", "content": prefix})
+    } else {
+        json!({
+            "type": "message",
+            "role": "system",
+            "content": [{"type": "input_text", "text": prefix}]
+        })
+    };
+    items.insert(0, system_message);
+}
+
+/// Size of `body` as sent upstream (compact JSON), counted without buffering it.
+fn json_body_bytes(body: &Value) -> u64 {
+    struct ByteCounter(u64);
+    impl std::io::Write for ByteCounter {
+        fn write(&mut self, buf: &[u8]) -> std::io::Result<usize> {
+            self.0 = self.0.saturating_add(buf.len() as u64);
+            Ok(buf.len())
+        }
+        fn flush(&mut self) -> std::io::Result<()> {
+            Ok(())
+        }
+    }
+    let mut counter = ByteCounter(0);
+    let _ = serde_json::to_writer(&mut counter, body);
+    counter.0
+}
+
 fn body_session_source_is_agent(body: &Value) -> bool {
     let source = body
         .get("session_source")
@@ -379,7 +516,8 @@ fn ends_with_items(haystack: &[Value], needle: &[Value]) -> bool {
 #[cfg(test)]
 mod tests {
     use super::{
-        body_agent_parent_session_id, codex_session_id_from_request,
+        apply_provider_default_model, apply_provider_model_alias, apply_provider_system_prefix,
+        body_agent_parent_session_id, codex_session_id_from_request, json_body_bytes,
         scrub_session_id_aliases_from_body, session_history_snapshot_looks_incomplete,
         usage_origin_from_base_url,
     };
@@ -537,4 +675,140 @@ mod tests {
         assert!(body.get("codex_session_id").is_none());
         assert!(body.get("codexSessionId").is_none());
     }
+
+    #[test]
+    fn model_alias_rewrites_only_mapped_models() {
+        let provider = crate::orchestrator::config::ProviderConfig {
+            display_name: "P1".to_string(),
+            base_url: "https://example.com/v1".to_string(),
+            group: None,
+            disabled: false,
+            supports_websockets: false,
+            usage_adapter: String::new(),
+            usage_base_url: None,
+            api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::from([(
+                "gpt-5-codex".to_string(),
+                "vendor-codex".to_string(),
+            )]),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
+        };
+        let mut body = json!({"model": "gpt-5-codex"});
+        assert_eq!(
+            apply_provider_model_alias(&mut body, &provider).as_deref(),
+            Some("vendor-codex")
+        );
+        assert_eq!(body["model"], "vendor-codex");
+
+        let mut other = json!({"model": "gpt-5"});
+        assert_eq!(apply_provider_model_alias(&mut other, &provider), None);
+        assert_eq!(other["model"], "gpt-5");
+    }
+
+    fn provider_with_system_prefix(
+        prefix: Option<&str>,
+    ) -> crate::orchestrator::config::ProviderConfig {
+        crate::orchestrator::config::ProviderConfig {
+            display_name: "P1".to_string(),
+            base_url: "https://example.com/v1".to_string(),
+            group: None,
+            disabled: false,
+            supports_websockets: false,
+            usage_adapter: String::new(),
+            usage_base_url: None,
+            api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: prefix.map(str::to_string),
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
+        }
+    }
+
+    #[test]
+    fn json_body_bytes_matches_compact_serialization() {
+        let body = json!({"model": "gpt-5", "input": [{"role": "user", "content": "héllo"}]});
+        assert_eq!(
+            json_body_bytes(&body),
+            serde_json::to_vec(&body).expect("serialize").len() as u64
+        );
+    }
+
+    #[test]
+    fn default_model_fills_only_missing_or_blank_model() {
+        let mut provider = provider_with_system_prefix(None);
+        provider.default_model = Some(" gpt-5 ".to_string());
+
+        let mut missing = json!({"input": "hi"});
+        assert_eq!(
+            apply_provider_default_model(&mut missing, &provider).as_deref(),
+            Some("gpt-5")
+        );
+        assert_eq!(missing["model"], "gpt-5");
+
+        let mut blank = json!({"model": "  ", "input": "hi"});
+        assert_eq!(
+            apply_provider_default_model(&mut blank, &provider).as_deref(),
+            Some("gpt-5")
+        );
+        assert_eq!(blank["model"], "gpt-5");
+
+        let mut explicit = json!({"model": "gpt-4.1", "input": "hi"});
+        assert_eq!(apply_provider_default_model(&mut explicit, &provider), None);
+        assert_eq!(explicit["model"], "gpt-4.1");
+
+        provider.default_model = None;
+        let mut untouched = json!({"input": "hi"});
+        assert_eq!(
+            apply_provider_default_model(&mut untouched, &provider),
+            None
+        );
+        assert!(untouched.get("model").is_none());
+    }
+
+    #[test]
+    fn system_prefix_merges_into_existing_system_message_or_prepends() {
+        let provider = provider_with_system_prefix(Some("Be terse."));
+
+        let mut merged = json!({
+            "input": [
+                {"role": "system", "content": "Existing."},
+                {"role": "user", "content": "hi"}
+            ]
+        });
+        apply_provider_system_prefix(&mut merged, &provider);
+        assert_eq!(merged["input"][0]["content"], "Be terse.\n\nExisting.");
+        assert_eq!(merged["input"].as_array().map(Vec::len), Some(2));
+
+        let mut prepended = json!({
+            "input": [{
+                "type": "message",
+                "role": "user",
+                "content": [{"type": "input_text", "text": "hi"}]
+            }]
+        });
+        apply_provider_system_prefix(&mut prepended, &provider);
+        assert_eq!(prepended["input"][0]["role"], "system");
+        assert_eq!(prepended["input"][0]["content"][0]["text"], "Be terse.");
+    }
+
+    #[test]
+    fn system_prefix_skips_non_chat_input_and_empty_prefix() {
+        let mut plain = json!({"input": "hello"});
+        apply_provider_system_prefix(&mut plain, &provider_with_system_prefix(Some("Be terse.")));
+        assert_eq!(plain, json!({"input": "hello"}));
+
+        let mut chat = json!({"input": [{"role": "user", "content": "hi"}]});
+        apply_provider_system_prefix(&mut chat, &provider_with_system_prefix(Some("  ")));
+        assert_eq!(chat, json!({"input": [{"role": "user", "content": "hi"}]}));
+    }
 }
@@ -7,6 +7,17 @@ fn sse_response(response_id: &str, response_obj: &Value, text: &str) -> Response
     let mut resp = Response::new(body);
     *resp.status_mut() = StatusCode::OK;
     let headers = resp.headers_mut();
+    insert_sse_passthrough_headers(headers);
+    headers.insert(
+        header::HeaderName::from_static("x-response-id"),
+        header::HeaderValue::from_str(response_id).unwrap(),
+    );
+    resp
+}
+
+/// SSE bodies are forwarded frame-by-frame; `x-accel-buffering: no` keeps reverse proxies in
+/// front of the gateway from re-buffering them.
+fn insert_sse_passthrough_headers(headers: &mut HeaderMap) {
     headers.insert(
         header::CONTENT_TYPE,
         header::HeaderValue::from_static("text/event-stream"),
@@ -20,10 +31,9 @@ fn sse_response(response_id: &str, response_obj: &Value, text: &str) -> Response
         header::HeaderValue::from_static("keep-alive"),
     );
     headers.insert(
-        header::HeaderName::from_static("x-response-id"),
-        header::HeaderValue::from_str(response_id).unwrap(),
+        header::HeaderName::from_static("x-accel-buffering"),
+        header::HeaderValue::from_static("no"),
     );
-    resp
 }
 
 fn bearer_token(auth: &str) -> Option<&str> {
@@ -57,6 +67,17 @@ fn api_key_ref_from_raw(key: Option<&str>) -> String {
     format!("{start}******{end}")
 }
 
+fn usage_label_for_recording(st: &GatewayState) -> Option<String> {
+    st.cfg
+        .read()
+        .routing
+        .usage_label
+        .as_deref()
+        .map(str::trim)
+        .filter(|label| !label.is_empty())
+        .map(str::to_string)
+}
+
 fn redact_url_for_logs(url: &reqwest::Url) -> String {
     // Avoid logging secrets in query strings. Keep only scheme://host[:port]/path.
     let scheme = url.scheme();
@@ -98,20 +119,22 @@ fn format_reqwest_error_for_logs(e: &reqwest::Error) -> String {
     parts.join("; ")
 }
 
-fn require_gateway_auth(st: &GatewayState, headers: &HeaderMap) -> Option<Response> {
-    let Some(expected) = st.secrets.get_gateway_token() else {
+/// Checks the client's bearer token against the primary and named gateway tokens.
+///
+/// On success returns the matched token label (`None` when no token is configured).
+fn require_gateway_auth(
+    st: &GatewayState,
+    headers: &HeaderMap,
+) -> Result<Option<String>, Response> {
+    if !st.secrets.gateway_auth_required() {
         // No token configured: allow for local dev.
-        return None;
-    };
-    let expected = expected.trim();
-    if expected.is_empty() {
-        return None;
+        return Ok(None);
     }
     let Some(auth) = headers
         .get(header::AUTHORIZATION)
         .and_then(|v| v.to_str().ok())
     else {
-        return Some(
+        return Err(
             (
                 StatusCode::UNAUTHORIZED,
                 Json(json!({"error": {"message":"missing Authorization (set OPENAI_API_KEY in .codex/auth.json to the gateway token)","type":"unauthorized"}})),
@@ -120,7 +143,7 @@ fn require_gateway_auth(st: &GatewayState, headers: &HeaderMap) -> Option<Respon
         );
     };
     let Some(tok) = bearer_token(auth) else {
-        return Some(
+        return Err(
             (
                 StatusCode::UNAUTHORIZED,
                 Json(json!({"error":{"message":"invalid Authorization format","type":"unauthorized"}})),
@@ -128,25 +151,23 @@ fn require_gateway_auth(st: &GatewayState, headers: &HeaderMap) -> Option<Respon
                 .into_response(),
         );
     };
-    if tok != expected {
-        return Some(
+    let Some(label) = st.secrets.gateway_token_label(tok) else {
+        return Err(
             (
                 StatusCode::UNAUTHORIZED,
                 Json(json!({"error": {"message":"invalid gateway token","type":"unauthorized"}})),
             )
                 .into_response(),
         );
-    }
-    None
+    };
+    Ok(Some(label))
 }
 
 fn upstream_auth<'a>(st: &GatewayState, client_auth: Option<&'a str>) -> Option<&'a str> {
     let auth = client_auth?;
-    // Never forward the local gateway token upstream.
-    if let (Some(tok), Some(b)) = (st.secrets.get_gateway_token(), bearer_token(auth)) {
-        if !tok.trim().is_empty() && b == tok.trim() {
-            return None;
-        }
+    // Never forward a local gateway token (primary or named) upstream.
+    if bearer_token(auth).is_some_and(|b| st.secrets.gateway_token_label(b).is_some()) {
+        return None;
     }
     Some(auth)
 }
@@ -156,8 +177,11 @@ struct SsePersistContext {
     api_key_ref: String,
     session_key: String,
     requested_model: Option<String>,
+    /// Model recorded in usage stats instead of the upstream one (set when the model was aliased).
+    usage_model: Option<String>,
     request_origin: String,
     transport: &'static str,
+    request_bytes: u64,
 }
 
 fn passthrough_sse_and_persist(
@@ -184,7 +208,9 @@ fn passthrough_sse_and_persist(
     let api_key_ref2 = persist_ctx.api_key_ref.clone();
     let session_key2 = persist_ctx.session_key.clone();
     let requested_model2 = persist_ctx.requested_model.clone();
+    let usage_model2 = persist_ctx.usage_model.clone();
     let request_origin2 = persist_ctx.request_origin.clone();
+    let request_bytes2 = persist_ctx.request_bytes;
     let tap3 = tap.clone();
     let stream = async_stream::stream! {
         let mut forwarded_bytes: u64 = 0;
@@ -315,6 +341,7 @@ fn passthrough_sse_and_persist(
                 }
             }
             let local_node = st2.secrets.get_lan_node_identity();
+            let usage_label = usage_label_for_recording(&st2);
             st2.store
                 .record_success_with_model(
                     &provider2,
@@ -326,8 +353,11 @@ fn passthrough_sse_and_persist(
                         session_id: Some(session_key2.as_str()),
                         node_id: local_node.as_ref().map(|value| value.node_id.as_str()),
                         node_name: local_node.as_ref().map(|value| value.node_name.as_str()),
+                        label: usage_label.as_deref(),
+                        request_bytes: request_bytes2,
+                        response_bytes: forwarded_bytes,
                     },
-                    created_model_for_usage.as_deref(),
+                    usage_model2.as_deref().or(created_model_for_usage.as_deref()),
                 );
         }
     };
@@ -336,18 +366,7 @@ fn passthrough_sse_and_persist(
     let mut resp = Response::new(body);
     *resp.status_mut() = StatusCode::OK;
     let headers = resp.headers_mut();
-    headers.insert(
-        header::CONTENT_TYPE,
-        header::HeaderValue::from_static("text/event-stream"),
-    );
-    headers.insert(
-        header::CACHE_CONTROL,
-        header::HeaderValue::from_static("no-cache"),
-    );
-    headers.insert(
-        header::CONNECTION,
-        header::HeaderValue::from_static("keep-alive"),
-    );
+    insert_sse_passthrough_headers(headers);
     resp
 }
 
@@ -370,7 +389,9 @@ fn passthrough_websocket_sse_and_persist(
     let api_key_ref2 = persist_ctx.api_key_ref.clone();
     let session_key2 = persist_ctx.session_key.clone();
     let requested_model2 = persist_ctx.requested_model.clone();
+    let usage_model2 = persist_ctx.usage_model.clone();
     let request_origin2 = persist_ctx.request_origin.clone();
+    let request_bytes2 = persist_ctx.request_bytes;
     let transport2 = persist_ctx.transport;
     let tap3 = tap.clone();
     let stream = async_stream::stream! {
@@ -481,6 +502,7 @@ fn passthrough_websocket_sse_and_persist(
                 }
             }
             let local_node = st2.secrets.get_lan_node_identity();
+            let usage_label = usage_label_for_recording(&st2);
             st2.store
                 .record_success_with_model(
                     &provider2,
@@ -492,8 +514,11 @@ fn passthrough_websocket_sse_and_persist(
                         session_id: Some(session_key2.as_str()),
                         node_id: local_node.as_ref().map(|value| value.node_id.as_str()),
                         node_name: local_node.as_ref().map(|value| value.node_name.as_str()),
+                        label: usage_label.as_deref(),
+                        request_bytes: request_bytes2,
+                        response_bytes: forwarded_bytes,
                     },
-                    created_model_for_usage.as_deref(),
+                    usage_model2.as_deref().or(created_model_for_usage.as_deref()),
                 );
         }
     };
@@ -502,18 +527,7 @@ fn passthrough_websocket_sse_and_persist(
     let mut resp = Response::new(body);
     *resp.status_mut() = StatusCode::OK;
     let headers = resp.headers_mut();
-    headers.insert(
-        header::CONTENT_TYPE,
-        header::HeaderValue::from_static("text/event-stream"),
-    );
-    headers.insert(
-        header::CACHE_CONTROL,
-        header::HeaderValue::from_static("no-cache"),
-    );
-    headers.insert(
-        header::CONNECTION,
-        header::HeaderValue::from_static("keep-alive"),
-    );
+    insert_sse_passthrough_headers(headers);
     resp
 }
 
@@ -521,6 +535,8 @@ struct SseTap {
     buf: String,
     created_model: Option<String>,
     completed: Option<(String, Value)>,
+    /// Usage from a standalone trailing chunk (`stream_options.include_usage` style upstreams).
+    trailing_usage: Option<Value>,
 }
 
 impl SseTap {
@@ -529,11 +545,12 @@ impl SseTap {
             buf: String::new(),
             created_model: None,
             completed: None,
+            trailing_usage: None,
         }
     }
 
     fn feed(&mut self, chunk: &Bytes) {
-        if self.completed.is_some() {
+        if self.completed.is_some() && self.trailing_usage.is_some() {
             return;
         }
         if let Ok(s) = std::str::from_utf8(chunk) {
@@ -542,9 +559,6 @@ impl SseTap {
                 let msg = self.buf[..idx].to_string();
                 self.buf = self.buf[idx + 2..].to_string();
                 self.consume_message(&msg);
-                if self.completed.is_some() {
-                    break;
-                }
             }
         }
     }
@@ -561,17 +575,24 @@ impl SseTap {
             let Ok(v) = serde_json::from_str::<Value>(data) else {
                 continue;
             };
-            if v.get("type").and_then(|x| x.as_str()) == Some("response.created") {
+            let event_type = v.get("type").and_then(|x| x.as_str());
+            if !event_type.is_some_and(|t| t.starts_with("response."))
+                && v.get("usage").is_some_and(Value::is_object)
+            {
+                self.trailing_usage = v.get("usage").cloned();
+                continue;
+            }
+            if self.completed.is_some() {
+                continue;
+            }
+            if event_type == Some("response.created") {
                 if let Some(resp) = v.get("response") {
                     if let Some(model) = extract_response_model_option(resp) {
                         self.created_model = Some(model);
                     }
                 }
             }
-            if matches!(
-                v.get("type").and_then(|x| x.as_str()),
-                Some("response.completed" | "response.done")
-            ) {
+            if matches!(event_type, Some("response.completed" | "response.done")) {
                 if let Some(resp) = v.get("response") {
                     if let Some(id) = resp.get("id").and_then(|x| x.as_str()) {
                         self.completed = Some((id.to_string(), resp.clone()));
@@ -583,9 +604,16 @@ impl SseTap {
     }
 
     fn take_completed(&mut self) -> Option<(String, Value)> {
-        let out = self.completed.take();
-        if out.is_some() {
+        let mut out = self.completed.take();
+        if let Some((_, resp)) = out.as_mut() {
             self.created_model = None;
+            // Prefer the completed response's own usage; fall back to a trailing usage chunk.
+            if !resp.get("usage").is_some_and(Value::is_object) {
+                if let (Some(usage), Some(map)) = (self.trailing_usage.take(), resp.as_object_mut())
+                {
+                    map.insert("usage".to_string(), usage);
+                }
+            }
         }
         out
     }
@@ -1,5 +1,387 @@
-async fn health() -> impl IntoResponse {
-    Json(json!({"ok": true}))
+#[derive(serde::Deserialize)]
+struct HealthQuery {
+    #[serde(default)]
+    deep: Option<String>,
+}
+
+/// `/health` stays a cheap liveness probe; `?deep=1` reports upstream reachability instead.
+async fn health(State(st): State<GatewayState>, Query(query): Query<HealthQuery>) -> Response {
+    let deep = matches!(
+        query.deep.as_deref().map(str::trim),
+        Some("1") | Some("true") | Some("yes")
+    );
+    if !deep {
+        return Json(json!({"ok": true})).into_response();
+    }
+    let (status, body) = deep_health_payload(&st, unix_ms());
+    (status, Json(body)).into_response()
+}
+
+fn deep_health_payload(st: &GatewayState, now: u64) -> (StatusCode, Value) {
+    let cfg = st.cfg.read().clone();
+    let snapshot = st.router.snapshot(now);
+    let mut providers = Vec::new();
+    let mut any_healthy = false;
+    for name in provider_iteration_order(&cfg) {
+        let health = snapshot.get(&name);
+        let status = health.map(|h| h.status.as_str()).unwrap_or("unknown");
+        let healthy = status == "healthy";
+        any_healthy |= healthy;
+        providers.push(json!({
+            "provider": name,
+            "status": status,
+            "healthy": healthy,
+            "consecutive_failures": health.map(|h| h.consecutive_failures).unwrap_or(0),
+            "cooldown_until_unix_ms": health.map(|h| h.cooldown_until_unix_ms).unwrap_or(0),
+            "last_ok_at_unix_ms": health.map(|h| h.last_ok_at_unix_ms).unwrap_or(0),
+            "last_error": health.map(|h| h.last_error.as_str()).unwrap_or(""),
+        }));
+    }
+    let status = if any_healthy {
+        StatusCode::OK
+    } else {
+        StatusCode::SERVICE_UNAVAILABLE
+    };
+    (
+        status,
+        json!({"ok": any_healthy, "deep": true, "providers": providers}),
+    )
+}
+
+pub(crate) struct ProviderProbeOutcome {
+    pub status: u16,
+    pub latency_ms: u64,
+}
+
+pub(crate) enum ProviderProbeError {
+    UnknownProvider,
+    Upstream {
+        status: Option<u16>,
+        message: String,
+        latency_ms: u64,
+    },
+}
+
+/// Issues a `/v1/models` request to `provider` and records the result in router health and
+/// the event log, exactly like the desktop "probe" action. Probes are never recorded as usage.
+pub(crate) async fn probe_provider_health(
+    st: &GatewayState,
+    provider: &str,
+) -> Result<ProviderProbeOutcome, ProviderProbeError> {
+    let cfg = st.cfg.read().clone();
+    let Some(p) = cfg.providers.get(provider) else {
+        return Err(ProviderProbeError::UnknownProvider);
+    };
+    let now = unix_ms();
+    st.router.sync_with_config(&cfg, now);
+    let key = st.secrets.get_provider_key(provider);
+
+    let started = std::time::Instant::now();
+    let result = st
+        .upstream
+        .get_json(
+            p,
+            "/v1/models",
+            key.as_deref(),
+            None,
+            super::upstream::UpstreamTimeouts::from_routing(&cfg.routing),
+        )
+        .await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+    let (status, payload) = match result {
+        Ok(result) => result,
+        Err(e) => {
+            let message = format!("request error: {e}");
+            st.router.mark_failure(provider, &cfg, &message, now);
+            st.store.events().emit(
+                provider,
+                crate::orchestrator::store::EventCode::HEALTH_PROBE_FAILED,
+                "health probe failed (request error)",
+                Value::Null,
+            );
+            return Err(ProviderProbeError::Upstream {
+                status: None,
+                message,
+                latency_ms,
+            });
+        }
+    };
+
+    if (200..300).contains(&status) {
+        record_provider_models(st, provider, &payload, now);
+        st.router.mark_success(provider, now);
+        st.store.events().emit(
+            provider,
+            crate::orchestrator::store::EventCode::HEALTH_PROBE_OK,
+            "Provider is reachable and responding",
+            Value::Null,
+        );
+        return Ok(ProviderProbeOutcome { status, latency_ms });
+    }
+
+    let message = format!("http {status}");
+    st.router.mark_failure(provider, &cfg, &message, now);
+    st.store.events().emit(
+        provider,
+        crate::orchestrator::store::EventCode::HEALTH_PROBE_FAILED,
+        "health probe failed",
+        Value::Null,
+    );
+    Err(ProviderProbeError::Upstream {
+        status: Some(status),
+        message,
+        latency_ms,
+    })
+}
+
+/// How long a provider's models catalog is served before `get_status` refreshes it.
+pub(crate) const PROVIDER_MODELS_CACHE_TTL_MS: u64 = 60 * 60 * 1000;
+
+fn model_ids_from_listing(payload: &Value) -> Option<Vec<String>> {
+    let data = payload.get("data")?.as_array()?;
+    let mut models = data
+        .iter()
+        .filter_map(|item| item.get("id").and_then(Value::as_str))
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(str::to_string)
+        .collect::<Vec<_>>();
+    models.sort();
+    models.dedup();
+    Some(models)
+}
+
+/// Caches the model ids from a successful `/models` response for `provider`.
+pub(crate) fn record_provider_models(st: &GatewayState, provider: &str, payload: &Value, now: u64) {
+    let Some(models) = model_ids_from_listing(payload) else {
+        return;
+    };
+    let mut cache = st.provider_models_cache.write();
+    let entry = cache.entry(provider.to_string()).or_default();
+    entry.models = models;
+    entry.fetched_at_unix_ms = now;
+}
+
+/// Marks a lazy catalog refresh as started unless one ran within the cache TTL.
+pub(crate) fn claim_provider_models_refresh(st: &GatewayState, provider: &str, now: u64) -> bool {
+    let mut cache = st.provider_models_cache.write();
+    let entry = cache.entry(provider.to_string()).or_default();
+    let last = entry.attempted_at_unix_ms.max(entry.fetched_at_unix_ms);
+    if last > 0 && now.saturating_sub(last) < PROVIDER_MODELS_CACHE_TTL_MS {
+        return false;
+    }
+    entry.attempted_at_unix_ms = now;
+    true
+}
+
+/// Fetches `/models` for `provider` into the catalog cache. Unlike a probe, this leaves router
+/// health untouched and emits no events.
+pub(crate) async fn refresh_provider_models(st: &GatewayState, provider: &str) {
+    let cfg = st.cfg.read().clone();
+    let Some(p) = cfg.providers.get(provider) else {
+        return;
+    };
+    let key = st.secrets.get_provider_key(provider);
+    if let Ok((status, payload)) = st
+        .upstream
+        .get_json(
+            p,
+            crate::orchestrator::upstream::MODELS_ENDPOINT,
+            key.as_deref(),
+            None,
+            super::upstream::UpstreamTimeouts::from_routing(&cfg.routing),
+        )
+        .await
+    {
+        if (200..300).contains(&status) {
+            record_provider_models(st, provider, &payload, unix_ms());
+        }
+    }
+}
+
+/// Requests `/models` from `provider` so the upstream pool holds a live TLS connection for the
+/// next real request. Any non-5xx answer (a missing `/models` included) counts as warm and leaves
+/// router health untouched; transport errors and 5xx are recorded like a failed probe. Returns
+/// the round-trip latency.
+pub(crate) async fn warm_provider_connection(
+    st: &GatewayState,
+    provider: &str,
+) -> Result<u64, String> {
+    let cfg = st.cfg.read().clone();
+    let Some(p) = cfg.providers.get(provider) else {
+        return Err(format!("unknown provider: {provider}"));
+    };
+    let key = st.secrets.get_provider_key(provider);
+    let started = std::time::Instant::now();
+    let result = st
+        .upstream
+        .get_json(
+            p,
+            crate::orchestrator::upstream::MODELS_ENDPOINT,
+            key.as_deref(),
+            None,
+            super::upstream::UpstreamTimeouts::from_routing(&cfg.routing),
+        )
+        .await;
+    let latency_ms = started.elapsed().as_millis() as u64;
+    let now = unix_ms();
+    let message = match result {
+        Ok((status, payload)) if (200..300).contains(&status) => {
+            record_provider_models(st, provider, &payload, now);
+            return Ok(latency_ms);
+        }
+        Ok((status, _)) if status < 500 => return Ok(latency_ms),
+        Ok((status, _)) => format!("http {status}"),
+        Err(e) => format!("request error: {e}"),
+    };
+    st.router.sync_with_config(&cfg, now);
+    st.router.mark_failure(provider, &cfg, &message, now);
+    st.store.events().emit(
+        provider,
+        crate::orchestrator::store::EventCode::HEALTH_PROBE_FAILED,
+        "connection warm-up failed",
+        json!({ "error": message }),
+    );
+    Err(message)
+}
+
+/// Warms every enabled, routable provider concurrently; results are in provider-name order.
+pub(crate) async fn warm_providers(st: &GatewayState) -> Vec<(String, Result<u64, String>)> {
+    let providers = st
+        .cfg
+        .read()
+        .providers
+        .iter()
+        .filter(|(_, provider)| !provider.disabled && provider.routing_eligible)
+        .map(|(name, _)| name.clone())
+        .collect::<Vec<_>>();
+    futures_util::future::join_all(providers.into_iter().map(|name| async move {
+        let result = warm_provider_connection(st, &name).await;
+        (name, result)
+    }))
+    .await
+}
+
+/// Background loop behind `routing.keepalive_warm_seconds`. The setting is re-read every pass,
+/// so enabling or changing it takes effect without a restart.
+pub(crate) async fn run_connection_warmer(st: GatewayState) {
+    loop {
+        let interval = st.cfg.read().routing.keepalive_warm_interval();
+        let Some(interval) = interval else {
+            tokio::time::sleep(std::time::Duration::from_secs(
+                crate::orchestrator::config::MIN_KEEPALIVE_WARM_SECONDS,
+            ))
+            .await;
+            continue;
+        };
+        tokio::time::sleep(interval).await;
+        if st.cfg.read().routing.keepalive_warm_interval().is_some() {
+            warm_providers(&st).await;
+        }
+    }
+}
+
+#[derive(serde::Deserialize)]
+struct TimeseriesQuery {
+    #[serde(default)]
+    metric: Option<String>,
+    /// Comma-separated provider names; all configured providers when absent.
+    #[serde(default)]
+    provider: Option<String>,
+    #[serde(default)]
+    from: Option<u64>,
+    #[serde(default)]
+    to: Option<u64>,
+    #[serde(default)]
+    bucket: Option<String>,
+}
+
+/// Per-provider usage series for Grafana (SimpleJSON / Infinity). Defaults to hourly tokens over
+/// the last 24 hours.
+async fn timeseries(
+    State(st): State<GatewayState>,
+    headers: HeaderMap,
+    Query(query): Query<TimeseriesQuery>,
+) -> Response {
+    if let Err(resp) = require_gateway_auth(&st, &headers) {
+        return resp;
+    }
+    let to = query.to.unwrap_or_else(unix_ms);
+    let from = query
+        .from
+        .unwrap_or_else(|| to.saturating_sub(24 * 60 * 60 * 1000));
+    let providers = query.provider.map(|raw| {
+        raw.split(',')
+            .map(str::trim)
+            .filter(|name| !name.is_empty())
+            .map(str::to_string)
+            .collect::<Vec<_>>()
+    });
+    let metric = query.metric.unwrap_or_else(|| "tokens".to_string());
+    let bucket = query.bucket.unwrap_or_else(|| "hour".to_string());
+    let result = tokio::task::spawn_blocking(move || {
+        crate::commands::usage_timeseries(&st, &metric, providers, from, to, &bucket)
+    })
+    .await;
+    match result {
+        Ok(Ok(series)) => Json(series).into_response(),
+        Ok(Err(message)) => (
+            StatusCode::BAD_REQUEST,
+            Json(json!({"error": {"message": message, "type": "invalid_request_error"}})),
+        )
+            .into_response(),
+        Err(err) => (
+            StatusCode::INTERNAL_SERVER_ERROR,
+            Json(json!({"error": {"message": err.to_string(), "type": "internal"}})),
+        )
+            .into_response(),
+    }
+}
+
+async fn probe_provider_http(
+    State(st): State<GatewayState>,
+    headers: HeaderMap,
+    axum::extract::Path(provider): axum::extract::Path<String>,
+) -> Response {
+    if let Err(resp) = require_gateway_auth(&st, &headers) {
+        return resp;
+    }
+    match probe_provider_health(&st, &provider).await {
+        Ok(outcome) => Json(json!({
+            "ok": true,
+            "status": outcome.status,
+            "latencyMs": outcome.latency_ms,
+        }))
+        .into_response(),
+        Err(ProviderProbeError::UnknownProvider) => (
+            StatusCode::NOT_FOUND,
+            Json(json!({
+                "ok": false,
+                "error": {
+                    "message": format!("unknown provider: {provider}"),
+                    "type": "unknown_provider",
+                },
+            })),
+        )
+            .into_response(),
+        Err(ProviderProbeError::Upstream {
+            status,
+            message,
+            latency_ms,
+        }) => (
+            StatusCode::BAD_GATEWAY,
+            Json(json!({
+                "ok": false,
+                "status": status,
+                "latencyMs": latency_ms,
+                "error": {
+                    "message": message.chars().take(300).collect::<String>(),
+                    "type": "upstream",
+                },
+            })),
+        )
+            .into_response(),
+    }
 }
 
 pub(crate) fn provider_has_remaining_quota_with_hard_cap(
@@ -128,7 +510,7 @@ fn provider_is_routable_for_selection(
     };
     cfg.providers
         .get(provider)
-        .is_some_and(|provider_cfg| !provider_cfg.disabled)
+        .is_some_and(|provider_cfg| provider_cfg.routable())
         && router_routable
         && provider_has_remaining_quota_with_hard_cap(cfg, quota_snapshots, provider, &hard_cap)
 }
@@ -162,11 +544,11 @@ fn fallback_with_quota(
 fn fallback_decision(
     fallback: Option<String>,
     preferred: &str,
-    fallback_reason: &'static str,
-) -> (String, &'static str) {
+    fallback_reason: RouteReason,
+) -> (String, RouteReason) {
     match fallback {
         Some(provider) => (provider, fallback_reason),
-        None => (preferred.to_string(), "no_routable_provider"),
+        None => (preferred.to_string(), RouteReason::NoRoutableProvider),
     }
 }
 
@@ -219,6 +601,7 @@ fn unhealthy_retry_delay_ms(cfg: &AppConfig) -> u64 {
 enum BalancedAssignmentPersistMode {
     Full,
     Bootstrap,
+    Preview,
 }
 
 impl BalancedAssignmentPersistMode {
@@ -421,9 +804,23 @@ fn provider_per_request_cost_signal(
         api_key_ref.as_deref(),
         now_ms,
     )?;
-    if let Some(amount) =
-        crate::orchestrator::secrets::pricing_per_request_amount_at(Some(pricing), now_ms)
-    {
+    let requests_today = if pricing.mode == "per_request_tiered" {
+        Store::local_day_key_from_unix_ms(now_ms)
+            .and_then(|day_key| Store::day_start_unix_ms_from_day_key(&day_key))
+            .map(|day_start| {
+                st.store
+                    .summarize_usage_requests_since_by_provider(provider, day_start)
+                    .0
+            })
+            .unwrap_or(0)
+    } else {
+        0
+    };
+    if let Some(amount) = crate::orchestrator::secrets::pricing_per_request_amount_at(
+        Some(pricing),
+        now_ms,
+        requests_today,
+    ) {
         return Some(amount);
     }
     if pricing
@@ -537,7 +934,7 @@ fn load_balanced_assignment_counts(
         if !cfg
             .providers
             .get(&row.provider)
-            .is_some_and(|provider_cfg| !provider_cfg.disabled)
+            .is_some_and(|provider_cfg| provider_cfg.routable())
         {
             continue;
         }
@@ -765,7 +1162,7 @@ fn pick_balanced_provider_for_verified_main_session(
     if assignment.as_ref().is_some_and(|row| {
         !cfg.providers
             .get(&row.provider)
-            .is_some_and(|provider_cfg| !provider_cfg.disabled)
+            .is_some_and(|provider_cfg| provider_cfg.routable())
     }) {
         if rewrite_existing_assignment {
             st.store.delete_session_route_assignment(session_key);
@@ -959,13 +1356,95 @@ fn pick_balanced_provider_for_verified_session(
     )
 }
 
+/// A session's last route stops pinning provider selection once it has been idle past the TTL.
+pub(crate) fn session_route_expired(cfg: &AppConfig, route: &LastUsedRoute, now_ms: u64) -> bool {
+    now_ms.saturating_sub(route.unix_ms)
+        > cfg.routing.session_sticky_ttl_seconds.saturating_mul(1000)
+}
+
+/// Records a session's latest route in memory, returning the previous route.
+///
+/// The store is only written when the route itself changes; timestamp-only refreshes are
+/// batched by the periodic [`persist_last_used_routes`] task.
+pub(crate) fn record_last_used_route(
+    st: &GatewayState,
+    session_key: &str,
+    route: LastUsedRoute,
+) -> Option<LastUsedRoute> {
+    let row = session_last_route_row(session_key, &route);
+    let previous = st
+        .last_used_by_session
+        .write()
+        .insert(session_key.to_string(), route);
+    let changed = previous.as_ref().map_or(true, |prev| {
+        prev.provider != row.provider
+            || prev.reason.as_str() != row.reason
+            || prev.preferred != row.preferred
+    });
+    if changed {
+        st.store.put_session_last_route(&row);
+    }
+    previous
+}
+
+/// Rewrites the persisted session routes after bulk edits to `last_used_by_session`.
+pub(crate) fn persist_last_used_routes(st: &GatewayState) {
+    let rows = st
+        .last_used_by_session
+        .read()
+        .iter()
+        .map(|(session_key, route)| session_last_route_row(session_key, route))
+        .collect::<Vec<_>>();
+    st.store.replace_session_last_routes(&rows);
+}
+
+/// Reloads session routes saved before a restart. Routes idle past the sticky TTL, pointing
+/// at providers that no longer exist, or carrying an unrecognised reason are dropped.
+pub(crate) fn load_persisted_last_used_routes(
+    store: &Store,
+    cfg: &AppConfig,
+    now_ms: u64,
+) -> HashMap<String, LastUsedRoute> {
+    let cutoff = now_ms.saturating_sub(cfg.routing.session_sticky_ttl_seconds.saturating_mul(1000));
+    store.delete_session_last_routes_before(cutoff);
+    store
+        .list_session_last_routes_since(cutoff)
+        .into_iter()
+        .filter(|row| cfg.providers.contains_key(&row.provider))
+        .filter_map(|row| {
+            Some((
+                row.session_id,
+                LastUsedRoute {
+                    provider: row.provider,
+                    reason: RouteReason::from_wire(&row.reason)?,
+                    preferred: row.preferred,
+                    unix_ms: row.unix_ms,
+                },
+            ))
+        })
+        .collect()
+}
+
+fn session_last_route_row(
+    session_key: &str,
+    route: &LastUsedRoute,
+) -> crate::orchestrator::store::SessionLastRoute {
+    crate::orchestrator::store::SessionLastRoute {
+        session_id: session_key.to_string(),
+        provider: route.provider.clone(),
+        reason: route.reason.to_string(),
+        preferred: route.preferred.clone(),
+        unix_ms: route.unix_ms,
+    }
+}
+
 fn decide_provider_with_balanced_mode(
     st: &GatewayState,
     cfg: &AppConfig,
     preferred: &str,
     session_key: &str,
     balanced_persist_mode: BalancedAssignmentPersistMode,
-) -> (String, &'static str) {
+) -> (String, RouteReason) {
     let quota_snapshots = st.store.list_quota_snapshots();
     let now_ms = unix_ms();
     let clear_usage_confirmation_requirement =
@@ -1032,7 +1511,7 @@ fn decide_provider_with_balanced_mode(
             &manual,
             clear_usage_confirmation_requirement,
         ) {
-            return (manual, "manual_override");
+            return (manual, RouteReason::ManualOverride);
         }
         return fallback_decision(
             fallback_with_quota(
@@ -1043,7 +1522,7 @@ fn decide_provider_with_balanced_mode(
                 clear_usage_confirmation_requirement,
             ),
             preferred,
-            "manual_override_unhealthy",
+            RouteReason::ManualOverrideUnhealthy,
         );
     }
 
@@ -1055,6 +1534,7 @@ fn decide_provider_with_balanced_mode(
         .last_used_by_session
         .read()
         .get(session_key)
+        .filter(|v| !session_route_expired(cfg, v, now_ms))
         .map(|v| v.provider.clone());
     let suppress_preferred_in_balanced = last_provider
         .as_deref()
@@ -1077,7 +1557,7 @@ fn decide_provider_with_balanced_mode(
             balanced_persist_mode,
             0,
         ) {
-            return (provider, "balanced_auto");
+            return (provider, RouteReason::BalancedAuto);
         }
     }
 
@@ -1097,7 +1577,7 @@ fn decide_provider_with_balanced_mode(
                     &p,
                     clear_usage_confirmation_requirement,
                 ) {
-                    return (p, "preferred_stabilizing");
+                    return (p, RouteReason::PreferredStabilizing);
                 }
             }
             return fallback_decision(
@@ -1109,7 +1589,7 @@ fn decide_provider_with_balanced_mode(
                     clear_usage_confirmation_requirement,
                 ),
                 preferred,
-                "preferred_stabilizing",
+                RouteReason::PreferredStabilizing,
             );
         }
     }
@@ -1121,7 +1601,20 @@ fn decide_provider_with_balanced_mode(
         preferred,
         clear_usage_confirmation_requirement,
     ) {
-        return (preferred.to_string(), "preferred_healthy");
+        // The session's own preferred list picked this provider. A session whose listed
+        // providers no longer exist falls back to the global preference, which is not that.
+        let preferred_from_session = session_has_explicit_preferred
+            && cfg
+                .routing
+                .session_preferred_list(session_key)
+                .iter()
+                .any(|name| name == preferred && cfg.providers.contains_key(name));
+        let reason = if preferred_from_session {
+            RouteReason::SessionPreferred
+        } else {
+            RouteReason::PreferredHealthy
+        };
+        return (preferred.to_string(), reason);
     }
     fallback_decision(
         fallback_with_quota(
@@ -1132,16 +1625,49 @@ fn decide_provider_with_balanced_mode(
             clear_usage_confirmation_requirement,
         ),
         preferred,
-        "preferred_unhealthy",
+        RouteReason::PreferredUnhealthy,
     )
 }
 
+/// Preferred provider for a session: the first entry of the session's ordered list that is
+/// routable and not excluded by `skip`, else its first configured entry (so the global strategy
+/// fails over from it), else the global `preferred_provider`.
+pub(crate) fn session_preferred_provider<'a>(
+    st: &GatewayState,
+    cfg: &'a AppConfig,
+    session_key: &str,
+    skip: impl Fn(&str) -> bool,
+) -> &'a str {
+    let candidates: Vec<&'a str> = cfg
+        .routing
+        .session_preferred_list(session_key)
+        .iter()
+        .map(String::as_str)
+        .filter(|name| cfg.providers.contains_key(*name))
+        .collect();
+    if candidates.len() > 1 {
+        let quota_snapshots = st.store.list_quota_snapshots();
+        if let Some(name) = candidates.iter().copied().find(|name| {
+            !skip(name)
+                && provider_is_routable_for_selection(st, cfg, &quota_snapshots, name, false)
+        }) {
+            return name;
+        }
+    }
+    candidates
+        .iter()
+        .copied()
+        .find(|name| !skip(name))
+        .or(candidates.first().copied())
+        .unwrap_or(cfg.routing.preferred_provider.as_str())
+}
+
 pub(crate) fn decide_provider(
     st: &GatewayState,
     cfg: &AppConfig,
     preferred: &str,
     session_key: &str,
-) -> (String, &'static str) {
+) -> (String, RouteReason) {
     decide_provider_with_balanced_mode(
         st,
         cfg,
@@ -1156,7 +1682,7 @@ pub(crate) fn decide_provider_for_display(
     cfg: &AppConfig,
     preferred: &str,
     session_key: &str,
-) -> (String, &'static str) {
+) -> (String, RouteReason) {
     // Display path may bootstrap an initial balanced assignment so idle session cards stay
     // stable, but it must not clear runtime usage-confirmation gates.
     decide_provider_with_balanced_mode(
@@ -1168,6 +1694,119 @@ pub(crate) fn decide_provider_for_display(
     )
 }
 
+/// Skip reasons for one provider, in the order selection checks them; empty when routable.
+fn provider_routing_skip_reasons(
+    st: &GatewayState,
+    cfg: &AppConfig,
+    quota_snapshots: &Value,
+    unsupported_model_providers: &HashSet<String>,
+    provider: &str,
+) -> Vec<&'static str> {
+    let mut reasons = Vec::new();
+    let Some(provider_cfg) = cfg.providers.get(provider) else {
+        return vec!["unknown"];
+    };
+    if provider_cfg.disabled {
+        reasons.push("disabled");
+    }
+    if !provider_cfg.routing_eligible {
+        reasons.push("usage_only");
+    }
+    if st.router.is_provider_in_cooldown(provider) {
+        reasons.push("cooldown");
+    } else if !st.router.is_provider_routable(provider) {
+        reasons.push("unhealthy");
+    }
+    let hard_cap = st.secrets.get_provider_quota_hard_cap(provider);
+    if st.router.is_waiting_usage_confirmation(provider)
+        && !quota_snapshot_confirms_available(cfg, quota_snapshots, provider, &hard_cap)
+    {
+        reasons.push("awaiting_usage_confirmation");
+    }
+    if !provider_has_remaining_quota_with_hard_cap(cfg, quota_snapshots, provider, &hard_cap) {
+        reasons.push("quota");
+    }
+    if unsupported_model_providers.contains(provider) {
+        reasons.push("model_not_allowed");
+    }
+    reasons
+}
+
+/// Dry run of request-time provider selection for `session_key`/`model`: returns the provider
+/// a request would be sent to and why each configured provider was or was not usable. Nothing is
+/// sent upstream and no routing state (assignments, confirmation gates, recovery) is touched.
+pub(crate) fn explain_routing_decision(
+    st: &GatewayState,
+    session_key: &str,
+    requested_model: Option<&str>,
+) -> Value {
+    let cfg = st.cfg.read().clone();
+    let unsupported_model_providers =
+        load_session_unsupported_model_providers(session_key, requested_model, unix_ms());
+    let preferred = session_preferred_provider(st, &cfg, session_key, |name| {
+        unsupported_model_providers.contains(name)
+    });
+    let (mut provider, mut reason) = decide_provider_with_balanced_mode(
+        st,
+        &cfg,
+        preferred,
+        session_key,
+        BalancedAssignmentPersistMode::Preview,
+    );
+    let quota_snapshots = st.store.list_quota_snapshots();
+    if unsupported_model_providers.contains(&provider) {
+        let picked = select_fallback_provider(&cfg, preferred, |name| {
+            !unsupported_model_providers.contains(name)
+                && provider_is_routable_for_selection(st, &cfg, &quota_snapshots, name, false)
+        });
+        if !unsupported_model_providers.contains(&picked)
+            && provider_is_routable_for_selection(st, &cfg, &quota_snapshots, &picked, false)
+        {
+            provider = picked;
+            reason = RouteReason::SessionInvalidRequestFallback;
+        } else {
+            reason = RouteReason::ModelNotAllowed;
+        }
+    }
+
+    let mut ordered = crate::orchestrator::router::provider_iteration_order(&cfg);
+    for name in cfg.provider_order.iter().chain(cfg.providers.keys()) {
+        if cfg.providers.contains_key(name) && !ordered.contains(name) {
+            ordered.push(name.clone());
+        }
+    }
+    let candidates: Vec<Value> = ordered
+        .iter()
+        .map(|name| {
+            let skip_reasons = provider_routing_skip_reasons(
+                st,
+                &cfg,
+                &quota_snapshots,
+                &unsupported_model_providers,
+                name,
+            );
+            json!({
+                "provider": name,
+                "eligible": skip_reasons.is_empty(),
+                "skip_reasons": skip_reasons,
+                "preferred": name == preferred,
+                "chosen": *name == provider,
+            })
+        })
+        .collect();
+    let routable = reason.is_routable();
+    json!({
+        "session_id": session_key,
+        "model": requested_model,
+        "route_mode": cfg.routing.route_mode,
+        "preferred_provider": preferred,
+        "manual_override": st.router.manual_override.read().clone(),
+        "provider": routable.then_some(provider),
+        "reason": reason,
+        "candidates": candidates,
+    })
+}
+
 // Lightweight HTTP status for gateway health/ops.
 // Full dashboard session details (including client_sessions/model fields) are exposed
 // by the Tauri `get_status` command in `src-tauri/src/lib.rs`.
@@ -1193,7 +1832,8 @@ async fn status(State(st): State<GatewayState>) -> impl IntoResponse {
     }
     let ledgers = st.store.list_ledgers();
     let last_activity = st.last_activity_unix_ms.load(Ordering::Relaxed);
-    let active_recent = last_activity > 0 && now.saturating_sub(last_activity) < 2 * 60 * 1000;
+    let active_recent =
+        last_activity > 0 && now.saturating_sub(last_activity) < cfg.routing.active_window_ms();
     let (active_provider, active_reason) = if active_recent {
         let last = st
             .last_used_by_session
@@ -1231,7 +1871,7 @@ async fn models(
     State(st): State<GatewayState>,
     headers: HeaderMap,
 ) -> impl IntoResponse {
-    if let Some(resp) = require_gateway_auth(&st, &headers) {
+    if let Err(resp) = require_gateway_auth(&st, &headers) {
         return resp;
     }
     let cfg = st.cfg.read().clone();
@@ -1241,13 +1881,7 @@ async fn models(
     let session_key = codex_session_id_from_request(&headers, &Value::Null)
         .unwrap_or_else(|| format!("peer:{peer}"));
 
-    let preferred = cfg
-        .routing
-        .session_preferred_providers
-        .get(&session_key)
-        .filter(|p| cfg.providers.contains_key(*p))
-        .map(|s| s.as_str())
-        .unwrap_or(cfg.routing.preferred_provider.as_str());
+    let preferred = session_preferred_provider(&st, &cfg, &session_key, |_| false);
 
     let (provider_name, _reason) = decide_provider(&st, &cfg, preferred, &session_key);
     let p = match cfg.providers.get(&provider_name) {
@@ -1270,7 +1904,7 @@ async fn models(
     // Do not update `client_sessions` for `/v1/models`.
     // Codex may call it opportunistically, and it may not carry a stable Codex session id.
 
-    let timeout = cfg.routing.request_timeout_seconds;
+    let timeouts = crate::orchestrator::upstream::UpstreamTimeouts::from_routing(&cfg.routing);
     match st
         .upstream
         .get_json(
@@ -1278,11 +1912,12 @@ async fn models(
             crate::orchestrator::upstream::MODELS_ENDPOINT,
             api_key.as_deref(),
             client_auth,
-            timeout,
+            timeouts,
         )
         .await
     {
         Ok((code, j)) if (200..300).contains(&code) => {
+            record_provider_models(&st, &provider_name, &j, unix_ms());
             // Do not update `last_used_by_session` for `/v1/models` since Codex may call it
             // opportunistically. We only want to track actual routing decisions for user
             // requests (/v1/responses) to keep "back to preferred" semantics stable. It also
@@ -1298,6 +1933,19 @@ async fn models(
 mod routing_and_status_tests {
     use super::*;
 
+    #[test]
+    fn model_ids_from_listing_sorts_and_dedups_ids() {
+        let payload = json!({
+            "object": "list",
+            "data": [{"id": "gpt-5"}, {"id": " gpt-4.1 "}, {"id": "gpt-5"}, {"id": ""}, {}]
+        });
+        assert_eq!(
+            model_ids_from_listing(&payload),
+            Some(vec!["gpt-4.1".to_string(), "gpt-5".to_string()])
+        );
+        assert_eq!(model_ids_from_listing(&json!({"error": "nope"})), None);
+    }
+
     #[test]
     fn session_demand_ratio_defaults_and_is_bounded() {
         let fresh = session_demand_ratio_from_usage(0, 0);
@@ -1545,6 +2193,14 @@ mod routing_and_status_tests {
                 group: None,
                 disabled: false,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
         let hard_cap = crate::orchestrator::secrets::ProviderQuotaHardCapConfig::default();
@@ -1618,6 +2274,7 @@ mod routing_and_status_tests {
             last_used_by_session: std::sync::Arc::new(parking_lot::RwLock::new(HashMap::new())),
             usage_base_speed_cache: std::sync::Arc::new(parking_lot::RwLock::new(HashMap::new())),
             prev_id_support_cache: std::sync::Arc::new(parking_lot::RwLock::new(HashMap::new())),
+            provider_models_cache: std::sync::Arc::new(parking_lot::RwLock::new(HashMap::new())),
             client_sessions: std::sync::Arc::new(parking_lot::RwLock::new(HashMap::new())),
         };
         let mut pricing = std::collections::BTreeMap::new();
@@ -1629,6 +2286,8 @@ mod routing_and_status_tests {
                 periods: Vec::new(),
                 gap_fill_mode: None,
                 gap_fill_amount_usd: None,
+                tiers: Vec::new(),
+                model_rates: std::collections::BTreeMap::new(),
             },
         );
         pricing.insert(
@@ -1646,6 +2305,8 @@ mod routing_and_status_tests {
                 }],
                 gap_fill_mode: None,
                 gap_fill_amount_usd: None,
+                tiers: Vec::new(),
+                model_rates: std::collections::BTreeMap::new(),
             },
         );
         pricing.insert(
@@ -1656,6 +2317,8 @@ mod routing_and_status_tests {
                 periods: Vec::new(),
                 gap_fill_mode: Some("per_request".to_string()),
                 gap_fill_amount_usd: Some(0.03),
+                tiers: Vec::new(),
+                model_rates: std::collections::BTreeMap::new(),
             },
         );
         pricing.insert(
@@ -1666,6 +2329,8 @@ mod routing_and_status_tests {
                 periods: Vec::new(),
                 gap_fill_mode: Some("per_request".to_string()),
                 gap_fill_amount_usd: None,
+                tiers: Vec::new(),
+                model_rates: std::collections::BTreeMap::new(),
             },
         );
         pricing.insert(
@@ -1676,6 +2341,29 @@ mod routing_and_status_tests {
                 periods: Vec::new(),
                 gap_fill_mode: Some("per_request".to_string()),
                 gap_fill_amount_usd: Some(0.0),
+                tiers: Vec::new(),
+                model_rates: std::collections::BTreeMap::new(),
+            },
+        );
+        pricing.insert(
+            "tiered".to_string(),
+            crate::orchestrator::secrets::ProviderPricingConfig {
+                mode: "per_request_tiered".to_string(),
+                amount_usd: 0.05,
+                periods: Vec::new(),
+                gap_fill_mode: None,
+                gap_fill_amount_usd: None,
+                tiers: vec![
+                    crate::orchestrator::secrets::ProviderPricingTier {
+                        up_to_requests: Some(10),
+                        usd_per_request: 0.05,
+                    },
+                    crate::orchestrator::secrets::ProviderPricingTier {
+                        up_to_requests: None,
+                        usd_per_request: 0.01,
+                    },
+                ],
+                model_rates: std::collections::BTreeMap::new(),
             },
         );
 
@@ -1697,5 +2385,10 @@ mod routing_and_status_tests {
             None
         );
         assert_eq!(provider_per_request_cost_signal(&state, &pricing, "missing", now), None);
+        // No requests yet today, so the next one is billed at the first tier's rate.
+        assert_eq!(
+            provider_per_request_cost_signal(&state, &pricing, "tiered", now),
+            Some(0.05)
+        );
     }
 }
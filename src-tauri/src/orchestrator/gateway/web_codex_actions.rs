@@ -1561,6 +1561,7 @@ mod tests {
             last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
             usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
             prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+            provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
             client_sessions: Arc::new(RwLock::new(HashMap::new())),
         }
     }
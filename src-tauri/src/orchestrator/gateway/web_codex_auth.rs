@@ -64,28 +64,28 @@ pub(super) fn auth_cookie_token(headers: &HeaderMap) -> Option<&str> {
     None
 }
 
-fn matches_expected_auth_token(
-    expected: &str,
+/// True when the request carries a live gateway credential (primary or named), or when no
+/// credential is configured. Matches the token set accepted by `require_gateway_auth`.
+fn matches_gateway_token(
+    secrets: &SecretStore,
     headers: &HeaderMap,
     query_token: Option<&str>,
 ) -> bool {
-    let expected = expected.trim();
-    if expected.is_empty() {
+    if !secrets.gateway_auth_required() {
         return true;
     }
+    let is_live = |tok: &str| secrets.gateway_token_label(tok).is_some();
     if let Some(tok) = auth_bearer_token(headers) {
-        return tok == expected;
+        return is_live(tok);
     }
     if let Some(tok) = auth_cookie_token(headers) {
-        return tok == expected;
+        return is_live(tok);
     }
-    query_token.map(str::trim) == Some(expected)
+    query_token.is_some_and(is_live)
 }
 
 pub(super) fn require_codex_auth(st: &GatewayState, headers: &HeaderMap) -> Option<Response> {
-    let expected = st.secrets.get_gateway_token()?;
-    let expected = expected.trim();
-    if expected.is_empty() {
+    if !st.secrets.gateway_auth_required() {
         return None;
     }
     let tok = auth_bearer_token(headers).or_else(|| auth_cookie_token(headers));
@@ -95,7 +95,7 @@ pub(super) fn require_codex_auth(st: &GatewayState, headers: &HeaderMap) -> Opti
             "missing or invalid Authorization header",
         ));
     };
-    if tok != expected {
+    if st.secrets.gateway_token_label(tok).is_none() {
         return Some(api_error(StatusCode::UNAUTHORIZED, "invalid token"));
     }
     None
@@ -112,10 +112,7 @@ pub(super) fn is_codex_ws_authorized(
     headers: &HeaderMap,
     query: &WsQuery,
 ) -> bool {
-    let Some(expected) = st.secrets.get_gateway_token() else {
-        return true;
-    };
-    matches_expected_auth_token(&expected, headers, query.token.as_deref())
+    matches_gateway_token(&st.secrets, headers, query.token.as_deref())
 }
 
 #[cfg(test)]
@@ -142,36 +139,62 @@ mod tests {
     }
 
     #[test]
-    fn expected_auth_match_accepts_header_cookie_and_query() {
+    fn gateway_token_match_accepts_header_cookie_and_query() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let secrets = SecretStore::new(tmp.path().join("secrets.json"));
+        secrets.set_gateway_token("expected").expect("primary");
+
         let mut bearer_headers = HeaderMap::new();
         bearer_headers.insert(header::AUTHORIZATION, "Bearer expected".parse().unwrap());
-        assert!(matches_expected_auth_token(
-            "expected",
-            &bearer_headers,
-            None
-        ));
+        assert!(matches_gateway_token(&secrets, &bearer_headers, None));
 
         let mut cookie_headers = HeaderMap::new();
         cookie_headers.insert(
             header::COOKIE,
             "api_router_gateway_token=expected".parse().unwrap(),
         );
-        assert!(matches_expected_auth_token(
-            "expected",
-            &cookie_headers,
-            None
-        ));
+        assert!(matches_gateway_token(&secrets, &cookie_headers, None));
 
         let empty_headers = HeaderMap::new();
-        assert!(matches_expected_auth_token(
-            "expected",
+        assert!(matches_gateway_token(
+            &secrets,
             &empty_headers,
             Some(" expected ")
         ));
-        assert!(!matches_expected_auth_token(
-            "expected",
+        assert!(!matches_gateway_token(
+            &secrets,
             &empty_headers,
             Some("wrong")
         ));
+        assert!(!matches_gateway_token(&secrets, &empty_headers, None));
+    }
+
+    #[test]
+    fn gateway_token_match_accepts_named_tokens_until_revoked() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let secrets = SecretStore::new(tmp.path().join("secrets.json"));
+        let empty_headers = HeaderMap::new();
+        assert!(matches_gateway_token(&secrets, &empty_headers, None));
+
+        secrets.set_gateway_token("ao-primary").expect("primary");
+        let laptop = secrets
+            .create_named_gateway_token("laptop")
+            .expect("create");
+        let mut headers = HeaderMap::new();
+        headers.insert(
+            header::AUTHORIZATION,
+            format!("Bearer {}", laptop.token).parse().unwrap(),
+        );
+        assert!(matches_gateway_token(&secrets, &headers, None));
+        assert!(matches_gateway_token(
+            &secrets,
+            &empty_headers,
+            Some(&laptop.token)
+        ));
+
+        secrets
+            .revoke_named_gateway_token("laptop")
+            .expect("revoke");
+        assert!(!matches_gateway_token(&secrets, &headers, None));
     }
 }
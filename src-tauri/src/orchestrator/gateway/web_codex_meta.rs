@@ -721,6 +721,7 @@ pub(super) async fn codex_provider_switchboard_set(
         homes,
         target,
         req.provider,
+        crate::provider_switchboard::ModelOverride::Keep,
         official_auth,
     ) {
         Ok(value) => {
@@ -1238,6 +1239,7 @@ model_reasoning_effort = "medium"
             last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
             usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
             prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+            provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
             client_sessions: Arc::new(RwLock::new(HashMap::new())),
         };
 
@@ -12,13 +12,116 @@ use serde_json::json;
 pub(crate) struct PreparedGatewayListeners {
     pub(crate) listen_port: u16,
     pub(crate) listeners: Vec<(SocketAddr, std::net::TcpListener)>,
+    #[cfg(unix)]
+    pub(crate) unix_listener: Option<(std::path::PathBuf, std::os::unix::net::UnixListener)>,
+}
+
+/// Binds the configured Unix socket, replacing a stale socket file left by a previous run.
+/// A live socket (another process accepts on it) or a non-socket file at the path is an error.
+#[cfg(unix)]
+pub(crate) fn bind_unix_socket_listener(
+    path: &std::path::Path,
+) -> std::io::Result<std::os::unix::net::UnixListener> {
+    use std::os::unix::fs::{FileTypeExt, PermissionsExt};
+
+    match std::fs::symlink_metadata(path) {
+        Ok(meta) if meta.file_type().is_socket() => {
+            if std::os::unix::net::UnixStream::connect(path).is_ok() {
+                return Err(std::io::Error::new(
+                    ErrorKind::AddrInUse,
+                    format!("{} is already served by another process", path.display()),
+                ));
+            }
+            std::fs::remove_file(path)?;
+        }
+        Ok(_) => {
+            return Err(std::io::Error::new(
+                ErrorKind::AlreadyExists,
+                format!("{} exists and is not a socket", path.display()),
+            ));
+        }
+        Err(err) if err.kind() == ErrorKind::NotFound => {}
+        Err(err) => return Err(err),
+    }
+    if let Some(parent) = path
+        .parent()
+        .filter(|parent| !parent.as_os_str().is_empty())
+    {
+        std::fs::create_dir_all(parent)?;
+    }
+    let listener = std::os::unix::net::UnixListener::bind(path)?;
+    // Owner-only: the socket file is the access-control boundary.
+    std::fs::set_permissions(path, std::fs::Permissions::from_mode(0o600))?;
+    listener.set_nonblocking(true)?;
+    Ok(listener)
+}
+
+/// The Unix socket is additive; failing to bind it (or running on a platform without Unix
+/// sockets) leaves the gateway on TCP only.
+#[cfg(unix)]
+fn prepare_unix_socket_listener(
+    socket_path: Option<&str>,
+) -> Option<(std::path::PathBuf, std::os::unix::net::UnixListener)> {
+    let path = std::path::PathBuf::from(socket_path.map(str::trim).filter(|p| !p.is_empty())?);
+    match bind_unix_socket_listener(&path) {
+        Ok(listener) => {
+            write_gateway_bootstrap_diag(
+                "prepare_gateway_unix_socket_ok",
+                Some(&format!("path={}", path.display())),
+            );
+            Some((path, listener))
+        }
+        Err(err) => {
+            write_gateway_bootstrap_diag(
+                "prepare_gateway_unix_socket_failed",
+                Some(&format!("path={}; {err}", path.display())),
+            );
+            None
+        }
+    }
+}
+
+#[cfg(not(unix))]
+fn prepare_unix_socket_listener(socket_path: Option<&str>) {
+    if let Some(path) = socket_path.map(str::trim).filter(|p| !p.is_empty()) {
+        write_gateway_bootstrap_diag(
+            "prepare_gateway_unix_socket_unsupported",
+            Some(&format!("path={path}")),
+        );
+    }
 }
 
 struct GatewayListenerBindPlan {
     primary: SocketAddr,
+    loopback_partner: Option<SocketAddr>,
     optional: Vec<SocketAddr>,
 }
 
+/// Resolves the configured host to the primary listen address. `localhost` means the IPv4
+/// loopback; its IPv6 sibling is bound alongside (see [`dual_stack_loopback_partner`]).
+fn primary_listen_addr(listen_host: &str, listen_port: u16) -> anyhow::Result<SocketAddr> {
+    let host = listen_host.trim();
+    if host.eq_ignore_ascii_case("localhost") {
+        return Ok(SocketAddr::new(
+            std::net::Ipv4Addr::LOCALHOST.into(),
+            listen_port,
+        ));
+    }
+    let ip: std::net::IpAddr = host.trim_start_matches('[').trim_end_matches(']').parse()?;
+    Ok(SocketAddr::new(ip, listen_port))
+}
+
+/// Clients resolve `localhost` to either `127.0.0.1` or `::1`, so a loopback primary also
+/// listens on the other family's loopback.
+fn dual_stack_loopback_partner(primary: SocketAddr) -> Option<SocketAddr> {
+    let partner: std::net::IpAddr = match primary.ip() {
+        std::net::IpAddr::V4(ip) if ip.is_loopback() => std::net::Ipv6Addr::LOCALHOST.into(),
+        std::net::IpAddr::V6(ip) if ip.is_loopback() => std::net::Ipv4Addr::LOCALHOST.into(),
+        _ => return None,
+    };
+    Some(SocketAddr::new(partner, primary.port()))
+}
+
 #[cfg(windows)]
 fn push_unique_addr(addrs: &mut Vec<SocketAddr>, addr: SocketAddr) {
     if !addrs.contains(&addr) {
@@ -32,7 +135,7 @@ pub(crate) fn tailscale_overlay_listener_addrs(
     listen_port: u16,
     extra_ips: &[IpAddr],
 ) -> anyhow::Result<Vec<SocketAddr>> {
-    let primary: SocketAddr = format!("{listen_host}:{listen_port}").parse()?;
+    let primary = primary_listen_addr(listen_host, listen_port)?;
     if primary.ip().to_string() != crate::constants::GATEWAY_WINDOWS_HOST {
         return Ok(Vec::new());
     }
@@ -51,7 +154,7 @@ pub(crate) fn wsl_overlay_listener_addr(
     listen_port: u16,
     wsl_host: &str,
 ) -> anyhow::Result<Option<SocketAddr>> {
-    let primary: SocketAddr = format!("{listen_host}:{listen_port}").parse()?;
+    let primary = primary_listen_addr(listen_host, listen_port)?;
     if primary.ip().to_string() != crate::constants::GATEWAY_WINDOWS_HOST {
         return Ok(None);
     }
@@ -73,7 +176,7 @@ fn gateway_listen_addrs_with_overlays(
     lan_ip: Option<IpAddr>,
     extra_ips: &[IpAddr],
 ) -> anyhow::Result<Vec<SocketAddr>> {
-    let primary: SocketAddr = format!("{listen_host}:{listen_port}").parse()?;
+    let primary = primary_listen_addr(listen_host, listen_port)?;
     let mut addrs = vec![primary];
 
     if let Some(wsl_addr) = wsl_overlay_listener_addr(listen_host, listen_port, wsl_host)? {
@@ -196,7 +299,7 @@ fn gateway_listen_addrs_with_overlays(
     listen_host: &str,
     listen_port: u16,
 ) -> anyhow::Result<Vec<SocketAddr>> {
-    let primary: SocketAddr = format!("{listen_host}:{listen_port}").parse()?;
+    let primary = primary_listen_addr(listen_host, listen_port)?;
     Ok(vec![primary])
 }
 
@@ -267,20 +370,59 @@ fn gateway_listener_bind_plan(
         .first()
         .copied()
         .ok_or_else(|| anyhow::anyhow!("gateway listen address list was empty"))?;
-    let optional = addrs.drain(1..).collect::<Vec<_>>();
-    Ok(GatewayListenerBindPlan { primary, optional })
+    let loopback_partner = dual_stack_loopback_partner(primary);
+    let optional = addrs
+        .drain(1..)
+        .filter(|addr| Some(*addr) != loopback_partner)
+        .collect::<Vec<_>>();
+    Ok(GatewayListenerBindPlan {
+        primary,
+        loopback_partner,
+        optional,
+    })
+}
+
+fn warn_loopback_family_unavailable(addr: SocketAddr, err: &std::io::Error) {
+    log::warn!(
+        "gateway loopback listener {addr} unavailable; serving the other family only: {err}"
+    );
+    write_gateway_bootstrap_diag(
+        "loopback_gateway_listener_bind_skipped",
+        Some(&format!("addr={addr} err={err}")),
+    );
 }
 
+/// The primary must bind, except that a loopback primary whose address family is unavailable
+/// (not merely in use) hands over to its partner from the other family.
 fn bind_listener_addrs_with_policy<L, F>(
     primary: SocketAddr,
+    loopback_partner: Option<SocketAddr>,
     optional: Vec<SocketAddr>,
     mut bind_one: F,
 ) -> anyhow::Result<Vec<(SocketAddr, L)>>
 where
     F: FnMut(SocketAddr) -> std::io::Result<L>,
 {
-    let primary_listener = bind_one(primary)?;
-    let mut listeners = vec![(primary, primary_listener)];
+    let mut listeners = match (bind_one(primary), loopback_partner) {
+        (Ok(listener), partner) => {
+            let mut listeners = vec![(primary, listener)];
+            if let Some(partner) = partner {
+                match bind_one(partner) {
+                    Ok(listener) => listeners.push((partner, listener)),
+                    Err(err) => warn_loopback_family_unavailable(partner, &err),
+                }
+            }
+            listeners
+        }
+        (Err(err), Some(partner)) if err.kind() != ErrorKind::AddrInUse => {
+            let Ok(listener) = bind_one(partner) else {
+                return Err(err.into());
+            };
+            warn_loopback_family_unavailable(primary, &err);
+            vec![(partner, listener)]
+        }
+        (Err(err), _) => return Err(err.into()),
+    };
     for addr in optional {
         match bind_one(addr) {
             Ok(listener) => listeners.push((addr, listener)),
@@ -331,11 +473,16 @@ fn try_bind_gateway_listeners(
         Some(&format!("host={listen_host} port={listen_port}")),
     );
     let plan = gateway_listener_bind_plan(listen_host, listen_port, config_path)?;
-    let listeners = bind_listener_addrs_with_policy(plan.primary, plan.optional, |addr| {
-        let listener = std::net::TcpListener::bind(addr)?;
-        listener.set_nonblocking(true)?;
-        Ok(listener)
-    })?;
+    let listeners = bind_listener_addrs_with_policy(
+        plan.primary,
+        plan.loopback_partner,
+        plan.optional,
+        |addr| {
+            let listener = std::net::TcpListener::bind(addr)?;
+            listener.set_nonblocking(true)?;
+            Ok(listener)
+        },
+    )?;
     write_gateway_bootstrap_diag(
         "try_bind_gateway_listeners_ok",
         Some(
@@ -353,12 +500,12 @@ fn bind_fallback_gateway_listeners(
     listen_host: &str,
     config_path: Option<&std::path::Path>,
 ) -> anyhow::Result<Vec<(SocketAddr, std::net::TcpListener)>> {
-    let primary = std::net::TcpListener::bind(format!("{listen_host}:0"))?;
+    let primary = std::net::TcpListener::bind(primary_listen_addr(listen_host, 0)?)?;
     let primary_addr = primary.local_addr()?;
     primary.set_nonblocking(true)?;
     let plan = gateway_listener_bind_plan(listen_host, primary_addr.port(), config_path)?;
     let mut primary_listener = Some(primary);
-    bind_listener_addrs_with_policy(plan.primary, plan.optional, |addr| {
+    bind_listener_addrs_with_policy(plan.primary, plan.loopback_partner, plan.optional, |addr| {
         if addr == primary_addr {
             let listener = primary_listener
                 .take()
@@ -416,16 +563,23 @@ pub(crate) fn prepare_gateway_listeners(
         "prepare_gateway_listeners_ok",
         Some(&format!("listen_port={listen_port}")),
     );
+    #[cfg(unix)]
+    let unix_listener = prepare_unix_socket_listener(cfg.listen.unix_socket_path.as_deref());
+    #[cfg(not(unix))]
+    prepare_unix_socket_listener(cfg.listen.unix_socket_path.as_deref());
     Ok(PreparedGatewayListeners {
         listen_port,
         listeners: bound,
+        #[cfg(unix)]
+        unix_listener,
     })
 }
 
 #[cfg(test)]
 mod tests {
     use super::{
-        bind_listener_addrs_with_policy, gateway_listen_addrs, persist_gateway_runtime_port,
+        bind_listener_addrs_with_policy, dual_stack_loopback_partner, gateway_listen_addrs,
+        persist_gateway_runtime_port, primary_listen_addr,
     };
     #[cfg(windows)]
     use super::{
@@ -582,6 +736,7 @@ mod tests {
         let valid_optional: SocketAddr = "192.168.3.137:4000".parse().unwrap();
         let listeners = bind_listener_addrs_with_policy(
             primary,
+            None,
             vec![invalid_optional, valid_optional],
             |addr| {
                 if addr == invalid_optional {
@@ -606,7 +761,7 @@ mod tests {
     #[test]
     fn bind_listener_policy_still_fails_when_primary_bind_fails() {
         let primary: SocketAddr = "127.0.0.1:4000".parse().unwrap();
-        let result = bind_listener_addrs_with_policy(primary, Vec::new(), |_addr| {
+        let result = bind_listener_addrs_with_policy(primary, None, Vec::new(), |_addr| {
             Err::<SocketAddr, std::io::Error>(std::io::Error::new(
                 ErrorKind::AddrInUse,
                 "Only one usage of each socket address is normally permitted",
@@ -615,6 +770,72 @@ mod tests {
         assert!(result.is_err());
     }
 
+    #[test]
+    fn bind_listener_policy_serves_whichever_loopback_family_is_available() {
+        let v4: SocketAddr = "127.0.0.1:4000".parse().unwrap();
+        let v6: SocketAddr = "[::1]:4000".parse().unwrap();
+        let unavailable = |missing: SocketAddr| {
+            move |addr: SocketAddr| {
+                if addr == missing {
+                    Err(std::io::Error::new(
+                        ErrorKind::AddrNotAvailable,
+                        "Cannot assign requested address",
+                    ))
+                } else {
+                    Ok(addr)
+                }
+            }
+        };
+        let bound_addrs = |listeners: Vec<(SocketAddr, SocketAddr)>| {
+            listeners
+                .into_iter()
+                .map(|(addr, _)| addr)
+                .collect::<Vec<_>>()
+        };
+
+        let both = bind_listener_addrs_with_policy(v4, Some(v6), Vec::new(), Ok)
+            .expect("both loopback families bind");
+        assert_eq!(bound_addrs(both), vec![v4, v6]);
+
+        let v4_only = bind_listener_addrs_with_policy(v4, Some(v6), Vec::new(), unavailable(v6))
+            .expect("missing ipv6 keeps ipv4");
+        assert_eq!(bound_addrs(v4_only), vec![v4]);
+
+        let v6_only = bind_listener_addrs_with_policy(v4, Some(v6), Vec::new(), unavailable(v4))
+            .expect("missing ipv4 falls over to ipv6");
+        assert_eq!(bound_addrs(v6_only), vec![v6]);
+
+        let in_use = bind_listener_addrs_with_policy(v4, Some(v6), Vec::new(), |addr| {
+            if addr == v4 {
+                Err(std::io::Error::from(ErrorKind::AddrInUse))
+            } else {
+                Ok(addr)
+            }
+        });
+        assert!(
+            in_use.is_err(),
+            "a port conflict must not be masked by ipv6"
+        );
+    }
+
+    #[test]
+    fn localhost_and_ipv6_hosts_resolve_to_dual_stack_loopback() {
+        let localhost = primary_listen_addr("localhost", 4000).expect("localhost");
+        assert_eq!(localhost, "127.0.0.1:4000".parse::<SocketAddr>().unwrap());
+        assert_eq!(
+            dual_stack_loopback_partner(localhost),
+            Some("[::1]:4000".parse().unwrap())
+        );
+        let v6 = primary_listen_addr("[::1]", 4000).expect("bracketed ipv6");
+        assert_eq!(v6, primary_listen_addr("::1", 4000).expect("bare ipv6"));
+        assert_eq!(
+            dual_stack_loopback_partner(v6),
+            Some("127.0.0.1:4000".parse().unwrap())
+        );
+        let lan = primary_listen_addr("0.0.0.0", 4000).expect("wildcard");
+        assert_eq!(dual_stack_loopback_partner(lan), None);
+    }
+
     #[test]
     fn persist_gateway_runtime_port_rekeys_ui_watchdog_state() {
         let tmp = tempfile::tempdir().expect("tempdir");
@@ -645,4 +866,32 @@ mod tests {
         );
         assert_eq!(state.gateway.cfg.read().listen.port, reassigned_port);
     }
+
+    #[cfg(unix)]
+    #[test]
+    fn unix_socket_bind_replaces_stale_socket_and_refuses_live_or_foreign_files() {
+        use std::os::unix::fs::PermissionsExt;
+
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let path = tmp.path().join("run").join("gateway.sock");
+
+        // Stale: a socket file whose listener is gone.
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        drop(std::os::unix::net::UnixListener::bind(&path).expect("seed stale socket"));
+        let listener = super::bind_unix_socket_listener(&path).expect("rebind stale socket");
+        let mode = std::fs::metadata(&path).unwrap().permissions().mode() & 0o777;
+        assert_eq!(mode, 0o600);
+
+        // Live: another listener still accepts on it.
+        let err = super::bind_unix_socket_listener(&path).expect_err("live socket");
+        assert_eq!(err.kind(), ErrorKind::AddrInUse);
+        drop(listener);
+
+        // Foreign: never delete a regular file that happens to sit at the path.
+        let file_path = tmp.path().join("not-a-socket");
+        std::fs::write(&file_path, b"keep").unwrap();
+        let err = super::bind_unix_socket_listener(&file_path).expect_err("regular file");
+        assert_eq!(err.kind(), ErrorKind::AlreadyExists);
+        assert_eq!(std::fs::read(&file_path).unwrap(), b"keep");
+    }
 }
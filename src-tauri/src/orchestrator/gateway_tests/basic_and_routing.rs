@@ -16,6 +16,7 @@ async fn health_and_status_work_without_upstream() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -51,6 +52,352 @@ async fn health_and_status_work_without_upstream() {
     assert!(json.get("windows_firewall").is_some());
 }
 
+#[tokio::test]
+async fn deep_health_reflects_router_provider_health() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = open_store_dir(tmp.path().join("data")).expect("store");
+    let secrets = SecretStore::new(tmp.path().join("secrets.json"));
+
+    let cfg = AppConfig::default_config();
+    let provider = crate::orchestrator::router::provider_iteration_order(&cfg)
+        .into_iter()
+        .next()
+        .expect("default config has an enabled provider");
+    let router = Arc::new(RouterState::new(&cfg, unix_ms()));
+    let state = GatewayState {
+        cfg: Arc::new(RwLock::new(cfg)),
+        router: router.clone(),
+        store,
+        upstream: UpstreamClient::new(),
+        secrets,
+        last_activity_unix_ms: Arc::new(AtomicU64::new(0)),
+        last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
+        usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
+        prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
+        client_sessions: Arc::new(RwLock::new(HashMap::new())),
+    };
+    let app = build_router(state);
+
+    // The shallow probe only reports liveness.
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/health")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/health?deep=1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::SERVICE_UNAVAILABLE);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .expect("health body");
+    let json: serde_json::Value = serde_json::from_slice(&body).expect("health json");
+    assert_eq!(json["ok"], false);
+    assert!(json["providers"]
+        .as_array()
+        .expect("providers")
+        .iter()
+        .any(|p| p["provider"] == provider.as_str() && p["healthy"] == false));
+
+    router.mark_success(&provider, unix_ms());
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri("/health?deep=1")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .expect("health body");
+    let json: serde_json::Value = serde_json::from_slice(&body).expect("health json");
+    assert_eq!(json["ok"], true);
+}
+
+#[tokio::test]
+async fn provider_probe_route_reports_status_and_latency() {
+    let upstream = Router::new().route(
+        "/v1/models",
+        axum::routing::get(|| async { Json(json!({"object": "list", "data": []})) }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, upstream).await;
+    });
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = open_store_dir(tmp.path().join("data")).expect("store");
+    let secrets = SecretStore::new(tmp.path().join("secrets.json"));
+
+    let mut cfg = AppConfig::default_config();
+    let provider = crate::orchestrator::router::provider_iteration_order(&cfg)
+        .into_iter()
+        .next()
+        .expect("default config has an enabled provider");
+    cfg.providers
+        .get_mut(&provider)
+        .expect("provider config")
+        .base_url = format!("http://{addr}");
+    let router = Arc::new(RouterState::new(&cfg, unix_ms()));
+    let state = GatewayState {
+        cfg: Arc::new(RwLock::new(cfg)),
+        router: router.clone(),
+        store,
+        upstream: UpstreamClient::new(),
+        secrets,
+        last_activity_unix_ms: Arc::new(AtomicU64::new(0)),
+        last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
+        usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
+        prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
+        client_sessions: Arc::new(RwLock::new(HashMap::new())),
+    };
+    let secrets = state.secrets.clone();
+    let app = build_router(state);
+
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/providers/{provider}/probe"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let body = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .expect("probe body");
+    let json: serde_json::Value = serde_json::from_slice(&body).expect("probe json");
+    assert_eq!(json["ok"], true);
+    assert_eq!(json["status"], 200);
+    assert!(json["latencyMs"].is_u64());
+    assert_eq!(
+        router
+            .snapshot(unix_ms())
+            .get(&provider)
+            .map(|h| h.status.as_str()),
+        Some("healthy")
+    );
+
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/providers/missing/probe")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NOT_FOUND);
+
+    secrets
+        .set_gateway_token("test-token")
+        .expect("set gateway token");
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri(format!("/providers/{provider}/probe"))
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::UNAUTHORIZED);
+}
+
+#[tokio::test]
+async fn provider_probe_does_not_count_as_usage() {
+    let upstream = Router::new().route(
+        "/v1/models",
+        axum::routing::get(|| async { Json(json!({"object": "list", "data": []})) }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, upstream).await;
+    });
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = open_store_dir(tmp.path().join("data")).expect("store");
+    let secrets = SecretStore::new(tmp.path().join("secrets.json"));
+
+    let mut cfg = AppConfig::default_config();
+    let provider = crate::orchestrator::router::provider_iteration_order(&cfg)
+        .into_iter()
+        .next()
+        .expect("default config has an enabled provider");
+    cfg.providers
+        .get_mut(&provider)
+        .expect("provider config")
+        .base_url = format!("http://{addr}");
+    let router = Arc::new(RouterState::new(&cfg, unix_ms()));
+    let state = GatewayState {
+        cfg: Arc::new(RwLock::new(cfg)),
+        router,
+        store: store.clone(),
+        upstream: UpstreamClient::new(),
+        secrets,
+        last_activity_unix_ms: Arc::new(AtomicU64::new(0)),
+        last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
+        usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
+        prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
+        client_sessions: Arc::new(RwLock::new(HashMap::new())),
+    };
+    let provider_request_count = || {
+        store
+            .list_usage_request_stats_rows_window(0)
+            .iter()
+            .filter(|row| row.provider == provider)
+            .count()
+    };
+    let before = provider_request_count();
+
+    let outcome = crate::orchestrator::gateway::probe_provider_health(&state, &provider).await;
+
+    assert!(outcome.is_ok());
+    assert_eq!(provider_request_count(), before);
+    assert!(store.list_usage_days(&provider).is_empty());
+}
+
+#[tokio::test]
+async fn otel_endpoint_exports_span_per_proxied_request() {
+    let upstream = Router::new().route(
+        "/v1/responses",
+        post(|| async {
+            Json(json!({
+                "id": "resp_otel",
+                "model": "gpt-otel",
+                "output": [{"content": [{"type": "output_text", "text": "ok"}]}],
+                "usage": {"input_tokens": 3, "output_tokens": 5, "total_tokens": 8}
+            }))
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let upstream_addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, upstream).await;
+    });
+
+    let exported = Arc::new(Mutex::new(Vec::<serde_json::Value>::new()));
+    let exported2 = exported.clone();
+    let collector = Router::new().route(
+        "/v1/traces",
+        post(move |Json(body): Json<serde_json::Value>| {
+            exported2.lock().push(body);
+            async { StatusCode::OK }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let collector_addr = listener.local_addr().unwrap();
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, collector).await;
+    });
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = open_store_dir(tmp.path().join("data")).expect("store");
+    let secrets = SecretStore::new(tmp.path().join("secrets.json"));
+
+    let mut cfg = AppConfig::default_config();
+    let provider = crate::orchestrator::router::provider_iteration_order(&cfg)
+        .into_iter()
+        .next()
+        .expect("default config has an enabled provider");
+    cfg.providers
+        .get_mut(&provider)
+        .expect("provider config")
+        .base_url = format!("http://{upstream_addr}/v1");
+    cfg.routing.preferred_provider = provider.clone();
+    cfg.routing.otel_endpoint = Some(format!("http://{collector_addr}"));
+    let router = Arc::new(RouterState::new(&cfg, unix_ms()));
+    let state = GatewayState {
+        cfg: Arc::new(RwLock::new(cfg)),
+        router,
+        store,
+        upstream: UpstreamClient::new(),
+        secrets,
+        last_activity_unix_ms: Arc::new(AtomicU64::new(0)),
+        last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
+        usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
+        prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
+        client_sessions: Arc::new(RwLock::new(HashMap::new())),
+    };
+    let app = build_router(state);
+
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri("/v1/responses")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    json!({"model": "gpt-otel", "input": "hi", "stream": false}).to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .expect("response body");
+
+    let deadline = std::time::Instant::now() + std::time::Duration::from_secs(5);
+    while exported.lock().is_empty() && std::time::Instant::now() < deadline {
+        tokio::time::sleep(std::time::Duration::from_millis(20)).await;
+    }
+    let exported = exported.lock();
+    assert_eq!(exported.len(), 1);
+    let spans = exported[0]["resourceSpans"][0]["scopeSpans"][0]["spans"]
+        .as_array()
+        .expect("spans");
+    let attr = |span: &serde_json::Value, key: &str| {
+        span["attributes"]
+            .as_array()
+            .and_then(|attrs| attrs.iter().find(|a| a["key"] == key))
+            .map(|a| a["value"].clone())
+            .unwrap_or_default()
+    };
+    let root = &spans[0];
+    assert_eq!(root["name"], "POST /v1/responses");
+    assert_eq!(attr(root, "http.response.status_code")["intValue"], "200");
+    assert_eq!(
+        attr(root, "apirouter.provider")["stringValue"],
+        provider.as_str()
+    );
+    assert_eq!(attr(root, "apirouter.model")["stringValue"], "gpt-otel");
+    assert_eq!(attr(root, "apirouter.usage.total_tokens")["intValue"], "8");
+    assert!(spans
+        .iter()
+        .any(|span| span["name"] == "upstream http" && span["parentSpanId"].is_string()));
+}
+
 #[tokio::test]
 async fn codex_transport_events_route_records_web_transport_metrics() {
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -72,6 +419,7 @@ async fn codex_transport_events_route_records_web_transport_metrics() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -113,6 +461,72 @@ async fn codex_transport_events_route_records_web_transport_metrics() {
     assert!(after_count > before_count);
 }
 
+#[tokio::test]
+async fn gateway_accepts_named_tokens_until_revoked() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = open_store_dir(tmp.path().join("data")).expect("store");
+    let secrets = SecretStore::new(tmp.path().join("secrets.json"));
+    secrets
+        .set_gateway_token("test-token")
+        .expect("set gateway token");
+    let laptop = secrets
+        .create_named_gateway_token("laptop")
+        .expect("create named token");
+
+    let cfg = AppConfig::default_config();
+    let router = Arc::new(RouterState::new(&cfg, unix_ms()));
+    let state = GatewayState {
+        cfg: Arc::new(RwLock::new(cfg)),
+        router,
+        store,
+        upstream: UpstreamClient::new(),
+        secrets: secrets.clone(),
+        last_activity_unix_ms: Arc::new(AtomicU64::new(0)),
+        last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
+        usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
+        prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
+        client_sessions: Arc::new(RwLock::new(HashMap::new())),
+    };
+    let app = build_router(state);
+    let models_status = |token: String| {
+        let app = app.clone();
+        async move {
+            app.oneshot(
+                Request::builder()
+                    .uri("/v1/models")
+                    .header("authorization", format!("Bearer {token}"))
+                    .body(Body::empty())
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+            .status()
+        }
+    };
+
+    assert_ne!(
+        models_status(laptop.token.clone()).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_ne!(
+        models_status("test-token".to_string()).await,
+        StatusCode::UNAUTHORIZED
+    );
+    assert_eq!(
+        models_status("not-a-token".to_string()).await,
+        StatusCode::UNAUTHORIZED
+    );
+
+    secrets
+        .revoke_named_gateway_token("laptop")
+        .expect("revoke named token");
+    assert_eq!(
+        models_status(laptop.token.clone()).await,
+        StatusCode::UNAUTHORIZED
+    );
+}
+
 #[tokio::test]
 async fn models_probe_does_not_update_last_ok_or_activity() {
     use axum::routing::get;
@@ -156,6 +570,7 @@ async fn models_probe_does_not_update_last_ok_or_activity() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -228,6 +643,7 @@ async fn responses_fallback_from_ws_to_http_records_http_transport_and_event() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -340,6 +756,7 @@ async fn responses_with_previous_response_id_skip_ws_attempts() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -466,6 +883,7 @@ async fn responses_fallback_from_failed_ws_response_done_to_http() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -611,6 +1029,7 @@ async fn responses_stream_via_websocket_records_ws_transport() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -760,6 +1179,7 @@ async fn responses_stream_http_retry_does_not_repeat_failed_ws_attempt() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -833,6 +1253,7 @@ async fn auth_verify_returns_while_history_read_is_blocked() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -919,11 +1340,43 @@ async fn auth_verify_returns_while_history_read_is_blocked() {
     crate::orchestrator::gateway::_set_test_web_codex_history_loader(None);
 }
 
+#[test]
+fn route_reason_wire_names_are_stable_snake_case() {
+    for (reason, wire) in [
+        (RouteReason::PreferredHealthy, "preferred_healthy"),
+        (RouteReason::SessionPreferred, "session_preferred"),
+        (RouteReason::PreferredUnhealthy, "preferred_unhealthy"),
+        (RouteReason::PreferredStabilizing, "preferred_stabilizing"),
+        (RouteReason::ManualOverride, "manual_override"),
+        (
+            RouteReason::ManualOverrideUnhealthy,
+            "manual_override_unhealthy",
+        ),
+        (RouteReason::BalancedAuto, "balanced_auto"),
+        (
+            RouteReason::SessionInvalidRequestFallback,
+            "session_invalid_request_fallback",
+        ),
+        (RouteReason::ModelNotAllowed, "model_not_allowed"),
+        (RouteReason::NoRoutableProvider, "no_routable_provider"),
+    ] {
+        assert_eq!(json!(reason), json!(wire));
+        assert_eq!(reason.to_string(), wire);
+        assert_eq!(RouteReason::from_wire(wire), Some(reason));
+    }
+    assert_eq!(RouteReason::from_wire("seed"), None);
+    assert!(RouteReason::PreferredUnhealthy.is_failover());
+    assert!(RouteReason::ManualOverrideUnhealthy.is_failover());
+    assert!(!RouteReason::ManualOverride.is_failover());
+    assert!(!RouteReason::BalancedAuto.is_failover());
+    assert!(!RouteReason::SessionPreferred.is_failover());
+}
+
 #[test]
 fn routing_info_event_only_logs_when_route_state_changes() {
     let last = LastUsedRoute {
         provider: "packycode".to_string(),
-        reason: "preferred_unhealthy".to_string(),
+        reason: RouteReason::PreferredUnhealthy,
         preferred: "openai".to_string(),
         unix_ms: unix_ms(),
     };
@@ -931,28 +1384,28 @@ fn routing_info_event_only_logs_when_route_state_changes() {
     assert!(should_log_routing_path_event(
         None,
         "packycode",
-        "preferred_unhealthy",
+        RouteReason::PreferredUnhealthy,
         "openai",
         true,
     ));
     assert!(!should_log_routing_path_event(
         Some(&last),
         "packycode",
-        "preferred_unhealthy",
+        RouteReason::PreferredUnhealthy,
         "openai",
         true,
     ));
     assert!(!should_log_routing_path_event(
         Some(&last),
         "packycode",
-        "preferred_unhealthy",
+        RouteReason::PreferredUnhealthy,
         "openai",
         false,
     ));
     assert!(should_log_routing_path_event(
         Some(&last),
         "another-provider",
-        "preferred_unhealthy",
+        RouteReason::PreferredUnhealthy,
         "openai",
         true,
     ));
@@ -960,7 +1413,7 @@ fn routing_info_event_only_logs_when_route_state_changes() {
     assert!(!should_log_routing_path_event(
         Some(&last),
         "openai",
-        "preferred_healthy",
+        RouteReason::PreferredHealthy,
         "openai",
         true,
     ));
@@ -989,6 +1442,14 @@ fn decide_provider_holds_fallback_during_preferred_stabilizing_window() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
     providers.insert(
@@ -1002,6 +1463,14 @@ fn decide_provider_holds_fallback_during_preferred_stabilizing_window() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
 
@@ -1009,6 +1478,7 @@ fn decide_provider_holds_fallback_during_preferred_stabilizing_window() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1020,9 +1490,38 @@ fn decide_provider_holds_fallback_during_preferred_stabilizing_window() {
             failure_threshold: 2,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let router = Arc::new(RouterState::new(&cfg, unix_ms()));
@@ -1039,19 +1538,20 @@ fn decide_provider_holds_fallback_during_preferred_stabilizing_window() {
             "s1".to_string(),
             LastUsedRoute {
                 provider: "p2".to_string(),
-                reason: "preferred_unhealthy".to_string(),
+                reason: RouteReason::PreferredUnhealthy,
                 preferred: "p1".to_string(),
                 unix_ms: unix_ms(),
             },
         )]))),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
     let (picked, reason) = decide_provider(&state, &cfg, "p1", "s1");
     assert_eq!(picked, "p2");
-    assert_eq!(reason, "preferred_stabilizing");
+    assert_eq!(reason, RouteReason::PreferredStabilizing);
 }
 
 #[test]
@@ -1072,6 +1572,14 @@ fn decide_provider_keeps_fallback_when_last_reason_already_preferred_stabilizing
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
     providers.insert(
@@ -1085,6 +1593,14 @@ fn decide_provider_keeps_fallback_when_last_reason_already_preferred_stabilizing
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
 
@@ -1092,6 +1608,7 @@ fn decide_provider_keeps_fallback_when_last_reason_already_preferred_stabilizing
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1102,9 +1619,38 @@ fn decide_provider_keeps_fallback_when_last_reason_already_preferred_stabilizing
             failure_threshold: 2,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let router = Arc::new(RouterState::new(&cfg, unix_ms()));
@@ -1121,19 +1667,212 @@ fn decide_provider_keeps_fallback_when_last_reason_already_preferred_stabilizing
             "s1".to_string(),
             LastUsedRoute {
                 provider: "p2".to_string(),
-                reason: "preferred_stabilizing".to_string(),
+                reason: RouteReason::PreferredStabilizing,
                 preferred: "p1".to_string(),
                 unix_ms: unix_ms(),
             },
         )]))),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
     let (picked, reason) = decide_provider(&state, &cfg, "p1", "s1");
     assert_eq!(picked, "p2");
-    assert_eq!(reason, "preferred_stabilizing");
+    assert_eq!(reason, RouteReason::PreferredStabilizing);
+}
+
+#[test]
+fn decide_provider_reevaluates_session_route_after_sticky_ttl() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = open_store_dir(tmp.path().join("data")).expect("store");
+    let secrets = SecretStore::new(tmp.path().join("secrets.json"));
+
+    let mut providers = std::collections::BTreeMap::new();
+    providers.insert(
+        "p1".to_string(),
+        ProviderConfig {
+            display_name: "P1".to_string(),
+            base_url: "https://example.com".to_string(),
+            group: None,
+            disabled: false,
+            supports_websockets: false,
+            usage_adapter: String::new(),
+            usage_base_url: None,
+            api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
+        },
+    );
+    providers.insert(
+        "p2".to_string(),
+        ProviderConfig {
+            display_name: "P2".to_string(),
+            base_url: "https://example.com".to_string(),
+            group: None,
+            disabled: false,
+            supports_websockets: false,
+            usage_adapter: String::new(),
+            usage_base_url: None,
+            api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
+        },
+    );
+
+    let cfg = AppConfig {
+        listen: ListenConfig {
+            host: "127.0.0.1".to_string(),
+            port: 4000,
+            unix_socket_path: None,
+        },
+        routing: RoutingConfig {
+            preferred_provider: "p1".to_string(),
+            session_preferred_providers: std::collections::BTreeMap::new(),
+            route_mode: crate::orchestrator::config::RouteMode::FollowPreferredAuto,
+            auto_return_to_preferred: true,
+            preferred_stable_seconds: 3600,
+            failure_threshold: 2,
+            cooldown_seconds: 30,
+            request_timeout_seconds: 300,
+            session_sticky_ttl_seconds: 60,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
+        },
+        providers,
+        provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
+    };
+
+    let router = Arc::new(RouterState::new(&cfg, unix_ms()));
+    let now = unix_ms();
+    router.mark_failure("p1", &cfg, "boom", now);
+    let state = GatewayState {
+        cfg: Arc::new(RwLock::new(cfg.clone())),
+        router,
+        store,
+        upstream: UpstreamClient::new(),
+        secrets,
+        last_activity_unix_ms: Arc::new(AtomicU64::new(0)),
+        last_used_by_session: Arc::new(RwLock::new(HashMap::from([(
+            "s1".to_string(),
+            LastUsedRoute {
+                provider: "p2".to_string(),
+                reason: RouteReason::PreferredStabilizing,
+                preferred: "p1".to_string(),
+                unix_ms: now - 120_000,
+            },
+        )]))),
+        usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
+        prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
+        client_sessions: Arc::new(RwLock::new(HashMap::new())),
+    };
+
+    let (picked, reason) = decide_provider(&state, &cfg, "p1", "s1");
+    assert_eq!(picked, "p1");
+    assert_eq!(reason, RouteReason::PreferredHealthy);
+}
+
+#[test]
+fn persisted_session_routes_reload_and_prune_after_restart() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = open_store_dir(tmp.path().join("data")).expect("store");
+    let secrets = SecretStore::new(tmp.path().join("secrets.json"));
+
+    let mut cfg = AppConfig::default_config();
+    cfg.routing.session_sticky_ttl_seconds = 60;
+    let now = unix_ms();
+    let state = GatewayState {
+        cfg: Arc::new(RwLock::new(cfg.clone())),
+        router: Arc::new(RouterState::new(&cfg, now)),
+        store,
+        upstream: UpstreamClient::new(),
+        secrets,
+        last_activity_unix_ms: Arc::new(AtomicU64::new(0)),
+        last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
+        usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
+        prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
+        client_sessions: Arc::new(RwLock::new(HashMap::new())),
+    };
+    let route = |provider: &str, unix_ms: u64| LastUsedRoute {
+        provider: provider.to_string(),
+        reason: RouteReason::PreferredHealthy,
+        preferred: "official".to_string(),
+        unix_ms,
+    };
+    assert!(record_last_used_route(&state, "s-fresh", route("official", now - 1_000)).is_none());
+    record_last_used_route(&state, "s-stale", route("official", now - 120_000));
+    record_last_used_route(&state, "s-removed-provider", route("gone", now - 1_000));
+
+    // A fresh gateway only sees what was written to the store.
+    let reloaded = load_persisted_last_used_routes(&state.store, &cfg, now);
+    assert_eq!(reloaded.len(), 1);
+    let fresh = reloaded.get("s-fresh").expect("fresh route reloaded");
+    assert_eq!(fresh.provider, "official");
+    assert_eq!(fresh.reason, RouteReason::PreferredHealthy);
+    assert_eq!(fresh.unix_ms, now - 1_000);
+    assert!(state
+        .store
+        .list_session_last_routes_since(0)
+        .iter()
+        .all(|row| row.session_id != "s-stale"));
+
+    // Timestamp-only refreshes stay in memory until the periodic flush rewrites the rows.
+    record_last_used_route(&state, "s-fresh", route("official", now));
+    let persisted_unix_ms = |session: &str| {
+        state
+            .store
+            .list_session_last_routes_since(0)
+            .into_iter()
+            .find(|row| row.session_id == session)
+            .map(|row| row.unix_ms)
+    };
+    assert_eq!(persisted_unix_ms("s-fresh"), Some(now - 1_000));
+    persist_last_used_routes(&state);
+    assert_eq!(persisted_unix_ms("s-fresh"), Some(now));
+
+    state.last_used_by_session.write().remove("s-fresh");
+    persist_last_used_routes(&state);
+    assert!(load_persisted_last_used_routes(&state.store, &cfg, now).is_empty());
 }
 
 #[test]
@@ -1155,6 +1894,14 @@ fn decide_provider_balanced_auto_spreads_multi_sessions_deterministically() {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -1163,6 +1910,7 @@ fn decide_provider_balanced_auto_spreads_multi_sessions_deterministically() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1173,9 +1921,38 @@ fn decide_provider_balanced_auto_spreads_multi_sessions_deterministically() {
             failure_threshold: 2,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string(), "p3".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let now = unix_ms();
@@ -1193,6 +1970,8 @@ fn decide_provider_balanced_auto_spreads_multi_sessions_deterministically() {
         is_agent: false,
         is_review: false,
         confirmed_router: true,
+        auth_token_label: None,
+        peer_process: None,
     };
     let state = GatewayState {
         cfg: Arc::new(RwLock::new(cfg.clone())),
@@ -1206,7 +1985,7 @@ fn decide_provider_balanced_auto_spreads_multi_sessions_deterministically() {
                 "session-a".to_string(),
                 LastUsedRoute {
                     provider: "p1".to_string(),
-                    reason: "preferred_healthy".to_string(),
+                    reason: RouteReason::PreferredHealthy,
                     preferred: "p1".to_string(),
                     unix_ms: now,
                 },
@@ -1215,7 +1994,7 @@ fn decide_provider_balanced_auto_spreads_multi_sessions_deterministically() {
                 "session-b".to_string(),
                 LastUsedRoute {
                     provider: "p2".to_string(),
-                    reason: "preferred_unhealthy".to_string(),
+                    reason: RouteReason::PreferredUnhealthy,
                     preferred: "p1".to_string(),
                     unix_ms: now,
                 },
@@ -1223,6 +2002,7 @@ fn decide_provider_balanced_auto_spreads_multi_sessions_deterministically() {
         ]))),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([
             ("session-a".to_string(), mk_runtime("session-a")),
             ("session-b".to_string(), mk_runtime("session-b")),
@@ -1233,17 +2013,17 @@ fn decide_provider_balanced_auto_spreads_multi_sessions_deterministically() {
     let (a1, r1) = decide_provider(&state, &cfg, "p1", "session-a");
     let (a2, r2) = decide_provider(&state, &cfg, "p1", "session-a");
     assert_eq!(a1, a2, "same session should map stably");
-    assert_eq!(r1, "balanced_auto");
-    assert_eq!(r2, "balanced_auto");
+    assert_eq!(r1, RouteReason::BalancedAuto);
+    assert_eq!(r2, RouteReason::BalancedAuto);
 
     let (b1, r3) = decide_provider(&state, &cfg, "p1", "session-b");
     let (b2, r4) = decide_provider(&state, &cfg, "p1", "session-b");
     assert_eq!(b1, b2, "same session should map stably");
-    assert_eq!(r3, "balanced_auto");
-    assert_eq!(r4, "balanced_auto");
+    assert_eq!(r3, RouteReason::BalancedAuto);
+    assert_eq!(r4, RouteReason::BalancedAuto);
 
     let (c1, r5) = decide_provider(&state, &cfg, "p1", "session-c");
-    assert_eq!(r5, "balanced_auto");
+    assert_eq!(r5, RouteReason::BalancedAuto);
 
     let unique = std::collections::BTreeSet::from([a1, b1, c1]);
     assert!(
@@ -1271,6 +2051,14 @@ fn decide_provider_balanced_auto_single_session_follows_preferred() {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -1279,6 +2067,7 @@ fn decide_provider_balanced_auto_single_session_follows_preferred() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1289,9 +2078,38 @@ fn decide_provider_balanced_auto_single_session_follows_preferred() {
             failure_threshold: 2,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let state = GatewayState {
@@ -1304,12 +2122,13 @@ fn decide_provider_balanced_auto_single_session_follows_preferred() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
     let (picked, reason) = decide_provider(&state, &cfg, "p1", "session-single");
     assert_eq!(picked, "p1");
-    assert_eq!(reason, "preferred_healthy");
+    assert_eq!(reason, RouteReason::PreferredHealthy);
 }
 
 #[test]
@@ -1331,6 +2150,14 @@ fn decide_provider_balanced_auto_sticks_to_verified_session_assignment_even_if_p
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -1339,6 +2166,7 @@ fn decide_provider_balanced_auto_sticks_to_verified_session_assignment_even_if_p
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1349,9 +2177,38 @@ fn decide_provider_balanced_auto_sticks_to_verified_session_assignment_even_if_p
             failure_threshold: 2,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let state = GatewayState {
         cfg: Arc::new(RwLock::new(cfg.clone())),
@@ -1363,6 +2220,7 @@ fn decide_provider_balanced_auto_sticks_to_verified_session_assignment_even_if_p
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "session-main".to_string(),
             crate::orchestrator::gateway::ClientSessionRuntime {
@@ -1379,6 +2237,8 @@ fn decide_provider_balanced_auto_sticks_to_verified_session_assignment_even_if_p
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]))),
     };
@@ -1410,6 +2270,14 @@ fn decide_provider_balanced_auto_agent_session_follows_parent_assignment() {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -1418,6 +2286,7 @@ fn decide_provider_balanced_auto_agent_session_follows_parent_assignment() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1428,9 +2297,38 @@ fn decide_provider_balanced_auto_agent_session_follows_parent_assignment() {
             failure_threshold: 2,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -1443,6 +2341,7 @@ fn decide_provider_balanced_auto_agent_session_follows_parent_assignment() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([
             (
                 "main-session".to_string(),
@@ -1460,6 +2359,8 @@ fn decide_provider_balanced_auto_agent_session_follows_parent_assignment() {
                     is_agent: false,
                     is_review: false,
                     confirmed_router: true,
+                    auth_token_label: None,
+                    peer_process: None,
                 },
             ),
             (
@@ -1478,6 +2379,8 @@ fn decide_provider_balanced_auto_agent_session_follows_parent_assignment() {
                     is_agent: true,
                     is_review: false,
                     confirmed_router: true,
+                    auth_token_label: None,
+                    peer_process: None,
                 },
             ),
         ]))),
@@ -1509,6 +2412,14 @@ fn decide_provider_balanced_auto_persists_assignment_across_restart() {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -1517,6 +2428,7 @@ fn decide_provider_balanced_auto_persists_assignment_across_restart() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1527,9 +2439,38 @@ fn decide_provider_balanced_auto_persists_assignment_across_restart() {
             failure_threshold: 2,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: providers.clone(),
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let session_runtime = crate::orchestrator::gateway::ClientSessionRuntime {
@@ -1546,6 +2487,8 @@ fn decide_provider_balanced_auto_persists_assignment_across_restart() {
         is_agent: false,
         is_review: false,
         confirmed_router: true,
+        auth_token_label: None,
+        peer_process: None,
     };
 
     let state1 = GatewayState {
@@ -1558,6 +2501,7 @@ fn decide_provider_balanced_auto_persists_assignment_across_restart() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "session-main".to_string(),
             session_runtime.clone(),
@@ -1578,6 +2522,7 @@ fn decide_provider_balanced_auto_persists_assignment_across_restart() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "session-main".to_string(),
             session_runtime,
@@ -1611,6 +2556,14 @@ fn decide_provider_balanced_auto_keeps_provider_when_alternative_shares_same_api
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -1619,6 +2572,7 @@ fn decide_provider_balanced_auto_keeps_provider_when_alternative_shares_same_api
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1629,9 +2583,38 @@ fn decide_provider_balanced_auto_keeps_provider_when_alternative_shares_same_api
             failure_threshold: 2,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string(), "p3".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -1644,6 +2627,7 @@ fn decide_provider_balanced_auto_keeps_provider_when_alternative_shares_same_api
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "session-main".to_string(),
             crate::orchestrator::gateway::ClientSessionRuntime {
@@ -1660,6 +2644,8 @@ fn decide_provider_balanced_auto_keeps_provider_when_alternative_shares_same_api
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]))),
     };
@@ -1695,6 +2681,14 @@ fn decide_provider_balanced_auto_rebalances_after_sticky_window_when_assignment_
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -1703,6 +2697,7 @@ fn decide_provider_balanced_auto_rebalances_after_sticky_window_when_assignment_
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1713,9 +2708,38 @@ fn decide_provider_balanced_auto_rebalances_after_sticky_window_when_assignment_
             failure_threshold: 2,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -1728,6 +2752,7 @@ fn decide_provider_balanced_auto_rebalances_after_sticky_window_when_assignment_
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "session-main".to_string(),
             crate::orchestrator::gateway::ClientSessionRuntime {
@@ -1744,6 +2769,8 @@ fn decide_provider_balanced_auto_rebalances_after_sticky_window_when_assignment_
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]))),
     };
@@ -1790,6 +2817,14 @@ fn decide_provider_balanced_auto_retries_unhealthy_provider_when_load_is_skewed(
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -1798,6 +2833,7 @@ fn decide_provider_balanced_auto_retries_unhealthy_provider_when_load_is_skewed(
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1808,9 +2844,38 @@ fn decide_provider_balanced_auto_retries_unhealthy_provider_when_load_is_skewed(
             failure_threshold: 3,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -1823,6 +2888,7 @@ fn decide_provider_balanced_auto_retries_unhealthy_provider_when_load_is_skewed(
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "session-main".to_string(),
             crate::orchestrator::gateway::ClientSessionRuntime {
@@ -1839,6 +2905,8 @@ fn decide_provider_balanced_auto_retries_unhealthy_provider_when_load_is_skewed(
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]))),
     };
@@ -1862,7 +2930,7 @@ fn decide_provider_balanced_auto_retries_unhealthy_provider_when_load_is_skewed(
 
     let (picked, reason) = decide_provider(&state, &cfg, "p1", "session-main");
     assert_eq!(picked, "p1");
-    assert_eq!(reason, "balanced_auto");
+    assert_eq!(reason, RouteReason::BalancedAuto);
 }
 
 #[test]
@@ -1886,6 +2954,14 @@ fn decide_provider_balanced_auto_retries_unhealthy_provider_even_with_fresh_assi
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -1894,6 +2970,7 @@ fn decide_provider_balanced_auto_retries_unhealthy_provider_even_with_fresh_assi
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1904,9 +2981,38 @@ fn decide_provider_balanced_auto_retries_unhealthy_provider_even_with_fresh_assi
             failure_threshold: 3,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -1919,6 +3025,7 @@ fn decide_provider_balanced_auto_retries_unhealthy_provider_even_with_fresh_assi
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "session-main".to_string(),
             crate::orchestrator::gateway::ClientSessionRuntime {
@@ -1935,6 +3042,8 @@ fn decide_provider_balanced_auto_retries_unhealthy_provider_even_with_fresh_assi
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]))),
     };
@@ -1951,7 +3060,7 @@ fn decide_provider_balanced_auto_retries_unhealthy_provider_even_with_fresh_assi
 
     let (picked, reason) = decide_provider(&state, &cfg, "p1", "session-main");
     assert_eq!(picked, "p1");
-    assert_eq!(reason, "balanced_auto");
+    assert_eq!(reason, RouteReason::BalancedAuto);
 }
 
 #[test]
@@ -1975,6 +3084,14 @@ fn decide_provider_balanced_auto_fallback_keeps_primary_assignment_when_unhealth
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -1983,6 +3100,7 @@ fn decide_provider_balanced_auto_fallback_keeps_primary_assignment_when_unhealth
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1993,9 +3111,38 @@ fn decide_provider_balanced_auto_fallback_keeps_primary_assignment_when_unhealth
             failure_threshold: 3,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -2008,6 +3155,7 @@ fn decide_provider_balanced_auto_fallback_keeps_primary_assignment_when_unhealth
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "session-main".to_string(),
             crate::orchestrator::gateway::ClientSessionRuntime {
@@ -2024,6 +3172,8 @@ fn decide_provider_balanced_auto_fallback_keeps_primary_assignment_when_unhealth
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]))),
     };
@@ -2035,7 +3185,7 @@ fn decide_provider_balanced_auto_fallback_keeps_primary_assignment_when_unhealth
 
     let (picked, reason) = decide_provider(&state, &cfg, "p1", "session-main");
     assert_eq!(picked, "p2");
-    assert_eq!(reason, "balanced_auto");
+    assert_eq!(reason, RouteReason::BalancedAuto);
 
     let assignment = state
         .store
@@ -2065,6 +3215,14 @@ fn decide_provider_balanced_auto_fallback_keeps_primary_assignment_during_cooldo
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -2073,6 +3231,7 @@ fn decide_provider_balanced_auto_fallback_keeps_primary_assignment_during_cooldo
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -2083,9 +3242,38 @@ fn decide_provider_balanced_auto_fallback_keeps_primary_assignment_during_cooldo
             failure_threshold: 1,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -2098,6 +3286,7 @@ fn decide_provider_balanced_auto_fallback_keeps_primary_assignment_during_cooldo
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "session-main".to_string(),
             crate::orchestrator::gateway::ClientSessionRuntime {
@@ -2114,6 +3303,8 @@ fn decide_provider_balanced_auto_fallback_keeps_primary_assignment_during_cooldo
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]))),
     };
@@ -2125,7 +3316,7 @@ fn decide_provider_balanced_auto_fallback_keeps_primary_assignment_during_cooldo
 
     let (picked, reason) = decide_provider(&state, &cfg, "p1", "session-main");
     assert_eq!(picked, "p2");
-    assert_eq!(reason, "balanced_auto");
+    assert_eq!(reason, RouteReason::BalancedAuto);
 
     let assignment = state
         .store
@@ -2153,6 +3344,14 @@ fn decide_provider_balanced_auto_reassigns_immediately_after_closed_provider_reo
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -2161,6 +3360,7 @@ fn decide_provider_balanced_auto_reassigns_immediately_after_closed_provider_reo
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -2171,9 +3371,38 @@ fn decide_provider_balanced_auto_reassigns_immediately_after_closed_provider_reo
             failure_threshold: 2,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -2186,6 +3415,7 @@ fn decide_provider_balanced_auto_reassigns_immediately_after_closed_provider_reo
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([
             (
                 "session-main".to_string(),
@@ -2203,6 +3433,8 @@ fn decide_provider_balanced_auto_reassigns_immediately_after_closed_provider_reo
                     is_agent: false,
                     is_review: false,
                     confirmed_router: true,
+                    auth_token_label: None,
+                    peer_process: None,
                 },
             ),
             (
@@ -2221,6 +3453,8 @@ fn decide_provider_balanced_auto_reassigns_immediately_after_closed_provider_reo
                     is_agent: false,
                     is_review: false,
                     confirmed_router: true,
+                    auth_token_label: None,
+                    peer_process: None,
                 },
             ),
         ]))),
@@ -2251,7 +3485,7 @@ fn decide_provider_balanced_auto_reassigns_immediately_after_closed_provider_reo
 
     let (first_pick, first_reason) = decide_provider(&state, &cfg, "p1", "session-main");
     assert_eq!(first_pick, "p2");
-    assert_eq!(first_reason, "balanced_auto");
+    assert_eq!(first_reason, RouteReason::BalancedAuto);
     let initial_assignment = state
         .store
         .get_session_route_assignment("session-main")
@@ -2272,7 +3506,7 @@ fn decide_provider_balanced_auto_reassigns_immediately_after_closed_provider_reo
 
     let (second_pick, second_reason) = decide_provider(&state, &cfg, "p1", "session-main");
     assert_eq!(second_pick, "p1");
-    assert_eq!(second_reason, "balanced_auto");
+    assert_eq!(second_reason, RouteReason::BalancedAuto);
     let reassigned = state
         .store
         .get_session_route_assignment("session-main")
@@ -2299,6 +3533,14 @@ fn decide_provider_balanced_auto_reassigns_after_unhealthy_provider_recovers() {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -2307,6 +3549,7 @@ fn decide_provider_balanced_auto_reassigns_after_unhealthy_provider_recovers() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -2317,9 +3560,38 @@ fn decide_provider_balanced_auto_reassigns_after_unhealthy_provider_recovers() {
             failure_threshold: 10,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -2332,6 +3604,7 @@ fn decide_provider_balanced_auto_reassigns_after_unhealthy_provider_recovers() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([
             (
                 "session-main".to_string(),
@@ -2349,6 +3622,8 @@ fn decide_provider_balanced_auto_reassigns_after_unhealthy_provider_recovers() {
                     is_agent: false,
                     is_review: false,
                     confirmed_router: true,
+                    auth_token_label: None,
+                    peer_process: None,
                 },
             ),
             (
@@ -2367,6 +3642,8 @@ fn decide_provider_balanced_auto_reassigns_after_unhealthy_provider_recovers() {
                     is_agent: false,
                     is_review: false,
                     confirmed_router: true,
+                    auth_token_label: None,
+                    peer_process: None,
                 },
             ),
         ]))),
@@ -2398,7 +3675,7 @@ fn decide_provider_balanced_auto_reassigns_after_unhealthy_provider_recovers() {
     state.router.mark_failure("p1", &cfg, "boom", now);
     let (first_pick, first_reason) = decide_provider(&state, &cfg, "p1", "session-main");
     assert_eq!(first_pick, "p2");
-    assert_eq!(first_reason, "balanced_auto");
+    assert_eq!(first_reason, RouteReason::BalancedAuto);
     let initial_assignment = state
         .store
         .get_session_route_assignment("session-main")
@@ -2408,7 +3685,7 @@ fn decide_provider_balanced_auto_reassigns_after_unhealthy_provider_recovers() {
     state.router.mark_success("p1", now.saturating_add(1));
     let (second_pick, second_reason) = decide_provider(&state, &cfg, "p1", "session-main");
     assert_eq!(second_pick, "p1");
-    assert_eq!(second_reason, "balanced_auto");
+    assert_eq!(second_reason, RouteReason::BalancedAuto);
     let reassigned = state
         .store
         .get_session_route_assignment("session-main")
@@ -2437,6 +3714,14 @@ fn decide_provider_balanced_auto_prefers_healthy_when_load_gap_is_small() {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -2445,6 +3730,7 @@ fn decide_provider_balanced_auto_prefers_healthy_when_load_gap_is_small() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -2455,9 +3741,38 @@ fn decide_provider_balanced_auto_prefers_healthy_when_load_gap_is_small() {
             failure_threshold: 3,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -2470,6 +3785,7 @@ fn decide_provider_balanced_auto_prefers_healthy_when_load_gap_is_small() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "session-main".to_string(),
             crate::orchestrator::gateway::ClientSessionRuntime {
@@ -2486,6 +3802,8 @@ fn decide_provider_balanced_auto_prefers_healthy_when_load_gap_is_small() {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]))),
     };
@@ -2495,7 +3813,7 @@ fn decide_provider_balanced_auto_prefers_healthy_when_load_gap_is_small() {
 
     let (picked, reason) = decide_provider(&state, &cfg, "p1", "session-main");
     assert_eq!(picked, "p2");
-    assert_eq!(reason, "balanced_auto");
+    assert_eq!(reason, RouteReason::BalancedAuto);
 }
 
 #[test]
@@ -2519,6 +3837,14 @@ fn decide_provider_balanced_auto_suppresses_preferred_during_stabilizing_window(
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -2527,6 +3853,7 @@ fn decide_provider_balanced_auto_suppresses_preferred_during_stabilizing_window(
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -2537,9 +3864,38 @@ fn decide_provider_balanced_auto_suppresses_preferred_during_stabilizing_window(
             failure_threshold: 3,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -2553,13 +3909,14 @@ fn decide_provider_balanced_auto_suppresses_preferred_during_stabilizing_window(
             "session-main".to_string(),
             LastUsedRoute {
                 provider: "p2".to_string(),
-                reason: "preferred_unhealthy".to_string(),
+                reason: RouteReason::PreferredUnhealthy,
                 preferred: "p1".to_string(),
                 unix_ms: now,
             },
         )]))),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "session-main".to_string(),
             crate::orchestrator::gateway::ClientSessionRuntime {
@@ -2576,6 +3933,8 @@ fn decide_provider_balanced_auto_suppresses_preferred_during_stabilizing_window(
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]))),
     };
@@ -2588,7 +3947,7 @@ fn decide_provider_balanced_auto_suppresses_preferred_during_stabilizing_window(
     assert!(state.router.should_suppress_preferred("p1", &cfg, now));
     let (picked, reason) = decide_provider(&state, &cfg, "p1", "session-main");
     assert_eq!(picked, "p2");
-    assert_eq!(reason, "balanced_auto");
+    assert_eq!(reason, RouteReason::BalancedAuto);
 }
 
 #[test]
@@ -2612,6 +3971,14 @@ fn decide_provider_balanced_auto_keeps_healthy_when_unhealthy_gain_is_within_mar
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -2620,6 +3987,7 @@ fn decide_provider_balanced_auto_keeps_healthy_when_unhealthy_gain_is_within_mar
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -2630,9 +3998,38 @@ fn decide_provider_balanced_auto_keeps_healthy_when_unhealthy_gain_is_within_mar
             failure_threshold: 3,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -2645,6 +4042,7 @@ fn decide_provider_balanced_auto_keeps_healthy_when_unhealthy_gain_is_within_mar
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "session-main".to_string(),
             crate::orchestrator::gateway::ClientSessionRuntime {
@@ -2661,6 +4059,8 @@ fn decide_provider_balanced_auto_keeps_healthy_when_unhealthy_gain_is_within_mar
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]))),
     };
@@ -2677,7 +4077,7 @@ fn decide_provider_balanced_auto_keeps_healthy_when_unhealthy_gain_is_within_mar
 
     let (picked, reason) = decide_provider(&state, &cfg, "p1", "session-main");
     assert_eq!(picked, "p2");
-    assert_eq!(reason, "balanced_auto");
+    assert_eq!(reason, RouteReason::BalancedAuto);
 }
 
 #[test]
@@ -2699,6 +4099,14 @@ fn decide_provider_balanced_auto_weights_daily_remaining_with_other_signals() {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -2707,6 +4115,7 @@ fn decide_provider_balanced_auto_weights_daily_remaining_with_other_signals() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -2717,9 +4126,38 @@ fn decide_provider_balanced_auto_weights_daily_remaining_with_other_signals() {
             failure_threshold: 2,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -2732,6 +4170,7 @@ fn decide_provider_balanced_auto_weights_daily_remaining_with_other_signals() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "session-main".to_string(),
             crate::orchestrator::gateway::ClientSessionRuntime {
@@ -2748,6 +4187,8 @@ fn decide_provider_balanced_auto_weights_daily_remaining_with_other_signals() {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]))),
     };
@@ -2788,7 +4229,7 @@ fn decide_provider_balanced_auto_weights_daily_remaining_with_other_signals() {
         picked, "p1",
         "daily remaining is one equal-weight signal and may be offset by other balancing signals"
     );
-    assert_eq!(reason, "balanced_auto");
+    assert_eq!(reason, RouteReason::BalancedAuto);
 }
 
 #[test]
@@ -2810,6 +4251,14 @@ fn decide_provider_balanced_auto_prefers_higher_quota_for_heavy_session_when_loa
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -2818,6 +4267,7 @@ fn decide_provider_balanced_auto_prefers_higher_quota_for_heavy_session_when_loa
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -2828,9 +4278,38 @@ fn decide_provider_balanced_auto_prefers_higher_quota_for_heavy_session_when_loa
             failure_threshold: 2,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -2843,6 +4322,7 @@ fn decide_provider_balanced_auto_prefers_higher_quota_for_heavy_session_when_loa
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "session-heavy-headroom".to_string(),
             crate::orchestrator::gateway::ClientSessionRuntime {
@@ -2859,6 +4339,8 @@ fn decide_provider_balanced_auto_prefers_higher_quota_for_heavy_session_when_loa
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]))),
     };
@@ -2907,12 +4389,10 @@ fn decide_provider_balanced_auto_prefers_higher_quota_for_heavy_session_when_loa
                 }
             }),
             crate::orchestrator::store::UsageRequestContext {
-                api_key_ref: Some("-"),
-                origin: crate::constants::USAGE_ORIGIN_WINDOWS,
-                transport: "http",
                 session_id: Some("session-heavy-headroom"),
                 node_id: Some("node-test"),
                 node_name: Some("Desk Test"),
+                ..crate::orchestrator::store::UsageRequestContext::test_context()
             },
             None,
         );
@@ -2950,7 +4430,7 @@ fn decide_provider_balanced_auto_prefers_higher_quota_for_heavy_session_when_loa
 
     let (picked, reason) = decide_provider(&state, &cfg, "p1", "session-heavy-headroom");
     assert_eq!(picked, "p2", "expected heavy session to choose larger headroom; reason={reason}");
-    assert_eq!(reason, "balanced_auto");
+    assert_eq!(reason, RouteReason::BalancedAuto);
 }
 
 #[test]
@@ -2978,6 +4458,14 @@ fn decide_provider_balanced_auto_heavy_session_prefers_lower_per_request_cost()
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -2986,6 +4474,7 @@ fn decide_provider_balanced_auto_heavy_session_prefers_lower_per_request_cost()
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -2996,9 +4485,38 @@ fn decide_provider_balanced_auto_heavy_session_prefers_lower_per_request_cost()
             failure_threshold: 2,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -3011,6 +4529,7 @@ fn decide_provider_balanced_auto_heavy_session_prefers_lower_per_request_cost()
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "session-heavy-cost".to_string(),
             crate::orchestrator::gateway::ClientSessionRuntime {
@@ -3027,6 +4546,8 @@ fn decide_provider_balanced_auto_heavy_session_prefers_lower_per_request_cost()
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]))),
     };
@@ -3074,12 +4595,10 @@ fn decide_provider_balanced_auto_heavy_session_prefers_lower_per_request_cost()
                 }
             }),
             crate::orchestrator::store::UsageRequestContext {
-                api_key_ref: Some("-"),
-                origin: crate::constants::USAGE_ORIGIN_WINDOWS,
-                transport: "http",
                 session_id: Some("session-heavy-cost"),
                 node_id: Some("node-test"),
                 node_name: Some("Desk Test"),
+                ..crate::orchestrator::store::UsageRequestContext::test_context()
             },
             None,
         );
@@ -3087,7 +4606,7 @@ fn decide_provider_balanced_auto_heavy_session_prefers_lower_per_request_cost()
 
     let (picked, reason) = decide_provider(&state, &cfg, "p1", "session-heavy-cost");
     assert_eq!(picked, "p2");
-    assert_eq!(reason, "balanced_auto");
+    assert_eq!(reason, RouteReason::BalancedAuto);
 }
 
 #[test]
@@ -3115,6 +4634,14 @@ fn decide_provider_balanced_auto_no_longer_hard_prioritizes_load_pressure() {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         );
     }
@@ -3123,6 +4650,7 @@ fn decide_provider_balanced_auto_no_longer_hard_prioritizes_load_pressure() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -3133,9 +4661,38 @@ fn decide_provider_balanced_auto_no_longer_hard_prioritizes_load_pressure() {
             failure_threshold: 2,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -3148,6 +4705,7 @@ fn decide_provider_balanced_auto_no_longer_hard_prioritizes_load_pressure() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "session-heavy-pressure".to_string(),
             crate::orchestrator::gateway::ClientSessionRuntime {
@@ -3164,6 +4722,8 @@ fn decide_provider_balanced_auto_no_longer_hard_prioritizes_load_pressure() {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]))),
     };
@@ -3211,12 +4771,10 @@ fn decide_provider_balanced_auto_no_longer_hard_prioritizes_load_pressure() {
                 }
             }),
             crate::orchestrator::store::UsageRequestContext {
-                api_key_ref: Some("-"),
-                origin: crate::constants::USAGE_ORIGIN_WINDOWS,
-                transport: "http",
                 session_id: Some("session-heavy-pressure"),
                 node_id: Some("node-test"),
                 node_name: Some("Desk Test"),
+                ..crate::orchestrator::store::UsageRequestContext::test_context()
             },
             None,
         );
@@ -3227,7 +4785,7 @@ fn decide_provider_balanced_auto_no_longer_hard_prioritizes_load_pressure() {
         picked, "p2",
         "load pressure is blended with capacity, cost, and budget pressure under equal weighting"
     );
-    assert_eq!(reason, "balanced_auto");
+    assert_eq!(reason, RouteReason::BalancedAuto);
 }
 
 #[test]
@@ -3248,6 +4806,14 @@ fn decide_provider_for_display_keeps_usage_confirmation_requirement() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
     providers.insert(
@@ -3261,6 +4827,14 @@ fn decide_provider_for_display_keeps_usage_confirmation_requirement() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
 
@@ -3268,6 +4842,7 @@ fn decide_provider_for_display_keeps_usage_confirmation_requirement() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -3278,9 +4853,38 @@ fn decide_provider_for_display_keeps_usage_confirmation_requirement() {
             failure_threshold: 2,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -3293,6 +4897,7 @@ fn decide_provider_for_display_keeps_usage_confirmation_requirement() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -3312,14 +4917,14 @@ fn decide_provider_for_display_keeps_usage_confirmation_requirement() {
     let (display_provider, display_reason) =
         crate::orchestrator::gateway::decide_provider_for_display(&state, &cfg, "p1", "sid-1");
     assert_eq!(display_provider, "p1");
-    assert_eq!(display_reason, "preferred_healthy");
+    assert_eq!(display_reason, RouteReason::PreferredHealthy);
     assert!(
         state.router.is_waiting_usage_confirmation("p1"),
         "display route should not clear runtime usage confirmation gate"
     );
 
     let (_route_provider, route_reason) = decide_provider(&state, &cfg, "p1", "sid-1");
-    assert_eq!(route_reason, "preferred_healthy");
+    assert_eq!(route_reason, RouteReason::PreferredHealthy);
     assert!(
         !state.router.is_waiting_usage_confirmation("p1"),
         "real routing path should clear usage confirmation gate once confirmed"
@@ -3344,6 +4949,14 @@ fn decide_provider_for_display_balanced_bootstrap_keeps_usage_confirmation_requi
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
 
@@ -3351,6 +4964,7 @@ fn decide_provider_for_display_balanced_bootstrap_keeps_usage_confirmation_requi
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -3361,9 +4975,38 @@ fn decide_provider_for_display_balanced_bootstrap_keeps_usage_confirmation_requi
             failure_threshold: 2,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -3376,6 +5019,7 @@ fn decide_provider_for_display_balanced_bootstrap_keeps_usage_confirmation_requi
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "sid-1".to_string(),
             ClientSessionRuntime {
@@ -3392,6 +5036,8 @@ fn decide_provider_for_display_balanced_bootstrap_keeps_usage_confirmation_requi
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]))),
     };
@@ -3412,7 +5058,7 @@ fn decide_provider_for_display_balanced_bootstrap_keeps_usage_confirmation_requi
     let (display_provider, display_reason) =
         crate::orchestrator::gateway::decide_provider_for_display(&state, &cfg, "p1", "sid-1");
     assert_eq!(display_provider, "p1");
-    assert_eq!(display_reason, "balanced_auto");
+    assert_eq!(display_reason, RouteReason::BalancedAuto);
     assert!(
         state.router.is_waiting_usage_confirmation("p1"),
         "balanced display route should not clear runtime usage confirmation gate"
@@ -3423,7 +5069,7 @@ fn decide_provider_for_display_balanced_bootstrap_keeps_usage_confirmation_requi
     );
 
     let (_route_provider, route_reason) = decide_provider(&state, &cfg, "p1", "sid-1");
-    assert_eq!(route_reason, "balanced_auto");
+    assert_eq!(route_reason, RouteReason::BalancedAuto);
     assert!(
         !state.router.is_waiting_usage_confirmation("p1"),
         "real routing path should clear usage confirmation gate once confirmed"
@@ -3448,6 +5094,14 @@ fn decide_provider_for_display_balanced_bootstrap_persists_rebalanced_stale_assi
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
     providers.insert(
@@ -3461,6 +5115,14 @@ fn decide_provider_for_display_balanced_bootstrap_persists_rebalanced_stale_assi
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
 
@@ -3468,6 +5130,7 @@ fn decide_provider_for_display_balanced_bootstrap_persists_rebalanced_stale_assi
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -3478,9 +5141,38 @@ fn decide_provider_for_display_balanced_bootstrap_persists_rebalanced_stale_assi
             failure_threshold: 2,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let stale_ms = now.saturating_sub((2 * 60 * 60 * 1000) + 5_000);
@@ -3494,6 +5186,7 @@ fn decide_provider_for_display_balanced_bootstrap_persists_rebalanced_stale_assi
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "sid-1".to_string(),
             ClientSessionRuntime {
@@ -3510,6 +5203,8 @@ fn decide_provider_for_display_balanced_bootstrap_persists_rebalanced_stale_assi
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]))),
     };
@@ -3531,7 +5226,7 @@ fn decide_provider_for_display_balanced_bootstrap_persists_rebalanced_stale_assi
     let (display_provider, display_reason) =
         crate::orchestrator::gateway::decide_provider_for_display(&state, &cfg, "p1", "sid-1");
     assert_eq!(display_provider, "p1");
-    assert_eq!(display_reason, "balanced_auto");
+    assert_eq!(display_reason, RouteReason::BalancedAuto);
 
     let updated = state
         .store
@@ -3561,6 +5256,14 @@ fn decide_provider_balanced_auto_ignores_explicit_session_lock_assignments() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
     providers.insert(
@@ -3574,16 +5277,25 @@ fn decide_provider_balanced_auto_ignores_explicit_session_lock_assignments() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
 
     let mut session_preferred = std::collections::BTreeMap::new();
-    session_preferred.insert("session-locked".to_string(), "p2".to_string());
+    session_preferred.insert("session-locked".to_string(), vec!["p2".to_string()]);
 
     let cfg = AppConfig {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -3594,9 +5306,38 @@ fn decide_provider_balanced_auto_ignores_explicit_session_lock_assignments() {
             failure_threshold: 2,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
     let now = unix_ms();
     let state = GatewayState {
@@ -3609,6 +5350,7 @@ fn decide_provider_balanced_auto_ignores_explicit_session_lock_assignments() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::from([(
             "session-main".to_string(),
             crate::orchestrator::gateway::ClientSessionRuntime {
@@ -3625,6 +5367,8 @@ fn decide_provider_balanced_auto_ignores_explicit_session_lock_assignments() {
                 is_agent: false,
                 is_review: false,
                 confirmed_router: true,
+                auth_token_label: None,
+                peer_process: None,
             },
         )]))),
     };
@@ -3638,7 +5382,132 @@ fn decide_provider_balanced_auto_ignores_explicit_session_lock_assignments() {
 
     let (picked, reason) = decide_provider(&state, &cfg, "p1", "session-main");
     assert_eq!(picked, "p2");
-    assert_eq!(reason, "balanced_auto");
+    assert_eq!(reason, RouteReason::BalancedAuto);
+}
+
+#[test]
+fn session_preferred_provider_tries_session_list_in_order_among_healthy() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = open_store_dir(tmp.path().join("data")).expect("store");
+    let secrets = SecretStore::new(tmp.path().join("secrets.json"));
+
+    let mut providers = std::collections::BTreeMap::new();
+    for name in ["p1", "p2", "p3"] {
+        providers.insert(
+            name.to_string(),
+            ProviderConfig {
+                display_name: name.to_uppercase(),
+                base_url: format!("https://{name}.example.com"),
+                group: None,
+                disabled: false,
+                supports_websockets: false,
+                usage_adapter: String::new(),
+                usage_base_url: None,
+                api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
+            },
+        );
+    }
+
+    let mut session_preferred = std::collections::BTreeMap::new();
+    session_preferred.insert("s1".to_string(), vec!["p2".to_string(), "p3".to_string()]);
+    session_preferred.insert("stale".to_string(), vec!["removed".to_string()]);
+
+    let cfg = AppConfig {
+        listen: ListenConfig {
+            host: "127.0.0.1".to_string(),
+            port: 4000,
+            unix_socket_path: None,
+        },
+        routing: RoutingConfig {
+            preferred_provider: "p1".to_string(),
+            session_preferred_providers: session_preferred,
+            route_mode: crate::orchestrator::config::RouteMode::FollowPreferredAuto,
+            auto_return_to_preferred: false,
+            preferred_stable_seconds: 30,
+            failure_threshold: 1,
+            cooldown_seconds: 30,
+            request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
+        },
+        providers,
+        provider_order: vec!["p1".to_string(), "p2".to_string(), "p3".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
+    };
+    let state = GatewayState {
+        cfg: Arc::new(RwLock::new(cfg.clone())),
+        router: Arc::new(RouterState::new(&cfg, unix_ms())),
+        store,
+        upstream: UpstreamClient::new(),
+        secrets,
+        last_activity_unix_ms: Arc::new(AtomicU64::new(0)),
+        last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
+        usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
+        prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
+        client_sessions: Arc::new(RwLock::new(HashMap::new())),
+    };
+
+    assert_eq!(
+        session_preferred_provider(&state, &cfg, "s1", |_| false),
+        "p2"
+    );
+    assert_eq!(
+        session_preferred_provider(&state, &cfg, "other", |_| false),
+        "p1"
+    );
+    assert_eq!(
+        session_preferred_provider(&state, &cfg, "s1", |name| name == "p2"),
+        "p3"
+    );
+
+    state.router.mark_failure("p2", &cfg, "boom", unix_ms());
+    let preferred = session_preferred_provider(&state, &cfg, "s1", |_| false);
+    assert_eq!(preferred, "p3");
+    let (picked, reason) = decide_provider(&state, &cfg, preferred, "s1");
+    assert_eq!(picked, "p3");
+    assert_eq!(reason, RouteReason::SessionPreferred);
+
+    // None of the session's providers exist, so the global preference picked p1.
+    let preferred = session_preferred_provider(&state, &cfg, "stale", |_| false);
+    assert_eq!(preferred, "p1");
+    let (picked, reason) = decide_provider(&state, &cfg, preferred, "stale");
+    assert_eq!(picked, "p1");
+    assert_eq!(reason, RouteReason::PreferredHealthy);
 }
 
 #[test]
@@ -3659,6 +5528,14 @@ fn decide_provider_skips_fallback_with_no_remaining_quota() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
     providers.insert(
@@ -3672,6 +5549,14 @@ fn decide_provider_skips_fallback_with_no_remaining_quota() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
     providers.insert(
@@ -3685,6 +5570,14 @@ fn decide_provider_skips_fallback_with_no_remaining_quota() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
 
@@ -3692,6 +5585,7 @@ fn decide_provider_skips_fallback_with_no_remaining_quota() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -3702,9 +5596,38 @@ fn decide_provider_skips_fallback_with_no_remaining_quota() {
             failure_threshold: 1,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string(), "p3".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let router = Arc::new(RouterState::new(&cfg, unix_ms()));
@@ -3733,12 +5656,13 @@ fn decide_provider_skips_fallback_with_no_remaining_quota() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
     let (picked, reason) = decide_provider(&state, &cfg, "p1", "s1");
     assert_eq!(picked, "p3");
-    assert_eq!(reason, "preferred_unhealthy");
+    assert_eq!(reason, RouteReason::PreferredUnhealthy);
 }
 
 #[test]
@@ -3759,6 +5683,14 @@ fn decide_provider_skips_fallback_when_daily_budget_exhausted() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
     providers.insert(
@@ -3772,6 +5704,14 @@ fn decide_provider_skips_fallback_when_daily_budget_exhausted() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
     providers.insert(
@@ -3785,6 +5725,14 @@ fn decide_provider_skips_fallback_when_daily_budget_exhausted() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
 
@@ -3792,6 +5740,7 @@ fn decide_provider_skips_fallback_when_daily_budget_exhausted() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -3802,9 +5751,38 @@ fn decide_provider_skips_fallback_when_daily_budget_exhausted() {
             failure_threshold: 1,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string(), "p3".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let router = Arc::new(RouterState::new(&cfg, unix_ms()));
@@ -3834,15 +5812,16 @@ fn decide_provider_skips_fallback_when_daily_budget_exhausted() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
     let (picked, reason) = decide_provider(&state, &cfg, "p1", "s1");
     assert_eq!(picked, "p3");
-    assert_eq!(reason, "preferred_unhealthy");
+    assert_eq!(reason, RouteReason::PreferredUnhealthy);
 }
 
-fn decide_with_budget_snapshot_for_p2(snapshot: serde_json::Value) -> (String, &'static str) {
+fn decide_with_budget_snapshot_for_p2(snapshot: serde_json::Value) -> (String, RouteReason) {
     let tmp = tempfile::tempdir().expect("tempdir");
     let store = open_store_dir(tmp.path().join("data")).expect("store");
     let secrets = SecretStore::new(tmp.path().join("secrets.json"));
@@ -3859,6 +5838,14 @@ fn decide_with_budget_snapshot_for_p2(snapshot: serde_json::Value) -> (String, &
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
     providers.insert(
@@ -3872,6 +5859,14 @@ fn decide_with_budget_snapshot_for_p2(snapshot: serde_json::Value) -> (String, &
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
     providers.insert(
@@ -3885,6 +5880,14 @@ fn decide_with_budget_snapshot_for_p2(snapshot: serde_json::Value) -> (String, &
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
 
@@ -3892,6 +5895,7 @@ fn decide_with_budget_snapshot_for_p2(snapshot: serde_json::Value) -> (String, &
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -3902,9 +5906,38 @@ fn decide_with_budget_snapshot_for_p2(snapshot: serde_json::Value) -> (String, &
             failure_threshold: 1,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string(), "p3".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let router = Arc::new(RouterState::new(&cfg, unix_ms()));
@@ -3922,6 +5955,7 @@ fn decide_with_budget_snapshot_for_p2(snapshot: serde_json::Value) -> (String, &
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -3941,7 +5975,7 @@ fn decide_provider_skips_fallback_when_daily_budget_equals_limit() {
         "updated_at_unix_ms": unix_ms()
     }));
     assert_eq!(picked, "p3");
-    assert_eq!(reason, "preferred_unhealthy");
+    assert_eq!(reason, RouteReason::PreferredUnhealthy);
 }
 
 #[test]
@@ -3957,7 +5991,7 @@ fn decide_provider_skips_fallback_when_weekly_budget_exhausted() {
         "updated_at_unix_ms": unix_ms()
     }));
     assert_eq!(picked, "p3");
-    assert_eq!(reason, "preferred_unhealthy");
+    assert_eq!(reason, RouteReason::PreferredUnhealthy);
 }
 
 #[test]
@@ -3973,7 +6007,7 @@ fn decide_provider_skips_fallback_when_monthly_budget_exhausted() {
         "updated_at_unix_ms": unix_ms()
     }));
     assert_eq!(picked, "p3");
-    assert_eq!(reason, "preferred_unhealthy");
+    assert_eq!(reason, RouteReason::PreferredUnhealthy);
 }
 
 #[test]
@@ -3990,7 +6024,7 @@ fn decide_provider_budget_cap_wins_over_positive_remaining_field() {
         "updated_at_unix_ms": unix_ms()
     }));
     assert_eq!(picked, "p3");
-    assert_eq!(reason, "preferred_unhealthy");
+    assert_eq!(reason, RouteReason::PreferredUnhealthy);
 }
 
 #[test]
@@ -4056,7 +6090,7 @@ fn decide_provider_with_budget_fields_still_closes_when_remaining_is_zero() {
         "updated_at_unix_ms": unix_ms()
     }));
     assert_eq!(picked, "p3");
-    assert_eq!(reason, "preferred_unhealthy");
+    assert_eq!(reason, RouteReason::PreferredUnhealthy);
 }
 
 #[test]
@@ -4077,6 +6111,14 @@ fn decide_provider_reports_no_routable_provider_when_all_candidates_are_unavaila
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
     providers.insert(
@@ -4090,6 +6132,14 @@ fn decide_provider_reports_no_routable_provider_when_all_candidates_are_unavaila
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
 
@@ -4097,6 +6147,7 @@ fn decide_provider_reports_no_routable_provider_when_all_candidates_are_unavaila
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -4107,9 +6158,38 @@ fn decide_provider_reports_no_routable_provider_when_all_candidates_are_unavaila
             failure_threshold: 1,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let router = Arc::new(RouterState::new(&cfg, unix_ms()));
@@ -4126,12 +6206,13 @@ fn decide_provider_reports_no_routable_provider_when_all_candidates_are_unavaila
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
     let (picked, reason) = decide_provider(&state, &cfg, "p1", "s1");
     assert_eq!(picked, "p1");
-    assert_eq!(reason, "no_routable_provider");
+    assert_eq!(reason, RouteReason::NoRoutableProvider);
 }
 
 #[test]
@@ -4152,6 +6233,14 @@ fn decide_provider_manual_override_falls_back_when_daily_budget_exhausted() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
     providers.insert(
@@ -4165,6 +6254,14 @@ fn decide_provider_manual_override_falls_back_when_daily_budget_exhausted() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
 
@@ -4172,6 +6269,7 @@ fn decide_provider_manual_override_falls_back_when_daily_budget_exhausted() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -4182,9 +6280,38 @@ fn decide_provider_manual_override_falls_back_when_daily_budget_exhausted() {
             failure_threshold: 1,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let router = Arc::new(RouterState::new(&cfg, unix_ms()));
@@ -4214,12 +6341,13 @@ fn decide_provider_manual_override_falls_back_when_daily_budget_exhausted() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
     let (picked, reason) = decide_provider(&state, &cfg, "p1", "s1");
     assert_eq!(picked, "p1");
-    assert_eq!(reason, "manual_override_unhealthy");
+    assert_eq!(reason, RouteReason::ManualOverrideUnhealthy);
 }
 
 #[test]
@@ -4240,6 +6368,14 @@ fn decide_provider_stabilizing_skips_last_provider_with_no_remaining_quota() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
     providers.insert(
@@ -4253,6 +6389,14 @@ fn decide_provider_stabilizing_skips_last_provider_with_no_remaining_quota() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
     providers.insert(
@@ -4266,6 +6410,14 @@ fn decide_provider_stabilizing_skips_last_provider_with_no_remaining_quota() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
 
@@ -4273,6 +6425,7 @@ fn decide_provider_stabilizing_skips_last_provider_with_no_remaining_quota() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -4283,9 +6436,38 @@ fn decide_provider_stabilizing_skips_last_provider_with_no_remaining_quota() {
             failure_threshold: 1,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         provider_order: vec!["p1".to_string(), "p2".to_string(), "p3".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let router = Arc::new(RouterState::new(&cfg, unix_ms()));
@@ -4315,19 +6497,20 @@ fn decide_provider_stabilizing_skips_last_provider_with_no_remaining_quota() {
             "s1".to_string(),
             LastUsedRoute {
                 provider: "p2".to_string(),
-                reason: "preferred_unhealthy".to_string(),
+                reason: RouteReason::PreferredUnhealthy,
                 preferred: "p1".to_string(),
                 unix_ms: unix_ms(),
             },
         )]))),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
     let (picked, reason) = decide_provider(&state, &cfg, "p1", "s1");
     assert_eq!(picked, "p3");
-    assert_eq!(reason, "preferred_stabilizing");
+    assert_eq!(reason, RouteReason::PreferredStabilizing);
 }
 
 #[test]
@@ -4348,6 +6531,14 @@ fn decide_provider_respects_provider_order_for_fallback() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
     providers.insert(
@@ -4361,6 +6552,14 @@ fn decide_provider_respects_provider_order_for_fallback() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
     providers.insert(
@@ -4374,6 +6573,14 @@ fn decide_provider_respects_provider_order_for_fallback() {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         },
     );
 
@@ -4381,6 +6588,7 @@ fn decide_provider_respects_provider_order_for_fallback() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "alpha".to_string(),
@@ -4391,10 +6599,39 @@ fn decide_provider_respects_provider_order_for_fallback() {
             failure_threshold: 1,
             cooldown_seconds: 30,
             request_timeout_seconds: 300,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers,
         // Non-alphabetical order: fallback should pick zeta first.
         provider_order: vec!["zeta".to_string(), "beta".to_string(), "alpha".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let router = Arc::new(RouterState::new(&cfg, unix_ms()));
@@ -4410,10 +6647,11 @@ fn decide_provider_respects_provider_order_for_fallback() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
     let (picked, reason) = decide_provider(&state, &cfg, "alpha", "s1");
     assert_eq!(picked, "zeta");
-    assert_eq!(reason, "preferred_unhealthy");
+    assert_eq!(reason, RouteReason::PreferredUnhealthy);
 }
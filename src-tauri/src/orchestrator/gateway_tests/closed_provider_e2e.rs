@@ -40,6 +40,7 @@ async fn assert_closed_provider_not_used_e2e(tag: &str, p2_snapshot: serde_json:
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -50,6 +51,32 @@ async fn assert_closed_provider_not_used_e2e(tag: &str, p2_snapshot: serde_json:
             failure_threshold: 1,
             cooldown_seconds: 120,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([
             (
@@ -63,6 +90,14 @@ async fn assert_closed_provider_not_used_e2e(tag: &str, p2_snapshot: serde_json:
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -76,6 +111,14 @@ async fn assert_closed_provider_not_used_e2e(tag: &str, p2_snapshot: serde_json:
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -89,10 +132,21 @@ async fn assert_closed_provider_not_used_e2e(tag: &str, p2_snapshot: serde_json:
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]),
         provider_order: vec!["p1".to_string(), "p2".to_string(), "p3".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -112,6 +166,7 @@ async fn assert_closed_provider_not_used_e2e(tag: &str, p2_snapshot: serde_json:
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -319,6 +374,7 @@ async fn e2e_first_failure_refreshes_usage_once_and_closes_provider_before_retry
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -329,6 +385,32 @@ async fn e2e_first_failure_refreshes_usage_once_and_closes_provider_before_retry
             failure_threshold: 5,
             cooldown_seconds: 120,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([
             (
@@ -342,6 +424,14 @@ async fn e2e_first_failure_refreshes_usage_once_and_closes_provider_before_retry
                     usage_adapter: "budget_info".to_string(),
                     usage_base_url: Some(usage_base),
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -355,10 +445,21 @@ async fn e2e_first_failure_refreshes_usage_once_and_closes_provider_before_retry
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]),
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -393,6 +494,7 @@ async fn e2e_first_failure_refreshes_usage_once_and_closes_provider_before_retry
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -515,6 +617,7 @@ async fn e2e_stops_retrying_preferred_when_refresh_leaves_no_routable_provider()
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -525,6 +628,32 @@ async fn e2e_stops_retrying_preferred_when_refresh_leaves_no_routable_provider()
             failure_threshold: 5,
             cooldown_seconds: 120,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([
             (
@@ -538,6 +667,14 @@ async fn e2e_stops_retrying_preferred_when_refresh_leaves_no_routable_provider()
                     usage_adapter: "budget_info".to_string(),
                     usage_base_url: Some(usage_base),
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -551,10 +688,21 @@ async fn e2e_stops_retrying_preferred_when_refresh_leaves_no_routable_provider()
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]),
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -604,6 +752,7 @@ async fn e2e_stops_retrying_preferred_when_refresh_leaves_no_routable_provider()
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -714,6 +863,7 @@ async fn e2e_web_codex_requests_emit_gateway_reconnect_statuses() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -724,6 +874,32 @@ async fn e2e_web_codex_requests_emit_gateway_reconnect_statuses() {
             failure_threshold: 1,
             cooldown_seconds: 120,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([(
             "p1".to_string(),
@@ -736,9 +912,20 @@ async fn e2e_web_codex_requests_emit_gateway_reconnect_statuses() {
                 usage_adapter: "openai".to_string(),
                 usage_base_url: Some(usage_base),
                 api_key: "test-key".to_string(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]),
         provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let store = open_store_dir(tmp.path().join("data")).expect("store");
@@ -757,6 +944,7 @@ async fn e2e_web_codex_requests_emit_gateway_reconnect_statuses() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -835,6 +1023,14 @@ fn weekly_budget_can_be_excluded_from_hard_cap_close() {
                 group: None,
                 disabled: false,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]);
     cfg.provider_order = vec!["p1".to_string()];
@@ -948,6 +1144,7 @@ async fn e2e_failure_usage_refresh_only_runs_once_per_request() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -958,6 +1155,32 @@ async fn e2e_failure_usage_refresh_only_runs_once_per_request() {
             failure_threshold: 5,
             cooldown_seconds: 120,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([
             (
@@ -971,6 +1194,14 @@ async fn e2e_failure_usage_refresh_only_runs_once_per_request() {
                     usage_adapter: "budget_info".to_string(),
                     usage_base_url: Some(usage_base.clone()),
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -984,10 +1215,21 @@ async fn e2e_failure_usage_refresh_only_runs_once_per_request() {
                     usage_adapter: "budget_info".to_string(),
                     usage_base_url: Some(usage_base),
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]),
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -1010,6 +1252,7 @@ async fn e2e_failure_usage_refresh_only_runs_once_per_request() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -1089,6 +1332,7 @@ async fn e2e_same_turn_does_not_restart_reconnect_budget() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1099,6 +1343,32 @@ async fn e2e_same_turn_does_not_restart_reconnect_budget() {
             failure_threshold: 1,
             cooldown_seconds: 120,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([(
             "p1".to_string(),
@@ -1111,9 +1381,20 @@ async fn e2e_same_turn_does_not_restart_reconnect_budget() {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]),
         provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -1134,6 +1415,7 @@ async fn e2e_same_turn_does_not_restart_reconnect_budget() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -1353,6 +1635,7 @@ async fn e2e_provider_stays_skipped_until_usage_refresh_confirms_available() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1363,6 +1646,32 @@ async fn e2e_provider_stays_skipped_until_usage_refresh_confirms_available() {
             failure_threshold: 5,
             cooldown_seconds: 120,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([
             (
@@ -1376,6 +1685,14 @@ async fn e2e_provider_stays_skipped_until_usage_refresh_confirms_available() {
                     usage_adapter: "budget_info".to_string(),
                     usage_base_url: Some(usage_base),
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -1389,10 +1706,21 @@ async fn e2e_provider_stays_skipped_until_usage_refresh_confirms_available() {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]),
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -1412,6 +1740,7 @@ async fn e2e_provider_stays_skipped_until_usage_refresh_confirms_available() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
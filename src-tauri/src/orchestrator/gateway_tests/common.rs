@@ -14,8 +14,9 @@ use crate::constants::GATEWAY_MODEL_PROVIDER_ID;
 use crate::orchestrator::config::{AppConfig, ListenConfig, ProviderConfig, RoutingConfig};
 use crate::orchestrator::gateway::{
     build_router, build_router_with_body_limit, decide_provider, is_back_to_preferred_transition,
-    open_store_dir, should_log_routing_path_event, ClientSessionRuntime, GatewayState,
-    LastUsedRoute,
+    load_persisted_last_used_routes, open_store_dir, persist_last_used_routes,
+    record_last_used_route, session_preferred_provider, should_log_routing_path_event,
+    ClientSessionRuntime, GatewayState, LastUsedRoute, RouteReason,
 };
 use crate::orchestrator::router::RouterState;
 use crate::orchestrator::secrets::SecretStore;
@@ -41,6 +41,7 @@ async fn e2e_status_closed_provider_keeps_failure_fields() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -51,6 +52,32 @@ async fn e2e_status_closed_provider_keeps_failure_fields() {
             failure_threshold: 1,
             cooldown_seconds: 120,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([
             (
@@ -64,6 +91,14 @@ async fn e2e_status_closed_provider_keeps_failure_fields() {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -77,10 +112,21 @@ async fn e2e_status_closed_provider_keeps_failure_fields() {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]),
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -115,6 +161,7 @@ async fn e2e_status_closed_provider_keeps_failure_fields() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -163,6 +210,7 @@ async fn e2e_recovery_success_resets_failures_but_keeps_last_error() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -173,6 +221,32 @@ async fn e2e_recovery_success_resets_failures_but_keeps_last_error() {
             failure_threshold: 1,
             cooldown_seconds: 120,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([(
             "p1".to_string(),
@@ -185,9 +259,20 @@ async fn e2e_recovery_success_resets_failures_but_keeps_last_error() {
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]),
         provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -204,6 +289,7 @@ async fn e2e_recovery_success_resets_failures_but_keeps_last_error() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
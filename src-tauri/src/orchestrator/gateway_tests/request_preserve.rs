@@ -18,6 +18,7 @@ async fn accepts_large_json_body_over_default_limit() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -64,6 +65,7 @@ async fn logs_pre_handler_json_rejections() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -108,6 +110,436 @@ async fn logs_pre_handler_json_rejections() {
     assert!(joined.contains("413") || joined.contains("400"));
 }
 
+#[tokio::test]
+async fn rejects_proxy_request_over_configured_body_limit() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = open_store_dir(tmp.path().join("data")).expect("store");
+    let secrets = SecretStore::new(tmp.path().join("secrets.json"));
+
+    let mut cfg = AppConfig::default_config();
+    cfg.routing.max_request_body_bytes = 256;
+    let router = Arc::new(RouterState::new(&cfg, unix_ms()));
+    let cfg = Arc::new(RwLock::new(cfg));
+    let state = GatewayState {
+        cfg: cfg.clone(),
+        router,
+        store: store.clone(),
+        upstream: UpstreamClient::new(),
+        secrets,
+        last_activity_unix_ms: Arc::new(AtomicU64::new(0)),
+        last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
+        usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
+        prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
+        client_sessions: Arc::new(RwLock::new(HashMap::new())),
+    };
+    let app = build_router(state);
+    let body = json!({ "input": "a".repeat(1024), "stream": false }).to_string();
+
+    // Declared Content-Length is rejected up front.
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/responses")
+                .header("content-type", "application/json")
+                .header("content-length", body.len().to_string())
+                .body(Body::from(body.clone()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let payload: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(payload["error"]["type"], "request_too_large");
+
+    // Without a Content-Length the limit is enforced while the body streams in.
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/responses")
+                .header("content-type", "application/json")
+                .body(Body::from(body.clone()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+
+    let events = store.list_events_range(None, None, Some(10));
+    let too_large = events
+        .iter()
+        .filter(|e| e.get("code").and_then(|v| v.as_str()) == Some("routing.request_too_large"))
+        .count();
+    assert_eq!(too_large, 2);
+
+    // Zero disables the limit.
+    cfg.write().routing.max_request_body_bytes = 0;
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/responses")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_ne!(resp.status(), StatusCode::PAYLOAD_TOO_LARGE);
+}
+
+#[tokio::test]
+async fn cors_headers_follow_configured_origins() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = open_store_dir(tmp.path().join("data")).expect("store");
+    let secrets = SecretStore::new(tmp.path().join("secrets.json"));
+    let mut cfg = AppConfig::default_config();
+    cfg.routing.cors_allowed_origins = vec!["http://localhost:5173".to_string()];
+    let router = Arc::new(RouterState::new(&cfg, unix_ms()));
+    let cfg = Arc::new(RwLock::new(cfg));
+    let state = GatewayState {
+        cfg: cfg.clone(),
+        router,
+        store,
+        upstream: UpstreamClient::new(),
+        secrets,
+        last_activity_unix_ms: Arc::new(AtomicU64::new(0)),
+        last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
+        usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
+        prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
+        client_sessions: Arc::new(RwLock::new(HashMap::new())),
+    };
+    let app = build_router(state);
+    let preflight = |origin: &str| {
+        Request::builder()
+            .method("OPTIONS")
+            .uri("/v1/responses")
+            .header("origin", origin)
+            .header("access-control-request-method", "POST")
+            .header("access-control-request-headers", "authorization, x-custom")
+            .body(Body::empty())
+            .unwrap()
+    };
+
+    let resp = app
+        .clone()
+        .oneshot(preflight("http://localhost:5173"))
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::NO_CONTENT);
+    let headers = resp.headers();
+    assert_eq!(
+        headers["access-control-allow-origin"],
+        "http://localhost:5173"
+    );
+    assert!(headers["access-control-allow-headers"]
+        .to_str()
+        .unwrap()
+        .contains("authorization"));
+    assert!(headers["access-control-allow-methods"]
+        .to_str()
+        .unwrap()
+        .contains("POST"));
+
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/health")
+                .header("origin", "http://localhost:5173")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers()["access-control-allow-origin"],
+        "http://localhost:5173"
+    );
+
+    let resp = app
+        .clone()
+        .oneshot(preflight("http://other.example"))
+        .await
+        .unwrap();
+    assert!(resp.headers().get("access-control-allow-origin").is_none());
+
+    cfg.write().routing.cors_allowed_origins = vec!["*".to_string()];
+    let resp = app
+        .clone()
+        .oneshot(preflight("http://other.example"))
+        .await
+        .unwrap();
+    assert_eq!(resp.headers()["access-control-allow-origin"], "*");
+
+    cfg.write().routing.cors_allowed_origins.clear();
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri("/health")
+                .header("origin", "http://localhost:5173")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert!(resp.headers().get("access-control-allow-origin").is_none());
+}
+
+#[tokio::test]
+async fn rejects_peers_outside_allowed_peer_ips() {
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = open_store_dir(tmp.path().join("data")).expect("store");
+    let secrets = SecretStore::new(tmp.path().join("secrets.json"));
+
+    // Router::oneshot has no ConnectInfo, so the peer falls back to 127.0.0.1.
+    let mut cfg = AppConfig::default_config();
+    cfg.routing.allowed_peer_ips = vec!["10.0.0.0/8".to_string()];
+    let router = Arc::new(RouterState::new(&cfg, unix_ms()));
+    let cfg = Arc::new(RwLock::new(cfg));
+    let state = GatewayState {
+        cfg: cfg.clone(),
+        router,
+        store: store.clone(),
+        upstream: UpstreamClient::new(),
+        secrets,
+        last_activity_unix_ms: Arc::new(AtomicU64::new(0)),
+        last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
+        usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
+        prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
+        client_sessions: Arc::new(RwLock::new(HashMap::new())),
+    };
+    let app = build_router(state);
+
+    for uri in ["/health", "/v1/responses"] {
+        let resp = app
+            .clone()
+            .oneshot(
+                Request::builder()
+                    .method(if uri == "/health" { "GET" } else { "POST" })
+                    .uri(uri)
+                    .header("content-type", "application/json")
+                    .body(Body::from("{}"))
+                    .unwrap(),
+            )
+            .await
+            .unwrap();
+        assert_eq!(resp.status(), StatusCode::FORBIDDEN);
+        let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+            .await
+            .unwrap();
+        let payload: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+        assert_eq!(payload["error"]["type"], "forbidden");
+    }
+
+    // Repeated rejections of the same peer are debounced into a single event.
+    let events = store.list_events_range(None, None, Some(10));
+    let rejected = events
+        .iter()
+        .filter(|e| e.get("code").and_then(|v| v.as_str()) == Some("routing.peer_rejected"))
+        .count();
+    assert_eq!(rejected, 1);
+
+    cfg.write().routing.allowed_peer_ips = vec!["127.0.0.1".to_string()];
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/health")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    // An empty list allows every peer.
+    cfg.write().routing.allowed_peer_ips.clear();
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri("/health")
+                .body(Body::empty())
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+}
+
+#[tokio::test]
+async fn anthropic_messages_round_trip_through_responses_upstream() {
+    let captured = Arc::new(Mutex::new(None));
+    let captured2 = captured.clone();
+    let app = Router::new().route(
+        "/v1/responses",
+        post(move |Json(body): Json<serde_json::Value>| {
+            *captured2.lock() = Some(body);
+            async move {
+                Json(json!({
+                    "id": "resp_test",
+                    "model": "gpt-test",
+                    "output": [{"content": [{"type": "output_text", "text": "ok"}]}],
+                    "usage": {"input_tokens": 5, "output_tokens": 1, "total_tokens": 6}
+                }))
+            }
+        }),
+    );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let base_url = format!("http://{}:{}/v1", addr.ip(), addr.port());
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+
+    let cfg = AppConfig {
+        listen: ListenConfig {
+            host: "127.0.0.1".to_string(),
+            port: 0,
+            unix_socket_path: None,
+        },
+        routing: RoutingConfig {
+            preferred_provider: "p1".to_string(),
+            session_preferred_providers: std::collections::BTreeMap::new(),
+            route_mode: crate::orchestrator::config::RouteMode::FollowPreferredAuto,
+            auto_return_to_preferred: true,
+            preferred_stable_seconds: 1,
+            failure_threshold: 1,
+            cooldown_seconds: 1,
+            request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: true,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
+        },
+        providers: std::collections::BTreeMap::from([(
+            "p1".to_string(),
+            ProviderConfig {
+                display_name: "P1".to_string(),
+                base_url,
+                usage_adapter: String::new(),
+                usage_base_url: None,
+                group: None,
+                disabled: false,
+                supports_websockets: false,
+                api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
+            },
+        )]),
+        provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
+    };
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = open_store_dir(tmp.path().join("data")).expect("store");
+    let secrets = SecretStore::new(tmp.path().join("secrets.json"));
+    let router = Arc::new(RouterState::new(&cfg, unix_ms()));
+    let state = GatewayState {
+        cfg: Arc::new(RwLock::new(cfg)),
+        router,
+        store,
+        upstream: UpstreamClient::new(),
+        secrets,
+        last_activity_unix_ms: Arc::new(AtomicU64::new(0)),
+        last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
+        usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
+        prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
+        client_sessions: Arc::new(RwLock::new(HashMap::new())),
+    };
+
+    let app = build_router(state);
+    let send = |body: serde_json::Value| {
+        app.clone().oneshot(
+            Request::builder()
+                .uri("/v1/messages")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+    };
+
+    let resp = send(json!({
+        "model": "gpt-test",
+        "max_tokens": 64,
+        "system": "Be brief.",
+        "messages": [{"role": "user", "content": "hi"}]
+    }))
+    .await
+    .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let message: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(message["type"], "message");
+    assert_eq!(message["content"][0]["text"], "ok");
+    assert_eq!(message["usage"]["input_tokens"], 5);
+
+    let captured = captured.lock().clone().expect("captured body");
+    assert_eq!(captured["instructions"], "Be brief.");
+    assert_eq!(captured["max_output_tokens"], 64);
+    assert_eq!(captured["input"][0]["content"][0]["text"], "hi");
+
+    let resp = send(json!({
+        "model": "gpt-test",
+        "max_tokens": 64,
+        "tools": [{"name": "lookup", "input_schema": {}}],
+        "messages": [{"role": "user", "content": "hi"}]
+    }))
+    .await
+    .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let error: serde_json::Value = serde_json::from_slice(&bytes).unwrap();
+    assert_eq!(error["error"]["type"], "invalid_request_error");
+}
+
 #[tokio::test]
 async fn preserves_tool_input_and_previous_response_id() {
     let captured = Arc::new(Mutex::new(None));
@@ -136,6 +568,153 @@ async fn preserves_tool_input_and_previous_response_id() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
+        },
+        routing: RoutingConfig {
+            preferred_provider: "p1".to_string(),
+            session_preferred_providers: std::collections::BTreeMap::new(),
+            route_mode: crate::orchestrator::config::RouteMode::FollowPreferredAuto,
+            auto_return_to_preferred: true,
+            preferred_stable_seconds: 1,
+            failure_threshold: 1,
+            cooldown_seconds: 1,
+            request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
+        },
+        providers: std::collections::BTreeMap::from([(
+            "p1".to_string(),
+            ProviderConfig {
+                display_name: "P1".to_string(),
+                base_url,
+                usage_adapter: String::new(),
+                usage_base_url: None,
+                group: None,
+                disabled: false,
+                supports_websockets: false,
+                api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
+            },
+        )]),
+        provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
+    };
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = open_store_dir(tmp.path().join("data")).expect("store");
+    let secrets = SecretStore::new(tmp.path().join("secrets.json"));
+    let router = Arc::new(RouterState::new(&cfg, unix_ms()));
+    let state = GatewayState {
+        cfg: Arc::new(RwLock::new(cfg)),
+        router,
+        store,
+        upstream: UpstreamClient::new(),
+        secrets,
+        last_activity_unix_ms: Arc::new(AtomicU64::new(0)),
+        last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
+        usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
+        prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
+        client_sessions: Arc::new(RwLock::new(HashMap::new())),
+    };
+
+    let app = build_router(state);
+    let input = json!({
+        "type": "message",
+        "role": "user",
+        "content": [{
+            "type": "tool_output",
+            "tool_call_id": "call_1",
+            "output": "C:\\\\work\\\\example-project"
+        }]
+    });
+    let body = json!({
+        "model": "gpt-test",
+        "input": input,
+        "previous_response_id": "resp_prev",
+        "stream": false
+    });
+
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri("/v1/responses")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(body.to_string()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+
+    let captured = captured.lock().clone().expect("captured body");
+    assert_eq!(captured.get("previous_response_id").unwrap(), "resp_prev");
+    assert_eq!(captured.get("input").unwrap(), &input);
+}
+
+#[tokio::test]
+async fn injects_provider_default_model_when_request_omits_model() {
+    let captured = Arc::new(Mutex::new(None));
+    let captured2 = captured.clone();
+    let app = Router::new().route(
+        "/v1/responses",
+        post(move |Json(body): Json<serde_json::Value>| {
+            *captured2.lock() = Some(body);
+            async move {
+                Json(json!({
+                    "id": "resp_test",
+                    "output": [{"content": [{"type": "output_text", "text": "ok"}]}]
+                }))
+            }
+        }),
+    );
+
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let base_url = format!("http://{}:{}/v1", addr.ip(), addr.port());
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+
+    let cfg = AppConfig {
+        listen: ListenConfig {
+            host: "127.0.0.1".to_string(),
+            port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -146,73 +725,187 @@ async fn preserves_tool_input_and_previous_response_id() {
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
-        providers: std::collections::BTreeMap::from([(
-            "p1".to_string(),
-            ProviderConfig {
-                display_name: "P1".to_string(),
-                base_url,
-                usage_adapter: String::new(),
-                usage_base_url: None,
-                group: None,
-                disabled: false,
-                supports_websockets: false,
-                api_key: String::new(),
-            },
-        )]),
-        provider_order: vec!["p1".to_string()],
+        providers: std::collections::BTreeMap::from([
+            (
+                "p1".to_string(),
+                ProviderConfig {
+                    display_name: "P1".to_string(),
+                    base_url: base_url.clone(),
+                    usage_adapter: String::new(),
+                    usage_base_url: None,
+                    group: None,
+                    disabled: false,
+                    supports_websockets: false,
+                    api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: Some("gpt-default".to_string()),
+                    tier: 0,
+                    notes: String::new(),
+                },
+            ),
+            (
+                "p2".to_string(),
+                ProviderConfig {
+                    display_name: "P2".to_string(),
+                    base_url,
+                    usage_adapter: String::new(),
+                    usage_base_url: None,
+                    group: None,
+                    disabled: false,
+                    supports_websockets: false,
+                    api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
+                },
+            ),
+        ]),
+        provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
     let store = open_store_dir(tmp.path().join("data")).expect("store");
     let secrets = SecretStore::new(tmp.path().join("secrets.json"));
     let router = Arc::new(RouterState::new(&cfg, unix_ms()));
+    let cfg = Arc::new(RwLock::new(cfg));
     let state = GatewayState {
-        cfg: Arc::new(RwLock::new(cfg)),
-        router,
-        store,
+        cfg: cfg.clone(),
+        router: router.clone(),
+        store: store.clone(),
         upstream: UpstreamClient::new(),
         secrets,
         last_activity_unix_ms: Arc::new(AtomicU64::new(0)),
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
     let app = build_router(state);
-    let input = json!({
-        "type": "message",
-        "role": "user",
-        "content": [{
-            "type": "tool_output",
-            "tool_call_id": "call_1",
-            "output": "C:\\\\work\\\\example-project"
-        }]
-    });
-    let body = json!({
-        "model": "gpt-test",
-        "input": input,
-        "previous_response_id": "resp_prev",
-        "stream": false
-    });
+    let body = json!({ "input": "hello", "stream": false }).to_string();
 
     let resp = app
+        .clone()
         .oneshot(
             Request::builder()
                 .uri("/v1/responses")
                 .method("POST")
                 .header("content-type", "application/json")
-                .body(Body::from(body.to_string()))
+                .body(Body::from(body.clone()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let captured_body = captured.lock().take().expect("captured body");
+    assert_eq!(captured_body["model"], "gpt-default");
+    let events = store.list_events_range(None, None, Some(10));
+    assert!(events.iter().any(|event| {
+        event.get("code").and_then(|v| v.as_str()) == Some("routing.default_model_applied")
+    }));
+
+    // A preferred provider without a default is skipped in favour of one that has it.
+    {
+        let mut cfg = cfg.write();
+        cfg.providers.get_mut("p1").expect("p1").default_model = None;
+        cfg.providers.get_mut("p2").expect("p2").default_model = Some("gpt-p2".to_string());
+    }
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/v1/responses")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(body.clone()))
                 .unwrap(),
         )
         .await
         .unwrap();
     assert_eq!(resp.status(), StatusCode::OK);
+    let captured_body = captured.lock().take().expect("captured body");
+    assert_eq!(captured_body["model"], "gpt-p2");
 
-    let captured = captured.lock().clone().expect("captured body");
-    assert_eq!(captured.get("previous_response_id").unwrap(), "resp_prev");
-    assert_eq!(captured.get("input").unwrap(), &input);
+    // The only provider with a default is cooling down: still the client's bad request.
+    {
+        let mut cooling_cfg = cfg.read().clone();
+        cooling_cfg.routing.cooldown_seconds = 600;
+        router.mark_failure("p2", &cooling_cfg, "boom", unix_ms());
+    }
+    let resp = app
+        .clone()
+        .oneshot(
+            Request::builder()
+                .uri("/v1/responses")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(body.clone()))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert!(captured.lock().is_none());
+
+    // Without a client model or any provider default the gateway rejects the request itself.
+    cfg.write()
+        .providers
+        .get_mut("p2")
+        .expect("p2")
+        .default_model = None;
+    let resp = app
+        .oneshot(
+            Request::builder()
+                .uri("/v1/responses")
+                .method("POST")
+                .header("content-type", "application/json")
+                .body(Body::from(body))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::BAD_REQUEST);
+    assert!(captured.lock().is_none());
 }
 
 #[tokio::test]
@@ -243,6 +936,7 @@ async fn preserves_tool_input_inside_message_array() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -253,6 +947,32 @@ async fn preserves_tool_input_inside_message_array() {
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([(
             "p1".to_string(),
@@ -265,9 +985,20 @@ async fn preserves_tool_input_inside_message_array() {
                 disabled: false,
                 supports_websockets: false,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]),
         provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -284,6 +1015,7 @@ async fn preserves_tool_input_inside_message_array() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -350,6 +1082,7 @@ async fn preserves_previous_response_id_for_plain_messages() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -360,6 +1093,32 @@ async fn preserves_previous_response_id_for_plain_messages() {
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([(
             "p1".to_string(),
@@ -372,9 +1131,20 @@ async fn preserves_previous_response_id_for_plain_messages() {
                 disabled: false,
                 supports_websockets: false,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]),
         provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -391,6 +1161,7 @@ async fn preserves_previous_response_id_for_plain_messages() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -424,3 +1195,443 @@ async fn preserves_previous_response_id_for_plain_messages() {
     assert_eq!(captured.get("previous_response_id").unwrap(), "resp_prev");
     assert_eq!(captured.get("input").unwrap(), &input);
 }
+
+#[tokio::test]
+async fn rewrites_model_alias_only_for_provider_with_mapping() {
+    fn capture_upstream(captured: Arc<Mutex<Option<serde_json::Value>>>) -> Router {
+        Router::new().route(
+            "/v1/responses",
+            post(move |Json(body): Json<serde_json::Value>| {
+                *captured.lock() = Some(body);
+                async move {
+                    Json(json!({
+                        "id": "resp_test",
+                        "output": [{"content": [{"type": "output_text", "text": "ok"}]}]
+                    }))
+                }
+            }),
+        )
+    }
+
+    let captured_p1 = Arc::new(Mutex::new(None));
+    let captured_p2 = Arc::new(Mutex::new(None));
+    let mut base_urls = Vec::new();
+    for captured in [captured_p1.clone(), captured_p2.clone()] {
+        let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let addr = listener.local_addr().unwrap();
+        base_urls.push(format!("http://{}:{}/v1", addr.ip(), addr.port()));
+        let app = capture_upstream(captured);
+        tokio::spawn(async move {
+            let _ = axum::serve(listener, app).await;
+        });
+    }
+
+    let cfg = AppConfig {
+        listen: ListenConfig {
+            host: "127.0.0.1".to_string(),
+            port: 0,
+            unix_socket_path: None,
+        },
+        routing: RoutingConfig {
+            preferred_provider: "p1".to_string(),
+            session_preferred_providers: std::collections::BTreeMap::new(),
+            route_mode: crate::orchestrator::config::RouteMode::FollowPreferredAuto,
+            auto_return_to_preferred: true,
+            preferred_stable_seconds: 1,
+            failure_threshold: 1,
+            cooldown_seconds: 1,
+            request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
+        },
+        providers: std::collections::BTreeMap::from([
+            (
+                "p1".to_string(),
+                ProviderConfig {
+                    display_name: "P1".to_string(),
+                    base_url: base_urls[0].clone(),
+                    usage_adapter: String::new(),
+                    usage_base_url: None,
+                    group: None,
+                    disabled: false,
+                    supports_websockets: false,
+                    api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::from([(
+                        "gpt-5-codex".to_string(),
+                        "vendor-codex-large".to_string(),
+                    )]),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
+                },
+            ),
+            (
+                "p2".to_string(),
+                ProviderConfig {
+                    display_name: "P2".to_string(),
+                    base_url: base_urls[1].clone(),
+                    usage_adapter: String::new(),
+                    usage_base_url: None,
+                    group: None,
+                    disabled: false,
+                    supports_websockets: false,
+                    api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
+                },
+            ),
+        ]),
+        provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
+    };
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = open_store_dir(tmp.path().join("data")).expect("store");
+    let secrets = SecretStore::new(tmp.path().join("secrets.json"));
+    let router = Arc::new(RouterState::new(&cfg, unix_ms()));
+    let state = GatewayState {
+        cfg: Arc::new(RwLock::new(cfg)),
+        router: router.clone(),
+        store,
+        upstream: UpstreamClient::new(),
+        secrets,
+        last_activity_unix_ms: Arc::new(AtomicU64::new(0)),
+        last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
+        usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
+        prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
+        client_sessions: Arc::new(RwLock::new(HashMap::new())),
+    };
+
+    let app = build_router(state);
+    let body = json!({
+        "model": "gpt-5-codex",
+        "input": "hello",
+        "stream": false
+    });
+    let send = |app: Router| {
+        let body = body.to_string();
+        async move {
+            app.oneshot(
+                Request::builder()
+                    .uri("/v1/responses")
+                    .method("POST")
+                    .header("content-type", "application/json")
+                    .body(Body::from(body))
+                    .unwrap(),
+            )
+            .await
+            .unwrap()
+        }
+    };
+
+    let resp = send(app.clone()).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let p1_body = captured_p1.lock().clone().expect("p1 captured body");
+    assert_eq!(p1_body.get("model").unwrap(), "vendor-codex-large");
+
+    router.set_manual_override(Some("p2".to_string()));
+    let resp = send(app).await;
+    assert_eq!(resp.status(), StatusCode::OK);
+    let p2_body = captured_p2.lock().clone().expect("p2 captured body");
+    assert_eq!(p2_body.get("model").unwrap(), "gpt-5-codex");
+}
+
+#[tokio::test]
+async fn stream_passthrough_forwards_upstream_events_incrementally() {
+    // The upstream holds the completion event back until the test has already received the
+    // first event through the gateway. A buffering proxy would never deliver it.
+    let release = Arc::new(tokio::sync::Notify::new());
+    let release_upstream = release.clone();
+    let app = Router::new().route(
+        "/v1/responses",
+        post(move |_body: Json<serde_json::Value>| {
+            let release = release_upstream.clone();
+            async move {
+                let stream = async_stream::stream! {
+                    yield Ok::<_, std::convert::Infallible>(bytes::Bytes::from_static(
+                        b"data: {\"type\":\"response.created\",\"response\":{\"id\":\"resp_pt\",\"model\":\"gpt-5\"}}\n\n",
+                    ));
+                    release.notified().await;
+                    yield Ok(bytes::Bytes::from_static(
+                        b"data: {\"type\":\"response.completed\",\"response\":{\"id\":\"resp_pt\",\"model\":\"gpt-5\"}}\n\n",
+                    ));
+                };
+                let mut resp = axum::response::Response::new(Body::from_stream(stream));
+                resp.headers_mut().insert(
+                    axum::http::header::CONTENT_TYPE,
+                    axum::http::HeaderValue::from_static("text/event-stream"),
+                );
+                resp
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let base_url = format!("http://{}:{}/v1", addr.ip(), addr.port());
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+
+    let mut cfg = AppConfig::default_config();
+    cfg.routing.preferred_provider = "p1".to_string();
+    cfg.providers = std::collections::BTreeMap::from([(
+        "p1".to_string(),
+        ProviderConfig {
+            display_name: "P1".to_string(),
+            base_url,
+            usage_adapter: String::new(),
+            usage_base_url: None,
+            group: None,
+            disabled: false,
+            supports_websockets: false,
+            api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
+        },
+    )]);
+    cfg.provider_order = vec!["p1".to_string()];
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = open_store_dir(tmp.path().join("data")).expect("store");
+    let secrets = SecretStore::new(tmp.path().join("secrets.json"));
+    let router = Arc::new(RouterState::new(&cfg, unix_ms()));
+    let state = GatewayState {
+        cfg: Arc::new(RwLock::new(cfg)),
+        router,
+        store,
+        upstream: UpstreamClient::new(),
+        secrets,
+        last_activity_unix_ms: Arc::new(AtomicU64::new(0)),
+        last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
+        usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
+        prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
+        client_sessions: Arc::new(RwLock::new(HashMap::new())),
+    };
+
+    let resp = build_router(state)
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/responses")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    json!({
+                        "model": "gpt-5",
+                        "input": [{
+                            "type": "message",
+                            "role": "user",
+                            "content": [{"type": "input_text", "text": "hello"}]
+                        }],
+                        "stream": true
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    assert_eq!(
+        resp.headers()
+            .get("content-type")
+            .and_then(|v| v.to_str().ok()),
+        Some("text/event-stream")
+    );
+    assert_eq!(
+        resp.headers()
+            .get("x-accel-buffering")
+            .and_then(|v| v.to_str().ok()),
+        Some("no")
+    );
+
+    let mut body = resp.into_body().into_data_stream();
+    let mut received = String::new();
+    while !received.contains("response.created") {
+        let chunk = tokio::time::timeout(
+            std::time::Duration::from_secs(5),
+            futures_util::StreamExt::next(&mut body),
+        )
+        .await
+        .expect("first event should arrive before upstream completes")
+        .expect("stream open")
+        .expect("chunk");
+        received.push_str(&String::from_utf8_lossy(&chunk));
+    }
+    assert!(!received.contains("response.completed"));
+    assert!(
+        received.ends_with("\n\n"),
+        "SSE framing preserved: {received:?}"
+    );
+
+    release.notify_one();
+    while let Some(chunk) = tokio::time::timeout(
+        std::time::Duration::from_secs(5),
+        futures_util::StreamExt::next(&mut body),
+    )
+    .await
+    .expect("stream should finish after release")
+    {
+        received.push_str(&String::from_utf8_lossy(&chunk.expect("chunk")));
+    }
+    assert!(received.contains("response.completed"));
+}
+
+#[tokio::test]
+async fn stream_usage_from_trailing_usage_chunk_is_recorded() {
+    let captured = Arc::new(Mutex::new(None));
+    let captured_upstream = captured.clone();
+    let app = Router::new().route(
+        "/v1/responses",
+        post(move |Json(body): Json<serde_json::Value>| {
+            *captured_upstream.lock() = Some(body);
+            async move {
+                let stream = async_stream::stream! {
+                    yield Ok::<_, std::convert::Infallible>(bytes::Bytes::from_static(
+                        b"data: {\"type\":\"response.created\",\"response\":{\"id\":\"resp_u\",\"model\":\"gpt-5\"}}\n\n",
+                    ));
+                    yield Ok(bytes::Bytes::from_static(
+                        b"data: {\"type\":\"response.completed\",\"response\":{\"id\":\"resp_u\",\"model\":\"gpt-5\"}}\n\n",
+                    ));
+                    yield Ok(bytes::Bytes::from_static(
+                        b"data: {\"object\":\"chat.completion.chunk\",\"choices\":[],\"usage\":{\"prompt_tokens\":7,\"completion_tokens\":5,\"total_tokens\":12}}\n\ndata: [DONE]\n\n",
+                    ));
+                };
+                let mut resp = axum::response::Response::new(Body::from_stream(stream));
+                resp.headers_mut().insert(
+                    axum::http::header::CONTENT_TYPE,
+                    axum::http::HeaderValue::from_static("text/event-stream"),
+                );
+                resp
+            }
+        }),
+    );
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let addr = listener.local_addr().unwrap();
+    let base_url = format!("http://{}:{}/v1", addr.ip(), addr.port());
+    tokio::spawn(async move {
+        let _ = axum::serve(listener, app).await;
+    });
+
+    let mut cfg = AppConfig::default_config();
+    cfg.routing.preferred_provider = "p1".to_string();
+    cfg.providers = std::collections::BTreeMap::from([(
+        "p1".to_string(),
+        ProviderConfig {
+            display_name: "P1".to_string(),
+            base_url,
+            usage_adapter: String::new(),
+            usage_base_url: None,
+            group: None,
+            disabled: false,
+            supports_websockets: false,
+            api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
+        },
+    )]);
+    cfg.provider_order = vec!["p1".to_string()];
+
+    let tmp = tempfile::tempdir().expect("tempdir");
+    let store = open_store_dir(tmp.path().join("data")).expect("store");
+    let secrets = SecretStore::new(tmp.path().join("secrets.json"));
+    let router = Arc::new(RouterState::new(&cfg, unix_ms()));
+    let state = GatewayState {
+        cfg: Arc::new(RwLock::new(cfg)),
+        router,
+        store: store.clone(),
+        upstream: UpstreamClient::new(),
+        secrets,
+        last_activity_unix_ms: Arc::new(AtomicU64::new(0)),
+        last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
+        usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
+        prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
+        client_sessions: Arc::new(RwLock::new(HashMap::new())),
+    };
+
+    let resp = build_router(state)
+        .oneshot(
+            Request::builder()
+                .method("POST")
+                .uri("/v1/responses")
+                .header("content-type", "application/json")
+                .body(Body::from(
+                    json!({
+                        "model": "gpt-5",
+                        "input": "hello",
+                        "stream": true,
+                        "stream_options": { "include_usage": true }
+                    })
+                    .to_string(),
+                ))
+                .unwrap(),
+        )
+        .await
+        .unwrap();
+    assert_eq!(resp.status(), StatusCode::OK);
+    let bytes = axum::body::to_bytes(resp.into_body(), usize::MAX)
+        .await
+        .unwrap();
+    let received = String::from_utf8_lossy(&bytes);
+    assert!(received.contains("response.completed"));
+    assert!(received.contains("\"usage\""));
+
+    let upstream_body = captured.lock().clone().expect("captured body");
+    assert_eq!(upstream_body["stream_options"]["include_usage"], true);
+
+    let rows = store.list_usage_requests(10);
+    assert_eq!(rows.len(), 1);
+    assert_eq!(rows[0]["input_tokens"], 7);
+    assert_eq!(rows[0]["output_tokens"], 5);
+    assert_eq!(rows[0]["total_tokens"], 12);
+}
@@ -42,6 +42,7 @@ async fn switches_provider_rebuilds_history_without_prev_id() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -52,6 +53,32 @@ async fn switches_provider_rebuilds_history_without_prev_id() {
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([
             (
@@ -65,6 +92,14 @@ async fn switches_provider_rebuilds_history_without_prev_id() {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -78,10 +113,21 @@ async fn switches_provider_rebuilds_history_without_prev_id() {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]),
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -118,6 +164,7 @@ async fn switches_provider_rebuilds_history_without_prev_id() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -215,6 +262,7 @@ async fn switches_provider_rebuilds_history_with_session_alias_header() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -225,6 +273,32 @@ async fn switches_provider_rebuilds_history_with_session_alias_header() {
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([
             (
@@ -238,6 +312,14 @@ async fn switches_provider_rebuilds_history_with_session_alias_header() {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -251,10 +333,21 @@ async fn switches_provider_rebuilds_history_with_session_alias_header() {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]),
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -291,6 +384,7 @@ async fn switches_provider_rebuilds_history_with_session_alias_header() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -388,6 +482,7 @@ async fn switches_provider_rebuilds_history_with_session_id_in_body() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -398,6 +493,32 @@ async fn switches_provider_rebuilds_history_with_session_id_in_body() {
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([
             (
@@ -411,6 +532,14 @@ async fn switches_provider_rebuilds_history_with_session_id_in_body() {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -424,10 +553,21 @@ async fn switches_provider_rebuilds_history_with_session_id_in_body() {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]),
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -464,6 +604,7 @@ async fn switches_provider_rebuilds_history_with_session_id_in_body() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -561,6 +702,7 @@ async fn switches_provider_rebuilds_history_when_session_file_flush_is_delayed()
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -571,6 +713,32 @@ async fn switches_provider_rebuilds_history_when_session_file_flush_is_delayed()
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([
             (
@@ -584,6 +752,14 @@ async fn switches_provider_rebuilds_history_when_session_file_flush_is_delayed()
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -597,10 +773,21 @@ async fn switches_provider_rebuilds_history_when_session_file_flush_is_delayed()
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]),
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -672,6 +859,7 @@ async fn switches_provider_rebuilds_history_when_session_file_flush_is_delayed()
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -776,6 +964,7 @@ async fn retries_without_prev_id_when_upstream_rejects_it() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -786,6 +975,32 @@ async fn retries_without_prev_id_when_upstream_rejects_it() {
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([(
             "p1".to_string(),
@@ -798,9 +1013,20 @@ async fn retries_without_prev_id_when_upstream_rejects_it() {
                 disabled: false,
                 supports_websockets: false,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]),
         provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -838,6 +1064,7 @@ async fn retries_without_prev_id_when_upstream_rejects_it() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -917,6 +1144,7 @@ async fn unsupported_model_does_not_trip_provider_health_or_record_error_event()
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -927,6 +1155,32 @@ async fn unsupported_model_does_not_trip_provider_health_or_record_error_event()
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([(
             "p1".to_string(),
@@ -939,9 +1193,20 @@ async fn unsupported_model_does_not_trip_provider_health_or_record_error_event()
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]),
         provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -958,6 +1223,7 @@ async fn unsupported_model_does_not_trip_provider_health_or_record_error_event()
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -1056,6 +1322,7 @@ async fn unsupported_model_switches_session_to_next_provider_without_failure() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1066,6 +1333,32 @@ async fn unsupported_model_switches_session_to_next_provider_without_failure() {
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([
             (
@@ -1079,6 +1372,14 @@ async fn unsupported_model_switches_session_to_next_provider_without_failure() {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -1092,10 +1393,21 @@ async fn unsupported_model_switches_session_to_next_provider_without_failure() {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]),
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -1132,6 +1444,7 @@ async fn unsupported_model_switches_session_to_next_provider_without_failure() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -1258,6 +1571,7 @@ async fn unsupported_model_cache_skips_provider_on_same_session_and_model() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1268,6 +1582,32 @@ async fn unsupported_model_cache_skips_provider_on_same_session_and_model() {
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([
             (
@@ -1281,6 +1621,14 @@ async fn unsupported_model_cache_skips_provider_on_same_session_and_model() {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -1294,10 +1642,21 @@ async fn unsupported_model_cache_skips_provider_on_same_session_and_model() {
                     usage_adapter: String::new(),
                     usage_base_url: None,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]),
         provider_order: vec!["p1".to_string(), "p2".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -1314,6 +1673,7 @@ async fn unsupported_model_cache_skips_provider_on_same_session_and_model() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -1386,6 +1746,7 @@ async fn unsupported_model_cache_returns_cached_400_without_rehitting_only_provi
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1396,6 +1757,32 @@ async fn unsupported_model_cache_returns_cached_400_without_rehitting_only_provi
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([(
             "p1".to_string(),
@@ -1408,9 +1795,20 @@ async fn unsupported_model_cache_returns_cached_400_without_rehitting_only_provi
                 usage_adapter: String::new(),
                 usage_base_url: None,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]),
         provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -1427,6 +1825,7 @@ async fn unsupported_model_cache_returns_cached_400_without_rehitting_only_provi
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -1516,6 +1915,7 @@ async fn allows_request_without_prev_id_even_if_session_history_missing() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1526,6 +1926,32 @@ async fn allows_request_without_prev_id_even_if_session_history_missing() {
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([(
             "p1".to_string(),
@@ -1538,9 +1964,20 @@ async fn allows_request_without_prev_id_even_if_session_history_missing() {
                 disabled: false,
                 supports_websockets: false,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]),
         provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -1557,6 +1994,7 @@ async fn allows_request_without_prev_id_even_if_session_history_missing() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -1614,6 +2052,7 @@ async fn allows_request_without_session_id_when_no_prev() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1624,6 +2063,32 @@ async fn allows_request_without_session_id_when_no_prev() {
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([(
             "p1".to_string(),
@@ -1636,9 +2101,20 @@ async fn allows_request_without_session_id_when_no_prev() {
                 disabled: false,
                 supports_websockets: false,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]),
         provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -1655,6 +2131,7 @@ async fn allows_request_without_session_id_when_no_prev() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -1713,6 +2190,7 @@ async fn gateway_request_sets_session_model_provider_to_api_router() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1723,6 +2201,32 @@ async fn gateway_request_sets_session_model_provider_to_api_router() {
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([(
             "p1".to_string(),
@@ -1735,9 +2239,20 @@ async fn gateway_request_sets_session_model_provider_to_api_router() {
                 disabled: false,
                 supports_websockets: false,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]),
         provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -1755,6 +2270,7 @@ async fn gateway_request_sets_session_model_provider_to_api_router() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: client_sessions.clone(),
     };
 
@@ -1825,6 +2341,7 @@ async fn review_request_keeps_parent_main_session_unverified_from_body_source()
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1835,6 +2352,32 @@ async fn review_request_keeps_parent_main_session_unverified_from_body_source()
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([(
             "p1".to_string(),
@@ -1847,9 +2390,20 @@ async fn review_request_keeps_parent_main_session_unverified_from_body_source()
                 disabled: false,
                 supports_websockets: false,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]),
         provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -1867,6 +2421,7 @@ async fn review_request_keeps_parent_main_session_unverified_from_body_source()
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: client_sessions.clone(),
     };
 
@@ -1948,6 +2503,7 @@ async fn parent_thread_backfill_detects_missing_review_header_path() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -1958,6 +2514,32 @@ async fn parent_thread_backfill_detects_missing_review_header_path() {
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([(
             "p1".to_string(),
@@ -1970,9 +2552,20 @@ async fn parent_thread_backfill_detects_missing_review_header_path() {
                 disabled: false,
                 supports_websockets: false,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]),
         provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -1990,6 +2583,7 @@ async fn parent_thread_backfill_detects_missing_review_header_path() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: client_sessions.clone(),
     };
 
@@ -2072,6 +2666,7 @@ async fn stream_usage_prefers_response_created_model_not_unknown() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 0,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -2082,6 +2677,32 @@ async fn stream_usage_prefers_response_created_model_not_unknown() {
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([(
             "p1".to_string(),
@@ -2094,9 +2715,20 @@ async fn stream_usage_prefers_response_created_model_not_unknown() {
                 disabled: false,
                 supports_websockets: false,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]),
         provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -2114,6 +2746,7 @@ async fn stream_usage_prefers_response_created_model_not_unknown() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: client_sessions.clone(),
     };
 
@@ -2189,6 +2822,7 @@ async fn request_host_updates_session_reported_base_url_for_origin_detection() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -2199,6 +2833,32 @@ async fn request_host_updates_session_reported_base_url_for_origin_detection() {
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([(
             "p1".to_string(),
@@ -2211,9 +2871,20 @@ async fn request_host_updates_session_reported_base_url_for_origin_detection() {
                 disabled: false,
                 supports_websockets: false,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]),
         provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -2231,6 +2902,7 @@ async fn request_host_updates_session_reported_base_url_for_origin_detection() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: client_sessions.clone(),
     };
 
@@ -2308,6 +2980,7 @@ async fn usage_records_store_windows_and_wsl2_origin_from_request_host() {
         listen: ListenConfig {
             host: "127.0.0.1".to_string(),
             port: 4000,
+            unix_socket_path: None,
         },
         routing: RoutingConfig {
             preferred_provider: "p1".to_string(),
@@ -2318,6 +2991,32 @@ async fn usage_records_store_windows_and_wsl2_origin_from_request_host() {
             failure_threshold: 1,
             cooldown_seconds: 1,
             request_timeout_seconds: 5,
+            session_sticky_ttl_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+            usage_label: None,
+            max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+            allowed_peer_ips: Vec::new(),
+            connect_timeout_seconds: None,
+            read_timeout_seconds: None,
+            otel_endpoint: None,
+            key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+            default_base_url: None,
+            default_usage_adapter: None,
+            active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+            session_active_window_seconds:
+                crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+            read_only: false,
+            spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+            ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+            fractional_percent_display: false,
+            anthropic_compat: false,
+            cors_allowed_origins: Vec::new(),
+            max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+            evict_usage_requests: false,
+            event_query_default_limit:
+                crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+            event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+            keepalive_warm_seconds: 0,
         },
         providers: std::collections::BTreeMap::from([(
             "p1".to_string(),
@@ -2330,9 +3029,20 @@ async fn usage_records_store_windows_and_wsl2_origin_from_request_host() {
                 disabled: false,
                 supports_websockets: false,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]),
         provider_order: vec!["p1".to_string()],
+        events: Default::default(),
+        switchboard_profiles: Default::default(),
+        provider_templates: std::collections::BTreeMap::new(),
     };
 
     let tmp = tempfile::tempdir().expect("tempdir");
@@ -2349,6 +3059,7 @@ async fn usage_records_store_windows_and_wsl2_origin_from_request_host() {
         last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
         usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
         prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+        provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
         client_sessions: Arc::new(RwLock::new(HashMap::new())),
     };
 
@@ -1,7 +1,12 @@
+pub mod anthropic;
 pub mod config;
+pub mod config_validation;
+pub mod cors;
 pub mod gateway;
 pub mod gateway_bootstrap;
 pub mod openai;
+pub mod otel;
+pub mod peer_allowlist;
 pub mod providers;
 pub mod quota;
 pub mod router;
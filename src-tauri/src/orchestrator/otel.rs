@@ -0,0 +1,498 @@
+//! `routing.otel_endpoint`: optional OTLP/HTTP (JSON) span export for proxied requests.
+//!
+//! Each proxied request gets a root span; every provider attempt becomes a child "failover hop"
+//! span and every upstream call a span under the hop that made it. The trace lives in a task
+//! local, so instrumented code paths cost a single failed lookup when export is disabled.
+
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::{Arc, OnceLock};
+use std::task::{Context, Poll};
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+
+use futures_util::Stream;
+use parking_lot::Mutex;
+use serde_json::{json, Value};
+
+const OTLP_TRACES_PATH: &str = "/v1/traces";
+const OTLP_EXPORT_TIMEOUT: Duration = Duration::from_secs(5);
+const SPAN_KIND_INTERNAL: u8 = 1;
+const SPAN_KIND_SERVER: u8 = 2;
+const SPAN_KIND_CLIENT: u8 = 3;
+const STATUS_CODE_OK: u8 = 1;
+const STATUS_CODE_ERROR: u8 = 2;
+
+tokio::task_local! {
+    static CURRENT_TRACE: ProxyTrace;
+}
+
+#[derive(Debug, Clone)]
+enum AttrValue {
+    Str(String),
+    Int(u64),
+}
+
+#[derive(Debug, Clone)]
+struct SpanRecord {
+    span_id: String,
+    parent_span_id: Option<String>,
+    name: String,
+    kind: u8,
+    start_unix_nanos: u64,
+    end_unix_nanos: Option<u64>,
+    attributes: Vec<(&'static str, AttrValue)>,
+    error: Option<String>,
+}
+
+#[derive(Debug)]
+struct TraceState {
+    endpoint: String,
+    trace_id: String,
+    root: SpanRecord,
+    provider: Option<String>,
+    model: Option<String>,
+    usage: Option<(u64, u64, u64)>,
+    hops: Vec<SpanRecord>,
+    upstream_calls: Vec<SpanRecord>,
+    /// Monotonic start for `apirouter.duration_ms`; span timestamps stay wall-clock for OTLP.
+    started: std::time::Instant,
+    finished: bool,
+}
+
+/// Span data collected for one proxied request; exported once when the response completes.
+#[derive(Debug, Clone)]
+pub struct ProxyTrace(Arc<Mutex<TraceState>>);
+
+impl ProxyTrace {
+    pub fn start(endpoint: &str, method: &str, path: &str) -> Self {
+        let root = SpanRecord {
+            span_id: new_span_id(),
+            parent_span_id: None,
+            name: format!("{method} {path}"),
+            kind: SPAN_KIND_SERVER,
+            start_unix_nanos: unix_nanos(),
+            end_unix_nanos: None,
+            attributes: vec![
+                ("http.request.method", AttrValue::Str(method.to_string())),
+                ("url.path", AttrValue::Str(path.to_string())),
+            ],
+            error: None,
+        };
+        Self(Arc::new(Mutex::new(TraceState {
+            endpoint: traces_url(endpoint),
+            trace_id: uuid::Uuid::new_v4().simple().to_string(),
+            root,
+            provider: None,
+            model: None,
+            usage: None,
+            hops: Vec::new(),
+            upstream_calls: Vec::new(),
+            started: std::time::Instant::now(),
+            finished: false,
+        })))
+    }
+
+    /// Trace of the proxied request running on the current task, if export is enabled.
+    pub fn current() -> Option<Self> {
+        CURRENT_TRACE.try_with(Clone::clone).ok()
+    }
+
+    pub async fn scope<F: Future>(self, fut: F) -> F::Output {
+        CURRENT_TRACE.scope(self, fut).await
+    }
+
+    fn note_provider_attempt(&self, provider: &str) {
+        let mut state = self.0.lock();
+        let now = unix_nanos();
+        if let Some(previous) = state.hops.last_mut() {
+            previous.end_unix_nanos.get_or_insert(now);
+        }
+        let hop = state.hops.len() as u64;
+        let root_span_id = state.root.span_id.clone();
+        state.hops.push(SpanRecord {
+            span_id: new_span_id(),
+            parent_span_id: Some(root_span_id),
+            name: "failover hop".to_string(),
+            kind: SPAN_KIND_INTERNAL,
+            start_unix_nanos: now,
+            end_unix_nanos: None,
+            attributes: vec![
+                ("apirouter.provider", AttrValue::Str(provider.to_string())),
+                ("apirouter.failover.hop", AttrValue::Int(hop)),
+            ],
+            error: None,
+        });
+        state.provider = Some(provider.to_string());
+    }
+
+    fn record_upstream_call(
+        &self,
+        transport: &'static str,
+        target: &str,
+        start_unix_nanos: u64,
+        status: Option<u16>,
+        error: Option<String>,
+    ) {
+        let mut state = self.0.lock();
+        let parent = state
+            .hops
+            .last()
+            .map(|hop| hop.span_id.clone())
+            .unwrap_or_else(|| state.root.span_id.clone());
+        let mut attributes = vec![
+            (
+                "apirouter.upstream.transport",
+                AttrValue::Str(transport.to_string()),
+            ),
+            (
+                "apirouter.upstream.target",
+                AttrValue::Str(target.to_string()),
+            ),
+        ];
+        if let Some(provider) = state.provider.clone() {
+            attributes.push(("apirouter.provider", AttrValue::Str(provider)));
+        }
+        if let Some(status) = status {
+            attributes.push(("http.response.status_code", AttrValue::Int(status.into())));
+        }
+        let error = error.or_else(|| {
+            status
+                .filter(|status| *status >= 400)
+                .map(|status| format!("upstream returned {status}"))
+        });
+        state.upstream_calls.push(SpanRecord {
+            span_id: new_span_id(),
+            parent_span_id: Some(parent),
+            name: format!("upstream {transport}"),
+            kind: SPAN_KIND_CLIENT,
+            start_unix_nanos,
+            end_unix_nanos: Some(unix_nanos()),
+            attributes,
+            error,
+        });
+    }
+
+    /// Closes the root span and exports the trace. Later calls are no-ops.
+    pub fn finish(&self, http_status: u16) {
+        let (endpoint, payload) = {
+            let mut state = self.0.lock();
+            if state.finished {
+                return;
+            }
+            state.finished = true;
+            (
+                state.endpoint.clone(),
+                build_export_payload(&state, http_status, unix_nanos()),
+            )
+        };
+        let Ok(handle) = tokio::runtime::Handle::try_current() else {
+            return;
+        };
+        handle.spawn(async move {
+            // Export is best effort: a missing or slow collector must never affect proxying.
+            let _ = export_client().post(endpoint).json(&payload).send().await;
+        });
+    }
+}
+
+/// Marks the start of an attempt against `provider` on the current request's trace.
+pub fn note_provider_attempt(provider: &str) {
+    if let Some(trace) = ProxyTrace::current() {
+        trace.note_provider_attempt(provider);
+    }
+}
+
+/// Attaches provider, model, and token counts to the current request's root span.
+pub fn record_usage(provider: &str, model: &str, input: u64, output: u64, total: u64) {
+    let Some(trace) = ProxyTrace::current() else {
+        return;
+    };
+    let mut state = trace.0.lock();
+    state.provider = Some(provider.to_string());
+    if !model.trim().is_empty() {
+        state.model = Some(model.to_string());
+    }
+    state.usage = Some((input, output, total));
+}
+
+/// Runs one upstream call, recording it as a client span when the request is being traced.
+pub async fn trace_upstream_call<T, E, F>(
+    transport: &'static str,
+    target: &str,
+    status_of: impl FnOnce(&T) -> Option<u16>,
+    fut: F,
+) -> Result<T, E>
+where
+    E: std::fmt::Display,
+    F: Future<Output = Result<T, E>>,
+{
+    let Some(trace) = ProxyTrace::current() else {
+        return fut.await;
+    };
+    let started = unix_nanos();
+    let result = fut.await;
+    match &result {
+        Ok(value) => trace.record_upstream_call(transport, target, started, status_of(value), None),
+        Err(err) => {
+            trace.record_upstream_call(transport, target, started, None, Some(err.to_string()))
+        }
+    }
+    result
+}
+
+/// Response body wrapper that keeps the trace in scope while the body streams (so usage
+/// recorded at end of stream lands on the span) and finishes the trace when the body ends.
+pub struct TracedBody<S> {
+    inner: S,
+    trace: ProxyTrace,
+    http_status: u16,
+}
+
+impl<S> TracedBody<S> {
+    pub fn new(inner: S, trace: ProxyTrace, http_status: u16) -> Self {
+        Self {
+            inner,
+            trace,
+            http_status,
+        }
+    }
+}
+
+impl<S: Stream + Unpin> Stream for TracedBody<S> {
+    type Item = S::Item;
+
+    fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let trace = this.trace.clone();
+        let polled = CURRENT_TRACE.sync_scope(trace, || Pin::new(&mut this.inner).poll_next(cx));
+        if let Poll::Ready(None) = polled {
+            this.trace.finish(this.http_status);
+        }
+        polled
+    }
+}
+
+impl<S> Drop for TracedBody<S> {
+    fn drop(&mut self) {
+        // Covers clients that disconnect mid-stream.
+        self.trace.finish(self.http_status);
+    }
+}
+
+fn build_export_payload(state: &TraceState, http_status: u16, end_unix_nanos: u64) -> Value {
+    let mut root = state.root.clone();
+    root.end_unix_nanos = Some(end_unix_nanos);
+    root.attributes.push((
+        "http.response.status_code",
+        AttrValue::Int(http_status.into()),
+    ));
+    root.attributes.push((
+        "apirouter.duration_ms",
+        AttrValue::Int(state.started.elapsed().as_millis() as u64),
+    ));
+    if let Some(provider) = state.provider.clone() {
+        root.attributes
+            .push(("apirouter.provider", AttrValue::Str(provider)));
+    }
+    if let Some(model) = state.model.clone() {
+        root.attributes
+            .push(("apirouter.model", AttrValue::Str(model)));
+    }
+    if let Some((input, output, total)) = state.usage {
+        root.attributes
+            .push(("apirouter.usage.input_tokens", AttrValue::Int(input)));
+        root.attributes
+            .push(("apirouter.usage.output_tokens", AttrValue::Int(output)));
+        root.attributes
+            .push(("apirouter.usage.total_tokens", AttrValue::Int(total)));
+    }
+    root.attributes.push((
+        "apirouter.failover.hops",
+        AttrValue::Int(state.hops.len() as u64),
+    ));
+    if http_status >= 500 {
+        root.error = Some(format!("gateway responded {http_status}"));
+    }
+
+    let mut spans = vec![span_json(&state.trace_id, &root, end_unix_nanos)];
+    spans.extend(
+        state
+            .hops
+            .iter()
+            .chain(state.upstream_calls.iter())
+            .map(|span| span_json(&state.trace_id, span, end_unix_nanos)),
+    );
+    json!({
+        "resourceSpans": [{
+            "resource": {
+                "attributes": [attr_json("service.name", &AttrValue::Str("api-router".to_string()))],
+            },
+            "scopeSpans": [{
+                "scope": { "name": "api-router.gateway" },
+                "spans": spans,
+            }],
+        }],
+    })
+}
+
+fn span_json(trace_id: &str, span: &SpanRecord, fallback_end_unix_nanos: u64) -> Value {
+    let end = span.end_unix_nanos.unwrap_or(fallback_end_unix_nanos);
+    let status = match &span.error {
+        Some(message) => json!({ "code": STATUS_CODE_ERROR, "message": message }),
+        None => json!({ "code": STATUS_CODE_OK }),
+    };
+    let mut out = json!({
+        "traceId": trace_id,
+        "spanId": span.span_id,
+        "name": span.name,
+        "kind": span.kind,
+        // OTLP/JSON encodes 64-bit integers as strings.
+        "startTimeUnixNano": span.start_unix_nanos.to_string(),
+        "endTimeUnixNano": end.to_string(),
+        "attributes": span
+            .attributes
+            .iter()
+            .map(|(key, value)| attr_json(key, value))
+            .collect::<Vec<_>>(),
+        "status": status,
+    });
+    if let Some(parent) = &span.parent_span_id {
+        out["parentSpanId"] = Value::String(parent.clone());
+    }
+    out
+}
+
+fn attr_json(key: &str, value: &AttrValue) -> Value {
+    let value = match value {
+        AttrValue::Str(s) => json!({ "stringValue": s }),
+        AttrValue::Int(n) => json!({ "intValue": n.to_string() }),
+    };
+    json!({ "key": key, "value": value })
+}
+
+fn traces_url(endpoint: &str) -> String {
+    let trimmed = endpoint.trim().trim_end_matches('/');
+    if trimmed.ends_with(OTLP_TRACES_PATH) {
+        trimmed.to_string()
+    } else {
+        format!("{trimmed}{OTLP_TRACES_PATH}")
+    }
+}
+
+fn new_span_id() -> String {
+    let mut id = uuid::Uuid::new_v4().simple().to_string();
+    id.truncate(16);
+    id
+}
+
+fn unix_nanos() -> u64 {
+    SystemTime::now()
+        .duration_since(UNIX_EPOCH)
+        .map(|d| d.as_nanos() as u64)
+        .unwrap_or(0)
+}
+
+fn export_client() -> &'static reqwest::Client {
+    static CLIENT: OnceLock<reqwest::Client> = OnceLock::new();
+    CLIENT.get_or_init(|| {
+        reqwest::Client::builder()
+            .user_agent("api-router/0.1")
+            .timeout(OTLP_EXPORT_TIMEOUT)
+            .build()
+            .expect("reqwest client")
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn root_span(payload: &Value) -> &Value {
+        &payload["resourceSpans"][0]["scopeSpans"][0]["spans"][0]
+    }
+
+    fn attr<'a>(span: &'a Value, key: &str) -> Option<&'a Value> {
+        span["attributes"]
+            .as_array()?
+            .iter()
+            .find(|a| a["key"] == key)
+            .map(|a| &a["value"])
+    }
+
+    #[test]
+    fn traces_url_appends_otlp_path_once() {
+        assert_eq!(
+            traces_url("http://127.0.0.1:4318"),
+            "http://127.0.0.1:4318/v1/traces"
+        );
+        assert_eq!(
+            traces_url("http://127.0.0.1:4318/v1/traces/"),
+            "http://127.0.0.1:4318/v1/traces"
+        );
+    }
+
+    #[tokio::test]
+    async fn payload_nests_upstream_calls_under_failover_hops() {
+        let trace = ProxyTrace::start("http://collector", "POST", "/v1/responses");
+        trace
+            .clone()
+            .scope(async {
+                note_provider_attempt("p1");
+                let _ = trace_upstream_call("http", "/responses", |s: &u16| Some(*s), async {
+                    Ok::<_, String>(503)
+                })
+                .await;
+                note_provider_attempt("p2");
+                let _ = trace_upstream_call("http", "/responses", |s: &u16| Some(*s), async {
+                    Ok::<_, String>(200)
+                })
+                .await;
+                record_usage("p2", "gpt-test", 3, 5, 8);
+            })
+            .await;
+
+        let state = trace.0.lock();
+        let payload = build_export_payload(&state, 200, unix_nanos());
+        let spans = payload["resourceSpans"][0]["scopeSpans"][0]["spans"]
+            .as_array()
+            .unwrap();
+        assert_eq!(spans.len(), 5);
+        let root = root_span(&payload);
+        assert_eq!(root["kind"], SPAN_KIND_SERVER);
+        assert!(root.get("parentSpanId").is_none());
+        assert_eq!(
+            attr(root, "apirouter.provider").unwrap()["stringValue"],
+            "p2"
+        );
+        assert_eq!(
+            attr(root, "apirouter.model").unwrap()["stringValue"],
+            "gpt-test"
+        );
+        assert_eq!(
+            attr(root, "apirouter.usage.total_tokens").unwrap()["intValue"],
+            "8"
+        );
+        assert_eq!(
+            attr(root, "apirouter.failover.hops").unwrap()["intValue"],
+            "2"
+        );
+
+        let hops = &spans[1..3];
+        let calls = &spans[3..5];
+        for (hop, call) in hops.iter().zip(calls) {
+            assert_eq!(hop["parentSpanId"], root["spanId"]);
+            assert_eq!(call["parentSpanId"], hop["spanId"]);
+            assert_eq!(call["kind"], SPAN_KIND_CLIENT);
+            assert_eq!(call["traceId"], root["traceId"]);
+        }
+        assert_eq!(calls[0]["status"]["code"], STATUS_CODE_ERROR);
+        assert_eq!(calls[1]["status"]["code"], STATUS_CODE_OK);
+    }
+
+    #[test]
+    fn tracing_helpers_are_noops_outside_a_traced_request() {
+        assert!(ProxyTrace::current().is_none());
+        note_provider_attempt("p1");
+        record_usage("p1", "m", 1, 1, 2);
+    }
+}
@@ -0,0 +1,152 @@
+//! `routing.allowed_peer_ips`: exact addresses or CIDR ranges checked against the connecting
+//! peer before any auth or routing.
+
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::OnceLock;
+
+use parking_lot::Mutex;
+
+/// Repeated rejections of the same address are reported at most once per window.
+const PEER_REJECTED_EVENT_DEBOUNCE_MS: u64 = 60_000;
+const PEER_REJECTED_TRACKED_ADDRS_MAX: usize = 1024;
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PeerIpRule {
+    network: IpAddr,
+    prefix_len: u8,
+}
+
+impl PeerIpRule {
+    pub fn matches(&self, ip: IpAddr) -> bool {
+        match (self.network, ip.to_canonical()) {
+            (IpAddr::V4(net), IpAddr::V4(ip)) => prefix_eq(
+                u32::from(net).into(),
+                u32::from(ip).into(),
+                32,
+                self.prefix_len,
+            ),
+            (IpAddr::V6(net), IpAddr::V6(ip)) => {
+                prefix_eq(u128::from(net), u128::from(ip), 128, self.prefix_len)
+            }
+            _ => false,
+        }
+    }
+}
+
+fn prefix_eq(a: u128, b: u128, bits: u8, prefix_len: u8) -> bool {
+    if prefix_len == 0 {
+        return true;
+    }
+    let shift = u32::from(bits - prefix_len);
+    (a >> shift) == (b >> shift)
+}
+
+/// Accepts `10.0.0.5`, `10.0.0.0/8`, `::1` or `fd00::/8`.
+pub fn parse_peer_ip_rule(raw: &str) -> Result<PeerIpRule, String> {
+    let raw = raw.trim();
+    let (addr, prefix) = match raw.split_once('/') {
+        Some((addr, prefix)) => (addr.trim(), Some(prefix.trim())),
+        None => (raw, None),
+    };
+    let network = addr
+        .parse::<IpAddr>()
+        .map_err(|_| format!("invalid IP address {addr:?}"))?
+        .to_canonical();
+    let max_len = if network.is_ipv4() { 32 } else { 128 };
+    let prefix_len = match prefix {
+        Some(prefix) => prefix
+            .parse::<u8>()
+            .ok()
+            .filter(|len| *len <= max_len)
+            .ok_or_else(|| format!("invalid prefix length {prefix:?} (expected 0-{max_len})"))?,
+        None => max_len,
+    };
+    Ok(PeerIpRule {
+        network,
+        prefix_len,
+    })
+}
+
+/// Empty list allows every peer. Entries that fail to parse never match, so a typo narrows
+/// access instead of widening it; config validation reports them.
+pub fn peer_ip_allowed(rules: &[String], ip: IpAddr) -> bool {
+    if rules.is_empty() {
+        return true;
+    }
+    rules
+        .iter()
+        .filter_map(|rule| parse_peer_ip_rule(rule).ok())
+        .any(|rule| rule.matches(ip))
+}
+
+pub fn should_report_peer_rejection(ip: IpAddr, now_unix_ms: u64) -> bool {
+    static LAST_REPORTED: OnceLock<Mutex<HashMap<IpAddr, u64>>> = OnceLock::new();
+    let mut last_reported = LAST_REPORTED
+        .get_or_init(|| Mutex::new(HashMap::new()))
+        .lock();
+    if let Some(last) = last_reported.get(&ip) {
+        if now_unix_ms.saturating_sub(*last) < PEER_REJECTED_EVENT_DEBOUNCE_MS {
+            return false;
+        }
+    }
+    if last_reported.len() >= PEER_REJECTED_TRACKED_ADDRS_MAX {
+        last_reported
+            .retain(|_, last| now_unix_ms.saturating_sub(*last) < PEER_REJECTED_EVENT_DEBOUNCE_MS);
+    }
+    last_reported.insert(ip, now_unix_ms);
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse_peer_ip_rule, peer_ip_allowed, should_report_peer_rejection};
+    use std::net::IpAddr;
+
+    fn ip(raw: &str) -> IpAddr {
+        raw.parse().unwrap()
+    }
+
+    #[test]
+    fn empty_allowlist_allows_everyone() {
+        assert!(peer_ip_allowed(&[], ip("203.0.113.7")));
+    }
+
+    #[test]
+    fn matches_exact_and_cidr_entries() {
+        let rules = vec![
+            "127.0.0.1".to_string(),
+            "192.168.1.0/24".to_string(),
+            "fd00::/8".to_string(),
+        ];
+        assert!(peer_ip_allowed(&rules, ip("127.0.0.1")));
+        assert!(peer_ip_allowed(&rules, ip("192.168.1.77")));
+        assert!(peer_ip_allowed(&rules, ip("fd12::1")));
+        assert!(!peer_ip_allowed(&rules, ip("192.168.2.1")));
+        assert!(!peer_ip_allowed(&rules, ip("127.0.0.2")));
+        assert!(!peer_ip_allowed(&rules, ip("::1")));
+    }
+
+    #[test]
+    fn ipv4_mapped_peers_match_ipv4_rules() {
+        let rules = vec!["10.0.0.0/8".to_string()];
+        assert!(peer_ip_allowed(&rules, ip("::ffff:10.1.2.3")));
+    }
+
+    #[test]
+    fn invalid_entries_never_match() {
+        assert!(parse_peer_ip_rule("10.0.0.0/33").is_err());
+        assert!(parse_peer_ip_rule("not-an-ip").is_err());
+        let rules = vec!["not-an-ip".to_string()];
+        assert!(!peer_ip_allowed(&rules, ip("127.0.0.1")));
+    }
+
+    #[test]
+    fn rejection_reports_are_debounced_per_address() {
+        let addr = ip("198.51.100.200");
+        assert!(should_report_peer_rejection(addr, 1_000));
+        assert!(!should_report_peer_rejection(addr, 30_000));
+        assert!(should_report_peer_rejection(ip("198.51.100.201"), 30_000));
+        assert!(should_report_peer_rejection(addr, 61_000));
+    }
+}
@@ -1266,6 +1266,14 @@ fn matched_definition_for_base_url(base_url: &str) -> Option<ProviderDefinition>
         usage_adapter: String::new(),
         usage_base_url: None,
         api_key: String::new(),
+        model_aliases: std::collections::BTreeMap::new(),
+        system_prefix: None,
+        usage_field_map: None,
+        headers: std::collections::BTreeMap::new(),
+        routing_eligible: true,
+        default_model: None,
+        tier: 0,
+        notes: String::new(),
     };
     matched_provider_definition(&provider)
 }
@@ -1632,6 +1640,14 @@ explicit_endpoint_url = "{explicit_endpoint_url}"
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
 
         let profile = resolve_quota_profile(&provider);
@@ -1662,6 +1678,14 @@ explicit_endpoint_url = "{explicit_endpoint_url}"
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
 
         let profile = resolve_quota_profile(&provider);
@@ -1692,6 +1716,14 @@ explicit_endpoint_url = "{explicit_endpoint_url}"
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
 
         let profile = resolve_quota_profile(&provider);
@@ -1717,6 +1749,14 @@ explicit_endpoint_url = "{explicit_endpoint_url}"
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
 
         let profile = resolve_quota_profile(&provider);
@@ -1743,6 +1783,14 @@ explicit_endpoint_url = "{explicit_endpoint_url}"
             usage_adapter: String::new(),
             usage_base_url: Some("https://usage.routeai.cc/custom".to_string()),
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
 
         let profile = resolve_quota_profile(&provider);
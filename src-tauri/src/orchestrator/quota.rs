@@ -23,6 +23,7 @@ pub(crate) use super::providers::{
 };
 use super::secrets::UsageLoginConfig;
 use super::store::unix_ms;
+use super::upstream::UpstreamTimeouts;
 
 fn redact_url_for_logs(url: &reqwest::Url) -> String {
     // Avoid logging secrets in query strings (e.g. token_key=...).
@@ -113,6 +114,9 @@ pub struct QuotaSnapshot {
     pub monthly_spent_usd: Option<f64>,
     pub monthly_budget_usd: Option<f64>,
     pub package_expires_at_unix_ms: Option<u64>,
+    /// Filled from the provider's `usage_field_map` when one is configured.
+    pub reset_at_unix_ms: Option<u64>,
+    pub used_percent: Option<f64>,
     pub last_error: String,
     pub effective_usage_base: Option<String>,
     pub effective_usage_source: Option<String>,
@@ -164,6 +168,7 @@ struct QuotaCredentials<'a> {
 const USAGE_BASE_MIN_GAP_MS: u64 = 1_250;
 const USAGE_BASE_429_BACKOFF_MS: u64 = 20_000;
 const USAGE_BASE_MAX_INLINE_WAIT_MS: u64 = 2_500;
+const QUOTA_REFRESH_ALL_MAX_CONCURRENT_HOSTS: usize = 4;
 
 fn usage_base_refresh_gate() -> &'static Mutex<HashMap<String, u64>> {
     static STATE: OnceLock<Mutex<HashMap<String, u64>>> = OnceLock::new();
@@ -305,6 +310,8 @@ impl QuotaSnapshot {
             monthly_spent_usd: None,
             monthly_budget_usd: None,
             package_expires_at_unix_ms: None,
+            reset_at_unix_ms: None,
+            used_percent: None,
             last_error: String::new(),
             effective_usage_base: None,
             effective_usage_source: None,
@@ -330,6 +337,8 @@ impl QuotaSnapshot {
             "monthly_spent_usd": self.monthly_spent_usd,
             "monthly_budget_usd": self.monthly_budget_usd,
             "package_expires_at_unix_ms": self.package_expires_at_unix_ms,
+            "reset_at_unix_ms": self.reset_at_unix_ms,
+            "used_percent": self.used_percent,
             "last_error": self.last_error,
             "effective_usage_base": self.effective_usage_base,
             "effective_usage_source": self.effective_usage_source,
@@ -355,6 +364,8 @@ impl QuotaSnapshot {
             monthly_spent_usd: usage.monthly_used,
             monthly_budget_usd: usage.monthly_limit,
             package_expires_at_unix_ms: usage.expires_at_unix_ms,
+            reset_at_unix_ms: None,
+            used_percent: None,
             last_error: String::new(),
             effective_usage_base: usage.effective_usage_base,
             effective_usage_source: usage.effective_usage_source,
@@ -415,11 +426,17 @@ fn next_usage_proxy_for_provider(st: &GatewayState, provider_name: &str) -> Opti
     Some(proxy)
 }
 
+/// Connect and idle-read limits follow the routing settings; each usage call still sets its own
+/// short overall `.timeout(...)` so one hung endpoint cannot stall a whole refresh.
 fn build_usage_http_client(
     st: &GatewayState,
     provider_name: &str,
 ) -> Result<reqwest::Client, String> {
-    let mut builder = reqwest::Client::builder().user_agent("api-router/0.1");
+    let timeouts = UpstreamTimeouts::from_routing(&st.cfg.read().routing);
+    let mut builder = reqwest::Client::builder()
+        .user_agent("api-router/0.1")
+        .connect_timeout(Duration::from_secs(timeouts.connect_seconds))
+        .read_timeout(Duration::from_secs(timeouts.read_seconds));
     if let Some(proxy_url) = next_usage_proxy_for_provider(st, provider_name) {
         let proxy = reqwest::Proxy::all(&proxy_url)
             .map_err(|e| format!("invalid usage proxy for {provider_name}: {e}"))?;
@@ -1295,6 +1312,8 @@ pub(crate) fn quota_snapshot_from_json(value: &Value) -> Option<QuotaSnapshot> {
         package_expires_at_unix_ms: value
             .get("package_expires_at_unix_ms")
             .and_then(Value::as_u64),
+        reset_at_unix_ms: value.get("reset_at_unix_ms").and_then(Value::as_u64),
+        used_percent: as_f64(value.get("used_percent")),
         last_error: value
             .get("last_error")
             .and_then(Value::as_str)
@@ -1375,6 +1394,8 @@ fn preserved_quota_snapshot_for_storage(
         monthly_spent_usd: previous.monthly_spent_usd,
         monthly_budget_usd: previous.monthly_budget_usd,
         package_expires_at_unix_ms: previous.package_expires_at_unix_ms,
+        reset_at_unix_ms: previous.reset_at_unix_ms,
+        used_percent: previous.used_percent,
         last_error: snap.last_error.clone(),
         effective_usage_base: previous.effective_usage_base,
         effective_usage_source: previous.effective_usage_source,
@@ -1912,11 +1933,32 @@ async fn propagate_quota_snapshot_shared(
 }
 
 pub async fn refresh_quota_for_provider(st: &GatewayState, provider_name: &str) -> QuotaSnapshot {
+    let (snap, shared_key) = fetch_quota_snapshot_for_provider(st, provider_name).await;
+    let Some(shared_key) = shared_key else {
+        return snap;
+    };
+    store_quota_snapshot(st, provider_name, &snap);
+    propagate_quota_snapshot_shared(st, provider_name, &shared_key, &snap).await;
+    snap
+}
+
+/// Performs a single quota fetch with the provider's current usage config without persisting
+/// the result, so usage settings can be verified before relying on them.
+pub async fn test_quota_for_provider(st: &GatewayState, provider_name: &str) -> QuotaSnapshot {
+    fetch_quota_snapshot_for_provider(st, provider_name).await.0
+}
+
+/// Fetches a provider's quota snapshot. The shared key is `None` when no fetch was attempted
+/// (unknown provider or no usage endpoint), in which case the snapshot only carries the error.
+async fn fetch_quota_snapshot_for_provider(
+    st: &GatewayState,
+    provider_name: &str,
+) -> (QuotaSnapshot, Option<UsageSharedKey>) {
     let cfg = st.cfg.read().clone();
     let Some(p) = cfg.providers.get(provider_name) else {
         let mut out = QuotaSnapshot::empty(UsageKind::None);
         out.last_error = format!("unknown provider: {provider_name}");
-        return out;
+        return (out, None);
     };
 
     let provider_key = st.secrets.get_provider_key(provider_name);
@@ -1927,7 +1969,7 @@ pub async fn refresh_quota_for_provider(st: &GatewayState, provider_name: &str)
     let Some(shared_base) = bases_raw.first().cloned() else {
         let mut out = QuotaSnapshot::empty(UsageKind::None);
         out.last_error = "usage endpoint not found (set Usage base URL)".to_string();
-        return out;
+        return (out, None);
     };
     let bases =
         reorder_bases_for_speed(st, provider_name, bases_raw, provider_key.as_deref()).await;
@@ -1963,9 +2005,7 @@ pub async fn refresh_quota_for_provider(st: &GatewayState, provider_name: &str)
     if snap.package_expires_at_unix_ms.is_none() {
         snap.package_expires_at_unix_ms = cached_package_expiry;
     }
-    store_quota_snapshot(st, provider_name, &snap);
-    propagate_quota_snapshot_shared(st, provider_name, &shared_key, &snap).await;
-    snap
+    (snap, Some(shared_key))
 }
 
 async fn refresh_quota_for_provider_cached(
@@ -2047,6 +2087,15 @@ async fn refresh_quota_for_provider_cached(
     snap
 }
 
+/// The provider's declared `group`: providers in one group share an account and its quota.
+pub(crate) fn provider_quota_group(provider: &ProviderConfig) -> Option<&str> {
+    provider
+        .group
+        .as_deref()
+        .map(str::trim)
+        .filter(|group| !group.is_empty())
+}
+
 fn usage_shared_key_for_provider(st: &GatewayState, provider_name: &str) -> Option<UsageSharedKey> {
     let cfg = st.cfg.read().clone();
     let p = cfg.providers.get(provider_name)?;
@@ -2085,19 +2134,24 @@ pub async fn refresh_quota_shared(
         return Ok(Vec::new());
     }
     let target_key = usage_shared_key_for_provider(st, provider_name);
+    let target_group = provider_quota_group(provider);
     let mut cache: HashMap<UsageRequestKey, QuotaSnapshot> = HashMap::new();
     let mut group = Vec::new();
+    // Declared group members that do not share the usage key still need their own fetch.
+    let mut declared_peers = Vec::new();
 
-    if let Some(target_key) = target_key {
-        for (name, provider) in cfg.providers.iter() {
-            if !can_refresh_quota_for_provider(st, name, provider) {
-                continue;
-            }
-            if let Some(key) = usage_shared_key_for_provider(st, name) {
-                if key == target_key {
-                    group.push(name.clone());
-                }
-            }
+    for (name, provider) in cfg.providers.iter() {
+        if !can_refresh_quota_for_provider(st, name, provider) {
+            continue;
+        }
+        let same_key =
+            target_key.is_some() && usage_shared_key_for_provider(st, name) == target_key;
+        let same_group = target_group.is_some() && provider_quota_group(provider) == target_group;
+        if same_key || same_group {
+            group.push(name.clone());
+        }
+        if same_group && !same_key && name != provider_name {
+            declared_peers.push(name.clone());
         }
     }
 
@@ -2110,6 +2164,9 @@ pub async fn refresh_quota_shared(
             snap.last_error
         });
     }
+    for name in declared_peers {
+        let _ = refresh_quota_for_provider_cached(st, &name, &mut cache).await;
+    }
 
     if group.is_empty() {
         group.push(provider_name.to_string());
@@ -2121,12 +2178,10 @@ pub async fn refresh_quota_all_with_summary(
     st: &GatewayState,
     lan_sync: &crate::lan_sync::LanSyncRuntime,
 ) -> (usize, usize, Vec<String>) {
-    let cfg = st.cfg.read().clone();
-    let mut cache: HashMap<UsageRequestKey, QuotaSnapshot> = HashMap::new();
-    let mut ok = 0usize;
-    let mut err = 0usize;
-    let mut failed = Vec::new();
+    use futures_util::stream::{self, StreamExt};
 
+    let cfg = st.cfg.read().clone();
+    let mut targets = Vec::new();
     for (name, provider) in cfg.providers.iter() {
         if !can_refresh_quota_for_provider(st, name, provider) {
             continue;
@@ -2136,18 +2191,68 @@ pub async fn refresh_quota_all_with_summary(
         {
             continue;
         }
-        let snap = refresh_quota_for_provider_cached(st, name, &mut cache).await;
-        if snap.last_error.is_empty() && snap.updated_at_unix_ms > 0 {
-            ok += 1;
-        } else {
-            err += 1;
-            failed.push(name.clone());
+        targets.push(name.clone());
+    }
+
+    let batches = quota_refresh_batches_by_usage_host(&cfg, targets);
+    let mut results: Vec<(String, bool)> = stream::iter(batches)
+        .map(|batch| async move {
+            let mut cache: HashMap<UsageRequestKey, QuotaSnapshot> = HashMap::new();
+            let mut out = Vec::with_capacity(batch.len());
+            for (idx, name) in batch.into_iter().enumerate() {
+                if idx > 0 {
+                    // Space requests enough to avoid tripping the shared usage host.
+                    tokio::time::sleep(Duration::from_millis(USAGE_BASE_MIN_GAP_MS)).await;
+                }
+                let snap = refresh_quota_for_provider_cached(st, &name, &mut cache).await;
+                out.push((
+                    name,
+                    snap.last_error.is_empty() && snap.updated_at_unix_ms > 0,
+                ));
+            }
+            out
+        })
+        .buffer_unordered(QUOTA_REFRESH_ALL_MAX_CONCURRENT_HOSTS)
+        .collect::<Vec<_>>()
+        .await
+        .into_iter()
+        .flatten()
+        .collect();
+    results.sort_by(|a, b| a.0.cmp(&b.0));
+
+    let ok = results.iter().filter(|(_, refreshed)| *refreshed).count();
+    let failed: Vec<String> = results
+        .into_iter()
+        .filter(|(_, refreshed)| !refreshed)
+        .map(|(name, _)| name)
+        .collect();
+    (ok, failed.len(), failed)
+}
+
+/// Splits a refresh-all run into per-usage-host batches. Batches run concurrently, but providers
+/// within one stay sequential so the per-host refresh gate (and any 429 backoff it holds) fails
+/// a throttled host fast instead of stalling the rest of the run.
+fn quota_refresh_batches_by_usage_host(cfg: &AppConfig, names: Vec<String>) -> Vec<Vec<String>> {
+    let mut batches: Vec<(String, Vec<String>)> = Vec::new();
+    for name in names {
+        let host = cfg
+            .providers
+            .get(&name)
+            .and_then(|p| {
+                resolve_quota_profile(p)
+                    .candidate_bases
+                    .first()
+                    .map(String::as_str)
+                    .map(usage_base_gate_key)
+            })
+            .filter(|key| !key.is_empty())
+            .unwrap_or_else(|| name.clone());
+        match batches.iter_mut().find(|(key, _)| *key == host) {
+            Some((_, batch)) => batch.push(name),
+            None => batches.push((host, vec![name])),
         }
-        // Manual/all refresh: space requests enough to avoid tripping shared usage hosts.
-        tokio::time::sleep(Duration::from_millis(USAGE_BASE_MIN_GAP_MS)).await;
     }
-
-    (ok, err, failed)
+    batches.into_iter().map(|(_, batch)| batch).collect()
 }
 
 pub async fn run_quota_scheduler(st: GatewayState, lan_sync: crate::lan_sync::LanSyncRuntime) {
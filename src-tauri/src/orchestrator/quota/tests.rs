@@ -449,6 +449,7 @@ mod tests {
             listen: ListenConfig {
                 host: "127.0.0.1".to_string(),
                 port: 0,
+                unix_socket_path: None,
             },
             routing: RoutingConfig {
                 preferred_provider: provider_name.to_string(),
@@ -459,6 +460,32 @@ mod tests {
                 failure_threshold: 1,
                 cooldown_seconds: 1,
                 request_timeout_seconds: 5,
+                session_sticky_ttl_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+                usage_label: None,
+                max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+                allowed_peer_ips: Vec::new(),
+                connect_timeout_seconds: None,
+                read_timeout_seconds: None,
+                otel_endpoint: None,
+                key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+                default_base_url: None,
+                default_usage_adapter: None,
+                active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+                session_active_window_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+                read_only: false,
+                spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+                ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+                fractional_percent_display: false,
+                anthropic_compat: false,
+                cors_allowed_origins: Vec::new(),
+                max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+                evict_usage_requests: false,
+                event_query_default_limit:
+                    crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+                event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+                keepalive_warm_seconds: 0,
             },
             providers: std::collections::BTreeMap::from([(
                 provider_name.to_string(),
@@ -471,9 +498,20 @@ mod tests {
                     disabled: false,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             )]),
             provider_order: vec![provider_name.to_string()],
+            events: Default::default(),
+            switchboard_profiles: Default::default(),
+            provider_templates: std::collections::BTreeMap::new(),
         };
 
         let tmp = tempfile::tempdir().expect("tempdir");
@@ -489,6 +527,7 @@ mod tests {
             last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
             usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
             prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+            provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
             client_sessions: Arc::new(RwLock::new(HashMap::new())),
         };
         crate::lan_sync::register_gateway_status_runtime(crate::lan_sync::LanSyncRuntime::new(
@@ -511,6 +550,8 @@ mod tests {
             monthly_spent_usd: Some(2_597.81),
             monthly_budget_usd: Some(6_000.0),
             package_expires_at_unix_ms: Some(1_900_000_000_000),
+            reset_at_unix_ms: None,
+            used_percent: None,
             last_error: String::new(),
             effective_usage_base: Some("https://codex-for.me".to_string()),
             effective_usage_source: Some("codex_for_me_balance".to_string()),
@@ -535,6 +576,8 @@ mod tests {
             monthly_spent_usd: None,
             monthly_budget_usd: None,
             package_expires_at_unix_ms: None,
+            reset_at_unix_ms: None,
+            used_percent: None,
             last_error: "http 500 from https://codex-for.me".to_string(),
             effective_usage_base: None,
             effective_usage_source: None,
@@ -615,6 +658,14 @@ mod tests {
                     disabled: false,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             )]),
             vec!["p1".to_string()],
@@ -635,6 +686,7 @@ mod tests {
             listen: ListenConfig {
                 host: "127.0.0.1".to_string(),
                 port: 0,
+                unix_socket_path: None,
             },
             routing: RoutingConfig {
                 preferred_provider,
@@ -645,9 +697,38 @@ mod tests {
                 failure_threshold: 1,
                 cooldown_seconds: 1,
                 request_timeout_seconds: 5,
+                session_sticky_ttl_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+                usage_label: None,
+                max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+                allowed_peer_ips: Vec::new(),
+                connect_timeout_seconds: None,
+                read_timeout_seconds: None,
+                otel_endpoint: None,
+                key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+                default_base_url: None,
+                default_usage_adapter: None,
+                active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+                session_active_window_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+                read_only: false,
+                spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+                ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+                fractional_percent_display: false,
+                anthropic_compat: false,
+                cors_allowed_origins: Vec::new(),
+                max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+                evict_usage_requests: false,
+                event_query_default_limit:
+                    crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+                event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+                keepalive_warm_seconds: 0,
             },
             providers,
             provider_order,
+            events: Default::default(),
+            switchboard_profiles: Default::default(),
+            provider_templates: std::collections::BTreeMap::new(),
         };
 
         // Keep the sled directory alive for the test duration.
@@ -665,6 +746,7 @@ mod tests {
             last_used_by_session: Arc::new(RwLock::new(HashMap::new())),
             usage_base_speed_cache: Arc::new(RwLock::new(HashMap::new())),
             prev_id_support_cache: Arc::new(RwLock::new(HashMap::new())),
+            provider_models_cache: Arc::new(RwLock::new(HashMap::new())),
             client_sessions: Arc::new(RwLock::new(HashMap::new())),
         }
     }
@@ -686,6 +768,14 @@ mod tests {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
         let bases = resolve_quota_profile(&p).candidate_bases;
         assert!(bases.is_empty());
@@ -702,6 +792,14 @@ mod tests {
             usage_adapter: String::new(),
             usage_base_url: Some("https://explicit.example.com/".to_string()),
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
         let bases = resolve_quota_profile(&p).candidate_bases;
         assert_eq!(bases, vec!["https://explicit.example.com".to_string()]);
@@ -718,6 +816,14 @@ mod tests {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
         let bases = resolve_quota_profile(&p).candidate_bases;
         assert_eq!(bases, vec!["https://codex.packycode.com".to_string()]);
@@ -734,6 +840,14 @@ mod tests {
             usage_adapter: String::new(),
             usage_base_url: Some("https://www.packycode.com".to_string()),
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
         let bases = resolve_quota_profile(&p).candidate_bases;
         assert_eq!(bases, vec!["https://codex.packycode.com".to_string()]);
@@ -799,6 +913,14 @@ mod tests {
             usage_base_url: None,
             supports_websockets: false,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
         let bases_a = vec![
             "https://code.ppchat.vip".to_string(),
@@ -839,6 +961,14 @@ mod tests {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
         let pumpkin = ProviderConfig {
             display_name: "Pumpkin".to_string(),
@@ -849,6 +979,14 @@ mod tests {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
 
         let bases_pp = resolve_quota_profile(&pp).candidate_bases;
@@ -885,6 +1023,14 @@ mod tests {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
         let bases = resolve_quota_profile(&p).candidate_bases;
         assert_eq!(bases, vec!["https://api-vip.codex-for.me".to_string()]);
@@ -901,6 +1047,14 @@ mod tests {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
         let bases = resolve_quota_profile(&p).candidate_bases;
         assert_eq!(bases, vec!["https://blackaicoding.com".to_string()]);
@@ -917,6 +1071,14 @@ mod tests {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
         let bases = resolve_quota_profile(&p).candidate_bases;
         assert_eq!(bases, vec!["https://api-vip.codex-for.vip".to_string()]);
@@ -933,6 +1095,14 @@ mod tests {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
         assert_eq!(
             explicit_usage_endpoint_url(&provider).as_deref(),
@@ -963,6 +1133,14 @@ mod tests {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
 
         assert_eq!(
@@ -982,6 +1160,14 @@ mod tests {
             usage_adapter: String::new(),
             usage_base_url: Some("not-a-url".to_string()),
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
 
         assert_eq!(
@@ -1234,6 +1420,14 @@ mod tests {
                     disabled: false,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             )]),
             vec!["p1".to_string()],
@@ -1888,6 +2082,14 @@ mod tests {
                     disabled: false,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -1901,6 +2103,14 @@ mod tests {
                     disabled: false,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]);
@@ -2046,24 +2256,18 @@ mod tests {
             .single()
             .unwrap()
             .timestamp_millis() as u64;
-        st.store.upsert_usage_request_sync_rows(&[crate::orchestrator::store::UsageRequestSyncRow {
-            id: "req-1".to_string(),
-            unix_ms: ts,
-            ingested_at_unix_ms: ts,
-            provider: "p1".to_string(),
-            api_key_ref: "-".to_string(),
-            model: String::new(),
-            origin: "windows".to_string(),
-            transport: "http".to_string(),
-            session_id: String::new(),
-            node_id: String::new(),
-            node_name: String::new(),
-            input_tokens: 0,
-            output_tokens: 0,
-            total_tokens: 100,
-            cache_creation_input_tokens: 0,
-            cache_read_input_tokens: 0,
-        }]);
+        st.store.upsert_usage_request_sync_rows(&[
+            crate::orchestrator::store::UsageRequestSyncRow {
+                model: String::new(),
+                session_id: String::new(),
+                node_id: String::new(),
+                node_name: String::new(),
+                input_tokens: 0,
+                output_tokens: 0,
+                total_tokens: 100,
+                ..crate::orchestrator::store::UsageRequestSyncRow::test_row("req-1", "p1", ts)
+            },
+        ]);
 
         let mut snap = QuotaSnapshot::empty(UsageKind::BudgetInfo);
         snap.updated_at_unix_ms = ts;
@@ -2098,40 +2302,25 @@ mod tests {
             .timestamp_millis() as u64;
         st.store.upsert_usage_request_sync_rows(&[
             crate::orchestrator::store::UsageRequestSyncRow {
-                id: "req-1".to_string(),
-                unix_ms: first_ts,
-                ingested_at_unix_ms: first_ts,
-                provider: "p1".to_string(),
-                api_key_ref: "-".to_string(),
                 model: String::new(),
-                origin: "windows".to_string(),
-                transport: "http".to_string(),
                 session_id: String::new(),
-                node_id: "node-a".to_string(),
                 node_name: "desk-a".to_string(),
                 input_tokens: 0,
                 output_tokens: 0,
                 total_tokens: 100,
-                cache_creation_input_tokens: 0,
-                cache_read_input_tokens: 0,
+                ..crate::orchestrator::store::UsageRequestSyncRow::test_row("req-1", "p1", first_ts)
             },
             crate::orchestrator::store::UsageRequestSyncRow {
-                id: "req-2".to_string(),
-                unix_ms: second_ts,
-                ingested_at_unix_ms: second_ts,
-                provider: "p1".to_string(),
-                api_key_ref: "-".to_string(),
                 model: String::new(),
-                origin: "windows".to_string(),
-                transport: "http".to_string(),
                 session_id: String::new(),
                 node_id: "node-b".to_string(),
                 node_name: "desk-b".to_string(),
                 input_tokens: 0,
                 output_tokens: 0,
                 total_tokens: 200,
-                cache_creation_input_tokens: 0,
-                cache_read_input_tokens: 0,
+                ..crate::orchestrator::store::UsageRequestSyncRow::test_row(
+                    "req-2", "p1", second_ts,
+                )
             },
         ]);
 
@@ -2177,24 +2366,18 @@ mod tests {
             .single()
             .unwrap()
             .timestamp_millis() as u64;
-        st.store.upsert_usage_request_sync_rows(&[crate::orchestrator::store::UsageRequestSyncRow {
-            id: "req-remote".to_string(),
-            unix_ms: ts,
-            ingested_at_unix_ms: ts,
-            provider: "p1".to_string(),
-            api_key_ref: "-".to_string(),
-            model: String::new(),
-            origin: "windows".to_string(),
-            transport: "http".to_string(),
-            session_id: String::new(),
-            node_id: "node-remote".to_string(),
-            node_name: "remote-box".to_string(),
-            input_tokens: 0,
-            output_tokens: 0,
-            total_tokens: 123,
-            cache_creation_input_tokens: 0,
-            cache_read_input_tokens: 0,
-        }]);
+        st.store.upsert_usage_request_sync_rows(&[
+            crate::orchestrator::store::UsageRequestSyncRow {
+                model: String::new(),
+                session_id: String::new(),
+                node_id: "node-remote".to_string(),
+                node_name: "remote-box".to_string(),
+                input_tokens: 0,
+                output_tokens: 0,
+                total_tokens: 123,
+                ..crate::orchestrator::store::UsageRequestSyncRow::test_row("req-remote", "p1", ts)
+            },
+        ]);
 
         let mut snap = QuotaSnapshot::empty(UsageKind::BudgetInfo);
         snap.updated_at_unix_ms = ts;
@@ -2307,6 +2490,14 @@ mod tests {
                     disabled: false,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -2320,6 +2511,14 @@ mod tests {
                     disabled: true,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]);
@@ -2528,6 +2727,7 @@ mod tests {
             listen: crate::orchestrator::config::ListenConfig {
                 host: "127.0.0.1".to_string(),
                 port: 4000,
+                unix_socket_path: None,
             },
             routing: crate::orchestrator::config::RoutingConfig {
                 preferred_provider: "p1".to_string(),
@@ -2538,6 +2738,32 @@ mod tests {
                 failure_threshold: 1,
                 cooldown_seconds: 600,
                 request_timeout_seconds: 5,
+                session_sticky_ttl_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+                usage_label: None,
+                max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+                allowed_peer_ips: Vec::new(),
+                connect_timeout_seconds: None,
+                read_timeout_seconds: None,
+                otel_endpoint: None,
+                key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+                default_base_url: None,
+                default_usage_adapter: None,
+                active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+                session_active_window_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+                read_only: false,
+                spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+                ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+                fractional_percent_display: false,
+                anthropic_compat: false,
+                cors_allowed_origins: Vec::new(),
+                max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+                evict_usage_requests: false,
+                event_query_default_limit:
+                    crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+                event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+                keepalive_warm_seconds: 0,
             },
             providers: std::collections::BTreeMap::from([(
                 "p1".to_string(),
@@ -2550,9 +2776,20 @@ mod tests {
                     disabled: false,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             )]),
             provider_order: vec!["p1".to_string()],
+            events: Default::default(),
+            switchboard_profiles: Default::default(),
+            provider_templates: std::collections::BTreeMap::new(),
         };
         secrets_a.set_provider_key("p1", "sk-same").unwrap();
         secrets_b.set_provider_key("p1", "sk-same").unwrap();
@@ -2614,6 +2851,7 @@ filter_gt = {filter_gt}
             listen: crate::orchestrator::config::ListenConfig {
                 host: "127.0.0.1".to_string(),
                 port: 4000,
+                unix_socket_path: None,
             },
             routing: crate::orchestrator::config::RoutingConfig {
                 preferred_provider: "codex-for.me".to_string(),
@@ -2624,6 +2862,32 @@ filter_gt = {filter_gt}
                 failure_threshold: 1,
                 cooldown_seconds: 600,
                 request_timeout_seconds: 5,
+                session_sticky_ttl_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+                usage_label: None,
+                max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+                allowed_peer_ips: Vec::new(),
+                connect_timeout_seconds: None,
+                read_timeout_seconds: None,
+                otel_endpoint: None,
+                key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+                default_base_url: None,
+                default_usage_adapter: None,
+                active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+                session_active_window_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+                read_only: false,
+                spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+                ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+                fractional_percent_display: false,
+                anthropic_compat: false,
+                cors_allowed_origins: Vec::new(),
+                max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+                evict_usage_requests: false,
+                event_query_default_limit:
+                    crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+                event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+                keepalive_warm_seconds: 0,
             },
             providers: std::collections::BTreeMap::from([(
                 "codex-for.me".to_string(),
@@ -2636,9 +2900,20 @@ filter_gt = {filter_gt}
                     disabled: false,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             )]),
             provider_order: vec!["codex-for.me".to_string()],
+            events: Default::default(),
+            switchboard_profiles: Default::default(),
+            provider_templates: std::collections::BTreeMap::new(),
         };
         let secrets = SecretStore::new(temp.path().join("secrets.json"));
         secrets
@@ -2706,6 +2981,7 @@ filter_gt = 0.0
             listen: crate::orchestrator::config::ListenConfig {
                 host: "127.0.0.1".to_string(),
                 port: 4000,
+                unix_socket_path: None,
             },
             routing: crate::orchestrator::config::RoutingConfig {
                 preferred_provider: "codex-for.me".to_string(),
@@ -2716,6 +2992,32 @@ filter_gt = 0.0
                 failure_threshold: 1,
                 cooldown_seconds: 600,
                 request_timeout_seconds: 5,
+                session_sticky_ttl_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_STICKY_TTL_SECONDS,
+                usage_label: None,
+                max_request_body_bytes: crate::orchestrator::config::DEFAULT_MAX_REQUEST_BODY_BYTES,
+                allowed_peer_ips: Vec::new(),
+                connect_timeout_seconds: None,
+                read_timeout_seconds: None,
+                otel_endpoint: None,
+                key_preview_tail: crate::orchestrator::config::DEFAULT_KEY_PREVIEW_TAIL,
+                default_base_url: None,
+                default_usage_adapter: None,
+                active_window_seconds: crate::orchestrator::config::DEFAULT_ACTIVE_WINDOW_SECONDS,
+                session_active_window_seconds:
+                    crate::orchestrator::config::DEFAULT_SESSION_ACTIVE_WINDOW_SECONDS,
+                read_only: false,
+                spike_sigma: crate::orchestrator::config::DEFAULT_SPIKE_SIGMA,
+                ambiguous_local_time: crate::orchestrator::config::AmbiguousLocalTime::Earliest,
+                fractional_percent_display: false,
+                anthropic_compat: false,
+                cors_allowed_origins: Vec::new(),
+                max_store_bytes: crate::orchestrator::config::DEFAULT_MAX_STORE_BYTES,
+                evict_usage_requests: false,
+                event_query_default_limit:
+                    crate::orchestrator::config::DEFAULT_EVENT_QUERY_DEFAULT_LIMIT,
+                event_query_max_limit: crate::orchestrator::config::DEFAULT_EVENT_QUERY_MAX_LIMIT,
+                keepalive_warm_seconds: 0,
             },
             providers: std::collections::BTreeMap::from([(
                 "codex-for.me".to_string(),
@@ -2728,9 +3030,20 @@ filter_gt = 0.0
                     disabled: false,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             )]),
             provider_order: vec!["codex-for.me".to_string()],
+            events: Default::default(),
+            switchboard_profiles: Default::default(),
+            provider_templates: std::collections::BTreeMap::new(),
         };
         let secrets = SecretStore::new(temp.path().join("secrets.json"));
         secrets
@@ -2772,6 +3085,8 @@ filter_gt = 0.0
             monthly_spent_usd: Some(30.0),
             monthly_budget_usd: Some(200.0),
             package_expires_at_unix_ms: Some(1_800_000_000_000),
+            reset_at_unix_ms: None,
+            used_percent: None,
             last_error: String::new(),
             effective_usage_base: Some("https://usage.example".to_string()),
             effective_usage_source: Some("usage_base".to_string()),
@@ -3662,6 +3977,14 @@ filter_gt = 0.0
                     disabled: false,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -3675,6 +3998,14 @@ filter_gt = 0.0
                     disabled: false,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]);
@@ -3709,6 +4040,14 @@ filter_gt = 0.0
                     disabled: false,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -3722,6 +4061,14 @@ filter_gt = 0.0
                     disabled: false,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]);
@@ -3761,6 +4108,14 @@ filter_gt = 0.0
                     disabled: false,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -3774,6 +4129,14 @@ filter_gt = 0.0
                     disabled: false,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]);
@@ -3817,6 +4180,14 @@ filter_gt = 0.0
                 disabled: false,
                 supports_websockets: false,
                 api_key: String::new(),
+                model_aliases: std::collections::BTreeMap::new(),
+                system_prefix: None,
+                usage_field_map: None,
+                headers: std::collections::BTreeMap::new(),
+                routing_eligible: true,
+                default_model: None,
+                tier: 0,
+                notes: String::new(),
             },
         )]);
         let tmp = tempfile::tempdir().unwrap();
@@ -3850,6 +4221,14 @@ filter_gt = 0.0
                     disabled: false,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
             (
@@ -3863,6 +4242,14 @@ filter_gt = 0.0
                     disabled: true,
                     supports_websockets: false,
                     api_key: String::new(),
+                    model_aliases: std::collections::BTreeMap::new(),
+                    system_prefix: None,
+                    usage_field_map: None,
+                    headers: std::collections::BTreeMap::new(),
+                    routing_eligible: true,
+                    default_model: None,
+                    tier: 0,
+                    notes: String::new(),
                 },
             ),
         ]);
@@ -3885,6 +4272,43 @@ filter_gt = 0.0
         );
     }
 
+    #[test]
+    fn refresh_quota_all_batches_providers_sharing_a_usage_host() {
+        let mut cfg = AppConfig::default_config();
+        let template = cfg.providers.values().next().cloned().expect("default provider");
+        cfg.providers.clear();
+        for (name, usage_base) in [
+            ("a1", Some("https://usage-a.example/api")),
+            ("b1", Some("https://usage-b.example")),
+            ("a2", Some("https://usage-a.example/other/")),
+            ("n1", None),
+            ("n2", None),
+        ] {
+            let mut provider = template.clone();
+            provider.base_url = "https://usage-router.example/v1".to_string();
+            provider.usage_base_url = usage_base.map(str::to_string);
+            cfg.providers.insert(name.to_string(), provider);
+        }
+
+        let batches = quota_refresh_batches_by_usage_host(
+            &cfg,
+            ["a1", "a2", "b1", "n1", "n2"]
+                .into_iter()
+                .map(str::to_string)
+                .collect(),
+        );
+
+        assert_eq!(
+            batches,
+            vec![
+                vec!["a1".to_string(), "a2".to_string()],
+                vec!["b1".to_string()],
+                vec!["n1".to_string()],
+                vec!["n2".to_string()],
+            ]
+        );
+    }
+
     #[test]
     fn explicit_usage_mapping_normalizes_user_me_payload_to_usd() {
         let payload = serde_json::json!({
@@ -3927,6 +4351,14 @@ filter_gt = 0.0
             group: None,
             disabled: false,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
         let mapping = resolve_quota_profile(&provider)
             .budget_info_mapping
@@ -3972,6 +4404,14 @@ filter_gt = 0.0
             group: None,
             disabled: false,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
         let mapping = resolve_quota_profile(&provider)
             .summary_mapping
@@ -4064,6 +4504,14 @@ filter_gt = 0.0
             group: None,
             disabled: false,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
         let mapping = resolve_quota_profile(&provider)
             .summary_mapping
@@ -4113,6 +4561,14 @@ filter_gt = 0.0
             group: None,
             disabled: false,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
         let mapping = resolve_quota_profile(&provider)
             .summary_mapping
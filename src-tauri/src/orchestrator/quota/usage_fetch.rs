@@ -110,7 +110,7 @@ async fn fetch_token_stats_any(
                     out.effective_usage_source = Some("token_stats".to_string());
                     out.updated_at_unix_ms = unix_ms();
                     out.last_error.clear();
-                    return out;
+                    return apply_usage_field_map(st, provider_name, &j, out);
                 }
 
                 if let Some((remaining, today_used, today_added)) =
@@ -183,6 +183,47 @@ fn map_snapshot_from_usage_payload(
     .map(snapshot_from_canonical_usage)
 }
 
+/// Reads `reset_at_unix_ms` / `used_percent` from the raw usage response at the provider's
+/// `usage_field_map` pointers. Providers without a mapping are returned untouched.
+fn apply_usage_field_map(
+    st: &GatewayState,
+    provider_name: &str,
+    payload: &Value,
+    mut snapshot: QuotaSnapshot,
+) -> QuotaSnapshot {
+    let field_map = st
+        .cfg
+        .read()
+        .providers
+        .get(provider_name)
+        .and_then(|provider| provider.usage_field_map.clone());
+    let Some(field_map) = field_map else {
+        return snapshot;
+    };
+    if let Some(pointer) = field_map.reset_at_pointer() {
+        snapshot.reset_at_unix_ms = payload.pointer(pointer).and_then(usage_field_unix_ms);
+    }
+    if let Some(pointer) = field_map.used_percent_pointer() {
+        snapshot.used_percent = as_f64(payload.pointer(pointer));
+    }
+    snapshot
+}
+
+/// Epoch seconds/milliseconds (number or numeric string) or an RFC 3339 timestamp.
+fn usage_field_unix_ms(value: &Value) -> Option<u64> {
+    if let Some(n) = as_f64(Some(value)).filter(|n| n.is_finite() && *n >= 0.0) {
+        let n = n as u64;
+        // Heuristic: seconds vs milliseconds.
+        return Some(if n < 1_000_000_000_000 {
+            n.saturating_mul(1000)
+        } else {
+            n
+        });
+    }
+    let parsed = chrono::DateTime::parse_from_rfc3339(value.as_str()?.trim()).ok()?;
+    u64::try_from(parsed.timestamp_millis()).ok()
+}
+
 async fn fetch_explicit_usage_endpoint_any(
     st: &GatewayState,
     provider_name: &str,
@@ -262,7 +303,7 @@ async fn fetch_explicit_usage_endpoint_any(
             "usage_base",
             response_now_ms,
         ) {
-            return snapshot;
+            return apply_usage_field_map(st, provider_name, &payload, snapshot);
         }
         last_err = format!("unexpected response from {endpoint_url}");
     }
@@ -903,7 +944,12 @@ async fn fetch_provider_key_card_login_summary_any(
                     non_404_err.get_or_insert_with(|| last_err.clone());
                     continue;
                 };
-                return snapshot_from_canonical_usage(usage);
+                return apply_usage_field_map(
+                    st,
+                    provider_name,
+                    &payload,
+                    snapshot_from_canonical_usage(usage),
+                );
             }
             Err(err) => {
                 if err.contains("http 404") {
@@ -1025,7 +1071,12 @@ async fn fetch_login_summary_any(
                     (Some(balance), Some(spent)) => Some(balance + spent),
                     _ => None,
                 };
-                return snapshot_from_canonical_usage(usage);
+                return apply_usage_field_map(
+                    st,
+                    provider_name,
+                    &payload,
+                    snapshot_from_canonical_usage(usage),
+                );
             }
             Err(err) => {
                 if err.contains("http 404") {
@@ -1135,7 +1186,12 @@ async fn fetch_subscription_login_any(
             non_404_err.get_or_insert_with(|| last_err.clone());
             continue;
         };
-        return snapshot_from_canonical_usage(usage);
+        return apply_usage_field_map(
+            st,
+            provider_name,
+            &payload,
+            snapshot_from_canonical_usage(usage),
+        );
     }
 
     if let Some(err) = non_404_err {
@@ -1245,7 +1301,7 @@ async fn fetch_budget_info_any(
                     Some(root),
                 )
                 .await;
-                return snapshot;
+                return apply_usage_field_map(st, provider_name, &j, snapshot);
             }
             Err(e) => {
                 last_err = format_reqwest_error_for_logs(&e);
@@ -1304,4 +1360,41 @@ mod usage_fetch_tests {
 
         assert_eq!(snapshot.last_error, "missing usage token");
     }
+
+    #[test]
+    fn usage_field_map_pointers_fill_reset_and_used_percent() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let config_path = tmp.path().join("user-data").join("config.toml");
+        let data_dir = tmp.path().join("data");
+        let state = crate::app_state::build_state(config_path, data_dir).expect("build state");
+        let payload = serde_json::json!({
+            "limits": { "resets": "2026-01-02T00:00:00Z", "pct": "42.5%" },
+            "reset_at": 1,
+        });
+        let snapshot = super::QuotaSnapshot::empty(super::UsageKind::BudgetInfo);
+
+        let unmapped =
+            super::apply_usage_field_map(&state.gateway, "official", &payload, snapshot.clone());
+        assert_eq!(unmapped.reset_at_unix_ms, None);
+        assert_eq!(unmapped.used_percent, None);
+
+        state
+            .gateway
+            .cfg
+            .write()
+            .providers
+            .get_mut("official")
+            .expect("official provider")
+            .usage_field_map = Some(crate::orchestrator::config::UsageFieldMap {
+            reset_at_pointer: Some("/limits/resets".to_string()),
+            used_percent_pointer: Some("/limits/pct".to_string()),
+        });
+        let mapped = super::apply_usage_field_map(&state.gateway, "official", &payload, snapshot);
+        assert_eq!(mapped.reset_at_unix_ms, Some(1_767_312_000_000));
+        assert_eq!(mapped.used_percent, Some(42.5));
+        assert_eq!(
+            super::quota_snapshot_from_json(&mapped.to_json()).and_then(|s| s.reset_at_unix_ms),
+            Some(1_767_312_000_000)
+        );
+    }
 }
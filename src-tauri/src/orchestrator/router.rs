@@ -1,5 +1,5 @@
 use reqwest::Url;
-use std::collections::{BTreeSet, HashMap, HashSet};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet, VecDeque};
 use std::time::Duration;
 
 use parking_lot::RwLock;
@@ -16,6 +16,22 @@ pub struct ProviderHealthSnapshot {
     pub last_ok_at_unix_ms: u64,
     pub last_fail_at_unix_ms: u64,
     pub last_error_event_id: Option<String>,
+    /// Exponentially weighted moving average of successful request latency.
+    pub ewma_latency_ms: Option<f64>,
+    /// Latencies of the most recent successful requests (oldest first), for sparklines.
+    pub recent_latencies_ms: Vec<u32>,
+    /// Failures / total over the recent outcome window; 0 when nothing was recorded yet.
+    pub recent_error_rate: f64,
+    /// Share of tracked time spent healthy over the last 24 hours; `None` until observed.
+    pub uptime_24h_percent: Option<f64>,
+    /// Share of tracked time spent healthy over the last 7 days; `None` until observed.
+    pub uptime_7d_percent: Option<f64>,
+    /// Model ids from the provider's last successful `/models` listing; `None` until fetched.
+    pub served_models: Option<Vec<String>>,
+    /// How long ago `served_models` was fetched.
+    pub served_models_age_ms: Option<u64>,
+    /// The provider's configured auto-selection tier.
+    pub tier: u8,
 }
 
 #[derive(Debug, Clone, serde::Serialize, serde::Deserialize)]
@@ -47,6 +63,97 @@ struct ProviderHealth {
     last_shared_runtime_update_unix_ms: u64,
     last_shared_runtime_source_node_id: String,
     last_shared_runtime_origin_local: bool,
+    recent_outcomes: VecDeque<RecentOutcome>,
+    ewma_latency_ms: Option<f64>,
+}
+
+#[derive(Debug, Clone, Copy)]
+struct RecentOutcome {
+    ok: bool,
+    latency_ms: Option<u32>,
+}
+
+const RECENT_OUTCOME_WINDOW: usize = 20;
+const LATENCY_EWMA_ALPHA: f64 = 0.3;
+
+const UPTIME_BUCKET_MS: u64 = 60 * 60 * 1000;
+const UPTIME_DAY_MS: u64 = 24 * UPTIME_BUCKET_MS;
+const UPTIME_RETENTION_MS: u64 = 7 * UPTIME_DAY_MS;
+/// Uptime totals are flushed on every health transition and otherwise at most this often.
+const UPTIME_PERSIST_INTERVAL_MS: u64 = 60 * 1000;
+const UPTIME_STATE_META_KEY: &str = "router_provider_uptime_v1";
+
+/// Cumulative healthy/unhealthy milliseconds within one hour.
+#[derive(Debug, Clone, Copy, Default, serde::Serialize, serde::Deserialize)]
+struct UptimeBucket {
+    up_ms: u64,
+    down_ms: u64,
+}
+
+#[derive(Debug, Default)]
+struct ProviderUptime {
+    /// Hour-aligned bucket start -> durations, pruned to the 7d window.
+    buckets: BTreeMap<u64, UptimeBucket>,
+    /// Last observed health (`true` = healthy) and since when; unknown health is not counted.
+    observed: Option<(bool, u64)>,
+}
+
+impl ProviderUptime {
+    fn accrue(&mut self, healthy: bool, from_ms: u64, to_ms: u64) {
+        let mut start = from_ms;
+        while start < to_ms {
+            let bucket_start = start - start % UPTIME_BUCKET_MS;
+            let end = (bucket_start + UPTIME_BUCKET_MS).min(to_ms);
+            let bucket = self.buckets.entry(bucket_start).or_default();
+            if healthy {
+                bucket.up_ms += end - start;
+            } else {
+                bucket.down_ms += end - start;
+            }
+            start = end;
+        }
+    }
+
+    /// Folds the time since the previous observation into the buckets and restarts the clock.
+    /// Returns whether the observed health changed.
+    fn observe(&mut self, healthy: Option<bool>, now_ms: u64) -> bool {
+        let previous = self.observed;
+        let at = previous.map_or(now_ms, |(_, since)| since.max(now_ms));
+        if let Some((was_healthy, since)) = previous {
+            self.accrue(was_healthy, since, at);
+        }
+        self.observed = healthy.map(|healthy| (healthy, at));
+        let cutoff = at.saturating_sub(UPTIME_RETENTION_MS);
+        self.buckets
+            .retain(|start, _| start.saturating_add(UPTIME_BUCKET_MS) > cutoff);
+        previous.map(|(was_healthy, _)| was_healthy) != healthy
+    }
+
+    fn percent(&self, window_ms: u64, now_ms: u64) -> Option<f64> {
+        let cutoff = now_ms.saturating_sub(window_ms);
+        let (mut up_ms, mut down_ms) = self
+            .buckets
+            .range(cutoff - cutoff % UPTIME_BUCKET_MS..)
+            .fold((0u64, 0u64), |(up, down), (_, bucket)| {
+                (up + bucket.up_ms, down + bucket.down_ms)
+            });
+        if let Some((healthy, since)) = self.observed {
+            let pending = now_ms.saturating_sub(since.max(cutoff));
+            if healthy {
+                up_ms += pending;
+            } else {
+                down_ms += pending;
+            }
+        }
+        let total = up_ms + down_ms;
+        (total > 0).then(|| up_ms as f64 * 100.0 / total as f64)
+    }
+}
+
+#[derive(Debug, Default)]
+struct UptimeTracker {
+    providers: HashMap<String, ProviderUptime>,
+    persisted_at_unix_ms: u64,
 }
 
 #[derive(Debug, Clone, Copy)]
@@ -72,6 +179,8 @@ impl ProviderHealth {
             last_shared_runtime_update_unix_ms: 0,
             last_shared_runtime_source_node_id: String::new(),
             last_shared_runtime_origin_local: false,
+            recent_outcomes: VecDeque::with_capacity(RECENT_OUTCOME_WINDOW),
+            ewma_latency_ms: None,
         }
     }
 
@@ -82,6 +191,29 @@ impl ProviderHealth {
     fn in_cooldown_at(&self, now_ms: u64) -> bool {
         self.cooldown_until_unix_ms != 0 && now_ms < self.cooldown_until_unix_ms
     }
+
+    fn record_outcome(&mut self, ok: bool, latency: Option<Duration>) {
+        let latency_ms = latency.map(|d| u32::try_from(d.as_millis()).unwrap_or(u32::MAX));
+        if self.recent_outcomes.len() >= RECENT_OUTCOME_WINDOW {
+            self.recent_outcomes.pop_front();
+        }
+        self.recent_outcomes
+            .push_back(RecentOutcome { ok, latency_ms });
+        if let Some(ms) = latency_ms.map(f64::from) {
+            self.ewma_latency_ms = Some(match self.ewma_latency_ms {
+                Some(prev) => prev + LATENCY_EWMA_ALPHA * (ms - prev),
+                None => ms,
+            });
+        }
+    }
+
+    fn recent_error_rate(&self) -> f64 {
+        if self.recent_outcomes.is_empty() {
+            return 0.0;
+        }
+        let errors = self.recent_outcomes.iter().filter(|o| !o.ok).count();
+        errors as f64 / self.recent_outcomes.len() as f64
+    }
 }
 
 pub struct RouterState {
@@ -90,15 +222,17 @@ pub struct RouterState {
     quota_closed_by_provider: RwLock<HashMap<String, bool>>,
     unhealthy_by_provider: RwLock<HashMap<String, bool>>,
     balanced_main_session_ids: RwLock<Option<BTreeSet<String>>>,
+    uptime: RwLock<UptimeTracker>,
     store: Option<Store>,
 }
 
 const SHARED_HEALTH_STATE_META_KEY: &str = "router_shared_health_state_v1";
+const SIMULATED_FAILURE_ERROR: &str = "simulated failure";
 
 fn provider_is_enabled(cfg: &AppConfig, name: &str) -> bool {
     cfg.providers
         .get(name)
-        .is_some_and(|provider| !provider.disabled)
+        .is_some_and(|provider| provider.routable())
 }
 
 pub(crate) fn provider_iteration_order(cfg: &AppConfig) -> Vec<String> {
@@ -117,6 +251,8 @@ pub(crate) fn provider_iteration_order(cfg: &AppConfig) -> Vec<String> {
         }
     }
 
+    // Stable: `provider_order` still decides within a tier.
+    ordered.sort_by_key(|name| cfg.providers.get(name).map_or(0, |provider| provider.tier));
     ordered
 }
 
@@ -178,8 +314,10 @@ impl RouterState {
             quota_closed_by_provider: RwLock::new(quota_closed_by_provider),
             unhealthy_by_provider: RwLock::new(unhealthy_by_provider),
             balanced_main_session_ids: RwLock::new(None),
+            uptime: RwLock::new(UptimeTracker::default()),
             store,
         };
+        state.load_persisted_uptime();
         state.load_persisted_shared_health_state(cfg, now_ms);
         state
     }
@@ -209,7 +347,85 @@ impl RouterState {
         self.persist_shared_health_state(now_ms);
     }
 
+    /// Restores cumulative uptime buckets; time while the app was not running is not counted.
+    fn load_persisted_uptime(&self) {
+        let Some(store) = self.store.as_ref() else {
+            return;
+        };
+        let Ok(Some(value)) = store.get_event_meta(UPTIME_STATE_META_KEY) else {
+            return;
+        };
+        let Ok(persisted) =
+            serde_json::from_str::<HashMap<String, BTreeMap<u64, UptimeBucket>>>(&value)
+        else {
+            return;
+        };
+        let mut tracker = self.uptime.write();
+        for (provider, buckets) in persisted {
+            tracker.providers.insert(
+                provider,
+                ProviderUptime {
+                    buckets,
+                    observed: None,
+                },
+            );
+        }
+    }
+
+    fn uptime_health(h: &ProviderHealth, now_ms: u64) -> Option<bool> {
+        if h.in_cooldown_at(now_ms) {
+            return Some(false);
+        }
+        match h.state {
+            HealthState::Unknown => None,
+            HealthState::Healthy => Some(true),
+            HealthState::Unhealthy => Some(false),
+        }
+    }
+
+    /// Accrues uptime for every provider; runs on each health mutation so transitions are
+    /// timestamped exactly.
+    fn record_uptime(&self, now_ms: u64) {
+        let observed: Vec<(String, Option<bool>)> = self
+            .health
+            .read()
+            .iter()
+            .map(|(provider, h)| (provider.clone(), Self::uptime_health(h, now_ms)))
+            .collect();
+        let mut tracker = self.uptime.write();
+        tracker
+            .providers
+            .retain(|provider, _| observed.iter().any(|(name, _)| name == provider));
+        let mut transitioned = false;
+        for (provider, healthy) in observed {
+            transitioned |= tracker
+                .providers
+                .entry(provider)
+                .or_default()
+                .observe(healthy, now_ms);
+        }
+        let Some(store) = self.store.as_ref() else {
+            return;
+        };
+        if !transitioned
+            && now_ms.saturating_sub(tracker.persisted_at_unix_ms) < UPTIME_PERSIST_INTERVAL_MS
+        {
+            return;
+        }
+        tracker.persisted_at_unix_ms = now_ms;
+        let payload: HashMap<&String, &BTreeMap<u64, UptimeBucket>> = tracker
+            .providers
+            .iter()
+            .filter(|(_, uptime)| !uptime.buckets.is_empty())
+            .map(|(provider, uptime)| (provider, &uptime.buckets))
+            .collect();
+        if let Ok(value) = serde_json::to_string(&payload) {
+            let _ = store.set_event_meta(UPTIME_STATE_META_KEY, &value);
+        }
+    }
+
     fn persist_shared_health_state(&self, now_ms: u64) {
+        self.record_uptime(now_ms);
         let Some(store) = self.store.as_ref() else {
             return;
         };
@@ -283,18 +499,18 @@ impl RouterState {
     }
 
     pub fn reset_provider_health(&self, provider: &str, now_ms: u64) -> bool {
-        let reset = {
+        let transition = {
             let mut health = self.health.write();
             let Some(existing) = health.get_mut(provider) else {
                 return false;
             };
+            let from_state = Self::health_status(existing, now_ms);
             *existing = ProviderHealth::new(now_ms);
-            true
+            (from_state, Self::health_status(existing, now_ms))
         };
-        if reset {
-            self.persist_shared_health_state(now_ms);
-        }
-        reset
+        self.persist_shared_health_state(now_ms);
+        self.record_transition(provider, Some(transition), "reset", now_ms);
+        true
     }
 
     pub fn record_quota_closed_states(&self, states: &HashMap<String, bool>) -> Vec<String> {
@@ -358,8 +574,8 @@ impl RouterState {
                 })
     }
 
-    fn snapshot_from_health(v: &ProviderHealth, now_ms: u64) -> ProviderHealthSnapshot {
-        let status = if v.in_cooldown_at(now_ms) {
+    fn health_status(v: &ProviderHealth, now_ms: u64) -> &'static str {
+        if v.in_cooldown_at(now_ms) {
             "cooldown"
         } else {
             match v.state {
@@ -368,15 +584,45 @@ impl RouterState {
                 HealthState::Unhealthy => "unhealthy",
             }
         }
-        .to_string();
+    }
+
+    /// Persists a `health_transition:` entry when a mutation changed the provider's status.
+    fn record_transition(
+        &self,
+        provider: &str,
+        transition: Option<(&'static str, &'static str)>,
+        reason: &str,
+        now_ms: u64,
+    ) {
+        let Some((from_state, to_state)) = transition.filter(|(from, to)| from != to) else {
+            return;
+        };
+        if let Some(store) = self.store.as_ref() {
+            store.record_health_transition(provider, from_state, to_state, reason, now_ms);
+        }
+    }
+
+    fn snapshot_from_health(v: &ProviderHealth, now_ms: u64) -> ProviderHealthSnapshot {
         ProviderHealthSnapshot {
-            status,
+            status: Self::health_status(v, now_ms).to_string(),
             consecutive_failures: v.consecutive_failures,
             cooldown_until_unix_ms: v.cooldown_until_unix_ms,
             last_error: v.last_error.clone(),
             last_ok_at_unix_ms: v.last_ok_at_unix_ms,
             last_fail_at_unix_ms: v.last_fail_at_unix_ms,
             last_error_event_id: None,
+            ewma_latency_ms: v.ewma_latency_ms,
+            recent_latencies_ms: v
+                .recent_outcomes
+                .iter()
+                .filter_map(|o| o.latency_ms)
+                .collect(),
+            recent_error_rate: v.recent_error_rate(),
+            uptime_24h_percent: None,
+            uptime_7d_percent: None,
+            served_models: None,
+            served_models_age_ms: None,
+            tier: 0,
         }
     }
 
@@ -418,7 +664,8 @@ impl RouterState {
         if !matches!(status.as_str(), "healthy" | "cooldown" | "unhealthy") {
             return false;
         }
-        let applied = {
+        let now_ms = snapshot.updated_at_unix_ms;
+        let transition = {
             let mut health = self.health.write();
             let Some(h) = health.get_mut(provider) else {
                 return false;
@@ -431,6 +678,7 @@ impl RouterState {
             ) {
                 return false;
             }
+            let from_state = Self::health_status(h, now_ms);
             h.consecutive_failures = snapshot.consecutive_failures;
             h.cooldown_until_unix_ms = snapshot.cooldown_until_unix_ms;
             h.shared_probe_required = snapshot.shared_probe_required;
@@ -450,24 +698,39 @@ impl RouterState {
             h.last_shared_runtime_update_unix_ms = snapshot.updated_at_unix_ms;
             h.last_shared_runtime_source_node_id = snapshot.source_node_id.clone();
             h.last_shared_runtime_origin_local = source_is_local;
-            true
+            (from_state, Self::health_status(h, now_ms))
         };
-        if applied {
-            self.persist_shared_health_state(snapshot.updated_at_unix_ms);
-        }
-        applied
+        self.persist_shared_health_state(now_ms);
+        self.record_transition(provider, Some(transition), "shared_sync", now_ms);
+        true
     }
 
     pub fn mark_success(&self, provider: &str, now_ms: u64) -> Option<ProviderHealthSnapshot> {
+        self.mark_success_with_latency(provider, now_ms, None)
+    }
+
+    pub fn mark_success_with_latency(
+        &self,
+        provider: &str,
+        now_ms: u64,
+        latency: Option<Duration>,
+    ) -> Option<ProviderHealthSnapshot> {
         let mut out = None;
+        let mut recovered_from = None;
+        let mut transition = None;
         {
             let mut health = self.health.write();
             if let Some(h) = health.get_mut(provider) {
+                let from_state = Self::health_status(h, now_ms);
+                h.record_outcome(true, latency);
                 if h.in_cooldown_at(now_ms) && h.cooldown_from_transient_warnings {
                     h.last_ok_at_unix_ms = now_ms;
                     Self::mark_local_runtime_update(h, now_ms);
                     out = Some(Self::snapshot_from_health(h, now_ms));
                 } else {
+                    if matches!(h.state, HealthState::Unhealthy) {
+                        recovered_from = Some((h.last_fail_at_unix_ms, h.last_error.clone()));
+                    }
                     h.state = HealthState::Healthy;
                     h.consecutive_failures = 0;
                     h.cooldown_until_unix_ms = 0;
@@ -478,14 +741,44 @@ impl RouterState {
                     Self::mark_local_runtime_update(h, now_ms);
                     out = Some(Self::snapshot_from_health(h, now_ms));
                 }
+                transition = Some((from_state, Self::health_status(h, now_ms)));
             }
         }
         if out.is_some() {
             self.persist_shared_health_state(now_ms);
         }
+        self.record_transition(provider, transition, "request_succeeded", now_ms);
+        if let Some((last_fail_at_unix_ms, last_error)) = recovered_from {
+            self.emit_recovered(provider, last_fail_at_unix_ms, &last_error, now_ms);
+        }
         out
     }
 
+    /// Marks the end of an outage: emitted once on the unhealthy -> healthy transition.
+    fn emit_recovered(
+        &self,
+        provider: &str,
+        last_fail_at_unix_ms: u64,
+        last_error: &str,
+        now_ms: u64,
+    ) {
+        let Some(store) = self.store.as_ref() else {
+            return;
+        };
+        let downtime_ms = now_ms.saturating_sub(last_fail_at_unix_ms);
+        store.events().emit_at_unix_ms(
+            provider,
+            crate::orchestrator::store::EventCode::HEALTH_RECOVERED,
+            &format!("Provider recovered after {}s", downtime_ms.div_ceil(1000)),
+            serde_json::json!({
+                "downtime_ms": downtime_ms,
+                "last_fail_at_unix_ms": last_fail_at_unix_ms,
+                "last_error": last_error,
+            }),
+            now_ms,
+        );
+    }
+
     pub fn mark_failure(
         &self,
         provider: &str,
@@ -505,9 +798,11 @@ impl RouterState {
     ) -> Option<ProviderHealthSnapshot> {
         let mut out = None;
         let mut mutated = false;
+        let mut transition = None;
         {
             let mut health = self.health.write();
             if let Some(h) = health.get_mut(provider) {
+                let from_state = Self::health_status(h, now_ms);
                 const TRANSIENT_WARNING_THRESHOLD: usize = 3;
                 let warning_window_ms = cfg
                     .routing
@@ -533,12 +828,14 @@ impl RouterState {
                     Self::mark_local_runtime_update(h, now_ms);
                     mutated = true;
                 }
+                transition = Some((from_state, Self::health_status(h, now_ms)));
                 out = Some(Self::snapshot_from_health(h, now_ms));
             }
         }
         if mutated {
             self.persist_shared_health_state(now_ms);
         }
+        self.record_transition(provider, transition, err, now_ms);
         out
     }
 
@@ -551,10 +848,13 @@ impl RouterState {
         cfg: &AppConfig,
     ) -> Option<ProviderHealthSnapshot> {
         let mut out = None;
+        let mut transition = None;
         {
             let mut health = self.health.write();
             if let Some(h) = health.get_mut(provider) {
+                let from_state = Self::health_status(h, now_ms);
                 h.transient_warning_timestamps_unix_ms.clear();
+                h.record_outcome(false, None);
                 h.state = HealthState::Unhealthy;
                 h.consecutive_failures = h.consecutive_failures.saturating_add(1);
                 h.last_error = err.to_string();
@@ -569,27 +869,85 @@ impl RouterState {
                             .saturating_mul(1000);
                 }
                 Self::mark_local_runtime_update(h, now_ms);
+                transition = Some((from_state, Self::health_status(h, now_ms)));
                 out = Some(Self::snapshot_from_health(h, now_ms));
             }
         }
         if out.is_some() {
             self.persist_shared_health_state(now_ms);
         }
+        self.record_transition(provider, transition, err, now_ms);
         out
     }
 
     pub fn mark_usage_refresh_success(&self, provider: &str, now_ms: u64) {
+        let mut transition = None;
         {
             let mut health = self.health.write();
             if let Some(h) = health.get_mut(provider) {
+                let from_state = Self::health_status(h, now_ms);
                 if matches!(h.state, HealthState::Unknown) {
                     h.state = HealthState::Healthy;
                 }
                 h.last_ok_at_unix_ms = now_ms;
                 Self::mark_local_runtime_update(h, now_ms);
+                transition = Some((from_state, Self::health_status(h, now_ms)));
             }
         }
         self.persist_shared_health_state(now_ms);
+        self.record_transition(provider, transition, "usage_refresh_succeeded", now_ms);
+    }
+
+    /// Forces `provider` unhealthy and into cooldown until `until_ms` for failover drills. Only
+    /// local router state changes: nothing is sent upstream or written to the shared health state.
+    pub fn simulate_failure(
+        &self,
+        provider: &str,
+        until_ms: u64,
+        now_ms: u64,
+    ) -> Option<ProviderHealthSnapshot> {
+        let (transition, out) = {
+            let mut health = self.health.write();
+            let h = health.get_mut(provider)?;
+            let from_state = Self::health_status(h, now_ms);
+            h.state = HealthState::Unhealthy;
+            h.cooldown_from_transient_warnings = false;
+            h.cooldown_until_unix_ms = until_ms;
+            h.last_error = SIMULATED_FAILURE_ERROR.to_string();
+            h.last_fail_at_unix_ms = now_ms;
+            (
+                (from_state, Self::health_status(h, now_ms)),
+                Self::snapshot_from_health(h, now_ms),
+            )
+        };
+        self.record_transition(provider, Some(transition), "simulated_failure", now_ms);
+        Some(out)
+    }
+
+    /// Lifts a failure started by [`Self::simulate_failure`] unless real traffic has since
+    /// replaced it. Returns whether the provider was recovered.
+    pub fn end_simulated_failure(&self, provider: &str, until_ms: u64, now_ms: u64) -> bool {
+        let transition = {
+            let mut health = self.health.write();
+            let Some(h) = health.get_mut(provider) else {
+                return false;
+            };
+            if h.cooldown_until_unix_ms != until_ms || h.last_error != SIMULATED_FAILURE_ERROR {
+                return false;
+            }
+            let from_state = Self::health_status(h, now_ms);
+            h.state = HealthState::Unknown;
+            h.cooldown_until_unix_ms = 0;
+            h.last_error.clear();
+            (from_state, Self::health_status(h, now_ms))
+        };
+        self.record_transition(
+            provider,
+            Some(transition),
+            "simulated_failure_ended",
+            now_ms,
+        );
+        true
     }
 
     pub fn require_usage_confirmation(&self, provider: &str) {
@@ -615,9 +973,18 @@ impl RouterState {
 
     pub fn snapshot(&self, now_ms: u64) -> HashMap<String, ProviderHealthSnapshot> {
         let health = self.health.read();
+        let uptime = self.uptime.read();
         health
             .iter()
-            .map(|(k, v)| (k.clone(), Self::snapshot_from_health(v, now_ms)))
+            .map(|(k, v)| {
+                let mut snapshot = Self::snapshot_from_health(v, now_ms);
+                if let Some(provider_uptime) = uptime.providers.get(k) {
+                    snapshot.uptime_24h_percent = provider_uptime.percent(UPTIME_DAY_MS, now_ms);
+                    snapshot.uptime_7d_percent =
+                        provider_uptime.percent(UPTIME_RETENTION_MS, now_ms);
+                }
+                (k.clone(), snapshot)
+            })
             .collect()
     }
 
@@ -692,6 +1059,80 @@ mod tests {
         (tmp, store)
     }
 
+    #[test]
+    fn uptime_percent_tracks_alternating_periods_and_survives_restart() {
+        let cfg = AppConfig::default_config();
+        let provider = "official";
+        let hour = 60 * 60 * 1000;
+        let start = 1_717_171_200_000;
+        let (_tmp, store) = build_test_store();
+        let router = RouterState::new_with_store(&cfg, start, Some(store.clone()));
+
+        router.mark_success(provider, start);
+        router.mark_failure(provider, &cfg, "down", start + 3 * hour);
+        router.mark_success(provider, start + 4 * hour);
+        router.mark_failure(provider, &cfg, "down", start + 7 * hour);
+        router.mark_success(provider, start + 8 * hour);
+
+        let snapshot = router.snapshot(start + 8 * hour);
+        let health = snapshot.get(provider).expect("provider health snapshot");
+        assert_eq!(health.uptime_24h_percent, Some(75.0));
+        assert_eq!(health.uptime_7d_percent, Some(75.0));
+        let untouched = snapshot.get("provider_1").expect("provider_1 snapshot");
+        assert_eq!(untouched.uptime_24h_percent, None);
+
+        let restored = RouterState::new_with_store(&cfg, start + 8 * hour, Some(store));
+        let snapshot = restored.snapshot(start + 8 * hour);
+        let health = snapshot
+            .get(provider)
+            .expect("restored provider health snapshot");
+        assert_eq!(health.uptime_24h_percent, Some(75.0));
+
+        let later = restored.snapshot(start + 2 * 24 * hour);
+        let health = later.get(provider).expect("later provider health snapshot");
+        assert_eq!(health.uptime_24h_percent, Some(100.0));
+        assert!(health
+            .uptime_7d_percent
+            .is_some_and(|percent| percent > 75.0));
+    }
+
+    #[test]
+    fn usage_only_providers_are_never_selected_for_routing() {
+        let mut cfg = AppConfig::default_config();
+        cfg.providers
+            .get_mut("provider_1")
+            .expect("provider_1")
+            .routing_eligible = false;
+
+        let order = provider_iteration_order(&cfg);
+        assert!(!order.contains(&"provider_1".to_string()));
+        assert!(order.contains(&"provider_2".to_string()));
+        assert_eq!(
+            select_fallback_provider(&cfg, "official", |_| true),
+            "provider_2"
+        );
+    }
+
+    #[test]
+    fn lower_tiers_come_first_and_provider_order_breaks_ties() {
+        let mut cfg = AppConfig::default_config();
+        cfg.provider_order = vec![
+            "official".to_string(),
+            "provider_1".to_string(),
+            "provider_2".to_string(),
+        ];
+        cfg.providers.get_mut("official").expect("official").tier = 1;
+
+        assert_eq!(
+            provider_iteration_order(&cfg),
+            vec!["provider_1", "provider_2", "official"]
+        );
+        assert_eq!(
+            select_fallback_provider(&cfg, "provider_1", |name| name != "provider_2"),
+            "official"
+        );
+    }
+
     #[test]
     fn mark_success_keeps_last_error_but_resets_failure_state() {
         let mut cfg = AppConfig::default_config();
@@ -713,6 +1154,42 @@ mod tests {
         assert_eq!(health.last_error_event_id, None);
     }
 
+    #[test]
+    fn snapshot_reports_bounded_recent_latencies_and_error_rate() {
+        let mut cfg = AppConfig::default_config();
+        cfg.routing.failure_threshold = 100;
+        let provider = "official";
+        let router = RouterState::new(&cfg, 0);
+
+        router.mark_success_with_latency(provider, 1_000, Some(Duration::from_millis(100)));
+        router.mark_success_with_latency(provider, 1_001, Some(Duration::from_millis(200)));
+        let snapshot = router.snapshot(1_001);
+        let health = snapshot.get(provider).expect("provider health snapshot");
+        assert_eq!(health.recent_latencies_ms, vec![100, 200]);
+        assert_eq!(health.ewma_latency_ms, Some(130.0));
+        assert_eq!(health.recent_error_rate, 0.0);
+
+        for i in 0..RECENT_OUTCOME_WINDOW as u64 {
+            if i % 4 == 0 {
+                router.mark_failure(provider, &cfg, "boom", 2_000 + i);
+            } else {
+                router.mark_success_with_latency(
+                    provider,
+                    2_000 + i,
+                    Some(Duration::from_millis(50)),
+                );
+            }
+        }
+        let snapshot = router.snapshot(3_000);
+        let health = snapshot.get(provider).expect("provider health snapshot");
+        assert_eq!(
+            health.recent_latencies_ms.len(),
+            RECENT_OUTCOME_WINDOW * 3 / 4
+        );
+        assert!(health.recent_latencies_ms.iter().all(|ms| *ms == 50));
+        assert_eq!(health.recent_error_rate, 0.25);
+    }
+
     #[test]
     fn mark_failure_keeps_full_last_error_without_truncation() {
         let mut cfg = AppConfig::default_config();
@@ -730,6 +1207,121 @@ mod tests {
         assert_eq!(health.last_error_event_id, None);
     }
 
+    #[test]
+    fn mark_success_emits_recovered_event_only_on_transition() {
+        let mut cfg = AppConfig::default_config();
+        cfg.routing.failure_threshold = 1;
+        let provider = "official";
+        let (_tmp, store) = build_test_store();
+        let router = RouterState::new_with_store(&cfg, 0, Some(store.clone()));
+
+        let start = 1_717_171_709_000;
+        router.mark_failure(provider, &cfg, "boom", start);
+        router.mark_success(provider, start + 5_000);
+        router.mark_success(provider, start + 6_000);
+
+        let recovered: Vec<_> = store
+            .list_events_range(None, None, Some(50))
+            .into_iter()
+            .filter(|event| event["code"] == "health.recovered")
+            .collect();
+        assert_eq!(recovered.len(), 1);
+        assert_eq!(recovered[0]["provider"], provider);
+        assert_eq!(recovered[0]["fields"]["downtime_ms"], 5_000);
+        assert_eq!(recovered[0]["fields"]["last_error"], "boom");
+    }
+
+    #[test]
+    fn health_transitions_are_persisted_only_when_status_changes() {
+        let mut cfg = AppConfig::default_config();
+        cfg.routing.failure_threshold = 2;
+        let provider = "official";
+        let (_tmp, store) = build_test_store();
+        let router = RouterState::new_with_store(&cfg, 0, Some(store.clone()));
+
+        let start = 1_717_171_709_000;
+        router.mark_success(provider, start);
+        router.mark_failure(provider, &cfg, "boom", start + 1_000);
+        router.mark_failure(provider, &cfg, "boom again", start + 2_000);
+        router.mark_success(provider, start + 3_000);
+        router.mark_success(provider, start + 4_000);
+
+        let history = store.list_health_transitions(provider, None, None);
+        let steps: Vec<(&str, &str, &str)> = history
+            .iter()
+            .map(|entry| {
+                (
+                    entry["from_state"].as_str().unwrap(),
+                    entry["to_state"].as_str().unwrap(),
+                    entry["reason"].as_str().unwrap(),
+                )
+            })
+            .collect();
+        assert_eq!(
+            steps,
+            vec![
+                ("unknown", "healthy", "request_succeeded"),
+                ("healthy", "unhealthy", "boom"),
+                ("unhealthy", "cooldown", "boom again"),
+                ("cooldown", "healthy", "request_succeeded"),
+            ]
+        );
+        assert_eq!(history[3]["unix_ms"], start + 3_000);
+
+        let window =
+            store.list_health_transitions(provider, Some(start + 1_000), Some(start + 2_000));
+        assert_eq!(window.len(), 2);
+        assert!(store
+            .list_health_transitions("provider_1", None, None)
+            .is_empty());
+    }
+
+    #[test]
+    fn simulated_failure_cools_provider_down_and_recovers_unless_superseded() {
+        let cfg = AppConfig::default_config();
+        let provider = "official";
+        let (_tmp, store) = build_test_store();
+        let router = RouterState::new_with_store(&cfg, 0, Some(store.clone()));
+        let now = unix_ms();
+        let until = now + 60_000;
+
+        router.mark_success(provider, now);
+        let snapshot = router
+            .simulate_failure(provider, until, now)
+            .expect("known provider");
+        assert_eq!(snapshot.status, "cooldown");
+        assert_eq!(snapshot.last_error, "simulated failure");
+        assert!(!router.is_provider_routable(provider));
+        assert!(router.simulate_failure("missing", until, now).is_none());
+
+        assert!(router.end_simulated_failure(provider, until, now + 1_000));
+        assert!(router.is_provider_routable(provider));
+        assert!(!router.end_simulated_failure(provider, until, now + 2_000));
+
+        router.simulate_failure(provider, until, now + 3_000);
+        router.mark_failure(provider, &cfg, "real outage", now + 4_000);
+        assert!(!router.end_simulated_failure(provider, until, now + 5_000));
+        assert_eq!(
+            router.snapshot(now + 5_000)[provider].last_error,
+            "real outage"
+        );
+
+        let reasons: Vec<String> = store
+            .list_health_transitions(provider, None, None)
+            .iter()
+            .map(|entry| entry["reason"].as_str().unwrap().to_string())
+            .collect();
+        assert_eq!(
+            reasons,
+            vec![
+                "request_succeeded",
+                "simulated_failure",
+                "simulated_failure_ended",
+                "simulated_failure",
+            ]
+        );
+    }
+
     #[test]
     fn snapshot_keeps_last_error_event_id_empty() {
         let mut cfg = AppConfig::default_config();
@@ -42,6 +42,33 @@ struct SecretsFile {
     official_account_profiles: BTreeMap<String, OfficialAccountProfileSecret>,
     #[serde(default)]
     active_official_account_profile_id: Option<String>,
+    /// Additional gateway tokens keyed by label, so each client can be revoked on its own.
+    #[serde(default)]
+    named_gateway_tokens: BTreeMap<String, NamedGatewayTokenSecret>,
+    /// Masked previews of retired provider keys (newest last); raw old keys are never kept.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    provider_key_history: BTreeMap<String, Vec<ProviderKeyHistoryEntry>>,
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq, Eq)]
+pub struct ProviderKeyHistoryEntry {
+    pub preview: String,
+    pub rotated_at_unix_ms: u64,
+}
+
+const PROVIDER_KEY_HISTORY_LIMIT: usize = 5;
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+struct NamedGatewayTokenSecret {
+    token: String,
+    created_at_unix_ms: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct NamedGatewayToken {
+    pub label: String,
+    pub token: String,
+    pub created_at_unix_ms: u64,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -54,6 +81,10 @@ pub struct ProviderPricingOverride {
     gap_fill_mode: Option<String>,
     #[serde(default)]
     gap_fill_amount_usd: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    tiers: Vec<ProviderPricingTier>,
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    model_rates: BTreeMap<String, ProviderModelRate>,
 }
 
 #[derive(Debug, Clone, Serialize, Deserialize)]
@@ -492,6 +523,165 @@ impl Default for ProviderQuotaHardCapConfig {
     }
 }
 
+/// One step of `per_request_tiered` pricing: the day's requests up to and including
+/// `up_to_requests` cost `usd_per_request` each. `None` marks the open-ended last tier.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProviderPricingTier {
+    #[serde(default)]
+    pub up_to_requests: Option<u64>,
+    pub usd_per_request: f64,
+}
+
+/// Tiers must be non-empty, priced at a finite non-negative rate, and strictly ascending by
+/// `up_to_requests`; only the last tier may be open-ended.
+pub fn validate_pricing_tiers(tiers: &[ProviderPricingTier]) -> Result<(), String> {
+    if tiers.is_empty() {
+        return Err("tiers must not be empty".to_string());
+    }
+    let mut previous_up_to = 0u64;
+    for (idx, tier) in tiers.iter().enumerate() {
+        if !tier.usd_per_request.is_finite() || tier.usd_per_request < 0.0 {
+            return Err(format!("tier {} usd_per_request must be >= 0", idx + 1));
+        }
+        match tier.up_to_requests {
+            Some(up_to) if up_to <= previous_up_to => {
+                return Err(format!(
+                    "tier {} up_to_requests must be greater than {previous_up_to}",
+                    idx + 1
+                ));
+            }
+            Some(up_to) => previous_up_to = up_to,
+            None if idx + 1 != tiers.len() => {
+                return Err("only the last tier may omit up_to_requests".to_string());
+            }
+            None => {}
+        }
+    }
+    Ok(())
+}
+
+/// Cost of one day's requests numbered `prior_requests + 1 ..= prior_requests + count` under
+/// `per_request_tiered` pricing; requests past the last tier keep its rate.
+pub fn tiered_request_cost_usd(
+    tiers: &[ProviderPricingTier],
+    prior_requests: u64,
+    count: u64,
+) -> f64 {
+    let end = prior_requests.saturating_add(count);
+    let mut lower = 0u64;
+    let mut cost = 0.0_f64;
+    for (idx, tier) in tiers.iter().enumerate() {
+        let upper = if idx + 1 == tiers.len() {
+            u64::MAX
+        } else {
+            tier.up_to_requests.unwrap_or(u64::MAX)
+        };
+        let covered = end.min(upper).saturating_sub(prior_requests.max(lower));
+        cost += covered as f64 * tier.usd_per_request;
+        if upper >= end {
+            break;
+        }
+        lower = upper;
+    }
+    cost
+}
+
+/// Token rates for one model, in USD per million tokens. Cache writes and reads fall back to the
+/// input rate.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct ProviderModelRate {
+    pub input_usd_per_million: f64,
+    pub output_usd_per_million: f64,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_creation_usd_per_million: Option<f64>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub cache_read_usd_per_million: Option<f64>,
+}
+
+/// Cost of one request under a [`ProviderModelRate`], split by token column.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct ModelRateCost {
+    pub input_usd: f64,
+    pub output_usd: f64,
+    pub cache_creation_usd: f64,
+    pub cache_read_usd: f64,
+}
+
+impl ModelRateCost {
+    pub fn total_usd(&self) -> f64 {
+        self.input_usd + self.output_usd + self.cache_creation_usd + self.cache_read_usd
+    }
+}
+
+impl ProviderModelRate {
+    /// `input_tokens` already includes cache writes and reads; those are carved out and billed
+    /// at their own rates.
+    pub fn cost(
+        &self,
+        input_tokens: u64,
+        output_tokens: u64,
+        cache_creation_tokens: u64,
+        cache_read_tokens: u64,
+    ) -> ModelRateCost {
+        let cache_read_tokens = cache_read_tokens.min(input_tokens);
+        let cache_creation_tokens = cache_creation_tokens.min(input_tokens - cache_read_tokens);
+        let uncached_input_tokens = input_tokens - cache_read_tokens - cache_creation_tokens;
+        let per_token = |tokens: u64, usd_per_million: f64| tokens as f64 * usd_per_million / 1e6;
+        ModelRateCost {
+            input_usd: per_token(uncached_input_tokens, self.input_usd_per_million),
+            output_usd: per_token(output_tokens, self.output_usd_per_million),
+            cache_creation_usd: per_token(
+                cache_creation_tokens,
+                self.cache_creation_usd_per_million
+                    .unwrap_or(self.input_usd_per_million),
+            ),
+            cache_read_usd: per_token(
+                cache_read_tokens,
+                self.cache_read_usd_per_million
+                    .unwrap_or(self.input_usd_per_million),
+            ),
+        }
+    }
+}
+
+/// Looks up the rate for `model`, preferring an exact key and then a case-insensitive match.
+pub fn model_rate_for<'a>(
+    rates: &'a BTreeMap<String, ProviderModelRate>,
+    model: &str,
+) -> Option<&'a ProviderModelRate> {
+    let model = model.trim();
+    rates.get(model).or_else(|| {
+        rates
+            .iter()
+            .find(|(key, _)| key.eq_ignore_ascii_case(model))
+            .map(|(_, rate)| rate)
+    })
+}
+
+pub fn validate_model_rate(model: &str, rate: &ProviderModelRate) -> Result<(), String> {
+    if model.trim().is_empty() {
+        return Err("model is required".to_string());
+    }
+    let rates = [
+        ("input_usd_per_million", Some(rate.input_usd_per_million)),
+        ("output_usd_per_million", Some(rate.output_usd_per_million)),
+        (
+            "cache_creation_usd_per_million",
+            rate.cache_creation_usd_per_million,
+        ),
+        (
+            "cache_read_usd_per_million",
+            rate.cache_read_usd_per_million,
+        ),
+    ];
+    for (field, value) in rates {
+        if value.is_some_and(|v| !v.is_finite() || v < 0.0) {
+            return Err(format!("{model}: {field} must be >= 0"));
+        }
+    }
+    Ok(())
+}
+
 #[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
 pub struct ProviderPricingPeriod {
     pub id: String,
@@ -511,6 +701,11 @@ pub struct ProviderPricingConfig {
     pub periods: Vec<ProviderPricingPeriod>,
     pub gap_fill_mode: Option<String>,
     pub gap_fill_amount_usd: Option<f64>,
+    #[serde(default, skip_serializing_if = "Vec::is_empty")]
+    pub tiers: Vec<ProviderPricingTier>,
+    /// Per-model token rates keyed by model id; they apply regardless of `mode`.
+    #[serde(default, skip_serializing_if = "BTreeMap::is_empty")]
+    pub model_rates: BTreeMap<String, ProviderModelRate>,
 }
 
 pub fn resolve_provider_pricing_config<'a>(
@@ -557,9 +752,12 @@ pub fn resolve_provider_pricing_config<'a>(
     matched.map(|(cfg, _)| cfg)
 }
 
+/// Per-request price in effect at `ts_unix_ms`. Under `per_request_tiered` pricing this is the
+/// rate of the tier the next request falls into after `requests_earlier_that_day`.
 pub fn pricing_per_request_amount_at(
     pricing_cfg: Option<&ProviderPricingConfig>,
     ts_unix_ms: u64,
+    requests_earlier_that_day: u64,
 ) -> Option<f64> {
     let cfg = pricing_cfg?;
     let mut matched: Option<(f64, u64)> = None;
@@ -587,6 +785,14 @@ pub fn pricing_per_request_amount_at(
     if cfg.mode == "per_request" && cfg.amount_usd.is_finite() && cfg.amount_usd > 0.0 {
         return Some(cfg.amount_usd);
     }
+    if cfg.mode == "per_request_tiered" && !cfg.tiers.is_empty() {
+        return Some(tiered_request_cost_usd(
+            &cfg.tiers,
+            requests_earlier_that_day,
+            1,
+        ))
+        .filter(|amount| *amount > 0.0);
+    }
     None
 }
 
@@ -597,6 +803,8 @@ pub struct SecretStore {
 }
 
 const GATEWAY_TOKEN_KEY: &str = "__gateway_token__";
+/// Label reported for requests authenticated with the primary `gateway_token`.
+pub const PRIMARY_GATEWAY_TOKEN_LABEL: &str = "default";
 const PROVIDER_KEY_STORAGE_AUTH_JSON: &str = "auth_json";
 const PROVIDER_KEY_STORAGE_CONFIG_TOML_EXPERIMENTAL_BEARER_TOKEN: &str =
     "config_toml_experimental_bearer_token";
@@ -800,6 +1008,35 @@ impl SecretStore {
         self.persist(&data)
     }
 
+    pub fn record_provider_key_rotation(
+        &self,
+        provider: &str,
+        previous_preview: String,
+        rotated_at_unix_ms: u64,
+    ) -> Result<(), String> {
+        let mut data = self.inner.lock();
+        let history = data
+            .provider_key_history
+            .entry(provider.to_string())
+            .or_default();
+        history.push(ProviderKeyHistoryEntry {
+            preview: previous_preview,
+            rotated_at_unix_ms,
+        });
+        let overflow = history.len().saturating_sub(PROVIDER_KEY_HISTORY_LIMIT);
+        history.drain(..overflow);
+        self.persist(&data)
+    }
+
+    /// Retired key previews for `provider`, newest first.
+    pub fn provider_key_history(&self, provider: &str) -> Vec<ProviderKeyHistoryEntry> {
+        let data = self.inner.lock();
+        data.provider_key_history
+            .get(provider)
+            .map(|history| history.iter().rev().cloned().collect())
+            .unwrap_or_default()
+    }
+
     pub fn clear_provider_key(&self, provider: &str) -> Result<(), String> {
         let mut data = self.inner.lock();
         data.providers.remove(provider);
@@ -1286,6 +1523,9 @@ impl SecretStore {
         if let Some(v) = data.provider_account_emails.remove(old) {
             data.provider_account_emails.insert(new.to_string(), v);
         }
+        if let Some(v) = data.provider_key_history.remove(old) {
+            data.provider_key_history.insert(new.to_string(), v);
+        }
         if let Some(v) = data.usage_tokens.remove(old) {
             data.usage_tokens.insert(new.to_string(), v);
         }
@@ -1393,6 +1633,8 @@ impl SecretStore {
                         periods: v.periods.clone(),
                         gap_fill_mode: v.gap_fill_mode.clone(),
                         gap_fill_amount_usd: v.gap_fill_amount_usd,
+                        tiers: v.tiers.clone(),
+                        model_rates: v.model_rates.clone(),
                     },
                 )
             })
@@ -1406,6 +1648,45 @@ impl SecretStore {
         amount_usd: f64,
         package_expires_at_unix_ms: Option<u64>,
         api_key_ref: Option<String>,
+    ) -> Result<(), String> {
+        self.store_provider_pricing(
+            provider,
+            mode,
+            amount_usd,
+            package_expires_at_unix_ms,
+            api_key_ref,
+            Vec::new(),
+        )
+    }
+
+    /// Switches the provider to `per_request_tiered` pricing. Tiers are validated here so a bad
+    /// schedule never reaches disk; `amount_usd` mirrors the first tier's rate for display.
+    pub fn set_provider_pricing_tiered(
+        &self,
+        provider: &str,
+        tiers: Vec<ProviderPricingTier>,
+        api_key_ref: Option<String>,
+    ) -> Result<(), String> {
+        validate_pricing_tiers(&tiers)?;
+        let first_rate = tiers[0].usd_per_request;
+        self.store_provider_pricing(
+            provider,
+            "per_request_tiered",
+            first_rate,
+            None,
+            api_key_ref,
+            tiers,
+        )
+    }
+
+    fn store_provider_pricing(
+        &self,
+        provider: &str,
+        mode: &str,
+        amount_usd: f64,
+        package_expires_at_unix_ms: Option<u64>,
+        api_key_ref: Option<String>,
+        tiers: Vec<ProviderPricingTier>,
     ) -> Result<(), String> {
         let mut data = self.inner.lock();
         let existing_gap_mode = data
@@ -1416,6 +1697,11 @@ impl SecretStore {
             .provider_pricing
             .get(provider)
             .and_then(|v| v.gap_fill_amount_usd);
+        let existing_model_rates = data
+            .provider_pricing
+            .get(provider)
+            .map(|v| v.model_rates.clone())
+            .unwrap_or_default();
         let normalized_mode = mode.trim().to_lowercase();
         let now = crate::orchestrator::store::unix_ms();
         let mut periods = data
@@ -1423,18 +1709,28 @@ impl SecretStore {
             .get(provider)
             .map(|v| v.periods.clone())
             .unwrap_or_default();
+        // Switching modes ends every open period; otherwise a period of the old mode would keep
+        // billing alongside the new one.
+        let mode_changed = data
+            .provider_pricing
+            .get(provider)
+            .is_some_and(|v| v.mode != normalized_mode);
 
         for period in periods.iter_mut() {
             if period.ended_at_unix_ms.is_some() {
                 continue;
             }
-            if normalized_mode == "none" || period.mode == normalized_mode {
+            if normalized_mode == "none" || mode_changed || period.mode == normalized_mode {
                 period.ended_at_unix_ms = Some(now);
             }
         }
 
         if normalized_mode == "none" && amount_usd <= 0.0 {
-            if existing_gap_mode.is_none() && existing_gap_amount.is_none() && periods.is_empty() {
+            if existing_gap_mode.is_none()
+                && existing_gap_amount.is_none()
+                && periods.is_empty()
+                && existing_model_rates.is_empty()
+            {
                 data.provider_pricing.remove(provider);
             } else {
                 data.provider_pricing.insert(
@@ -1445,6 +1741,8 @@ impl SecretStore {
                         periods,
                         gap_fill_mode: existing_gap_mode,
                         gap_fill_amount_usd: existing_gap_amount,
+                        tiers: Vec::new(),
+                        model_rates: existing_model_rates,
                     },
                 );
             }
@@ -1479,11 +1777,64 @@ impl SecretStore {
                 periods,
                 gap_fill_mode: existing_gap_mode,
                 gap_fill_amount_usd: existing_gap_amount,
+                tiers,
+                model_rates: existing_model_rates,
             },
         );
         self.persist(&data)
     }
 
+    /// Splits the open-ended `package_total` period billed to `old_key_ref` at `now_unix_ms`,
+    /// continuing it under `new_key_ref`. Returns the closed and the new slice when a split
+    /// happened; periods that would yield a zero-length slice are left untouched.
+    pub fn split_active_package_period_for_key(
+        &self,
+        provider: &str,
+        old_key_ref: &str,
+        new_key_ref: &str,
+        now_unix_ms: u64,
+    ) -> Result<Option<(ProviderPricingPeriod, ProviderPricingPeriod)>, String> {
+        let old_key_ref = old_key_ref.trim();
+        let new_key_ref = new_key_ref.trim();
+        if old_key_ref.is_empty() || old_key_ref == "-" || old_key_ref == new_key_ref {
+            return Ok(None);
+        }
+        let mut data = self.inner.lock();
+        let Some(entry) = data.provider_pricing.get_mut(provider) else {
+            return Ok(None);
+        };
+        let Some(active) = entry
+            .periods
+            .iter_mut()
+            .filter(|period| {
+                period.mode == "package_total"
+                    && period.ended_at_unix_ms.is_none()
+                    && period.started_at_unix_ms < now_unix_ms
+                    && period.api_key_ref.trim() == old_key_ref
+            })
+            .max_by_key(|period| period.started_at_unix_ms)
+        else {
+            return Ok(None);
+        };
+        active.ended_at_unix_ms = Some(now_unix_ms);
+        let closed = active.clone();
+        let next = ProviderPricingPeriod {
+            id: Uuid::new_v4().to_string(),
+            mode: closed.mode.clone(),
+            amount_usd: closed.amount_usd,
+            api_key_ref: if new_key_ref.is_empty() {
+                "-".to_string()
+            } else {
+                new_key_ref.to_string()
+            },
+            started_at_unix_ms: now_unix_ms,
+            ended_at_unix_ms: None,
+        };
+        entry.periods.push(next.clone());
+        self.persist(&data)?;
+        Ok(Some((closed, next)))
+    }
+
     pub fn list_provider_schedule(&self, provider: &str) -> Vec<ProviderPricingPeriod> {
         let data = self.inner.lock();
         let mut periods = data
@@ -1605,8 +1956,17 @@ impl SecretStore {
             .provider_pricing
             .get(provider)
             .and_then(|v| v.gap_fill_amount_usd);
+        let existing_model_rates = data
+            .provider_pricing
+            .get(provider)
+            .map(|v| v.model_rates.clone())
+            .unwrap_or_default();
 
-        if periods.is_empty() && existing_gap_mode.is_none() && existing_gap_amount.is_none() {
+        if periods.is_empty()
+            && existing_gap_mode.is_none()
+            && existing_gap_amount.is_none()
+            && existing_model_rates.is_empty()
+        {
             data.provider_pricing.remove(provider);
             return self.persist(&data);
         }
@@ -1619,6 +1979,8 @@ impl SecretStore {
                 periods,
                 gap_fill_mode: existing_gap_mode,
                 gap_fill_amount_usd: existing_gap_amount,
+                tiers: Vec::new(),
+                model_rates: existing_model_rates,
             },
         );
         self.persist(&data)
@@ -1640,6 +2002,8 @@ impl SecretStore {
                     periods: Vec::new(),
                     gap_fill_mode: None,
                     gap_fill_amount_usd: None,
+                    tiers: Vec::new(),
+                    model_rates: BTreeMap::new(),
                 });
         entry.gap_fill_mode = mode.map(|s| s.to_string());
         entry.gap_fill_amount_usd = amount_usd;
@@ -1649,18 +2013,75 @@ impl SecretStore {
             && entry.amount_usd <= 0.0
             && entry.periods.is_empty()
             && entry.gap_fill_mode.is_none()
-            && entry.gap_fill_amount_usd.is_none();
+            && entry.gap_fill_amount_usd.is_none()
+            && entry.model_rates.is_empty();
         if remove {
             data.provider_pricing.remove(provider);
         }
         self.persist(&data)
     }
 
+    /// Adds or replaces per-model rates for `provider`, leaving its other pricing untouched.
+    /// Every rate is validated before anything is written.
+    pub fn upsert_provider_model_rates(
+        &self,
+        provider: &str,
+        rates: BTreeMap<String, ProviderModelRate>,
+    ) -> Result<(), String> {
+        for (model, rate) in &rates {
+            validate_model_rate(model, rate)?;
+        }
+        let mut data = self.inner.lock();
+        let entry =
+            data.provider_pricing
+                .entry(provider.to_string())
+                .or_insert(ProviderPricingOverride {
+                    mode: "none".to_string(),
+                    amount_usd: 0.0,
+                    periods: Vec::new(),
+                    gap_fill_mode: None,
+                    gap_fill_amount_usd: None,
+                    tiers: Vec::new(),
+                    model_rates: BTreeMap::new(),
+                });
+        entry.model_rates.extend(
+            rates
+                .into_iter()
+                .map(|(model, rate)| (model.trim().to_string(), rate)),
+        );
+        self.persist(&data)
+    }
+
+    /// Removes the rate for `model`; returns whether one existed. The pricing row is dropped
+    /// once nothing else is configured for the provider.
+    pub fn remove_provider_model_rate(&self, provider: &str, model: &str) -> Result<bool, String> {
+        let mut data = self.inner.lock();
+        let Some(entry) = data.provider_pricing.get_mut(provider) else {
+            return Ok(false);
+        };
+        if entry.model_rates.remove(model.trim()).is_none() {
+            return Ok(false);
+        }
+        let remove = entry.mode == "none"
+            && entry.amount_usd <= 0.0
+            && entry.periods.is_empty()
+            && entry.gap_fill_mode.is_none()
+            && entry.gap_fill_amount_usd.is_none()
+            && entry.tiers.is_empty()
+            && entry.model_rates.is_empty();
+        if remove {
+            data.provider_pricing.remove(provider);
+        }
+        self.persist(&data)?;
+        Ok(true)
+    }
+
     pub fn delete_provider(&self, provider: &str) -> Result<(), String> {
         let mut data = self.inner.lock();
         data.providers.remove(provider);
         data.provider_key_storage_modes.remove(provider);
         data.provider_account_emails.remove(provider);
+        data.provider_key_history.remove(provider);
         data.usage_tokens.remove(provider);
         data.usage_logins.remove(provider);
         data.usage_proxy_pools.remove(provider);
@@ -1812,6 +2233,98 @@ impl SecretStore {
         self.persist(&data)
     }
 
+    pub fn named_gateway_tokens(&self) -> Vec<NamedGatewayToken> {
+        self.inner
+            .lock()
+            .named_gateway_tokens
+            .iter()
+            .map(|(label, secret)| NamedGatewayToken {
+                label: label.clone(),
+                token: secret.token.clone(),
+                created_at_unix_ms: secret.created_at_unix_ms,
+            })
+            .collect()
+    }
+
+    pub fn create_named_gateway_token(&self, label: &str) -> Result<NamedGatewayToken, String> {
+        let label = label.trim();
+        if label.is_empty() {
+            return Err("token label is required".to_string());
+        }
+        if label.chars().count() > 64 {
+            return Err("token label must be at most 64 characters".to_string());
+        }
+        if label.eq_ignore_ascii_case(PRIMARY_GATEWAY_TOKEN_LABEL) {
+            return Err(format!(
+                "token label is reserved for the primary gateway token: {label}"
+            ));
+        }
+        let mut data = self.inner.lock();
+        if data.named_gateway_tokens.contains_key(label) {
+            return Err(format!("gateway token label already exists: {label}"));
+        }
+        let created = NamedGatewayToken {
+            label: label.to_string(),
+            token: Self::new_gateway_token(),
+            created_at_unix_ms: unix_ms_now(),
+        };
+        data.named_gateway_tokens.insert(
+            created.label.clone(),
+            NamedGatewayTokenSecret {
+                token: created.token.clone(),
+                created_at_unix_ms: created.created_at_unix_ms,
+            },
+        );
+        if let Err(e) = self.persist(&data) {
+            data.named_gateway_tokens.remove(label);
+            return Err(e);
+        }
+        Ok(created)
+    }
+
+    pub fn revoke_named_gateway_token(&self, label: &str) -> Result<(), String> {
+        let label = label.trim();
+        let mut data = self.inner.lock();
+        let Some(prev) = data.named_gateway_tokens.remove(label) else {
+            return Err(format!("unknown gateway token: {label}"));
+        };
+        if let Err(e) = self.persist(&data) {
+            data.named_gateway_tokens.insert(label.to_string(), prev);
+            return Err(e);
+        }
+        Ok(())
+    }
+
+    /// True when any gateway credential (primary or named) is configured.
+    pub fn gateway_auth_required(&self) -> bool {
+        let data = self.inner.lock();
+        !data.named_gateway_tokens.is_empty()
+            || data
+                .providers
+                .get(GATEWAY_TOKEN_KEY)
+                .is_some_and(|t| !t.trim().is_empty())
+    }
+
+    /// Label of the gateway credential matching `token`, or `None` if it is not a live token.
+    pub fn gateway_token_label(&self, token: &str) -> Option<String> {
+        let token = token.trim();
+        if token.is_empty() {
+            return None;
+        }
+        let data = self.inner.lock();
+        if data
+            .providers
+            .get(GATEWAY_TOKEN_KEY)
+            .is_some_and(|t| t.trim() == token)
+        {
+            return Some(PRIMARY_GATEWAY_TOKEN_LABEL.to_string());
+        }
+        data.named_gateway_tokens
+            .iter()
+            .find(|(_, secret)| secret.token == token)
+            .map(|(label, _)| label.clone())
+    }
+
     pub fn rotate_gateway_token(&self) -> Result<String, String> {
         let t = Self::new_gateway_token();
         self.set_gateway_token(&t)?;
@@ -1826,9 +2339,10 @@ impl SecretStore {
 #[cfg(test)]
 mod tests {
     use super::{
-        pricing_per_request_amount_at, resolve_provider_pricing_config,
-        OfficialAccountUsageSnapshot, ProviderPricingConfig, ProviderPricingPeriod,
-        ProviderQuotaHardCapConfig, ProviderStateBundle, SecretStore, UsageLoginConfig,
+        pricing_per_request_amount_at, resolve_provider_pricing_config, ModelRateCost,
+        OfficialAccountUsageSnapshot, ProviderModelRate, ProviderPricingConfig,
+        ProviderPricingPeriod, ProviderPricingTier, ProviderQuotaHardCapConfig,
+        ProviderStateBundle, SecretStore, UsageLoginConfig,
     };
     use std::collections::BTreeMap;
     use std::sync::{Arc, Barrier};
@@ -2533,6 +3047,85 @@ mod tests {
         );
     }
 
+    #[test]
+    fn provider_key_history_keeps_last_five_rotations_newest_first() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let path = tmp.path().join("secrets.json");
+        let store = SecretStore::new(path.clone());
+        for i in 0..7u64 {
+            store
+                .record_provider_key_rotation("p1", format!("sk-ol{i}******000{i}"), 1_000 + i)
+                .expect("record rotation");
+        }
+        store.rename_provider("p1", "p2").expect("rename");
+
+        let history = SecretStore::new(path).provider_key_history("p2");
+        assert_eq!(history.len(), 5);
+        assert_eq!(history[0].preview, "sk-ol6******0006");
+        assert_eq!(history[0].rotated_at_unix_ms, 1_006);
+        assert_eq!(history[4].preview, "sk-ol2******0002");
+        assert!(store.provider_key_history("p1").is_empty());
+    }
+
+    #[test]
+    fn split_active_package_period_for_key_hands_open_period_to_new_key() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let store = SecretStore::new(tmp.path().join("secrets.json"));
+        store
+            .set_provider_pricing(
+                "p1",
+                "package_total",
+                30.0,
+                None,
+                Some("sk-old******1111".to_string()),
+            )
+            .expect("set pricing");
+        let started = store.list_provider_schedule("p1")[0].started_at_unix_ms;
+
+        assert_eq!(
+            store
+                .split_active_package_period_for_key(
+                    "p1",
+                    "sk-old******1111",
+                    "sk-new******2222",
+                    started,
+                )
+                .expect("zero-length split"),
+            None
+        );
+        assert_eq!(
+            store
+                .split_active_package_period_for_key(
+                    "p1",
+                    "sk-other****3333",
+                    "sk-new******2222",
+                    started + 1_000,
+                )
+                .expect("unmatched key"),
+            None
+        );
+
+        let (closed, next) = store
+            .split_active_package_period_for_key(
+                "p1",
+                "sk-old******1111",
+                "sk-new******2222",
+                started + 1_000,
+            )
+            .expect("split")
+            .expect("split happened");
+        assert_eq!(closed.ended_at_unix_ms, Some(started + 1_000));
+        assert_eq!(next.started_at_unix_ms, started + 1_000);
+        assert_eq!(next.ended_at_unix_ms, None);
+        assert_eq!(next.api_key_ref, "sk-new******2222");
+        assert_eq!(next.amount_usd, 30.0);
+
+        let schedule = store.list_provider_schedule("p1");
+        assert_eq!(schedule.len(), 2);
+        assert_eq!(schedule[0].api_key_ref, "sk-old******1111");
+        assert_eq!(schedule[1].api_key_ref, "sk-new******2222");
+    }
+
     #[test]
     fn resolve_provider_pricing_config_matches_renamed_per_request_period_by_key_ref() {
         let pricing = std::collections::BTreeMap::from([(
@@ -2550,6 +3143,8 @@ mod tests {
                 }],
                 gap_fill_mode: None,
                 gap_fill_amount_usd: None,
+                tiers: Vec::new(),
+                model_rates: BTreeMap::new(),
             },
         )]);
 
@@ -2561,8 +3156,202 @@ mod tests {
         );
 
         assert_eq!(
-            pricing_per_request_amount_at(resolved, 1_700_100_000_000),
+            pricing_per_request_amount_at(resolved, 1_700_100_000_000, 0),
             Some(0.035)
         );
     }
+
+    #[test]
+    fn tiered_pricing_persists_sorted_tiers_and_rejects_overlaps() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let path = tmp.path().join("secrets.json");
+        let store = SecretStore::new(path.clone());
+        let tier = |up_to_requests: Option<u64>, usd_per_request: f64| ProviderPricingTier {
+            up_to_requests,
+            usd_per_request,
+        };
+
+        for bad in [
+            Vec::new(),
+            vec![tier(Some(10), 0.05), tier(Some(10), 0.03)],
+            vec![tier(Some(50), 0.05), tier(Some(10), 0.03)],
+            vec![tier(None, 0.05), tier(Some(10), 0.03)],
+            vec![tier(Some(0), 0.05)],
+            vec![tier(Some(10), -0.01)],
+        ] {
+            assert!(store.set_provider_pricing_tiered("p1", bad, None).is_err());
+        }
+        assert!(store.list_provider_pricing().get("p1").is_none());
+
+        let tiers = vec![tier(Some(10), 0.05), tier(Some(50), 0.03), tier(None, 0.01)];
+        store
+            .set_provider_pricing_tiered("p1", tiers.clone(), None)
+            .expect("set tiered pricing");
+        let reloaded = SecretStore::new(path);
+        let pricing = reloaded
+            .list_provider_pricing()
+            .remove("p1")
+            .expect("p1 pricing");
+        assert_eq!(pricing.mode, "per_request_tiered");
+        assert_eq!(pricing.amount_usd, 0.05);
+        assert_eq!(pricing.tiers, tiers);
+
+        reloaded
+            .set_provider_pricing("p1", "per_request", 0.02, None, None)
+            .expect("switch to flat per request");
+        assert!(reloaded.list_provider_pricing()["p1"].tiers.is_empty());
+    }
+
+    #[test]
+    fn switching_pricing_mode_ends_open_periods_of_the_old_mode() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let store = SecretStore::new(tmp.path().join("secrets.json"));
+        store
+            .set_provider_pricing("p1", "per_request", 0.02, None, None)
+            .expect("set per request pricing");
+        store
+            .set_provider_pricing_tiered(
+                "p1",
+                vec![ProviderPricingTier {
+                    up_to_requests: None,
+                    usd_per_request: 0.01,
+                }],
+                None,
+            )
+            .expect("switch to tiered pricing");
+
+        let pricing = store
+            .list_provider_pricing()
+            .remove("p1")
+            .expect("p1 pricing");
+        assert_eq!(pricing.periods.len(), 1);
+        assert!(pricing.periods[0].ended_at_unix_ms.is_some());
+        let later = crate::orchestrator::store::unix_ms() + 1;
+        assert_eq!(
+            pricing_per_request_amount_at(Some(&pricing), later, 0),
+            Some(0.01)
+        );
+    }
+
+    #[test]
+    fn model_rates_survive_mode_changes_and_reject_negative_rates() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let path = tmp.path().join("secrets.json");
+        let store = SecretStore::new(path.clone());
+        let rate = |input: f64, cache_read: Option<f64>| ProviderModelRate {
+            input_usd_per_million: input,
+            output_usd_per_million: 10.0,
+            cache_creation_usd_per_million: None,
+            cache_read_usd_per_million: cache_read,
+        };
+
+        for bad in [
+            rate(-1.0, None),
+            rate(f64::NAN, None),
+            rate(1.0, Some(-0.5)),
+        ] {
+            let rates = BTreeMap::from([("gpt-5.2".to_string(), bad)]);
+            assert!(store.upsert_provider_model_rates("p1", rates).is_err());
+        }
+        assert!(store.list_provider_pricing().get("p1").is_none());
+
+        store
+            .upsert_provider_model_rates(
+                "p1",
+                BTreeMap::from([(" gpt-5.2 ".to_string(), rate(1.25, Some(0.125)))]),
+            )
+            .expect("store model rates");
+        store
+            .set_provider_pricing("p1", "per_request", 0.02, None, None)
+            .expect("set per request");
+        store
+            .set_provider_pricing("p1", "none", 0.0, None, None)
+            .expect("clear pricing");
+
+        let reloaded = SecretStore::new(path);
+        let pricing = reloaded
+            .list_provider_pricing()
+            .remove("p1")
+            .expect("p1 pricing");
+        assert_eq!(pricing.mode, "none");
+        assert_eq!(
+            pricing.model_rates,
+            BTreeMap::from([("gpt-5.2".to_string(), rate(1.25, Some(0.125)))])
+        );
+
+        assert!(reloaded
+            .remove_provider_model_rate("p1", "gpt-5.2")
+            .expect("remove rate"));
+        assert!(!reloaded
+            .remove_provider_model_rate("p1", "gpt-5.2")
+            .expect("remove missing rate"));
+        assert!(reloaded.list_provider_pricing().get("p1").is_none());
+    }
+
+    #[test]
+    fn model_rate_cost_bills_cache_writes_and_reads_at_their_own_rates() {
+        let rate = ProviderModelRate {
+            input_usd_per_million: 3.0,
+            output_usd_per_million: 15.0,
+            cache_creation_usd_per_million: Some(3.75),
+            cache_read_usd_per_million: Some(0.3),
+        };
+        let cost = rate.cost(1_000_000, 100_000, 200_000, 500_000);
+        let ModelRateCost {
+            input_usd,
+            output_usd,
+            cache_creation_usd,
+            cache_read_usd,
+        } = cost;
+        for (actual, expected) in [
+            (input_usd, 0.9),
+            (output_usd, 1.5),
+            (cache_creation_usd, 0.75),
+            (cache_read_usd, 0.15),
+            (cost.total_usd(), 3.3),
+        ] {
+            assert!((actual - expected).abs() < 1e-9, "{actual} != {expected}");
+        }
+
+        let fallback = ProviderModelRate {
+            cache_creation_usd_per_million: None,
+            cache_read_usd_per_million: None,
+            ..rate
+        };
+        let cost = fallback.cost(1_000_000, 0, 200_000, 500_000);
+        assert!((cost.cache_creation_usd - 0.6).abs() < 1e-9);
+        assert!((cost.cache_read_usd - 1.5).abs() < 1e-9);
+    }
+
+    #[test]
+    fn named_gateway_tokens_match_until_revoked() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let path = tmp.path().join("secrets.json");
+        let store = SecretStore::new(path.clone());
+        assert!(!store.gateway_auth_required());
+        store.set_gateway_token("ao-primary").expect("primary");
+
+        let laptop = store
+            .create_named_gateway_token(" laptop ")
+            .expect("create");
+        assert_eq!(laptop.label, "laptop");
+        assert!(store.create_named_gateway_token("laptop").is_err());
+        assert!(store.create_named_gateway_token("default").is_err());
+        assert_eq!(
+            store.gateway_token_label("ao-primary").as_deref(),
+            Some(super::PRIMARY_GATEWAY_TOKEN_LABEL)
+        );
+        assert_eq!(
+            store.gateway_token_label(&laptop.token).as_deref(),
+            Some("laptop")
+        );
+
+        let reloaded = SecretStore::new(path);
+        assert_eq!(reloaded.named_gateway_tokens(), vec![laptop.clone()]);
+        reloaded
+            .revoke_named_gateway_token("laptop")
+            .expect("revoke");
+        assert!(reloaded.gateway_token_label(&laptop.token).is_none());
+        assert!(reloaded.revoke_named_gateway_token("laptop").is_err());
+    }
 }
@@ -11,8 +11,14 @@ mod usage_tracking;
 #[derive(Clone)]
 pub struct Store {
     db: sled::Db,
+    sled_path: PathBuf,
     events_db_path: PathBuf,
     events_db: Arc<Mutex<rusqlite::Connection>>,
+    pending_usage_requests: Arc<Mutex<Vec<usage_tracking::PendingUsageRequest>>>,
+    /// Last `usage.unparsed` warning per provider/model, so a changed format warns once per window.
+    unparsed_usage_reported_at: Arc<Mutex<std::collections::HashMap<(String, String), u64>>>,
+    /// Latest timestamp handed out by `recorded_unix_ms`, to notice the wall clock stepping back.
+    clock_high_water_unix_ms: Arc<std::sync::atomic::AtomicU64>,
 }
 
 #[derive(Clone, Copy)]
@@ -51,9 +57,20 @@ pub struct EventCode {
     code: &'static str,
 }
 
+/// Buffered usage rows are written in one transaction once this many accumulate...
+const USAGE_REQUEST_BATCH_MAX_ROWS: usize = 64;
+/// ...or once the oldest buffered row is this old; also the background flush cadence.
+pub const USAGE_REQUEST_BATCH_MAX_DELAY_MS: u64 = 500;
+/// Most usage rows kept buffered while inserts keep failing; older rows are dropped first.
+const USAGE_REQUEST_BUFFER_MAX_ROWS: usize = 10_000;
+/// Longest label suffix kept on a manual backup directory name.
+const MANUAL_BACKUP_LABEL_MAX_CHARS: usize = 48;
+/// How long recorded usage failures are kept; matches the longest usage statistics window.
+pub const USAGE_FAILURE_RETENTION_MS: u64 = 30 * 24 * 60 * 60 * 1000;
+
 const LEDGER_DEFAULT: &str = r#"{"since_last_quota_refresh_requests":0,"since_last_quota_refresh_input_tokens":0,"since_last_quota_refresh_output_tokens":0,"since_last_quota_refresh_total_tokens":0,"last_reset_unix_ms":0}"#;
 
-#[derive(Clone, Copy)]
+#[derive(Clone, Copy, Default)]
 struct UsageTokenIncrements {
     input_tokens: u64,
     output_tokens: u64,
@@ -70,6 +87,29 @@ pub struct UsageRequestContext<'a> {
     pub session_id: Option<&'a str>,
     pub node_id: Option<&'a str>,
     pub node_name: Option<&'a str>,
+    pub label: Option<&'a str>,
+    /// Body sizes of the proxied request and response; 0 when unknown.
+    pub request_bytes: u64,
+    pub response_bytes: u64,
+}
+
+#[cfg(test)]
+impl UsageRequestContext<'_> {
+    /// A `windows`/`http` context with api key ref `-` and nothing else set. Tests override the
+    /// fields they care about with struct update syntax.
+    pub(crate) fn test_context() -> Self {
+        Self {
+            api_key_ref: Some("-"),
+            origin: crate::constants::USAGE_ORIGIN_WINDOWS,
+            transport: "http",
+            session_id: None,
+            node_id: None,
+            node_name: None,
+            label: None,
+            request_bytes: 0,
+            response_bytes: 0,
+        }
+    }
 }
 
 type UsageRequestSqlRow = (
@@ -87,6 +127,12 @@ type UsageRequestSqlRow = (
     i64,
 );
 
+/// Column list matching the field order read by `Store::usage_request_sync_row`.
+const USAGE_REQUEST_SYNC_COLUMNS: &str =
+    "id, unix_ms, ingested_at_unix_ms, provider, api_key_ref, model, origin,
+     transport, session_id, node_id, node_name, input_tokens, output_tokens, total_tokens,
+     cache_creation_input_tokens, cache_read_input_tokens, label, request_bytes, response_bytes";
+
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq, Eq)]
 pub struct UsageRequestSyncRow {
     pub id: String,
@@ -105,6 +151,41 @@ pub struct UsageRequestSyncRow {
     pub total_tokens: u64,
     pub cache_creation_input_tokens: u64,
     pub cache_read_input_tokens: u64,
+    #[serde(default, skip_serializing_if = "String::is_empty")]
+    pub label: String,
+    #[serde(default)]
+    pub request_bytes: u64,
+    #[serde(default)]
+    pub response_bytes: u64,
+}
+
+#[cfg(test)]
+impl UsageRequestSyncRow {
+    /// A `gpt-5.2-codex` row ingested at `unix_ms` from session `s1` on node `node-a` (`Desk A`),
+    /// with 10 input and 5 output tokens. Tests override the rest with struct update syntax.
+    pub(crate) fn test_row(id: impl Into<String>, provider: &str, unix_ms: u64) -> Self {
+        Self {
+            id: id.into(),
+            unix_ms,
+            ingested_at_unix_ms: unix_ms,
+            provider: provider.to_string(),
+            api_key_ref: "-".to_string(),
+            model: "gpt-5.2-codex".to_string(),
+            origin: crate::constants::USAGE_ORIGIN_WINDOWS.to_string(),
+            transport: "http".to_string(),
+            session_id: "s1".to_string(),
+            node_id: "node-a".to_string(),
+            node_name: "Desk A".to_string(),
+            input_tokens: 10,
+            output_tokens: 5,
+            total_tokens: 15,
+            cache_creation_input_tokens: 0,
+            cache_read_input_tokens: 0,
+            label: String::new(),
+            request_bytes: 0,
+            response_bytes: 0,
+        }
+    }
 }
 
 #[derive(Clone, Debug, PartialEq, Eq)]
@@ -114,12 +195,30 @@ pub struct UsageRequestStatsRow {
     pub model: String,
     pub origin: String,
     pub node_name: String,
+    pub label: String,
     pub unix_ms: u64,
     pub input_tokens: u64,
     pub output_tokens: u64,
     pub total_tokens: u64,
     pub cache_creation_input_tokens: u64,
     pub cache_read_input_tokens: u64,
+    pub request_bytes: u64,
+    pub response_bytes: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct UsageFailureRow {
+    pub provider: String,
+    pub unix_ms: u64,
+}
+
+/// Rows dropped by [`Store::evict_to_size_cap`] and the store size around it.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct StoreEviction {
+    pub events_deleted: usize,
+    pub usage_requests_deleted: usize,
+    pub bytes_before: u64,
+    pub bytes_after: u64,
 }
 
 #[derive(Clone, Debug, Serialize, Deserialize, PartialEq)]
@@ -166,6 +265,16 @@ pub struct SessionRouteAssignment {
     pub assigned_at_unix_ms: u64,
 }
 
+/// Last provider a session was routed to, persisted so sticky routing survives restarts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SessionLastRoute {
+    pub session_id: String,
+    pub provider: String,
+    pub reason: String,
+    pub preferred: String,
+    pub unix_ms: u64,
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 enum EventDailyCountBucketKind {
     ProviderCodePerMinute,
@@ -250,6 +359,7 @@ define_event_codes! {
     APP_UI_RECOVERED => ("info", "app.ui_recovered"),
     APP_UI_UNRESPONSIVE => ("warning", "app.ui_unresponsive"),
     CODEX_CLI_AUTH_CONFIG_SWAPPED => ("info", "codex.cli_auth_config_swapped"),
+    CODEX_CLI_HOME_RESTORED => ("info", "codex.cli_home_restored"),
     CODEX_PROVIDER_SWITCHBOARD_BASE_META_SAVE_FAILED => ("error", "codex.provider_switchboard.base_meta_save_failed"),
     CODEX_PROVIDER_SWITCHBOARD_BASE_SAVE_FAILED => ("error", "codex.provider_switchboard.base_save_failed"),
     CODEX_PROVIDER_SWITCHBOARD_GATEWAY_TOKEN_SYNC_FAILED => ("error", "codex.provider_switchboard.gateway_token_sync_failed"),
@@ -262,11 +372,15 @@ define_event_codes! {
     CONFIG_FOLLOWED_SOURCE_SNAPSHOT_MISSING => ("warning", "config.followed_source_snapshot_missing"),
     CONFIG_FOLLOWED_SOURCE_UPDATED => ("info", "config.followed_source_updated"),
     CONFIG_PREFERRED_PROVIDER_UPDATED => ("info", "config.preferred_provider_updated"),
+    CONFIG_PRICING_IMPORTED => ("info", "config.pricing_imported"),
+    CONFIG_PRICING_PERIOD_SPLIT => ("info", "config.pricing_period_split"),
     CONFIG_PROVIDER_ACCOUNT_EMAIL_CLEARED => ("info", "config.provider_account_email_cleared"),
     CONFIG_PROVIDER_ACCOUNT_EMAIL_UPDATED => ("info", "config.provider_account_email_updated"),
+    CONFIG_PROVIDER_BASE_URL_SUSPICIOUS => ("warning", "config.provider_base_url_suspicious"),
     CONFIG_PROVIDER_COPIED_FROM_SOURCE => ("info", "config.provider_copied_from_source"),
     CONFIG_PROVIDER_ACTIVATED => ("info", "config.provider_activated"),
     CONFIG_PROVIDER_DEACTIVATED => ("info", "config.provider_deactivated"),
+    CONFIG_PROVIDER_DEFAULT_MODEL_UPDATED => ("info", "config.provider_default_model_updated"),
     CONFIG_PROVIDER_DELETED => ("info", "config.provider_deleted"),
     CONFIG_PROVIDER_DISABLED_AFTER_PACKAGE_EXPIRY => ("warning", "config.provider_disabled_after_package_expiry"),
     CONFIG_PROVIDER_GAP_FILL_CLEARED => ("info", "config.provider_gap_fill_cleared"),
@@ -276,6 +390,9 @@ define_event_codes! {
     CONFIG_PROVIDER_KEY_CLEARED => ("info", "config.provider_key_cleared"),
     CONFIG_PROVIDER_KEY_UPDATED => ("info", "config.provider_key_updated"),
     CONFIG_PROVIDER_LINKED_FROM_SOURCE => ("info", "config.provider_linked_from_source"),
+    CONFIG_PROVIDER_MODEL_PRICING_CLEARED => ("info", "config.provider_model_pricing_cleared"),
+    CONFIG_PROVIDER_MODEL_PRICING_UPDATED => ("info", "config.provider_model_pricing_updated"),
+    CONFIG_PROVIDER_NOTES_UPDATED => ("info", "config.provider_notes_updated"),
     CONFIG_PROVIDER_ORDER_UPDATED => ("info", "config.provider_order_updated"),
     CONFIG_PROVIDER_PRICING_CLEARED => ("info", "config.provider_pricing_cleared"),
     CONFIG_PROVIDER_PRICING_UPDATED => ("info", "config.provider_pricing_updated"),
@@ -283,18 +400,25 @@ define_event_codes! {
     CONFIG_PROVIDER_RENAMED => ("info", "config.provider_renamed"),
     CONFIG_PROVIDER_SCHEDULE_UPDATED => ("info", "config.provider_schedule_updated"),
     CONFIG_PROVIDER_SUPPORTS_WEBSOCKETS_UPDATED => ("info", "config.provider_supports_websockets_updated"),
+    CONFIG_PROVIDER_SYSTEM_PREFIX_UPDATED => ("info", "config.provider_system_prefix_updated"),
     CONFIG_PROVIDER_TIMELINE_UPDATED => ("info", "config.provider_timeline_updated"),
     CONFIG_PROVIDER_UPSERTED => ("info", "config.provider_upserted"),
+    CONFIG_PROVIDERS_MERGED => ("info", "config.providers_merged"),
     CONFIG_ROUTE_MODE_UPDATED => ("info", "config.route_mode_updated"),
     CONFIG_SESSION_PREFERRED_PROVIDER_CLEARED => ("info", "config.session_preferred_provider_cleared"),
     CONFIG_SESSION_PREFERRED_PROVIDER_UPDATED => ("info", "config.session_preferred_provider_updated"),
+    CONFIG_SWITCHBOARD_PROFILE_ADDED => ("info", "config.switchboard_profile_added"),
+    CONFIG_SWITCHBOARD_PROFILE_REMOVED => ("info", "config.switchboard_profile_removed"),
     CONFIG_USAGE_AUTH_CLEARED => ("info", "config.usage_auth_cleared"),
     CONFIG_USAGE_AUTH_UPDATED => ("info", "config.usage_auth_updated"),
     CONFIG_USAGE_BASE_URL_CLEARED => ("info", "config.usage_base_url_cleared"),
     CONFIG_USAGE_BASE_URL_UPDATED => ("info", "config.usage_base_url_updated"),
+    CONFIG_USAGE_LABEL_CLEARED => ("info", "config.usage_label_cleared"),
+    CONFIG_USAGE_LABEL_UPDATED => ("info", "config.usage_label_updated"),
     CONFIG_USAGE_PROXY_POOL_UPDATED => ("info", "config.usage_proxy_pool_updated"),
     CONFIG_USAGE_TOKEN_CLEARED => ("info", "config.usage_token_cleared"),
     CONFIG_USAGE_TOKEN_UPDATED => ("info", "config.usage_token_updated"),
+    GATEWAY_CORS_ENABLED => ("info", "gateway.cors_enabled"),
     GATEWAY_LISTEN_PORT_REASSIGNED => ("warning", "gateway.listen_port_reassigned"),
     GATEWAY_PREVIOUS_RESPONSE_ID_PRESENT => ("debug", "gateway.previous_response_id_present"),
     GATEWAY_REQUEST_PARSE_ERROR => ("error", "gateway.request_parse_error"),
@@ -303,11 +427,15 @@ define_event_codes! {
     GATEWAY_RUNTIME_LISTENER_FAILED => ("warning", "gateway.runtime_listener_failed"),
     #[cfg_attr(not(target_os = "windows"), allow(dead_code))]
     GATEWAY_RUNTIME_LISTENER_SKIPPED => ("info", "gateway.runtime_listener_skipped"),
+    GATEWAY_SHUTDOWN => ("info", "gateway.shutdown"),
     GATEWAY_STREAM_FALLBACK_TO_NON_STREAM => ("warning", "gateway.stream_fallback_to_non_stream"),
+    GATEWAY_TOKEN_CREATED => ("info", "gateway.token_created"),
+    GATEWAY_TOKEN_REVOKED => ("info", "gateway.token_revoked"),
     GATEWAY_UPSTREAM_RETRY => ("warning", "gateway.upstream_retry"),
     GATEWAY_WEBSOCKET_FALLBACK_TO_HTTP => ("warning", "gateway.websocket_fallback_to_http"),
     HEALTH_PROBE_FAILED => ("error", "health.probe_failed"),
     HEALTH_PROBE_OK => ("info", "health.probe_ok"),
+    HEALTH_RECOVERED => ("info", "health.recovered"),
     LAN_EDIT_SYNC_APPLIED => ("info", "lan.edit_sync_applied"),
     LAN_EDIT_SYNC_ENTITY_DOMAIN_MISSING => ("warning", "lan.edit_sync_entity_domain_missing"),
     LAN_EDIT_SYNC_HINT_IGNORED_UNKNOWN_PEER => ("debug", "lan.edit_sync_hint_ignored_unknown_peer"),
@@ -349,18 +477,29 @@ define_event_codes! {
     ROUTING_BALANCED_REASSIGN_ON_REOPEN => ("info", "routing.balanced_reassign_on_reopen"),
     ROUTING_BALANCED_REASSIGN_ON_SESSION_TOPOLOGY_CHANGE => ("info", "routing.balanced_reassign_on_session_topology_change"),
     ROUTING_CLOSED_AFTER_FAILURE_USAGE_REFRESH => ("warning", "routing.closed_after_failure_usage_refresh"),
+    ROUTING_DEFAULT_MODEL_APPLIED => ("info", "routing.default_model_applied"),
     ROUTING_MANUAL_OVERRIDE_CHANGED => ("info", "routing.manual_override_changed"),
     ROUTING_MODEL_MISMATCH => ("warning", "routing.model_mismatch"),
+    ROUTING_PEER_REJECTED => ("warning", "routing.peer_rejected"),
+    ROUTING_REQUEST_TOO_LARGE => ("warning", "routing.request_too_large"),
     ROUTING_ROUTE => ("info", "routing.route"),
+    ROUTING_SESSION_REEVALUATED => ("info", "routing.session_reevaluated"),
+    ROUTING_SIMULATED_FAILURE => ("warning", "routing.simulated_failure"),
     ROUTING_STREAM => ("info", "routing.stream"),
     ROUTING_USAGE_REFRESH_UNCONFIRMED_AFTER_FAILURE => ("warning", "routing.usage_refresh_unconfirmed_after_failure"),
+    SESSION_FORGOTTEN => ("info", "session.forgotten"),
+    STORE_EVENTS_PRUNED => ("info", "store.events_pruned"),
+    STORE_EVICTED => ("warning", "store.evicted"),
+    STORE_MANUAL_BACKUP_CREATED => ("info", "store.manual_backup_created"),
     STREAM_IDLE_TIMEOUT => ("error", "stream.idle_timeout"),
     STREAM_READ_ERROR => ("error", "stream.read_error"),
+    SYSTEM_CLOCK_REGRESSION => ("warning", "system.clock_regression"),
     TEST_PROFILE_BULK_EVENT => ("info", "test_profile.bulk_event"),
     TEST_PROFILE_MOCK_SEEDED => ("info", "test_profile.mock_seeded"),
     UPSTREAM_HTTP_ERROR => ("error", "upstream.http_error"),
     UPSTREAM_INVALID_REQUEST => ("warning", "upstream.invalid_request"),
     UPSTREAM_REQUEST_ERROR => ("error", "upstream.request_error"),
+    USAGE_DAY_AGGREGATES_REBUILT => ("info", "usage.day_aggregates_rebuilt"),
     USAGE_REFRESH_FAILED => ("error", "usage.refresh_failed"),
     USAGE_REFRESH_FORWARDED => ("info", "usage.refresh_forwarded"),
     USAGE_REFRESH_PARTIAL => ("error", "usage.refresh_partial"),
@@ -368,9 +507,14 @@ define_event_codes! {
     USAGE_REFRESH_SHARED_APPLIED => ("info", "usage.refresh_shared_applied"),
     USAGE_REFRESH_SUCCEEDED => ("info", "usage.refresh_succeeded"),
     USAGE_REFRESH_SUCCEEDED_SUMMARY => ("info", "usage.refresh_succeeded_summary"),
+    USAGE_REQUESTS_BUFFER_OVERFLOW => ("warning", "usage.requests_buffer_overflow"),
+    USAGE_REQUESTS_DROPPED => ("error", "usage.requests_dropped"),
     USAGE_SPEND_HISTORY_ENTRY_CLEARED => ("info", "usage.spend_history_entry_cleared"),
     USAGE_SPEND_HISTORY_ENTRY_UPDATED => ("info", "usage.spend_history_entry_updated"),
+    USAGE_SPEND_HISTORY_RANGE_CLEARED => ("info", "usage.spend_history_range_cleared"),
+    USAGE_SPEND_HISTORY_RANGE_UPDATED => ("info", "usage.spend_history_range_updated"),
     USAGE_TRACKED_SPEND_HISTORY_ENTRIES_REMOVED => ("warning", "usage.tracked_spend_history_entries_removed"),
+    USAGE_UNPARSED => ("warning", "usage.unparsed"),
 }
 
 impl<'a> EventReporter<'a> {
@@ -395,7 +539,8 @@ impl<'a> EventReporter<'a> {
     }
 
     pub fn emit(self, provider: &str, event_code: EventCode, message: &str, fields: Value) {
-        self.emit_at_unix_ms(provider, event_code, message, fields, unix_ms());
+        let unix_ms = self.store.recorded_unix_ms();
+        self.emit_at_unix_ms(provider, event_code, message, fields, unix_ms);
     }
 
     pub fn emit_at_unix_ms(
@@ -419,6 +564,7 @@ impl<'a> EventReporter<'a> {
 
 define_scoped_event_methods!(CodexEventReporter {
     cli_auth_config_swapped => CODEX_CLI_AUTH_CONFIG_SWAPPED,
+    cli_home_restored => CODEX_CLI_HOME_RESTORED,
     provider_switchboard_gateway_token_sync_failed => CODEX_PROVIDER_SWITCHBOARD_GATEWAY_TOKEN_SYNC_FAILED,
     provider_switchboard_rename_sync_failed => CODEX_PROVIDER_SWITCHBOARD_RENAME_SYNC_FAILED,
     provider_switchboard_sync_failed => CODEX_PROVIDER_SWITCHBOARD_SYNC_FAILED,
@@ -430,24 +576,33 @@ define_scoped_event_methods!(ConfigEventReporter {
     followed_source_snapshot_missing => CONFIG_FOLLOWED_SOURCE_SNAPSHOT_MISSING,
     followed_source_updated => CONFIG_FOLLOWED_SOURCE_UPDATED,
     preferred_provider_updated => CONFIG_PREFERRED_PROVIDER_UPDATED,
+    pricing_period_split => CONFIG_PRICING_PERIOD_SPLIT,
     provider_account_email_cleared => CONFIG_PROVIDER_ACCOUNT_EMAIL_CLEARED,
     provider_account_email_updated => CONFIG_PROVIDER_ACCOUNT_EMAIL_UPDATED,
     provider_copied_from_source => CONFIG_PROVIDER_COPIED_FROM_SOURCE,
     provider_activated => CONFIG_PROVIDER_ACTIVATED,
     provider_deactivated => CONFIG_PROVIDER_DEACTIVATED,
+    provider_default_model_updated => CONFIG_PROVIDER_DEFAULT_MODEL_UPDATED,
     provider_deleted => CONFIG_PROVIDER_DELETED,
     provider_group_bulk_updated => CONFIG_PROVIDER_GROUP_BULK_UPDATED,
     provider_group_updated => CONFIG_PROVIDER_GROUP_UPDATED,
     provider_key_cleared => CONFIG_PROVIDER_KEY_CLEARED,
     provider_key_updated => CONFIG_PROVIDER_KEY_UPDATED,
     provider_linked_from_source => CONFIG_PROVIDER_LINKED_FROM_SOURCE,
+    provider_notes_updated => CONFIG_PROVIDER_NOTES_UPDATED,
     provider_order_updated => CONFIG_PROVIDER_ORDER_UPDATED,
     provider_renamed => CONFIG_PROVIDER_RENAMED,
     provider_upserted => CONFIG_PROVIDER_UPSERTED,
+    providers_merged => CONFIG_PROVIDERS_MERGED,
     provider_supports_websockets_updated => CONFIG_PROVIDER_SUPPORTS_WEBSOCKETS_UPDATED,
+    provider_system_prefix_updated => CONFIG_PROVIDER_SYSTEM_PREFIX_UPDATED,
     route_mode_updated => CONFIG_ROUTE_MODE_UPDATED,
     session_preferred_provider_cleared => CONFIG_SESSION_PREFERRED_PROVIDER_CLEARED,
     session_preferred_provider_updated => CONFIG_SESSION_PREFERRED_PROVIDER_UPDATED,
+    switchboard_profile_added => CONFIG_SWITCHBOARD_PROFILE_ADDED,
+    switchboard_profile_removed => CONFIG_SWITCHBOARD_PROFILE_REMOVED,
+    usage_label_cleared => CONFIG_USAGE_LABEL_CLEARED,
+    usage_label_updated => CONFIG_USAGE_LABEL_UPDATED,
 });
 
 define_scoped_event_methods!(LanEventReporter {
@@ -463,7 +618,12 @@ define_scoped_event_methods!(LanEventReporter {
 });
 
 define_scoped_event_methods!(RoutingEventReporter {
+    default_model_applied => ROUTING_DEFAULT_MODEL_APPLIED,
     manual_override_changed => ROUTING_MANUAL_OVERRIDE_CHANGED,
+    peer_rejected => ROUTING_PEER_REJECTED,
+    request_too_large => ROUTING_REQUEST_TOO_LARGE,
+    session_reevaluated => ROUTING_SESSION_REEVALUATED,
+    simulated_failure => ROUTING_SIMULATED_FAILURE,
 });
 
 impl<'a> AppEventReporter<'a> {
@@ -528,6 +688,7 @@ impl Store {
     }
 
     const MAX_DB_BYTES: u64 = 64 * 1024 * 1024; // 64 MiB, best-effort cap via compaction
+    const MAX_HEALTH_TRANSITIONS_PER_PROVIDER: usize = 1000;
     const EVENTS_SQLITE_SCHEMA_VERSION: &'static str = "1";
     const EVENTS_SQLITE_MIGRATED_FROM_SLED_KEY: &'static str = "migrated_from_sled_v1";
     const EVENTS_SQLITE_MERGED_LEGACY_SQLITE_KEY: &'static str = "merged_legacy_sqlite_v1";
@@ -539,9 +700,15 @@ impl Store {
         "usage_requests_migrated_from_sled_v1";
     const SPEND_HISTORY_SQLITE_MIGRATED_FROM_SLED_KEY: &'static str =
         "spend_history_sqlite_migrated_from_sled_v1";
+    // Newest `unix_ms` among raw usage rows dropped by the size cap. Days up to this one may be
+    // only partly present in `usage_requests`, so their `usage_day` aggregates are not rebuilt.
+    const USAGE_REQUESTS_EVICTED_THROUGH_KEY: &'static str =
+        "usage_requests_evicted_through_unix_ms";
 
-    fn allowed_key_prefixes() -> [&'static [u8]; 10] {
+    fn allowed_key_prefixes() -> [&'static [u8]; 12] {
         [
+            b"audit:",
+            b"health_transition:",
             b"event:",
             b"event_day:",
             b"metrics:",
@@ -640,8 +807,12 @@ impl Store {
         trace("store_events_sqlite_open_ok", None);
         let store = Self {
             db,
+            sled_path: path.to_path_buf(),
             events_db_path,
             events_db: Arc::new(Mutex::new(events_db)),
+            pending_usage_requests: Arc::new(Mutex::new(Vec::new())),
+            unparsed_usage_reported_at: Arc::new(Mutex::new(std::collections::HashMap::new())),
+            clock_high_water_unix_ms: Arc::new(std::sync::atomic::AtomicU64::new(0)),
         };
         trace("store_events_schema_start", None);
         store
@@ -678,6 +849,76 @@ impl Store {
         Ok(store)
     }
 
+    /// Writes buffered usage rows, flushes sled (metrics, ledger) and truncates the SQLite WAL.
+    ///
+    /// Returns the number of buffered usage rows written. Safe to call at any time. sled and the
+    /// WAL are flushed either way, but buffered rows that could not be written are an error.
+    pub fn flush_to_disk(&self) -> anyhow::Result<usize> {
+        let usage_requests_flushed = self.flush_usage_requests();
+        self.db.flush()?;
+        let conn = self.events_db.lock();
+        conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")?;
+        drop(conn);
+        let still_buffered = self.pending_usage_requests.lock().len();
+        if still_buffered > 0 {
+            anyhow::bail!("{still_buffered} usage request rows are still buffered after flushing");
+        }
+        Ok(usage_requests_flushed)
+    }
+
+    /// Snapshots the sled tree and the events database into `sled.manual-backup.<ts>[-label]`
+    /// next to the sled dir, where the event log already looks for backups. The WAL is
+    /// checkpointed under the events lock so the copied database is complete.
+    pub fn create_manual_backup(&self, label: Option<&str>) -> anyhow::Result<PathBuf> {
+        let label: String = label
+            .unwrap_or_default()
+            .trim()
+            .chars()
+            .map(|c| {
+                if c.is_ascii_alphanumeric() || c == '-' || c == '_' {
+                    c
+                } else {
+                    '-'
+                }
+            })
+            .take(MANUAL_BACKUP_LABEL_MAX_CHARS)
+            .collect();
+        let label = label.trim_matches('-');
+        let mut dir_name = format!("sled.manual-backup.{}", unix_ms());
+        if !label.is_empty() {
+            dir_name.push('-');
+            dir_name.push_str(label);
+        }
+        let parent = self.sled_path.parent().unwrap_or_else(|| Path::new("."));
+        let backup_dir = parent.join(dir_name);
+        if backup_dir.exists() {
+            anyhow::bail!("backup already exists: {}", backup_dir.display());
+        }
+
+        self.flush_usage_requests();
+        {
+            let backup = sled::open(&backup_dir)?;
+            for entry in self.db.iter() {
+                let (key, value) = entry?;
+                backup.insert(key, value)?;
+            }
+            backup.flush()?;
+        }
+
+        let conn = self.events_db.lock();
+        conn.execute_batch("PRAGMA wal_checkpoint(TRUNCATE);")?;
+        let events_backup_path = backup_dir.join("events.sqlite3");
+        std::fs::copy(&self.events_db_path, &events_backup_path)?;
+        for suffix in ["-wal", "-shm"] {
+            let src = Self::sqlite_sidecar_path(&self.events_db_path, suffix);
+            let dst = Self::sqlite_sidecar_path(&events_backup_path, suffix);
+            if !Self::copy_file_if_exists(&src, &dst) {
+                anyhow::bail!("failed to copy {}", src.display());
+            }
+        }
+        Ok(backup_dir)
+    }
+
     fn sqlite_sidecar_path(path: &Path, suffix: &str) -> PathBuf {
         let mut out = path.as_os_str().to_os_string();
         out.push(suffix);
@@ -751,7 +992,10 @@ impl Store {
               output_tokens INTEGER NOT NULL,
               total_tokens INTEGER NOT NULL,
               cache_creation_input_tokens INTEGER NOT NULL,
-              cache_read_input_tokens INTEGER NOT NULL
+              cache_read_input_tokens INTEGER NOT NULL,
+              label TEXT NOT NULL DEFAULT '',
+              request_bytes INTEGER NOT NULL DEFAULT 0,
+              response_bytes INTEGER NOT NULL DEFAULT 0
             );
             CREATE INDEX IF NOT EXISTS idx_usage_requests_unix_ms ON usage_requests(unix_ms DESC);
             CREATE INDEX IF NOT EXISTS idx_usage_requests_provider ON usage_requests(provider);
@@ -965,6 +1209,19 @@ impl Store {
               ON session_route_assignments(provider);
             CREATE INDEX IF NOT EXISTS idx_session_route_assignments_assigned_at
               ON session_route_assignments(assigned_at_unix_ms DESC);
+            CREATE TABLE IF NOT EXISTS session_last_routes(
+              session_id TEXT PRIMARY KEY,
+              provider TEXT NOT NULL,
+              reason TEXT NOT NULL,
+              preferred TEXT NOT NULL,
+              unix_ms INTEGER NOT NULL
+            );
+            CREATE TABLE IF NOT EXISTS usage_failures(
+              id INTEGER PRIMARY KEY AUTOINCREMENT,
+              provider TEXT NOT NULL,
+              unix_ms INTEGER NOT NULL
+            );
+            CREATE INDEX IF NOT EXISTS idx_usage_failures_unix_ms ON usage_failures(unix_ms DESC);
             ",
         )?;
         trace("store_events_schema_ddl_ok", None);
@@ -1089,6 +1346,7 @@ impl Store {
     }
 
     fn with_events_read_conn<T>(&self, f: impl FnOnce(&rusqlite::Connection) -> T) -> T {
+        self.flush_usage_requests();
         match self.open_events_read_connection() {
             Ok(conn) => f(&conn),
             Err(_) => {
@@ -1133,6 +1391,22 @@ impl Store {
                 [],
             )?;
         }
+        if !columns.contains("label") {
+            conn.execute(
+                "ALTER TABLE usage_requests ADD COLUMN label TEXT NOT NULL DEFAULT ''",
+                [],
+            )?;
+        }
+        for column in ["request_bytes", "response_bytes"] {
+            if !columns.contains(column) {
+                conn.execute(
+                    &format!(
+                        "ALTER TABLE usage_requests ADD COLUMN {column} INTEGER NOT NULL DEFAULT 0"
+                    ),
+                    [],
+                )?;
+            }
+        }
         conn.execute(
             "CREATE INDEX IF NOT EXISTS idx_usage_requests_ingested_at_id
              ON usage_requests(ingested_at_unix_ms ASC, id ASC)",
@@ -1639,7 +1913,7 @@ impl Store {
         Some(dt.format("%Y-%m-%d").to_string())
     }
 
-    fn day_start_unix_ms_from_day_key(day_key: &str) -> Option<u64> {
+    pub(crate) fn day_start_unix_ms_from_day_key(day_key: &str) -> Option<u64> {
         let date = chrono::NaiveDate::parse_from_str(day_key, "%Y-%m-%d").ok()?;
         let dt = Local
             .with_ymd_and_hms(date.year(), date.month(), date.day(), 0, 0, 0)
@@ -1647,7 +1921,7 @@ impl Store {
         u64::try_from(dt.timestamp_millis()).ok()
     }
 
-    fn parse_event_key_id(key: &[u8]) -> Option<String> {
+    pub(crate) fn parse_event_key_id(key: &[u8]) -> Option<String> {
         let body = key.strip_prefix(b"event:")?;
         let split_at = body.iter().position(|b| *b == b':')?;
         let id_bytes = body.get(split_at + 1..)?;
@@ -2692,16 +2966,15 @@ impl Store {
             total_tokens,
             false,
         );
-        self.add_usage_request(
-            provider,
-            &Self::model_for_usage(response_obj, model_override),
-            increments,
-            context,
-        );
+        let model = Self::model_for_usage(response_obj, model_override);
+        self.report_unparsed_usage(provider, &model, response_obj);
+        super::otel::record_usage(provider, &model, input_tokens, output_tokens, total_tokens);
+        self.add_usage_request(provider, &model, increments, context);
     }
 
     pub fn record_failure(&self, provider: &str) {
         self.bump_metrics(provider, 0, 1, 0, true);
+        self.add_usage_failure(provider);
     }
 
     pub fn get_metrics(&self) -> serde_json::Value {
@@ -2913,6 +3186,95 @@ impl Store {
             .unwrap_or(0)
     }
 
+    pub fn list_session_last_routes_since(&self, min_unix_ms: u64) -> Vec<SessionLastRoute> {
+        let Ok(min_unix_ms_i64) = i64::try_from(min_unix_ms) else {
+            return Vec::new();
+        };
+        let conn = self.events_db.lock();
+        let Ok(mut stmt) = conn.prepare(
+            "SELECT session_id, provider, reason, preferred, unix_ms
+             FROM session_last_routes
+             WHERE unix_ms >= ?1",
+        ) else {
+            return Vec::new();
+        };
+        let Ok(rows) = stmt.query_map([min_unix_ms_i64], |row| {
+            Ok(SessionLastRoute {
+                session_id: row.get::<_, String>(0)?,
+                provider: row.get::<_, String>(1)?,
+                reason: row.get::<_, String>(2)?,
+                preferred: row.get::<_, String>(3)?,
+                unix_ms: u64::try_from(row.get::<_, i64>(4)?).unwrap_or(0),
+            })
+        }) else {
+            return Vec::new();
+        };
+        rows.flatten().collect()
+    }
+
+    pub fn put_session_last_route(&self, route: &SessionLastRoute) {
+        let Ok(unix_ms_i64) = i64::try_from(route.unix_ms) else {
+            return;
+        };
+        let conn = self.events_db.lock();
+        let _ = conn.execute(
+            "INSERT INTO session_last_routes(session_id, provider, reason, preferred, unix_ms)
+             VALUES(?1, ?2, ?3, ?4, ?5)
+             ON CONFLICT(session_id) DO UPDATE SET
+               provider=excluded.provider,
+               reason=excluded.reason,
+               preferred=excluded.preferred,
+               unix_ms=excluded.unix_ms",
+            params![
+                route.session_id,
+                route.provider,
+                route.reason,
+                route.preferred,
+                unix_ms_i64
+            ],
+        );
+    }
+
+    /// Replaces every persisted session route with `routes` (used after bulk edits).
+    pub fn replace_session_last_routes(&self, routes: &[SessionLastRoute]) {
+        let mut conn = self.events_db.lock();
+        let Ok(tx) = conn.transaction() else {
+            return;
+        };
+        if tx.execute("DELETE FROM session_last_routes", []).is_err() {
+            return;
+        }
+        for route in routes {
+            let Ok(unix_ms_i64) = i64::try_from(route.unix_ms) else {
+                continue;
+            };
+            let _ = tx.execute(
+                "INSERT INTO session_last_routes(session_id, provider, reason, preferred, unix_ms)
+                 VALUES(?1, ?2, ?3, ?4, ?5)",
+                params![
+                    route.session_id,
+                    route.provider,
+                    route.reason,
+                    route.preferred,
+                    unix_ms_i64
+                ],
+            );
+        }
+        let _ = tx.commit();
+    }
+
+    pub fn delete_session_last_routes_before(&self, cutoff_unix_ms: u64) -> usize {
+        let Ok(cutoff_i64) = i64::try_from(cutoff_unix_ms) else {
+            return 0;
+        };
+        let conn = self.events_db.lock();
+        conn.execute(
+            "DELETE FROM session_last_routes WHERE unix_ms < ?1",
+            [cutoff_i64],
+        )
+        .unwrap_or(0)
+    }
+
     pub fn get_ledger(&self, provider: &str) -> Value {
         let key = format!("ledger:{provider}");
         self.db
@@ -2950,12 +3312,171 @@ impl Store {
         let _ = self.db.flush();
     }
 
+    // Audit keys are `audit:{unix_ms:020}:{uuid}` so a prefix scan is already time-ordered.
+    pub fn record_config_audit(&self, action: &str, target: &str, old: Value, new: Value) {
+        self.record_config_audit_at_unix_ms(action, target, old, new, unix_ms());
+    }
+
+    fn record_config_audit_at_unix_ms(
+        &self,
+        action: &str,
+        target: &str,
+        old: Value,
+        new: Value,
+        ts: u64,
+    ) {
+        let id = uuid::Uuid::new_v4().to_string();
+        let key = format!("audit:{ts:020}:{id}");
+        let v = serde_json::json!({
+            "id": id,
+            "unix_ms": ts,
+            "action": action,
+            "target": target,
+            "old": old,
+            "new": new,
+        });
+        let _ = self
+            .db
+            .insert(key.as_bytes(), serde_json::to_vec(&v).unwrap_or_default());
+        let _ = self.db.flush();
+    }
+
+    /// Newest first, bounded by the inclusive `[from_unix_ms, to_unix_ms]` window.
+    pub fn list_config_audit(
+        &self,
+        from_unix_ms: Option<u64>,
+        to_unix_ms: Option<u64>,
+        limit: usize,
+    ) -> Vec<Value> {
+        let from = from_unix_ms.unwrap_or(0);
+        let to = to_unix_ms.unwrap_or(u64::MAX);
+        let mut out: Vec<Value> = Vec::new();
+        if limit == 0 || from > to {
+            return out;
+        }
+        for (k, v) in self.db.scan_prefix(b"audit:").rev().flatten() {
+            let key = String::from_utf8_lossy(&k);
+            let Some(ts) = key
+                .strip_prefix("audit:")
+                .and_then(|rest| rest.split(':').next())
+                .and_then(|ts| ts.parse::<u64>().ok())
+            else {
+                continue;
+            };
+            if ts > to {
+                continue;
+            }
+            if ts < from {
+                break;
+            }
+            if let Ok(entry) = serde_json::from_slice::<Value>(&v) {
+                out.push(entry);
+                if out.len() >= limit {
+                    break;
+                }
+            }
+        }
+        out
+    }
+
+    // Health transition keys are `health_transition:{provider}:{unix_ms:020}:{uuid}` so one
+    // provider's history is a time-ordered prefix scan.
+    pub fn record_health_transition(
+        &self,
+        provider: &str,
+        from_state: &str,
+        to_state: &str,
+        reason: &str,
+        ts: u64,
+    ) {
+        let id = uuid::Uuid::new_v4().to_string();
+        let key = format!("health_transition:{provider}:{ts:020}:{id}");
+        let v = serde_json::json!({
+            "provider": provider,
+            "from_state": from_state,
+            "to_state": to_state,
+            "unix_ms": ts,
+            "reason": reason,
+        });
+        let _ = self
+            .db
+            .insert(key.as_bytes(), serde_json::to_vec(&v).unwrap_or_default());
+        self.prune_health_transitions(provider, Self::MAX_HEALTH_TRANSITIONS_PER_PROVIDER);
+    }
+
+    /// `provider`'s transition keys, oldest first. A provider whose name extends this one shares
+    /// the scan prefix; its keys have no timestamp right after it and are skipped.
+    fn health_transition_entries(&self, provider: &str) -> Vec<(u64, sled::IVec, sled::IVec)> {
+        let prefix = format!("health_transition:{provider}:");
+        self.db
+            .scan_prefix(prefix.as_bytes())
+            .flatten()
+            .filter_map(|(k, v)| {
+                let ts = std::str::from_utf8(&k)
+                    .ok()?
+                    .strip_prefix(prefix.as_str())?
+                    .split(':')
+                    .next()?
+                    .parse::<u64>()
+                    .ok()?;
+                Some((ts, k, v))
+            })
+            .collect()
+    }
+
+    /// Drops `provider`'s oldest transitions so at most `keep` remain.
+    fn prune_health_transitions(&self, provider: &str, keep: usize) {
+        let entries = self.health_transition_entries(provider);
+        let excess = entries.len().saturating_sub(keep);
+        for (_, key, _) in entries.into_iter().take(excess) {
+            let _ = self.db.remove(key);
+        }
+    }
+
+    /// Moves `from`'s transitions under `to`, interleaving by time with any `to` already has.
+    fn move_health_transitions(&self, from: &str, to: &str) {
+        let old_prefix_len = format!("health_transition:{from}:").len();
+        for (_, old_key, value) in self.health_transition_entries(from) {
+            let mut new_key = format!("health_transition:{to}:").into_bytes();
+            new_key.extend_from_slice(&old_key[old_prefix_len..]);
+            let mut value = value.to_vec();
+            if let Ok(mut entry) = serde_json::from_slice::<Value>(&value) {
+                if let Some(object) = entry.as_object_mut() {
+                    object.insert("provider".to_string(), Value::String(to.to_string()));
+                }
+                value = serde_json::to_vec(&entry).unwrap_or(value);
+            }
+            let _ = self.db.insert(new_key, value);
+            let _ = self.db.remove(old_key);
+        }
+        self.prune_health_transitions(to, Self::MAX_HEALTH_TRANSITIONS_PER_PROVIDER);
+    }
+
+    /// Oldest first, bounded by the inclusive `[from_unix_ms, to_unix_ms]` window.
+    pub fn list_health_transitions(
+        &self,
+        provider: &str,
+        from_unix_ms: Option<u64>,
+        to_unix_ms: Option<u64>,
+    ) -> Vec<Value> {
+        let from = from_unix_ms.unwrap_or(0);
+        let to = to_unix_ms.unwrap_or(u64::MAX);
+        if from > to {
+            return Vec::new();
+        }
+        self.health_transition_entries(provider)
+            .into_iter()
+            .filter(|(ts, _, _)| (from..=to).contains(ts))
+            .filter_map(|(_, _, v)| serde_json::from_slice::<Value>(&v).ok())
+            .collect()
+    }
+
     pub fn rename_provider(&self, old: &str, new: &str) {
+        self.flush_usage_requests();
         if old == new {
             return;
         }
 
-        let new_provider = new.to_string();
         for prefix in ["metrics:", "quota:", "ledger:", "spend_state:"] {
             let old_key = format!("{prefix}{old}");
             if let Ok(Some(v)) = self.db.get(old_key.as_bytes()) {
@@ -2965,32 +3486,7 @@ impl Store {
             }
         }
 
-        let mut usage_req_updates: Vec<(sled::IVec, Vec<u8>)> = Vec::new();
-        for res in self.db.scan_prefix(b"usage_req:") {
-            let Ok((key, value)) = res else {
-                continue;
-            };
-            let Ok(mut request) = serde_json::from_slice::<Value>(&value) else {
-                continue;
-            };
-            let Some(object) = request.as_object_mut() else {
-                continue;
-            };
-            let provider = object
-                .get("provider")
-                .and_then(|v| v.as_str())
-                .unwrap_or("");
-            if provider != old {
-                continue;
-            }
-            object.insert("provider".to_string(), Value::String(new_provider.clone()));
-            if let Ok(encoded) = serde_json::to_vec(&request) {
-                usage_req_updates.push((key, encoded));
-            }
-        }
-        for (key, encoded) in usage_req_updates {
-            let _ = self.db.insert(key, encoded);
-        }
+        self.reassign_legacy_usage_requests(old, new);
         {
             let conn = self.events_db.lock();
             let _ = conn.execute(
@@ -3013,6 +3509,10 @@ impl Store {
                 "UPDATE tracked_spend_days_shared_sources SET provider=?1 WHERE provider=?2",
                 params![new, old],
             );
+            let _ = conn.execute(
+                "UPDATE usage_failures SET provider=?1 WHERE provider=?2",
+                params![new, old],
+            );
         }
 
         for prefix in ["usage_day:"] {
@@ -3039,10 +3539,126 @@ impl Store {
                 let _ = self.db.remove(old_key);
             }
         }
+        self.move_health_transitions(old, new);
+
+        let _ = self.db.flush();
+    }
+
+    /// Folds `source` into an existing `target`: rows that only exist for `source` are
+    /// reassigned, counters that exist for both are summed, and `target` keeps its own quota
+    /// and spend tracking state.
+    pub fn merge_provider(&self, source: &str, target: &str) {
+        self.flush_usage_requests();
+        if source == target {
+            return;
+        }
+
+        for prefix in ["metrics:", "ledger:"] {
+            let source_key = format!("{prefix}{source}");
+            let Ok(Some(source_value)) = self.db.remove(source_key.as_bytes()) else {
+                continue;
+            };
+            let target_key = format!("{prefix}{target}");
+            let merged = match self.db.get(target_key.as_bytes()).ok().flatten() {
+                Some(target_value) => merge_counter_json(&target_value, &source_value),
+                None => source_value.to_vec(),
+            };
+            let _ = self.db.insert(target_key.as_bytes(), merged);
+        }
+        for prefix in ["quota:", "spend_state:"] {
+            let source_key = format!("{prefix}{source}");
+            let Ok(Some(source_value)) = self.db.remove(source_key.as_bytes()) else {
+                continue;
+            };
+            let target_key = format!("{prefix}{target}");
+            let _ = self.db.compare_and_swap(
+                target_key.as_bytes(),
+                None as Option<&[u8]>,
+                Some(source_value),
+            );
+        }
+
+        self.reassign_legacy_usage_requests(source, target);
+        self.move_health_transitions(source, target);
+
+        let source_prefix = format!("usage_day:{source}:");
+        let source_days: Vec<(sled::IVec, sled::IVec)> = self
+            .db
+            .scan_prefix(source_prefix.as_bytes())
+            .flatten()
+            .collect();
+        for (source_key, source_value) in source_days {
+            let day_key = &source_key.as_ref()[source_prefix.len()..];
+            let mut target_key = format!("usage_day:{target}:").into_bytes();
+            target_key.extend_from_slice(day_key);
+            let mut merged = match self.db.get(&target_key).ok().flatten() {
+                Some(target_value) => merge_counter_json(&target_value, &source_value),
+                None => source_value.to_vec(),
+            };
+            if let Ok(mut row) = serde_json::from_slice::<Value>(&merged) {
+                if let Some(object) = row.as_object_mut() {
+                    object.insert("provider".to_string(), Value::String(target.to_string()));
+                }
+                merged = serde_json::to_vec(&row).unwrap_or(merged);
+            }
+            let _ = self.db.insert(target_key, merged);
+            let _ = self.db.remove(source_key);
+        }
+
+        {
+            let conn = self.events_db.lock();
+            for sql in [
+                "UPDATE usage_requests SET provider=?1 WHERE provider=?2",
+                "UPDATE events SET provider=?1 WHERE provider=?2",
+                "UPDATE usage_failures SET provider=?1 WHERE provider=?2",
+            ] {
+                let _ = conn.execute(sql, params![target, source]);
+            }
+            for (table, key_columns) in [
+                ("spend_days", &["day_started_at_unix_ms"][..]),
+                ("spend_manual_days", &["day_key"][..]),
+                ("tracked_spend_days_shared", &["day_key"][..]),
+                (
+                    "tracked_spend_days_shared_sources",
+                    &["day_key", "source_node_id"][..],
+                ),
+            ] {
+                merge_spend_rows(&conn, table, key_columns, source, target);
+            }
+        }
 
         let _ = self.db.flush();
     }
 
+    fn reassign_legacy_usage_requests(&self, old: &str, new: &str) {
+        let mut usage_req_updates: Vec<(sled::IVec, Vec<u8>)> = Vec::new();
+        for res in self.db.scan_prefix(b"usage_req:") {
+            let Ok((key, value)) = res else {
+                continue;
+            };
+            let Ok(mut request) = serde_json::from_slice::<Value>(&value) else {
+                continue;
+            };
+            let Some(object) = request.as_object_mut() else {
+                continue;
+            };
+            let provider = object
+                .get("provider")
+                .and_then(|v| v.as_str())
+                .unwrap_or("");
+            if provider != old {
+                continue;
+            }
+            object.insert("provider".to_string(), Value::String(new.to_string()));
+            if let Ok(encoded) = serde_json::to_vec(&request) {
+                usage_req_updates.push((key, encoded));
+            }
+        }
+        for (key, encoded) in usage_req_updates {
+            let _ = self.db.insert(key, encoded);
+        }
+    }
+
     fn bump_ledger(
         &self,
         provider: &str,
@@ -3301,6 +3917,210 @@ impl Store {
         })
     }
 
+    /// Size of the sled directory alone; the events DB is subtracted when it lives inside it.
+    fn sled_dir_bytes(&self, events_db_total_bytes: u64) -> u64 {
+        let bytes = dir_size_bytes(&self.sled_path);
+        if self.events_db_path.starts_with(&self.sled_path) {
+            bytes.saturating_sub(events_db_total_bytes)
+        } else {
+            bytes
+        }
+    }
+
+    /// On-disk footprint of the sled directory and events SQLite file (including WAL/SHM),
+    /// plus row counts and the oldest retained timestamp for events and usage requests.
+    pub fn storage_stats(&self) -> Value {
+        self.flush_usage_requests();
+        let file_bytes = |path: &Path| std::fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        let sqlite_bytes = file_bytes(&self.events_db_path);
+        let wal_bytes = file_bytes(&Self::sqlite_sidecar_path(&self.events_db_path, "-wal"));
+        let shm_bytes = file_bytes(&Self::sqlite_sidecar_path(&self.events_db_path, "-shm"));
+        let events_db_total_bytes = sqlite_bytes
+            .saturating_add(wal_bytes)
+            .saturating_add(shm_bytes);
+        let sled_bytes = self.sled_dir_bytes(events_db_total_bytes);
+        let ((event_count, oldest_event_unix_ms), (usage_count, oldest_usage_unix_ms)) = {
+            let conn = self.events_db.lock();
+            let summarize = |table: &str| -> (u64, Option<u64>) {
+                conn.query_row(
+                    &format!("SELECT COUNT(*), MIN(unix_ms) FROM {table}"),
+                    [],
+                    |row| Ok((row.get::<_, i64>(0)?, row.get::<_, Option<i64>>(1)?)),
+                )
+                .map(|(count, oldest)| {
+                    (
+                        u64::try_from(count).unwrap_or(0),
+                        oldest.and_then(|value| u64::try_from(value).ok()),
+                    )
+                })
+                .unwrap_or((0, None))
+            };
+            (summarize("events"), summarize("usage_requests"))
+        };
+        serde_json::json!({
+            "sled": {
+                "path": self.sled_path.display().to_string(),
+                "bytes": sled_bytes,
+            },
+            "events_db": {
+                "path": self.events_db_path.display().to_string(),
+                "bytes": sqlite_bytes,
+                "wal_bytes": wal_bytes,
+                "shm_bytes": shm_bytes,
+                "total_bytes": events_db_total_bytes,
+            },
+            "events": {
+                "count": event_count,
+                "oldest_unix_ms": oldest_event_unix_ms,
+            },
+            "usage_requests": {
+                "count": usage_count,
+                "oldest_unix_ms": oldest_usage_unix_ms,
+            },
+            "total_bytes": sled_bytes.saturating_add(events_db_total_bytes),
+        })
+    }
+
+    /// Deletes events older than `cutoff_unix_ms` unless their level is in `keep_levels`.
+    pub fn prune_events_older_than(
+        &self,
+        cutoff_unix_ms: u64,
+        keep_levels: &[&str],
+    ) -> anyhow::Result<usize> {
+        self.prune_events_older_than_except(cutoff_unix_ms, keep_levels, &[])
+    }
+
+    /// Same as [`Self::prune_events_older_than`], additionally keeping events from `keep_providers`.
+    ///
+    /// Daily counts are rebuilt afterwards so the calendar matches the remaining rows.
+    pub fn prune_events_older_than_except(
+        &self,
+        cutoff_unix_ms: u64,
+        keep_levels: &[&str],
+        keep_providers: &[&str],
+    ) -> anyhow::Result<usize> {
+        let mut sql = "DELETE FROM events WHERE unix_ms < ?".to_string();
+        let mut params: Vec<rusqlite::types::Value> = vec![rusqlite::types::Value::Integer(
+            i64::try_from(cutoff_unix_ms).unwrap_or(i64::MAX),
+        )];
+        for (column, values) in [("level", keep_levels), ("provider", keep_providers)] {
+            if values.is_empty() {
+                continue;
+            }
+            let placeholders = vec!["?"; values.len()].join(", ");
+            sql.push_str(&format!(" AND {column} NOT IN ({placeholders})"));
+            params.extend(
+                values
+                    .iter()
+                    .map(|value| rusqlite::types::Value::Text(value.to_string())),
+            );
+        }
+        let deleted = {
+            let conn = self.events_db.lock();
+            let deleted = conn.execute(&sql, params_from_iter(params.iter()))?;
+            if deleted > 0 {
+                conn.execute(
+                    "INSERT INTO event_meta(key, value) VALUES(?1, '0')
+                     ON CONFLICT(key) DO UPDATE SET value='0'",
+                    [Self::EVENT_DAY_COUNTS_INDEX_VERSION_KEY],
+                )?;
+            }
+            deleted
+        };
+        if deleted > 0 {
+            self.rebuild_event_day_counts_index_if_needed()?;
+        }
+        Ok(deleted)
+    }
+
+    /// Bytes of the events database that hold data (allocated pages minus the freelist), so
+    /// deletes show up before a VACUUM hands the space back to the filesystem.
+    fn events_db_used_bytes(conn: &rusqlite::Connection) -> u64 {
+        let pragma = |name: &str| {
+            conn.query_row(&format!("PRAGMA {name}"), [], |row| row.get::<_, i64>(0))
+                .ok()
+                .and_then(|value| u64::try_from(value).ok())
+                .unwrap_or(0)
+        };
+        pragma("page_count")
+            .saturating_sub(pragma("freelist_count"))
+            .saturating_mul(pragma("page_size"))
+    }
+
+    /// Brings the events database under `max_bytes` (0 disables) by dropping the oldest `info`
+    /// events, then (only with `evict_usage_requests`) the oldest raw usage requests. Non-info
+    /// events and aggregate tables are never touched.
+    ///
+    /// Only the SQLite data pages count against the budget; sled has its own compaction. Freed
+    /// pages are reused by later inserts, so no VACUUM runs under the shared connection lock.
+    pub fn evict_to_size_cap(
+        &self,
+        max_bytes: u64,
+        evict_usage_requests: bool,
+    ) -> anyhow::Result<StoreEviction> {
+        self.flush_usage_requests();
+        const BATCH_ROWS: i64 = 500;
+        let mut eviction = StoreEviction::default();
+        eviction.bytes_before = Self::events_db_used_bytes(&self.events_db.lock());
+        eviction.bytes_after = eviction.bytes_before;
+        if max_bytes == 0 {
+            return Ok(eviction);
+        }
+        while eviction.bytes_after > max_bytes {
+            // Lock per batch so request logging is not stalled for the whole pass.
+            let conn = self.events_db.lock();
+            let mut deleted = conn.execute(
+                "DELETE FROM events WHERE id IN (
+                   SELECT id FROM events WHERE level='info' ORDER BY unix_ms ASC LIMIT ?1
+                 )",
+                [BATCH_ROWS],
+            )?;
+            eviction.events_deleted += deleted;
+            if deleted == 0 && evict_usage_requests {
+                let batch_last_unix_ms: Option<i64> = conn.query_row(
+                    "SELECT MAX(unix_ms) FROM (
+                       SELECT unix_ms FROM usage_requests ORDER BY unix_ms ASC LIMIT ?1
+                     )",
+                    [BATCH_ROWS],
+                    |row| row.get(0),
+                )?;
+                deleted = conn.execute(
+                    "DELETE FROM usage_requests WHERE id IN (
+                       SELECT id FROM usage_requests ORDER BY unix_ms ASC LIMIT ?1
+                     )",
+                    [BATCH_ROWS],
+                )?;
+                eviction.usage_requests_deleted += deleted;
+                if let Some(last_unix_ms) = batch_last_unix_ms.filter(|_| deleted > 0) {
+                    conn.execute(
+                        "INSERT INTO event_meta(key, value) VALUES(?1, ?2)
+                         ON CONFLICT(key) DO UPDATE SET value=CAST(
+                           MAX(CAST(value AS INTEGER), CAST(excluded.value AS INTEGER)) AS TEXT
+                         )",
+                        params![
+                            Self::USAGE_REQUESTS_EVICTED_THROUGH_KEY,
+                            last_unix_ms.to_string()
+                        ],
+                    )?;
+                }
+            }
+            eviction.bytes_after = Self::events_db_used_bytes(&conn);
+            // Nothing evictable is left, so used bytes cannot go any lower.
+            if deleted == 0 {
+                break;
+            }
+        }
+        if eviction.events_deleted > 0 {
+            self.events_db.lock().execute(
+                "INSERT INTO event_meta(key, value) VALUES(?1, '0')
+                 ON CONFLICT(key) DO UPDATE SET value='0'",
+                [Self::EVENT_DAY_COUNTS_INDEX_VERSION_KEY],
+            )?;
+            self.rebuild_event_day_counts_index_if_needed()?;
+        }
+        Ok(eviction)
+    }
+
     pub fn list_event_years(&self) -> std::collections::BTreeSet<i32> {
         let mut years = std::collections::BTreeSet::<i32>::new();
         let conn = self.events_db.lock();
@@ -3389,10 +4209,24 @@ impl Store {
         from_unix_ms: Option<u64>,
         to_unix_ms: Option<u64>,
         limit: Option<usize>,
+    ) -> Vec<Value> {
+        self.list_events_before(from_unix_ms, to_unix_ms, None, limit)
+    }
+
+    /// Events newest first, ordered by `(unix_ms, id)`. `before` is an exclusive
+    /// `(unix_ms, id)` cursor, so pages never skip events that share a millisecond.
+    pub fn list_events_before(
+        &self,
+        from_unix_ms: Option<u64>,
+        to_unix_ms: Option<u64>,
+        before: Option<(u64, &str)>,
+        limit: Option<usize>,
     ) -> Vec<Value> {
         let cap = limit.unwrap_or(usize::MAX).max(1);
         let from_i64 = from_unix_ms.and_then(|x| i64::try_from(x).ok());
         let to_i64 = to_unix_ms.and_then(|x| i64::try_from(x).ok());
+        let before_unix_ms = before.and_then(|(unix_ms, _)| i64::try_from(unix_ms).ok());
+        let before_id = before.map(|(_, id)| id);
         let mut out: Vec<Value> = Vec::with_capacity(cap.min(1024));
         let conn = self.events_db.lock();
         let Ok(mut stmt) = conn.prepare(
@@ -3400,12 +4234,14 @@ impl Store {
              FROM events
              WHERE (?1 IS NULL OR unix_ms >= ?1)
                AND (?2 IS NULL OR unix_ms <= ?2)
-             ORDER BY unix_ms DESC
-             LIMIT ?3",
+               AND (?3 IS NULL OR unix_ms < ?3 OR (unix_ms = ?3 AND id < ?4))
+             ORDER BY unix_ms DESC, id DESC
+             LIMIT ?5",
         ) else {
             return out;
         };
-        let Ok(rows) = stmt.query_map(params![from_i64, to_i64, cap as i64], |row| {
+        let bound = params![from_i64, to_i64, before_unix_ms, before_id, cap as i64];
+        let Ok(rows) = stmt.query_map(bound, |row| {
             Ok((
                 row.get::<_, String>(0)?,
                 row.get::<_, i64>(1)?,
@@ -3582,6 +4418,7 @@ impl Store {
     }
 
     pub fn backfill_usage_request_node_identity(&self, node_id: &str, node_name: &str) -> usize {
+        self.flush_usage_requests();
         let trimmed_node_id = node_id.trim();
         let trimmed_node_name = node_name.trim();
         if trimmed_node_id.is_empty() || trimmed_node_name.is_empty() {
@@ -3599,12 +4436,13 @@ impl Store {
 
     #[cfg(test)]
     pub fn list_usage_requests(&self, limit: usize) -> Vec<Value> {
+        self.flush_usage_requests();
         let mut out: Vec<Value> = Vec::with_capacity(limit.min(1024));
         let conn = self.events_db.lock();
         let Ok(mut stmt) = conn.prepare(
             "SELECT id, provider, api_key_ref, model, origin, transport, session_id, unix_ms, node_id, node_name,
                     input_tokens, output_tokens, total_tokens,
-                    cache_creation_input_tokens, cache_read_input_tokens
+                    cache_creation_input_tokens, cache_read_input_tokens, label
              FROM usage_requests
              ORDER BY unix_ms DESC
              LIMIT ?1",
@@ -3628,6 +4466,7 @@ impl Store {
                 "total_tokens": u64::try_from(row.get::<_, i64>(12)?).unwrap_or(0),
                 "cache_creation_input_tokens": u64::try_from(row.get::<_, i64>(13)?).unwrap_or(0),
                 "cache_read_input_tokens": u64::try_from(row.get::<_, i64>(14)?).unwrap_or(0),
+                "label": row.get::<_, String>(15)?,
             }))
         }) else {
             return out;
@@ -3638,40 +4477,79 @@ impl Store {
         out
     }
 
+    fn usage_request_sync_row(row: &rusqlite::Row<'_>) -> rusqlite::Result<UsageRequestSyncRow> {
+        Ok(UsageRequestSyncRow {
+            id: row.get::<_, String>(0)?,
+            unix_ms: u64::try_from(row.get::<_, i64>(1)?).unwrap_or(0),
+            ingested_at_unix_ms: u64::try_from(row.get::<_, i64>(2)?).unwrap_or(0),
+            provider: row.get::<_, String>(3)?,
+            api_key_ref: row.get::<_, String>(4)?,
+            model: row.get::<_, String>(5)?,
+            origin: row.get::<_, String>(6)?,
+            transport: row.get::<_, String>(7)?,
+            session_id: row.get::<_, String>(8)?,
+            node_id: row.get::<_, String>(9)?,
+            node_name: row.get::<_, String>(10)?,
+            input_tokens: u64::try_from(row.get::<_, i64>(11)?).unwrap_or(0),
+            output_tokens: u64::try_from(row.get::<_, i64>(12)?).unwrap_or(0),
+            total_tokens: u64::try_from(row.get::<_, i64>(13)?).unwrap_or(0),
+            cache_creation_input_tokens: u64::try_from(row.get::<_, i64>(14)?).unwrap_or(0),
+            cache_read_input_tokens: u64::try_from(row.get::<_, i64>(15)?).unwrap_or(0),
+            label: row.get::<_, String>(16)?,
+            request_bytes: u64::try_from(row.get::<_, i64>(17)?).unwrap_or(0),
+            response_bytes: u64::try_from(row.get::<_, i64>(18)?).unwrap_or(0),
+        })
+    }
+
+    /// Streams every usage request with `from_unix_ms <= unix_ms < to_unix_ms` to `out` as JSON
+    /// Lines, oldest first, one row at a time. Returns the number of rows written.
+    pub fn export_usage_requests_jsonl(
+        &self,
+        from_unix_ms: u64,
+        to_unix_ms: u64,
+        out: &mut impl std::io::Write,
+    ) -> anyhow::Result<u64> {
+        self.with_events_read_conn(|conn| -> anyhow::Result<u64> {
+            let mut stmt = conn.prepare(&format!(
+                "SELECT {USAGE_REQUEST_SYNC_COLUMNS}
+                 FROM usage_requests
+                 WHERE unix_ms >= ?1 AND unix_ms < ?2
+                 ORDER BY unix_ms ASC, id ASC"
+            ))?;
+            let mut rows = stmt.query(params![
+                i64::try_from(from_unix_ms).unwrap_or(i64::MAX),
+                i64::try_from(to_unix_ms).unwrap_or(i64::MAX),
+            ])?;
+            let mut written = 0u64;
+            while let Some(row) = rows.next()? {
+                serde_json::to_writer(&mut *out, &Self::usage_request_sync_row(row)?)?;
+                out.write_all(b"\n")?;
+                written += 1;
+            }
+            out.flush()?;
+            Ok(written)
+        })
+    }
+
     pub fn list_usage_request_sync_batch(
         &self,
         after_ingested_at_unix_ms: u64,
         after_id: Option<&str>,
         limit: usize,
     ) -> (Vec<UsageRequestSyncRow>, bool) {
+        self.flush_usage_requests();
         let mut out = Vec::with_capacity(limit.min(128));
         let after_ingested_i64 = i64::try_from(after_ingested_at_unix_ms).unwrap_or(i64::MAX);
         let after_id = after_id.unwrap_or_default().trim();
         let conn = self.events_db.lock();
-        let Ok(mut stmt) = conn.prepare(
-            "SELECT
-                id,
-                unix_ms,
-                ingested_at_unix_ms,
-                provider,
-                api_key_ref,
-                model,
-                origin,
-                transport,
-                session_id,
-                node_id,
-                node_name,
-                input_tokens,
-                output_tokens,
-                total_tokens,
-                cache_creation_input_tokens,
-                cache_read_input_tokens
+        let Ok(mut stmt) = conn.prepare(&format!(
+            "SELECT {USAGE_REQUEST_SYNC_COLUMNS}
              FROM usage_requests
              WHERE ingested_at_unix_ms > ?1
                 OR (ingested_at_unix_ms = ?1 AND id > ?2)
              ORDER BY ingested_at_unix_ms ASC, id ASC
-             LIMIT ?3",
-        ) else {
+             LIMIT ?3"
+        )) else {
             return (out, false);
         };
         let fetch_limit = limit.saturating_add(1);
@@ -3681,26 +4559,7 @@ impl Store {
                 after_id,
                 i64::try_from(fetch_limit).unwrap_or(i64::MAX)
             ],
-            |row| {
-                Ok(UsageRequestSyncRow {
-                    id: row.get::<_, String>(0)?,
-                    unix_ms: u64::try_from(row.get::<_, i64>(1)?).unwrap_or(0),
-                    ingested_at_unix_ms: u64::try_from(row.get::<_, i64>(2)?).unwrap_or(0),
-                    provider: row.get::<_, String>(3)?,
-                    api_key_ref: row.get::<_, String>(4)?,
-                    model: row.get::<_, String>(5)?,
-                    origin: row.get::<_, String>(6)?,
-                    transport: row.get::<_, String>(7)?,
-                    session_id: row.get::<_, String>(8)?,
-                    node_id: row.get::<_, String>(9)?,
-                    node_name: row.get::<_, String>(10)?,
-                    input_tokens: u64::try_from(row.get::<_, i64>(11)?).unwrap_or(0),
-                    output_tokens: u64::try_from(row.get::<_, i64>(12)?).unwrap_or(0),
-                    total_tokens: u64::try_from(row.get::<_, i64>(13)?).unwrap_or(0),
-                    cache_creation_input_tokens: u64::try_from(row.get::<_, i64>(14)?).unwrap_or(0),
-                    cache_read_input_tokens: u64::try_from(row.get::<_, i64>(15)?).unwrap_or(0),
-                })
-            },
+            Self::usage_request_sync_row,
         ) else {
             return (out, false);
         };
@@ -3715,6 +4574,7 @@ impl Store {
     }
 
     pub fn upsert_usage_request_sync_rows(&self, rows: &[UsageRequestSyncRow]) -> usize {
+        self.flush_usage_requests();
         if rows.is_empty() {
             return 0;
         }
@@ -3728,8 +4588,12 @@ impl Store {
                 "INSERT OR IGNORE INTO usage_requests(
                     id, unix_ms, ingested_at_unix_ms, provider, api_key_ref, model, origin, transport, session_id,
                     node_id, node_name, input_tokens, output_tokens, total_tokens,
-                    cache_creation_input_tokens, cache_read_input_tokens
-                 ) VALUES(?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16)",
+                    cache_creation_input_tokens, cache_read_input_tokens, label,
+                    request_bytes, response_bytes
+                 ) VALUES(
+                    ?1, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16, ?17,
+                    ?18, ?19
+                 )",
                 params![
                     row.id,
                     i64::try_from(row.unix_ms).unwrap_or(i64::MAX),
@@ -3747,6 +4611,9 @@ impl Store {
                     i64::try_from(row.total_tokens).unwrap_or(i64::MAX),
                     i64::try_from(row.cache_creation_input_tokens).unwrap_or(i64::MAX),
                     i64::try_from(row.cache_read_input_tokens).unwrap_or(i64::MAX),
+                    row.label,
+                    i64::try_from(row.request_bytes).unwrap_or(i64::MAX),
+                    i64::try_from(row.response_bytes).unwrap_or(i64::MAX),
                 ],
             ) else {
                 let _ = tx.rollback();
@@ -3775,6 +4642,7 @@ impl Store {
         limit: usize,
         offset: usize,
     ) -> (Vec<Value>, bool) {
+        self.flush_usage_requests();
         let mut sql = String::from(
             "SELECT id, provider, api_key_ref, model, origin, transport, session_id, unix_ms, node_id, node_name,
                     input_tokens, output_tokens, total_tokens,
@@ -3910,6 +4778,7 @@ impl Store {
         transports: &[String],
         sessions: &[String],
     ) -> (u64, u64, u64, u64, u64, u64) {
+        self.flush_usage_requests();
         let mut sql = String::from(
             "SELECT
                 COUNT(*),
@@ -4010,7 +4879,6 @@ impl Store {
         })
     }
 
-    #[cfg(test)]
     pub fn summarize_usage_requests_since_by_provider(
         &self,
         provider: &str,
@@ -4230,6 +5098,101 @@ impl Store {
         updated
     }
 }
+
+/// Sums the numeric counters of two JSON records; `*_unix_ms` fields keep the later value.
+fn merge_counter_json(target: &[u8], source: &[u8]) -> Vec<u8> {
+    let (Ok(mut merged), Ok(source)) = (
+        serde_json::from_slice::<Value>(target),
+        serde_json::from_slice::<Value>(source),
+    ) else {
+        return target.to_vec();
+    };
+    let (Some(merged_obj), Some(source_obj)) = (merged.as_object_mut(), source.as_object()) else {
+        return target.to_vec();
+    };
+    for (key, value) in source_obj {
+        let current = merged_obj.get(key);
+        let next = match (current, value.as_u64(), value.as_f64()) {
+            (None, _, _) => value.clone(),
+            (Some(cur), Some(add), _) if cur.is_u64() => {
+                let cur = cur.as_u64().unwrap_or(0);
+                if key.ends_with("_unix_ms") {
+                    Value::from(cur.max(add))
+                } else {
+                    Value::from(cur.saturating_add(add))
+                }
+            }
+            (Some(cur), _, Some(add)) if cur.is_number() => {
+                Value::from(cur.as_f64().unwrap_or(0.0) + add)
+            }
+            (Some(cur), _, _) => cur.clone(),
+        };
+        merged_obj.insert(key.clone(), next);
+    }
+    serde_json::to_vec(&merged).unwrap_or_else(|_| target.to_vec())
+}
+
+/// Moves `source` spend rows to `target`. When `target` already has a row with the same
+/// `key_columns`, every numeric field of the source row is added into it (see
+/// `merge_counter_json`) and the source row is dropped instead of being re-keyed.
+fn merge_spend_rows(
+    conn: &rusqlite::Connection,
+    table: &str,
+    key_columns: &[&str],
+    source: &str,
+    target: &str,
+) {
+    let keys = key_columns.join(", ");
+    let rows: Vec<(i64, String, Vec<rusqlite::types::Value>)> = conn
+        .prepare(&format!(
+            "SELECT rowid, row_json, {keys} FROM {table} WHERE provider=?1"
+        ))
+        .and_then(|mut stmt| {
+            stmt.query_map(params![source], |row| {
+                let key = (0..key_columns.len())
+                    .map(|idx| row.get(idx + 2))
+                    .collect::<rusqlite::Result<Vec<_>>>()?;
+                Ok((row.get(0)?, row.get(1)?, key))
+            })
+            .map(|rows| rows.flatten().collect())
+        })
+        .unwrap_or_default();
+    let key_filter: String = key_columns
+        .iter()
+        .enumerate()
+        .map(|(idx, column)| format!(" AND {column}=?{}", idx + 2))
+        .collect();
+    for (rowid, source_json, key) in rows {
+        let target_row: Option<(i64, String)> = conn
+            .query_row(
+                &format!("SELECT rowid, row_json FROM {table} WHERE provider=?1{key_filter}"),
+                params_from_iter(
+                    std::iter::once(rusqlite::types::Value::from(target.to_string())).chain(key),
+                ),
+                |row| Ok((row.get(0)?, row.get(1)?)),
+            )
+            .optional()
+            .ok()
+            .flatten();
+        let Some((target_rowid, target_json)) = target_row else {
+            let _ = conn.execute(
+                &format!("UPDATE {table} SET provider=?1 WHERE rowid=?2"),
+                params![target, rowid],
+            );
+            continue;
+        };
+        let merged = merge_counter_json(target_json.as_bytes(), source_json.as_bytes());
+        let merged = String::from_utf8(merged).unwrap_or(target_json);
+        let _ = conn.execute(
+            &format!("UPDATE {table} SET row_json=?1 WHERE rowid=?2"),
+            params![merged, target_rowid],
+        );
+        let _ = conn.execute(
+            &format!("DELETE FROM {table} WHERE rowid=?1"),
+            params![rowid],
+        );
+    }
+}
 include!("store/time_and_fs.rs");
 pub fn maintain_store_dir(path: &Path) -> anyhow::Result<()> {
     if !path.exists() {
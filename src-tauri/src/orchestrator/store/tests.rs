@@ -36,6 +36,138 @@ mod tests {
         assert_eq!(out[1].get("unix_ms").and_then(|v| v.as_u64()), Some(2000));
     }
 
+    #[test]
+    fn config_audit_entries_are_listed_newest_first_within_window_and_survive_reopen() {
+        let tmp = tempfile::tempdir().unwrap();
+        {
+            let store = Store::open(tmp.path()).unwrap();
+            for (ts, action) in [(1000, "a"), (3000, "c"), (2000, "b"), (4000, "d")] {
+                store.record_config_audit_at_unix_ms(
+                    action,
+                    "routing.preferred_provider",
+                    serde_json::json!("old"),
+                    serde_json::json!("new"),
+                    ts,
+                );
+            }
+        }
+        let store = Store::open(tmp.path()).unwrap();
+
+        let all = store.list_config_audit(None, None, 10);
+        let actions: Vec<&str> = all
+            .iter()
+            .filter_map(|e| e.get("action").and_then(|v| v.as_str()))
+            .collect();
+        assert_eq!(actions, vec!["d", "c", "b", "a"]);
+        assert_eq!(all[0]["old"], serde_json::json!("old"));
+        assert_eq!(all[0]["new"], serde_json::json!("new"));
+
+        let window = store.list_config_audit(Some(2000), Some(3000), 10);
+        assert_eq!(window.len(), 2);
+        assert_eq!(window[0]["unix_ms"].as_u64(), Some(3000));
+        assert_eq!(window[1]["unix_ms"].as_u64(), Some(2000));
+
+        let limited = store.list_config_audit(None, None, 1);
+        assert_eq!(limited.len(), 1);
+        assert_eq!(limited[0]["action"].as_str(), Some("d"));
+    }
+
+    #[test]
+    fn health_transitions_survive_reopen_and_follow_provider_rename() {
+        let tmp = tempfile::tempdir().unwrap();
+        {
+            let store = Store::open(tmp.path()).unwrap();
+            store.record_health_transition("p1", "healthy", "cooldown", "boom", 3000);
+            store.record_health_transition("p1", "unknown", "healthy", "request_succeeded", 1000);
+            store.record_health_transition(
+                "p1-eu",
+                "unknown",
+                "healthy",
+                "request_succeeded",
+                2000,
+            );
+        }
+        let store = Store::open(tmp.path()).unwrap();
+
+        let history = store.list_health_transitions("p1", None, None);
+        let times: Vec<u64> = history
+            .iter()
+            .filter_map(|e| e["unix_ms"].as_u64())
+            .collect();
+        assert_eq!(times, vec![1000, 3000]);
+        assert_eq!(history[1]["reason"], "boom");
+        assert_eq!(
+            store.list_health_transitions("p1", Some(2000), None).len(),
+            1
+        );
+
+        store.rename_provider("p1", "p2");
+        assert!(store.list_health_transitions("p1", None, None).is_empty());
+        assert_eq!(store.list_health_transitions("p2", None, None).len(), 2);
+        assert_eq!(
+            store.list_health_transitions("p2", None, None)[0]["provider"],
+            "p2"
+        );
+        assert_eq!(store.list_health_transitions("p1-eu", None, None).len(), 1);
+    }
+
+    #[test]
+    fn health_transitions_follow_provider_merge_and_prune_oldest() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = Store::open(tmp.path()).unwrap();
+        store.record_health_transition("src", "healthy", "cooldown", "boom", 2000);
+        store.record_health_transition("dst", "unknown", "healthy", "request_succeeded", 1000);
+        store.record_health_transition("dst", "cooldown", "healthy", "request_succeeded", 3000);
+
+        store.merge_provider("src", "dst");
+        assert!(store.list_health_transitions("src", None, None).is_empty());
+        let history = store.list_health_transitions("dst", None, None);
+        let times: Vec<u64> = history
+            .iter()
+            .filter_map(|e| e["unix_ms"].as_u64())
+            .collect();
+        assert_eq!(times, vec![1000, 2000, 3000]);
+        assert_eq!(history[1]["provider"], "dst");
+
+        store.prune_health_transitions("dst", 2);
+        let times: Vec<u64> = store
+            .list_health_transitions("dst", None, None)
+            .iter()
+            .filter_map(|e| e["unix_ms"].as_u64())
+            .collect();
+        assert_eq!(times, vec![2000, 3000]);
+    }
+
+    #[test]
+    fn flush_to_disk_checkpoints_sqlite_wal() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = Store::open(tmp.path()).unwrap();
+        for i in 0..20 {
+            store.events().emit(
+                "gateway",
+                EventCode::GATEWAY_SHUTDOWN,
+                &format!("shutdown test {i}"),
+                Value::Null,
+            );
+        }
+
+        store.record_success(
+            "p1",
+            &serde_json::json!({ "id": "r1", "usage": { "total_tokens": 1 } }),
+            UsageRequestContext {
+                api_key_ref: None,
+                ..UsageRequestContext::test_context()
+            },
+        );
+
+        assert_eq!(store.flush_to_disk().unwrap(), 1);
+
+        let wal = Store::sqlite_sidecar_path(&store.events_db_path, "-wal");
+        let wal_len = std::fs::metadata(&wal).map(|m| m.len()).unwrap_or(0);
+        assert_eq!(wal_len, 0);
+        assert!(!store.list_events_range(None, None, Some(50)).is_empty());
+    }
+
     #[test]
     fn list_recent_error_events_is_not_limited_by_latest_all_events_window() {
         let tmp = tempfile::tempdir().unwrap();
@@ -621,6 +753,34 @@ mod tests {
         assert!(store.list_session_route_assignments_since(0).is_empty());
     }
 
+    #[test]
+    fn session_last_routes_upsert_replace_and_prune() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = Store::open(tmp.path()).unwrap();
+        let route = |session_id: &str, provider: &str, unix_ms: u64| SessionLastRoute {
+            session_id: session_id.to_string(),
+            provider: provider.to_string(),
+            reason: "preferred_healthy".to_string(),
+            preferred: "p1".to_string(),
+            unix_ms,
+        };
+
+        store.put_session_last_route(&route("s1", "p1", 1_000));
+        store.put_session_last_route(&route("s1", "p2", 3_000));
+        store.put_session_last_route(&route("s2", "p1", 2_000));
+        let rows = store.list_session_last_routes_since(2_500);
+        assert_eq!(rows, vec![route("s1", "p2", 3_000)]);
+
+        assert_eq!(store.delete_session_last_routes_before(2_500), 1);
+        assert_eq!(store.list_session_last_routes_since(0).len(), 1);
+
+        store.replace_session_last_routes(&[route("s3", "p1", 4_000)]);
+        assert_eq!(
+            store.list_session_last_routes_since(0),
+            vec![route("s3", "p1", 4_000)]
+        );
+    }
+
     #[test]
     fn reopening_store_backfills_daily_index_from_events() {
         let tmp = tempfile::tempdir().unwrap();
@@ -855,6 +1015,8 @@ mod tests {
             assert!(columns.iter().any(|col| col == "ingested_at_unix_ms"));
             assert!(columns.iter().any(|col| col == "node_id"));
             assert!(columns.iter().any(|col| col == "node_name"));
+            assert!(columns.iter().any(|col| col == "request_bytes"));
+            assert!(columns.iter().any(|col| col == "response_bytes"));
 
             let ingested: i64 = conn
                 .query_row(
@@ -1151,6 +1313,106 @@ mod tests {
         assert_eq!(merged.5, 1);
     }
 
+    #[test]
+    fn usage_failures_follow_rename_and_merge_and_expire() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = Store::open(tmp.path()).unwrap();
+        let now = 40 * 24 * 60 * 60 * 1000;
+        store.insert_usage_failure("provider_old", now - 1_000);
+        store.insert_usage_failure("provider_other", now - 2_000);
+        store.insert_usage_failure("provider_old", now - USAGE_FAILURE_RETENTION_MS - 1);
+
+        store.rename_provider("provider_old", "provider_new");
+        store.merge_provider("provider_other", "provider_new");
+        let providers = |store: &Store| {
+            store
+                .list_usage_failures_window(0)
+                .into_iter()
+                .map(|failure| failure.provider)
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(providers(&store), vec!["provider_new"; 3]);
+
+        assert_eq!(store.prune_usage_failures(now), 1);
+        assert_eq!(providers(&store), vec!["provider_new"; 2]);
+    }
+
+    #[test]
+    fn merge_provider_sums_overlapping_counters_and_spend() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = Store::open(tmp.path()).unwrap();
+        let _ = store.db.insert(
+            b"metrics:provider_old",
+            br#"{"ok_requests":2,"error_requests":1,"total_tokens":100}"#.as_slice(),
+        );
+        let _ = store.db.insert(
+            b"metrics:provider_new",
+            br#"{"ok_requests":3,"error_requests":0,"total_tokens":200}"#.as_slice(),
+        );
+        for (provider, day_key, usd) in [
+            ("provider_old", "2026-04-01", 1.5),
+            ("provider_new", "2026-04-01", 2.0),
+            ("provider_old", "2026-04-02", 4.0),
+        ] {
+            store.put_spend_manual_day(
+                provider,
+                day_key,
+                &serde_json::json!({
+                    "provider": provider,
+                    "day_key": day_key,
+                    "manual_total_usd": usd
+                }),
+            );
+        }
+        for (provider, shared_provider_id, usd, requests) in [
+            ("provider_old", "shared-old", 1.0, 2_u64),
+            ("provider_new", "shared-new", 3.0, 5),
+        ] {
+            store.put_shared_tracked_spend_day(
+                provider,
+                shared_provider_id,
+                "2026-04-01",
+                &serde_json::json!({
+                    "provider": provider,
+                    "shared_provider_id": shared_provider_id,
+                    "day_key": "2026-04-01",
+                    "tracked_spend_usd": usd,
+                    "request_count": requests
+                }),
+                1_000,
+            );
+        }
+
+        store.merge_provider("provider_old", "provider_new");
+
+        let metrics = store.get_metrics();
+        assert!(metrics.get("provider_old").is_none());
+        assert_eq!(
+            metrics["provider_new"],
+            serde_json::json!({ "ok_requests": 5, "error_requests": 1, "total_tokens": 300 })
+        );
+        assert!(store
+            .list_local_spend_manual_days("provider_old")
+            .is_empty());
+        let manual_days = store.list_local_spend_manual_days("provider_new");
+        assert_eq!(manual_days.len(), 2);
+        assert_eq!(
+            manual_days
+                .iter()
+                .filter_map(|row| row.get("manual_total_usd").and_then(Value::as_f64))
+                .sum::<f64>(),
+            7.5
+        );
+        assert!(store
+            .list_shared_tracked_spend_days("provider_old")
+            .is_empty());
+        let shared_days = store.list_shared_tracked_spend_days("provider_new");
+        assert_eq!(shared_days.len(), 1);
+        assert_eq!(shared_days[0]["shared_provider_id"], "shared-new");
+        assert_eq!(shared_days[0]["tracked_spend_usd"].as_f64(), Some(4.0));
+        assert_eq!(shared_days[0]["request_count"].as_u64(), Some(7));
+    }
+
     #[test]
     fn list_usage_requests_page_supports_day_range() {
         let tmp = tempfile::tempdir().unwrap();
@@ -1221,22 +1483,11 @@ mod tests {
         let ts = 1_710_000_000_000u64;
         assert_eq!(
             store.upsert_usage_request_sync_rows(&[UsageRequestSyncRow {
-                id: "row-ws".to_string(),
-                unix_ms: ts,
-                ingested_at_unix_ms: ts,
-                provider: "official".to_string(),
-                api_key_ref: "-".to_string(),
-                model: "gpt-5.2-codex".to_string(),
-                origin: "windows".to_string(),
                 transport: "ws".to_string(),
                 session_id: "session-ws".to_string(),
-                node_id: "node-a".to_string(),
-                node_name: "Desk A".to_string(),
-                input_tokens: 10,
                 output_tokens: 2,
                 total_tokens: 12,
-                cache_creation_input_tokens: 0,
-                cache_read_input_tokens: 0,
+                ..UsageRequestSyncRow::test_row("row-ws", "official", ts)
             }]),
             1
         );
@@ -1248,6 +1499,29 @@ mod tests {
         assert_eq!(rows[0].get("transport").and_then(|value| value.as_str()), Some("ws"));
     }
 
+    #[test]
+    fn usage_request_body_sizes_round_trip_through_sync_rows() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = Store::open(tmp.path()).unwrap();
+        let ts = 1_710_000_000_000u64;
+        assert_eq!(
+            store.upsert_usage_request_sync_rows(&[UsageRequestSyncRow {
+                session_id: "session-sized".to_string(),
+                output_tokens: 2,
+                total_tokens: 12,
+                request_bytes: 2_048,
+                response_bytes: 512,
+                ..UsageRequestSyncRow::test_row("row-sized", "official", ts)
+            }]),
+            1
+        );
+
+        let rows = store.list_usage_request_stats_rows_window(0);
+        assert_eq!(rows.len(), 1);
+        assert_eq!(rows[0].request_bytes, 2_048);
+        assert_eq!(rows[0].response_bytes, 512);
+    }
+
     #[test]
     fn summarize_usage_requests_is_not_limited_by_page_size() {
         let tmp = tempfile::tempdir().unwrap();
@@ -1452,22 +1726,15 @@ mod tests {
         let mut rows = Vec::new();
         for i in 0..300u64 {
             rows.push(UsageRequestSyncRow {
-                id: format!("row-{i:03}"),
-                unix_ms: 10_000 + i,
                 ingested_at_unix_ms: 20_000 + i,
-                provider: "official".to_string(),
-                api_key_ref: "-".to_string(),
-                model: "gpt-5.2-codex".to_string(),
                 origin: if i % 2 == 0 { "windows" } else { "wsl2" }.to_string(),
-                transport: "http".to_string(),
                 session_id: format!("session-{i:03}"),
                 node_id: if i % 2 == 0 { "node-a" } else { "node-b" }.to_string(),
                 node_name: if i % 2 == 0 { "Desk A" } else { "Desk B" }.to_string(),
                 input_tokens: 100 + i,
                 output_tokens: 10,
                 total_tokens: 110 + i,
-                cache_creation_input_tokens: 0,
-                cache_read_input_tokens: 0,
+                ..UsageRequestSyncRow::test_row(format!("row-{i:03}"), "official", 10_000 + i)
             });
         }
 
@@ -1555,4 +1822,442 @@ mod tests {
         assert_eq!(cache_create, 0);
         assert_eq!(cache_read, 0);
     }
+
+    #[test]
+    fn prune_events_older_than_keeps_listed_levels_and_rebuilds_day_counts() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = Store::open(tmp.path()).unwrap();
+        let old = 1_700_000_000_000_i64;
+        let recent = old + 10 * 24 * 60 * 60 * 1000;
+
+        {
+            let conn = store.events_db.lock();
+            for (id, unix_ms, level) in [
+                ("old-info", old, "info"),
+                ("old-warning", old, "warning"),
+                ("old-error", old, "error"),
+                ("recent-info", recent, "info"),
+            ] {
+                conn.execute(
+                    "INSERT INTO events(id, unix_ms, provider, level, code, message, fields_json)
+                     VALUES (?1, ?2, 'p1', ?3, 'test_event', ?1, '{}')",
+                    params![id, unix_ms, level],
+                )
+                .unwrap();
+            }
+        }
+        store
+            .set_event_meta(Store::EVENT_DAY_COUNTS_INDEX_VERSION_KEY, "0")
+            .unwrap();
+        store.rebuild_event_day_counts_index_if_needed().unwrap();
+
+        let cutoff = u64::try_from(old + 1).unwrap();
+        let deleted = store
+            .prune_events_older_than(cutoff, &["error", "warning"])
+            .unwrap();
+        assert_eq!(deleted, 1);
+
+        let mut ids: Vec<String> = store
+            .list_events_range(None, None, Some(10))
+            .iter()
+            .filter_map(|row| row.get("id").and_then(|v| v.as_str()).map(str::to_string))
+            .collect();
+        ids.sort();
+        assert_eq!(ids, vec!["old-error", "old-warning", "recent-info"]);
+
+        let counts = store.list_event_daily_counts_range(None, None);
+        let total: u64 = counts
+            .iter()
+            .filter_map(|row| row.get("total").and_then(|v| v.as_u64()))
+            .sum();
+        let infos: u64 = counts
+            .iter()
+            .filter_map(|row| row.get("infos").and_then(|v| v.as_u64()))
+            .sum();
+        assert_eq!(total, 3);
+        assert_eq!(infos, 1);
+
+        assert_eq!(store.prune_events_older_than(cutoff, &[]).unwrap(), 2);
+    }
+
+    #[test]
+    fn storage_stats_reports_sizes_counts_and_oldest_rows() {
+        let tmp = tempfile::tempdir().unwrap();
+        let sled_dir = tmp.path().join("sled");
+        let store = Store::open(&sled_dir).unwrap();
+
+        {
+            let conn = store.events_db.lock();
+            for (id, unix_ms) in [("e1", 5_000_i64), ("e2", 2_000_i64)] {
+                conn.execute(
+                    "INSERT INTO events(id, unix_ms, provider, level, code, message, fields_json)
+                     VALUES (?1, ?2, 'p1', 'info', 'test_event', 'hello', '{}')",
+                    params![id, unix_ms],
+                )
+                .unwrap();
+            }
+        }
+
+        let stats = store.storage_stats();
+        assert_eq!(stats["events"]["count"].as_u64(), Some(2));
+        assert_eq!(stats["events"]["oldest_unix_ms"].as_u64(), Some(2_000));
+        assert_eq!(stats["usage_requests"]["count"].as_u64(), Some(0));
+        assert!(stats["usage_requests"]["oldest_unix_ms"].is_null());
+        assert!(stats["sled"]["bytes"].as_u64().unwrap_or(0) > 0);
+        assert!(stats["events_db"]["total_bytes"].as_u64().unwrap_or(0) > 0);
+        assert_eq!(
+            stats["total_bytes"].as_u64(),
+            Some(
+                stats["sled"]["bytes"].as_u64().unwrap()
+                    + stats["events_db"]["total_bytes"].as_u64().unwrap()
+            )
+        );
+    }
+
+    #[test]
+    fn storage_stats_counts_nested_events_db_once() {
+        let tmp = tempfile::tempdir().unwrap();
+        // Not named `sled`, so events.sqlite3 lives inside the store directory.
+        let store = Store::open(tmp.path()).unwrap();
+        store.flush_to_disk().unwrap();
+
+        let stats = store.storage_stats();
+        let total = stats["total_bytes"].as_u64().unwrap();
+        let on_disk = dir_size_bytes(tmp.path());
+        assert!(stats["events_db"]["total_bytes"].as_u64().unwrap() > 0);
+        assert!(total <= on_disk, "total {total} exceeds on-disk {on_disk}");
+    }
+
+    #[test]
+    fn create_manual_backup_copies_sled_tree_and_events_db() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = Store::open(&tmp.path().join("sled")).unwrap();
+        store
+            .put_quota_snapshot("p1", &serde_json::json!({ "remaining": 7 }))
+            .unwrap();
+        {
+            let conn = store.events_db.lock();
+            conn.execute(
+                "INSERT INTO events(id, unix_ms, provider, level, code, message, fields_json)
+                 VALUES ('e1', 1000, 'p1', 'info', 'test_event', 'hello', '{}')",
+                [],
+            )
+            .unwrap();
+        }
+
+        let backup_dir = store
+            .create_manual_backup(Some(" pre migration! "))
+            .unwrap();
+        assert_eq!(backup_dir.parent(), Some(tmp.path()));
+        let name = backup_dir.file_name().and_then(|n| n.to_str()).unwrap();
+        assert!(name.starts_with("sled.manual-backup."));
+        assert!(name.ends_with("-pre-migration"));
+
+        let backup = sled::open(&backup_dir).unwrap();
+        let quota = backup.get(b"quota:p1").unwrap().unwrap();
+        assert_eq!(
+            serde_json::from_slice::<Value>(&quota).unwrap(),
+            serde_json::json!({ "remaining": 7 })
+        );
+        drop(backup);
+
+        let conn = rusqlite::Connection::open(backup_dir.join("events.sqlite3")).unwrap();
+        let count: i64 = conn
+            .query_row("SELECT COUNT(*) FROM events WHERE id='e1'", [], |row| {
+                row.get(0)
+            })
+            .unwrap();
+        assert_eq!(count, 1);
+    }
+
+    #[test]
+    fn evict_to_size_cap_drops_info_events_and_usage_requests_only_on_opt_in() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = Store::open(tmp.path()).unwrap();
+        {
+            let conn = store.events_db.lock();
+            for (id, unix_ms, level) in [
+                ("e1", 1_000_i64, "info"),
+                ("e2", 2_000_i64, "error"),
+                ("e3", 3_000_i64, "info"),
+            ] {
+                conn.execute(
+                    "INSERT INTO events(id, unix_ms, provider, level, code, message, fields_json)
+                     VALUES (?1, ?2, 'p1', ?3, 'test_event', 'hello', '{}')",
+                    params![id, unix_ms, level],
+                )
+                .unwrap();
+            }
+        }
+        let rows: Vec<UsageRequestSyncRow> = (0..3)
+            .map(|idx| UsageRequestSyncRow {
+                model: "gpt-5".to_string(),
+                node_id: String::new(),
+                node_name: String::new(),
+                input_tokens: 1,
+                output_tokens: 1,
+                total_tokens: 2,
+                ..UsageRequestSyncRow::test_row(format!("u{idx}"), "p1", 10_000 + idx)
+            })
+            .collect();
+        store.upsert_usage_request_sync_rows(&rows);
+
+        assert_eq!(store.evict_to_size_cap(0, true).unwrap().events_deleted, 0);
+        assert_eq!(
+            store
+                .evict_to_size_cap(u64::MAX, true)
+                .unwrap()
+                .events_deleted,
+            0
+        );
+
+        let usage_count = |store: &Store| -> i64 {
+            store
+                .events_db
+                .lock()
+                .query_row("SELECT COUNT(*) FROM usage_requests", [], |row| row.get(0))
+                .unwrap()
+        };
+        let eviction = store.evict_to_size_cap(1, false).unwrap();
+        assert!(eviction.events_deleted >= 2);
+        assert_eq!(eviction.usage_requests_deleted, 0);
+        assert!(eviction.bytes_before > 1);
+        assert_eq!(usage_count(&store), 3);
+        {
+            let conn = store.events_db.lock();
+            let remaining: Vec<String> = conn
+                .prepare("SELECT id FROM events ORDER BY unix_ms")
+                .unwrap()
+                .query_map([], |row| row.get(0))
+                .unwrap()
+                .flatten()
+                .collect();
+            assert!(remaining.contains(&"e2".to_string()));
+            assert!(!remaining.contains(&"e1".to_string()));
+            assert!(!remaining.contains(&"e3".to_string()));
+        }
+
+        let eviction = store.evict_to_size_cap(1, true).unwrap();
+        assert_eq!(eviction.usage_requests_deleted, 3);
+        assert_eq!(usage_count(&store), 0);
+    }
+
+    #[test]
+    fn buffered_usage_requests_all_land_after_flush() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = Store::open(tmp.path()).unwrap();
+        for id in ["r1", "r2", "r3"] {
+            store.record_success_with_model(
+                "p1",
+                &serde_json::json!({
+                    "id": id,
+                    "model": "gpt-5.2",
+                    "usage": { "input_tokens": 2, "output_tokens": 3, "total_tokens": 5 }
+                }),
+                UsageRequestContext {
+                    session_id: Some("s1"),
+                    ..UsageRequestContext::test_context()
+                },
+                None,
+            );
+        }
+
+        let raw_count = |store: &Store| -> i64 {
+            store
+                .events_db
+                .lock()
+                .query_row("SELECT COUNT(*) FROM usage_requests", [], |row| row.get(0))
+                .unwrap()
+        };
+        // Rows sit in the buffer, but the live counters already reflect them.
+        assert_eq!(raw_count(&store), 0);
+        assert_eq!(store.get_metrics()["p1"]["ok_requests"], 3);
+
+        assert_eq!(store.flush_usage_requests(), 3);
+        assert_eq!(raw_count(&store), 3);
+        assert_eq!(store.flush_usage_requests(), 0);
+        let (requests, _, _, total_tokens, _, _) =
+            store.summarize_usage_requests(0, None, None, &[], &[], &[], &[], &[], &[]);
+        assert_eq!((requests, total_tokens), (3, 15));
+    }
+
+    #[test]
+    fn failed_usage_request_flush_keeps_rows_buffered() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = Store::open(tmp.path()).unwrap();
+        for id in ["r1", "r2"] {
+            store.record_success_with_model(
+                "p1",
+                &serde_json::json!({
+                    "id": id,
+                    "model": "gpt-5.2",
+                    "usage": { "input_tokens": 2, "output_tokens": 3, "total_tokens": 5 }
+                }),
+                UsageRequestContext {
+                    session_id: Some("s1"),
+                    ..UsageRequestContext::test_context()
+                },
+                None,
+            );
+        }
+        let rename = |from: &str, to: &str| {
+            store
+                .events_db
+                .lock()
+                .execute_batch(&format!("ALTER TABLE {from} RENAME TO {to}"))
+                .unwrap();
+        };
+
+        // The insert cannot even be prepared, so the whole batch stays buffered.
+        rename("usage_requests", "usage_requests_offline");
+        assert_eq!(store.flush_usage_requests(), 0);
+        assert_eq!(store.pending_usage_requests.lock().len(), 2);
+        // A shutdown flush must not report success while rows are still only in memory.
+        assert!(store.flush_to_disk().is_err());
+        assert_eq!(store.pending_usage_requests.lock().len(), 2);
+
+        rename("usage_requests_offline", "usage_requests");
+        assert_eq!(store.flush_to_disk().unwrap(), 2);
+        assert!(store.pending_usage_requests.lock().is_empty());
+        let count: i64 = store
+            .events_db
+            .lock()
+            .query_row("SELECT COUNT(*) FROM usage_requests", [], |row| row.get(0))
+            .unwrap();
+        assert_eq!(count, 2);
+    }
+
+    #[test]
+    fn successful_response_without_usage_emits_one_unparsed_warning_per_model() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = Store::open(tmp.path()).unwrap();
+        let context = UsageRequestContext::test_context;
+        for (id, model, usage) in [
+            (
+                "r1",
+                "gpt-5.2",
+                serde_json::json!({ "tokens": { "in": 3 } }),
+            ),
+            (
+                "r2",
+                "gpt-5.2",
+                serde_json::json!({ "tokens": { "in": 4 } }),
+            ),
+            ("r3", "gpt-5.2-mini", Value::Null),
+            ("r4", "gpt-5.2", serde_json::json!({ "total_tokens": 7 })),
+        ] {
+            store.record_success_with_model(
+                "p1",
+                &serde_json::json!({
+                    "id": id,
+                    "object": "response",
+                    "model": model,
+                    "usage": usage,
+                    "output_text": "secret body text",
+                }),
+                context(),
+                None,
+            );
+        }
+
+        let unparsed: Vec<Value> = store
+            .list_events_range(None, None, Some(50))
+            .into_iter()
+            .filter(|event| event["code"] == "usage.unparsed")
+            .collect();
+        assert_eq!(unparsed.len(), 2);
+        let by_model = |model: &str| {
+            unparsed
+                .iter()
+                .find(|event| event["fields"]["model"] == model)
+                .cloned()
+                .expect("event for model")
+        };
+        let object_usage = by_model("gpt-5.2");
+        assert_eq!(object_usage["level"], "warning");
+        assert_eq!(object_usage["provider"], "p1");
+        assert_eq!(object_usage["fields"]["shape"]["object"], "response");
+        assert_eq!(object_usage["fields"]["shape"]["usage_type"], "object");
+        assert_eq!(
+            object_usage["fields"]["shape"]["usage_keys"],
+            serde_json::json!(["tokens"])
+        );
+        assert_eq!(
+            by_model("gpt-5.2-mini")["fields"]["shape"]["usage_type"],
+            "null"
+        );
+        assert!(!serde_json::to_string(&unparsed)
+            .unwrap()
+            .contains("secret body text"));
+    }
+
+    #[test]
+    fn clock_regression_clamps_small_steps_and_warns_once_for_large_ones() {
+        use std::sync::atomic::Ordering;
+        let tmp = tempfile::tempdir().unwrap();
+        let store = Store::open(tmp.path()).unwrap();
+        let high_water = &store.clock_high_water_unix_ms;
+
+        // A step back within the threshold keeps new rows at the previous timestamp.
+        let ahead = unix_ms() + 1_000;
+        high_water.store(ahead, Ordering::Relaxed);
+        assert_eq!(store.recorded_unix_ms(), ahead);
+
+        // A larger step is reported once, then the corrected clock is used.
+        high_water.store(unix_ms() + 60 * 60 * 1000, Ordering::Relaxed);
+        let corrected = store.recorded_unix_ms();
+        assert!(corrected <= unix_ms());
+        assert!(store.recorded_unix_ms() >= corrected);
+        store.events().emit(
+            "gateway",
+            EventCode::STORE_MANUAL_BACKUP_CREATED,
+            "after regression",
+            Value::Null,
+        );
+
+        let regressions: Vec<Value> = store
+            .list_events_range(None, None, Some(50))
+            .into_iter()
+            .filter(|event| event["code"] == "system.clock_regression")
+            .collect();
+        assert_eq!(regressions.len(), 1);
+        assert_eq!(regressions[0]["level"], "warning");
+        assert!(regressions[0]["fields"]["regression_ms"].as_u64().unwrap() > 5_000);
+    }
+
+    #[test]
+    fn usage_requests_record_trimmed_label_from_context() {
+        let tmp = tempfile::tempdir().unwrap();
+        let store = Store::open(tmp.path()).unwrap();
+        for (id, label) in [("labeled", Some(" bench-1 ")), ("unlabeled", None)] {
+            store.record_success_with_model(
+                "p1",
+                &serde_json::json!({
+                    "id": id,
+                    "model": "gpt-5.2",
+                    "usage": { "input_tokens": 1, "output_tokens": 1, "total_tokens": 2 }
+                }),
+                UsageRequestContext {
+                    session_id: Some("s1"),
+                    label,
+                    ..UsageRequestContext::test_context()
+                },
+                None,
+            );
+        }
+
+        let mut labels: Vec<String> = store
+            .list_usage_requests(10)
+            .iter()
+            .filter_map(|row| {
+                row.get("label")
+                    .and_then(|v| v.as_str())
+                    .map(str::to_string)
+            })
+            .collect();
+        labels.sort();
+        assert_eq!(labels, vec!["".to_string(), "bench-1".to_string()]);
+        let (synced, _) = store.list_usage_request_sync_batch(0, None, 10);
+        assert!(synced.iter().any(|row| row.label == "bench-1"));
+    }
 }
@@ -6,6 +6,53 @@ pub fn unix_ms() -> u64 {
         .as_millis() as u64
 }
 
+/// Backward clock steps up to this size (NTP slews, VM jitter) are absorbed without a warning.
+const CLOCK_REGRESSION_THRESHOLD_MS: u64 = 5_000;
+
+impl Store {
+    /// Wall-clock time for a new event or usage row, never earlier than the previous one.
+    ///
+    /// A backward step (NTP correction, resume from sleep) would otherwise date new rows before
+    /// older ones and make elapsed-time math collapse to zero. Small steps are clamped to the
+    /// previous timestamp. A larger step is reported once as `system.clock_regression`, after which
+    /// the corrected clock is adopted rather than pinning every later row to the old time.
+    pub fn recorded_unix_ms(&self) -> u64 {
+        use std::sync::atomic::Ordering;
+        let now = unix_ms();
+        let previous = self
+            .clock_high_water_unix_ms
+            .fetch_max(now, Ordering::Relaxed);
+        if previous <= now {
+            return now;
+        }
+        let regression_ms = previous - now;
+        if regression_ms <= CLOCK_REGRESSION_THRESHOLD_MS {
+            return previous;
+        }
+        // Only the caller that resets the high-water mark reports, so one step warns once.
+        if self
+            .clock_high_water_unix_ms
+            .compare_exchange(previous, now, Ordering::Relaxed, Ordering::Relaxed)
+            .is_ok()
+        {
+            let code = EventCode::SYSTEM_CLOCK_REGRESSION;
+            self.add_event_at_unix_ms(
+                "gateway",
+                code.level(),
+                code.code(),
+                "system clock moved backwards; new rows use the corrected clock",
+                serde_json::json!({
+                    "previous_unix_ms": previous,
+                    "current_unix_ms": now,
+                    "regression_ms": regression_ms,
+                }),
+                now,
+            );
+        }
+        now
+    }
+}
+
 fn dir_size_bytes(path: &Path) -> u64 {
     fn walk(p: &Path, sum: &mut u64) {
         let Ok(rd) = std::fs::read_dir(p) else {
@@ -1,6 +1,27 @@
 use super::*;
 use rusqlite::params;
 
+const UNPARSED_USAGE_REPORT_INTERVAL_MS: u64 = 10 * 60 * 1000;
+const UNPARSED_USAGE_MAX_SHAPE_KEYS: usize = 24;
+
+/// A usage row waiting in the in-memory buffer for the next batched insert.
+pub(super) struct PendingUsageRequest {
+    id: String,
+    unix_ms: i64,
+    provider: String,
+    api_key_ref: String,
+    model: String,
+    origin: &'static str,
+    transport: &'static str,
+    session_id: String,
+    node_id: String,
+    node_name: String,
+    increments: UsageTokenIncrements,
+    label: String,
+    request_bytes: u64,
+    response_bytes: u64,
+}
+
 fn merge_json_source_fields(row: &mut Value, source_node_id: &str, source_node_name: &str) {
     let Some(map) = row.as_object_mut() else {
         return;
@@ -77,12 +98,15 @@ impl Store {
                    model,
                    origin,
                    node_name,
+                   label,
                    unix_ms,
                    input_tokens,
                    output_tokens,
                    total_tokens,
                    cache_creation_input_tokens,
-                   cache_read_input_tokens
+                   cache_read_input_tokens,
+                   request_bytes,
+                   response_bytes
                  FROM usage_requests
                  WHERE unix_ms >= ?1
                  ORDER BY unix_ms DESC, id DESC",
@@ -96,12 +120,15 @@ impl Store {
                     model: row.get::<_, String>(2)?,
                     origin: row.get::<_, String>(3)?,
                     node_name: row.get::<_, String>(4)?,
-                    unix_ms: u64::try_from(row.get::<_, i64>(5)?).unwrap_or(0),
-                    input_tokens: u64::try_from(row.get::<_, i64>(6)?).unwrap_or(0),
-                    output_tokens: u64::try_from(row.get::<_, i64>(7)?).unwrap_or(0),
-                    total_tokens: u64::try_from(row.get::<_, i64>(8)?).unwrap_or(0),
-                    cache_creation_input_tokens: u64::try_from(row.get::<_, i64>(9)?).unwrap_or(0),
-                    cache_read_input_tokens: u64::try_from(row.get::<_, i64>(10)?).unwrap_or(0),
+                    label: row.get::<_, String>(5)?,
+                    unix_ms: u64::try_from(row.get::<_, i64>(6)?).unwrap_or(0),
+                    input_tokens: u64::try_from(row.get::<_, i64>(7)?).unwrap_or(0),
+                    output_tokens: u64::try_from(row.get::<_, i64>(8)?).unwrap_or(0),
+                    total_tokens: u64::try_from(row.get::<_, i64>(9)?).unwrap_or(0),
+                    cache_creation_input_tokens: u64::try_from(row.get::<_, i64>(10)?).unwrap_or(0),
+                    cache_read_input_tokens: u64::try_from(row.get::<_, i64>(11)?).unwrap_or(0),
+                    request_bytes: u64::try_from(row.get::<_, i64>(12)?).unwrap_or(0),
+                    response_bytes: u64::try_from(row.get::<_, i64>(13)?).unwrap_or(0),
                 })
             }) else {
                 return out;
@@ -111,6 +138,58 @@ impl Store {
         })
     }
 
+    pub fn list_usage_failures_window(&self, since_unix_ms: u64) -> Vec<UsageFailureRow> {
+        let Ok(since_i64) = i64::try_from(since_unix_ms) else {
+            return Vec::new();
+        };
+        self.with_events_read_conn(|conn| {
+            let Ok(mut stmt) = conn.prepare(
+                "SELECT provider, unix_ms
+                 FROM usage_failures
+                 WHERE unix_ms >= ?1
+                 ORDER BY unix_ms DESC, id DESC",
+            ) else {
+                return Vec::new();
+            };
+            let Ok(rows) = stmt.query_map(params![since_i64], |row| {
+                Ok(UsageFailureRow {
+                    provider: row.get::<_, String>(0)?,
+                    unix_ms: u64::try_from(row.get::<_, i64>(1)?).unwrap_or(0),
+                })
+            }) else {
+                return Vec::new();
+            };
+            rows.flatten().collect()
+        })
+    }
+
+    pub(super) fn add_usage_failure(&self, provider: &str) {
+        self.insert_usage_failure(provider, self.recorded_unix_ms());
+    }
+
+    pub(crate) fn insert_usage_failure(&self, provider: &str, unix_ms: u64) {
+        let Ok(ts_i64) = i64::try_from(unix_ms) else {
+            return;
+        };
+        let conn = self.events_db.lock();
+        let _ = conn.execute(
+            "INSERT INTO usage_failures(provider, unix_ms) VALUES(?1, ?2)",
+            params![provider, ts_i64],
+        );
+    }
+
+    /// Drops recorded failures older than [`USAGE_FAILURE_RETENTION_MS`]; usage statistics never
+    /// look back further than that.
+    pub fn prune_usage_failures(&self, now_unix_ms: u64) -> usize {
+        let cutoff = now_unix_ms.saturating_sub(USAGE_FAILURE_RETENTION_MS);
+        let conn = self.events_db.lock();
+        conn.execute(
+            "DELETE FROM usage_failures WHERE unix_ms < ?1",
+            params![i64::try_from(cutoff).unwrap_or(i64::MAX)],
+        )
+        .unwrap_or(0)
+    }
+
     pub fn list_usage_request_day_counts_for_provider(
         &self,
         provider: &str,
@@ -661,6 +740,50 @@ impl Store {
         );
     }
 
+    /// Removes local manual spend days with `from_day_key <= day_key <= to_day_key` and returns
+    /// the removed day keys.
+    pub fn remove_spend_manual_days_in_range(
+        &self,
+        provider: &str,
+        from_day_key: &str,
+        to_day_key: &str,
+    ) -> Vec<String> {
+        let mut conn = self.events_db.lock();
+        let Ok(tx) = conn.transaction() else {
+            return Vec::new();
+        };
+        let day_keys: Vec<String> = {
+            let Ok(mut stmt) = tx.prepare(
+                "SELECT day_key FROM spend_manual_days
+                 WHERE provider = ?1 AND day_key >= ?2 AND day_key <= ?3
+                 ORDER BY day_key ASC",
+            ) else {
+                return Vec::new();
+            };
+            let Ok(rows) = stmt.query_map(params![provider, from_day_key, to_day_key], |row| {
+                row.get::<_, String>(0)
+            }) else {
+                return Vec::new();
+            };
+            rows.flatten().collect()
+        };
+        if day_keys.is_empty() {
+            return day_keys;
+        }
+        if tx
+            .execute(
+                "DELETE FROM spend_manual_days
+                 WHERE provider = ?1 AND day_key >= ?2 AND day_key <= ?3",
+                params![provider, from_day_key, to_day_key],
+            )
+            .is_err()
+            || tx.commit().is_err()
+        {
+            return Vec::new();
+        }
+        day_keys
+    }
+
     pub fn remove_remote_spend_manual_day(
         &self,
         provider: &str,
@@ -1147,62 +1270,42 @@ impl Store {
     }
 
     pub(super) fn extract_usage_tokens(response_obj: &Value) -> (u64, u64, u64, u64, u64) {
-        let usage = response_obj.get("usage").cloned().unwrap_or(Value::Null);
-        let input_tokens = usage
-            .get("input_tokens")
-            .and_then(|v| v.as_u64())
-            .or_else(|| {
-                response_obj
-                    .pointer("/usage/input_tokens")
-                    .and_then(|v| v.as_u64())
-            })
+        // OpenAI nests usage under `usage`; Anthropic streams carry it under `message.usage`, and
+        // some Anthropic-compatible providers put the counters directly on the response object.
+        let usage = response_obj
+            .get("usage")
+            .filter(|v| v.is_object())
+            .or_else(|| response_obj.pointer("/message/usage"))
+            .unwrap_or(response_obj);
+        let field = |key: &str| usage.get(key).and_then(Value::as_u64);
+        let output_tokens = field("output_tokens")
+            .or_else(|| field("completion_tokens"))
             .unwrap_or(0);
-        let output_tokens = usage
-            .get("output_tokens")
-            .and_then(|v| v.as_u64())
-            .or_else(|| {
-                response_obj
-                    .pointer("/usage/output_tokens")
-                    .and_then(|v| v.as_u64())
-            })
-            .unwrap_or(0);
-        let total_tokens = usage
-            .get("total_tokens")
-            .and_then(|v| v.as_u64())
-            .or_else(|| {
-                response_obj
-                    .pointer("/usage/total_tokens")
-                    .and_then(|v| v.as_u64())
-            })
-            .unwrap_or(input_tokens + output_tokens);
-        let cache_creation_input_tokens = usage
-            .get("cache_creation_input_tokens")
-            .and_then(|v| v.as_u64())
-            .or_else(|| {
-                response_obj
-                    .pointer("/usage/cache_creation_input_tokens")
-                    .and_then(|v| v.as_u64())
-            })
+        let cache_creation_input_tokens = field("cache_creation_input_tokens").unwrap_or(0);
+        let openai_cached_tokens = usage
+            .pointer("/input_tokens_details/cached_tokens")
+            .or_else(|| usage.pointer("/prompt_tokens_details/cached_tokens"))
+            .and_then(Value::as_u64);
+        let cache_read_input_tokens = field("cache_read_input_tokens")
+            .or(openai_cached_tokens)
             .unwrap_or(0);
-        let cache_read_input_tokens = usage
-            .get("cache_read_input_tokens")
-            .and_then(|v| v.as_u64())
-            .or_else(|| {
-                usage
-                    .pointer("/input_tokens_details/cached_tokens")
-                    .and_then(|v| v.as_u64())
-            })
-            .or_else(|| {
-                response_obj
-                    .pointer("/usage/cache_read_input_tokens")
-                    .and_then(|v| v.as_u64())
-            })
-            .or_else(|| {
-                response_obj
-                    .pointer("/usage/input_tokens_details/cached_tokens")
-                    .and_then(|v| v.as_u64())
-            })
+        // Anthropic reports `input_tokens` excluding cache writes/reads, whereas OpenAI's input
+        // count already includes cached tokens. Normalize to the OpenAI meaning.
+        let anthropic_shape = openai_cached_tokens.is_none()
+            && field("total_tokens").is_none()
+            && (usage.get("cache_creation_input_tokens").is_some()
+                || usage.get("cache_read_input_tokens").is_some());
+        let raw_input_tokens = field("input_tokens")
+            .or_else(|| field("prompt_tokens"))
             .unwrap_or(0);
+        let input_tokens = if anthropic_shape {
+            raw_input_tokens
+                .saturating_add(cache_creation_input_tokens)
+                .saturating_add(cache_read_input_tokens)
+        } else {
+            raw_input_tokens
+        };
+        let total_tokens = field("total_tokens").unwrap_or(input_tokens + output_tokens);
         (
             input_tokens,
             output_tokens,
@@ -1212,6 +1315,87 @@ impl Store {
         )
     }
 
+    /// Shape hints (key names and value types only, never values) for a successful response that
+    /// carries no token counters `extract_usage_tokens` understands; `None` when usage parses.
+    pub(super) fn unparsed_usage_shape(response_obj: &Value) -> Option<Value> {
+        const COUNTER_KEYS: [&str; 5] = [
+            "input_tokens",
+            "output_tokens",
+            "prompt_tokens",
+            "completion_tokens",
+            "total_tokens",
+        ];
+        let usage = response_obj
+            .get("usage")
+            .filter(|v| v.is_object())
+            .or_else(|| response_obj.pointer("/message/usage"))
+            .unwrap_or(response_obj);
+        if COUNTER_KEYS
+            .iter()
+            .any(|key| usage.get(*key).and_then(Value::as_u64).is_some())
+        {
+            return None;
+        }
+        fn type_name(value: &Value) -> &'static str {
+            match value {
+                Value::Null => "null",
+                Value::Bool(_) => "bool",
+                Value::Number(_) => "number",
+                Value::String(_) => "string",
+                Value::Array(_) => "array",
+                Value::Object(_) => "object",
+            }
+        }
+        let keys = |value: &Value| -> Vec<String> {
+            value
+                .as_object()
+                .map(|map| {
+                    map.keys()
+                        .take(UNPARSED_USAGE_MAX_SHAPE_KEYS)
+                        .cloned()
+                        .collect()
+                })
+                .unwrap_or_default()
+        };
+        let usage_field = response_obj.get("usage");
+        Some(serde_json::json!({
+            "response_type": type_name(response_obj),
+            "response_keys": keys(response_obj),
+            "object": response_obj.get("object").and_then(Value::as_str),
+            "usage_type": usage_field.map(type_name).unwrap_or("missing"),
+            "usage_keys": usage_field.map(keys).unwrap_or_default(),
+        }))
+    }
+
+    /// Emits `usage.unparsed` when a successful response yields no token counters, at most once per
+    /// provider/model every `UNPARSED_USAGE_REPORT_INTERVAL_MS`.
+    pub(super) fn report_unparsed_usage(&self, provider: &str, model: &str, response_obj: &Value) {
+        let Some(shape) = Self::unparsed_usage_shape(response_obj) else {
+            return;
+        };
+        let now = unix_ms();
+        {
+            let mut reported = self.unparsed_usage_reported_at.lock();
+            let key = (provider.to_string(), model.to_string());
+            if reported
+                .get(&key)
+                .is_some_and(|last| now.saturating_sub(*last) < UNPARSED_USAGE_REPORT_INTERVAL_MS)
+            {
+                return;
+            }
+            reported.insert(key, now);
+        }
+        self.events().emit(
+            provider,
+            EventCode::USAGE_UNPARSED,
+            "response had no parseable token usage; recorded as zero tokens",
+            serde_json::json!({
+                "model": model,
+                "shape": shape,
+            }),
+        );
+    }
+
     fn extract_model(response_obj: &Value) -> String {
         extract_response_model_option(response_obj).unwrap_or_else(|| "unknown".to_string())
     }
@@ -1240,7 +1424,7 @@ impl Store {
             "sse" => "sse",
             _ => "http",
         };
-        let ts = unix_ms();
+        let ts = self.recorded_unix_ms();
         let id = uuid::Uuid::new_v4().to_string();
         let session_id = context
             .session_id
@@ -1257,37 +1441,158 @@ impl Store {
             .map(str::trim)
             .filter(|s| !s.is_empty())
             .unwrap_or("");
+        let label = context
+            .label
+            .map(str::trim)
+            .filter(|s| !s.is_empty())
+            .unwrap_or("");
         if let Ok(ts_i64) = i64::try_from(ts) {
-            let conn = self.events_db.lock();
-            let _ = conn.execute(
-                "INSERT INTO usage_requests(
-                    id, unix_ms, ingested_at_unix_ms, provider, api_key_ref, model, origin, transport, session_id, node_id, node_name,
-                    input_tokens, output_tokens, total_tokens,
-                    cache_creation_input_tokens, cache_read_input_tokens
-                 ) VALUES(?1, ?2, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15)",
-                params![
+            let should_flush = {
+                let mut pending = self.pending_usage_requests.lock();
+                pending.push(PendingUsageRequest {
                     id,
-                    ts_i64,
-                    provider,
-                    context.api_key_ref.unwrap_or("-"),
-                    model,
+                    unix_ms: ts_i64,
+                    provider: provider.to_string(),
+                    api_key_ref: context.api_key_ref.unwrap_or("-").to_string(),
+                    model: model.to_string(),
                     origin,
                     transport,
-                    session_id,
-                    node_id,
-                    node_name,
-                    i64::try_from(increments.input_tokens).unwrap_or(i64::MAX),
-                    i64::try_from(increments.output_tokens).unwrap_or(i64::MAX),
-                    i64::try_from(increments.total_tokens).unwrap_or(i64::MAX),
-                    i64::try_from(increments.cache_creation_input_tokens).unwrap_or(i64::MAX),
-                    i64::try_from(increments.cache_read_input_tokens).unwrap_or(i64::MAX),
-                ],
-            );
+                    session_id: session_id.to_string(),
+                    node_id: node_id.to_string(),
+                    node_name: node_name.to_string(),
+                    increments,
+                    label: label.to_string(),
+                    request_bytes: context.request_bytes,
+                    response_bytes: context.response_bytes,
+                });
+                pending.len() >= USAGE_REQUEST_BATCH_MAX_ROWS
+                    || pending.first().is_some_and(|row| {
+                        ts_i64.saturating_sub(row.unix_ms)
+                            >= i64::try_from(USAGE_REQUEST_BATCH_MAX_DELAY_MS).unwrap_or(i64::MAX)
+                    })
+            };
+            if should_flush {
+                // Keep the request path off SQLite when a runtime is available.
+                match tokio::runtime::Handle::try_current() {
+                    Ok(handle) => {
+                        let store = self.clone();
+                        handle.spawn_blocking(move || store.flush_usage_requests());
+                    }
+                    Err(_) => {
+                        self.flush_usage_requests();
+                    }
+                }
+            }
         }
         self.bump_usage_day(provider, ts, increments);
         let _ = self.db.flush();
     }
 
+    /// Writes every buffered usage row in a single transaction. Returns the number written.
+    ///
+    /// Readers of `usage_requests` call this first so buffered rows are never invisible to them.
+    pub fn flush_usage_requests(&self) -> usize {
+        if self.pending_usage_requests.lock().is_empty() {
+            return 0;
+        }
+        let (written, dropped, overflowed) = {
+            // Hold the connection while draining so a concurrent reader cannot slip in between.
+            let mut conn = self.events_db.lock();
+            let rows = std::mem::take(&mut *self.pending_usage_requests.lock());
+            if rows.is_empty() {
+                return 0;
+            }
+            match Self::insert_usage_request_rows(&mut conn, &rows) {
+                Ok((written, dropped)) => (written, dropped, 0),
+                Err(err) => {
+                    // Nothing was committed: put the rows back ahead of newer ones for next time.
+                    log::warn!(
+                        "usage request flush failed; keeping {} rows buffered: {err}",
+                        rows.len()
+                    );
+                    let mut pending = self.pending_usage_requests.lock();
+                    let newer = std::mem::replace(&mut *pending, rows);
+                    pending.extend(newer);
+                    // Bound memory while inserts keep failing by giving up the oldest rows.
+                    let overflowed = pending.len().saturating_sub(USAGE_REQUEST_BUFFER_MAX_ROWS);
+                    pending.drain(..overflowed);
+                    (0, 0, overflowed)
+                }
+            }
+        };
+        if overflowed > 0 {
+            log::warn!("usage request buffer full; dropped {overflowed} oldest rows");
+            self.events().emit(
+                "gateway",
+                EventCode::USAGE_REQUESTS_BUFFER_OVERFLOW,
+                &format!("dropped {overflowed} oldest buffered usage rows after failed flushes"),
+                serde_json::json!({
+                    "dropped": overflowed,
+                    "buffered": USAGE_REQUEST_BUFFER_MAX_ROWS,
+                }),
+            );
+        }
+        if dropped > 0 {
+            self.events().emit(
+                "gateway",
+                EventCode::USAGE_REQUESTS_DROPPED,
+                &format!("dropped {dropped} usage rows that could not be inserted"),
+                serde_json::json!({ "dropped": dropped, "written": written }),
+            );
+        }
+        written
+    }
+
+    /// Inserts `rows` in one transaction. Returns `(written, dropped)`, where dropped rows failed
+    /// their own insert; an error means the transaction itself failed and nothing was written.
+    fn insert_usage_request_rows(
+        conn: &mut rusqlite::Connection,
+        rows: &[PendingUsageRequest],
+    ) -> rusqlite::Result<(usize, usize)> {
+        let tx = conn.transaction()?;
+        let mut written = 0;
+        {
+            let mut stmt = tx.prepare(
+                "INSERT INTO usage_requests(
+                    id, unix_ms, ingested_at_unix_ms, provider, api_key_ref, model, origin, transport, session_id, node_id, node_name,
+                    input_tokens, output_tokens, total_tokens,
+                    cache_creation_input_tokens, cache_read_input_tokens, label,
+                    request_bytes, response_bytes
+                 ) VALUES(
+                    ?1, ?2, ?2, ?3, ?4, ?5, ?6, ?7, ?8, ?9, ?10, ?11, ?12, ?13, ?14, ?15, ?16,
+                    ?17, ?18
+                 )",
+            )?;
+            for row in rows {
+                let inserted = stmt.execute(params![
+                    row.id,
+                    row.unix_ms,
+                    row.provider,
+                    row.api_key_ref,
+                    row.model,
+                    row.origin,
+                    row.transport,
+                    row.session_id,
+                    row.node_id,
+                    row.node_name,
+                    i64::try_from(row.increments.input_tokens).unwrap_or(i64::MAX),
+                    i64::try_from(row.increments.output_tokens).unwrap_or(i64::MAX),
+                    i64::try_from(row.increments.total_tokens).unwrap_or(i64::MAX),
+                    i64::try_from(row.increments.cache_creation_input_tokens).unwrap_or(i64::MAX),
+                    i64::try_from(row.increments.cache_read_input_tokens).unwrap_or(i64::MAX),
+                    row.label,
+                    i64::try_from(row.request_bytes).unwrap_or(i64::MAX),
+                    i64::try_from(row.response_bytes).unwrap_or(i64::MAX),
+                ]);
+                if inserted.is_ok() {
+                    written += 1;
+                }
+            }
+        }
+        tx.commit()?;
+        Ok((written, rows.len() - written))
+    }
+
     fn local_day_key(ts_unix_ms: u64) -> String {
         let ts = ts_unix_ms as i64;
         if let Some(dt) = Local.timestamp_millis_opt(ts).single() {
@@ -1297,6 +1602,26 @@ impl Store {
         }
     }
 
+    fn usage_day_value(
+        provider: &str,
+        day_key: &str,
+        req_count: u64,
+        totals: UsageTokenIncrements,
+        updated_at_unix_ms: u64,
+    ) -> Value {
+        serde_json::json!({
+            "provider": provider,
+            "day_key": day_key,
+            "req_count": req_count,
+            "input_tokens": totals.input_tokens,
+            "output_tokens": totals.output_tokens,
+            "total_tokens": totals.total_tokens,
+            "cache_creation_input_tokens": totals.cache_creation_input_tokens,
+            "cache_read_input_tokens": totals.cache_read_input_tokens,
+            "updated_at_unix_ms": updated_at_unix_ms
+        })
+    }
+
     fn bump_usage_day(&self, provider: &str, ts_unix_ms: u64, increments: UsageTokenIncrements) {
         let day_key = Self::local_day_key(ts_unix_ms);
         let key = format!("usage_day:{provider}:{day_key}");
@@ -1306,44 +1631,124 @@ impl Store {
             .ok()
             .flatten()
             .and_then(|v| serde_json::from_slice::<Value>(&v).ok())
-            .unwrap_or_else(|| {
-                serde_json::json!({
-                    "provider": provider,
-                    "day_key": day_key,
-                    "req_count": 0u64,
-                    "input_tokens": 0u64,
-                    "output_tokens": 0u64,
-                    "total_tokens": 0u64,
-                    "cache_creation_input_tokens": 0u64,
-                    "cache_read_input_tokens": 0u64,
-                    "updated_at_unix_ms": 0u64
-                })
-            });
-
-        let next = serde_json::json!({
-            "provider": provider,
-            "day_key": day_key,
-            "req_count": cur.get("req_count").and_then(|v| v.as_u64()).unwrap_or(0).saturating_add(1),
-            "input_tokens": cur.get("input_tokens").and_then(|v| v.as_u64()).unwrap_or(0).saturating_add(increments.input_tokens),
-            "output_tokens": cur.get("output_tokens").and_then(|v| v.as_u64()).unwrap_or(0).saturating_add(increments.output_tokens),
-            "total_tokens": cur.get("total_tokens").and_then(|v| v.as_u64()).unwrap_or(0).saturating_add(increments.total_tokens),
-            "cache_creation_input_tokens": cur
-                .get("cache_creation_input_tokens")
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0)
-                .saturating_add(increments.cache_creation_input_tokens),
-            "cache_read_input_tokens": cur
-                .get("cache_read_input_tokens")
-                .and_then(|v| v.as_u64())
-                .unwrap_or(0)
-                .saturating_add(increments.cache_read_input_tokens),
-            "updated_at_unix_ms": ts_unix_ms
-        });
+            .unwrap_or(Value::Null);
+        let field = |name: &str| cur.get(name).and_then(|v| v.as_u64()).unwrap_or(0);
+
+        let next = Self::usage_day_value(
+            provider,
+            &day_key,
+            field("req_count").saturating_add(1),
+            UsageTokenIncrements {
+                input_tokens: field("input_tokens").saturating_add(increments.input_tokens),
+                output_tokens: field("output_tokens").saturating_add(increments.output_tokens),
+                total_tokens: field("total_tokens").saturating_add(increments.total_tokens),
+                cache_creation_input_tokens: field("cache_creation_input_tokens")
+                    .saturating_add(increments.cache_creation_input_tokens),
+                cache_read_input_tokens: field("cache_read_input_tokens")
+                    .saturating_add(increments.cache_read_input_tokens),
+            },
+            ts_unix_ms,
+        );
         let _ = self.db.insert(
             key.as_bytes(),
             serde_json::to_vec(&next).unwrap_or_default(),
         );
     }
+
+    /// Recomputes `usage_day` aggregates from the raw `usage_requests` rows and overwrites every
+    /// day that still has raw rows. Days up to the newest row dropped by the size cap keep their
+    /// stored aggregate, since their raw rows may be only partly left. Returns the number of days
+    /// rebuilt.
+    pub fn rebuild_usage_day_aggregates(&self, provider: Option<&str>) -> usize {
+        let (evicted_through_unix_ms, rows) = self.with_events_read_conn(|conn| {
+            let evicted_through_unix_ms = conn
+                .query_row(
+                    "SELECT value FROM event_meta WHERE key=?1",
+                    [Self::USAGE_REQUESTS_EVICTED_THROUGH_KEY],
+                    |row| row.get::<_, String>(0),
+                )
+                .optional()
+                .ok()
+                .flatten()
+                .and_then(|value| value.parse::<u64>().ok());
+            let mut out = Vec::new();
+            let Ok(mut stmt) = conn.prepare(
+                "SELECT
+                   provider,
+                   unix_ms,
+                   input_tokens,
+                   output_tokens,
+                   total_tokens,
+                   cache_creation_input_tokens,
+                   cache_read_input_tokens
+                 FROM usage_requests
+                 WHERE ?1 IS NULL OR provider = ?1",
+            ) else {
+                return (evicted_through_unix_ms, out);
+            };
+            let Ok(rows) = stmt.query_map(params![provider], |row| {
+                let column = |idx: usize| -> rusqlite::Result<u64> {
+                    Ok(u64::try_from(row.get::<_, i64>(idx)?).unwrap_or(0))
+                };
+                Ok((
+                    row.get::<_, String>(0)?,
+                    column(1)?,
+                    UsageTokenIncrements {
+                        input_tokens: column(2)?,
+                        output_tokens: column(3)?,
+                        total_tokens: column(4)?,
+                        cache_creation_input_tokens: column(5)?,
+                        cache_read_input_tokens: column(6)?,
+                    },
+                ))
+            }) else {
+                return (evicted_through_unix_ms, out);
+            };
+            out.extend(rows.flatten());
+            (evicted_through_unix_ms, out)
+        });
+        let evicted_through_day = evicted_through_unix_ms.map(Self::local_day_key);
+
+        let mut days: std::collections::BTreeMap<
+            (String, String),
+            (u64, UsageTokenIncrements, u64),
+        > = std::collections::BTreeMap::new();
+        for (row_provider, unix_ms, increments) in rows {
+            let day_key = Self::local_day_key(unix_ms);
+            if evicted_through_day
+                .as_ref()
+                .is_some_and(|through| day_key <= *through)
+            {
+                continue;
+            }
+            let (req_count, totals, updated_at) = days.entry((row_provider, day_key)).or_default();
+            *req_count = req_count.saturating_add(1);
+            totals.input_tokens = totals.input_tokens.saturating_add(increments.input_tokens);
+            totals.output_tokens = totals
+                .output_tokens
+                .saturating_add(increments.output_tokens);
+            totals.total_tokens = totals.total_tokens.saturating_add(increments.total_tokens);
+            totals.cache_creation_input_tokens = totals
+                .cache_creation_input_tokens
+                .saturating_add(increments.cache_creation_input_tokens);
+            totals.cache_read_input_tokens = totals
+                .cache_read_input_tokens
+                .saturating_add(increments.cache_read_input_tokens);
+            *updated_at = (*updated_at).max(unix_ms);
+        }
+
+        for ((row_provider, day_key), (req_count, totals, updated_at)) in &days {
+            let key = format!("usage_day:{row_provider}:{day_key}");
+            let value =
+                Self::usage_day_value(row_provider, day_key, *req_count, *totals, *updated_at);
+            let _ = self.db.insert(
+                key.as_bytes(),
+                serde_json::to_vec(&value).unwrap_or_default(),
+            );
+        }
+        let _ = self.db.flush();
+        days.len()
+    }
 }
 
 #[cfg(test)]
@@ -1371,6 +1776,8 @@ mod tests {
                 }],
                 gap_fill_mode: Some("per_request".to_string()),
                 gap_fill_amount_usd: Some(0.04),
+                tiers: Vec::new(),
+                model_rates: std::collections::BTreeMap::new(),
             },
         )]);
 
@@ -1401,6 +1808,8 @@ mod tests {
                     }],
                     gap_fill_mode: None,
                     gap_fill_amount_usd: None,
+                    tiers: Vec::new(),
+                    model_rates: std::collections::BTreeMap::new(),
                 },
             ),
             (
@@ -1418,6 +1827,8 @@ mod tests {
                     }],
                     gap_fill_mode: None,
                     gap_fill_amount_usd: None,
+                    tiers: Vec::new(),
+                    model_rates: std::collections::BTreeMap::new(),
                 },
             ),
         ]);
@@ -1523,6 +1934,41 @@ mod tests {
         );
     }
 
+    #[test]
+    fn remove_spend_manual_days_in_range_is_inclusive_and_provider_scoped() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let store = open_store_dir(tmp.path().join("data")).expect("store");
+        for (provider, day_key) in [
+            ("provider_1", "2026-04-01"),
+            ("provider_1", "2026-04-02"),
+            ("provider_1", "2026-04-03"),
+            ("provider_1", "2026-04-04"),
+            ("provider_2", "2026-04-02"),
+        ] {
+            store.put_spend_manual_day(
+                provider,
+                day_key,
+                &serde_json::json!({ "provider": provider, "day_key": day_key }),
+            );
+        }
+
+        let removed =
+            store.remove_spend_manual_days_in_range("provider_1", "2026-04-02", "2026-04-03");
+
+        assert_eq!(removed, vec!["2026-04-02", "2026-04-03"]);
+        let remaining: Vec<_> = store
+            .list_local_spend_manual_days("provider_1")
+            .iter()
+            .filter_map(|row| {
+                row.get("day_key")
+                    .and_then(Value::as_str)
+                    .map(str::to_string)
+            })
+            .collect();
+        assert_eq!(remaining, vec!["2026-04-01", "2026-04-04"]);
+        assert_eq!(store.list_local_spend_manual_days("provider_2").len(), 1);
+    }
+
     #[test]
     fn migrate_legacy_remote_usage_sources_moves_remote_rows_out_of_local_tables() {
         let tmp = tempfile::tempdir().expect("tempdir");
@@ -1837,4 +2283,227 @@ mod tests {
         assert!(providers.contains(&"usage-only-provider".to_string()));
         assert!(providers.contains(&"legacy-provider".to_string()));
     }
+
+    #[test]
+    fn failing_usage_request_flushes_keep_only_the_newest_buffered_rows() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let store = open_store_dir(tmp.path().join("data")).expect("store");
+        store
+            .events_db
+            .lock()
+            .execute_batch("ALTER TABLE usage_requests RENAME TO usage_requests_offline")
+            .expect("rename usage_requests");
+        let row = |idx: usize| PendingUsageRequest {
+            id: format!("row-{idx}"),
+            unix_ms: idx as i64,
+            provider: "p1".to_string(),
+            api_key_ref: "-".to_string(),
+            model: "gpt-5.2".to_string(),
+            origin: crate::constants::USAGE_ORIGIN_WINDOWS,
+            transport: "http",
+            session_id: "s1".to_string(),
+            node_id: String::new(),
+            node_name: String::new(),
+            increments: UsageTokenIncrements::default(),
+            label: String::new(),
+            request_bytes: 0,
+            response_bytes: 0,
+        };
+        store
+            .pending_usage_requests
+            .lock()
+            .extend((0..USAGE_REQUEST_BUFFER_MAX_ROWS + 5).map(row));
+
+        assert_eq!(store.flush_usage_requests(), 0);
+        {
+            let pending = store.pending_usage_requests.lock();
+            assert_eq!(pending.len(), USAGE_REQUEST_BUFFER_MAX_ROWS);
+            assert_eq!(pending[0].id, "row-5");
+        }
+        let events = store.list_events_range(None, None, Some(10));
+        assert!(events.iter().any(|event| {
+            event["code"] == "usage.requests_buffer_overflow" && event["fields"]["dropped"] == 5
+        }));
+    }
+
+    #[test]
+    fn rebuild_usage_day_aggregates_keeps_days_touched_by_size_cap_eviction() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let store = open_store_dir(tmp.path().join("data")).expect("store");
+        let at = |day: u32, hour: u32| {
+            Local
+                .with_ymd_and_hms(2026, 4, day, hour, 0, 0)
+                .single()
+                .expect("local time")
+                .timestamp_millis()
+        };
+        let insert = |id: &str, ts: i64| {
+            store
+                .events_db
+                .lock()
+                .execute(
+                    "INSERT INTO usage_requests(
+                        id, unix_ms, ingested_at_unix_ms, provider, api_key_ref, model, origin, session_id, node_id, node_name,
+                        input_tokens, output_tokens, total_tokens, cache_creation_input_tokens, cache_read_input_tokens
+                    ) VALUES(?1, ?2, ?2, 'p1', '-', 'gpt-5.2', 'windows', '', '', '', 10, 0, 10, 0, 0)",
+                    params![id, ts],
+                )
+                .expect("insert usage request");
+        };
+        insert("req-1", at(2, 9));
+        assert_eq!(
+            store
+                .evict_to_size_cap(1, true)
+                .expect("evict")
+                .usage_requests_deleted,
+            1
+        );
+        // The rest of April 2 survived the cap; April 3 was never touched by it.
+        insert("req-2", at(2, 18));
+        insert("req-3", at(3, 9));
+        store
+            .db
+            .insert(
+                b"usage_day:p1:2026-04-02",
+                serde_json::to_vec(&serde_json::json!({
+                    "provider": "p1",
+                    "day_key": "2026-04-02",
+                    "req_count": 2,
+                    "total_tokens": 20
+                }))
+                .expect("sled json"),
+            )
+            .expect("insert usage_day");
+
+        assert_eq!(store.rebuild_usage_day_aggregates(Some("p1")), 1);
+        let days = store.list_usage_days("p1");
+        let day = |key: &str| {
+            days.iter()
+                .find(|row| row["day_key"] == key)
+                .expect("usage day")
+                .clone()
+        };
+        assert_eq!(day("2026-04-02")["req_count"], 2);
+        assert_eq!(day("2026-04-03")["req_count"], 1);
+    }
+
+    #[test]
+    fn rebuild_usage_day_aggregates_overwrites_drifted_days_from_raw_rows() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let store = open_store_dir(tmp.path().join("data")).expect("store");
+        let day = Local
+            .with_ymd_and_hms(2026, 4, 2, 12, 0, 0)
+            .single()
+            .expect("local time")
+            .timestamp_millis();
+        {
+            let conn = store.events_db.lock();
+            let insert = |id: &str, ts: i64, provider: &str, total: i64| {
+                conn.execute(
+                    "INSERT INTO usage_requests(
+                        id, unix_ms, ingested_at_unix_ms, provider, api_key_ref, model, origin, session_id, node_id, node_name,
+                        input_tokens, output_tokens, total_tokens, cache_creation_input_tokens, cache_read_input_tokens
+                    ) VALUES(?1, ?2, ?2, ?3, '-', 'gpt-5.2', 'windows', '', '', '', ?4, 0, ?4, 0, 1)",
+                    params![id, ts, provider, total],
+                )
+                .expect("insert usage request");
+            };
+            insert("req-1", day, "p1", 100);
+            insert("req-2", day + 1_000, "p1", 200);
+            insert("req-3", day, "p2", 50);
+        }
+        let put_day = |provider: &str, day_key: &str, req_count: u64| {
+            store
+                .db
+                .insert(
+                    format!("usage_day:{provider}:{day_key}").as_bytes(),
+                    serde_json::to_vec(&serde_json::json!({
+                        "provider": provider,
+                        "day_key": day_key,
+                        "req_count": req_count,
+                        "total_tokens": 1
+                    }))
+                    .expect("sled json"),
+                )
+                .expect("insert usage_day");
+        };
+        put_day("p1", "2026-04-02", 7);
+        put_day("p1", "2026-03-01", 3);
+        put_day("p2", "2026-04-02", 9);
+
+        assert_eq!(store.rebuild_usage_day_aggregates(Some("p1")), 1);
+        let p1_days = store.list_usage_days("p1");
+        let rebuilt = p1_days
+            .iter()
+            .find(|row| row["day_key"] == "2026-04-02")
+            .expect("rebuilt day");
+        assert_eq!(rebuilt["req_count"], 2);
+        assert_eq!(rebuilt["total_tokens"], 300);
+        assert_eq!(rebuilt["cache_read_input_tokens"], 2);
+        assert_eq!(rebuilt["updated_at_unix_ms"], day + 1_000);
+        // Days without raw rows left are kept as they were.
+        assert!(p1_days
+            .iter()
+            .any(|row| row["day_key"] == "2026-03-01" && row["req_count"] == 3));
+        assert_eq!(store.list_usage_days("p2")[0]["req_count"], 9);
+
+        assert_eq!(store.rebuild_usage_day_aggregates(None), 2);
+        assert_eq!(store.list_usage_days("p2")[0]["req_count"], 1);
+    }
+
+    #[test]
+    fn extract_usage_tokens_normalizes_anthropic_usage_to_openai_accounting() {
+        let openai = serde_json::json!({
+            "usage": {
+                "input_tokens": 1_000,
+                "input_tokens_details": { "cached_tokens": 800 },
+                "output_tokens": 200,
+                "total_tokens": 1_200
+            }
+        });
+        let anthropic = serde_json::json!({
+            "usage": {
+                "input_tokens": 200,
+                "cache_creation_input_tokens": 0,
+                "cache_read_input_tokens": 800,
+                "output_tokens": 200
+            }
+        });
+        let anthropic_top_level = serde_json::json!({
+            "type": "message",
+            "input_tokens": 200,
+            "cache_creation_input_tokens": 0,
+            "cache_read_input_tokens": 800,
+            "output_tokens": 200
+        });
+        let anthropic_message_start = serde_json::json!({
+            "type": "message_start",
+            "message": { "usage": anthropic["usage"].clone() }
+        });
+
+        let expected = (1_000, 200, 1_200, 0, 800);
+        assert_eq!(Store::extract_usage_tokens(&openai), expected);
+        assert_eq!(Store::extract_usage_tokens(&anthropic), expected);
+        assert_eq!(Store::extract_usage_tokens(&anthropic_top_level), expected);
+        assert_eq!(
+            Store::extract_usage_tokens(&anthropic_message_start),
+            expected
+        );
+    }
+
+    #[test]
+    fn extract_usage_tokens_counts_anthropic_cache_writes_as_input() {
+        let anthropic = serde_json::json!({
+            "usage": {
+                "input_tokens": 50,
+                "cache_creation_input_tokens": 300,
+                "cache_read_input_tokens": 150,
+                "output_tokens": 25
+            }
+        });
+        assert_eq!(
+            Store::extract_usage_tokens(&anthropic),
+            (500, 25, 525, 300, 150)
+        );
+    }
 }
@@ -1,19 +1,18 @@
 use reqwest::header::ACCEPT;
-use reqwest::header::{HeaderMap, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
+use reqwest::header::{HeaderMap, HeaderName, HeaderValue, AUTHORIZATION, CONTENT_TYPE};
 use serde_json::Map;
 use serde_json::Value;
 use std::pin::Pin;
+use std::sync::Arc;
 use tokio_tungstenite::tungstenite::client::IntoClientRequest;
 use tokio_tungstenite::tungstenite::protocol::Message as WsMessage;
 
-use super::config::ProviderConfig;
+use super::config::{ProviderConfig, RoutingConfig};
 
 pub(crate) const RESPONSES_ENDPOINT: &str = "/responses";
 pub(crate) const MODELS_ENDPOINT: &str = "/models";
 pub(crate) const REALTIME_ENDPOINT: &str = "/realtime";
 
-const WEBSOCKET_CONNECT_TIMEOUT_SECONDS: u64 = 10;
-
 pub struct WebSocketResponseResult {
     pub response: Value,
 }
@@ -22,9 +21,34 @@ pub struct WebSocketSseStreamResult {
     pub stream: Pin<Box<dyn futures_util::Stream<Item = Result<bytes::Bytes, String>> + Send>>,
 }
 
+/// Connect and idle-read timeouts applied to every upstream request, plus the overall
+/// deadline for non-streaming requests.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct UpstreamTimeouts {
+    pub connect_seconds: u64,
+    pub read_seconds: u64,
+    pub request_seconds: u64,
+}
+
+impl UpstreamTimeouts {
+    pub fn from_routing(routing: &RoutingConfig) -> Self {
+        Self {
+            connect_seconds: routing.effective_connect_timeout_seconds(),
+            read_seconds: routing.effective_read_timeout_seconds(),
+            request_seconds: routing.request_timeout_seconds.max(1),
+        }
+    }
+
+    pub fn request_timeout(&self) -> std::time::Duration {
+        std::time::Duration::from_secs(self.request_seconds)
+    }
+}
+
 #[derive(Clone)]
 pub struct UpstreamClient {
-    client: reqwest::Client,
+    // reqwest only supports connect/read timeouts per client, so the client is rebuilt
+    // whenever the configured timeouts change.
+    client: Arc<Mutex<Option<(UpstreamTimeouts, reqwest::Client)>>>,
 }
 
 fn build_upstream_url(base_url: &str, path: &str) -> String {
@@ -44,6 +68,22 @@ fn apply_auth_headers(headers: &mut HeaderMap, api_key: Option<&str>, client_aut
     }
 }
 
+/// Adds the provider's configured extra headers without replacing ones already set
+/// (content type, auth); invalid names or values are skipped.
+fn apply_provider_headers(headers: &mut HeaderMap, provider: &ProviderConfig) {
+    for (name, value) in &provider.headers {
+        let (Ok(name), Ok(value)) = (
+            HeaderName::from_bytes(name.trim().as_bytes()),
+            HeaderValue::from_str(value.trim()),
+        ) else {
+            continue;
+        };
+        if !headers.contains_key(&name) {
+            headers.insert(name, value);
+        }
+    }
+}
+
 fn build_realtime_ws_url(payload: &Value, provider: &ProviderConfig) -> Result<String, String> {
     let http_url = build_upstream_url(&provider.base_url, REALTIME_ENDPOINT);
     let mut url = reqwest::Url::parse(&http_url).map_err(|e| e.to_string())?;
@@ -127,13 +167,28 @@ fn websocket_response_failure(response: &Value) -> Option<String> {
 
 impl UpstreamClient {
     pub fn new() -> Self {
+        Self {
+            client: Arc::new(Mutex::new(None)),
+        }
+    }
+
+    fn client_for(&self, timeouts: UpstreamTimeouts) -> reqwest::Client {
+        let mut cached = self.client.lock();
+        if let Some((current, client)) = cached.as_ref() {
+            if *current == timeouts {
+                return client.clone();
+            }
+        }
         let client = reqwest::Client::builder()
             .user_agent("api-router/0.1")
             // Avoid hanging forever on broken upstream TCP handshakes.
-            .connect_timeout(std::time::Duration::from_secs(10))
+            .connect_timeout(std::time::Duration::from_secs(timeouts.connect_seconds))
+            // Idle timeout per read, so long responses survive as long as bytes keep arriving.
+            .read_timeout(std::time::Duration::from_secs(timeouts.read_seconds))
             .build()
             .expect("reqwest client");
-        Self { client }
+        *cached = Some((timeouts, client.clone()));
+        client
     }
 
     pub async fn post_json(
@@ -143,25 +198,35 @@ impl UpstreamClient {
         payload: &Value,
         api_key: Option<&str>,
         client_auth: Option<&str>,
-        timeout_seconds: u64,
+        timeouts: UpstreamTimeouts,
     ) -> Result<(u16, Value), reqwest::Error> {
         let url = build_upstream_url(&provider.base_url, path);
         let mut headers = HeaderMap::new();
         headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
         apply_auth_headers(&mut headers, api_key, client_auth);
-
-        let r = self
-            .client
-            .post(url)
-            .headers(headers)
-            .timeout(std::time::Duration::from_secs(timeout_seconds))
-            .json(payload)
-            .send()
-            .await?;
-
-        let status = r.status().as_u16();
-        let j = r.json::<Value>().await.unwrap_or(Value::Null);
-        Ok((status, j))
+        apply_provider_headers(&mut headers, provider);
+
+        let call = async {
+            let r = self
+                .client_for(timeouts)
+                .post(url)
+                .headers(headers)
+                .timeout(timeouts.request_timeout())
+                .json(payload)
+                .send()
+                .await?;
+
+            let status = r.status().as_u16();
+            let j = r.json::<Value>().await.unwrap_or(Value::Null);
+            Ok::<_, reqwest::Error>((status, j))
+        };
+        super::otel::trace_upstream_call(
+            "http",
+            path,
+            |(status, _): &(u16, Value)| Some(*status),
+            call,
+        )
+        .await
     }
 
     pub async fn post_sse(
@@ -171,23 +236,30 @@ impl UpstreamClient {
         payload: &Value,
         api_key: Option<&str>,
         client_auth: Option<&str>,
-        timeout_seconds: u64,
+        timeouts: UpstreamTimeouts,
     ) -> Result<reqwest::Response, reqwest::Error> {
         let url = build_upstream_url(&provider.base_url, path);
         let mut headers = HeaderMap::new();
         headers.insert(CONTENT_TYPE, HeaderValue::from_static("application/json"));
         headers.insert(ACCEPT, HeaderValue::from_static("text/event-stream"));
         apply_auth_headers(&mut headers, api_key, client_auth);
+        apply_provider_headers(&mut headers, provider);
 
-        let _ = timeout_seconds;
         // Do NOT set a total request timeout for streaming; it would abort long-running streams
-        // even when data is flowing. The gateway applies an idle timeout while relaying chunks.
-        self.client
+        // even when data is flowing. Only the client's idle read timeout applies.
+        let call = self
+            .client_for(timeouts)
             .post(url)
             .headers(headers)
             .json(payload)
-            .send()
-            .await
+            .send();
+        super::otel::trace_upstream_call(
+            "http",
+            path,
+            |r: &reqwest::Response| Some(r.status().as_u16()),
+            call,
+        )
+        .await
     }
 
     pub async fn get_json(
@@ -196,17 +268,18 @@ impl UpstreamClient {
         path: &str,
         api_key: Option<&str>,
         client_auth: Option<&str>,
-        timeout_seconds: u64,
+        timeouts: UpstreamTimeouts,
     ) -> Result<(u16, Value), reqwest::Error> {
         let url = build_upstream_url(&provider.base_url, path);
         let mut headers = HeaderMap::new();
         apply_auth_headers(&mut headers, api_key, client_auth);
+        apply_provider_headers(&mut headers, provider);
 
         let r = self
-            .client
+            .client_for(timeouts)
             .get(url)
             .headers(headers)
-            .timeout(std::time::Duration::from_secs(timeout_seconds))
+            .timeout(timeouts.request_timeout())
             .send()
             .await?;
         let status = r.status().as_u16();
@@ -220,7 +293,25 @@ impl UpstreamClient {
         payload: &Value,
         api_key: Option<&str>,
         client_auth: Option<&str>,
-        timeout_seconds: u64,
+        timeouts: UpstreamTimeouts,
+    ) -> Result<WebSocketResponseResult, String> {
+        let call = self.post_json_via_websocket_untraced(
+            provider,
+            payload,
+            api_key,
+            client_auth,
+            timeouts,
+        );
+        super::otel::trace_upstream_call("websocket", "realtime", |_| None, call).await
+    }
+
+    async fn post_json_via_websocket_untraced(
+        &self,
+        provider: &ProviderConfig,
+        payload: &Value,
+        api_key: Option<&str>,
+        client_auth: Option<&str>,
+        timeouts: UpstreamTimeouts,
     ) -> Result<WebSocketResponseResult, String> {
         let ws_url = build_realtime_ws_url(payload, provider)?;
         let mut request = ws_url
@@ -229,9 +320,10 @@ impl UpstreamClient {
         let headers = request.headers_mut();
         headers.insert("OpenAI-Beta", HeaderValue::from_static("realtime=v1"));
         apply_auth_headers(headers, api_key, client_auth);
+        apply_provider_headers(headers, provider);
 
         let (mut socket, _) = tokio::time::timeout(
-            std::time::Duration::from_secs(WEBSOCKET_CONNECT_TIMEOUT_SECONDS),
+            std::time::Duration::from_secs(timeouts.connect_seconds),
             tokio_tungstenite::connect_async(request),
         )
         .await
@@ -245,7 +337,7 @@ impl UpstreamClient {
 
         loop {
             let next = tokio::time::timeout(
-                std::time::Duration::from_secs(timeout_seconds),
+                std::time::Duration::from_secs(timeouts.read_seconds),
                 futures_util::StreamExt::next(&mut socket),
             )
             .await
@@ -292,7 +384,20 @@ impl UpstreamClient {
         payload: &Value,
         api_key: Option<&str>,
         client_auth: Option<&str>,
-        timeout_seconds: u64,
+        timeouts: UpstreamTimeouts,
+    ) -> Result<WebSocketSseStreamResult, String> {
+        let call =
+            self.post_sse_via_websocket_untraced(provider, payload, api_key, client_auth, timeouts);
+        super::otel::trace_upstream_call("websocket", "realtime", |_| None, call).await
+    }
+
+    async fn post_sse_via_websocket_untraced(
+        &self,
+        provider: &ProviderConfig,
+        payload: &Value,
+        api_key: Option<&str>,
+        client_auth: Option<&str>,
+        timeouts: UpstreamTimeouts,
     ) -> Result<WebSocketSseStreamResult, String> {
         let ws_url = build_realtime_ws_url(payload, provider)?;
         let mut request = ws_url
@@ -301,9 +406,10 @@ impl UpstreamClient {
         let headers = request.headers_mut();
         headers.insert("OpenAI-Beta", HeaderValue::from_static("realtime=v1"));
         apply_auth_headers(headers, api_key, client_auth);
+        apply_provider_headers(headers, provider);
 
         let (mut socket, _) = tokio::time::timeout(
-            std::time::Duration::from_secs(WEBSOCKET_CONNECT_TIMEOUT_SECONDS),
+            std::time::Duration::from_secs(timeouts.connect_seconds),
             tokio_tungstenite::connect_async(request),
         )
         .await
@@ -318,7 +424,7 @@ impl UpstreamClient {
         let stream = async_stream::stream! {
             loop {
                 let next = tokio::time::timeout(
-                    std::time::Duration::from_secs(timeout_seconds),
+                    std::time::Duration::from_secs(timeouts.read_seconds),
                     futures_util::StreamExt::next(&mut socket),
                 )
                 .await;
@@ -455,6 +561,14 @@ mod tests {
             usage_adapter: String::new(),
             usage_base_url: None,
             api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
         };
         let result = client
             .post_json_via_websocket(
@@ -466,7 +580,11 @@ mod tests {
                 }),
                 Some("sk-test"),
                 None,
-                5,
+                UpstreamTimeouts {
+                    connect_seconds: 5,
+                    read_seconds: 5,
+                    request_seconds: 5,
+                },
             )
             .await
             .expect("websocket response");
@@ -556,4 +674,43 @@ mod tests {
             "https://api.openai.com/v1/models"
         );
     }
+
+    #[test]
+    fn apply_provider_headers_adds_extras_without_overriding_auth() {
+        let mut provider = ProviderConfig {
+            display_name: "P1".to_string(),
+            base_url: "https://example.com/v1".to_string(),
+            group: None,
+            disabled: false,
+            supports_websockets: false,
+            usage_adapter: String::new(),
+            usage_base_url: None,
+            api_key: String::new(),
+            model_aliases: std::collections::BTreeMap::new(),
+            system_prefix: None,
+            usage_field_map: None,
+            headers: std::collections::BTreeMap::new(),
+            routing_eligible: true,
+            default_model: None,
+            tier: 0,
+            notes: String::new(),
+        };
+        provider
+            .headers
+            .insert("X-Team".to_string(), "router".to_string());
+        provider
+            .headers
+            .insert("Authorization".to_string(), "Bearer other".to_string());
+        provider
+            .headers
+            .insert("bad header".to_string(), "x".to_string());
+
+        let mut headers = HeaderMap::new();
+        apply_auth_headers(&mut headers, Some("sk-test"), None);
+        apply_provider_headers(&mut headers, &provider);
+
+        assert_eq!(headers.get("x-team").unwrap(), "router");
+        assert_eq!(headers.get(AUTHORIZATION).unwrap(), "Bearer sk-test");
+        assert_eq!(headers.len(), 2);
+    }
 }
@@ -124,6 +124,8 @@ fn spawn_monitor_impl(app: &tauri::AppHandle, state: LocalNetworkState) {
             std::thread::sleep(std::time::Duration::from_secs(1));
             continue;
         }
+        // Address changes include the WSL virtual adapter, whose gateway IP can move.
+        crate::platform::wsl_gateway_host::invalidate_wsl_gateway_host();
         let Ok(next_online) = detect_online() else {
             let _ = state.refresh_from_system();
             continue;
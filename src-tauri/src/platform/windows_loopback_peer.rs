@@ -35,6 +35,12 @@ pub fn read_process_cwd(_pid: u32) -> Option<std::path::PathBuf> {
     None
 }
 
+#[cfg(not(windows))]
+#[allow(dead_code)]
+pub fn read_process_image_name(_pid: u32) -> Option<String> {
+    None
+}
+
 #[cfg(not(windows))]
 #[allow(dead_code)]
 pub fn list_process_ids_by_name(_names: &[&str]) -> Vec<u32> {
@@ -47,6 +53,51 @@ pub fn visible_window_title(_pid: u32) -> Option<String> {
     None
 }
 
+const PROCESS_IMAGE_NAME_TTL: std::time::Duration = std::time::Duration::from_secs(10);
+const PROCESS_IMAGE_NAME_CACHE_MAX: usize = 256;
+
+#[derive(Default)]
+struct ProcessImageNameCache {
+    entries: std::collections::HashMap<u32, (Option<String>, std::time::Instant)>,
+}
+
+impl ProcessImageNameCache {
+    fn get_or_lookup(
+        &mut self,
+        pid: u32,
+        now: std::time::Instant,
+        lookup: impl FnOnce(u32) -> Option<String>,
+    ) -> Option<String> {
+        if let Some((name, at)) = self.entries.get(&pid) {
+            if now.duration_since(*at) < PROCESS_IMAGE_NAME_TTL {
+                return name.clone();
+            }
+        }
+        let name = lookup(pid);
+        if self.entries.len() >= PROCESS_IMAGE_NAME_CACHE_MAX {
+            self.entries
+                .retain(|_, (_, at)| now.duration_since(*at) < PROCESS_IMAGE_NAME_TTL);
+        }
+        self.entries.insert(pid, (name.clone(), now));
+        name
+    }
+}
+
+/// Executable file name (e.g. `node.exe`) of `pid`, cached briefly so busy loopback
+/// connections do not re-open the process on every request.
+pub fn cached_process_image_name(pid: u32) -> Option<String> {
+    static CACHE: std::sync::OnceLock<std::sync::Mutex<ProcessImageNameCache>> =
+        std::sync::OnceLock::new();
+    if pid == 0 {
+        return None;
+    }
+    let mut cache = CACHE
+        .get_or_init(Default::default)
+        .lock()
+        .unwrap_or_else(|e| e.into_inner());
+    cache.get_or_lookup(pid, std::time::Instant::now(), read_process_image_name)
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct VisibleWindowSnapshot {
     pub hwnd: isize,
@@ -110,7 +161,8 @@ mod windows_impl {
         TH32CS_SNAPPROCESS,
     };
     use windows_sys::Win32::System::Threading::{
-        GetExitCodeProcess, OpenProcess, PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_VM_READ,
+        GetExitCodeProcess, OpenProcess, QueryFullProcessImageNameW, PROCESS_NAME_WIN32,
+        PROCESS_QUERY_LIMITED_INFORMATION, PROCESS_VM_READ,
     };
     use windows_sys::Win32::UI::Accessibility::{SetWinEventHook, UnhookWinEvent};
     use windows_sys::Win32::UI::WindowsAndMessaging::{
@@ -225,6 +277,28 @@ mod windows_impl {
         }
     }
 
+    pub fn read_process_image_name(pid: u32) -> Option<String> {
+        let mut buf = [0u16; 1024];
+        let mut len = buf.len() as u32;
+        let ok = unsafe {
+            let h = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION, 0, pid);
+            if h == 0 {
+                return None;
+            }
+            let ok = QueryFullProcessImageNameW(h, PROCESS_NAME_WIN32, buf.as_mut_ptr(), &mut len);
+            let _ = CloseHandle(h);
+            ok
+        };
+        if ok == 0 || len == 0 {
+            return None;
+        }
+        let path = widestr_to_string(&buf[..len as usize]);
+        let name = std::path::Path::new(&path)
+            .file_name()
+            .map(|n| n.to_string_lossy().to_string())?;
+        (!name.is_empty()).then_some(name)
+    }
+
     pub fn read_process_cwd(pid: u32) -> Option<std::path::PathBuf> {
         unsafe {
             let h = OpenProcess(PROCESS_QUERY_LIMITED_INFORMATION | PROCESS_VM_READ, 0, pid);
@@ -797,10 +871,38 @@ mod windows_impl {
 #[cfg(windows)]
 pub use windows_impl::{
     infer_loopback_peer_pid, is_pid_alive, list_process_ids_by_name, list_visible_windows,
-    read_process_command_line, read_process_cwd, read_process_env_var, visible_window_title,
-    watch_visible_window_show_events,
+    read_process_command_line, read_process_cwd, read_process_env_var, read_process_image_name,
+    visible_window_title, watch_visible_window_show_events,
 };
 
+#[cfg(test)]
+mod cache_tests {
+    use super::*;
+
+    #[test]
+    fn process_image_name_cache_reuses_lookup_until_ttl() {
+        let mut cache = ProcessImageNameCache::default();
+        let start = std::time::Instant::now();
+        let mut calls = 0;
+        let mut lookup = |now| {
+            cache.get_or_lookup(42, now, |_| {
+                calls += 1;
+                Some("node.exe".to_string())
+            })
+        };
+        assert_eq!(lookup(start).as_deref(), Some("node.exe"));
+        assert_eq!(
+            lookup(start + std::time::Duration::from_secs(1)).as_deref(),
+            Some("node.exe")
+        );
+        assert_eq!(
+            lookup(start + PROCESS_IMAGE_NAME_TTL).as_deref(),
+            Some("node.exe")
+        );
+        assert_eq!(calls, 2);
+    }
+}
+
 #[cfg(all(test, windows))]
 mod tests {
     use super::*;
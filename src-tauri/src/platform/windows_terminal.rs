@@ -3,7 +3,9 @@
 //! This module infers a stable terminal-session marker for Codex requests by mapping the loopback
 //! TCP connection to the owning process PID, then reading the process environment. When
 //! `WT_SESSION` is unavailable, it falls back to a `pid:<pid>` marker so ordinary Windows console
-//! hosts can still participate in runtime session sync.
+//! hosts can still participate in runtime session sync. The VS Code Codex extension runs
+//! `codex.exe app-server` under the extension host instead of a terminal; those processes get a
+//! `vscode:<pid>` marker, analogous to the `wsl:` prefix used for WSL sessions.
 
 #[cfg(windows)]
 use std::io::BufRead;
@@ -39,6 +41,8 @@ pub struct InferredWtSession {
 pub struct SessionDiscoverySnapshot {
     pub items: Vec<InferredWtSession>,
     pub fresh: bool,
+    /// Time since the last completed scan; `None` until the first scan finishes.
+    pub age_ms: Option<u64>,
 }
 
 #[cfg(windows)]
@@ -53,6 +57,38 @@ pub(crate) fn terminal_session_marker(wt_session: Option<&str>, pid: u32) -> Opt
     (pid > 0).then(|| format!("pid:{pid}"))
 }
 
+/// Marker for a Codex `app-server` process started by the VS Code extension. Requires either the
+/// extension's bundled binary path in argv or the extension-host environment, so app-server
+/// processes launched elsewhere (e.g. a terminal inside VS Code) stay excluded.
+#[cfg(windows)]
+fn vscode_extension_session_marker_from(
+    cmd: &str,
+    pid: u32,
+    extension_host_env: bool,
+) -> Option<String> {
+    let cmd = cmd.replace('\\', "/").to_ascii_lowercase();
+    if pid == 0 || !cmd.contains("app-server") {
+        return None;
+    }
+    let bundled_binary = cmd.contains("/extensions/openai.chatgpt-");
+    (bundled_binary || extension_host_env).then(|| format!("vscode:{pid}"))
+}
+
+#[cfg(windows)]
+pub(crate) fn vscode_extension_session_marker(pid: u32, cmd: Option<&str>) -> Option<String> {
+    let cmd = cmd?;
+    if let Some(marker) = vscode_extension_session_marker_from(cmd, pid, false) {
+        return Some(marker);
+    }
+    // Extension-host children inherit this from the VS Code extension host process.
+    let extension_host_env = crate::platform::windows_loopback_peer::read_process_env_var(
+        pid,
+        "VSCODE_CRASH_REPORTER_PROCESS_TYPE",
+    )
+    .is_some_and(|v| v.trim().eq_ignore_ascii_case("extensionHost"));
+    vscode_extension_session_marker_from(cmd, pid, extension_host_env)
+}
+
 #[cfg(windows)]
 fn parse_codex_session_id_from_cmdline(cmd: &str) -> Option<String> {
     // Codex sometimes launches as: `codex.exe resume <uuid>`.
@@ -572,15 +608,16 @@ pub fn infer_wt_session(peer: SocketAddr, server_port: u16) -> Option<InferredWt
     {
         let pid =
             crate::platform::windows_loopback_peer::infer_loopback_peer_pid(peer, server_port)?;
-        let wt = terminal_session_marker(
-            crate::platform::windows_loopback_peer::read_process_env_var(pid, "WT_SESSION")
-                .as_deref(),
-            pid,
-        )?;
-        let codex_session_id =
-            crate::platform::windows_loopback_peer::read_process_command_line(pid)
-                .as_deref()
-                .and_then(parse_codex_session_id_from_cmdline);
+        let cmd = crate::platform::windows_loopback_peer::read_process_command_line(pid);
+        let wt = match vscode_extension_session_marker(pid, cmd.as_deref()) {
+            Some(marker) => marker,
+            None => terminal_session_marker(
+                crate::platform::windows_loopback_peer::read_process_env_var(pid, "WT_SESSION")
+                    .as_deref(),
+                pid,
+            )?,
+        };
+        let codex_session_id = cmd.as_deref().and_then(parse_codex_session_id_from_cmdline);
         Some(InferredWtSession {
             wt_session: wt,
             pid,
@@ -617,6 +654,7 @@ pub fn discover_sessions_using_router_snapshot(
         SessionDiscoverySnapshot {
             items: Vec::new(),
             fresh: true,
+            age_ms: Some(0),
         }
     }
 
@@ -650,18 +688,20 @@ pub fn discover_sessions_using_router_snapshot(
         // This scan involves cross-process memory reads; keep it cheap on frequent UI polling.
         const TTL_MS: u64 = 2_000;
         let now = now_unix_ms();
-        let (cached_items, cache_is_fresh) = if let Ok(guard) = cache.lock() {
+        let (cached_items, age_ms) = if let Ok(guard) = cache.lock() {
             (
                 guard.items.clone(),
-                now.saturating_sub(guard.updated_at_unix_ms) < TTL_MS,
+                (guard.updated_at_unix_ms > 0)
+                    .then(|| now.saturating_sub(guard.updated_at_unix_ms)),
             )
         } else {
-            (Vec::new(), false)
+            (Vec::new(), None)
         };
-        if cache_is_fresh {
+        if age_ms.is_some_and(|age| age < TTL_MS) {
             return SessionDiscoverySnapshot {
                 items: cached_items,
                 fresh: true,
+                age_ms,
             };
         }
 
@@ -685,6 +725,7 @@ pub fn discover_sessions_using_router_snapshot(
             return SessionDiscoverySnapshot {
                 items: Vec::new(),
                 fresh: false,
+                age_ms,
             };
         }
 
@@ -707,6 +748,7 @@ pub fn discover_sessions_using_router_snapshot(
         SessionDiscoverySnapshot {
             items: cached_items,
             fresh: false,
+            age_ms,
         }
     }
 }
@@ -796,6 +838,12 @@ pub fn is_wt_session_alive(wt_session: &str) -> bool {
         if target.is_empty() {
             return false;
         }
+        if let Some(pid) = target
+            .strip_prefix("vscode:")
+            .and_then(|pid| pid.parse::<u32>().ok())
+        {
+            return is_pid_alive(pid);
+        }
 
         let snapshot = unsafe { CreateToolhelp32Snapshot(TH32CS_SNAPPROCESS, 0) };
         if snapshot == INVALID_HANDLE_VALUE {
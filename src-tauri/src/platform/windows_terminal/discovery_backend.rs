@@ -338,6 +338,17 @@ fn discover_sessions_using_router_uncached(
             .or_else(|| infer_codex_session_id_from_tui_log_for_pid(pid))
     }
 
+    fn vscode_codex_session_id(pid: u32, router_port: u16) -> Option<String> {
+        // One extension app-server hosts every thread opened in that VS Code window, so the active
+        // id changes over the process lifetime. Follow the newest rollout for its workspace instead
+        // of freezing the first inferred id per pid.
+        read_process_codex_session_id(pid).or_else(|| {
+            let cwd = crate::platform::windows_loopback_peer::read_process_cwd(pid)?;
+            let codex_home = process_codex_home(pid)?;
+            infer_codex_session_id_from_rollouts_dir(&codex_home, &cwd, router_port, None)
+        })
+    }
+
     fn frozen_codex_model_provider(pid: u32, allow_config_infer: bool) -> Option<String> {
         // Do not infer provider for historical/old processes from current config. This avoids
         // rewriting history when users edit config while the process is still alive.
@@ -1005,18 +1016,31 @@ fn discover_sessions_using_router_uncached(
                     .as_deref(),
                 pid,
             );
-            if let Some(wt) = wt {
+            if let Some(mut wt) = wt {
                 let cmd = crate::platform::windows_loopback_peer::read_process_command_line(pid);
 
-                if cmd
+                let is_app_server = cmd
                     .as_deref()
-                    .is_some_and(|s| s.to_ascii_lowercase().contains("app-server"))
-                {
-                    ok = unsafe { Process32NextW(snapshot, &mut entry) } != 0;
-                    continue;
+                    .is_some_and(|s| s.to_ascii_lowercase().contains("app-server"));
+                if is_app_server {
+                    // Only the VS Code extension's app-server maps to a user-visible session.
+                    let Some(marker) =
+                        crate::platform::windows_terminal::vscode_extension_session_marker(
+                            pid,
+                            cmd.as_deref(),
+                        )
+                    else {
+                        ok = unsafe { Process32NextW(snapshot, &mut entry) } != 0;
+                        continue;
+                    };
+                    wt = marker;
                 }
 
-                let codex_session_id = frozen_codex_session_id(pid, cmd.as_deref(), server_port);
+                let codex_session_id = if is_app_server {
+                    vscode_codex_session_id(pid, server_port)
+                } else {
+                    frozen_codex_session_id(pid, cmd.as_deref(), server_port)
+                };
                 let Some(codex_session_id) = codex_session_id else {
                     ok = unsafe { Process32NextW(snapshot, &mut entry) } != 0;
                     continue;
@@ -104,6 +104,22 @@ mod tests {
         assert!(terminal_session_marker(None, 0).is_none());
     }
 
+    #[test]
+    fn vscode_extension_session_marker_requires_extension_app_server() {
+        let bundled = r#""C:\Users\me\.vscode\extensions\openai.chatgpt-0.4.12-win32-x64\bin\windows-x86_64\codex.exe" app-server"#;
+        assert_eq!(
+            vscode_extension_session_marker_from(bundled, 42, false).as_deref(),
+            Some("vscode:42")
+        );
+        assert_eq!(
+            vscode_extension_session_marker_from("codex.exe app-server", 42, true).as_deref(),
+            Some("vscode:42")
+        );
+        assert!(vscode_extension_session_marker_from("codex.exe app-server", 42, false).is_none());
+        assert!(vscode_extension_session_marker_from("codex.exe resume", 42, true).is_none());
+        assert!(vscode_extension_session_marker_from(bundled, 0, true).is_none());
+    }
+
     #[test]
     fn wt_session_ids_equal_accepts_wsl_prefix() {
         assert!(wt_session_ids_equal(
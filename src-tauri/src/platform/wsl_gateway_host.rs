@@ -14,6 +14,15 @@ const DETECT_RETRY_MS: u64 = 30_000;
 struct RuntimeCache {
     host: Option<String>,
     last_detect_attempt_unix_ms: u64,
+    // Listen address the host was last resolved for; a change forces re-detection.
+    listen_key: Option<(String, u16)>,
+    stale: bool,
+    refreshing: bool,
+}
+
+fn runtime_cache() -> &'static Mutex<RuntimeCache> {
+    static CACHE: OnceLock<Mutex<RuntimeCache>> = OnceLock::new();
+    CACHE.get_or_init(|| Mutex::new(RuntimeCache::default()))
 }
 
 fn fast_cache_host() -> &'static std::sync::RwLock<Option<String>> {
@@ -179,9 +188,65 @@ fn detect_wsl_gateway_host() -> Option<String> {
     None
 }
 
+/// Marks the resolved host stale so the next status poll re-detects it, e.g. after the machine's
+/// network interfaces changed.
+pub fn invalidate_wsl_gateway_host() {
+    if let Ok(mut guard) = runtime_cache().lock() {
+        guard.stale = true;
+        guard.last_detect_attempt_unix_ms = 0;
+    }
+}
+
+/// Re-detects the WSL gateway host immediately, bypassing the retry window.
+pub fn refresh_wsl_gateway_host(config_path: Option<&Path>) -> String {
+    if let Ok(mut guard) = runtime_cache().lock() {
+        guard.last_detect_attempt_unix_ms = 0;
+    }
+    let host = resolve_wsl_gateway_host(config_path);
+    if let Ok(mut guard) = runtime_cache().lock() {
+        guard.stale = false;
+    }
+    host
+}
+
+/// Cached host for status polling. Detection shells into WSL, so when the listen address changed
+/// since the last poll or the host was invalidated, it re-runs on a background thread and this
+/// returns the previous value until it finishes.
+pub fn status_wsl_gateway_host(config_path: &Path, listen_host: &str, listen_port: u16) -> String {
+    let listen_key = (listen_host.to_ascii_lowercase(), listen_port);
+    let spawn_refresh = match runtime_cache().lock() {
+        Ok(mut guard) => {
+            if guard.listen_key.as_ref() != Some(&listen_key) {
+                guard.stale = true;
+                guard.listen_key = Some(listen_key);
+            }
+            let due = guard.stale && !guard.refreshing;
+            if due {
+                guard.refreshing = true;
+            }
+            due
+        }
+        Err(_) => false,
+    };
+    if spawn_refresh {
+        let config_path = config_path.to_path_buf();
+        std::thread::spawn(move || {
+            let _ = refresh_wsl_gateway_host(Some(&config_path));
+            if let Ok(mut guard) = runtime_cache().lock() {
+                guard.refreshing = false;
+            }
+        });
+    }
+    cached_or_default_wsl_gateway_host(Some(config_path))
+}
+
+/// Shell snippet WSL users can paste to point Codex at this gateway.
+pub fn wsl_openai_base_url_export(host: &str, port: u16) -> String {
+    format!("export OPENAI_BASE_URL=http://{host}:{port}/v1")
+}
+
 pub fn resolve_wsl_gateway_host(config_path: Option<&Path>) -> String {
-    static CACHE: OnceLock<Mutex<RuntimeCache>> = OnceLock::new();
-    let cache = CACHE.get_or_init(|| Mutex::new(RuntimeCache::default()));
+    let cache = runtime_cache();
 
     let now = now_unix_ms();
     let cache_path = resolve_cache_path(config_path);
@@ -231,7 +296,10 @@ pub fn resolve_wsl_gateway_host(config_path: Option<&Path>) -> String {
 
 #[cfg(test)]
 mod tests {
-    use super::{normalize_ipv4, read_cached_host, should_retry_detection, write_cached_host};
+    use super::{
+        normalize_ipv4, read_cached_host, should_retry_detection, write_cached_host,
+        wsl_openai_base_url_export,
+    };
 
     #[test]
     fn normalize_ipv4_accepts_valid_ipv4() {
@@ -267,6 +335,14 @@ mod tests {
         assert!(should_retry_detection(40_000, 40_000 - 30_000));
     }
 
+    #[test]
+    fn wsl_openai_base_url_export_targets_gateway_v1() {
+        assert_eq!(
+            wsl_openai_base_url_export("172.18.80.1", 4000),
+            "export OPENAI_BASE_URL=http://172.18.80.1:4000/v1"
+        );
+    }
+
     #[cfg(windows)]
     #[test]
     fn wsl_gateway_detection_skips_probe_without_registered_distro() {
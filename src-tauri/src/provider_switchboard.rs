@@ -21,6 +21,17 @@ impl<'a> ProviderSwitchboardRuntime<'a> {
     }
 }
 
+/// How a switchboard rewrite treats the top-level `model` key of a CLI home's config.
+#[derive(Clone, Copy, Debug)]
+pub enum ModelOverride<'a> {
+    /// Carry over whatever model the home currently pins.
+    Keep,
+    /// Pin this model.
+    Set(&'a str),
+    /// Drop any override and fall back to the base config's model.
+    Clear,
+}
+
 fn dedup_key(cli_home: &Path) -> String {
     let mut s = cli_home.to_string_lossy().to_string();
     if cfg!(windows) {
@@ -74,6 +85,55 @@ fn resolve_cli_homes(cli_homes: Vec<String>) -> Result<Vec<PathBuf>, String> {
     resolve_cli_homes_with_limit(cli_homes, Some(2))
 }
 
+/// Replaces configured switchboard profile names with their paths; other entries are paths.
+fn expand_cli_home_profiles(app_cfg: &AppConfig, cli_homes: Vec<String>) -> Vec<String> {
+    cli_homes
+        .into_iter()
+        .map(|entry| {
+            app_cfg
+                .switchboard_profiles
+                .get(entry.trim())
+                .cloned()
+                .unwrap_or(entry)
+        })
+        .collect()
+}
+
+/// Validates a named profile and returns the trimmed `(name, path)` to persist.
+pub(crate) fn validate_switchboard_profile(
+    name: &str,
+    path: &str,
+) -> Result<(String, String), String> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err("profile name is required".to_string());
+    }
+    if name.chars().count() > 64 {
+        return Err("profile name must be at most 64 characters".to_string());
+    }
+    if !name
+        .chars()
+        .all(|c| c.is_alphanumeric() || matches!(c, '-' | '_' | '.'))
+    {
+        return Err("profile name may only contain letters, digits, '-', '_' and '.'".to_string());
+    }
+    let path = path.trim();
+    if path.is_empty() {
+        return Err("profile path is required".to_string());
+    }
+    let home = Path::new(path);
+    if !home.is_dir() {
+        return Err(format!("Codex dir does not exist: {}", home.display()));
+    }
+    if !cli_cfg_path(home).exists() && !cli_auth_path(home).exists() {
+        return Err(format!(
+            "Not a Codex home (missing config.toml and auth.json): {}",
+            home.display()
+        ));
+    }
+    Ok((name.to_string(), path.to_string()))
+}
+
 fn augment_gateway_sync_homes_with_candidates(
     homes: Vec<PathBuf>,
     candidates: impl IntoIterator<Item = PathBuf>,
@@ -356,6 +416,7 @@ fn ensure_backup_exists(config_path: &Path, cli_home: &Path) -> Result<(), Strin
     if home_swap_state(cli_home)? == "swapped" {
         return Ok(());
     }
+    crate::codex_cli_swap::ensure_cli_home_undo_backup(cli_home)?;
     let state_dir = swap_state_dir(cli_home);
     std::fs::create_dir_all(&state_dir).map_err(|e| e.to_string())?;
     let auth_src = read_bytes(&cli_auth_path(cli_home))?;
@@ -408,9 +469,10 @@ fn normalize_cfg_for_switchboard_base(cfg: &str) -> String {
     base
 }
 
-fn read_cfg_base_text(config_path: &Path, cli_home: &Path) -> Result<String, String> {
-    let app_auth = load_app_auth_if_signed_in(config_path);
-    ensure_cli_files_exist(cli_home, app_auth.as_ref())?;
+/// Resolves the base config for the next swap without writing anything.
+///
+/// The flag is true when the saved base is stale and should be replaced by the returned text.
+fn resolve_cfg_base_text(config_path: &Path, cli_home: &Path) -> Result<(String, bool), String> {
     let state = home_swap_state(cli_home)?;
     if state == "original" {
         if let Some(base_txt) = load_switchboard_base_cfg(config_path, cli_home) {
@@ -422,29 +484,36 @@ fn read_cfg_base_text(config_path: &Path, cli_home: &Path) -> Result<String, Str
                 if current_norm != baseline_gateway_norm {
                     // The user edited the gateway config after we restored it. Prefer the latest
                     // gateway config and refresh the saved base to match.
-                    save_switchboard_base_cfg(config_path, cli_home, &current_norm)?;
-                    save_switchboard_base_meta(config_path, cli_home, &current_norm)?;
-                    return Ok(current_norm);
+                    return Ok((current_norm, true));
                 }
             } else {
                 // Base exists but meta is missing/corrupted. Prefer the current gateway config so we
                 // don't silently override user edits with a potentially stale saved base.
                 let current = read_text(&cli_cfg_path(cli_home))?;
-                let current_norm = normalize_cfg_for_switchboard_base(&current);
-                save_switchboard_base_cfg(config_path, cli_home, &current_norm)?;
-                save_switchboard_base_meta(config_path, cli_home, &current_norm)?;
-                return Ok(current_norm);
+                return Ok((normalize_cfg_for_switchboard_base(&current), true));
             }
-            return Ok(base_txt);
+            return Ok((base_txt, false));
         }
     }
     let current = read_text(&cli_cfg_path(cli_home))?;
-    Ok(normalize_cfg_for_switchboard_base(&current))
+    Ok((normalize_cfg_for_switchboard_base(&current), false))
+}
+
+fn read_cfg_base_text(config_path: &Path, cli_home: &Path) -> Result<String, String> {
+    let app_auth = load_app_auth_if_signed_in(config_path);
+    ensure_cli_files_exist(cli_home, app_auth.as_ref())?;
+    let (base_txt, refresh_saved_base) = resolve_cfg_base_text(config_path, cli_home)?;
+    if refresh_saved_base {
+        save_switchboard_base_cfg(config_path, cli_home, &base_txt)?;
+        save_switchboard_base_meta(config_path, cli_home, &base_txt)?;
+    }
+    Ok(base_txt)
 }
 
 fn switch_to_gateway_home_impl(
     runtime: &ProviderSwitchboardRuntime<'_>,
     cli_home: &Path,
+    model: ModelOverride<'_>,
 ) -> Result<(), String> {
     let gateway_token = runtime.secrets.ensure_gateway_token()?;
     if gateway_token.trim().is_empty() {
@@ -475,6 +544,7 @@ fn switch_to_gateway_home_impl(
         );
     }
 
+    let base_cfg = apply_model_override(runtime.config_path, cli_home, &base_cfg, model);
     let next_cfg =
         build_gateway_provider_cfg(runtime.config_path, runtime.gateway, cli_home, &base_cfg);
     let next_auth = auth_with_openai_key(gateway_token.trim());
@@ -555,6 +625,132 @@ fn escape_toml(s: &str) -> String {
     s.replace('\\', "\\\\").replace('"', "\\\"")
 }
 
+fn is_top_level_model_line(trimmed: &str) -> bool {
+    trimmed
+        .strip_prefix("model")
+        .is_some_and(|rest| rest.trim_start().starts_with('='))
+}
+
+/// Reads the top-level `model` key, ignoring same-named keys inside `[sections]`.
+fn top_level_model(cfg_txt: &str) -> Option<String> {
+    for line in cfg_txt.lines() {
+        let t = line.trim();
+        if t.starts_with('[') {
+            break;
+        }
+        if is_top_level_model_line(t) {
+            let value = t.split_once('=').map(|(_, v)| v).unwrap_or("");
+            return parse_toml_string_or_bare_value(value);
+        }
+    }
+    None
+}
+
+/// Sets the top-level `model` key and leaves the rest of the config untouched.
+fn with_top_level_model(cfg_txt: &str, model: &str) -> String {
+    let eol = if cfg_txt.contains("\r\n") {
+        "\r\n"
+    } else {
+        "\n"
+    };
+    let model_line = format!("model = \"{}\"", escape_toml(model));
+    let mut out: Vec<String> = Vec::new();
+    let mut in_top_level = true;
+    let mut replaced = false;
+    for line in cfg_txt.lines() {
+        let t = line.trim();
+        if t.starts_with('[') {
+            in_top_level = false;
+        }
+        if in_top_level && is_top_level_model_line(t) {
+            if !replaced {
+                out.push(model_line.clone());
+                replaced = true;
+            }
+            continue;
+        }
+        out.push(line.to_string());
+    }
+    if !replaced {
+        out.insert(0, model_line);
+    }
+    out.join(eol) + eol
+}
+
+fn switchboard_model_override_path_from_config_path(
+    config_path: &Path,
+    cli_home: &Path,
+) -> PathBuf {
+    let key = switchboard_base_key(cli_home);
+    switchboard_base_dir_from_config_path(config_path).join(format!("{key}.model.json"))
+}
+
+fn load_switchboard_model_override(config_path: &Path, cli_home: &Path) -> Option<String> {
+    read_json(&switchboard_model_override_path_from_config_path(
+        config_path,
+        cli_home,
+    ))
+    .ok()?
+    .get("model")
+    .and_then(|v| v.as_str())
+    .map(str::trim)
+    .filter(|s| !s.is_empty())
+    .map(|s| s.to_string())
+}
+
+/// Persists the override so later key/token/rename rewrites of the home keep the pinned model.
+fn save_switchboard_model_override(
+    config_path: &Path,
+    cli_home: &Path,
+    model: ModelOverride<'_>,
+) -> Result<(), String> {
+    let path = switchboard_model_override_path_from_config_path(config_path, cli_home);
+    match model {
+        ModelOverride::Keep => Ok(()),
+        ModelOverride::Set(model) => write_json(
+            &path,
+            &json!({
+              "model": model,
+              "updated_at_unix_ms": unix_ms(),
+            }),
+        ),
+        ModelOverride::Clear => match std::fs::remove_file(&path) {
+            Err(e) if e.kind() != std::io::ErrorKind::NotFound => Err(e.to_string()),
+            _ => Ok(()),
+        },
+    }
+}
+
+/// Applies `model` on top of the base config that is about to be written into `cli_home`.
+fn apply_model_override(
+    config_path: &Path,
+    cli_home: &Path,
+    base_cfg: &str,
+    model: ModelOverride<'_>,
+) -> String {
+    let pinned = match model {
+        ModelOverride::Set(model) => Some(model.to_string()),
+        ModelOverride::Clear => None,
+        ModelOverride::Keep => load_switchboard_model_override(config_path, cli_home),
+    };
+    match pinned {
+        Some(model) => with_top_level_model(base_cfg, &model),
+        None => base_cfg.to_string(),
+    }
+}
+
+/// Validates a user-supplied model override and returns the trimmed value.
+pub(crate) fn validate_model_override(model: &str) -> Result<String, String> {
+    let model = model.trim();
+    if model.is_empty() {
+        return Err("model must not be empty".to_string());
+    }
+    if model.chars().count() > 128 || model.chars().any(char::is_control) {
+        return Err("model must be at most 128 printable characters".to_string());
+    }
+    Ok(model.to_string())
+}
+
 fn insert_provider_section_near_top(base_cfg: &str, provider_section: &str) -> String {
     let eol = if base_cfg.contains("\r\n") {
         "\r\n"
@@ -981,16 +1177,42 @@ fn home_mode(cli_home: &Path) -> Result<(String, Option<String>), String> {
 pub fn get_status(
     state: &tauri::State<'_, AppState>,
     cli_homes: Vec<String>,
+    target: Option<String>,
+    provider: Option<String>,
 ) -> Result<serde_json::Value, String> {
-    get_status_for_gateway(&state.gateway, cli_homes)
+    let mut status = get_status_for_gateway(&state.gateway, cli_homes)?;
+    if let Some(target) = target.as_deref() {
+        mark_target_matches(&mut status, target, provider.as_deref());
+    }
+    Ok(status)
+}
+
+/// Flags each dir whose current state already matches the requested target, so the UI can
+/// disable a no-op apply.
+fn mark_target_matches(status: &mut serde_json::Value, target: &str, provider: Option<&str>) {
+    let target = target.trim().to_ascii_lowercase();
+    let provider = provider.map(str::trim).filter(|s| !s.is_empty());
+    let Some(dirs) = status.get_mut("dirs").and_then(|v| v.as_array_mut()) else {
+        return;
+    };
+    let mut all_match = !dirs.is_empty();
+    for dir in dirs.iter_mut() {
+        let mode_matches = dir.get("mode").and_then(|v| v.as_str()) == Some(target.as_str());
+        let provider_matches =
+            target != "provider" || dir.get("model_provider").and_then(|v| v.as_str()) == provider;
+        let matches = mode_matches && provider_matches;
+        all_match &= matches;
+        dir["matches_target"] = json!(matches);
+    }
+    status["target_matches"] = json!(all_match);
 }
 
 pub fn get_status_for_gateway(
     gateway: &crate::orchestrator::gateway::GatewayState,
     cli_homes: Vec<String>,
 ) -> Result<serde_json::Value, String> {
-    let homes = resolve_cli_homes(cli_homes)?;
     let app_cfg = gateway.cfg.read().clone();
+    let homes = resolve_cli_homes(expand_cli_home_profiles(&app_cfg, cli_homes))?;
     let dirs = collect_status_dirs(&homes, &app_cfg)?;
     let provider_options = app_cfg
         .provider_order
@@ -1023,7 +1245,9 @@ fn collect_status_dirs(
         dirs.push(json!({
           "cli_home": h.to_string_lossy(),
           "mode": mode,
-          "model_provider": provider
+          "model_provider": provider,
+          "model": top_level_model(&cfg_txt),
+          "backups": crate::codex_cli_swap::cli_home_undo_backup_status(h)
         }));
     }
 
@@ -1118,7 +1342,12 @@ fn sync_active_provider_target_for_key_impl(
         if mode != "provider" || mp.as_deref() != Some(provider) {
             continue;
         }
-        let orig_cfg = read_cfg_base_text(&state.config_path, h)?;
+        let orig_cfg = apply_model_override(
+            &state.config_path,
+            h,
+            &read_cfg_base_text(&state.config_path, h)?,
+            ModelOverride::Keep,
+        );
         let next_cfg = build_direct_provider_cfg(
             &orig_cfg,
             provider,
@@ -1228,9 +1457,11 @@ fn sync_gateway_target_for_rotated_token_report_impl(
         if mode != "gateway" {
             continue;
         }
-        if let Err(e) =
-            switch_to_gateway_home_impl(&ProviderSwitchboardRuntime::from_app_state(state), h)
-        {
+        if let Err(e) = switch_to_gateway_home_impl(
+            &ProviderSwitchboardRuntime::from_app_state(state),
+            h,
+            ModelOverride::Keep,
+        ) {
             failed_targets.push(format!(
                 "{} (rewrite gateway target failed: {e})",
                 h.to_string_lossy()
@@ -63,7 +63,12 @@ fn on_provider_renamed_impl(state: &AppState, old: &str, new: &str) -> Result<()
         if mode != "provider" || mp.as_deref() != Some(old) {
             continue;
         }
-        let orig_cfg = read_cfg_base_text(&state.config_path, h)?;
+        let orig_cfg = apply_model_override(
+            &state.config_path,
+            h,
+            &read_cfg_base_text(&state.config_path, h)?,
+            ModelOverride::Keep,
+        );
         let storage_mode = state.secrets.get_provider_key_storage_mode(new);
         let use_config_storage = provider_key_storage_uses_config(&storage_mode);
         let next_cfg = build_direct_provider_cfg(
@@ -97,9 +102,199 @@ pub fn set_target(
     cli_homes: Vec<String>,
     target: String,
     provider: Option<String>,
+    model: ModelOverride<'_>,
+    dry_run: bool,
 ) -> Result<serde_json::Value, String> {
     let runtime = ProviderSwitchboardRuntime::from_app_state(state);
-    set_target_for_runtime(&runtime, cli_homes, target, provider)
+    if dry_run {
+        return plan_target_for_runtime(&runtime, cli_homes, target, provider, model);
+    }
+    set_target_for_runtime(&runtime, cli_homes, target, provider, model)
+}
+
+fn resolve_direct_provider_target(
+    runtime: &ProviderSwitchboardRuntime<'_>,
+    app_cfg: &AppConfig,
+    provider_name: Option<&str>,
+) -> Result<(String, String, bool, String), String> {
+    let name = provider_name
+        .map(str::to_string)
+        .ok_or_else(|| "provider is required for target=provider".to_string())?;
+    let cfg = app_cfg
+        .providers
+        .get(&name)
+        .ok_or_else(|| format!("unknown provider: {name}"))?;
+    let base_url = cfg.base_url.trim().to_string();
+    if base_url.is_empty() {
+        return Err(format!("provider base_url is empty: {name}"));
+    }
+    let key = runtime
+        .secrets
+        .get_provider_key(&name)
+        .ok_or_else(|| format!("provider key is missing: {name}"))?;
+    if key.trim().is_empty() {
+        return Err(format!("provider key is empty: {name}"));
+    }
+    Ok((name, base_url, cfg.supports_websockets, key))
+}
+
+/// Computes what `set_target_for_runtime` would rewrite, without touching disk or emitting events.
+///
+/// Auth values are reported only as "set"/null so a plan never echoes keys or tokens.
+pub fn plan_target_for_runtime(
+    runtime: &ProviderSwitchboardRuntime<'_>,
+    cli_homes: Vec<String>,
+    target: String,
+    provider: Option<String>,
+    model: ModelOverride<'_>,
+) -> Result<serde_json::Value, String> {
+    let app_cfg = runtime.gateway.cfg.read().clone();
+    let homes = resolve_cli_homes(expand_cli_home_profiles(&app_cfg, cli_homes))?;
+    let target = target.trim().to_ascii_lowercase();
+    let provider_name = provider
+        .as_deref()
+        .map(str::trim)
+        .filter(|s| !s.is_empty())
+        .map(|s| s.to_string());
+
+    let mut direct = None;
+    let mut gateway_token_missing = false;
+    let next_auth = match target.as_str() {
+        "gateway" => {
+            let token = runtime.secrets.get_gateway_token().unwrap_or_default();
+            gateway_token_missing = token.trim().is_empty();
+            auth_with_openai_key(token.trim())
+        }
+        "official" => resolve_selected_official_auth_from_secrets(runtime.secrets)?,
+        "provider" => {
+            let (name, base_url, supports_websockets, key) =
+                resolve_direct_provider_target(runtime, &app_cfg, provider_name.as_deref())?;
+            let storage_mode = runtime.secrets.get_provider_key_storage_mode(&name);
+            let use_config_storage = provider_key_storage_uses_config(&storage_mode);
+            let next_auth = if use_config_storage {
+                auth_without_openai_key()
+            } else {
+                auth_with_openai_key(key.trim())
+            };
+            direct = Some((name, base_url, supports_websockets, key, use_config_storage));
+            next_auth
+        }
+        _ => return Err("target must be one of: gateway | official | provider".to_string()),
+    };
+
+    let mut planned_homes = Vec::new();
+    for h in &homes {
+        if !cli_cfg_path(h).exists() {
+            return Err(format!("Missing config.toml in: {}", h.display()));
+        }
+        let (base_cfg, _) = resolve_cfg_base_text(runtime.config_path, h)?;
+        let base_cfg = apply_model_override(runtime.config_path, h, &base_cfg, model);
+        let mut files = Vec::new();
+        for home in web_codex_runtime_auth_home_candidates(runtime.config_path, h) {
+            let next_cfg = match &direct {
+                Some((name, base_url, supports_websockets, key, use_config_storage)) => {
+                    build_direct_provider_cfg(
+                        &base_cfg,
+                        name,
+                        base_url,
+                        *supports_websockets,
+                        use_config_storage.then_some(key.trim()),
+                    )
+                }
+                None if target == "gateway" => build_gateway_provider_cfg(
+                    runtime.config_path,
+                    runtime.gateway,
+                    &home,
+                    &base_cfg,
+                ),
+                None => strip_model_provider_line(&base_cfg),
+            };
+            files.push(plan_cfg_file_change(&cli_cfg_path(&home), &next_cfg));
+            files.push(plan_auth_file_change(&cli_auth_path(&home), &next_auth));
+        }
+        let noop = files.iter().all(|f| f["changed"] == json!(false));
+        planned_homes.push(json!({
+          "cli_home": h.to_string_lossy(),
+          "noop": noop,
+          "creates_backup": !noop && home_swap_state(h)? == "original",
+          "files": files
+        }));
+    }
+
+    let noop = planned_homes.iter().all(|h| h["noop"] == json!(true));
+    Ok(json!({
+      "ok": true,
+      "dry_run": true,
+      "target": target,
+      "provider": provider_name,
+      "gateway_token_missing": gateway_token_missing,
+      "noop": noop,
+      "homes": planned_homes
+    }))
+}
+
+fn plan_cfg_file_change(path: &Path, next_cfg: &str) -> serde_json::Value {
+    let current = read_text(path).ok();
+    let current_cfg = current.as_deref().unwrap_or("");
+    let provider_and_base_url = |cfg: &str| {
+        let id = model_provider_id(cfg);
+        let base_url = id
+            .as_deref()
+            .and_then(|id| model_provider_section_base_url(cfg, id));
+        (id, base_url)
+    };
+    let (from_provider, from_base_url) = provider_and_base_url(current_cfg);
+    let (to_provider, to_base_url) = provider_and_base_url(next_cfg);
+    let (from_model, to_model) = (top_level_model(current_cfg), top_level_model(next_cfg));
+    let mut changes = Vec::new();
+    if from_provider != to_provider {
+        changes.push(json!({ "key": "model_provider", "from": from_provider, "to": to_provider }));
+    }
+    if from_base_url != to_base_url {
+        changes.push(json!({ "key": "base_url", "from": from_base_url, "to": to_base_url }));
+    }
+    if from_model != to_model {
+        changes.push(json!({ "key": "model", "from": from_model, "to": to_model }));
+    }
+    json!({
+      "path": path.to_string_lossy(),
+      "file": "config.toml",
+      "exists": current.is_some(),
+      "changed": current.as_deref() != Some(next_cfg),
+      "changes": changes
+    })
+}
+
+fn plan_auth_file_change(path: &Path, next_auth: &serde_json::Value) -> serde_json::Value {
+    let current = read_json(path).ok();
+    let empty = serde_json::Map::new();
+    let from = current
+        .as_ref()
+        .and_then(|v| v.as_object())
+        .unwrap_or(&empty);
+    let to = next_auth.as_object().unwrap_or(&empty);
+    let keys = from
+        .keys()
+        .chain(to.keys())
+        .collect::<std::collections::BTreeSet<_>>();
+    let changes = keys
+        .into_iter()
+        .filter(|k| from.get(*k) != to.get(*k))
+        .map(|k| {
+            json!({
+              "key": k,
+              "from": from.get(k).map(|_| "set"),
+              "to": to.get(k).map(|_| "set")
+            })
+        })
+        .collect::<Vec<_>>();
+    json!({
+      "path": path.to_string_lossy(),
+      "file": "auth.json",
+      "exists": current.is_some(),
+      "changed": current.as_ref() != Some(next_auth),
+      "changes": changes
+    })
 }
 
 pub fn set_target_for_runtime(
@@ -107,8 +302,9 @@ pub fn set_target_for_runtime(
     cli_homes: Vec<String>,
     target: String,
     provider: Option<String>,
+    model: ModelOverride<'_>,
 ) -> Result<serde_json::Value, String> {
-    set_target_for_runtime_with_official_auth(runtime, cli_homes, target, provider, None)
+    set_target_for_runtime_with_official_auth(runtime, cli_homes, target, provider, model, None)
 }
 
 pub fn set_target_for_runtime_with_official_auth(
@@ -116,12 +312,13 @@ pub fn set_target_for_runtime_with_official_auth(
     cli_homes: Vec<String>,
     target: String,
     provider: Option<String>,
+    model: ModelOverride<'_>,
     official_auth_override: Option<serde_json::Value>,
 ) -> Result<serde_json::Value, String> {
-    let homes = resolve_cli_homes(cli_homes)?;
+    let app_cfg = runtime.gateway.cfg.read().clone();
+    let homes = resolve_cli_homes(expand_cli_home_profiles(&app_cfg, cli_homes))?;
     let target = target.trim().to_ascii_lowercase();
 
-    let app_cfg = runtime.gateway.cfg.read().clone();
     let app_auth = if target == "official" {
         let auth = if let Some(auth) = official_auth_override {
             ensure_signed_in(&auth)?;
@@ -147,25 +344,9 @@ pub fn set_target_for_runtime_with_official_auth(
 
     let (direct_name, direct_base_url, direct_supports_websockets, direct_key) =
         if target == "provider" {
-        let name = provider_name
-            .clone()
-            .ok_or_else(|| "provider is required for target=provider".to_string())?;
-        let cfg = app_cfg
-            .providers
-            .get(&name)
-            .ok_or_else(|| format!("unknown provider: {name}"))?;
-        let base_url = cfg.base_url.trim().to_string();
-        if base_url.is_empty() {
-            return Err(format!("provider base_url is empty: {name}"));
-        }
-        let key = runtime
-            .secrets
-            .get_provider_key(&name)
-            .ok_or_else(|| format!("provider key is missing: {name}"))?;
-        if key.trim().is_empty() {
-            return Err(format!("provider key is empty: {name}"));
-        }
-        (Some(name), Some(base_url), Some(cfg.supports_websockets), Some(key))
+        let (name, base_url, supports_websockets, key) =
+            resolve_direct_provider_target(runtime, &app_cfg, provider_name.as_deref())?;
+        (Some(name), Some(base_url), Some(supports_websockets), Some(key))
     } else {
         (None, None, None, None)
     };
@@ -173,9 +354,14 @@ pub fn set_target_for_runtime_with_official_auth(
     let mut applied: Vec<PathBuf> = Vec::new();
     for h in &homes {
         let res = match target.as_str() {
-            "gateway" => switch_to_gateway_home_impl(runtime, h),
+            "gateway" => switch_to_gateway_home_impl(runtime, h, model),
             "official" => (|| {
-                let orig_cfg = read_cfg_base_text(runtime.config_path, h)?;
+                let orig_cfg = apply_model_override(
+                    runtime.config_path,
+                    h,
+                    &read_cfg_base_text(runtime.config_path, h)?,
+                    model,
+                );
                 let next_cfg = strip_model_provider_line(&orig_cfg);
                 let auth = app_auth.as_ref().ok_or_else(|| {
                     "Missing app Codex auth.json. Try logging in first.".to_string()
@@ -195,7 +381,12 @@ pub fn set_target_for_runtime_with_official_auth(
                 let key = direct_key
                     .as_deref()
                     .ok_or_else(|| "provider key is missing".to_string())?;
-                let orig_cfg = read_cfg_base_text(runtime.config_path, h)?;
+                let orig_cfg = apply_model_override(
+                    runtime.config_path,
+                    h,
+                    &read_cfg_base_text(runtime.config_path, h)?,
+                    model,
+                );
                 let storage_mode = runtime.secrets.get_provider_key_storage_mode(name);
                 let use_config_storage = provider_key_storage_uses_config(&storage_mode);
                 let next_cfg = build_direct_provider_cfg(
@@ -215,6 +406,7 @@ pub fn set_target_for_runtime_with_official_auth(
             })(),
             _ => Err("target must be one of: gateway | official | provider".to_string()),
         };
+        let res = res.and_then(|()| save_switchboard_model_override(runtime.config_path, h, model));
         if let Err(e) = res {
             if target != "gateway" {
                 for p in applied.iter().rev() {
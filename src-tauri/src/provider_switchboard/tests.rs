@@ -144,7 +144,8 @@ mod tests {
         std::fs::write(backup_cfg_path(&cli_home), "model = \"gpt-5.2\"\n").unwrap();
 
         let runtime = ProviderSwitchboardRuntime::from_app_state(&state);
-        switch_to_gateway_home_impl(&runtime, &cli_home).expect("switch gateway");
+        switch_to_gateway_home_impl(&runtime, &cli_home, ModelOverride::Keep)
+            .expect("switch gateway");
 
         let switched_cfg = std::fs::read_to_string(cli_cfg_path(&cli_home)).unwrap();
         assert!(switched_cfg.contains("model_provider = \"api_router\""));
@@ -204,7 +205,8 @@ mod tests {
         std::fs::write(cli_cfg_path(&cli_home), "model = \"gpt-5.2\"\n").unwrap();
 
         let runtime = ProviderSwitchboardRuntime::from_app_state(&state);
-        switch_to_gateway_home_impl(&runtime, &cli_home).expect("switch gateway");
+        switch_to_gateway_home_impl(&runtime, &cli_home, ModelOverride::Keep)
+            .expect("switch gateway");
 
         let backup_auth = read_json(&backup_auth_path(&cli_home)).expect("backup auth");
         assert_eq!(backup_auth, app_auth);
@@ -790,7 +792,8 @@ mod tests {
         std::fs::create_dir_all(&runtime_home).unwrap();
 
         let runtime = ProviderSwitchboardRuntime::from_app_state(&state);
-        switch_to_gateway_home_impl(&runtime, &cli_home).expect("switch gateway");
+        switch_to_gateway_home_impl(&runtime, &cli_home, ModelOverride::Keep)
+            .expect("switch gateway");
 
         let runtime_auth: serde_json::Value =
             serde_json::from_str(&std::fs::read_to_string(runtime_home.join("auth.json")).unwrap())
@@ -840,6 +843,7 @@ mod tests {
             vec![cli_home.to_string_lossy().to_string()],
             "official".to_string(),
             None,
+            ModelOverride::Keep,
             Some(official_auth.clone()),
         )
         .expect("switch official");
@@ -1206,6 +1210,186 @@ mod tests {
             .any(|path| dedup_key(Path::new(path)) == dedup_key(&app_runtime_home)));
     }
 
+    #[test]
+    fn plan_target_reports_changes_without_writing_files_or_events() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let config_path = tmp.path().join("user-data").join("config.toml");
+        let data_dir = tmp.path().join("data");
+        std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+
+        let state = crate::app_state::build_state(config_path.clone(), data_dir).expect("state");
+        {
+            let mut cfg = state.gateway.cfg.write();
+            cfg.providers.get_mut("provider_1").unwrap().base_url =
+                "https://example.com/v1".to_string();
+        }
+        state
+            .secrets
+            .set_provider_key("provider_1", "sk-new")
+            .expect("set key");
+
+        let cli_home = tmp.path().join("cli-home");
+        std::fs::create_dir_all(&cli_home).unwrap();
+        let orig_auth = r#"{"tokens":{"t":"x"}}"#;
+        let orig_cfg = "model = \"gpt-5.2\"\n";
+        std::fs::write(cli_auth_path(&cli_home), orig_auth).unwrap();
+        std::fs::write(cli_cfg_path(&cli_home), orig_cfg).unwrap();
+        let events_before = state
+            .gateway
+            .store
+            .list_events_range(None, None, None)
+            .len();
+
+        let runtime = ProviderSwitchboardRuntime::from_app_state(&state);
+        let plan = plan_target_for_runtime(
+            &runtime,
+            vec![cli_home.to_string_lossy().to_string()],
+            "provider".to_string(),
+            Some("provider_1".to_string()),
+            ModelOverride::Keep,
+        )
+        .expect("plan");
+
+        assert_eq!(plan["dry_run"], json!(true));
+        assert_eq!(plan["noop"], json!(false));
+        let files = plan["homes"][0]["files"].as_array().expect("files");
+        let cfg_change = files
+            .iter()
+            .find(|f| f["path"] == json!(cli_cfg_path(&cli_home).to_string_lossy()))
+            .expect("cfg change");
+        let changes = cfg_change["changes"].as_array().expect("changes");
+        assert!(changes.contains(&json!({
+          "key": "base_url",
+          "from": null,
+          "to": "https://example.com/v1"
+        })));
+        assert!(!plan.to_string().contains("sk-new"));
+
+        assert_eq!(
+            std::fs::read_to_string(cli_auth_path(&cli_home)).unwrap(),
+            orig_auth
+        );
+        assert_eq!(
+            std::fs::read_to_string(cli_cfg_path(&cli_home)).unwrap(),
+            orig_cfg
+        );
+        assert!(!swap_state_dir(&cli_home).exists());
+        assert_eq!(
+            state
+                .gateway
+                .store
+                .list_events_range(None, None, None)
+                .len(),
+            events_before
+        );
+    }
+
+    #[test]
+    fn model_override_survives_key_sync_until_cleared() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let config_path = tmp.path().join("user-data").join("config.toml");
+        let data_dir = tmp.path().join("data");
+        std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        let state = crate::app_state::build_state(config_path.clone(), data_dir).expect("state");
+        {
+            let mut cfg = state.gateway.cfg.write();
+            cfg.providers.get_mut("provider_1").unwrap().base_url =
+                "https://example.com/v1".to_string();
+        }
+        state
+            .secrets
+            .set_provider_key("provider_1", "sk-old")
+            .expect("set key");
+
+        let cli_home = tmp.path().join("cli-home");
+        std::fs::create_dir_all(&cli_home).unwrap();
+        std::fs::write(cli_auth_path(&cli_home), r#"{"tokens":{"t":"x"}}"#).unwrap();
+        std::fs::write(
+            cli_cfg_path(&cli_home),
+            "model = \"gpt-5.2\"\n\n[profiles.fast]\nmodel = \"gpt-5-mini\"\n",
+        )
+        .unwrap();
+        let homes = vec![cli_home.to_string_lossy().to_string()];
+        let runtime = ProviderSwitchboardRuntime::from_app_state(&state);
+
+        let status = set_target_for_runtime(
+            &runtime,
+            homes.clone(),
+            "provider".to_string(),
+            Some("provider_1".to_string()),
+            ModelOverride::Set("gpt-5.4"),
+        )
+        .expect("set target");
+        assert_eq!(status["dirs"][0]["model"], json!("gpt-5.4"));
+        let cfg_txt = read_text(&cli_cfg_path(&cli_home)).expect("cfg");
+        assert!(cfg_txt.contains("model = \"gpt-5-mini\""));
+        assert!(!cfg_txt.contains("model = \"gpt-5.2\""));
+
+        state
+            .secrets
+            .set_provider_key("provider_1", "sk-new")
+            .expect("rotate key");
+        sync_active_provider_target_for_key_impl(&state, "provider_1").expect("sync");
+        let cfg_txt = read_text(&cli_cfg_path(&cli_home)).expect("cfg");
+        assert_eq!(top_level_model(&cfg_txt).as_deref(), Some("gpt-5.4"));
+
+        set_target_for_runtime(
+            &runtime,
+            homes,
+            "provider".to_string(),
+            Some("provider_1".to_string()),
+            ModelOverride::Clear,
+        )
+        .expect("clear model");
+        let cfg_txt = read_text(&cli_cfg_path(&cli_home)).expect("cfg");
+        assert_eq!(top_level_model(&cfg_txt).as_deref(), Some("gpt-5.2"));
+        assert!(load_switchboard_model_override(&config_path, &cli_home).is_none());
+    }
+
+    #[test]
+    fn get_status_accepts_switchboard_profile_names() {
+        let tmp = tempfile::tempdir().expect("tempdir");
+        let config_path = tmp.path().join("user-data").join("config.toml");
+        let data_dir = tmp.path().join("data");
+        std::fs::create_dir_all(config_path.parent().unwrap()).unwrap();
+        let state = crate::app_state::build_state(config_path.clone(), data_dir).expect("state");
+
+        let cli_home = tmp.path().join("work-codex");
+        std::fs::create_dir_all(&cli_home).unwrap();
+        std::fs::write(cli_auth_path(&cli_home), r#"{"tokens":{"t":"x"}}"#).unwrap();
+        std::fs::write(cli_cfg_path(&cli_home), "model = \"gpt-5.2\"\n").unwrap();
+        state
+            .gateway
+            .cfg
+            .write()
+            .switchboard_profiles
+            .insert("work".to_string(), cli_home.to_string_lossy().to_string());
+
+        let status =
+            get_status_for_gateway(&state.gateway, vec!["work".to_string()]).expect("status");
+        assert_eq!(
+            status["dirs"][0]["cli_home"],
+            json!(cli_home.to_string_lossy())
+        );
+    }
+
+    #[test]
+    fn mark_target_matches_flags_dirs_already_on_requested_provider() {
+        let mut status = json!({
+          "dirs": [
+            { "cli_home": "a", "mode": "provider", "model_provider": "provider_1" },
+            { "cli_home": "b", "mode": "gateway", "model_provider": null }
+          ]
+        });
+        mark_target_matches(&mut status, "provider", Some("provider_1"));
+        assert_eq!(status["dirs"][0]["matches_target"], json!(true));
+        assert_eq!(status["dirs"][1]["matches_target"], json!(false));
+        assert_eq!(status["target_matches"], json!(false));
+
+        mark_target_matches(&mut status, "provider", Some("provider_2"));
+        assert_eq!(status["dirs"][0]["matches_target"], json!(false));
+    }
+
     #[test]
     fn on_provider_renamed_updates_state_and_swapped_cli_config() {
         let tmp = tempfile::tempdir().expect("tempdir");
@@ -23,6 +23,12 @@ export type EventLogFocusRequest = {
 type EventLevel = 'info' | 'warning' | 'error'
 type DateAnchor = 'from' | 'to'
 export type EventLogEntry = Status['recent_events'][number]
+export type EventLogCursor = { unix_ms: number; id: string }
+export type EventLogPage = {
+  events: EventLogEntry[]
+  /** Pass back as `before` to load the next older page; null when there is none. */
+  next_cursor: EventLogCursor | null
+}
 export type EventLogDailyStat = {
   day: string
   day_start_unix_ms: number
@@ -293,12 +299,13 @@ export function EventLogPanel({ events, dailyStatsSeed = [], focusRequest, onFoc
     const reqId = ++querySeqRef.current
     eventEntriesInFlightRef.current = true
     try {
-      const rows = await invoke<EventLogEntry[]>('get_event_log_entries', {
+      const page = await invoke<EventLogPage>('get_event_log_entries', {
         fromUnixMs,
         toUnixMs,
         limit,
       })
       if (querySeqRef.current !== reqId) return
+      const rows = page?.events
       if (!Array.isArray(rows)) return
       setSourceEvents([...rows].sort((a, b) => b.unix_ms - a.unix_ms))
       mergeKnownYears(rows)
@@ -315,12 +322,13 @@ export function EventLogPanel({ events, dailyStatsSeed = [], focusRequest, onFoc
     const fromUnixMs = Math.max(0, focus.unixMs - windowMs)
     const toUnixMs = focus.unixMs + windowMs
     try {
-      const rows = await invoke<EventLogEntry[]>('get_event_log_entries', {
+      const page = await invoke<EventLogPage>('get_event_log_entries', {
         fromUnixMs,
         toUnixMs,
         limit: EVENT_LOG_FETCH_FILTERED_LIMIT,
       })
       if (focusHydrateSeqRef.current !== reqId) return
+      const rows = page?.events
       if (!Array.isArray(rows) || rows.length === 0) return
       mergeSourceEvents(rows)
       mergeKnownYears(rows)
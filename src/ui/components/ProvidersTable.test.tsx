@@ -34,7 +34,7 @@ function buildStatus(): Status {
     ],
     active_provider: null,
     active_reason: null,
-    active_provider_counts: {},
+    active_provider_details: {},
     quota: {
       packycode: {
         kind: 'budget_info',
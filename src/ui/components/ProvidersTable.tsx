@@ -101,7 +101,7 @@ export function ProvidersTable({
           const isClosed = h.status === 'closed'
           const cooldownActive = !isClosed && h.cooldown_until_unix_ms > Date.now()
           const retryDue = !isClosed && h.status === 'unhealthy' && !cooldownActive
-          const isActive = (status.active_provider_counts?.[p] ?? 0) > 0
+          const isActive = (status.active_provider_details?.[p]?.count ?? 0) > 0
           const healthLabel =
             isClosed
               ? 'closed'
@@ -2,6 +2,7 @@ import { describe, expect, it } from 'vitest'
 import {
   arrangeSessionRowsByMainParent,
   compareSessionRowsByOriginThenLastSeen,
+  isVscodeSessionRow,
   isWslSessionRow,
   sessionsTableRenderTraceSignature,
   summarizeSessionsTableRender,
@@ -45,6 +46,14 @@ describe('isWslSessionRow', () => {
   })
 })
 
+describe('isVscodeSessionRow', () => {
+  it('detects the vscode extension marker', () => {
+    expect(isVscodeSessionRow({ wt_session: 'vscode:4242' })).toBe(true)
+    expect(isVscodeSessionRow({ wt_session: 'pid:4242' })).toBe(false)
+    expect(isVscodeSessionRow({ wt_session: undefined })).toBe(false)
+  })
+})
+
 describe('compareSessionRowsByOriginThenLastSeen', () => {
   it('sorts windows rows before wsl2 rows', () => {
     const rows = [
@@ -70,6 +70,10 @@ export function isWslSessionRow(
   return host === normalizedWslHost
 }
 
+export function isVscodeSessionRow(s: Pick<SessionRow, 'wt_session'>): boolean {
+  return (s.wt_session ?? '').trim().toLowerCase().startsWith('vscode:')
+}
+
 export function compareSessionRowsByOriginThenLastSeen(
   left: Pick<SessionRow, 'id' | 'wt_session' | 'reported_base_url' | 'last_seen_unix_ms'>,
   right: Pick<SessionRow, 'id' | 'wt_session' | 'reported_base_url' | 'last_seen_unix_ms'>,
@@ -335,10 +339,13 @@ export function SessionsTable({
         : isWslSession(s)
       const originClass = wsl ? 'aoSessionsIdWsl2' : 'aoSessionsIdWindows'
       const sessionIdClass = isChildRow ? `${originClass} aoSessionsIdChild` : originClass
+      const vscode = !wsl && isVscodeSessionRow(s)
       const originBadgeClass = wsl
         ? 'aoSessionOriginBadge aoSessionOriginBadgeWsl'
-        : 'aoSessionOriginBadge aoSessionOriginBadgeWindows'
-      const originLabel = wsl ? 'WSL2' : 'WIN'
+        : vscode
+          ? 'aoSessionOriginBadge aoSessionOriginBadgeVscode'
+          : 'aoSessionOriginBadge aoSessionOriginBadgeWindows'
+      const originLabel = wsl ? 'WSL2' : vscode ? 'VSCODE' : 'WIN'
       const rowClass = [
         !isChildRow && isAgent
           ? (wsl ? 'aoSessionRowAgent aoSessionRowAgentWsl' : 'aoSessionRowAgent')
@@ -26,6 +26,7 @@ export type SpendHistoryRow = {
   tracked_producer_node_id?: string | null
   tracked_producer_node_name?: string | null
   tracked_source_nodes?: Array<{ node_id: string; node_name: string }>
+  spike?: boolean
 }
 
 const DEV_NOW = Date.now()
@@ -275,6 +276,7 @@ export function evolveDevStatus(current: Status | null): Status {
 export const devStatus: Status = {
   listen: { host: '127.0.0.1', port: 4000 },
   wsl_gateway_host: '172.26.144.1',
+  wsl_openai_base_url_export: 'export OPENAI_BASE_URL=http://172.26.144.1:4000/v1',
   preferred_provider: 'provider_1',
   manual_override: null,
   providers: {
@@ -657,8 +659,11 @@ export const devStatus: Status = {
     limit_5h_reset_at: String(DEV_NOW + 2 * 60 * 60 * 1000),
     limit_weekly_remaining: '13%',
     limit_weekly_reset_at: String(DEV_NOW + 3 * 24 * 60 * 60 * 1000),
+    limit_weekly_reset_at_unix_ms: DEV_NOW + 3 * 24 * 60 * 60 * 1000,
     code_review_remaining: '92%',
     code_review_reset_at: String(DEV_NOW + 24 * 60 * 60 * 1000),
+    code_review_reset_at_unix_ms: DEV_NOW + 24 * 60 * 60 * 1000,
+    code_review_resets_in_seconds: 24 * 60 * 60 + 90,
     unlimited: false,
   },
 }
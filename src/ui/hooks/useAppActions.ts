@@ -119,7 +119,7 @@ export function useAppActions(params: Params) {
         throw new Error('This session has no Codex session id yet. Send one request through the gateway first.')
       }
       if (provider) {
-        await invoke('set_session_preferred_provider', { sessionId: codexSessionId, provider })
+        await invoke('set_session_preferred_provider', { sessionId: codexSessionId, providers: [provider] })
       } else {
         await invoke('clear_session_preferred_provider', { sessionId: codexSessionId })
       }
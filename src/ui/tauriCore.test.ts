@@ -1,6 +1,11 @@
 import { describe, expect, it } from 'vitest'
 
-import { shouldSuppressInvokeError, shouldSuppressSlowInvokeSuccess } from './tauriCore'
+import {
+  CommandInvokeError,
+  normalizeInvokeError,
+  shouldSuppressInvokeError,
+  shouldSuppressSlowInvokeSuccess,
+} from './tauriCore'
 
 describe('shouldSuppressInvokeError', () => {
   it('suppresses missing optional WSL home probe failures', () => {
@@ -31,3 +36,17 @@ describe('shouldSuppressSlowInvokeSuccess', () => {
     expect(shouldSuppressSlowInvokeSuccess('get_usage_statistics')).toBe(false)
   })
 })
+
+describe('normalizeInvokeError', () => {
+  it('wraps structured command errors and keeps the message printable', () => {
+    const error = normalizeInvokeError({ code: 'upstream', message: 'http 502', status: 502 })
+    expect(error).toBeInstanceOf(CommandInvokeError)
+    expect((error as CommandInvokeError).code).toBe('upstream')
+    expect((error as CommandInvokeError).status).toBe(502)
+    expect(String(error)).toBe('http 502')
+  })
+
+  it('passes through plain string errors unchanged', () => {
+    expect(normalizeInvokeError('unknown provider: p1')).toBe('unknown provider: p1')
+  })
+})
@@ -149,6 +149,34 @@ export function shouldSuppressInvokeError(command: string, message: string): boo
   )
 }
 
+export type CommandErrorCode = 'unknown_provider' | 'validation' | 'upstream' | 'io' | 'internal'
+
+// Structured `{ code, message }` error returned by provider, pricing and quota commands.
+export class CommandInvokeError extends Error {
+  readonly code: CommandErrorCode | string
+  readonly status: number | null
+
+  constructor(code: string, message: string, status: number | null = null) {
+    super(message)
+    this.name = 'CommandInvokeError'
+    this.code = code
+    this.status = status
+  }
+
+  // Keep `String(e)` rendering the plain message, as it did for string errors.
+  override toString(): string {
+    return this.message
+  }
+}
+
+export function normalizeInvokeError(error: unknown): unknown {
+  if (!error || typeof error !== 'object' || error instanceof Error) return error
+  const record = error as Record<string, unknown>
+  if (typeof record.code !== 'string' || typeof record.message !== 'string') return error
+  const status = typeof record.status === 'number' ? record.status : null
+  return new CommandInvokeError(record.code, record.message, status)
+}
+
 export async function invoke<T>(
   cmd: string,
   args?: actual.InvokeArgs,
@@ -174,7 +202,8 @@ export async function invoke<T>(
       })
     }
     return result
-  } catch (error) {
+  } catch (rawError) {
+    const error = normalizeInvokeError(rawError)
     const elapsedMs = Math.round(
       (typeof performance !== 'undefined' ? performance.now() : Date.now()) - startedAt,
     )
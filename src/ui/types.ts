@@ -6,6 +6,14 @@ export type ProviderHealth = {
   last_ok_at_unix_ms: number
   last_fail_at_unix_ms: number
   last_error_event_id?: string | null
+  ewma_latency_ms?: number | null
+  recent_latencies_ms?: number[]
+  recent_error_rate?: number
+  uptime_24h_percent?: number | null
+  uptime_7d_percent?: number | null
+  served_models?: string[] | null
+  served_models_age_ms?: number | null
+  tier?: number
 }
 
 export type OfficialAccountProfileSummary = {
@@ -33,14 +41,21 @@ export type RemoteOfficialAccountProfile = {
 }
 
 export type Status = {
-  listen: { host: string; port: number }
+  listen: {
+    host: string
+    port: number
+    unix_socket_path?: string | null
+    bound_addrs?: string[]
+  }
   config_revision?: string
   wsl_gateway_host?: string
+  wsl_openai_base_url_export?: string
   local_network_online?: boolean
   local_network_source?: string
   local_network_last_error?: string | null
   preferred_provider: string
   manual_override: string | null
+  read_only?: boolean
   providers: Record<string, ProviderHealth>
   metrics: Record<string, { ok_requests: number; error_requests: number; total_tokens: number }>
   // Dashboard snapshot window (small/recent only), not full Event Log history.
@@ -53,6 +68,8 @@ export type Status = {
     message: string
     fields: Record<string, unknown> | null
   }>
+  discovery_fresh?: boolean
+  discovery_age_ms?: number | null
   client_sessions?: Array<{
     id: string
     wt_session?: string
@@ -60,19 +77,33 @@ export type Status = {
     agent_parent_session_id?: string | null
     reported_model_provider?: string | null
     reported_model?: string | null
+    reported_model_served?: boolean | null
     reported_base_url?: string | null
     last_seen_unix_ms: number
     active: boolean
     preferred_provider?: string | null
+    preferred_providers?: string[]
     current_provider?: string | null
     current_reason?: string | null
     verified?: boolean
     is_agent?: boolean
     is_review?: boolean
+    auth_token_label?: string | null
+    auth_token_preview?: string | null
+    peer_process?: string | null
   }>
   active_provider?: string | null
   active_reason?: string | null
-  active_provider_counts?: Record<string, number>
+  active_provider_details?: Record<
+    string,
+    { count: number; failover_count?: number; reasons: Record<string, number> }
+  >
+  quota_groups?: Array<{
+    group: string
+    providers: string[]
+    source_provider: string | null
+    quota: Status['quota'][string] | null
+  }>
   quota: Record<
     string,
     {
@@ -88,6 +119,8 @@ export type Status = {
       monthly_spent_usd: number | null
       monthly_budget_usd: number | null
       package_expires_at_unix_ms?: number | null
+      reset_at_unix_ms?: number | null
+      used_percent?: number | null
       last_error: string
       effective_usage_base?: string | null
       effective_usage_source?: string | null
@@ -116,8 +149,11 @@ export type Status = {
     limit_5h_reset_at?: string | null
     limit_weekly_remaining?: string | null
     limit_weekly_reset_at?: string | null
+    limit_weekly_reset_at_unix_ms?: number | null
     code_review_remaining?: string | null
     code_review_reset_at?: string | null
+    code_review_reset_at_unix_ms?: number | null
+    code_review_resets_in_seconds?: number | null
     unlimited?: boolean | null
     error?: string
   }
@@ -306,6 +342,7 @@ export type CodexSwapStatus = {
   dirs: Array<{
     cli_home: string
     state: string
+    backups?: Record<string, string | null>
   }>
 }
 
@@ -317,8 +354,12 @@ export type ProviderSwitchboardStatus = {
     cli_home: string
     mode: string
     model_provider?: string | null
+    model?: string | null
+    matches_target?: boolean
+    backups?: Record<string, string | null>
   }>
   provider_options?: string[]
+  target_matches?: boolean
 }
 
 export type LanRemoteUpdateDebugResponse = {
@@ -451,16 +492,19 @@ export type LanRemoteUpdateDebugResponse = {
 }
 
 export type Config = {
-  listen: { host: string; port: number }
+  listen: { host: string; port: number; unix_socket_path?: string | null }
   routing: {
     preferred_provider: string
-    session_preferred_providers?: Record<string, string>
+    session_preferred_providers?: Record<string, string | string[]>
     route_mode?: 'follow_preferred_auto' | 'balanced_auto'
     auto_return_to_preferred: boolean
     preferred_stable_seconds: number
     failure_threshold: number
     cooldown_seconds: number
     request_timeout_seconds: number
+    connect_timeout_seconds?: number | null
+    read_timeout_seconds?: number | null
+    otel_endpoint?: string | null
   }
   providers: Record<
     string,
@@ -468,7 +512,10 @@ export type Config = {
       display_name: string
       base_url: string
       group?: string | null
+      routing_eligible?: boolean
+      tier?: number
       supports_websockets?: boolean
+      notes?: string
       usage_adapter?: string
       usage_presentation?: 'standard' | 'total_only'
       usage_base_url?: string | null
@@ -478,11 +525,21 @@ export type Config = {
         monthly: boolean
       }
       disabled?: boolean
-      manual_pricing_mode?: 'per_request' | 'package_total' | null
+      manual_pricing_mode?: 'per_request' | 'per_request_tiered' | 'package_total' | null
       manual_pricing_amount_usd?: number | null
       manual_pricing_expires_at_unix_ms?: number | null
+      manual_pricing_tiers?: Array<{ up_to_requests: number | null; usd_per_request: number }> | null
       manual_gap_fill_mode?: 'per_request' | 'total' | 'per_day_average' | null
       manual_gap_fill_amount_usd?: number | null
+      model_pricing?: Record<
+        string,
+        {
+          input_usd_per_million: number
+          output_usd_per_million: number
+          cache_creation_usd_per_million?: number
+          cache_read_usd_per_million?: number
+        }
+      >
       account_email?: string | null
       has_key: boolean
       key_preview?: string | null
@@ -575,6 +632,22 @@ export type UsageModelStatisticsRow = {
   estimated_total_cost_usd: number
   estimated_avg_request_cost_usd: number
   estimated_cost_request_count: number
+  cache_creation_cost_usd?: number
+  cache_read_cost_usd?: number
+}
+
+export type UsageProviderModelStatisticsRow = {
+  provider: string
+  model: string
+  requests: number
+  input_tokens: number
+  output_tokens: number
+  total_tokens: number
+  estimated_total_cost_usd: number
+  estimated_avg_request_cost_usd: number
+  estimated_cost_request_count: number
+  cache_creation_cost_usd?: number
+  cache_read_cost_usd?: number
 }
 
 export type UsageProviderStatisticsRow = {
@@ -590,11 +663,15 @@ export type UsageProviderStatisticsRow = {
   total_used_cost_usd?: number | null
   pricing_source?: string | null
   estimated_cost_request_count: number
+  burn_rate_usd_per_day?: number | null
+  projected_exhaustion_unix_ms?: number | null
 }
 
 export type UsageTimelinePoint = {
   bucket_unix_ms: number
   requests: number
+  errors?: number
+  error_rate?: number | null
   total_tokens: number
   cache_creation_tokens?: number
   cache_read_tokens?: number
@@ -607,12 +684,17 @@ export type UsageStatisticsOverview = {
   bucket_seconds: number
   summary: {
     total_requests: number
+    total_errors?: number
     total_tokens: number
     input_tokens: number
     output_tokens: number
     active_window_hours?: number
     cache_creation_tokens?: number
     cache_read_tokens?: number
+    request_bytes?: number
+    response_bytes?: number
+    avg_request_bytes?: number | null
+    avg_response_bytes?: number | null
     unique_models: number
     top_model?: {
       model: string
@@ -621,6 +703,8 @@ export type UsageStatisticsOverview = {
     } | null
     estimated_total_cost_usd: number
     estimated_daily_cost_usd?: number
+    cache_creation_cost_usd?: number
+    cache_read_cost_usd?: number
     by_provider: UsageProviderStatisticsRow[]
     timeline: UsageTimelinePoint[]
   }
@@ -641,5 +725,6 @@ export type UsageStatistics = UsageStatisticsOverview & {
   }
   summary: UsageStatisticsOverview['summary'] & {
     by_model: UsageModelStatisticsRow[]
+    by_provider_model?: UsageProviderModelStatisticsRow[]
   }
 }